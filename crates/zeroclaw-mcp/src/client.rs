@@ -2,11 +2,15 @@
 
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Instant;
 
 use anyhow::{Context, Result};
-use tracing::{debug, warn};
+use tracing::{debug, debug_span, warn, Instrument};
 
-use crate::jsonrpc::{JsonRpcNotification, JsonRpcRequest, RequestId};
+use crate::error::McpError;
+use crate::jsonrpc::{
+    JsonRpcError, JsonRpcMessage, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse, RequestId,
+};
 use crate::transport::McpTransport;
 use crate::types::{
     ClientCapabilities, Implementation, InitializeParams, InitializeResult, McpPrompt,
@@ -20,41 +24,103 @@ const MAX_PAGES: usize = 100;
 pub struct McpClient {
     transport: Box<dyn McpTransport>,
     next_id: AtomicI64,
+    config_name: Option<String>,
+    server_name: String,
+    server_version: String,
+    protocol_version: String,
     server_capabilities: Option<ServerCapabilities>,
+    state: SessionState,
+}
+
+/// Where the client is in the session lifecycle; `initialize` is only valid
+/// from `Uninitialized`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SessionState {
+    Uninitialized,
+    Initialized,
+    Closed,
+}
+
+/// `clientInfo` announced by [`McpClient::connect`].
+pub fn default_client_info() -> Implementation {
+    Implementation {
+        name: "zeroclaw".to_string(),
+        version: "0.1.0".to_string(),
+    }
 }
 
 impl McpClient {
-    pub async fn connect(mut transport: Box<dyn McpTransport>) -> Result<Self> {
+    pub async fn connect(transport: Box<dyn McpTransport>) -> Result<Self> {
+        Self::connect_with_client_info(transport, default_client_info()).await
+    }
+
+    /// Connect and announce `client_info` as the `initialize` request's
+    /// `clientInfo`, for servers that key behavior off the client identity.
+    pub async fn connect_with_client_info(
+        transport: Box<dyn McpTransport>,
+        client_info: Implementation,
+    ) -> Result<Self> {
+        let mut client = Self {
+            transport,
+            next_id: AtomicI64::new(1),
+            config_name: None,
+            server_name: String::new(),
+            server_version: String::new(),
+            protocol_version: String::new(),
+            server_capabilities: None,
+            state: SessionState::Uninitialized,
+        };
+        client.initialize(client_info).await?;
+        Ok(client)
+    }
+
+    /// Run the `initialize` handshake
+    ///
+    /// A session can only be initialized once; calling this on an
+    /// established or closed client fails locally instead of sending a
+    /// second `initialize` the server would reject.
+    pub async fn initialize(&mut self, client_info: Implementation) -> Result<()> {
+        match self.state {
+            SessionState::Uninitialized => {}
+            SessionState::Initialized => anyhow::bail!(
+                "MCP session with '{}' is already initialized; connect a new transport to start another session",
+                self.server_name
+            ),
+            SessionState::Closed => anyhow::bail!(
+                "MCP session with '{}' is closed; connect a new transport to start another session",
+                self.server_name
+            ),
+        }
+
         debug!("Starting MCP client handshake");
 
         let init_params = InitializeParams {
             protocol_version: "2024-11-05".to_string(),
             capabilities: ClientCapabilities::default(),
-            client_info: Implementation {
-                name: "zeroclaw".to_string(),
-                version: "0.1.0".to_string(),
-            },
+            client_info,
         };
 
         let init_request = JsonRpcRequest {
             jsonrpc: "2.0".to_string(),
-            id: RequestId::Number(1),
+            id: RequestId::Number(self.next_request_id()),
             method: "initialize".to_string(),
             params: Some(serde_json::to_value(&init_params)?),
         };
 
+        let transport = self.transport.as_mut();
         transport
             .send(&init_request)
             .await
-            .context("Failed to send initialize request")?;
+            .context("Failed to send initialize request")
+            .map_err(McpError::Transport)?;
 
-        let init_response = transport
-            .receive()
+        let init_response = receive_response(transport, &init_request.id)
             .await
-            .context("Failed to receive initialize response")?;
+            .context("Failed to receive initialize response")
+            .map_err(McpError::Transport)?;
 
         if let Some(error) = init_response.error {
-            anyhow::bail!("Initialize failed: {}", error.message);
+            return Err(McpError::from_rpc("initialize", error).into());
         }
 
         let result_value = init_response
@@ -77,21 +143,113 @@ impl McpClient {
         transport
             .send_notification(&initialized_notification)
             .await
-            .context("Failed to send initialized notification")?;
+            .context("Failed to send initialized notification")
+            .map_err(McpError::Transport)?;
 
         debug!("MCP client handshake complete");
 
-        Ok(Self {
-            transport,
-            next_id: AtomicI64::new(2),
-            server_capabilities: Some(init_result.capabilities),
-        })
+        self.server_name = init_result.server_info.name;
+        self.server_version = init_result.server_info.version;
+        self.protocol_version = init_result.protocol_version;
+        self.server_capabilities = Some(init_result.capabilities);
+        self.state = SessionState::Initialized;
+        Ok(())
+    }
+
+    /// Whether the `initialize` handshake has completed and the session has
+    /// not been closed.
+    pub fn is_initialized(&self) -> bool {
+        self.state == SessionState::Initialized
     }
 
     fn next_request_id(&self) -> i64 {
         self.next_id.fetch_add(1, Ordering::SeqCst)
     }
 
+    /// Label request spans with the name this server is configured under
+    ///
+    /// Without it spans fall back to the name the server reported in
+    /// `serverInfo`, which need not be unique or match the config.
+    pub fn with_config_name(mut self, name: impl Into<String>) -> Self {
+        self.config_name = Some(name.into());
+        self
+    }
+
+    /// Send `request` and wait for its response inside an `mcp.request` span
+    /// carrying the server name, method, request id and elapsed milliseconds.
+    async fn round_trip(
+        &mut self,
+        request: &JsonRpcRequest,
+        send_context: &'static str,
+        receive_context: &'static str,
+    ) -> Result<JsonRpcResponse> {
+        let span = debug_span!(
+            "mcp.request",
+            server = %self.config_name.as_deref().unwrap_or(&self.server_name),
+            method = %request.method,
+            request_id = %request.id,
+            duration_ms = tracing::field::Empty,
+        );
+        let started = Instant::now();
+        let transport = &mut self.transport;
+        let result = async move {
+            transport.send(request).await.context(send_context)?;
+            receive_response(transport.as_mut(), &request.id)
+                .await
+                .context(receive_context)
+        }
+        .instrument(span.clone())
+        .await
+        .map_err(|e| McpError::Transport(e).into());
+
+        let elapsed_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        span.record("duration_ms", elapsed_ms);
+        span.in_scope(|| debug!(ok = result.is_ok(), "MCP request finished"));
+        result
+    }
+
+    /// Name the server reported during the initialize handshake.
+    pub fn server_name(&self) -> &str {
+        &self.server_name
+    }
+
+    /// Version the server reported during the initialize handshake.
+    pub fn server_version(&self) -> &str {
+        &self.server_version
+    }
+
+    /// Protocol version the server agreed to during the initialize handshake.
+    pub fn protocol_version(&self) -> &str {
+        &self.protocol_version
+    }
+
+    /// Send a `ping` and wait for the server's empty result
+    ///
+    /// Cheap liveness check for health probes and keep-alive on long-lived
+    /// connections.
+    pub async fn ping(&mut self) -> Result<()> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(self.next_request_id()),
+            method: "ping".to_string(),
+            params: None,
+        };
+
+        let response = self
+            .round_trip(
+                &request,
+                "Failed to send ping request",
+                "Failed to receive ping response",
+            )
+            .await?;
+
+        if let Some(error) = response.error {
+            return Err(McpError::from_rpc("ping", error).into());
+        }
+        response.result.context("ping response missing result")?;
+        Ok(())
+    }
+
     pub async fn list_tools(&mut self) -> Result<Vec<McpToolInfo>> {
         debug!("Requesting tools list");
 
@@ -108,19 +266,16 @@ impl McpClient {
                 params,
             };
 
-            self.transport
-                .send(&request)
-                .await
-                .context("Failed to send tools/list request")?;
-
             let response = self
-                .transport
-                .receive()
-                .await
-                .context("Failed to receive tools/list response")?;
+                .round_trip(
+                    &request,
+                    "Failed to send tools/list request",
+                    "Failed to receive tools/list response",
+                )
+                .await?;
 
             if let Some(error) = response.error {
-                anyhow::bail!("tools/list failed: {}", error.message);
+                return Err(McpError::from_rpc("tools/list", error).into());
             }
 
             let result_value = response
@@ -176,18 +331,12 @@ impl McpClient {
             params: Some(serde_json::to_value(&params)?),
         };
 
-        self.transport.send(&request).await.context(
-            "Failed to send request to MCP server — the server may have crashed or disconnected",
-        )?;
-
         let response = self
-            .transport
-            .receive()
-            .await
-            .context("Failed to receive response from MCP server — the server may have crashed or disconnected")?;
+            .round_trip(&request, "Failed to send request to MCP server — the server may have crashed or disconnected", "Failed to receive response from MCP server — the server may have crashed or disconnected")
+            .await?;
 
         if let Some(error) = response.error {
-            anyhow::bail!("tools/call failed: {}", error.message);
+            return Err(McpError::from_rpc("tools/call", error).into());
         }
 
         let result_value = response
@@ -197,14 +346,8 @@ impl McpClient {
         let result: McpToolCallResult =
             serde_json::from_value(result_value).context("Failed to parse McpToolCallResult")?;
 
-        if result.is_error == Some(true) {
-            let error_msg = result
-                .content
-                .first()
-                .and_then(|c| c.text.as_ref())
-                .map(|t| t.as_str())
-                .unwrap_or("Unknown tool error");
-            anyhow::bail!("Tool execution error: {}", error_msg);
+        if result.is_error() {
+            return Err(McpError::ToolError(result).into());
         }
 
         Ok(result)
@@ -223,17 +366,15 @@ impl McpClient {
                 method: "resources/list".to_string(),
                 params,
             };
-            self.transport
-                .send(&request)
-                .await
-                .context("Failed to send resources/list request")?;
             let response = self
-                .transport
-                .receive()
-                .await
-                .context("Failed to receive resources/list response")?;
+                .round_trip(
+                    &request,
+                    "Failed to send resources/list request",
+                    "Failed to receive resources/list response",
+                )
+                .await?;
             if let Some(error) = response.error {
-                anyhow::bail!("resources/list failed: {}", error.message);
+                return Err(McpError::from_rpc("resources/list", error).into());
             }
             let result_value = response
                 .result
@@ -277,19 +418,16 @@ impl McpClient {
             params: Some(params),
         };
 
-        self.transport
-            .send(&request)
-            .await
-            .context("Failed to send resources/read request")?;
-
         let response = self
-            .transport
-            .receive()
-            .await
-            .context("Failed to receive resources/read response")?;
+            .round_trip(
+                &request,
+                "Failed to send resources/read request",
+                "Failed to receive resources/read response",
+            )
+            .await?;
 
         if let Some(error) = response.error {
-            anyhow::bail!("resources/read failed: {}", error.message);
+            return Err(McpError::from_rpc("resources/read", error).into());
         }
 
         let result_value = response
@@ -320,17 +458,15 @@ impl McpClient {
                 method: "prompts/list".to_string(),
                 params,
             };
-            self.transport
-                .send(&request)
-                .await
-                .context("Failed to send prompts/list request")?;
             let response = self
-                .transport
-                .receive()
-                .await
-                .context("Failed to receive prompts/list response")?;
+                .round_trip(
+                    &request,
+                    "Failed to send prompts/list request",
+                    "Failed to receive prompts/list response",
+                )
+                .await?;
             if let Some(error) = response.error {
-                anyhow::bail!("prompts/list failed: {}", error.message);
+                return Err(McpError::from_rpc("prompts/list", error).into());
             }
             let result_value = response
                 .result
@@ -379,19 +515,16 @@ impl McpClient {
             params: Some(params),
         };
 
-        self.transport
-            .send(&request)
-            .await
-            .context("Failed to send prompts/get request")?;
-
         let response = self
-            .transport
-            .receive()
-            .await
-            .context("Failed to receive prompts/get response")?;
+            .round_trip(
+                &request,
+                "Failed to send prompts/get request",
+                "Failed to receive prompts/get response",
+            )
+            .await?;
 
         if let Some(error) = response.error {
-            anyhow::bail!("prompts/get failed: {}", error.message);
+            return Err(McpError::from_rpc("prompts/get", error).into());
         }
 
         let result_value = response
@@ -410,8 +543,12 @@ impl McpClient {
     }
 
     /// Close the MCP connection
+    ///
+    /// Preferred over dropping the client: stdio servers get a chance to exit
+    /// on their own, while a dropped client's server process is killed.
     pub async fn close(&mut self) -> Result<()> {
         debug!("Closing MCP client connection");
+        self.state = SessionState::Closed;
         self.transport.close().await
     }
 
@@ -420,6 +557,57 @@ impl McpClient {
     }
 }
 
+/// Wait for the response to request `id`, answering any requests the server
+/// sends in the meantime: `ping` gets an empty result, anything else "method
+/// not found". Responses to other ids (e.g. a late answer to a request that
+/// timed out) are discarded.
+async fn receive_response(
+    transport: &mut dyn McpTransport,
+    id: &RequestId,
+) -> Result<JsonRpcResponse> {
+    loop {
+        let request = match transport.receive_message().await? {
+            JsonRpcMessage::Response(response) if response.id == *id => return Ok(response),
+            JsonRpcMessage::Response(response) => {
+                debug!(
+                    "Discarding MCP response for request {} while awaiting {}",
+                    response.id, id
+                );
+                continue;
+            }
+            JsonRpcMessage::Request(request) => request,
+            JsonRpcMessage::Notification(notification) => {
+                debug!("Ignoring MCP server notification: {}", notification.method);
+                continue;
+            }
+        };
+        let (result, error) = if request.method == "ping" {
+            debug!("Answering ping from MCP server");
+            (Some(serde_json::json!({})), None)
+        } else {
+            warn!(
+                "Rejecting unsupported MCP server request: {}",
+                request.method
+            );
+            let error = JsonRpcError {
+                code: -32601,
+                message: format!("Method not found: {}", request.method),
+                data: None,
+            };
+            (None, Some(error))
+        };
+        transport
+            .send_response(&JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: request.id,
+                result,
+                error,
+            })
+            .await
+            .context("Failed to answer server request")?;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::VecDeque;
@@ -431,22 +619,37 @@ mod tests {
     use crate::jsonrpc::JsonRpcResponse;
 
     struct MockTransport {
-        responses: VecDeque<JsonRpcResponse>,
-        sent_requests: std::sync::Mutex<Vec<JsonRpcRequest>>,
+        responses: VecDeque<JsonRpcMessage>,
+        sent_requests: std::sync::Arc<std::sync::Mutex<Vec<JsonRpcRequest>>>,
         sent_notifications: std::sync::Mutex<Vec<JsonRpcNotification>>,
+        sent_responses: std::sync::Arc<std::sync::Mutex<Vec<JsonRpcResponse>>>,
     }
 
     impl MockTransport {
         fn new() -> Self {
             Self {
                 responses: VecDeque::new(),
-                sent_requests: std::sync::Mutex::new(Vec::new()),
+                sent_requests: std::sync::Arc::default(),
                 sent_notifications: std::sync::Mutex::new(Vec::new()),
+                sent_responses: std::sync::Arc::default(),
             }
         }
 
         fn queue_response(&mut self, response: JsonRpcResponse) {
-            self.responses.push_back(response);
+            self.responses.push_back(JsonRpcMessage::Response(response));
+        }
+
+        fn queue_server_request(&mut self, request: JsonRpcRequest) {
+            self.responses.push_back(JsonRpcMessage::Request(request));
+        }
+
+        fn queue_server_notification(&mut self, method: &str) {
+            self.responses
+                .push_back(JsonRpcMessage::Notification(JsonRpcNotification {
+                    jsonrpc: "2.0".to_string(),
+                    method: method.to_string(),
+                    params: None,
+                }));
         }
     }
 
@@ -466,11 +669,28 @@ mod tests {
         }
 
         async fn receive(&mut self) -> Result<JsonRpcResponse> {
+            match self.receive_message().await? {
+                JsonRpcMessage::Response(response) => Ok(response),
+                JsonRpcMessage::Request(request) => {
+                    anyhow::bail!("Unexpected server request: {}", request.method)
+                }
+                JsonRpcMessage::Notification(notification) => {
+                    anyhow::bail!("Unexpected server notification: {}", notification.method)
+                }
+            }
+        }
+
+        async fn receive_message(&mut self) -> Result<JsonRpcMessage> {
             self.responses
                 .pop_front()
                 .ok_or_else(|| anyhow::anyhow!("No more queued responses"))
         }
 
+        async fn send_response(&mut self, response: &JsonRpcResponse) -> Result<()> {
+            self.sent_responses.lock().unwrap().push(response.clone());
+            Ok(())
+        }
+
         async fn close(&mut self) -> Result<()> {
             Ok(())
         }
@@ -497,6 +717,69 @@ mod tests {
         assert!(client.server_capabilities.as_ref().unwrap().tools.is_some());
     }
 
+    #[tokio::test]
+    async fn test_connect_sends_custom_client_info() {
+        let mut mock = MockTransport::new();
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(1),
+            result: Some(json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "serverInfo": {"name": "TestServer", "version": "1.0.0"}
+            })),
+            error: None,
+        });
+        let sent = std::sync::Arc::clone(&mock.sent_requests);
+
+        McpClient::connect_with_client_info(
+            Box::new(mock),
+            Implementation {
+                name: "my-agent".to_string(),
+                version: "2.3.4".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent[0].method, "initialize");
+        assert_eq!(
+            sent[0].params.as_ref().unwrap()["clientInfo"],
+            json!({"name": "my-agent", "version": "2.3.4"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_second_initialize_is_rejected_locally() {
+        let mut mock = MockTransport::new();
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(1),
+            result: Some(json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "serverInfo": {"name": "TestServer", "version": "1.0.0"}
+            })),
+            error: None,
+        });
+        let sent = std::sync::Arc::clone(&mock.sent_requests);
+
+        let mut client = McpClient::connect(Box::new(mock)).await.unwrap();
+        assert!(client.is_initialized());
+
+        let err = client.initialize(default_client_info()).await.unwrap_err();
+        assert!(err.to_string().contains("already initialized"), "{err}");
+        assert_eq!(sent.lock().unwrap().len(), 1);
+        assert!(client.is_initialized());
+
+        client.close().await.unwrap();
+        assert!(!client.is_initialized());
+        let err = client.initialize(default_client_info()).await.unwrap_err();
+        assert!(err.to_string().contains("is closed"), "{err}");
+        assert_eq!(sent.lock().unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_list_tools() {
         let mut mock = MockTransport::new();
@@ -783,6 +1066,224 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_ping() {
+        let mut mock = MockTransport::new();
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(1),
+            result: Some(json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "serverInfo": {"name": "TestServer", "version": "1.0.0"}
+            })),
+            error: None,
+        });
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(2),
+            result: Some(json!({})),
+            error: None,
+        });
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(3),
+            result: None,
+            error: Some(JsonRpcError {
+                code: -32603,
+                message: "shutting down".to_string(),
+                data: None,
+            }),
+        });
+        let sent = std::sync::Arc::clone(&mock.sent_requests);
+
+        let mut client = McpClient::connect(Box::new(mock)).await.unwrap();
+        client.ping().await.unwrap();
+
+        let ping = sent.lock().unwrap().last().cloned().unwrap();
+        assert_eq!(ping.method, "ping");
+        assert_eq!(ping.id, RequestId::Number(2));
+        assert!(ping.params.is_none());
+
+        let error = client.ping().await.unwrap_err();
+        assert!(error.to_string().contains("shutting down"));
+    }
+
+    #[tokio::test]
+    async fn test_answers_server_ping_while_waiting() {
+        let mut mock = MockTransport::new();
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(1),
+            result: Some(json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {"tools": {}},
+                "serverInfo": {"name": "TestServer", "version": "1.0.0"}
+            })),
+            error: None,
+        });
+        mock.queue_server_request(JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::String("srv-1".to_string()),
+            method: "ping".to_string(),
+            params: None,
+        });
+        mock.queue_server_request(JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(99),
+            method: "sampling/createMessage".to_string(),
+            params: Some(json!({})),
+        });
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(2),
+            result: Some(json!({"tools": []})),
+            error: None,
+        });
+        let answered = std::sync::Arc::clone(&mock.sent_responses);
+
+        let mut client = McpClient::connect(Box::new(mock)).await.unwrap();
+        assert!(client.list_tools().await.unwrap().is_empty());
+
+        let answered = answered.lock().unwrap();
+        assert_eq!(answered.len(), 2);
+        assert_eq!(answered[0].id, RequestId::String("srv-1".to_string()));
+        assert_eq!(answered[0].result, Some(json!({})));
+        assert!(answered[0].error.is_none());
+        assert_eq!(answered[1].id, RequestId::Number(99));
+        assert_eq!(answered[1].error.as_ref().unwrap().code, -32601);
+    }
+
+    #[tokio::test]
+    async fn test_skips_server_notifications_while_waiting() {
+        let mut mock = MockTransport::new();
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(1),
+            result: Some(json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {"tools": {}},
+                "serverInfo": {"name": "TestServer", "version": "1.0.0"}
+            })),
+            error: None,
+        });
+        mock.queue_server_notification("notifications/tools/list_changed");
+        mock.queue_server_notification("notifications/message");
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(2),
+            result: Some(json!({"tools": []})),
+            error: None,
+        });
+        let answered = std::sync::Arc::clone(&mock.sent_responses);
+
+        let mut client = McpClient::connect(Box::new(mock)).await.unwrap();
+        assert!(client.list_tools().await.unwrap().is_empty());
+        assert!(answered.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_discards_stale_responses_while_waiting() {
+        let mut mock = MockTransport::new();
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(1),
+            result: Some(json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {"tools": {}},
+                "serverInfo": {"name": "TestServer", "version": "1.0.0"}
+            })),
+            error: None,
+        });
+        // Late answer to an earlier request that the caller gave up on
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(1),
+            result: Some(json!({"content": [{"type": "text", "text": "stale"}]})),
+            error: None,
+        });
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(2),
+            result: Some(json!({"tools": [{"name": "fresh", "inputSchema": {}}]})),
+            error: None,
+        });
+
+        let mut client = McpClient::connect(Box::new(mock)).await.unwrap();
+        let tools = client.list_tools().await.unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name, "fresh");
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_classified() {
+        let mut mock = MockTransport::new();
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(1),
+            result: Some(json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {"tools": {}},
+                "serverInfo": {"name": "TestServer", "version": "1.0.0"}
+            })),
+            error: None,
+        });
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(2),
+            result: None,
+            error: Some(JsonRpcError {
+                code: -32603,
+                message: "internal".to_string(),
+                data: None,
+            }),
+        });
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(3),
+            result: Some(json!({
+                "content": [{"type": "text", "text": "disk full"}],
+                "isError": true
+            })),
+            error: None,
+        });
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(4),
+            result: None,
+            error: Some(JsonRpcError {
+                code: -32601,
+                message: "Method not found".to_string(),
+                data: None,
+            }),
+        });
+
+        let mut client = McpClient::connect(Box::new(mock)).await.unwrap();
+
+        let error = client.call_tool("write", None).await.unwrap_err();
+        assert!(matches!(
+            McpError::of(&error),
+            Some(McpError::Protocol { method, error }) if method == "tools/call" && error.code == -32603
+        ));
+
+        let error = client.call_tool("write", None).await.unwrap_err();
+        assert!(matches!(McpError::of(&error), Some(McpError::ToolError(_))));
+        assert_eq!(error.to_string(), "Tool execution error: disk full");
+
+        let error = client.list_resources().await.unwrap_err();
+        assert!(matches!(
+            McpError::of(&error),
+            Some(McpError::Capability { method, .. }) if method == "resources/list"
+        ));
+
+        // Nothing left to receive: the mock behaves like a closed pipe.
+        let error = client.list_tools().await.unwrap_err();
+        let classified = McpError::of(&error).unwrap();
+        assert!(matches!(classified, McpError::Transport(_)));
+        assert!(classified.needs_reconnect());
+        assert_eq!(error.to_string(), "Failed to receive tools/list response");
+    }
+
     #[tokio::test]
     async fn test_list_tools_pagination() {
         let mut mock = MockTransport::new();
@@ -890,4 +1391,89 @@ mod tests {
             format!("tool_{}", super::MAX_PAGES - 1)
         );
     }
+
+    /// Records the fields of every `mcp.request` span, including values
+    /// recorded after the span was created.
+    #[derive(Clone, Default)]
+    struct SpanCapture {
+        spans: std::sync::Arc<std::sync::Mutex<HashMap<u64, HashMap<String, String>>>>,
+    }
+
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl tracing::field::Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for SpanCapture {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            id: &tracing::span::Id,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            if attrs.metadata().name() != "mcp.request" {
+                return;
+            }
+            let mut fields = HashMap::new();
+            attrs.record(&mut FieldVisitor(&mut fields));
+            self.spans.lock().unwrap().insert(id.into_u64(), fields);
+        }
+
+        fn on_record(
+            &self,
+            id: &tracing::span::Id,
+            values: &tracing::span::Record<'_>,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            if let Some(fields) = self.spans.lock().unwrap().get_mut(&id.into_u64()) {
+                values.record(&mut FieldVisitor(fields));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_requests_emit_spans_with_fields() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let capture = SpanCapture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mut mock = MockTransport::new();
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(1),
+            result: Some(json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {"tools": {}},
+                "serverInfo": {"name": "TestServer", "version": "1.0.0"}
+            })),
+            error: None,
+        });
+        mock.queue_response(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(2),
+            result: Some(json!({"content": [{"type": "text", "text": "ok"}]})),
+            error: None,
+        });
+
+        let mut client = McpClient::connect(Box::new(mock))
+            .await
+            .unwrap()
+            .with_config_name("github");
+        assert_eq!(client.server_name(), "TestServer");
+        client.call_tool("echo", None).await.unwrap();
+
+        let spans = capture.spans.lock().unwrap();
+        assert_eq!(spans.len(), 1);
+        let fields = spans.values().next().unwrap();
+        assert_eq!(fields["server"], "github");
+        assert_eq!(fields["method"], "tools/call");
+        assert_eq!(fields["request_id"], "2");
+        assert!(fields["duration_ms"].parse::<u64>().is_ok());
+    }
 }
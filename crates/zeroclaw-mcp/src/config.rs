@@ -1,10 +1,11 @@
 //! MCP server configuration parsing
 //!
-//! Parses `.mcp.json` config files for MCP server definitions.
+//! Parses `.mcp.json` config files for MCP server definitions, and imports
+//! servers from editor-style registry files (`mcp.json` / `mcp.toml`).
 
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 /// MCP server configuration entry
@@ -16,6 +17,24 @@ pub struct McpServerConfig {
     pub args: Vec<String>,
     #[serde(default)]
     pub env: HashMap<String, String>,
+    /// Per-server cap on tool result text, overriding the registry default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_chars: Option<usize>,
+    /// Inherit the parent process environment. When false the server starts
+    /// with only the `env_allowlist` variables plus `env`.
+    #[serde(default = "default_inherit_env")]
+    pub inherit_env: bool,
+    /// Parent environment variables passed through when `inherit_env` is false
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env_allowlist: Vec<String>,
+}
+
+fn default_inherit_env() -> bool {
+    true
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
 }
 
 /// Internal structure for deserializing .mcp.json
@@ -33,6 +52,24 @@ struct McpServerEntry {
     args: Vec<String>,
     #[serde(default)]
     env: HashMap<String, String>,
+    #[serde(
+        rename = "maxOutputChars",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    max_output_chars: Option<usize>,
+    #[serde(
+        rename = "inheritEnv",
+        default = "default_inherit_env",
+        skip_serializing_if = "is_true"
+    )]
+    inherit_env: bool,
+    #[serde(
+        rename = "envAllowlist",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    env_allowlist: Vec<String>,
 }
 
 /// Parse .mcp.json file at given path.
@@ -54,12 +91,120 @@ pub fn parse_mcp_config(path: &Path) -> anyhow::Result<Vec<McpServerConfig>> {
             command: entry.command,
             args: entry.args,
             env: entry.env,
+            max_output_chars: entry.max_output_chars,
+            inherit_env: entry.inherit_env,
+            env_allowlist: entry.env_allowlist,
         });
     }
 
     Ok(configs)
 }
 
+/// Registry file in the community format shared by editors and desktop
+/// clients: `{"mcpServers": {name: {command, args, env}}}`. TOML registries
+/// use the same shape (`[mcpServers.<name>]`); the `mcp_servers` and
+/// `servers` spellings used by some clients are accepted too.
+#[derive(Debug, Deserialize)]
+struct McpRegistryFile {
+    #[serde(
+        rename = "mcpServers",
+        alias = "mcp_servers",
+        alias = "servers",
+        default
+    )]
+    mcp_servers: BTreeMap<String, McpRegistryEntry>,
+}
+
+/// Registry server entry. Fields other clients add (`type`, `url`,
+/// `headers`, ...) are ignored.
+#[derive(Debug, Deserialize)]
+struct McpRegistryEntry {
+    #[serde(default)]
+    command: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    env: HashMap<String, String>,
+    #[serde(default)]
+    disabled: bool,
+}
+
+/// Parse an `mcp.json` / `mcp.toml` registry file into server configs,
+/// sorted by name. Files ending in `.toml` are read as TOML, anything else
+/// as JSON. Disabled entries and entries without a `command` (remote
+/// servers, which need a transport this crate doesn't have) are skipped.
+pub fn parse_mcp_registry(path: &Path) -> anyhow::Result<Vec<McpServerConfig>> {
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+
+    let is_toml = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+    let registry: McpRegistryFile = if is_toml {
+        toml::from_str(&content).with_context(|| format!("Failed to parse {:?} as TOML", path))?
+    } else {
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {:?} as JSON", path))?
+    };
+
+    let mut configs = Vec::with_capacity(registry.mcp_servers.len());
+    for (name, entry) in registry.mcp_servers {
+        if entry.disabled {
+            tracing::debug!(
+                "MCP registry {:?}: skipping disabled server '{}'",
+                path,
+                name
+            );
+            continue;
+        }
+        let Some(command) = entry.command else {
+            tracing::warn!(
+                "MCP registry {:?}: skipping '{}' (no command; only stdio servers are supported)",
+                path,
+                name
+            );
+            continue;
+        };
+        configs.push(McpServerConfig {
+            name,
+            command,
+            args: entry.args,
+            env: entry.env,
+            max_output_chars: None,
+            inherit_env: default_inherit_env(),
+            env_allowlist: Vec::new(),
+        });
+    }
+
+    Ok(configs)
+}
+
+/// Append servers from registry files to `configs`. Names already present
+/// (from `.mcp.json` or an earlier registry) are kept; unreadable registries
+/// are logged and skipped.
+pub fn import_mcp_registries(configs: &mut Vec<McpServerConfig>, paths: &[std::path::PathBuf]) {
+    for path in paths {
+        let imported = match parse_mcp_registry(path) {
+            Ok(imported) => imported,
+            Err(e) => {
+                tracing::warn!("Failed to import MCP registry {:?}: {:#}", path, e);
+                continue;
+            }
+        };
+        for config in imported {
+            if configs.iter().any(|existing| existing.name == config.name) {
+                tracing::debug!(
+                    "MCP registry {:?}: '{}' already configured, keeping existing entry",
+                    path,
+                    config.name
+                );
+                continue;
+            }
+            configs.push(config);
+        }
+    }
+}
+
 /// Load MCP config with search order:
 /// workspace .mcp.json → ~/.zeroclaw/.mcp.json
 /// Returns configs from first found file, or empty vec if none found.
@@ -99,6 +244,9 @@ pub fn save_mcp_config(
                 command: config.command.clone(),
                 args: config.args.clone(),
                 env: config.env.clone(),
+                max_output_chars: config.max_output_chars,
+                inherit_env: config.inherit_env,
+                env_allowlist: config.env_allowlist.clone(),
             },
         );
     }
@@ -229,6 +377,30 @@ mod tests {
         assert_eq!(configs[0].command, "echo");
         assert!(configs[0].args.is_empty());
         assert!(configs[0].env.is_empty());
+        assert!(configs[0].inherit_env);
+        assert!(configs[0].env_allowlist.is_empty());
+    }
+
+    #[test]
+    fn test_env_policy_roundtrips_through_mcp_json() {
+        let json = r#"{
+            "mcpServers": {
+                "locked": {
+                    "command": "npx",
+                    "inheritEnv": false,
+                    "envAllowlist": ["PATH", "HOME"]
+                }
+            }
+        }"#;
+        let (config_path, _temp_dir) = create_temp_config(json);
+        let configs = parse_mcp_config(&config_path).expect("Failed to parse config");
+        assert!(!configs[0].inherit_env);
+        assert_eq!(configs[0].env_allowlist, vec!["PATH", "HOME"]);
+
+        let servers = HashMap::from([("locked".to_string(), configs[0].clone())]);
+        save_mcp_config(&config_path, &servers).expect("Failed to save config");
+        let reloaded = parse_mcp_config(&config_path).expect("Failed to reparse config");
+        assert_eq!(reloaded, configs);
     }
 
     #[test]
@@ -256,4 +428,124 @@ mod tests {
         assert_eq!(configs.len(), 1);
         assert_eq!(configs[0].name, "workspace");
     }
+
+    #[test]
+    fn test_parse_registry_json_community_format() {
+        let json = r#"{
+            "mcpServers": {
+                "github": {
+                    "command": "npx",
+                    "args": ["-y", "@modelcontextprotocol/server-github"],
+                    "env": {"GITHUB_PERSONAL_ACCESS_TOKEN": "ghp_test"}
+                },
+                "fetch": {
+                    "type": "stdio",
+                    "command": "uvx",
+                    "args": ["mcp-server-fetch"]
+                },
+                "remote": {
+                    "type": "http",
+                    "url": "https://mcp.example.com/mcp"
+                },
+                "off": {
+                    "command": "echo",
+                    "disabled": true
+                }
+            }
+        }"#;
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("mcp.json");
+        fs::write(&path, json).expect("Failed to write registry");
+
+        let configs = parse_mcp_registry(&path).expect("Failed to parse registry");
+
+        let names: Vec<&str> = configs.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["fetch", "github"]);
+        assert_eq!(configs[0].command, "uvx");
+        assert_eq!(configs[0].args, vec!["mcp-server-fetch"]);
+        assert_eq!(configs[1].command, "npx");
+        assert_eq!(
+            configs[1].env.get("GITHUB_PERSONAL_ACCESS_TOKEN"),
+            Some(&"ghp_test".to_string())
+        );
+        assert!(configs[1].inherit_env);
+        assert_eq!(configs[1].max_output_chars, None);
+    }
+
+    #[test]
+    fn test_parse_registry_toml() {
+        let toml = r#"
+            [mcpServers.filesystem]
+            command = "npx"
+            args = ["-y", "@modelcontextprotocol/server-filesystem", "/tmp"]
+
+            [mcpServers.git]
+            command = "uvx"
+            args = ["mcp-server-git"]
+
+            [mcpServers.git.env]
+            GIT_AUTHOR_NAME = "zeroclaw"
+        "#;
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("mcp.toml");
+        fs::write(&path, toml).expect("Failed to write registry");
+
+        let configs = parse_mcp_registry(&path).expect("Failed to parse registry");
+
+        assert_eq!(configs.len(), 2);
+        assert_eq!(configs[0].name, "filesystem");
+        assert_eq!(
+            configs[0].args,
+            vec!["-y", "@modelcontextprotocol/server-filesystem", "/tmp"]
+        );
+        assert_eq!(configs[1].name, "git");
+        assert_eq!(
+            configs[1].env.get("GIT_AUTHOR_NAME"),
+            Some(&"zeroclaw".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_registry_accepts_snake_case_table() {
+        let toml = r#"
+            [mcp_servers.docs]
+            command = "docs-mcp"
+        "#;
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("mcp.toml");
+        fs::write(&path, toml).expect("Failed to write registry");
+
+        let configs = parse_mcp_registry(&path).expect("Failed to parse registry");
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0].command, "docs-mcp");
+    }
+
+    #[test]
+    fn test_parse_registry_missing_file_is_error() {
+        assert!(parse_mcp_registry(Path::new("/nonexistent/mcp.toml")).is_err());
+    }
+
+    #[test]
+    fn test_import_registries_keeps_existing_servers() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let registry = temp_dir.path().join("mcp.json");
+        fs::write(
+            &registry,
+            r#"{"mcpServers": {"git": {"command": "imported"}, "fetch": {"command": "uvx"}}}"#,
+        )
+        .expect("Failed to write registry");
+        let (config_path, _config_dir) =
+            create_temp_config(r#"{"mcpServers": {"git": {"command": "local"}}}"#);
+        let mut configs = parse_mcp_config(&config_path).expect("Failed to parse config");
+
+        import_mcp_registries(
+            &mut configs,
+            &[temp_dir.path().join("missing.json"), registry],
+        );
+
+        assert_eq!(configs.len(), 2);
+        assert_eq!(configs[0].name, "git");
+        assert_eq!(configs[0].command, "local");
+        assert_eq!(configs[1].name, "fetch");
+    }
 }
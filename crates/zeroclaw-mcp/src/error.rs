@@ -0,0 +1,143 @@
+//! Classification of MCP client failures
+//!
+//! [`McpClient`](crate::client::McpClient) methods still return
+//! `anyhow::Result`, but the errors they raise are [`McpError`] values, so
+//! callers can recover the category with [`McpError::of`] and decide whether
+//! to reconnect, retry, or show the failure to the user.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::jsonrpc::JsonRpcError;
+use crate::types::McpToolCallResult;
+
+/// JSON-RPC "method not found" error code
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Why an MCP request failed
+#[derive(Debug)]
+pub enum McpError {
+    /// The connection is broken: the server crashed, closed its pipes or
+    /// wrote something that is not JSON-RPC. Reconnecting may help.
+    Transport(anyhow::Error),
+    /// The server did not answer in time
+    Timeout(Duration),
+    /// The server answered the request with a JSON-RPC error
+    Protocol { method: String, error: JsonRpcError },
+    /// The tool ran and reported `isError`
+    ToolError(McpToolCallResult),
+    /// The server does not implement the method
+    Capability { method: String, message: String },
+}
+
+impl McpError {
+    /// Classify a JSON-RPC error returned for `method`
+    pub fn from_rpc(method: &str, error: JsonRpcError) -> Self {
+        if error.code == METHOD_NOT_FOUND {
+            Self::Capability {
+                method: method.to_string(),
+                message: error.message,
+            }
+        } else {
+            Self::Protocol {
+                method: method.to_string(),
+                error,
+            }
+        }
+    }
+
+    /// The `McpError` behind `error`, looking through any added context
+    pub fn of(error: &anyhow::Error) -> Option<&Self> {
+        error.downcast_ref()
+    }
+
+    /// Whether the connection should be re-established before further calls
+    pub fn needs_reconnect(&self) -> bool {
+        matches!(self, Self::Transport(_))
+    }
+
+    /// Whether repeating the same request may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Transport(_) | Self::Timeout(_))
+    }
+}
+
+impl fmt::Display for McpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(error) => write!(f, "{error}"),
+            Self::Timeout(after) => write!(f, "MCP request timed out after {after:?}"),
+            Self::Protocol { method, error } => write!(f, "{method} failed: {}", error.message),
+            Self::ToolError(result) => {
+                let text = result.text();
+                let message = if text.is_empty() {
+                    "Unknown tool error"
+                } else {
+                    text.as_str()
+                };
+                write!(f, "Tool execution error: {message}")
+            }
+            Self::Capability { method, message } => {
+                write!(f, "{method} is not supported by the server: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for McpError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            // Display already shows the outermost message; continue the chain below it.
+            Self::Transport(error) => error.source(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    fn rpc_error(code: i64) -> JsonRpcError {
+        JsonRpcError {
+            code,
+            message: "nope".to_string(),
+            data: None,
+        }
+    }
+
+    #[test]
+    fn test_method_not_found_is_a_capability_error() {
+        let error = McpError::from_rpc("resources/list", rpc_error(-32601));
+        assert!(
+            matches!(error, McpError::Capability { ref method, .. } if method == "resources/list")
+        );
+
+        let error = McpError::from_rpc("tools/call", rpc_error(-32603));
+        assert!(matches!(error, McpError::Protocol { ref error, .. } if error.code == -32603));
+        assert_eq!(error.to_string(), "tools/call failed: nope");
+    }
+
+    #[test]
+    fn test_classification_survives_context() {
+        let inner = anyhow::anyhow!("broken pipe").context("Failed to send request");
+        let error = anyhow::Error::from(McpError::Transport(inner)).context("server 'fs' failed");
+
+        let classified = McpError::of(&error).unwrap();
+        assert!(classified.needs_reconnect());
+        assert!(classified.is_retryable());
+        assert_eq!(
+            format!("{error:#}"),
+            "server 'fs' failed: Failed to send request: broken pipe"
+        );
+
+        let timeout: anyhow::Result<()> = Err(McpError::Timeout(Duration::from_secs(1)).into());
+        let timeout = timeout.context("call timed out").unwrap_err();
+        let classified = McpError::of(&timeout).unwrap();
+        assert!(classified.is_retryable());
+        assert!(!classified.needs_reconnect());
+
+        assert!(McpError::of(&anyhow::anyhow!("unrelated")).is_none());
+    }
+}
@@ -10,6 +10,15 @@ pub enum RequestId {
     String(String),
 }
 
+impl std::fmt::Display for RequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{n}"),
+            Self::String(s) => f.write_str(s),
+        }
+    }
+}
+
 /// JSON-RPC error object
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct JsonRpcError {
@@ -52,6 +61,28 @@ pub struct JsonRpcNotification {
     pub params: Option<serde_json::Value>,
 }
 
+/// Any message the server may send: a response to one of our requests, a
+/// request of its own (e.g. `ping`), or a notification (e.g. `notifications/progress`)
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonRpcMessage {
+    Request(JsonRpcRequest),
+    Response(JsonRpcResponse),
+    Notification(JsonRpcNotification),
+}
+
+impl JsonRpcMessage {
+    /// Classify a received JSON value: messages carrying a `method` are
+    /// requests when they also have an `id` and notifications otherwise;
+    /// everything else is parsed as a response
+    pub fn from_value(value: serde_json::Value) -> serde_json::Result<Self> {
+        match (value.get("method").is_some(), value.get("id").is_some()) {
+            (true, true) => serde_json::from_value(value).map(Self::Request),
+            (true, false) => serde_json::from_value(value).map(Self::Notification),
+            (false, _) => serde_json::from_value(value).map(Self::Response),
+        }
+    }
+}
+
 fn jsonrpc_version() -> String {
     "2.0".to_string()
 }
@@ -164,6 +195,28 @@ mod tests {
         assert_eq!(deserialized, error);
     }
 
+    #[test]
+    fn test_message_classifies_requests_and_responses() {
+        let ping = JsonRpcMessage::from_value(json!({"jsonrpc": "2.0", "id": 7, "method": "ping"}))
+            .unwrap();
+        assert!(matches!(ping, JsonRpcMessage::Request(ref r) if r.method == "ping"));
+
+        let response =
+            JsonRpcMessage::from_value(json!({"jsonrpc": "2.0", "id": 7, "result": {}})).unwrap();
+        assert!(matches!(response, JsonRpcMessage::Response(ref r) if r.result == Some(json!({}))));
+
+        let progress = JsonRpcMessage::from_value(json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/progress",
+            "params": {"progressToken": 1, "progress": 50}
+        }))
+        .unwrap();
+        assert!(matches!(
+            progress,
+            JsonRpcMessage::Notification(ref n) if n.method == "notifications/progress"
+        ));
+    }
+
     #[test]
     fn test_request_with_string_id() {
         let request = JsonRpcRequest {
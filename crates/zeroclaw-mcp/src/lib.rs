@@ -5,7 +5,9 @@
 
 pub mod client;
 pub mod config;
+pub mod error;
 pub mod jsonrpc;
+pub mod probe;
 pub mod transport;
 pub mod types;
 
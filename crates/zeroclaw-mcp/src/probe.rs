@@ -0,0 +1,280 @@
+//! One-shot connection check for MCP servers
+//!
+//! [`probe`] runs the `initialize` handshake over a fresh transport, counts the
+//! tools, resources and prompts the server advertises, and disconnects. It
+//! backs `zeroclaw mcp test`, a smoke test for new server configs.
+
+use std::fmt;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tracing::warn;
+
+use crate::client::McpClient;
+use crate::error::McpError;
+use crate::transport::McpTransport;
+use crate::types::{Implementation, ServerCapabilities};
+
+/// Default bound for each [`probe`] phase (handshake, then listings)
+pub const DEFAULT_PROBE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// What a server reported during a [`probe`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeReport {
+    pub server_info: Implementation,
+    pub protocol_version: String,
+    pub capabilities: ServerCapabilities,
+    /// Tool count, or `None` if the server does not advertise tools
+    pub tool_count: Option<usize>,
+    /// Resource count, or `None` if the server does not advertise resources
+    pub resource_count: Option<usize>,
+    /// Prompt count, or `None` if the server does not advertise prompts
+    pub prompt_count: Option<usize>,
+}
+
+/// Connect over `transport`, collect a [`ProbeReport`] and close the session
+///
+/// Lists are only requested for capabilities the server advertised. The
+/// handshake and the listings are each bounded by `timeout`, so a server that
+/// never answers fails the probe instead of hanging it. The connection is
+/// closed even when a listing fails or times out.
+pub async fn probe(
+    transport: Box<dyn McpTransport>,
+    client_info: Implementation,
+    timeout: Duration,
+) -> Result<ProbeReport> {
+    let mut client = tokio::time::timeout(
+        timeout,
+        McpClient::connect_with_client_info(transport, client_info),
+    )
+    .await
+    .map_err(|_elapsed| McpError::Timeout(timeout))
+    .context("MCP initialize handshake did not complete")??;
+    let report = match tokio::time::timeout(timeout, collect(&mut client)).await {
+        Ok(report) => report,
+        Err(_elapsed) => Err(McpError::Timeout(timeout)).with_context(|| {
+            format!(
+                "MCP server '{}' did not finish listing its capabilities",
+                client.server_name()
+            )
+        }),
+    };
+    if let Err(e) = client.close().await {
+        warn!(
+            "Failed to close MCP client for server '{}': {e}",
+            client.server_name()
+        );
+    }
+    report
+}
+
+async fn collect(client: &mut McpClient) -> Result<ProbeReport> {
+    let capabilities = client.server_capabilities().cloned().unwrap_or_default();
+    let tool_count = match capabilities.tools {
+        Some(_) => Some(client.list_tools().await?.len()),
+        None => None,
+    };
+    let resource_count = match capabilities.resources {
+        Some(_) => Some(client.list_resources().await?.len()),
+        None => None,
+    };
+    let prompt_count = match capabilities.prompts {
+        Some(_) => Some(client.list_prompts().await?.len()),
+        None => None,
+    };
+    Ok(ProbeReport {
+        server_info: Implementation {
+            name: client.server_name().to_string(),
+            version: client.server_version().to_string(),
+        },
+        protocol_version: client.protocol_version().to_string(),
+        capabilities,
+        tool_count,
+        resource_count,
+        prompt_count,
+    })
+}
+
+impl fmt::Display for ProbeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count =
+            |n: Option<usize>| n.map_or_else(|| "not supported".to_string(), |n| n.to_string());
+
+        let mut advertised: Vec<&str> = Vec::new();
+        if self.capabilities.tools.is_some() {
+            advertised.push("tools");
+        }
+        if self.capabilities.resources.is_some() {
+            advertised.push("resources");
+        }
+        if self.capabilities.prompts.is_some() {
+            advertised.push("prompts");
+        }
+        let experimental: Vec<&str> = self
+            .capabilities
+            .experimental
+            .keys()
+            .map(String::as_str)
+            .collect();
+
+        writeln!(
+            f,
+            "Server:       {} {} (protocol {})",
+            self.server_info.name, self.server_info.version, self.protocol_version
+        )?;
+        if advertised.is_empty() {
+            writeln!(f, "Capabilities: none")?;
+        } else {
+            writeln!(f, "Capabilities: {}", advertised.join(", "))?;
+        }
+        if !experimental.is_empty() {
+            writeln!(f, "Experimental: {}", experimental.join(", "))?;
+        }
+        writeln!(f, "Tools:        {}", count(self.tool_count))?;
+        writeln!(f, "Resources:    {}", count(self.resource_count))?;
+        write!(f, "Prompts:      {}", count(self.prompt_count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    use super::*;
+    use crate::client::default_client_info;
+    use crate::jsonrpc::{JsonRpcNotification, JsonRpcRequest, JsonRpcResponse};
+
+    /// Replies to each request with the next queued result, recording the
+    /// methods called and whether the transport was closed.
+    struct ScriptedTransport {
+        results: VecDeque<serde_json::Value>,
+        pending: Option<JsonRpcRequest>,
+        methods: Arc<Mutex<Vec<String>>>,
+        closed: Arc<Mutex<bool>>,
+    }
+
+    #[async_trait]
+    impl McpTransport for ScriptedTransport {
+        async fn send(&mut self, request: &JsonRpcRequest) -> Result<()> {
+            self.methods.lock().unwrap().push(request.method.clone());
+            self.pending = Some(request.clone());
+            Ok(())
+        }
+
+        async fn send_notification(&mut self, _notification: &JsonRpcNotification) -> Result<()> {
+            Ok(())
+        }
+
+        async fn receive(&mut self) -> Result<JsonRpcResponse> {
+            let request = self.pending.take().expect("receive without a request");
+            // Out of script: behave like a server that stopped answering.
+            let Some(result) = self.results.pop_front() else {
+                return std::future::pending().await;
+            };
+            Ok(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: request.id,
+                result: Some(result),
+                error: None,
+            })
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            *self.closed.lock().unwrap() = true;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_probe_reports_advertised_capabilities() {
+        let methods = Arc::new(Mutex::new(Vec::new()));
+        let closed = Arc::new(Mutex::new(false));
+        let transport = ScriptedTransport {
+            results: VecDeque::from([
+                json!({
+                    "protocolVersion": "2024-11-05",
+                    "capabilities": {
+                        "tools": {},
+                        "prompts": {},
+                        "experimental": {"streaming": true}
+                    },
+                    "serverInfo": {"name": "Files", "version": "2.1.0"}
+                }),
+                json!({"tools": [
+                    {"name": "read", "inputSchema": {"type": "object"}},
+                    {"name": "write", "inputSchema": {"type": "object"}}
+                ]}),
+                json!({"prompts": [{"name": "summarize"}]}),
+            ]),
+            pending: None,
+            methods: Arc::clone(&methods),
+            closed: Arc::clone(&closed),
+        };
+
+        let report = probe(
+            Box::new(transport),
+            default_client_info(),
+            DEFAULT_PROBE_TIMEOUT,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(report.tool_count, Some(2));
+        assert_eq!(report.resource_count, None);
+        assert_eq!(report.prompt_count, Some(1));
+        // Resources were not advertised, so they were never requested.
+        assert_eq!(
+            *methods.lock().unwrap(),
+            vec!["initialize", "tools/list", "prompts/list"]
+        );
+        assert!(*closed.lock().unwrap());
+
+        let summary = report.to_string();
+        assert!(
+            summary.contains("Files 2.1.0 (protocol 2024-11-05)"),
+            "{summary}"
+        );
+        assert!(
+            summary.contains("Capabilities: tools, prompts"),
+            "{summary}"
+        );
+        assert!(summary.contains("Experimental: streaming"), "{summary}");
+        assert!(summary.contains("Tools:        2"), "{summary}");
+        assert!(summary.contains("Resources:    not supported"), "{summary}");
+        assert!(summary.contains("Prompts:      1"), "{summary}");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_probe_times_out_on_unresponsive_server() {
+        let closed = Arc::new(Mutex::new(false));
+        let transport = ScriptedTransport {
+            // Answers the handshake, then goes silent on tools/list.
+            results: VecDeque::from([json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {"tools": {}},
+                "serverInfo": {"name": "Stuck", "version": "0.1.0"}
+            })]),
+            pending: None,
+            methods: Arc::new(Mutex::new(Vec::new())),
+            closed: Arc::clone(&closed),
+        };
+
+        let error = probe(
+            Box::new(transport),
+            default_client_info(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(
+            matches!(McpError::of(&error), Some(McpError::Timeout(after)) if *after == Duration::from_secs(5))
+        );
+        assert!(error.to_string().contains("'Stuck'"), "{error}");
+        assert!(*closed.lock().unwrap());
+    }
+}
@@ -11,15 +11,19 @@ use anyhow::{Context, Result};
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
-use crate::client::McpClient;
+use crate::client::{default_client_info, McpClient};
 use crate::config::McpServerConfig;
-use crate::transport::StdioTransport;
-use crate::types::{McpPrompt, McpResource, McpToolCallResult, McpToolInfo};
+use crate::error::McpError;
+use crate::transport::{McpTransport, StdioTransport, TraceRedactor, TraceTransport};
+use crate::types::{Implementation, McpPrompt, McpResource, McpToolCallResult, McpToolInfo};
 
 /// Default timeout for MCP tool calls (30 seconds per MCP spec recommendation).
 const DEFAULT_TOOL_CALL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
 
 /// Internal state for a connected MCP server
+///
+/// A client carries one request at a time, so calls to the same server are
+/// serialized on its mutex.
 struct McpServerState {
     client: Arc<tokio::sync::Mutex<McpClient>>,
     tools: Vec<McpToolInfo>,
@@ -34,6 +38,19 @@ pub struct McpRegistry {
     builtin_tool_names: HashSet<String>,
     generation: AtomicU64,
     config_path: Option<std::path::PathBuf>,
+    /// Per-channel server allowlists; channels without an entry see every server
+    channel_servers: HashMap<String, HashSet<String>>,
+    /// Default cap on tool result text (0 = unlimited)
+    max_output_chars: usize,
+    /// Directory for per-server JSON-RPC trace files and the redactor their
+    /// lines go through (disabled when unset)
+    trace: Option<(std::path::PathBuf, TraceRedactor)>,
+    /// `clientInfo` announced to servers during `initialize`
+    client_info: Implementation,
+    /// Timeout for tool calls without a per-tool override
+    tool_call_timeout: std::time::Duration,
+    /// Per-tool timeout overrides, keyed by tool name
+    tool_timeouts: HashMap<String, std::time::Duration>,
 }
 
 impl McpRegistry {
@@ -49,6 +66,12 @@ impl McpRegistry {
             builtin_tool_names,
             generation: AtomicU64::new(0),
             config_path: None,
+            channel_servers: HashMap::new(),
+            max_output_chars: 0,
+            trace: None,
+            client_info: default_client_info(),
+            tool_call_timeout: DEFAULT_TOOL_CALL_TIMEOUT,
+            tool_timeouts: HashMap::new(),
         }
     }
 
@@ -58,6 +81,80 @@ impl McpRegistry {
         self
     }
 
+    /// Restrict which servers each channel may use
+    ///
+    /// Keys are channel names (e.g. `telegram`), values are server names.
+    /// Channels without an entry see tools from every connected server.
+    pub fn with_channel_servers(mut self, channel_servers: HashMap<String, Vec<String>>) -> Self {
+        self.channel_servers = channel_servers
+            .into_iter()
+            .map(|(channel, servers)| (channel, servers.into_iter().collect()))
+            .collect();
+        self
+    }
+
+    /// Truncate tool result text above `max_chars` (0 = unlimited)
+    ///
+    /// Servers can override this with `max_output_chars` in their config.
+    pub fn with_max_output_chars(mut self, max_chars: usize) -> Self {
+        self.max_output_chars = max_chars;
+        self
+    }
+
+    /// Dump each server's JSON-RPC traffic to `<dir>/<server>.jsonl`
+    ///
+    /// Every line is rendered by `redact` before it is written. Only applies
+    /// to servers spawned by [`add_server`](Self::add_server).
+    pub fn with_trace_dir(mut self, dir: std::path::PathBuf, redact: TraceRedactor) -> Self {
+        self.trace = Some((dir, redact));
+        self
+    }
+
+    /// Set the `clientInfo` (name and version) announced to every server
+    pub fn with_client_info(mut self, client_info: Implementation) -> Self {
+        self.client_info = client_info;
+        self
+    }
+
+    /// Time out tool calls after `timeout` (default: 30 seconds)
+    ///
+    /// Tools given their own limit with
+    /// [`with_tool_timeouts`](Self::with_tool_timeouts) are not affected.
+    pub fn with_tool_call_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.tool_call_timeout = timeout;
+        self
+    }
+
+    /// Override the tool call timeout for individual tools
+    ///
+    /// Keys are `server:tool` to target one server's tool, or a bare tool
+    /// name that applies to that tool on every server. Useful for
+    /// long-running tools such as builds, without raising the limit for
+    /// every other tool.
+    pub fn with_tool_timeouts(mut self, timeouts: HashMap<String, std::time::Duration>) -> Self {
+        self.tool_timeouts = timeouts;
+        self
+    }
+
+    /// Timeout applied to calls of `tool_name` on `server_name`
+    ///
+    /// A `server:tool` override wins over a bare `tool` override, which wins
+    /// over the registry-wide default.
+    pub fn tool_call_timeout(&self, server_name: &str, tool_name: &str) -> std::time::Duration {
+        self.tool_timeouts
+            .get(&format!("{server_name}:{tool_name}"))
+            .or_else(|| self.tool_timeouts.get(tool_name))
+            .copied()
+            .unwrap_or(self.tool_call_timeout)
+    }
+
+    /// Check whether a channel is allowed to use tools from a server
+    pub fn is_server_allowed(&self, channel: &str, server_name: &str) -> bool {
+        self.channel_servers
+            .get(channel)
+            .is_none_or(|allowed| allowed.contains(server_name))
+    }
+
     /// Persist current server configs to disk if config_path is set.
     /// Logs a warning on failure but does not propagate the error.
     fn persist_config(&self, servers: &HashMap<String, McpServerState>) {
@@ -77,6 +174,20 @@ impl McpRegistry {
         self.generation.load(Ordering::SeqCst)
     }
 
+    /// Build the registry entry for a connected server
+    fn server_state(
+        &self,
+        client: McpClient,
+        tools: Vec<McpToolInfo>,
+        config: McpServerConfig,
+    ) -> McpServerState {
+        McpServerState {
+            client: Arc::new(tokio::sync::Mutex::new(client)),
+            tools,
+            config,
+        }
+    }
+
     /// Validate that a server name is non-empty and not whitespace-only
     fn validate_server_name(name: &str) -> Result<()> {
         if name.trim().is_empty() {
@@ -103,13 +214,21 @@ impl McpRegistry {
 
         info!("Adding MCP server: {}", server_name);
 
-        let transport = StdioTransport::new(&config.command, &config.args, &config.env)
-            .await
-            .with_context(|| format!("Failed to spawn MCP server '{}'", server_name))?;
+        let mut transport: Box<dyn McpTransport> = Box::new(
+            StdioTransport::from_config(&config)
+                .await
+                .with_context(|| format!("Failed to spawn MCP server '{}'", server_name))?,
+        );
+        if let Some((dir, redact)) = &self.trace {
+            let path = dir.join(format!("{}.jsonl", trace_file_stem(&server_name)));
+            transport =
+                Box::new(TraceTransport::new(transport, &server_name, &path, *redact).await?);
+        }
 
-        let mut client = McpClient::connect(Box::new(transport))
+        let mut client = McpClient::connect_with_client_info(transport, self.client_info.clone())
             .await
-            .with_context(|| format!("Failed to connect to MCP server '{}'", server_name))?;
+            .with_context(|| format!("Failed to connect to MCP server '{}'", server_name))?
+            .with_config_name(&server_name);
 
         let tools = client
             .list_tools()
@@ -164,14 +283,8 @@ impl McpRegistry {
             );
         }
 
-        servers.insert(
-            server_name.clone(),
-            McpServerState {
-                client: Arc::new(tokio::sync::Mutex::new(client)),
-                tools: tools.clone(),
-                config,
-            },
-        );
+        let state = self.server_state(client, tools.clone(), config);
+        servers.insert(server_name.clone(), state);
         self.generation.fetch_add(1, Ordering::SeqCst);
 
         info!(
@@ -189,10 +302,11 @@ impl McpRegistry {
     pub async fn add_server_with_client(
         &self,
         name: String,
-        mut client: McpClient,
+        client: McpClient,
         config: McpServerConfig,
     ) -> Result<Vec<McpToolInfo>> {
         Self::validate_server_name(&name)?;
+        let mut client = client.with_config_name(&name);
         let tools = client
             .list_tools()
             .await
@@ -219,14 +333,8 @@ impl McpRegistry {
                 self.tool_cap
             );
         }
-        servers.insert(
-            name.clone(),
-            McpServerState {
-                client: Arc::new(tokio::sync::Mutex::new(client)),
-                tools: tools.clone(),
-                config,
-            },
-        );
+        let state = self.server_state(client, tools.clone(), config);
+        servers.insert(name.clone(), state);
         self.generation.fetch_add(1, Ordering::SeqCst);
         info!(
             "MCP server '{}' added successfully with {} tools",
@@ -339,18 +447,35 @@ impl McpRegistry {
         all_tools
     }
 
-    /// Call a tool on a specific MCP server
+    /// Get the tools advertised to a specific channel
+    ///
+    /// # Returns
+    /// Vector of (server_name, McpToolInfo) pairs for servers the channel may use
+    pub async fn get_tools_for_channel(&self, channel: &str) -> Vec<(String, McpToolInfo)> {
+        self.get_all_tools()
+            .await
+            .into_iter()
+            .filter(|(server_name, _)| self.is_server_allowed(channel, server_name))
+            .collect()
+    }
+
+    /// Call a tool on a specific MCP server on behalf of a channel
+    ///
+    /// The call is refused when `[mcp.channels]` does not allow `channel` to
+    /// use `server_name`.
     ///
     /// # Arguments
+    /// * `channel` - Channel the call originates from (e.g. `telegram`, `cli`)
     /// * `server_name` - Name of the server hosting the tool
     /// * `tool_name` - Name of the tool to call
     /// * `args` - Optional JSON arguments for the tool
     ///
     /// # Returns
     /// * `Ok(McpToolCallResult)` - Tool execution result
-    /// * `Err` - Server not found or tool execution failed
+    /// * `Err` - Server not allowed on the channel, not found, or tool execution failed
     pub async fn call_tool(
         &self,
+        channel: &str,
         server_name: &str,
         tool_name: &str,
         args: Option<serde_json::Value>,
@@ -359,30 +484,64 @@ impl McpRegistry {
             "Calling MCP tool '{}' on server '{}'",
             tool_name, server_name
         );
+        if !self.is_server_allowed(channel, server_name) {
+            anyhow::bail!(
+                "MCP server '{}' is not allowed on channel '{}'",
+                server_name,
+                channel
+            );
+        }
 
-        let client = {
+        let (client, max_output_chars) = {
             let servers = self.servers.read().await;
             let server = servers
                 .get(server_name)
                 .with_context(|| format!("MCP server '{}' not found", server_name))?;
-            Arc::clone(&server.client)
+            (
+                Arc::clone(&server.client),
+                server
+                    .config
+                    .max_output_chars
+                    .unwrap_or(self.max_output_chars),
+            )
         }; // read lock released here
 
         let mut client = client.lock().await;
 
-        match tokio::time::timeout(DEFAULT_TOOL_CALL_TIMEOUT, client.call_tool(tool_name, args)).await {
-            Ok(result) => result.with_context(|| {
+        let timeout = self.tool_call_timeout(server_name, tool_name);
+        match tokio::time::timeout(timeout, client.call_tool(tool_name, args)).await {
+            Ok(Ok(mut result)) => {
+                let original_len = result.text_len();
+                if result.truncate_text(max_output_chars) {
+                    debug!(
+                        "Truncated MCP tool '{}' output from {} to {} characters",
+                        tool_name, original_len, max_output_chars
+                    );
+                }
+                Ok(result)
+            }
+            Ok(Err(e)) => {
+                // Only a broken connection warrants the reconnect hint; tool
+                // and protocol errors are the server's answer to this call.
+                let context = if McpError::of(&e).is_some_and(McpError::needs_reconnect) {
+                    format!(
+                        "MCP server '{}' failed while calling tool '{}'. The server may have crashed or disconnected. Remove and re-add it to reconnect.",
+                        server_name, tool_name
+                    )
+                } else {
+                    format!(
+                        "MCP server '{}' failed while calling tool '{}'",
+                        server_name, tool_name
+                    )
+                };
+                Err(e.context(context))
+            }
+            Err(_elapsed) => Err(McpError::Timeout(timeout)).with_context(|| {
                 format!(
-                    "MCP server '{}' failed while calling tool '{}'. The server may have crashed or disconnected. Remove and re-add it to reconnect.",
-                    server_name, tool_name
-                )
-            }),
-            Err(_elapsed) => {
-                anyhow::bail!(
                     "MCP tool call '{}' on server '{}' timed out after {:?}. The server may be unresponsive.",
-                    tool_name, server_name, DEFAULT_TOOL_CALL_TIMEOUT
+                    tool_name, server_name, timeout
                 )
-            }
+            }),
         }
     }
 
@@ -463,6 +622,20 @@ impl McpRegistry {
     }
 }
 
+/// File-name-safe form of a server name for its trace file
+fn trace_file_stem(server_name: &str) -> String {
+    server_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::VecDeque;
@@ -559,6 +732,9 @@ mod tests {
             command: "test".to_string(),
             args: vec![],
             env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
         };
 
         let tools = registry
@@ -592,6 +768,9 @@ mod tests {
             command: "test".to_string(),
             args: vec![],
             env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
         };
 
         registry
@@ -622,6 +801,9 @@ mod tests {
             command: "test1".to_string(),
             args: vec![],
             env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
         };
         registry
             .add_server_with_client("server1".to_string(), client1, config1)
@@ -646,6 +828,9 @@ mod tests {
             command: "test2".to_string(),
             args: vec![],
             env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
         };
         registry
             .add_server_with_client("server2".to_string(), client2, config2)
@@ -685,6 +870,9 @@ mod tests {
             command: "test".to_string(),
             args: vec![],
             env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
         };
 
         let result = registry
@@ -728,6 +916,9 @@ mod tests {
             command: "test".to_string(),
             args: vec![],
             env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
         };
 
         let result = registry
@@ -757,6 +948,9 @@ mod tests {
             command: "test_a".to_string(),
             args: vec![],
             env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
         };
         registry
             .add_server_with_client("server_a".to_string(), client1, config1)
@@ -781,6 +975,9 @@ mod tests {
             command: "test_b".to_string(),
             args: vec![],
             env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
         };
         registry
             .add_server_with_client("server_b".to_string(), client2, config2)
@@ -801,12 +998,436 @@ mod tests {
         assert_eq!(server_b_tools.len(), 2);
     }
 
+    async fn add_single_tool_server(registry: &McpRegistry, server: &str, tool: &str) {
+        let client = create_mock_client(create_tools_response(vec![json!({
+            "name": tool,
+            "description": "Test tool",
+            "inputSchema": {"type": "object"}
+        })]))
+        .await;
+        let config = McpServerConfig {
+            name: server.to_string(),
+            command: "test".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
+        };
+        registry
+            .add_server_with_client(server.to_string(), client, config)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_channel_sees_only_allowed_servers() {
+        let registry = McpRegistry::new(50, HashSet::new()).with_channel_servers(HashMap::from([
+            ("telegram".to_string(), vec!["github".to_string()]),
+            ("discord".to_string(), vec![]),
+        ]));
+        add_single_tool_server(&registry, "github", "create_issue").await;
+        add_single_tool_server(&registry, "filesystem", "read_file").await;
+
+        let telegram = registry.get_tools_for_channel("telegram").await;
+        assert_eq!(telegram.len(), 1);
+        assert_eq!(telegram[0].0, "github");
+        assert_eq!(telegram[0].1.name, "create_issue");
+
+        assert!(registry.get_tools_for_channel("discord").await.is_empty());
+        assert!(registry.is_server_allowed("telegram", "github"));
+        assert!(!registry.is_server_allowed("telegram", "filesystem"));
+
+        let error = registry
+            .call_tool("telegram", "filesystem", "read_file", None)
+            .await
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("not allowed on channel 'telegram'"));
+    }
+
+    #[tokio::test]
+    async fn test_unlisted_channel_sees_all_servers() {
+        let registry =
+            McpRegistry::new(50, HashSet::new()).with_channel_servers(HashMap::from([(
+                "telegram".to_string(),
+                vec!["github".to_string()],
+            )]));
+        add_single_tool_server(&registry, "github", "create_issue").await;
+        add_single_tool_server(&registry, "filesystem", "read_file").await;
+
+        let cli_servers: HashSet<String> = registry
+            .get_tools_for_channel("cli")
+            .await
+            .into_iter()
+            .map(|(server, _)| server)
+            .collect();
+        assert_eq!(
+            cli_servers,
+            HashSet::from(["github".to_string(), "filesystem".to_string()])
+        );
+    }
+
+    /// Add a server exposing one tool (named after the server) that answers a
+    /// single call with `output`
+    async fn add_server_returning(
+        registry: &McpRegistry,
+        server: &str,
+        output: &str,
+        max_output_chars: Option<usize>,
+    ) {
+        let call_response = JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(3),
+            result: Some(json!({"content": [{"type": "text", "text": output}]})),
+            error: None,
+        };
+        let transport = MockTransport::new(vec![
+            create_init_response(),
+            create_tools_response(vec![json!({
+                "name": server,
+                "inputSchema": {"type": "object"}
+            })]),
+            call_response,
+        ]);
+        let client = McpClient::connect(Box::new(transport)).await.unwrap();
+        let config = McpServerConfig {
+            name: server.to_string(),
+            command: "test".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            max_output_chars,
+            inherit_env: true,
+            env_allowlist: vec![],
+        };
+        registry
+            .add_server_with_client(server.to_string(), client, config)
+            .await
+            .unwrap();
+    }
+
+    /// Mock transport that answers every call after `delay`
+    struct SlowTransport {
+        inner: MockTransport,
+        delay: std::time::Duration,
+    }
+
+    #[async_trait]
+    impl McpTransport for SlowTransport {
+        async fn send(&mut self, request: &JsonRpcRequest) -> Result<()> {
+            self.inner.send(request).await
+        }
+
+        async fn send_notification(&mut self, notification: &JsonRpcNotification) -> Result<()> {
+            self.inner.send_notification(notification).await
+        }
+
+        async fn receive(&mut self) -> Result<JsonRpcResponse> {
+            tokio::time::sleep(self.delay).await;
+            self.inner.receive().await
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Mock server that answers the last request it was sent, echoing
+    /// `arguments.n` back from `tools/call`
+    #[derive(Default)]
+    struct EchoTransport {
+        last_request: Option<JsonRpcRequest>,
+    }
+
+    #[async_trait]
+    impl McpTransport for EchoTransport {
+        async fn send(&mut self, request: &JsonRpcRequest) -> Result<()> {
+            assert!(
+                self.last_request.is_none(),
+                "request sent while another was awaiting its response"
+            );
+            self.last_request = Some(request.clone());
+            Ok(())
+        }
+
+        async fn send_notification(&mut self, _notification: &JsonRpcNotification) -> Result<()> {
+            Ok(())
+        }
+
+        async fn receive(&mut self) -> Result<JsonRpcResponse> {
+            // Give other callers a chance to run while this one waits.
+            tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+            let request = self.last_request.take().context("nothing to answer")?;
+            let result = match request.method.as_str() {
+                "initialize" => create_init_response().result,
+                "tools/list" => Some(json!({
+                    "tools": [{"name": "echo", "inputSchema": {"type": "object"}}]
+                })),
+                _ => {
+                    let n = request.params.as_ref().unwrap()["arguments"]["n"].clone();
+                    Some(json!({"content": [{"type": "text", "text": n.to_string()}]}))
+                }
+            };
+            Ok(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: request.id,
+                result,
+                error: None,
+            })
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_calls_to_one_server_get_their_own_results() {
+        let registry = Arc::new(McpRegistry::new(50, HashSet::new()));
+        let client = McpClient::connect(Box::new(EchoTransport::default()))
+            .await
+            .unwrap();
+        let config = McpServerConfig {
+            name: "echo".to_string(),
+            command: "test".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
+        };
+        registry
+            .add_server_with_client("echo".to_string(), client, config)
+            .await
+            .unwrap();
+
+        let calls: Vec<_> = (0..8)
+            .map(|n| {
+                let registry = Arc::clone(&registry);
+                tokio::spawn(async move {
+                    registry
+                        .call_tool("cli", "echo", "echo", Some(json!({ "n": n })))
+                        .await
+                })
+            })
+            .collect();
+        for (n, call) in calls.into_iter().enumerate() {
+            let result = call.await.unwrap().unwrap();
+            assert_eq!(result_text(&result), n.to_string());
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_call_tool_timeout_is_classified() {
+        let registry = McpRegistry::new(50, HashSet::new());
+        // Every receive outlasts the call timeout; with the clock paused the
+        // handshake simply advances virtual time.
+        let transport = SlowTransport {
+            inner: MockTransport::new(vec![
+                create_init_response(),
+                create_tools_response(vec![
+                    json!({"name": "hang", "inputSchema": {"type": "object"}}),
+                ]),
+            ]),
+            delay: DEFAULT_TOOL_CALL_TIMEOUT * 2,
+        };
+        let client = McpClient::connect(Box::new(transport)).await.unwrap();
+        let config = McpServerConfig {
+            name: "hang".to_string(),
+            command: "test".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
+        };
+        registry
+            .add_server_with_client("hang".to_string(), client, config)
+            .await
+            .unwrap();
+
+        let error = registry
+            .call_tool("cli", "hang", "hang", None)
+            .await
+            .unwrap_err();
+        let classified = McpError::of(&error).unwrap();
+        assert!(
+            matches!(classified, McpError::Timeout(after) if *after == DEFAULT_TOOL_CALL_TIMEOUT)
+        );
+        assert!(classified.is_retryable());
+        assert!(error.to_string().contains("timed out"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_per_tool_timeout_overrides_default() {
+        let registry = McpRegistry::new(50, HashSet::new()).with_tool_timeouts(HashMap::from([
+            ("build".to_string(), DEFAULT_TOOL_CALL_TIMEOUT * 2),
+            ("ci:build".to_string(), DEFAULT_TOOL_CALL_TIMEOUT * 4),
+        ]));
+        assert_eq!(
+            registry.tool_call_timeout("ci", "lint"),
+            DEFAULT_TOOL_CALL_TIMEOUT
+        );
+        assert_eq!(
+            registry.tool_call_timeout("ci", "build"),
+            DEFAULT_TOOL_CALL_TIMEOUT * 4
+        );
+        assert_eq!(
+            registry.tool_call_timeout("other", "build"),
+            DEFAULT_TOOL_CALL_TIMEOUT * 2
+        );
+
+        // Each response arrives after twice the default timeout.
+        let transport = SlowTransport {
+            inner: MockTransport::new(vec![
+                create_init_response(),
+                create_tools_response(vec![
+                    json!({"name": "build", "inputSchema": {"type": "object"}}),
+                    json!({"name": "lint", "inputSchema": {"type": "object"}}),
+                ]),
+                JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id: RequestId::Number(3),
+                    result: Some(json!({"content": [{"type": "text", "text": "built"}]})),
+                    error: None,
+                },
+            ]),
+            delay: DEFAULT_TOOL_CALL_TIMEOUT * 2,
+        };
+        let client = McpClient::connect(Box::new(transport)).await.unwrap();
+        let config = McpServerConfig {
+            name: "ci".to_string(),
+            command: "test".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
+        };
+        registry
+            .add_server_with_client("ci".to_string(), client, config)
+            .await
+            .unwrap();
+
+        let result = registry
+            .call_tool("cli", "ci", "build", None)
+            .await
+            .unwrap();
+        assert_eq!(result.text(), "built");
+
+        let error = registry
+            .call_tool("cli", "ci", "lint", None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            McpError::of(&error),
+            Some(McpError::Timeout(after)) if *after == DEFAULT_TOOL_CALL_TIMEOUT
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_hint_only_for_transport_failures() {
+        let registry = McpRegistry::new(50, HashSet::new());
+        let tool_error = JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(3),
+            result: Some(
+                json!({"content": [{"type": "text", "text": "bad input"}], "isError": true}),
+            ),
+            error: None,
+        };
+        let transport = MockTransport::new(vec![
+            create_init_response(),
+            create_tools_response(vec![
+                json!({"name": "t", "inputSchema": {"type": "object"}}),
+            ]),
+            tool_error,
+        ]);
+        let client = McpClient::connect(Box::new(transport)).await.unwrap();
+        let config = McpServerConfig {
+            name: "srv".to_string(),
+            command: "test".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
+        };
+        registry
+            .add_server_with_client("srv".to_string(), client, config)
+            .await
+            .unwrap();
+
+        let error = registry
+            .call_tool("cli", "srv", "t", None)
+            .await
+            .unwrap_err();
+        assert!(matches!(McpError::of(&error), Some(McpError::ToolError(_))));
+        assert!(!error.to_string().contains("reconnect"));
+
+        // The mock is out of responses, like a server that went away.
+        let error = registry
+            .call_tool("cli", "srv", "t", None)
+            .await
+            .unwrap_err();
+        assert!(McpError::of(&error).unwrap().needs_reconnect());
+        assert!(error
+            .to_string()
+            .contains("Remove and re-add it to reconnect"));
+    }
+
+    fn result_text(result: &McpToolCallResult) -> &str {
+        result.content[0].text.as_deref().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_call_tool_truncates_large_output() {
+        let registry = McpRegistry::new(50, HashSet::new()).with_max_output_chars(100);
+        add_server_returning(&registry, "files", &"x".repeat(10_000), None).await;
+        add_server_returning(&registry, "small", "hello", None).await;
+
+        let large = registry
+            .call_tool("cli", "files", "files", None)
+            .await
+            .unwrap();
+        assert!(result_text(&large).contains("characters omitted"));
+        assert!(result_text(&large).len() < 200);
+
+        let small = registry
+            .call_tool("cli", "small", "small", None)
+            .await
+            .unwrap();
+        assert_eq!(result_text(&small), "hello");
+    }
+
+    #[tokio::test]
+    async fn test_server_output_limit_overrides_default() {
+        let registry = McpRegistry::new(50, HashSet::new()).with_max_output_chars(100);
+        add_server_returning(&registry, "unlimited", &"x".repeat(1_000), Some(0)).await;
+        add_server_returning(&registry, "tight", &"y".repeat(50), Some(20)).await;
+
+        let unlimited = registry
+            .call_tool("cli", "unlimited", "unlimited", None)
+            .await
+            .unwrap();
+        assert_eq!(result_text(&unlimited).len(), 1_000);
+
+        let tight = registry
+            .call_tool("cli", "tight", "tight", None)
+            .await
+            .unwrap();
+        assert!(result_text(&tight).contains("30 characters omitted"));
+    }
+
     #[tokio::test]
     async fn test_call_tool() {
         // Error path test: server not found
         let registry = McpRegistry::new(50, HashSet::new());
 
-        let result = registry.call_tool("nonexistent", "some_tool", None).await;
+        let result = registry
+            .call_tool("cli", "nonexistent", "some_tool", None)
+            .await;
 
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
@@ -827,6 +1448,9 @@ mod tests {
             command: "test".to_string(),
             args: vec![],
             env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
         };
 
         let result = registry
@@ -851,6 +1475,9 @@ mod tests {
             command: "test".to_string(),
             args: vec![],
             env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
         };
 
         let result = registry
@@ -875,6 +1502,9 @@ mod tests {
             command: "test".to_string(),
             args: vec![],
             env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
         };
 
         let result = registry
@@ -899,6 +1529,9 @@ mod tests {
             command: "test".to_string(),
             args: vec![],
             env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
         };
 
         let result = registry
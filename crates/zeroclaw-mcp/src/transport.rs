@@ -1,19 +1,39 @@
 //! MCP transport layer implementations
 //!
 //! This module provides transport implementations for MCP JSON-RPC communication.
-//! Currently supports stdio-based transport using newline-delimited JSON framing.
+//! Currently supports stdio-based transport. Incoming messages may use either
+//! newline-delimited JSON (including pretty-printed, multi-line objects) or
+//! `Content-Length:` header framing; the framing is detected per message.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use serde_json::Value;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, Command};
 use tracing::{debug, error, info, warn};
 
-use crate::jsonrpc::{JsonRpcNotification, JsonRpcRequest, JsonRpcResponse};
+use crate::config::McpServerConfig;
+use crate::jsonrpc::{JsonRpcMessage, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse};
+
+/// Upper bound for a single incoming message, to guard against bogus headers
+/// or a server that never terminates its JSON.
+const MAX_MESSAGE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Number of recent stderr lines kept for error reports
+const STDERR_TAIL_LINES: usize = 20;
+
+/// Message framing on the stdio streams
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Framing {
+    /// One JSON value per message, terminated by a newline
+    NewlineDelimited,
+    /// LSP-style `Content-Length: N` headers, a blank line, then N bytes of JSON
+    ContentLength,
+}
 
 /// MCP transport trait for async JSON-RPC communication
 #[async_trait]
@@ -27,21 +47,49 @@ pub trait McpTransport: Send + Sync {
     /// Receive a JSON-RPC response
     async fn receive(&mut self) -> Result<JsonRpcResponse>;
 
+    /// Receive the next message, which may be a request from the server
+    ///
+    /// Transports that cannot carry server-initiated requests only ever
+    /// return responses.
+    async fn receive_message(&mut self) -> Result<JsonRpcMessage> {
+        self.receive().await.map(JsonRpcMessage::Response)
+    }
+
+    /// Answer a request the server sent
+    async fn send_response(&mut self, response: &JsonRpcResponse) -> Result<()> {
+        let _ = response;
+        anyhow::bail!("Transport cannot answer server requests")
+    }
+
     /// Close the transport and cleanup resources
     async fn close(&mut self) -> Result<()>;
 }
 
 /// Stdio-based MCP transport that spawns a child process
 ///
-/// Uses newline-delimited JSON framing:
-/// - Each message is serialized to JSON followed by a newline
-/// - Reading stops at each newline boundary
+/// Framing:
+/// - Outgoing messages are newline-delimited JSON until the server replies
+///   with `Content-Length:` framing, after which writes switch to match
+/// - Incoming messages are auto-detected from their first byte: `{` or `[`
+///   starts newline-delimited JSON (which may span several lines), anything
+///   else is read as a `Content-Length:` header block
+///
+/// The child's stderr is drained in the background, logged at debug level
+/// and the last few lines are attached to errors once the process has died.
+///
+/// Call [`close`](McpTransport::close) for a graceful shutdown: it closes
+/// stdin and gives the server time to exit. Dropping the transport without
+/// closing it kills the child instead, and the tokio runtime reaps it in the
+/// background.
 #[derive(Debug)]
 pub struct StdioTransport {
+    server_name: String,
     child: Child,
     stdin: Option<tokio::process::ChildStdin>,
     stdout: Option<BufReader<tokio::process::ChildStdout>>,
     stderr_task: Option<tokio::task::JoinHandle<()>>,
+    stderr_tail: Arc<Mutex<VecDeque<String>>>,
+    framing: Framing,
 }
 
 impl StdioTransport {
@@ -60,24 +108,57 @@ impl StdioTransport {
         args: &[String],
         env: &HashMap<String, String>,
     ) -> Result<Self> {
-        info!("Spawning MCP server: {} with {} args", command, args.len());
+        let mut cmd = Command::new(command);
+        cmd.args(args).envs(env);
+        Self::spawn(command, cmd).await
+    }
+
+    /// Spawn the server described by `config`
+    ///
+    /// Applies the config's environment policy: with `inherit_env = false` the
+    /// child starts from an empty environment, receives only the
+    /// `env_allowlist` variables from this process, then the explicit `env`.
+    /// Logs and errors are labelled with the server name.
+    pub async fn from_config(config: &McpServerConfig) -> Result<Self> {
+        let mut cmd = Command::new(&config.command);
+        cmd.args(&config.args);
+        if !config.inherit_env {
+            cmd.env_clear();
+            for name in &config.env_allowlist {
+                if let Some(value) = std::env::var_os(name) {
+                    cmd.env(name, value);
+                }
+            }
+        }
+        cmd.envs(&config.env);
+        Self::spawn(&config.name, cmd).await
+    }
 
-        let mut child = Command::new(command)
-            .args(args)
-            .envs(env)
+    async fn spawn(server_name: &str, mut cmd: Command) -> Result<Self> {
+        let program = cmd.as_std().get_program().to_string_lossy().into_owned();
+        info!(
+            "Spawning MCP server: {} with {} args",
+            program,
+            cmd.as_std().get_args().count()
+        );
+
+        let mut child = cmd
             .stdin(std::process::Stdio::piped())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
             .spawn()
-            .context(format!("Failed to spawn command: {}", command))?;
+            .context(format!("Failed to spawn command: {}", program))?;
 
         let stdin = child.stdin.take().context("Failed to open stdin")?;
         let stdout = child.stdout.take().context("Failed to open stdout")?;
         let stdout_reader = BufReader::new(stdout);
 
         // Spawn a background task to drain stderr and prevent pipe buffer deadlock
+        let stderr_tail = Arc::new(Mutex::new(VecDeque::with_capacity(STDERR_TAIL_LINES)));
         let stderr_task = if let Some(stderr) = child.stderr.take() {
             let mut reader = BufReader::new(stderr);
+            let name = server_name.to_string();
+            let tail = Arc::clone(&stderr_tail);
             Some(tokio::spawn(async move {
                 let mut line = String::new();
                 loop {
@@ -85,7 +166,14 @@ impl StdioTransport {
                     match reader.read_line(&mut line).await {
                         Ok(0) => break, // EOF
                         Ok(_) => {
-                            debug!("MCP stderr: {}", line.trim());
+                            let line = line.trim_end();
+                            debug!("MCP [{}] stderr: {}", name, line);
+                            if let Ok(mut tail) = tail.lock() {
+                                if tail.len() == STDERR_TAIL_LINES {
+                                    tail.pop_front();
+                                }
+                                tail.push_back(line.to_string());
+                            }
                         }
                         Err(e) => {
                             debug!("MCP stderr read error: {}", e);
@@ -101,58 +189,253 @@ impl StdioTransport {
         debug!("MCP server spawned successfully");
 
         Ok(Self {
+            server_name: server_name.to_string(),
             child,
             stdin: Some(stdin),
             stdout: Some(stdout_reader),
             stderr_task,
+            stderr_tail,
+            framing: Framing::NewlineDelimited,
         })
     }
 
-    /// Serialize a value to JSON and write it with a newline
+    /// Serialize a value to JSON and write it using the current framing
     async fn write_json(&mut self, value: &Value) -> Result<()> {
         let json = serde_json::to_string(value).context("Failed to serialize JSON")?;
-        let line = format!("{}\n", json);
+        let line = match self.framing {
+            Framing::NewlineDelimited => format!("{}\n", json),
+            Framing::ContentLength => format!("Content-Length: {}\r\n\r\n{}", json.len(), json),
+        };
 
         let stdin = self
             .stdin
             .as_mut()
             .context("Stdin already closed or not available")?;
 
-        stdin
-            .write_all(line.as_bytes())
-            .await
-            .context("Failed to write to stdin")?;
-        stdin.flush().await.context("Failed to flush stdin")?;
+        let written = match stdin.write_all(line.as_bytes()).await {
+            Ok(()) => stdin.flush().await.context("Failed to flush stdin"),
+            Err(e) => Err(e).context("Failed to write to stdin"),
+        };
+        if let Err(e) = written {
+            return Err(self.with_exit_diagnostics(e).await);
+        }
 
         debug!("Sent: {}", json);
         Ok(())
     }
 
-    /// Read a line from stdout and deserialize as JSON
+    /// Read the next message from stdout and deserialize as JSON
     async fn read_json(&mut self) -> Result<Value> {
         let stdout = self
             .stdout
             .as_mut()
             .context("Stdout already closed or not available")?;
 
-        let mut line = String::new();
-        stdout
-            .read_line(&mut line)
+        let (framing, value) = match read_message(stdout).await {
+            Ok(message) => message,
+            Err(e) => return Err(self.with_exit_diagnostics(e).await),
+        };
+        if framing != self.framing {
+            debug!("MCP server uses {:?} framing, switching", framing);
+            self.framing = framing;
+        }
+
+        Ok(value)
+    }
+
+    /// Snapshot of the most recent stderr lines from the server
+    pub fn recent_stderr(&self) -> Vec<String> {
+        self.stderr_tail
+            .lock()
+            .map(|tail| tail.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// If the server process has died, add its exit status and recent stderr
+    /// to an I/O error so the cause is visible to the caller.
+    ///
+    /// Only a closed stream waits (up to a second) for the process to exit;
+    /// other errors just check whether it already has.
+    async fn with_exit_diagnostics(&mut self, err: anyhow::Error) -> anyhow::Error {
+        let status = if is_stream_closed(&err) {
+            match tokio::time::timeout(Duration::from_secs(1), self.child.wait()).await {
+                Ok(Ok(status)) => status,
+                _ => return err,
+            }
+        } else {
+            match self.child.try_wait() {
+                Ok(Some(status)) => status,
+                _ => return err,
+            }
+        };
+        // The process is gone, so stderr hits EOF shortly; let the drain catch up.
+        if let Some(mut task) = self.stderr_task.take() {
+            if tokio::time::timeout(Duration::from_millis(500), &mut task)
+                .await
+                .is_err()
+            {
+                // Still draining (e.g. a grandchild holds the pipe); keep the
+                // handle so close/drop can abort it.
+                self.stderr_task = Some(task);
+            }
+        }
+
+        let stderr = self.recent_stderr();
+        if stderr.is_empty() {
+            err.context(format!(
+                "MCP server '{}' exited with {} (no stderr output)",
+                self.server_name, status
+            ))
+        } else {
+            err.context(format!(
+                "MCP server '{}' exited with {}; recent stderr:\n{}",
+                self.server_name,
+                status,
+                stderr.join("\n")
+            ))
+        }
+    }
+}
+
+/// End-of-stream error for a server that closed its stdout
+fn stdout_closed(message: &'static str) -> anyhow::Error {
+    std::io::Error::new(std::io::ErrorKind::UnexpectedEof, message).into()
+}
+
+/// Whether `err` means the server's pipes are gone (stdout hit EOF or stdin
+/// is broken), which is what a crashed or exited server looks like
+fn is_stream_closed(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause.downcast_ref::<std::io::Error>().is_some_and(|io| {
+            matches!(
+                io.kind(),
+                std::io::ErrorKind::UnexpectedEof | std::io::ErrorKind::BrokenPipe
+            )
+        })
+    })
+}
+
+/// Read one JSON message from `reader`, detecting its framing
+///
+/// Blank lines between messages are skipped. Returns the framing the message
+/// arrived in along with the parsed value.
+pub async fn read_message<R>(reader: &mut R) -> Result<(Framing, Value)>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let first = loop {
+        let buf = reader
+            .fill_buf()
+            .await
+            .context("Failed to read from stdout")?;
+        let Some(&first) = buf.first() else {
+            return Err(stdout_closed("MCP server closed stdout"));
+        };
+        if first.is_ascii_whitespace() {
+            reader.consume(1);
+            continue;
+        }
+        break first;
+    };
+
+    match first {
+        b'{' | b'[' => read_json_lines(reader)
+            .await
+            .map(|value| (Framing::NewlineDelimited, value)),
+        _ => read_content_length(reader)
+            .await
+            .map(|value| (Framing::ContentLength, value)),
+    }
+}
+
+/// Read newline-delimited JSON, continuing across lines while the value is
+/// incomplete so pretty-printed output still parses
+///
+/// Bytes are accumulated until a full line is buffered and only then decoded,
+/// so a message (or a multi-byte UTF-8 character) split across reads of any
+/// size is reassembled intact.
+async fn read_json_lines<R>(reader: &mut R) -> Result<Value>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut bytes = Vec::new();
+    loop {
+        let read = reader
+            .read_until(b'\n', &mut bytes)
+            .await
+            .context("Failed to read from stdout")?;
+        let message = std::str::from_utf8(&bytes).context("MCP message is not valid UTF-8")?;
+
+        match serde_json::from_str::<Value>(message.trim()) {
+            Ok(value) => {
+                debug!("Received: {}", message.trim());
+                return Ok(value);
+            }
+            Err(e) if e.is_eof() && read > 0 && bytes.len() <= MAX_MESSAGE_BYTES => continue,
+            Err(e) => {
+                return Err(e).context(format!("Failed to parse JSON: {}", message.trim()));
+            }
+        }
+    }
+}
+
+/// Read a `Content-Length:` header block followed by exactly that many bytes
+async fn read_content_length<R>(reader: &mut R) -> Result<Value>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut content_length = None;
+    loop {
+        let mut raw = Vec::new();
+        let read = reader
+            .read_until(b'\n', &mut raw)
             .await
             .context("Failed to read from stdout")?;
+        if read == 0 {
+            return Err(stdout_closed(
+                "MCP server closed stdout inside a header block",
+            ));
+        }
 
+        let line = String::from_utf8_lossy(&raw);
         let line = line.trim();
         if line.is_empty() {
-            anyhow::bail!("Received empty line from MCP server");
+            break;
+        }
+        let (name, value) = line
+            .split_once(':')
+            .with_context(|| format!("Malformed MCP message header: {}", line))?;
+        if name.trim().eq_ignore_ascii_case("content-length") {
+            content_length = Some(
+                value
+                    .trim()
+                    .parse::<usize>()
+                    .with_context(|| format!("Invalid Content-Length: {}", value.trim()))?,
+            );
         }
+    }
+
+    let length = content_length.context("MCP message header block has no Content-Length")?;
+    if length > MAX_MESSAGE_BYTES {
+        anyhow::bail!(
+            "MCP message of {} bytes exceeds the {} byte limit",
+            length,
+            MAX_MESSAGE_BYTES
+        );
+    }
 
-        debug!("Received: {}", line);
+    let mut body = vec![0u8; length];
+    reader
+        .read_exact(&mut body)
+        .await
+        .context("Failed to read MCP message body")?;
 
-        let value: Value =
-            serde_json::from_str(line).context(format!("Failed to parse JSON: {}", line))?;
+    debug!("Received: {}", String::from_utf8_lossy(&body));
 
-        Ok(value)
-    }
+    serde_json::from_slice(&body).context(format!(
+        "Failed to parse JSON: {}",
+        String::from_utf8_lossy(&body)
+    ))
 }
 
 #[async_trait]
@@ -181,6 +464,18 @@ impl McpTransport for StdioTransport {
         Ok(response)
     }
 
+    async fn receive_message(&mut self) -> Result<JsonRpcMessage> {
+        debug!("Waiting for JSON-RPC message");
+        let value = self.read_json().await?;
+        JsonRpcMessage::from_value(value).context("Failed to deserialize message")
+    }
+
+    async fn send_response(&mut self, response: &JsonRpcResponse) -> Result<()> {
+        debug!("Sending JSON-RPC response: id={}", response.id);
+        let value = serde_json::to_value(response).context("Failed to serialize response")?;
+        self.write_json(&value).await
+    }
+
     async fn close(&mut self) -> Result<()> {
         info!("Closing MCP transport");
 
@@ -233,6 +528,10 @@ impl McpTransport for StdioTransport {
 }
 
 impl Drop for StdioTransport {
+    /// Best-effort cleanup when the transport is dropped without `close()`:
+    /// kill the child if it is still running. A child that is still running
+    /// when the `Child` handle goes away is reaped by tokio's orphan reaper,
+    /// so no zombie is left behind.
     fn drop(&mut self) {
         // Abort stderr drain task to prevent leaked task
         if let Some(task) = self.stderr_task.take() {
@@ -242,12 +541,129 @@ impl Drop for StdioTransport {
         match self.child.try_wait() {
             Ok(Some(_)) => { /* Process already exited, nothing to do */ }
             _ => {
-                let _ = self.child.start_kill();
+                if let Err(e) = self.child.start_kill() {
+                    debug!(
+                        "MCP [{}] failed to kill server on drop: {}",
+                        self.server_name, e
+                    );
+                }
             }
         }
     }
 }
 
+/// Renders a trace line as text, masking any secrets it carries
+pub type TraceRedactor = fn(&Value) -> String;
+
+/// Transport wrapper that appends every JSON-RPC message to a JSONL trace
+///
+/// Each line records the Unix timestamp in milliseconds, the server name, the
+/// direction (`"send"` or `"receive"`) and the message itself, rendered
+/// through the redactor so credentials in tool arguments or results stay out
+/// of the file. Meant for debugging misbehaving servers; failing to write a
+/// line is logged and otherwise ignored so tracing never breaks the connection.
+pub struct TraceTransport {
+    inner: Box<dyn McpTransport>,
+    server_name: String,
+    file: tokio::fs::File,
+    redact: TraceRedactor,
+}
+
+impl TraceTransport {
+    /// Wrap `inner`, appending its traffic to the file at `path`
+    pub async fn new(
+        inner: Box<dyn McpTransport>,
+        server_name: &str,
+        path: &std::path::Path,
+        redact: TraceRedactor,
+    ) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("Failed to create MCP trace directory {:?}", parent))?;
+        }
+        let file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+            .with_context(|| format!("Failed to open MCP trace file {:?}", path))?;
+        info!("Tracing MCP server '{}' traffic to {:?}", server_name, path);
+        Ok(Self {
+            inner,
+            server_name: server_name.to_string(),
+            file,
+            redact,
+        })
+    }
+
+    async fn record(&mut self, direction: &str, message: Value) {
+        let ts_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis())
+            .unwrap_or_default();
+        let line = serde_json::json!({
+            "ts_ms": ts_ms,
+            "server": self.server_name,
+            "direction": direction,
+            "message": message,
+        });
+        let mut line = (self.redact)(&line);
+        line.push('\n');
+        let written = match self.file.write_all(line.as_bytes()).await {
+            Ok(()) => self.file.flush().await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = written {
+            warn!(
+                "Failed to write MCP trace for server '{}': {}",
+                self.server_name, e
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl McpTransport for TraceTransport {
+    async fn send(&mut self, request: &JsonRpcRequest) -> Result<()> {
+        self.record("send", serde_json::to_value(request)?).await;
+        self.inner.send(request).await
+    }
+
+    async fn send_notification(&mut self, notification: &JsonRpcNotification) -> Result<()> {
+        self.record("send", serde_json::to_value(notification)?)
+            .await;
+        self.inner.send_notification(notification).await
+    }
+
+    async fn receive(&mut self) -> Result<JsonRpcResponse> {
+        let response = self.inner.receive().await?;
+        self.record("receive", serde_json::to_value(&response)?)
+            .await;
+        Ok(response)
+    }
+
+    async fn receive_message(&mut self) -> Result<JsonRpcMessage> {
+        let message = self.inner.receive_message().await?;
+        let value = match &message {
+            JsonRpcMessage::Request(request) => serde_json::to_value(request)?,
+            JsonRpcMessage::Response(response) => serde_json::to_value(response)?,
+            JsonRpcMessage::Notification(notification) => serde_json::to_value(notification)?,
+        };
+        self.record("receive", value).await;
+        Ok(message)
+    }
+
+    async fn send_response(&mut self, response: &JsonRpcResponse) -> Result<()> {
+        self.record("send", serde_json::to_value(response)?).await;
+        self.inner.send_response(response).await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.inner.close().await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -478,6 +894,35 @@ done
         }
     }
 
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_stdio_transport_drop_kills_process() {
+        let transport = StdioTransport::new("sleep", &["60".to_string()], &HashMap::new())
+            .await
+            .unwrap();
+        let pid = transport.child.id().expect("child is running");
+        drop(transport);
+
+        // The process must disappear, or at least be a zombie awaiting the
+        // runtime's reaper, shortly after the drop.
+        let stat_path = format!("/proc/{pid}/stat");
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        loop {
+            let exited = std::fs::read_to_string(&stat_path).map_or(true, |stat| {
+                stat.rsplit_once(") ")
+                    .is_some_and(|(_, rest)| rest.starts_with(['Z', 'X']))
+            });
+            if exited {
+                break;
+            }
+            assert!(
+                std::time::Instant::now() < deadline,
+                "MCP child {pid} still running after drop"
+            );
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+
     #[tokio::test]
     async fn test_newline_delimited_framing() {
         // Test that JSON is properly framed with newlines
@@ -495,4 +940,311 @@ done
         assert_eq!(parsed["jsonrpc"], "2.0");
         assert_eq!(parsed["id"], 1);
     }
+
+    #[tokio::test]
+    async fn test_read_message_newline_delimited() {
+        let mut input: &[u8] = b"{\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{}}\n\n{\"id\":2}\n";
+
+        let (framing, first) = read_message(&mut input).await.unwrap();
+        assert_eq!(framing, Framing::NewlineDelimited);
+        assert_eq!(first["id"], 1);
+
+        let (_, second) = read_message(&mut input).await.unwrap();
+        assert_eq!(second["id"], 2);
+    }
+
+    /// Yields one byte per read, returning `Pending` between bytes like a
+    /// pipe that delivers data in tiny chunks.
+    struct TrickleReader {
+        data: Vec<u8>,
+        pos: usize,
+        ready: bool,
+    }
+
+    impl tokio::io::AsyncRead for TrickleReader {
+        fn poll_read(
+            mut self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+            buf: &mut tokio::io::ReadBuf<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            if !self.ready {
+                self.ready = true;
+                cx.waker().wake_by_ref();
+                return std::task::Poll::Pending;
+            }
+            self.ready = false;
+            if let Some(&byte) = self.data.get(self.pos) {
+                buf.put_slice(&[byte]);
+                self.pos += 1;
+            }
+            std::task::Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_message_reassembles_byte_by_byte_input() {
+        let body = r#"{"jsonrpc":"2.0","id":5,"result":{"text":"héllo 世界 🦀"}}"#;
+        let data = format!(
+            "{{\"jsonrpc\":\"2.0\",\"id\":4,\"result\":{{\"text\":\"ünïcödé 日本\"}}}}\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let mut input = BufReader::new(TrickleReader {
+            data: data.into_bytes(),
+            pos: 0,
+            ready: false,
+        });
+
+        let (framing, first) = read_message(&mut input).await.unwrap();
+        assert_eq!(framing, Framing::NewlineDelimited);
+        assert_eq!(first["result"]["text"], "ünïcödé 日本");
+
+        let (framing, second) = read_message(&mut input).await.unwrap();
+        assert_eq!(framing, Framing::ContentLength);
+        assert_eq!(second["result"]["text"], "héllo 世界 🦀");
+
+        assert!(read_message(&mut input).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_message_pretty_printed_json() {
+        let pretty = serde_json::to_string_pretty(&json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "result": {"tools": [{"name": "echo"}]}
+        }))
+        .unwrap();
+        let data = format!("{}\n{{\"id\":8}}\n", pretty);
+        let mut input = data.as_bytes();
+
+        let (framing, value) = read_message(&mut input).await.unwrap();
+        assert_eq!(framing, Framing::NewlineDelimited);
+        assert_eq!(value["result"]["tools"][0]["name"], "echo");
+
+        let (_, next) = read_message(&mut input).await.unwrap();
+        assert_eq!(next["id"], 8);
+    }
+
+    #[tokio::test]
+    async fn test_read_message_content_length() {
+        let body = r#"{"jsonrpc":"2.0","id":3,"result":{"ok":true}}"#;
+        let data = format!(
+            "Content-Length: {}\r\nContent-Type: application/json\r\n\r\n{}content-length: 8\r\n\r\n{{\"id\":4}}",
+            body.len(),
+            body
+        );
+        let mut input = data.as_bytes();
+
+        let (framing, first) = read_message(&mut input).await.unwrap();
+        assert_eq!(framing, Framing::ContentLength);
+        assert_eq!(first["result"]["ok"], true);
+
+        let (_, second) = read_message(&mut input).await.unwrap();
+        assert_eq!(second["id"], 4);
+    }
+
+    #[tokio::test]
+    async fn test_read_message_rejects_bad_frames() {
+        let mut missing_length: &[u8] = b"Content-Type: application/json\r\n\r\n{}";
+        let err = read_message(&mut missing_length).await.unwrap_err();
+        assert!(err.to_string().contains("no Content-Length"));
+
+        let mut oversized: &[u8] = b"Content-Length: 999999999999\r\n\r\n";
+        let err = read_message(&mut oversized).await.unwrap_err();
+        assert!(err.to_string().contains("exceeds"));
+
+        let mut truncated: &[u8] = b"{\"id\":";
+        assert!(read_message(&mut truncated).await.is_err());
+
+        let mut empty: &[u8] = b"\n\n";
+        let err = read_message(&mut empty).await.unwrap_err();
+        assert!(err.to_string().contains("closed stdout"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_dead_server_error_includes_stderr() {
+        let args = vec![
+            "-c".to_string(),
+            "echo 'starting up' >&2; echo 'fatal: missing API token' >&2; exit 3".to_string(),
+        ];
+        let config = McpServerConfig {
+            name: "github".to_string(),
+            command: "sh".to_string(),
+            args,
+            env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
+        };
+        let mut transport = StdioTransport::from_config(&config).await.unwrap();
+
+        let err = transport.receive().await.unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(
+            message.contains("MCP server 'github' exited"),
+            "{}",
+            message
+        );
+        assert!(message.contains("fatal: missing API token"), "{}", message);
+        assert_eq!(
+            transport.recent_stderr(),
+            vec!["starting up", "fatal: missing API token"]
+        );
+
+        // A second failure on the same transport reports the exit again.
+        let err = transport.receive().await.unwrap_err();
+        assert!(format!("{:#}", err).contains("exited"), "{:#}", err);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_malformed_output_does_not_wait_for_exit() {
+        let args = vec!["-c".to_string(), "echo 'not json'; sleep 5".to_string()];
+        let config = McpServerConfig {
+            name: "noisy".to_string(),
+            command: "sh".to_string(),
+            args,
+            env: HashMap::new(),
+            max_output_chars: None,
+            inherit_env: true,
+            env_allowlist: vec![],
+        };
+        let mut transport = StdioTransport::from_config(&config).await.unwrap();
+
+        let started = std::time::Instant::now();
+        let err = transport.receive().await.unwrap_err();
+        assert!(started.elapsed() < Duration::from_millis(500));
+        assert!(!format!("{:#}", err).contains("exited"), "{:#}", err);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_env_policy_limits_child_environment() {
+        std::env::set_var("ZEROCLAW_MCP_TEST_SECRET", "hunter2");
+        std::env::set_var("ZEROCLAW_MCP_TEST_ALLOWED", "visible");
+
+        let script = r#"printf '{"secret":"%s","allowed":"%s","explicit":"%s"}\n' "${ZEROCLAW_MCP_TEST_SECRET-unset}" "${ZEROCLAW_MCP_TEST_ALLOWED-unset}" "${EXPLICIT-unset}"; cat >/dev/null"#;
+        let mut config = McpServerConfig {
+            name: "envtest".to_string(),
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            env: HashMap::from([("EXPLICIT".to_string(), "set".to_string())]),
+            max_output_chars: None,
+            inherit_env: false,
+            env_allowlist: vec!["ZEROCLAW_MCP_TEST_ALLOWED".to_string()],
+        };
+
+        let mut transport = StdioTransport::from_config(&config).await.unwrap();
+        let seen = transport.read_json().await.unwrap();
+        transport.close().await.unwrap();
+        assert_eq!(
+            seen,
+            json!({"secret": "unset", "allowed": "visible", "explicit": "set"})
+        );
+
+        config.inherit_env = true;
+        let mut transport = StdioTransport::from_config(&config).await.unwrap();
+        let seen = transport.read_json().await.unwrap();
+        transport.close().await.unwrap();
+        assert_eq!(seen["secret"], "hunter2");
+    }
+
+    #[cfg(unix)]
+    async fn spawn_printf(output: &str) -> StdioTransport {
+        let args = vec![
+            "-c".to_string(),
+            "printf '%s' \"$1\"; cat >/dev/null".to_string(),
+            "sh".to_string(),
+            output.to_string(),
+        ];
+        StdioTransport::new("sh", &args, &HashMap::new())
+            .await
+            .unwrap()
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_trace_transport_records_initialize_exchange() {
+        let init_response = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "serverInfo": {"name": "TraceServer", "version": "1.0.0"},
+                "instructions": "authenticate with s3cret"
+            }
+        });
+        let inner = spawn_printf(&format!("{}\n", init_response)).await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("traces").join("trace-test.jsonl");
+        // Stand-in for the host's secret masking
+        let redact: TraceRedactor = |line| line.to_string().replace("s3cret", "[REDACTED]");
+        let transport = TraceTransport::new(Box::new(inner), "trace-test", &path, redact)
+            .await
+            .unwrap();
+
+        let mut client = crate::client::McpClient::connect(Box::new(transport))
+            .await
+            .unwrap();
+        client.close().await.unwrap();
+
+        let trace = std::fs::read_to_string(&path).unwrap();
+        assert!(!trace.contains("s3cret"));
+        let lines: Vec<Value> = trace
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let exchange: Vec<(&str, &str)> = lines
+            .iter()
+            .map(|line| {
+                let message = &line["message"];
+                let label = message["method"].as_str().unwrap_or("response");
+                (line["direction"].as_str().unwrap(), label)
+            })
+            .collect();
+        assert_eq!(
+            exchange,
+            vec![
+                ("send", "initialize"),
+                ("receive", "response"),
+                ("send", "notifications/initialized"),
+            ]
+        );
+        assert!(lines.iter().all(|line| line["server"] == "trace-test"));
+        assert!(lines.iter().all(|line| line["ts_ms"].as_u64().unwrap() > 0));
+        assert_eq!(
+            lines[1]["message"]["result"]["serverInfo"]["name"],
+            "TraceServer"
+        );
+        assert_eq!(
+            lines[1]["message"]["result"]["instructions"],
+            "authenticate with [REDACTED]"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_receive_detects_both_framings() {
+        let pretty = serde_json::to_string_pretty(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {"protocolVersion": "2024-11-05"}
+        }))
+        .unwrap();
+        let mut transport = spawn_printf(&format!("{}\n", pretty)).await;
+        let response = transport.receive().await.unwrap();
+        assert_eq!(response.id, crate::jsonrpc::RequestId::Number(1));
+        assert_eq!(transport.framing, Framing::NewlineDelimited);
+        transport.close().await.unwrap();
+
+        let body = r#"{"jsonrpc":"2.0","id":2,"result":{}}"#;
+        let mut transport =
+            spawn_printf(&format!("Content-Length: {}\r\n\r\n{}", body.len(), body)).await;
+        let response = transport.receive().await.unwrap();
+        assert_eq!(response.id, crate::jsonrpc::RequestId::Number(2));
+        assert_eq!(transport.framing, Framing::ContentLength);
+        transport.close().await.unwrap();
+    }
 }
@@ -1,4 +1,8 @@
 //! MCP-specific message types and structures
+//!
+//! Parsing is lenient by default: fields a server sends that are not modelled
+//! here are ignored. Build with the `strict` feature to reject unknown fields
+//! on the core types and surface protocol drift during development.
 
 use serde::{Deserialize, Serialize};
 
@@ -28,10 +32,36 @@ pub struct ServerCapabilities {
     pub resources: Option<ResourcesCapability>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prompts: Option<PromptsCapability>,
+    /// Non-standard features under `capabilities.experimental`, keyed by
+    /// feature name
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub experimental: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ServerCapabilities {
+    /// Settings the server advertised for experimental feature `name`
+    pub fn experimental_feature(&self, name: &str) -> Option<&serde_json::Value> {
+        self.experimental.get(name)
+    }
+
+    /// Whether the server advertises experimental feature `name`
+    ///
+    /// Any value other than `false` or `null` counts as enabled, since servers
+    /// advertise features both as `true` and as (possibly empty) settings
+    /// objects.
+    pub fn has_experimental(&self, name: &str) -> bool {
+        self.experimental_feature(name).is_some_and(|value| {
+            !matches!(
+                value,
+                serde_json::Value::Null | serde_json::Value::Bool(false)
+            )
+        })
+    }
 }
 
 /// Implementation info (name and version)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Implementation {
     pub name: String,
     pub version: String,
@@ -49,6 +79,7 @@ pub struct InitializeParams {
 /// Initialize response result
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct InitializeResult {
     pub protocol_version: String,
     pub capabilities: ServerCapabilities,
@@ -58,6 +89,7 @@ pub struct InitializeResult {
 /// MCP tool information
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct McpToolInfo {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -65,9 +97,96 @@ pub struct McpToolInfo {
     pub input_schema: serde_json::Value,
 }
 
+impl McpToolInfo {
+    /// Create a tool with no description and an empty object input schema
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            input_schema: serde_json::json!({"type": "object"}),
+        }
+    }
+
+    /// Set the tool description
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the JSON Schema for the tool's arguments
+    pub fn with_schema(mut self, input_schema: serde_json::Value) -> Self {
+        self.input_schema = input_schema;
+        self
+    }
+
+    /// Top-level arguments declared by `inputSchema`
+    ///
+    /// Handles the common `{"type": "object", "properties": {...},
+    /// "required": [...]}` shape; nested schemas are not expanded. Required
+    /// arguments come first, each group sorted by name. Schemas without
+    /// `properties` yield no arguments.
+    pub fn arg_specs(&self) -> Vec<ArgSpec> {
+        let Some(properties) = self
+            .input_schema
+            .get("properties")
+            .and_then(serde_json::Value::as_object)
+        else {
+            return Vec::new();
+        };
+        let required: Vec<&str> = self
+            .input_schema
+            .get("required")
+            .and_then(serde_json::Value::as_array)
+            .map(|names| names.iter().filter_map(serde_json::Value::as_str).collect())
+            .unwrap_or_default();
+
+        let mut specs: Vec<ArgSpec> = properties
+            .iter()
+            .map(|(name, schema)| ArgSpec {
+                name: name.clone(),
+                arg_type: schema_type(schema),
+                required: required.contains(&name.as_str()),
+                description: schema
+                    .get("description")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::to_string),
+            })
+            .collect();
+        specs.sort_by(|a, b| {
+            b.required
+                .cmp(&a.required)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        specs
+    }
+}
+
+/// One argument of an MCP tool, as declared in its input schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgSpec {
+    pub name: String,
+    /// JSON Schema `type`; union types are joined with `|` (e.g.
+    /// `"string|null"`). `None` when the schema does not declare one.
+    pub arg_type: Option<String>,
+    pub required: bool,
+    pub description: Option<String>,
+}
+
+fn schema_type(schema: &serde_json::Value) -> Option<String> {
+    match schema.get("type")? {
+        serde_json::Value::String(name) => Some(name.clone()),
+        serde_json::Value::Array(names) => {
+            let names: Vec<&str> = names.iter().filter_map(serde_json::Value::as_str).collect();
+            (!names.is_empty()).then(|| names.join("|"))
+        }
+        _ => None,
+    }
+}
+
 /// MCP tool call parameters
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct McpToolCallParams {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -77,6 +196,7 @@ pub struct McpToolCallParams {
 /// MCP tool call result
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct McpToolCallResult {
     pub content: Vec<McpContent>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -84,19 +204,154 @@ pub struct McpToolCallResult {
     pub is_error: Option<bool>,
 }
 
-/// MCP content (text type)
+impl McpToolCallResult {
+    /// Create an empty result with no content and no error flag
+    pub fn new() -> Self {
+        Self {
+            content: Vec::new(),
+            is_error: None,
+        }
+    }
+
+    /// Append a text content block
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.content.push(McpContent::text(text));
+        self
+    }
+
+    /// Set the `isError` flag
+    pub fn with_error(mut self, is_error: bool) -> Self {
+        self.is_error = Some(is_error);
+        self
+    }
+
+    /// All text content blocks, newline-joined (empty when there are none)
+    pub fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|c| c.text.as_deref())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The `image` content blocks, in order
+    pub fn images(&self) -> Vec<&McpContent> {
+        self.content
+            .iter()
+            .filter(|c| c.content_type == "image")
+            .collect()
+    }
+
+    /// Whether the server flagged the call as failed (`isError: true`)
+    pub fn is_error(&self) -> bool {
+        self.is_error == Some(true)
+    }
+
+    /// Total number of characters across all text content blocks
+    pub fn text_len(&self) -> usize {
+        self.content
+            .iter()
+            .filter_map(|c| c.text.as_deref())
+            .map(|t| t.chars().count())
+            .sum()
+    }
+
+    /// Cap text content at roughly `max_chars`, keeping the head and tail
+    ///
+    /// When the combined text exceeds the limit, all text blocks are merged
+    /// (newline-joined, as the agent bridge presents them) into one block
+    /// holding the first and last halves of the budget around an elision
+    /// notice. Non-text blocks are kept as-is. Returns whether anything was
+    /// cut. A `max_chars` of 0 disables truncation.
+    pub fn truncate_text(&mut self, max_chars: usize) -> bool {
+        if max_chars == 0 || self.text_len() <= max_chars {
+            return false;
+        }
+
+        let joined = self
+            .content
+            .iter()
+            .filter_map(|c| c.text.as_deref())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let total = joined.chars().count();
+        let head_chars = max_chars / 2;
+        let tail_chars = max_chars - head_chars;
+
+        let head: String = joined.chars().take(head_chars).collect();
+        let tail: String = joined.chars().skip(total - tail_chars).collect();
+        let notice = format!(
+            "\n\n[... {} characters omitted: output exceeded the {} character limit ...]\n\n",
+            total - head_chars - tail_chars,
+            max_chars
+        );
+
+        let first_text = self.content.iter().position(|c| c.text.is_some());
+        let mut merged = McpContent::text(format!("{head}{notice}{tail}"));
+        if let Some(index) = first_text {
+            merged.content_type = self.content[index].content_type.clone();
+        }
+        self.content.retain(|c| c.text.is_none());
+        self.content
+            .insert(first_text.unwrap_or(0).min(self.content.len()), merged);
+        true
+    }
+}
+
+impl Default for McpToolCallResult {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// MCP content block (`text`, `image` or embedded `resource`)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct McpContent {
     #[serde(rename = "type")]
     pub content_type: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
+    /// Base64 payload of an `image` block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+    /// MIME type of an `image` block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Embedded contents of a `resource` block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource: Option<McpResourceContent>,
+}
+
+impl McpContent {
+    /// Create a `text` content block
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            content_type: "text".to_string(),
+            text: Some(text.into()),
+            data: None,
+            mime_type: None,
+            resource: None,
+        }
+    }
+
+    /// Create an `image` content block from base64 data
+    pub fn image(data: impl Into<String>, mime_type: impl Into<String>) -> Self {
+        Self {
+            content_type: "image".to_string(),
+            text: None,
+            data: Some(data.into()),
+            mime_type: Some(mime_type.into()),
+            resource: None,
+        }
+    }
 }
 
 /// MCP resource
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct McpResource {
     pub uri: String,
     pub name: String,
@@ -109,17 +364,22 @@ pub struct McpResource {
 /// MCP resource content
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct McpResourceContent {
     pub uri: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mime_type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
+    /// Base64 payload of a binary resource
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blob: Option<String>,
 }
 
 /// MCP prompt argument
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct McpPromptArgument {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -131,6 +391,7 @@ pub struct McpPromptArgument {
 /// MCP prompt
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct McpPrompt {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -142,6 +403,7 @@ pub struct McpPrompt {
 /// MCP prompt message
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct McpPromptMessage {
     pub role: String,
     pub content: McpContent,
@@ -152,6 +414,66 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn test_arg_specs_from_object_schema() {
+        let tool = McpToolInfo::new("search").with_schema(json!({
+            "type": "object",
+            "properties": {
+                "query": {"type": "string", "description": "Search terms"},
+                "limit": {"type": "integer"},
+                "cursor": {"type": ["string", "null"], "description": "Page token"},
+                "filters": {"type": "object", "properties": {"lang": {"type": "string"}}},
+                "raw": {}
+            },
+            "required": ["query", "filters"]
+        }));
+
+        assert_eq!(
+            tool.arg_specs(),
+            vec![
+                ArgSpec {
+                    name: "filters".to_string(),
+                    arg_type: Some("object".to_string()),
+                    required: true,
+                    description: None,
+                },
+                ArgSpec {
+                    name: "query".to_string(),
+                    arg_type: Some("string".to_string()),
+                    required: true,
+                    description: Some("Search terms".to_string()),
+                },
+                ArgSpec {
+                    name: "cursor".to_string(),
+                    arg_type: Some("string|null".to_string()),
+                    required: false,
+                    description: Some("Page token".to_string()),
+                },
+                ArgSpec {
+                    name: "limit".to_string(),
+                    arg_type: Some("integer".to_string()),
+                    required: false,
+                    description: None,
+                },
+                ArgSpec {
+                    name: "raw".to_string(),
+                    arg_type: None,
+                    required: false,
+                    description: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_arg_specs_without_properties() {
+        assert!(McpToolInfo::new("noop").arg_specs().is_empty());
+        assert!(McpToolInfo::new("odd")
+            .with_schema(json!({"type": "object", "properties": []}))
+            .arg_specs()
+            .is_empty());
+    }
+
     #[test]
     fn test_implementation_roundtrip() {
         let impl_info = Implementation {
@@ -191,6 +513,7 @@ mod tests {
                 tools: Some(ToolsCapability::default()),
                 resources: None,
                 prompts: Some(PromptsCapability::default()),
+                experimental: serde_json::Map::new(),
             },
             server_info: Implementation {
                 name: "ZeroClaw MCP Server".to_string(),
@@ -228,6 +551,35 @@ mod tests {
         assert!(deserialized.description.is_some());
     }
 
+    #[test]
+    fn test_mcp_tool_info_builder_roundtrip() {
+        let tool_info = McpToolInfo::new("file_read")
+            .with_description("Read a file from the filesystem")
+            .with_schema(json!({
+                "type": "object",
+                "properties": {"path": {"type": "string"}},
+                "required": ["path"]
+            }));
+
+        let serialized = serde_json::to_value(&tool_info).unwrap();
+        assert_eq!(serialized["name"], "file_read");
+        assert_eq!(serialized["inputSchema"]["required"], json!(["path"]));
+
+        let deserialized: McpToolInfo = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, tool_info);
+    }
+
+    #[test]
+    fn test_mcp_tool_info_builder_defaults() {
+        let tool_info = McpToolInfo::new("ping");
+
+        let serialized = serde_json::to_value(&tool_info).unwrap();
+        assert_eq!(
+            serialized,
+            json!({"name": "ping", "inputSchema": {"type": "object"}})
+        );
+    }
+
     #[test]
     fn test_mcp_tool_call_params_roundtrip() {
         let params = McpToolCallParams {
@@ -247,6 +599,9 @@ mod tests {
             content: vec![McpContent {
                 content_type: "text".to_string(),
                 text: Some("file1.txt\nfile2.txt".to_string()),
+                data: None,
+                mime_type: None,
+                resource: None,
             }],
             is_error: Some(false),
         };
@@ -258,11 +613,152 @@ mod tests {
         assert_eq!(deserialized.content.len(), 1);
     }
 
+    #[test]
+    fn test_mcp_tool_call_result_builder_roundtrip() {
+        let result = McpToolCallResult::new()
+            .with_text("permission denied")
+            .with_text("path: /etc/shadow")
+            .with_error(true);
+
+        let serialized = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            serialized,
+            json!({
+                "content": [
+                    {"type": "text", "text": "permission denied"},
+                    {"type": "text", "text": "path: /etc/shadow"}
+                ],
+                "isError": true
+            })
+        );
+
+        let deserialized: McpToolCallResult = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, result);
+    }
+
+    #[test]
+    fn test_mcp_tool_call_result_builder_omits_unset_error() {
+        let serialized = serde_json::to_value(McpToolCallResult::new().with_text("ok")).unwrap();
+        assert!(serialized.get("isError").is_none());
+    }
+
+    fn mixed_content_result() -> McpToolCallResult {
+        let mut result = McpToolCallResult::new().with_text("first");
+        result.content.push(McpContent::image("aGk=", "image/png"));
+        result.content.push(McpContent {
+            content_type: "resource".to_string(),
+            text: None,
+            data: None,
+            mime_type: None,
+            resource: Some(McpResourceContent {
+                uri: "file:///a.txt".to_string(),
+                mime_type: None,
+                text: Some("embedded".to_string()),
+                blob: None,
+            }),
+        });
+        result.with_text("second")
+    }
+
+    #[test]
+    fn test_text_joins_text_blocks_only() {
+        assert_eq!(mixed_content_result().text(), "first\nsecond");
+        assert_eq!(McpToolCallResult::new().text(), "");
+    }
+
+    #[test]
+    fn test_images_returns_image_blocks() {
+        let result = mixed_content_result();
+        let images = result.images();
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].data.as_deref(), Some("aGk="));
+        assert_eq!(images[0].mime_type.as_deref(), Some("image/png"));
+        assert!(McpToolCallResult::new().with_text("x").images().is_empty());
+    }
+
+    #[test]
+    fn test_is_error_only_when_flag_is_true() {
+        assert!(!mixed_content_result().is_error());
+        assert!(!mixed_content_result().with_error(false).is_error());
+        assert!(mixed_content_result().with_error(true).is_error());
+    }
+
+    #[test]
+    fn test_truncate_text_passes_small_results_through() {
+        let mut result = McpToolCallResult::new()
+            .with_text("short")
+            .with_text("output");
+        let original = result.clone();
+
+        assert!(!result.truncate_text(100));
+        assert_eq!(result, original);
+        assert!(!result.truncate_text(0));
+    }
+
+    #[test]
+    fn test_truncate_text_keeps_head_and_tail() {
+        let text = format!(
+            "{}{}{}",
+            "a".repeat(600),
+            "b".repeat(5_000),
+            "z".repeat(600)
+        );
+        let mut result = McpToolCallResult::new().with_text(text).with_error(false);
+
+        assert!(result.truncate_text(1_000));
+        assert_eq!(result.content.len(), 1);
+        let truncated = result.content[0].text.as_deref().unwrap();
+        assert!(truncated.starts_with(&"a".repeat(500)));
+        assert!(truncated.ends_with(&"z".repeat(500)));
+        assert!(truncated.contains("[... 5200 characters omitted"));
+        assert!(!truncated.contains('b'));
+        assert_eq!(result.is_error, Some(false));
+    }
+
+    #[test]
+    fn test_truncate_text_merges_blocks_and_keeps_other_content() {
+        let mut result = McpToolCallResult {
+            content: vec![
+                McpContent {
+                    content_type: "image".to_string(),
+                    text: None,
+                    data: None,
+                    mime_type: None,
+                    resource: None,
+                },
+                McpContent::text("x".repeat(80)),
+                McpContent::text("y".repeat(80)),
+            ],
+            is_error: None,
+        };
+
+        assert!(result.truncate_text(100));
+        assert_eq!(result.content.len(), 2);
+        assert_eq!(result.content[0].content_type, "image");
+        let truncated = result.content[1].text.as_deref().unwrap();
+        assert!(truncated.starts_with(&"x".repeat(50)));
+        assert!(truncated.ends_with(&"y".repeat(50)));
+        assert!(truncated.contains("61 characters omitted"));
+    }
+
+    #[test]
+    fn test_truncate_text_respects_char_boundaries() {
+        let mut result = McpToolCallResult::new().with_text("é".repeat(50));
+
+        assert!(result.truncate_text(10));
+        let truncated = result.content[0].text.as_deref().unwrap();
+        assert!(truncated.starts_with("ééééé\n"));
+        assert!(truncated.ends_with("\nééééé"));
+    }
+
     #[test]
     fn test_mcp_content_roundtrip() {
         let content = McpContent {
             content_type: "text".to_string(),
             text: Some("Hello, MCP!".to_string()),
+            data: None,
+            mime_type: None,
+            resource: None,
         };
 
         let serialized = serde_json::to_string(&content).unwrap();
@@ -271,6 +767,30 @@ mod tests {
         assert_eq!(deserialized, content);
     }
 
+    #[test]
+    fn test_image_and_resource_blocks_parse() {
+        let result: McpToolCallResult = serde_json::from_value(json!({
+            "content": [
+                {"type": "image", "data": "aGVsbG8=", "mimeType": "image/png"},
+                {"type": "resource", "resource": {
+                    "uri": "file:///tmp/chart.png",
+                    "mimeType": "image/png",
+                    "blob": "aGVsbG8="
+                }}
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            result.content[0],
+            McpContent::image("aGVsbG8=", "image/png")
+        );
+        let resource = result.content[1].resource.as_ref().unwrap();
+        assert_eq!(resource.blob.as_deref(), Some("aGVsbG8="));
+        assert_eq!(resource.mime_type.as_deref(), Some("image/png"));
+        assert_eq!(result.text_len(), 0);
+    }
+
     #[test]
     fn test_mcp_resource_roundtrip() {
         let resource = McpResource {
@@ -292,6 +812,7 @@ mod tests {
             uri: "file:///tmp/data.json".to_string(),
             mime_type: Some("application/json".to_string()),
             text: Some(r#"{"key": "value"}"#.to_string()),
+            blob: None,
         };
 
         let serialized = serde_json::to_string(&content).unwrap();
@@ -326,6 +847,9 @@ mod tests {
             content: McpContent {
                 content_type: "text".to_string(),
                 text: Some("Review this code".to_string()),
+                data: None,
+                mime_type: None,
+                resource: None,
             },
         };
 
@@ -335,12 +859,73 @@ mod tests {
         assert_eq!(deserialized, message);
     }
 
+    #[test]
+    fn test_tool_info_wire_format_is_stable() {
+        let tool_info = McpToolInfo::new("file_read")
+            .with_description("Read a file")
+            .with_schema(json!({"type": "object", "required": ["path"]}));
+
+        assert_eq!(
+            serde_json::to_string(&tool_info).unwrap(),
+            r#"{"name":"file_read","description":"Read a file","inputSchema":{"required":["path"],"type":"object"}}"#
+        );
+    }
+
+    #[test]
+    fn test_tool_call_result_wire_format_is_stable() {
+        let result = McpToolCallResult::new().with_text("done").with_error(false);
+
+        assert_eq!(
+            serde_json::to_string(&result).unwrap(),
+            r#"{"content":[{"type":"text","text":"done"}],"isError":false}"#
+        );
+    }
+
+    fn tool_info_with_extra_field() -> serde_json::Value {
+        json!({
+            "name": "file_read",
+            "inputSchema": {"type": "object"},
+            "annotations": {"readOnlyHint": true}
+        })
+    }
+
+    #[cfg(not(feature = "strict"))]
+    #[test]
+    fn test_lenient_mode_ignores_unknown_fields() {
+        let tool_info: McpToolInfo = serde_json::from_value(tool_info_with_extra_field()).unwrap();
+        assert_eq!(tool_info, McpToolInfo::new("file_read"));
+
+        let result: McpToolCallResult = serde_json::from_value(json!({
+            "content": [{"type": "text", "text": "ok", "annotations": {}}],
+            "structuredContent": {"ok": true}
+        }))
+        .unwrap();
+        assert_eq!(result, McpToolCallResult::new().with_text("ok"));
+    }
+
+    #[cfg(feature = "strict")]
+    #[test]
+    fn test_strict_mode_rejects_unknown_fields() {
+        let err = serde_json::from_value::<McpToolInfo>(tool_info_with_extra_field()).unwrap_err();
+        assert!(err.to_string().contains("unknown field `annotations`"));
+
+        let err = serde_json::from_value::<McpToolCallResult>(json!({
+            "content": [],
+            "structuredContent": {"ok": true}
+        }))
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("unknown field `structuredContent`"));
+    }
+
     #[test]
     fn test_server_capabilities_serialization() {
         let capabilities = ServerCapabilities {
             tools: Some(ToolsCapability::default()),
             resources: Some(ResourcesCapability::default()),
             prompts: None,
+            experimental: serde_json::Map::new(),
         };
 
         let serialized = serde_json::to_string(&capabilities).unwrap();
@@ -349,5 +934,33 @@ mod tests {
         assert!(serialized.contains("tools"));
         assert!(serialized.contains("resources"));
         assert!(!serialized.contains("prompts"));
+        assert!(!serialized.contains("experimental"));
+    }
+
+    #[test]
+    fn test_initialize_result_experimental_capabilities() {
+        let result: InitializeResult = serde_json::from_value(serde_json::json!({
+            "protocolVersion": "2024-11-05",
+            "capabilities": {
+                "tools": {},
+                "experimental": {
+                    "streaming": {"chunkSize": 4096},
+                    "batching": true,
+                    "sampling": false
+                }
+            },
+            "serverInfo": {"name": "Exp", "version": "1.0.0"}
+        }))
+        .unwrap();
+
+        let capabilities = &result.capabilities;
+        assert!(capabilities.has_experimental("streaming"));
+        assert!(capabilities.has_experimental("batching"));
+        assert!(!capabilities.has_experimental("sampling"));
+        assert!(!capabilities.has_experimental("missing"));
+        assert_eq!(
+            capabilities.experimental_feature("streaming").unwrap()["chunkSize"],
+            4096
+        );
     }
 }
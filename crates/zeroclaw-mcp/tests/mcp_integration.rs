@@ -97,6 +97,9 @@ async fn test_registry_creation_with_tool_cap() {
         command: "test".to_string(),
         args: vec![],
         env: HashMap::new(),
+        max_output_chars: None,
+        inherit_env: true,
+        env_allowlist: vec![],
     };
 
     let tools = registry
@@ -124,6 +127,9 @@ async fn test_tool_cap_enforced() {
         command: "test".to_string(),
         args: vec![],
         env: HashMap::new(),
+        max_output_chars: None,
+        inherit_env: true,
+        env_allowlist: vec![],
     };
 
     let result = registry
@@ -155,6 +161,9 @@ async fn test_tool_name_collision_rejected() {
         command: "test".to_string(),
         args: vec![],
         env: HashMap::new(),
+        max_output_chars: None,
+        inherit_env: true,
+        env_allowlist: vec![],
     };
 
     let result = registry
@@ -179,6 +188,9 @@ async fn test_registry_list_servers() {
         command: "test1".to_string(),
         args: vec![],
         env: HashMap::new(),
+        max_output_chars: None,
+        inherit_env: true,
+        env_allowlist: vec![],
     };
     registry
         .add_server_with_client("server1".to_string(), client1, config1)
@@ -195,6 +207,9 @@ async fn test_registry_list_servers() {
         command: "test2".to_string(),
         args: vec![],
         env: HashMap::new(),
+        max_output_chars: None,
+        inherit_env: true,
+        env_allowlist: vec![],
     };
     registry
         .add_server_with_client("server2".to_string(), client2, config2)
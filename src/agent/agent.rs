@@ -33,7 +33,7 @@ pub struct Agent {
     identity_config: crate::config::IdentityConfig,
     skills: Vec<crate::skills::Skill>,
     allowed_commands: Vec<String>,
-    skills_prompt_mode: crate::config::SkillsPromptInjectionMode,
+    skills_config: crate::config::SkillsConfig,
     auto_save: bool,
     history: Vec<ConversationMessage>,
     classification_config: crate::config::QueryClassificationConfig,
@@ -67,7 +67,7 @@ pub struct AgentBuilder {
     identity_config: Option<crate::config::IdentityConfig>,
     skills: Option<Vec<crate::skills::Skill>>,
     allowed_commands: Vec<String>,
-    skills_prompt_mode: Option<crate::config::SkillsPromptInjectionMode>,
+    skills_config: Option<crate::config::SkillsConfig>,
     auto_save: Option<bool>,
     classification_config: Option<crate::config::QueryClassificationConfig>,
     available_hints: Option<Vec<String>>,
@@ -98,7 +98,7 @@ impl AgentBuilder {
             identity_config: None,
             skills: None,
             allowed_commands: Vec::new(),
-            skills_prompt_mode: None,
+            skills_config: None,
             auto_save: None,
             classification_config: None,
             available_hints: None,
@@ -183,11 +183,8 @@ impl AgentBuilder {
     }
 
 
-    pub fn skills_prompt_mode(
-        mut self,
-        skills_prompt_mode: crate::config::SkillsPromptInjectionMode,
-    ) -> Self {
-        self.skills_prompt_mode = Some(skills_prompt_mode);
+    pub fn skills_config(mut self, skills_config: crate::config::SkillsConfig) -> Self {
+        self.skills_config = Some(skills_config);
         self
     }
 
@@ -278,7 +275,7 @@ impl AgentBuilder {
             identity_config: self.identity_config.unwrap_or_default(),
             skills: self.skills.unwrap_or_default(),
             allowed_commands: self.allowed_commands,
-            skills_prompt_mode: self.skills_prompt_mode.unwrap_or_default(),
+            skills_config: self.skills_config.unwrap_or_default(),
             auto_save: self.auto_save.unwrap_or(false),
             history: Vec::new(),
             classification_config: self.classification_config.unwrap_or_default(),
@@ -301,6 +298,10 @@ impl AgentBuilder {
     }
 }
 
+#[cfg(feature = "mcp")]
+/// Channel name used for `[mcp.channels]` lookups; the agent loop runs on the CLI.
+const MCP_AGENT_CHANNEL: &str = "cli";
+
 #[cfg(feature = "mcp")]
 /// Unique sentinel used to delimit MCP context inside the system prompt.
 /// Chosen to be unlikely to appear in natural MCP content; the sanitizer
@@ -402,47 +403,34 @@ impl Agent {
 
         // Wire MCP if enabled
         #[cfg(feature = "mcp")]
-        let (mcp_registry, mcp_pending_configs) = if config.mcp.enabled {
-            let config_path = config.mcp.config_path.as_deref().unwrap_or(".mcp.json");
-            let mcp_json_path = config.workspace_dir.join(config_path);
-
-            if mcp_json_path.exists() {
-                let builtin_names: std::collections::HashSet<String> =
-                    tools.iter().map(|t| t.name().to_string()).collect();
-                let registry = Arc::new(
-                    zeroclaw_mcp::registry::McpRegistry::new(config.mcp.tool_cap, builtin_names)
-                        .with_config_path(mcp_json_path.clone()),
-                );
-
-                // Register mcp_manage tool so the agent can manage MCP servers
-                let autonomy_level = security.autonomy;
-                tools.push(Box::new(crate::tools::mcp_manage::McpManageTool::new(
-                    Arc::clone(&registry),
-                    autonomy_level,
-                )));
-
-                // Parse configs now, connect async on first turn()
-                let pending = match zeroclaw_mcp::config::parse_mcp_config(&mcp_json_path) {
-                    Ok(configs) => {
-                        for c in &configs {
-                            tracing::info!(
-                                "MCP: server '{}' configured (will connect on first turn)",
-                                c.name
-                            );
-                        }
-                        configs
+        let (mcp_registry, mcp_pending_configs) = {
+            let builtin_names = tools.iter().map(|t| t.name().to_string());
+            match crate::tools::mcp_bridge::registry_from_config(config, builtin_names) {
+                Some((registry, pending)) => {
+                    // Register mcp_manage tool so the agent can manage MCP servers
+                    let autonomy_level = security.autonomy;
+                    tools.push(Box::new(crate::tools::mcp_manage::McpManageTool::new(
+                        Arc::clone(&registry),
+                        autonomy_level,
+                    )));
+                    if config.skills.register_tools {
+                        tools.extend(crate::tools::skill_tool::mcp_skill_tools(
+                            &skills,
+                            &registry,
+                            MCP_AGENT_CHANNEL,
+                        ));
                     }
-                    Err(e) => {
-                        tracing::warn!("Failed to load MCP config from {:?}: {}", mcp_json_path, e);
-                        Vec::new()
+                    // Connect async on first turn()
+                    for c in &pending {
+                        tracing::info!(
+                            "MCP: server '{}' configured (will connect on first turn)",
+                            c.name
+                        );
                     }
-                };
-                (Some(registry), pending)
-            } else {
-                (None, Vec::new())
+                    (Some(registry), pending)
+                }
+                None => (None, Vec::new()),
             }
-        } else {
-            (None, Vec::new())
         };
 
         let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
@@ -493,16 +481,14 @@ impl Agent {
             .identity_config(config.identity.clone())
             .skills(skills)
             .allowed_commands(config.autonomy.allowed_commands.clone())
-            .skills_prompt_mode(config.skills.prompt_injection_mode)
+            .skills_config(config.skills.clone())
             .shared_skills(shared_skills)
             .auto_save(config.memory.auto_save);
 
         // Wire hooks if enabled (mirrors channels/mod.rs pattern)
         if config.hooks.enabled {
             let mut runner = crate::hooks::HookRunner::new();
-            if config.hooks.builtin.command_logger {
-                runner.register(Box::new(crate::hooks::builtin::CommandLoggerHook::new()));
-            }
+            crate::hooks::builtin::register_builtins(&mut runner, config);
             builder = builder.hook_runner(Arc::new(runner));
         }
 
@@ -558,7 +544,7 @@ impl Agent {
             model_name: &self.model_name,
             tools: &self.tools,
             skills: &self.skills,
-            skills_prompt_mode: self.skills_prompt_mode,
+            skills_config: &self.skills_config,
             identity_config: Some(&self.identity_config),
             dispatcher_instructions: &instructions,
             #[cfg(feature = "feishu-docs-sync")]
@@ -569,12 +555,16 @@ impl Agent {
         Ok(prompt)
     }
 
-    async fn execute_tool_call(&self, call: &ParsedToolCall) -> ToolExecutionResult {
+    async fn execute_tool_call(
+        &self,
+        call: &ParsedToolCall,
+        hook_ctx: &crate::hooks::HookContext,
+    ) -> ToolExecutionResult {
         let start = Instant::now();
         // Hook: before_tool_call — respect Cancel
         let (tool_name, tool_args) = match self
             .hook_runner
-            .run_before_tool_call(call.name.clone(), call.arguments.clone())
+            .run_before_tool_call_with_context(hook_ctx, call.name.clone(), call.arguments.clone())
             .await
         {
             crate::hooks::HookResult::Continue(pair) => pair,
@@ -645,18 +635,22 @@ impl Agent {
         }
     }
 
-    async fn execute_tools(&self, calls: &[ParsedToolCall]) -> Vec<ToolExecutionResult> {
+    async fn execute_tools(
+        &self,
+        calls: &[ParsedToolCall],
+        hook_ctx: &crate::hooks::HookContext,
+    ) -> Vec<ToolExecutionResult> {
         if !self.config.parallel_tools {
             let mut results = Vec::with_capacity(calls.len());
             for call in calls {
-                results.push(self.execute_tool_call(call).await);
+                results.push(self.execute_tool_call(call, hook_ctx).await);
             }
             return results;
         }
 
         let futs: Vec<_> = calls
             .iter()
-            .map(|call| self.execute_tool_call(call))
+            .map(|call| self.execute_tool_call(call, hook_ctx))
             .collect();
         futures_util::future::join_all(futs).await
     }
@@ -672,7 +666,6 @@ impl Agent {
     }
 
     pub async fn turn(&mut self, user_message: &str) -> Result<String> {
-        // Fire session_start hook (void, errors logged internally)
         let session_id = format!(
             "cli-{}",
             std::time::SystemTime::now()
@@ -680,6 +673,19 @@ impl Agent {
                 .unwrap_or_default()
                 .as_millis()
         );
+        // Hook: before_session_start — respect Cancel
+        if let crate::hooks::HookResult::Cancel(reason) = self
+            .hook_runner
+            .run_before_session_start(&session_id, "cli")
+            .await
+        {
+            tracing::info!(
+                reason = reason.as_str(),
+                "before_session_start cancelled by hook"
+            );
+            anyhow::bail!("Session rejected by hook: {}", reason);
+        }
+        // Fire session_start hook (void, errors logged internally)
         if let Err(e) =
             std::panic::AssertUnwindSafe(self.hook_runner.fire_session_start(&session_id, "cli"))
                 .catch_unwind()
@@ -692,19 +698,13 @@ impl Agent {
         if !self.mcp_pending_configs.is_empty() {
             if let Some(ref registry) = self.mcp_registry {
                 let pending = std::mem::take(&mut self.mcp_pending_configs);
-                for server_config in pending {
-                    let name = server_config.name.clone();
-                    match registry.add_server(server_config).await {
-                        Ok(tools) => {
-                            tracing::info!(
-                                "MCP: server '{}' connected with {} tools",
-                                name,
-                                tools.len()
-                            );
-                        }
-                        Err(e) => {
-                            tracing::warn!("MCP: failed to connect server '{}': {}", name, e);
-                        }
+                crate::tools::mcp_bridge::connect_servers(registry, pending).await;
+                for (name, reason) in
+                    crate::tools::skill_tool::unresolved_mcp_skill_tools(&self.skills, registry)
+                        .await
+                {
+                    if self.tools.iter().any(|t| t.name() == name) {
+                        tracing::warn!("Skill tool '{name}' will fail: {reason}");
                     }
                 }
             }
@@ -716,17 +716,8 @@ impl Agent {
             // Remove previous MCP bridge tools (names start with "mcp_")
             self.tools.retain(|t| !t.name().starts_with("mcp_"));
 
-            let mcp_tools = registry.get_all_tools().await;
-            let bridge_tools: Vec<Box<dyn Tool>> = mcp_tools
-                .into_iter()
-                .map(|(server_name, tool_info)| {
-                    Box::new(crate::tools::McpBridgeTool::new(
-                        server_name,
-                        tool_info,
-                        Arc::clone(registry),
-                    )) as Box<dyn Tool>
-                })
-                .collect();
+            let bridge_tools =
+                crate::tools::mcp_bridge::channel_tools(registry, MCP_AGENT_CHANNEL).await;
             self.tools.extend(bridge_tools);
             self.tool_specs = self.tools.iter().map(|t| t.spec()).collect();
 
@@ -798,12 +789,14 @@ impl Agent {
             }
         }
 
+        let hook_ctx = crate::hooks::HookContext::session(session_id.as_str(), "cli")
+            .with_model(self.model_name.as_str());
         if self.history.is_empty() {
             let system_prompt = self.build_system_prompt()?;
             // Hook: before_prompt_build — respect Cancel
             let system_prompt = match self
                 .hook_runner
-                .run_before_prompt_build(system_prompt)
+                .run_before_prompt_build_with_context(&hook_ctx, system_prompt)
                 .await
             {
                 crate::hooks::HookResult::Continue(p) => p,
@@ -850,13 +843,14 @@ impl Agent {
             .push(ConversationMessage::Chat(ChatMessage::user(enriched)));
 
         let effective_model = self.classify_model(user_message);
+        let hook_ctx = hook_ctx.with_model(effective_model.as_str());
 
         for _ in 0..self.config.max_tool_iterations {
             let messages = self.tool_dispatcher.to_provider_messages(&self.history);
             // Hook: before_llm_call — respect Cancel
             let (messages, hook_model) = match self
                 .hook_runner
-                .run_before_llm_call(messages, effective_model.clone())
+                .run_before_llm_call_with_context(&hook_ctx, messages, effective_model.clone())
                 .await
             {
                 crate::hooks::HookResult::Continue(pair) => pair,
@@ -940,7 +934,7 @@ impl Agent {
                 reasoning_content: response.reasoning_content.clone(),
             });
 
-            let results = self.execute_tools(&calls).await;
+            let results = self.execute_tools(&calls, &hook_ctx).await;
             let formatted = self.tool_dispatcher.format_results(&results);
             self.history.push(formatted);
             self.trim_history();
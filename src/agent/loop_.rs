@@ -1852,6 +1852,7 @@ pub(crate) async fn agent_turn(
         None,
         None,
         None,
+        None,
         &[],
         None,
     )
@@ -2043,6 +2044,7 @@ pub(crate) async fn run_tool_call_loop(
     cancellation_token: Option<CancellationToken>,
     on_delta: Option<tokio::sync::mpsc::Sender<String>>,
     hooks: Option<&crate::hooks::HookRunner>,
+    hook_ctx: Option<&crate::hooks::HookContext>,
     excluded_tools: &[String],
     on_tool_event: Option<tokio::sync::mpsc::UnboundedSender<ToolEvent>>,
 ) -> Result<String> {
@@ -2058,6 +2060,14 @@ pub(crate) async fn run_tool_call_loop(
         .map(|tool| tool.spec())
         .collect();
     let use_native_tools = provider.supports_native_tools() && !tool_specs.is_empty();
+    // Callers without a session (CLI, delegates) still get the channel.
+    let hook_ctx = hook_ctx
+        .cloned()
+        .unwrap_or_else(|| crate::hooks::HookContext {
+            channel: Some(channel_name.to_string()),
+            ..crate::hooks::HookContext::default()
+        })
+        .with_model(model);
     let turn_id = Uuid::new_v4().to_string();
     let mut seen_tool_signatures: HashSet<(String, String)> = HashSet::new();
 
@@ -2097,8 +2107,18 @@ pub(crate) async fn run_tool_call_loop(
             }
         }
 
+        // One unreadable attachment should not sink the whole turn; the model
+        // sees an "[image unavailable: ...]" note in its place instead.
         let prepared_messages =
-            multimodal::prepare_messages_for_provider(history, multimodal_config).await?;
+            multimodal::prepare_messages_for_provider_lenient(history, multimodal_config).await?;
+        for failed in &prepared_messages.failed_images {
+            tracing::warn!(
+                provider = provider_name,
+                channel = channel_name,
+                error = %failed.error,
+                "skipping image that could not be loaded"
+            );
+        }
 
         // ── Progress: LLM thinking ────────────────────────────
         if let Some(ref tx) = on_delta {
@@ -2391,7 +2411,11 @@ pub(crate) async fn run_tool_call_loop(
             let mut tool_args = call.arguments.clone();
             if let Some(hooks) = hooks {
                 match hooks
-                    .run_before_tool_call(tool_name.clone(), tool_args.clone())
+                    .run_before_tool_call_with_context(
+                        &hook_ctx,
+                        tool_name.clone(),
+                        tool_args.clone(),
+                    )
                     .await
                 {
                     crate::hooks::HookResult::Cancel(reason) => {
@@ -2986,7 +3010,7 @@ pub async fn run(
         Some(&config.identity),
         bootstrap_max_chars,
         native_tools,
-        config.skills.prompt_injection_mode,
+        &config.skills,
     );
 
     // Append structured tool-use instructions with schemas (only for non-native providers)
@@ -2994,6 +3018,12 @@ pub async fn run(
         system_prompt.push_str(&build_tool_instructions(&tools_registry));
     }
     crate::channels::append_unrestricted_commands_hint(&mut system_prompt, &config.autonomy.allowed_commands);
+    crate::skills::apply_skills_prompt_position(
+        &mut system_prompt,
+        &skills,
+        &config.workspace_dir,
+        &config.skills,
+    );
 
     // ── Approval manager (supervised mode) ───────────────────────
     let approval_manager = if interactive {
@@ -3053,6 +3083,7 @@ pub async fn run(
             None,
             None,
             None,
+            None,
             &[],
             None,
         )
@@ -3175,6 +3206,7 @@ pub async fn run(
                 None,
                 None,
                 None,
+                None,
                 &[],
                 None,
             )
@@ -3216,12 +3248,18 @@ pub async fn run(
                         Some(&config.identity),
                         bootstrap_max_chars,
                         native_tools,
-                        config.skills.prompt_injection_mode,
+                        &config.skills,
                     );
                     if !native_tools {
                         new_prompt.push_str(&build_tool_instructions(&tools_registry));
                     }
                     crate::channels::append_unrestricted_commands_hint(&mut new_prompt, &config.autonomy.allowed_commands);
+                    crate::skills::apply_skills_prompt_position(
+                        &mut new_prompt,
+                        &state.skills,
+                        &config.workspace_dir,
+                        &config.skills,
+                    );
                     // Update system message in history
                     if !history.is_empty() {
                         history[0] = ChatMessage::system(&new_prompt);
@@ -3411,12 +3449,18 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         Some(&config.identity),
         bootstrap_max_chars,
         native_tools,
-        config.skills.prompt_injection_mode,
+        &config.skills,
     );
     if !native_tools {
         system_prompt.push_str(&build_tool_instructions(&tools_registry));
     }
     crate::channels::append_unrestricted_commands_hint(&mut system_prompt, &config.autonomy.allowed_commands);
+    crate::skills::apply_skills_prompt_position(
+        &mut system_prompt,
+        &skills,
+        &config.workspace_dir,
+        &config.skills,
+    );
 
     let mem_context = build_context(mem.as_ref(), message, config.memory.min_relevance_score).await;
     let rag_limit = if config.agent.compact_context { 2 } else { 5 };
@@ -3760,6 +3804,7 @@ mod tests {
             None,
             None,
             None,
+            None,
             &[],
             None,
         )
@@ -3772,7 +3817,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn run_tool_call_loop_rejects_oversized_image_payload() {
+    async fn run_tool_call_loop_skips_oversized_image_payload() {
         let calls = Arc::new(AtomicUsize::new(0));
         let provider = VisionProvider {
             calls: Arc::clone(&calls),
@@ -3780,7 +3825,7 @@ mod tests {
 
         let oversized_payload = STANDARD.encode(vec![0_u8; (1024 * 1024) + 1]);
         let mut history = vec![ChatMessage::user(format!(
-            "[IMAGE:data:image/png;base64,{oversized_payload}]"
+            "[IMAGE:data:image/png;base64,iVBORw0KGgo=] [IMAGE:data:image/png;base64,{oversized_payload}]"
         ))];
 
         let tools_registry: Vec<Box<dyn Tool>> = Vec::new();
@@ -3789,11 +3834,13 @@ mod tests {
             max_images: 4,
             max_image_size_mb: 1,
             allow_remote_fetch: false,
+            remote_fetch_allowed_hosts: Vec::new(),
             max_videos: 2,
             max_video_size_mb: 20,
+            max_total_bytes: None,
         };
 
-        let err = run_tool_call_loop(
+        let result = run_tool_call_loop(
             &provider,
             &mut history,
             &tools_registry,
@@ -3809,16 +3856,15 @@ mod tests {
             None,
             None,
             None,
+            None,
             &[],
             None,
         )
         .await
-        .expect_err("oversized payload must fail");
+        .expect("oversized payload should be skipped, not fail the turn");
 
-        assert!(err
-            .to_string()
-            .contains("multimodal image size limit exceeded"));
-        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(result, "vision-ok");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
     }
 
     #[tokio::test]
@@ -3850,6 +3896,7 @@ mod tests {
             None,
             None,
             None,
+            None,
             &[],
             None,
         )
@@ -3977,6 +4024,7 @@ mod tests {
             None,
             None,
             None,
+            None,
             &[],
             None,
         )
@@ -4047,6 +4095,7 @@ mod tests {
             None,
             None,
             None,
+            None,
             &[],
             None,
         )
@@ -4104,6 +4153,7 @@ mod tests {
             None,
             None,
             None,
+            None,
             &[],
             None,
         )
@@ -5270,7 +5320,7 @@ Let me check the result."#;
             None, // no identity config
             None, // no bootstrap_max_chars
             true, // native_tools
-            crate::config::SkillsPromptInjectionMode::Full,
+            &crate::config::SkillsConfig::default(),
         );
 
         // Must contain zero XML protocol artifacts
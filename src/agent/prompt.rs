@@ -16,7 +16,7 @@ pub struct PromptContext<'a> {
     pub model_name: &'a str,
     pub tools: &'a [Box<dyn Tool>],
     pub skills: &'a [Skill],
-    pub skills_prompt_mode: crate::config::SkillsPromptInjectionMode,
+    pub skills_config: &'a crate::config::SkillsConfig,
     pub identity_config: Option<&'a IdentityConfig>,
     pub dispatcher_instructions: &'a str,
     #[cfg(feature = "feishu-docs-sync")]
@@ -200,10 +200,10 @@ impl PromptSection for SkillsSection {
     }
 
     fn build(&self, ctx: &PromptContext<'_>) -> Result<String> {
-        Ok(crate::skills::skills_to_prompt_with_mode(
+        Ok(crate::skills::skills_to_prompt_from_config(
             ctx.skills,
             ctx.workspace_dir,
-            ctx.skills_prompt_mode,
+            ctx.skills_config,
         ))
     }
 }
@@ -507,7 +507,7 @@ mod tests {
             model_name: "test-model",
             tools: &tools,
             skills: &[],
-            skills_prompt_mode: crate::config::SkillsPromptInjectionMode::Full,
+            skills_config: &crate::config::SkillsConfig::default(),
             identity_config: Some(&identity_config),
             dispatcher_instructions: "",
             #[cfg(feature = "feishu-docs-sync")]
@@ -537,7 +537,7 @@ mod tests {
             model_name: "test-model",
             tools: &tools,
             skills: &[],
-            skills_prompt_mode: crate::config::SkillsPromptInjectionMode::Full,
+            skills_config: &crate::config::SkillsConfig::default(),
             identity_config: None,
             dispatcher_instructions: "instr",
             #[cfg(feature = "feishu-docs-sync")]
@@ -564,6 +564,7 @@ mod tests {
                 kind: "shell".into(),
                 command: "echo ok".into(),
                 args: std::collections::HashMap::new(),
+                required: vec![],
             }],
             prompts: vec!["Run smoke tests before deploy.".into()],
             location: None,
@@ -574,7 +575,7 @@ mod tests {
             model_name: "test-model",
             tools: &tools,
             skills: &skills,
-            skills_prompt_mode: crate::config::SkillsPromptInjectionMode::Full,
+            skills_config: &crate::config::SkillsConfig::default(),
             identity_config: None,
             dispatcher_instructions: "",
             #[cfg(feature = "feishu-docs-sync")]
@@ -604,6 +605,7 @@ mod tests {
                 kind: "shell".into(),
                 command: "echo ok".into(),
                 args: std::collections::HashMap::new(),
+                required: vec![],
             }],
             prompts: vec!["Run smoke tests before deploy.".into()],
             location: Some(Path::new("/tmp/workspace/skills/deploy/SKILL.md").to_path_buf()),
@@ -614,7 +616,10 @@ mod tests {
             model_name: "test-model",
             tools: &tools,
             skills: &skills,
-            skills_prompt_mode: crate::config::SkillsPromptInjectionMode::Compact,
+            skills_config: &crate::config::SkillsConfig {
+                prompt_injection_mode: crate::config::SkillsPromptInjectionMode::Compact,
+                ..crate::config::SkillsConfig::default()
+            },
             identity_config: None,
             dispatcher_instructions: "",
             #[cfg(feature = "feishu-docs-sync")]
@@ -629,6 +634,46 @@ mod tests {
         assert!(!output.contains("<tools>"));
     }
 
+    #[test]
+    fn skills_section_honors_configured_prompt_budget() {
+        let tools: Vec<Box<dyn Tool>> = vec![];
+        let skills = vec![crate::skills::Skill {
+            name: "deploy".into(),
+            description: "Release safely".into(),
+            version: "1.0.0".into(),
+            author: None,
+            tags: vec![],
+            tools: vec![],
+            prompts: vec!["Run smoke tests before deploy.".into()],
+            location: Some(Path::new("/tmp/workspace/skills/deploy/SKILL.md").to_path_buf()),
+        }];
+        let skills_config = |budget| crate::config::SkillsConfig {
+            prompt_injection_mode: crate::config::SkillsPromptInjectionMode::Budgeted,
+            prompt_budget_chars: Some(budget),
+            ..crate::config::SkillsConfig::default()
+        };
+        let build = |config: &crate::config::SkillsConfig| {
+            let ctx = PromptContext {
+                workspace_dir: Path::new("/tmp/workspace"),
+                model_name: "test-model",
+                tools: &tools,
+                skills: &skills,
+                skills_config: config,
+                identity_config: None,
+                dispatcher_instructions: "",
+                #[cfg(feature = "feishu-docs-sync")]
+                docs_sync_config: None,
+            };
+            SkillsSection.build(&ctx).unwrap()
+        };
+
+        let roomy = build(&skills_config(10_000));
+        assert!(roomy.contains("<instruction>Run smoke tests before deploy.</instruction>"));
+        let tight = build(&skills_config(1));
+        assert!(tight.contains("<name>deploy</name>"));
+        assert!(!tight.contains("<instruction>Run smoke tests before deploy.</instruction>"));
+    }
+
     #[test]
     fn datetime_section_includes_timestamp_and_timezone() {
         let tools: Vec<Box<dyn Tool>> = vec![];
@@ -637,7 +682,7 @@ mod tests {
             model_name: "test-model",
             tools: &tools,
             skills: &[],
-            skills_prompt_mode: crate::config::SkillsPromptInjectionMode::Full,
+            skills_config: &crate::config::SkillsConfig::default(),
             identity_config: None,
             dispatcher_instructions: "instr",
             #[cfg(feature = "feishu-docs-sync")]
@@ -668,6 +713,7 @@ mod tests {
                 kind: "shell&exec".into(),
                 command: "cargo clippy".into(),
                 args: std::collections::HashMap::new(),
+                required: vec![],
             }],
             prompts: vec!["Use <tool_call> and & keep output \"safe\"".into()],
             location: None,
@@ -677,7 +723,7 @@ mod tests {
             model_name: "test-model",
             tools: &tools,
             skills: &skills,
-            skills_prompt_mode: crate::config::SkillsPromptInjectionMode::Full,
+            skills_config: &crate::config::SkillsConfig::default(),
             identity_config: None,
             dispatcher_instructions: "",
             #[cfg(feature = "feishu-docs-sync")]
@@ -1,4 +1,4 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Channel, ChannelCapabilities, ChannelMessage, SendMessage};
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use parking_lot::Mutex;
@@ -611,6 +611,14 @@ impl Channel for DiscordChannel {
         Ok(())
     }
 
+    fn capabilities(&self) -> ChannelCapabilities {
+        ChannelCapabilities {
+            reactions: true,
+            typing: true,
+            ..ChannelCapabilities::default()
+        }
+    }
+
     async fn add_reaction(
         &self,
         channel_id: &str,
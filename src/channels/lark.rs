@@ -1,25 +1,42 @@
 use super::lark_ws_manager::LarkWsManager;
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{AttachmentKind, Channel, ChannelCapabilities, ChannelMessage, SendMessage};
+use crate::clock::{Clock, SystemClock};
+use crate::config::schema::{LarkInboundOverflow, LarkUrgentEscalation};
 use crate::config::StreamMode;
+use crate::security::{redact_json_for_log, redact_log_body};
 use async_trait::async_trait;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::broadcast;
 use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 const FEISHU_BASE_URL: &str = "https://open.feishu.cn/open-apis";
 const FEISHU_WS_BASE_URL: &str = "https://open.feishu.cn";
 const LARK_BASE_URL: &str = "https://open.larksuite.com/open-apis";
 const LARK_WS_BASE_URL: &str = "https://open.larksuite.com";
-const ACK_REACTION_EMOJI_TYPE: &str = "MUSCLE";
+/// Ack reaction used when `ack_reaction_emoji` is not configured.
+const ACK_REACTION_EMOJI_TYPE: &str = "OK";
 const LARK_MAX_FILE_UPLOAD_BYTES: usize = 20 * 1024 * 1024;
 const LARK_MAX_FILE_DOWNLOAD_BYTES: usize = 20 * 1024 * 1024;
 const STREAMING_ELEMENT_ID: &str = "content";
-
-/// Attachment types recognized in outgoing Lark messages.
+/// Budget (JSON-escaped bytes) for the streamed markdown element. CardKit
+/// rejects cards past roughly 30 KB, so leave headroom for the card frame.
+const LARK_CARD_MAX_CONTENT_BYTES: usize = 28 * 1024;
+/// Appended when a draft is cut to fit [`LARK_CARD_MAX_CONTENT_BYTES`].
+const LARK_CARD_TRUNCATION_NOTICE: &str = "\n\n… *(truncated: response too long for a card)*";
+/// How long WS message_ids are remembered for dedup.
+const LARK_SEEN_TTL_SECS: u64 = 30 * 60;
+/// After a (re)connect Feishu may redeliver buffered events; duplicates in
+/// this window are counted as redeliveries.
+const LARK_REPLAY_WINDOW_SECS: u64 = 60;
+/// How often changed WS message_ids are written to disk.
+const LARK_SEEN_FLUSH_SECS: u64 = 5;
+
+/// Attachment types recognized in Lark message markers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum LarkAttachmentKind {
     Image,
@@ -28,7 +45,6 @@ enum LarkAttachmentKind {
     Video,
 }
 
-
 /// LarkEvent envelope (method=1 / type=event payload)
 #[derive(Debug, serde::Deserialize)]
 struct LarkEvent {
@@ -79,6 +95,44 @@ struct LarkMessage {
     parent_id: Option<String>,
 }
 
+/// Event type of card callback (button click) events.
+const LARK_CARD_ACTION_EVENT: &str = "card.action.trigger";
+
+/// A user interaction with an interactive element (e.g. a button) on a card
+/// the bot sent, decoded from a `card.action.trigger` callback.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LarkCardAction {
+    /// open_id of the user who clicked.
+    pub operator_open_id: String,
+    /// Chat the card was posted in.
+    pub chat_id: String,
+    /// message_id of the card.
+    pub message_id: String,
+    /// Element tag, e.g. `button`.
+    pub tag: String,
+    /// The `value` attached to the element when the card was built.
+    pub value: serde_json::Value,
+    /// Event time in seconds since the epoch.
+    pub timestamp: u64,
+}
+
+impl LarkCardAction {
+    /// Convert into an inbound message so the action flows through the normal
+    /// dispatch path. The content is a `[CARD_ACTION:<tag>:<value json>]`
+    /// marker; `sender` is the acting user and replies go to the card's chat.
+    pub fn into_channel_message(self, channel: &str) -> ChannelMessage {
+        ChannelMessage {
+            id: Uuid::new_v4().to_string(),
+            sender: self.operator_open_id,
+            reply_target: self.chat_id,
+            content: format!("[CARD_ACTION:{}:{}]", self.tag, self.value),
+            channel: channel.to_string(),
+            timestamp: self.timestamp,
+            thread_ts: None,
+        }
+    }
+}
+
 /// Feishu/Lark API business code for expired/invalid tenant access token.
 const LARK_INVALID_ACCESS_TOKEN_CODE: i64 = 99_991_663;
 /// Refresh tenant token this many seconds before the announced expiry.
@@ -96,6 +150,24 @@ fn extract_lark_response_code(body: &serde_json::Value) -> Option<i64> {
     body.get("code").and_then(|c| c.as_i64())
 }
 
+/// `receive_id_type` for a recipient ID, inferred from its prefix: `ou_` is a
+/// user's open_id, `on_` a union_id, and anything else a chat_id.
+fn lark_receive_id_type(receive_id: &str) -> &'static str {
+    if receive_id.starts_with("ou_") {
+        "open_id"
+    } else if receive_id.starts_with("on_") {
+        "union_id"
+    } else {
+        "chat_id"
+    }
+}
+
+fn response_message_id(body: &serde_json::Value) -> Option<String> {
+    body.pointer("/data/message_id")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+}
+
 fn is_lark_invalid_access_token(body: &serde_json::Value) -> bool {
     extract_lark_response_code(body) == Some(LARK_INVALID_ACCESS_TOKEN_CODE)
 }
@@ -119,6 +191,12 @@ fn extract_lark_token_ttl_seconds(body: &serde_json::Value) -> u64 {
     ttl.max(1)
 }
 
+/// Trim a configured base URL, dropping trailing slashes; blank means unset.
+fn normalize_base_url(url: Option<&str>) -> Option<String> {
+    let url = url?.trim().trim_end_matches('/');
+    (!url.is_empty()).then(|| url.to_string())
+}
+
 fn next_token_refresh_deadline(now: Instant, ttl_seconds: u64) -> Instant {
     let ttl = Duration::from_secs(ttl_seconds.max(1));
     let refresh_in = ttl
@@ -133,12 +211,18 @@ fn ensure_lark_send_success(
     context: &str,
 ) -> anyhow::Result<()> {
     if !status.is_success() {
-        anyhow::bail!("Lark send failed {context}: status={status}, body={body}");
+        anyhow::bail!(
+            "Lark send failed {context}: status={status}, body={}",
+            redact_json_for_log(body)
+        );
     }
 
     let code = extract_lark_response_code(body).unwrap_or(0);
     if code != 0 {
-        anyhow::bail!("Lark send failed {context}: code={code}, body={body}");
+        anyhow::bail!(
+            "Lark send failed {context}: code={code}, body={}",
+            redact_json_for_log(body)
+        );
     }
 
     Ok(())
@@ -202,12 +286,22 @@ pub struct LarkChannel {
     allowed_users: Vec<String>,
     /// Runtime endpoint/platform selection.
     platform: LarkPlatform,
+    /// Open API base URL override (local proxies and tests).
+    api_base_override: Option<String>,
+    /// WebSocket endpoint base URL override (self-hosted gateways).
+    ws_base_override: Option<String>,
     /// How to receive events: WebSocket long-connection or HTTP webhook.
     receive_mode: crate::config::schema::LarkReceiveMode,
     /// Cached tenant access token
     tenant_token: Arc<RwLock<Option<CachedTenantToken>>>,
-    /// Dedup set: WS message_ids seen in last ~30 min to prevent double-dispatch
-    ws_seen_ids: Arc<RwLock<HashMap<String, Instant>>>,
+    /// Held while fetching a new tenant token so concurrent callers (across
+    /// clones) wait for one refresh instead of each hitting the auth endpoint.
+    token_refresh: Arc<tokio::sync::Mutex<()>>,
+    /// Time source for token refresh decisions; swapped out in tests.
+    clock: Arc<dyn Clock>,
+    /// Dedup of WS message_ids seen in the last ~30 min, with a strict replay
+    /// window after each (re)connect. Persisted when a workspace is set.
+    ws_replay_guard: Arc<std::sync::Mutex<ReplayGuard>>,
     /// Thread-reply dedup: tracks recent (chat_id, content_hash) to detect
     /// "also send to conversation" duplicates. Stores (timestamp, is_thread_reply).
     thread_dedup: Arc<std::sync::Mutex<HashMap<(String, u64), (Instant, bool)>>>,
@@ -218,10 +312,31 @@ pub struct LarkChannel {
     stream_mode: StreamMode,
     /// Minimum interval (ms) between card updates.
     draft_update_interval_ms: u64,
+    /// Floor applied to `draft_update_interval_ms`.
+    draft_update_min_interval_ms: u64,
+    /// Window (ms) for coalescing rapid draft deltas; 0 disables debouncing.
+    draft_update_debounce_ms: u64,
     /// CardKit card sequence numbers per card_id.
     card_sequence: Arc<std::sync::Mutex<HashMap<String, u64>>>,
     /// Throttle tracking: last draft update time per card_id.
     last_draft_update: Arc<std::sync::Mutex<HashMap<String, Instant>>>,
+    /// Latest draft text per card_id not yet shown on the card (debounced or
+    /// skipped by the throttle). Flushed on finalize.
+    pending_draft_text: Arc<std::sync::Mutex<HashMap<String, String>>>,
+    /// Text most recently written to each card_id.
+    shown_draft_text: Arc<std::sync::Mutex<HashMap<String, String>>>,
+    /// Drafts opened from urgent messages; their updates skip the throttle.
+    urgent_drafts: Arc<std::sync::Mutex<HashSet<String>>>,
+    /// Urgent notification sent alongside urgent messages.
+    urgent_escalation: LarkUrgentEscalation,
+    /// Emoji type for the ack reaction on answered messages; `None` disables it.
+    ack_reaction_emoji: Option<String>,
+    /// Inbound message length cap in characters; `None` means unlimited.
+    max_inbound_chars: Option<usize>,
+    /// Whether over-long inbound messages are truncated or rejected.
+    inbound_overflow: LarkInboundOverflow,
+    /// Send text segments and attachments in source order.
+    interleave_attachments: bool,
     /// Typing indicator card IDs per recipient (for "正在处理..." cards).
     typing_card_ids: Arc<std::sync::Mutex<HashMap<String, String>>>,
     /// Optional docs_sync sharer for auto-sharing documents with new users.
@@ -229,6 +344,8 @@ pub struct LarkChannel {
     docs_sharer: Option<std::sync::Arc<crate::docs_sync::DocsSyncSharer>>,
     /// Shared WS connection manager (None when using webhook mode).
     ws_manager: Option<Arc<LarkWsManager>>,
+    /// Cancelled on shutdown; ends `listen` after draining buffered messages.
+    shutdown: CancellationToken,
 }
 
 impl LarkChannel {
@@ -264,17 +381,32 @@ impl LarkChannel {
             port,
             allowed_users,
             platform,
+            api_base_override: None,
+            ws_base_override: None,
             receive_mode: crate::config::schema::LarkReceiveMode::default(),
             tenant_token: Arc::new(RwLock::new(None)),
-            ws_seen_ids: Arc::new(RwLock::new(HashMap::new())),
+            token_refresh: Arc::new(tokio::sync::Mutex::new(())),
+            clock: Arc::new(SystemClock),
+            ws_replay_guard: Arc::new(std::sync::Mutex::new(ReplayGuard::default())),
             thread_dedup: Arc::new(std::sync::Mutex::new(HashMap::new())),
             pending_cancel: Arc::new(std::sync::Mutex::new(HashMap::new())),
             stream_mode: StreamMode::default(),
             draft_update_interval_ms: 500,
+            draft_update_min_interval_ms: 500,
+            draft_update_debounce_ms: 0,
             card_sequence: Arc::new(std::sync::Mutex::new(HashMap::new())),
             last_draft_update: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            pending_draft_text: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            shown_draft_text: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            urgent_drafts: Arc::new(std::sync::Mutex::new(HashSet::new())),
+            urgent_escalation: LarkUrgentEscalation::default(),
+            ack_reaction_emoji: Some(ACK_REACTION_EMOJI_TYPE.to_string()),
+            max_inbound_chars: None,
+            inbound_overflow: LarkInboundOverflow::default(),
+            interleave_attachments: false,
             typing_card_ids: Arc::new(std::sync::Mutex::new(HashMap::new())),
             ws_manager: None,
+            shutdown: CancellationToken::new(),
             #[cfg(feature = "feishu-docs-sync")]
             docs_sharer: None,
         }
@@ -299,6 +431,19 @@ impl LarkChannel {
         ch.receive_mode = config.receive_mode.clone();
         ch.stream_mode = config.stream_mode.clone();
         ch.draft_update_interval_ms = config.draft_update_interval_ms;
+        ch.draft_update_min_interval_ms = config.draft_update_min_interval_ms;
+        ch.draft_update_debounce_ms = config.draft_update_debounce_ms;
+        ch.urgent_escalation = config.urgent_escalation;
+        if let Some(emoji) = &config.ack_reaction_emoji {
+            ch.ack_reaction_emoji = Some(emoji.trim().to_string()).filter(|e| !e.is_empty());
+        }
+        ch.max_inbound_chars = config.max_inbound_chars;
+        ch.inbound_overflow = config.inbound_overflow;
+        ch.interleave_attachments = config.interleave_attachments;
+        ch.apply_base_url_overrides(
+            config.api_base_url.as_deref(),
+            config.ws_base_url.as_deref(),
+        );
         ch
     }
 
@@ -315,6 +460,19 @@ impl LarkChannel {
         ch.receive_mode = config.receive_mode.clone();
         ch.stream_mode = config.stream_mode.clone();
         ch.draft_update_interval_ms = config.draft_update_interval_ms;
+        ch.draft_update_min_interval_ms = config.draft_update_min_interval_ms;
+        ch.draft_update_debounce_ms = config.draft_update_debounce_ms;
+        ch.urgent_escalation = config.urgent_escalation;
+        if let Some(emoji) = &config.ack_reaction_emoji {
+            ch.ack_reaction_emoji = Some(emoji.trim().to_string()).filter(|e| !e.is_empty());
+        }
+        ch.max_inbound_chars = config.max_inbound_chars;
+        ch.inbound_overflow = config.inbound_overflow;
+        ch.interleave_attachments = config.interleave_attachments;
+        ch.apply_base_url_overrides(
+            config.api_base_url.as_deref(),
+            config.ws_base_url.as_deref(),
+        );
         ch
     }
 
@@ -331,27 +489,180 @@ impl LarkChannel {
         ch.receive_mode = config.receive_mode.clone();
         ch.stream_mode = config.stream_mode.clone();
         ch.draft_update_interval_ms = config.draft_update_interval_ms;
+        ch.draft_update_min_interval_ms = config.draft_update_min_interval_ms;
+        ch.draft_update_debounce_ms = config.draft_update_debounce_ms;
+        ch.urgent_escalation = config.urgent_escalation;
+        if let Some(emoji) = &config.ack_reaction_emoji {
+            ch.ack_reaction_emoji = Some(emoji.trim().to_string()).filter(|e| !e.is_empty());
+        }
+        ch.max_inbound_chars = config.max_inbound_chars;
+        ch.inbound_overflow = config.inbound_overflow;
+        ch.interleave_attachments = config.interleave_attachments;
+        ch.apply_base_url_overrides(
+            config.api_base_url.as_deref(),
+            config.ws_base_url.as_deref(),
+        );
         ch
     }
 
     /// Configure streaming mode for progressive draft updates via CardKit.
-    pub fn with_streaming(mut self, stream_mode: StreamMode, draft_update_interval_ms: u64) -> Self {
+    pub fn with_streaming(
+        mut self,
+        stream_mode: StreamMode,
+        draft_update_interval_ms: u64,
+    ) -> Self {
         self.stream_mode = stream_mode;
         self.draft_update_interval_ms = draft_update_interval_ms;
         self
     }
 
+    /// Send an urgent notification of this kind alongside urgent messages.
+    pub fn with_urgent_escalation(mut self, escalation: LarkUrgentEscalation) -> Self {
+        self.urgent_escalation = escalation;
+        self
+    }
+
+    /// Override the Lark/Feishu Open API base URL.
+    /// Useful for local proxies or testing.
+    pub fn with_api_base(mut self, api_base: String) -> Self {
+        self.api_base_override = Some(api_base);
+        self
+    }
+
+    /// Apply configured API/WS base URL overrides; blank values keep the
+    /// platform defaults.
+    fn apply_base_url_overrides(&mut self, api_base_url: Option<&str>, ws_base_url: Option<&str>) {
+        if let Some(url) = normalize_base_url(api_base_url) {
+            self.api_base_override = Some(url);
+        }
+        if let Some(url) = normalize_base_url(ws_base_url) {
+            self.ws_base_override = Some(url);
+        }
+    }
+
+    /// Replace the time source used for tenant token refresh.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Override the throttle floor and the debounce window for draft updates.
+    pub fn with_draft_timing(mut self, min_interval_ms: u64, debounce_ms: u64) -> Self {
+        self.draft_update_min_interval_ms = min_interval_ms;
+        self.draft_update_debounce_ms = debounce_ms;
+        self
+    }
+
+    /// Effective minimum spacing between card updates.
+    fn draft_throttle(&self) -> Duration {
+        Duration::from_millis(
+            self.draft_update_interval_ms
+                .max(self.draft_update_min_interval_ms),
+        )
+    }
+
+    /// Time left before `draft_id` may be updated again, if any.
+    fn draft_throttle_remaining(&self, draft_id: &str) -> Option<Duration> {
+        let last_updates = self
+            .last_draft_update
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let elapsed = last_updates.get(draft_id)?.elapsed();
+        self.draft_throttle()
+            .checked_sub(elapsed)
+            .filter(|rem| !rem.is_zero())
+    }
+
+    /// Push `text` to the streaming card element and record the update time.
+    async fn push_draft_update(&self, draft_id: &str, text: &str) {
+        let sequence = {
+            let mut seqs = self.card_sequence.lock().unwrap_or_else(|e| e.into_inner());
+            let seq = seqs.entry(draft_id.to_string()).or_insert(1);
+            *seq += 1;
+            *seq
+        };
+        let rendered = truncate_card_content(lark_headers_to_bold(text));
+        if let Err(e) = self
+            .update_card_element(draft_id, &rendered, sequence)
+            .await
+        {
+            tracing::warn!("Lark CardKit update_card_element failed (non-fatal): {e}");
+        } else {
+            self.last_draft_update
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(draft_id.to_string(), Instant::now());
+            self.shown_draft_text
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(draft_id.to_string(), text.to_string());
+        }
+    }
+
+    /// Record the latest text for `draft_id` and, if no flush is pending,
+    /// schedule one after the debounce window (and any remaining throttle).
+    fn schedule_debounced_draft(&self, draft_id: &str, text: &str) {
+        let first = self
+            .pending_draft_text
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(draft_id.to_string(), text.to_string())
+            .is_none();
+        if !first {
+            return;
+        }
+        let channel = self.clone();
+        let draft_id = draft_id.to_string();
+        let debounce = Duration::from_millis(self.draft_update_debounce_ms);
+        tokio::spawn(async move {
+            tokio::time::sleep(debounce).await;
+            if let Some(remaining) = channel.draft_throttle_remaining(&draft_id) {
+                tokio::time::sleep(remaining).await;
+            }
+            let text = channel
+                .pending_draft_text
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(&draft_id);
+            // Finalize/cancel clears both maps; don't resurrect a closed card.
+            let open = channel
+                .card_sequence
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .contains_key(&draft_id);
+            if let (Some(text), true) = (text, open) {
+                channel.push_draft_update(&draft_id, &text).await;
+            }
+        });
+    }
+
     /// Set the docs_sync sharer for auto-sharing documents with Feishu users.
     #[cfg(feature = "feishu-docs-sync")]
     pub fn set_docs_sharer(&mut self, sharer: std::sync::Arc<crate::docs_sync::DocsSyncSharer>) {
         self.docs_sharer = Some(sharer);
     }
 
+    /// Persist WS dedup state under `<workspace>/state/` so redeliveries after
+    /// a restart are still recognised.
+    pub fn with_workspace_dir(mut self, dir: std::path::PathBuf) -> Self {
+        let path = dir
+            .join("state")
+            .join(format!("{}_seen_ids.json", self.channel_name()));
+        self.ws_replay_guard = Arc::new(std::sync::Mutex::new(ReplayGuard::load(path)));
+        self
+    }
+
     /// Set the shared WS connection manager.
     pub fn set_ws_manager(&mut self, manager: Arc<LarkWsManager>) {
         self.ws_manager = Some(manager);
     }
 
+    /// Stop listening (in either receive mode) when `token` is cancelled.
+    pub fn with_shutdown(mut self, token: CancellationToken) -> Self {
+        self.shutdown = token;
+        self
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client(self.platform.proxy_service_key())
     }
@@ -360,20 +671,28 @@ impl LarkChannel {
         self.platform.channel_name()
     }
 
-    fn api_base(&self) -> &'static str {
-        self.platform.api_base()
+    fn api_base(&self) -> &str {
+        self.api_base_override
+            .as_deref()
+            .unwrap_or_else(|| self.platform.api_base())
     }
 
-    fn ws_base(&self) -> &'static str {
-        self.platform.ws_base()
+    fn ws_base(&self) -> &str {
+        self.ws_base_override
+            .as_deref()
+            .unwrap_or_else(|| self.platform.ws_base())
     }
 
     fn tenant_access_token_url(&self) -> String {
         format!("{}/auth/v3/tenant_access_token/internal", self.api_base())
     }
 
-    fn send_message_url(&self) -> String {
-        format!("{}/im/v1/messages?receive_id_type=chat_id", self.api_base())
+    fn send_message_url(&self, receive_id: &str) -> String {
+        format!(
+            "{}/im/v1/messages?receive_id_type={}",
+            self.api_base(),
+            lark_receive_id_type(receive_id)
+        )
     }
 
     fn upload_image_url(&self) -> String {
@@ -392,6 +711,20 @@ impl LarkChannel {
         format!("{}/im/v1/messages/{message_id}/reply", self.api_base())
     }
 
+    fn chat_members_url(&self, chat_id: &str) -> String {
+        format!(
+            "{}/im/v1/chats/{chat_id}/members?member_id_type=open_id&page_size=100",
+            self.api_base()
+        )
+    }
+
+    /// `kind` is the urgent endpoint name, e.g. `urgent_app`.
+    fn urgent_message_url(&self, message_id: &str, kind: &str, user_id_type: &str) -> String {
+        format!(
+            "{}/im/v1/messages/{message_id}/{kind}?user_id_type={user_id_type}",
+            self.api_base()
+        )
+    }
 
     fn download_image_url(&self, message_id: &str, image_key: &str) -> String {
         format!(
@@ -437,6 +770,57 @@ impl LarkChannel {
         Ok(response)
     }
 
+    /// Enforce `max_inbound_chars` on inbound text. Over-long text is either
+    /// truncated with a marker or rejected with the reply to send to `chat_id`.
+    fn apply_inbound_limit(&self, text: String, chat_id: &str) -> Result<String, SendMessage> {
+        let Some(max_chars) = self.max_inbound_chars else {
+            return Ok(text);
+        };
+        let total_chars = text.chars().count();
+        if total_chars <= max_chars {
+            return Ok(text);
+        }
+        match self.inbound_overflow {
+            LarkInboundOverflow::Truncate => {
+                let kept: String = text.chars().take(max_chars).collect();
+                Ok(format!(
+                    "{kept}\n\n[message truncated: showing {max_chars} of {total_chars} characters]"
+                ))
+            }
+            LarkInboundOverflow::Reject => {
+                tracing::info!(
+                    "Lark: rejecting {total_chars}-character message in {chat_id} (limit {max_chars})"
+                );
+                Err(SendMessage::new(
+                    format!(
+                        "Sorry, your message is too long ({total_chars} characters). \
+                         Please keep it under {max_chars} characters."
+                    ),
+                    chat_id,
+                ))
+            }
+        }
+    }
+
+    /// Send a length-rejection reply produced by [`Self::apply_inbound_limit`].
+    async fn send_inbound_rejection(&self, reply: &SendMessage) {
+        if let Err(err) = self.send(reply).await {
+            tracing::warn!("Lark: failed to send length rejection reply: {err}");
+        }
+    }
+
+    /// Add the configured ack reaction to `message_id` in the background.
+    fn spawn_ack_reaction(&self, message_id: &str) {
+        let Some(emoji) = self.ack_reaction_emoji.clone() else {
+            return;
+        };
+        let channel = self.clone();
+        let message_id = message_id.to_string();
+        tokio::spawn(async move {
+            channel.try_add_ack_reaction(&message_id, &emoji).await;
+        });
+    }
+
     /// Best-effort "received" signal for incoming messages.
     /// Failures are logged and never block normal message handling.
     async fn try_add_ack_reaction(&self, message_id: &str, emoji_type: &str) {
@@ -492,7 +876,8 @@ impl LarkChannel {
 
             if !resp_status.is_success() {
                 tracing::warn!(
-                    "Lark: add reaction failed for {message_id}: status={resp_status}, body={resp_body}"
+                    "Lark: add reaction failed for {message_id}: status={resp_status}, body={}",
+                    redact_json_for_log(&resp_body)
                 );
                 return;
             }
@@ -502,59 +887,148 @@ impl LarkChannel {
                     .get("msg")
                     .and_then(|v| v.as_str())
                     .unwrap_or("unknown error");
-                tracing::warn!("Lark: add reaction returned code={code} for {message_id}: {msg}");
+                tracing::warn!(
+                    "Lark: add reaction returned code={code} for {message_id}: {}",
+                    redact_log_body(msg)
+                );
             }
             return;
         }
     }
 
-    /// WS event loop — receives decoded events from the shared `LarkWsManager`.
+    /// WS event loop — receives decoded events from the shared `LarkWsManager`
+    /// until `cancel` fires, then hands any buffered messages to `tx`.
     #[allow(clippy::too_many_lines)]
-    async fn listen_ws(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
-        let manager = self.ws_manager.as_ref()
+    async fn listen_ws(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        cancel: CancellationToken,
+    ) -> anyhow::Result<()> {
+        let manager = self
+            .ws_manager
+            .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Lark: ws_manager not set"))?;
         let mut rx = manager.subscribe();
+        let mut connections = manager.subscribe_connections();
+        // Starting to listen follows downtime just like a reconnect does.
+        self.ws_replay_guard
+            .lock()
+            .unwrap()
+            .on_connect(unix_now_secs());
 
         // Overflow buffer for messages that couldn't be sent (channel full).
         const OVERFLOW_CAP: usize = 20;
         let mut overflow: VecDeque<ChannelMessage> = VecDeque::new();
+        let mut flush_seen = tokio::time::interval(Duration::from_secs(LARK_SEEN_FLUSH_SECS));
 
         loop {
             // Drain overflow buffer first (non-blocking)
             while let Some(msg) = overflow.front() {
                 match tx.try_send(msg.clone()) {
-                    Ok(()) => { overflow.pop_front(); }
+                    Ok(()) => {
+                        overflow.pop_front();
+                    }
                     Err(tokio::sync::mpsc::error::TrySendError::Full(_)) => break,
                     Err(tokio::sync::mpsc::error::TrySendError::Closed(_)) => return Ok(()),
                 }
             }
 
-            let event = match rx.recv().await {
+            let received = tokio::select! {
+                received = rx.recv() => received,
+                _ = flush_seen.tick() => {
+                    flush_replay_guard(&self.ws_replay_guard).await;
+                    continue;
+                }
+                () = cancel.cancelled() => {
+                    drain_overflow(&tx, &mut overflow).await;
+                    flush_replay_guard(&self.ws_replay_guard).await;
+                    return Ok(());
+                }
+            };
+            let event = match received {
                 Ok(ev) => ev,
                 Err(broadcast::error::RecvError::Lagged(n)) => {
                     tracing::warn!("Lark: broadcast lagged, skipped {n} events");
                     continue;
                 }
                 Err(broadcast::error::RecvError::Closed) => {
+                    flush_replay_guard(&self.ws_replay_guard).await;
                     anyhow::bail!("Lark: WS manager broadcast closed");
                 }
             };
 
-            if event.event_type != "im.message.receive_v1" { continue; }
+            if connections.has_changed().unwrap_or(false) {
+                connections.borrow_and_update();
+                self.ws_replay_guard
+                    .lock()
+                    .unwrap()
+                    .on_connect(unix_now_secs());
+            }
+
+            if event.event_type == LARK_CARD_ACTION_EVENT {
+                let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&event.payload)
+                else {
+                    continue;
+                };
+                let Some(action) = self.parse_card_action(&payload) else {
+                    continue;
+                };
+                let event_id = payload
+                    .pointer("/header/event_id")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("");
+                if !event_id.is_empty() {
+                    let verdict = self
+                        .ws_replay_guard
+                        .lock()
+                        .unwrap()
+                        .check(event_id, unix_now_secs());
+                    if verdict != Delivery::New {
+                        tracing::debug!("Lark WS: dropping redelivered card action {event_id}");
+                        continue;
+                    }
+                }
+                #[cfg(feature = "metrics")]
+                crate::metrics::global().record_lark_message("inbound");
+                match tx.try_send(action.into_channel_message(self.channel_name())) {
+                    Ok(()) => {}
+                    Err(tokio::sync::mpsc::error::TrySendError::Full(msg)) => {
+                        if overflow.len() >= OVERFLOW_CAP {
+                            tracing::warn!("Lark WS: overflow full, dropping oldest");
+                            overflow.pop_front();
+                        }
+                        overflow.push_back(msg);
+                    }
+                    Err(tokio::sync::mpsc::error::TrySendError::Closed(_)) => break,
+                }
+                continue;
+            }
+
+            if event.event_type != "im.message.receive_v1" {
+                continue;
+            }
 
             let event_value: LarkEvent = match serde_json::from_slice(&event.payload) {
                 Ok(e) => e,
-                Err(e) => { tracing::error!("Lark: event JSON: {e}"); continue; }
+                Err(e) => {
+                    tracing::error!("Lark: event JSON: {e}");
+                    continue;
+                }
             };
 
             let event_payload = event_value.event;
 
             let recv: MsgReceivePayload = match serde_json::from_value(event_payload.clone()) {
                 Ok(r) => r,
-                Err(e) => { tracing::error!("Lark: payload parse: {e}"); continue; }
+                Err(e) => {
+                    tracing::error!("Lark: payload parse: {e}");
+                    continue;
+                }
             };
 
-            if recv.sender.sender_type == "app" || recv.sender.sender_type == "bot" { continue; }
+            if recv.sender.sender_type == "app" || recv.sender.sender_type == "bot" {
+                continue;
+            }
 
             let sender_open_id = recv.sender.sender_id.open_id.as_deref().unwrap_or("");
             if !self.is_user_allowed(sender_open_id) {
@@ -576,17 +1050,17 @@ impl LarkChannel {
 
             let lark_msg = &recv.message;
 
-            // Dedup
+            // Dedup (redeliveries after a reconnect are counted and reported)
             {
-                let now = Instant::now();
-                let mut seen = self.ws_seen_ids.write().await;
-                // GC
-                seen.retain(|_, t| now.duration_since(*t) < Duration::from_secs(30 * 60));
-                if seen.contains_key(&lark_msg.message_id) {
+                let verdict = self
+                    .ws_replay_guard
+                    .lock()
+                    .unwrap()
+                    .check(&lark_msg.message_id, unix_now_secs());
+                if verdict == Delivery::Duplicate {
                     tracing::debug!("Lark WS: dup {}", lark_msg.message_id);
                     continue;
                 }
-                seen.insert(lark_msg.message_id.clone(), now);
             }
 
             // Decode content by type (mirrors clawdbot-feishu parsing)
@@ -596,7 +1070,11 @@ impl LarkChannel {
                         Ok(v) => v,
                         Err(_) => continue,
                     };
-                    match v.get("text").and_then(|t| t.as_str()).filter(|s| !s.is_empty()) {
+                    match v
+                        .get("text")
+                        .and_then(|t| t.as_str())
+                        .filter(|s| !s.is_empty())
+                    {
                         Some(t) => t.to_string(),
                         None => continue,
                     }
@@ -605,12 +1083,10 @@ impl LarkChannel {
                     Some(t) => t,
                     None => continue,
                 },
-                "image" => {
-                    match extract_image_key(&lark_msg.content) {
-                        Some(key) => format!("[IMAGE:lark_image_key:{key}]"),
-                        None => continue,
-                    }
-                }
+                "image" => match extract_image_key(&lark_msg.content) {
+                    Some(key) => format!("[IMAGE:lark_image_key:{key}]"),
+                    None => continue,
+                },
                 "file" => match extract_file_key_and_name(&lark_msg.content) {
                     Some((key, name)) => {
                         let label = name.as_deref().unwrap_or(&key);
@@ -635,7 +1111,9 @@ impl LarkChannel {
             // Strip @_user_N placeholders
             let text = strip_at_placeholders(&text);
             let text = text.trim().to_string();
-            if text.is_empty() { continue; }
+            if text.is_empty() {
+                continue;
+            }
 
             // Group-chat: only respond when explicitly @-mentioned
             if lark_msg.chat_type == "group" && !should_respond_in_group(&lark_msg.mentions) {
@@ -662,7 +1140,8 @@ impl LarkChannel {
                     }
                     // Current is thread reply replacing a pending non-thread copy.
                     // Cancel the pending delayed send.
-                    if let Some(cancel_tx) = self.pending_cancel.lock().unwrap().remove(&dedup_key) {
+                    if let Some(cancel_tx) = self.pending_cancel.lock().unwrap().remove(&dedup_key)
+                    {
                         let _ = cancel_tx.send(());
                         tracing::debug!(
                             "Lark WS: cancelled pending non-thread msg, using thread reply {}",
@@ -675,32 +1154,44 @@ impl LarkChannel {
                 }
             }
 
-            // Build ACK reaction
-            let ack_emoji = "OK";
-            let reaction_channel = self.clone();
-            let reaction_message_id = lark_msg.message_id.clone();
-            tokio::spawn(async move {
-                reaction_channel
-                    .try_add_ack_reaction(&reaction_message_id, &ack_emoji)
-                    .await;
-            });
-
-            let channel_msg = ChannelMessage {
-                id: Uuid::new_v4().to_string(),
-                sender: lark_msg.chat_id.clone(),
-                reply_target: lark_msg.chat_id.clone(),
-                content: text,
-                channel: self.channel_name().to_string(),
-                timestamp: std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_secs(),
-                thread_ts: if lark_msg.chat_type == "p2p" { Some(lark_msg.message_id.clone()) } else { None },
-            };
+            // Length limit after dedup, so a redelivered or forwarded copy of
+            // an over-long message is rejected once.
+            let inbound = self
+                .apply_inbound_limit(text, &lark_msg.chat_id)
+                .map(|text| {
+                    // Ack only messages that passed the bot/allowlist/mention checks
+                    self.spawn_ack_reaction(&lark_msg.message_id);
+                    #[cfg(feature = "metrics")]
+                    crate::metrics::global().record_lark_message("inbound");
+                    ChannelMessage {
+                        id: Uuid::new_v4().to_string(),
+                        sender: lark_msg.chat_id.clone(),
+                        reply_target: lark_msg.chat_id.clone(),
+                        content: text,
+                        channel: self.channel_name().to_string(),
+                        timestamp: std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs(),
+                        thread_ts: if lark_msg.chat_type == "p2p" {
+                            Some(lark_msg.message_id.clone())
+                        } else {
+                            None
+                        },
+                    }
+                });
 
             if is_thread_reply {
                 // Thread reply: send immediately.
                 tracing::debug!("Lark WS: thread reply {}", lark_msg.message_id);
+                let channel_msg = match inbound {
+                    Ok(msg) => msg,
+                    Err(reply) => {
+                        let channel = self.clone();
+                        tokio::spawn(async move { channel.send_inbound_rejection(&reply).await });
+                        continue;
+                    }
+                };
                 match tx.try_send(channel_msg) {
                     Ok(()) => {}
                     Err(tokio::sync::mpsc::error::TrySendError::Full(msg)) => {
@@ -715,13 +1206,22 @@ impl LarkChannel {
             } else {
                 // Non-thread message: delay 100ms so a thread reply can supersede it.
                 let (cancel_tx, cancel_rx) = tokio::sync::oneshot::channel::<()>();
-                self.pending_cancel.lock().unwrap().insert(dedup_key, cancel_tx);
+                self.pending_cancel
+                    .lock()
+                    .unwrap()
+                    .insert(dedup_key, cancel_tx);
                 let tx2 = tx.clone();
+                let channel = self.clone();
                 tokio::spawn(async move {
                     tokio::select! {
                         _ = tokio::time::sleep(Duration::from_millis(200)) => {
                             // Grace window elapsed, no thread reply arrived — send it.
-                            let _ = tx2.send(channel_msg).await;
+                            match inbound {
+                                Ok(channel_msg) => {
+                                    let _ = tx2.send(channel_msg).await;
+                                }
+                                Err(reply) => channel.send_inbound_rejection(&reply).await,
+                            }
                         }
                         _ = cancel_rx => {
                             // Cancelled by an arriving thread reply — drop this copy.
@@ -741,14 +1241,15 @@ impl LarkChannel {
 
     /// Get or refresh tenant access token
     async fn get_tenant_access_token(&self) -> anyhow::Result<String> {
-        // Check cache first
-        {
-            let cached = self.tenant_token.read().await;
-            if let Some(ref token) = *cached {
-                if Instant::now() < token.refresh_after {
-                    return Ok(token.value.clone());
-                }
-            }
+        if let Some(token) = self.cached_tenant_token().await {
+            return Ok(token);
+        }
+
+        // Single-flight: whoever gets the lock first fetches; the rest find
+        // the fresh token in the cache once it is released.
+        let _refresh = self.token_refresh.lock().await;
+        if let Some(token) = self.cached_tenant_token().await {
+            return Ok(token);
         }
 
         let url = self.tenant_access_token_url();
@@ -762,7 +1263,10 @@ impl LarkChannel {
         let data: serde_json::Value = resp.json().await?;
 
         if !status.is_success() {
-            anyhow::bail!("Lark tenant_access_token request failed: status={status}, body={data}");
+            anyhow::bail!(
+                "Lark tenant_access_token request failed: status={status}, body={}",
+                redact_json_for_log(&data)
+            );
         }
 
         let code = data.get("code").and_then(|c| c.as_i64()).unwrap_or(-1);
@@ -771,7 +1275,7 @@ impl LarkChannel {
                 .get("msg")
                 .and_then(|m| m.as_str())
                 .unwrap_or("unknown error");
-            anyhow::bail!("Lark tenant_access_token failed: {msg}");
+            anyhow::bail!("Lark tenant_access_token failed: {}", redact_log_body(msg));
         }
 
         let token = data
@@ -781,7 +1285,7 @@ impl LarkChannel {
             .to_string();
 
         let ttl_seconds = extract_lark_token_ttl_seconds(&data);
-        let refresh_after = next_token_refresh_deadline(Instant::now(), ttl_seconds);
+        let refresh_after = next_token_refresh_deadline(self.clock.now(), ttl_seconds);
 
         // Cache it with proactive refresh metadata.
         {
@@ -795,6 +1299,15 @@ impl LarkChannel {
         Ok(token)
     }
 
+    /// The cached tenant token, if it is not yet due for refresh.
+    async fn cached_tenant_token(&self) -> Option<String> {
+        let cached = self.tenant_token.read().await;
+        cached
+            .as_ref()
+            .filter(|token| self.clock.now() < token.refresh_after)
+            .map(|token| token.value.clone())
+    }
+
     /// Invalidate cached token (called when API reports an expired tenant token).
     async fn invalidate_token(&self) {
         let mut cached = self.tenant_token.write().await;
@@ -821,11 +1334,17 @@ impl LarkChannel {
         let status = resp.status();
         let body: serde_json::Value = resp.json().await?;
         if !status.is_success() {
-            anyhow::bail!("Lark upload_image failed: status={status}, body={body}");
+            anyhow::bail!(
+                "Lark upload_image failed: status={status}, body={}",
+                redact_json_for_log(&body)
+            );
         }
         let code = extract_lark_response_code(&body).unwrap_or(-1);
         if code != 0 {
-            anyhow::bail!("Lark upload_image failed: code={code}, body={body}");
+            anyhow::bail!(
+                "Lark upload_image failed: code={code}, body={}",
+                redact_json_for_log(&body)
+            );
         }
         body.pointer("/data/image_key")
             .and_then(|k| k.as_str())
@@ -880,10 +1399,38 @@ impl LarkChannel {
         }
         Ok(bytes)
     }
-    /// Send an image message by image_key.
-    async fn send_image_msg(&self, chat_id: &str, image_key: &str) -> anyhow::Result<()> {
+    /// Download the resource behind an inbound attachment marker such as
+    /// `[IMAGE:lark_image_key:…]` or `[DOCUMENT:lark_file_key:…:report.pdf]`.
+    ///
+    /// Images go through the image endpoint; documents, audio and video use
+    /// the file endpoint. The MIME type is inferred from the file name, then
+    /// from the content.
+    pub async fn fetch_attachment(
+        &self,
+        message_id: &str,
+        marker: &str,
+    ) -> anyhow::Result<FetchedAttachment> {
+        let Some(attachment) = parse_inbound_attachment_marker(marker) else {
+            anyhow::bail!("Lark: not an inbound attachment marker: {marker}");
+        };
+        let bytes = match attachment.kind {
+            LarkAttachmentKind::Image => self.download_image(message_id, &attachment.key).await?,
+            _ => {
+                self.download_file(message_id, &attachment.key, attachment.resource_type())
+                    .await?
+            }
+        };
+        let mime = infer_attachment_mime(attachment.kind, attachment.name.as_deref(), &bytes);
+        Ok(FetchedAttachment { bytes, mime })
+    }
+    /// Send an image message by image_key. Returns the message ID.
+    async fn send_image_msg(
+        &self,
+        chat_id: &str,
+        image_key: &str,
+    ) -> anyhow::Result<Option<String>> {
         let token = self.get_tenant_access_token().await?;
-        let url = self.send_message_url();
+        let url = self.send_message_url(chat_id);
         let content = serde_json::json!({ "image_key": image_key }).to_string();
         let body = serde_json::json!({
             "receive_id": chat_id,
@@ -896,13 +1443,13 @@ impl LarkChannel {
             let new_token = self.get_tenant_access_token().await?;
             let (rs, rr) = self.send_text_once(&url, &new_token, &body).await?;
             ensure_lark_send_success(rs, &rr, "image after token refresh")?;
-            return Ok(());
+            return Ok(response_message_id(&rr));
         }
         ensure_lark_send_success(status, &response, "image")?;
-        Ok(())
+        Ok(response_message_id(&response))
     }
     /// Upload a local file or download a URL, then send as image message.
-    async fn send_lark_image(&self, chat_id: &str, target: &str) -> anyhow::Result<()> {
+    async fn send_lark_image(&self, chat_id: &str, target: &str) -> anyhow::Result<Option<String>> {
         let (bytes, filename) = if target.starts_with("http://") || target.starts_with("https://") {
             let resp = self.http_client().get(target).send().await?;
             if !resp.status().is_success() {
@@ -957,21 +1504,27 @@ impl LarkChannel {
         let status = resp.status();
         let body: serde_json::Value = resp.json().await?;
         if !status.is_success() {
-            anyhow::bail!("Lark upload_file failed: status={status}, body={body}");
+            anyhow::bail!(
+                "Lark upload_file failed: status={status}, body={}",
+                redact_json_for_log(&body)
+            );
         }
         let code = extract_lark_response_code(&body).unwrap_or(-1);
         if code != 0 {
-            anyhow::bail!("Lark upload_file failed: code={code}, body={body}");
+            anyhow::bail!(
+                "Lark upload_file failed: code={code}, body={}",
+                redact_json_for_log(&body)
+            );
         }
         body.pointer("/data/file_key")
             .and_then(|k| k.as_str())
             .map(String::from)
             .ok_or_else(|| anyhow::anyhow!("Lark upload_file: missing file_key in response"))
     }
-    /// Send a file message by file_key.
-    async fn send_file_msg(&self, chat_id: &str, file_key: &str) -> anyhow::Result<()> {
+    /// Send a file message by file_key. Returns the message ID.
+    async fn send_file_msg(&self, chat_id: &str, file_key: &str) -> anyhow::Result<Option<String>> {
         let token = self.get_tenant_access_token().await?;
-        let url = self.send_message_url();
+        let url = self.send_message_url(chat_id);
         let content = serde_json::json!({ "file_key": file_key }).to_string();
         let body = serde_json::json!({
             "receive_id": chat_id,
@@ -984,15 +1537,19 @@ impl LarkChannel {
             let new_token = self.get_tenant_access_token().await?;
             let (rs, rr) = self.send_text_once(&url, &new_token, &body).await?;
             ensure_lark_send_success(rs, &rr, "file after token refresh")?;
-            return Ok(());
+            return Ok(response_message_id(&rr));
         }
         ensure_lark_send_success(status, &response, "file")?;
-        Ok(())
+        Ok(response_message_id(&response))
     }
     /// Send an audio message by file_key.
-    async fn send_audio_msg(&self, chat_id: &str, file_key: &str) -> anyhow::Result<()> {
+    async fn send_audio_msg(
+        &self,
+        chat_id: &str,
+        file_key: &str,
+    ) -> anyhow::Result<Option<String>> {
         let token = self.get_tenant_access_token().await?;
-        let url = self.send_message_url();
+        let url = self.send_message_url(chat_id);
         let content = serde_json::json!({ "file_key": file_key }).to_string();
         let body = serde_json::json!({
             "receive_id": chat_id,
@@ -1005,15 +1562,19 @@ impl LarkChannel {
             let new_token = self.get_tenant_access_token().await?;
             let (rs, rr) = self.send_text_once(&url, &new_token, &body).await?;
             ensure_lark_send_success(rs, &rr, "audio after token refresh")?;
-            return Ok(());
+            return Ok(response_message_id(&rr));
         }
         ensure_lark_send_success(status, &response, "audio")?;
-        Ok(())
+        Ok(response_message_id(&response))
     }
-    /// Send a media (video) message by file_key.
-    async fn send_media_msg(&self, chat_id: &str, file_key: &str) -> anyhow::Result<()> {
+    /// Send a media (video) message by file_key. Returns the message ID.
+    async fn send_media_msg(
+        &self,
+        chat_id: &str,
+        file_key: &str,
+    ) -> anyhow::Result<Option<String>> {
         let token = self.get_tenant_access_token().await?;
-        let url = self.send_message_url();
+        let url = self.send_message_url(chat_id);
         let content = serde_json::json!({ "file_key": file_key }).to_string();
         let body = serde_json::json!({
             "receive_id": chat_id,
@@ -1026,17 +1587,17 @@ impl LarkChannel {
             let new_token = self.get_tenant_access_token().await?;
             let (rs, rr) = self.send_text_once(&url, &new_token, &body).await?;
             ensure_lark_send_success(rs, &rr, "media after token refresh")?;
-            return Ok(());
+            return Ok(response_message_id(&rr));
         }
         ensure_lark_send_success(status, &response, "media")?;
-        Ok(())
+        Ok(response_message_id(&response))
     }
     /// Upload a local file or download a URL, then send as the appropriate message type.
     async fn send_lark_attachment(
         &self,
         chat_id: &str,
         attachment: &LarkAttachment,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<Option<String>> {
         let target = attachment.target.trim();
         let (bytes, filename) = if target.starts_with("http://") || target.starts_with("https://") {
             let resp = self.http_client().get(target).send().await?;
@@ -1070,43 +1631,194 @@ impl LarkChannel {
             LarkAttachmentKind::Video => self.send_media_msg(chat_id, &file_key).await,
         }
     }
-    async fn send_text_once(
+    /// Log-and-continue send of one outgoing attachment. Returns the message
+    /// ID when the send succeeded.
+    async fn send_attachment_logged(
         &self,
-        url: &str,
-        token: &str,
-        body: &serde_json::Value,
-    ) -> anyhow::Result<(reqwest::StatusCode, serde_json::Value)> {
-        let resp = self
-            .http_client()
-            .post(url)
-            .header("Authorization", format!("Bearer {token}"))
-            .header("Content-Type", "application/json; charset=utf-8")
-            .json(body)
-            .send()
-            .await?;
-        let status = resp.status();
-        let raw = resp.text().await.unwrap_or_default();
-        let parsed = serde_json::from_str::<serde_json::Value>(&raw)
-            .unwrap_or_else(|_| serde_json::json!({ "raw": raw }));
-        Ok((status, parsed))
+        recipient: &str,
+        att: &LarkAttachment,
+    ) -> Option<String> {
+        let result = match att.kind {
+            LarkAttachmentKind::Image => self.send_lark_image(recipient, &att.target).await,
+            _ => self.send_lark_attachment(recipient, att).await,
+        };
+        result.unwrap_or_else(|e| {
+            tracing::warn!("Lark: {:?} send failed for {}: {e}", att.kind, att.target);
+            None
+        })
     }
 
-    /// Parse an event callback payload and extract text messages
-    pub fn parse_event_payload(&self, payload: &serde_json::Value) -> Vec<ChannelMessage> {
-        let mut messages = Vec::new();
-
-        // Lark event v2 structure:
-        // { "header": { "event_type": "im.message.receive_v1" }, "event": { "message": { ... }, "sender": { ... } } }
-        let event_type = payload
-            .pointer("/header/event_type")
-            .and_then(|e| e.as_str())
-            .unwrap_or("");
-
-        if event_type != "im.message.receive_v1" {
-            return messages;
-        }
-
-        let event = match payload.get("event") {
+    /// Send marker-free `text` to `message.recipient` (thread reply when
+    /// `thread_ts` is set, otherwise cards) and return the last message ID.
+    async fn send_text_content(
+        &self,
+        message: &SendMessage,
+        text: &str,
+    ) -> anyhow::Result<Option<String>> {
+        // If thread_ts is set (P2P message), reply via reply API with plain text
+        if let Some(ref msg_id) = message.thread_ts {
+            match self.reply_text(msg_id, text).await {
+                Err(e) => {
+                    tracing::warn!("Lark reply_text failed, falling back to card send: {e}");
+                    // Fall through to existing card-based send below
+                }
+                Ok(reply_id) => {
+                    #[cfg(feature = "metrics")]
+                    crate::metrics::global().record_lark_message("outbound");
+                    return Ok(reply_id);
+                }
+            }
+        }
+        let sections = split_lark_sections(text);
+        let token = self.get_tenant_access_token().await?;
+        let url = self.send_message_url(&message.recipient);
+        let mut last_message_id = None;
+        for (title, body) in &sections {
+            if body.is_empty() && title.is_none() {
+                continue;
+            }
+            let elements = build_lark_card_elements(body);
+            let mut card = serde_json::json!({
+                "elements": elements
+            });
+            if let Some(t) = title {
+                card["header"] = serde_json::json!({
+                    "title": { "tag": "plain_text", "content": t }
+                });
+            }
+            let content = card.to_string();
+            let msg_body = serde_json::json!({
+                "receive_id": message.recipient,
+                "msg_type": "interactive",
+                "content": content,
+            });
+            let (status, response) = self.send_text_once(&url, &token, &msg_body).await?;
+            if should_refresh_lark_tenant_token(status, &response) {
+                self.invalidate_token().await;
+                let new_token = self.get_tenant_access_token().await?;
+                let (rs, rr) = self.send_text_once(&url, &new_token, &msg_body).await?;
+                if should_refresh_lark_tenant_token(rs, &rr) {
+                    anyhow::bail!(
+                        "Lark send failed after token refresh: status={rs}, body={}",
+                        redact_json_for_log(&rr)
+                    );
+                }
+                ensure_lark_send_success(rs, &rr, "after token refresh")?;
+                last_message_id = response_message_id(&rr);
+            } else {
+                ensure_lark_send_success(status, &response, "without token refresh")?;
+                last_message_id = response_message_id(&response);
+            }
+        }
+        #[cfg(feature = "metrics")]
+        crate::metrics::global().record_lark_message("outbound");
+        Ok(last_message_id)
+    }
+
+    async fn send_text_once(
+        &self,
+        url: &str,
+        token: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<(reqwest::StatusCode, serde_json::Value)> {
+        let resp = self
+            .http_client()
+            .post(url)
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Content-Type", "application/json; charset=utf-8")
+            .json(body)
+            .send()
+            .await?;
+        let status = resp.status();
+        let raw = resp.text().await.unwrap_or_default();
+        let parsed = serde_json::from_str::<serde_json::Value>(&raw)
+            .unwrap_or_else(|_| serde_json::json!({ "raw": redact_log_body(&raw) }));
+        Ok((status, parsed))
+    }
+
+    /// Parse a `card.action.trigger` callback payload.
+    ///
+    /// Returns `None` for other event types, malformed payloads and users
+    /// outside `allowed_users`.
+    pub fn parse_card_action(&self, payload: &serde_json::Value) -> Option<LarkCardAction> {
+        // { "header": { "event_type": "card.action.trigger", "create_time": "..." },
+        //   "event": { "operator": { "open_id": ... }, "action": { "tag": ..., "value": ... },
+        //              "context": { "open_message_id": ..., "open_chat_id": ... } } }
+        let event_type = payload.pointer("/header/event_type")?.as_str()?;
+        if event_type != LARK_CARD_ACTION_EVENT {
+            return None;
+        }
+        let event = payload.get("event")?;
+
+        let open_id = event
+            .pointer("/operator/open_id")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())?;
+        if !self.is_user_allowed(open_id) {
+            tracing::warn!("Lark: ignoring card action from unauthorized user: {open_id}");
+            return None;
+        }
+
+        let action = event.get("action")?;
+        let str_at = |pointer: &str| {
+            event
+                .pointer(pointer)
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string()
+        };
+        let chat_id = str_at("/context/open_chat_id");
+        let timestamp = payload
+            .pointer("/header/create_time")
+            .and_then(|t| t.as_str())
+            .and_then(|t| t.parse::<u64>().ok())
+            .map_or_else(unix_now_secs, |ms| ms / 1000);
+
+        Some(LarkCardAction {
+            operator_open_id: open_id.to_string(),
+            chat_id: if chat_id.is_empty() {
+                open_id.to_string()
+            } else {
+                chat_id
+            },
+            message_id: str_at("/context/open_message_id"),
+            tag: action
+                .get("tag")
+                .and_then(|t| t.as_str())
+                .unwrap_or("button")
+                .to_string(),
+            value: action
+                .get("value")
+                .cloned()
+                .unwrap_or(serde_json::Value::Null),
+            timestamp,
+        })
+    }
+
+    /// Parse an event callback payload and extract text messages
+    pub fn parse_event_payload(&self, payload: &serde_json::Value) -> Vec<ChannelMessage> {
+        let mut messages = Vec::new();
+
+        // Lark event v2 structure:
+        // { "header": { "event_type": "im.message.receive_v1" }, "event": { "message": { ... }, "sender": { ... } } }
+        let event_type = payload
+            .pointer("/header/event_type")
+            .and_then(|e| e.as_str())
+            .unwrap_or("");
+
+        if event_type == LARK_CARD_ACTION_EVENT {
+            messages.extend(
+                self.parse_card_action(payload)
+                    .map(|action| action.into_channel_message(self.channel_name())),
+            );
+            return messages;
+        }
+
+        if event_type != "im.message.receive_v1" {
+            return messages;
+        }
+
+        let event = match payload.get("event") {
             Some(e) => e,
             None => return messages,
         };
@@ -1224,6 +1936,11 @@ impl LarkChannel {
             .filter(|s| !s.is_empty());
         let is_thread_reply = root_id.is_some() || parent_id.is_some();
 
+        let chat_id = event
+            .pointer("/message/chat_id")
+            .and_then(|c| c.as_str())
+            .unwrap_or(open_id);
+
         // Thread-reply dedup (parity with WS path)
         {
             let now = Instant::now();
@@ -1257,11 +1974,6 @@ impl LarkChannel {
                     .as_secs()
             });
 
-        let chat_id = event
-            .pointer("/message/chat_id")
-            .and_then(|c| c.as_str())
-            .unwrap_or(open_id);
-
         let message_id = event
             .pointer("/message/message_id")
             .and_then(|v| v.as_str())
@@ -1274,7 +1986,11 @@ impl LarkChannel {
             content: text,
             channel: self.channel_name().to_string(),
             timestamp,
-            thread_ts: if chat_type == "p2p" { Some(message_id.to_string()) } else { None },
+            thread_ts: if chat_type == "p2p" {
+                Some(message_id.to_string())
+            } else {
+                None
+            },
         });
 
         messages
@@ -1310,7 +2026,12 @@ impl LarkChannel {
             .ok_or_else(|| anyhow::anyhow!("Lark create_card: missing card_id in response"))
     }
     /// Update an existing CardKit card entity with new content (whole-card replacement).
-    async fn update_card_whole(&self, card_id: &str, content_json: &str, sequence: u64) -> anyhow::Result<()> {
+    async fn update_card_whole(
+        &self,
+        card_id: &str,
+        content_json: &str,
+        sequence: u64,
+    ) -> anyhow::Result<()> {
         let token = self.get_tenant_access_token().await?;
         let url = format!("{}/{card_id}", self.cardkit_url());
         let body = serde_json::json!({
@@ -1329,7 +2050,7 @@ impl LarkChannel {
         let status = resp.status();
         let raw = resp.text().await.unwrap_or_default();
         let parsed = serde_json::from_str::<serde_json::Value>(&raw)
-            .unwrap_or_else(|_| serde_json::json!({ "raw": raw }));
+            .unwrap_or_else(|_| serde_json::json!({ "raw": redact_log_body(&raw) }));
         if should_refresh_lark_tenant_token(status, &parsed) {
             self.invalidate_token().await;
             let new_token = self.get_tenant_access_token().await?;
@@ -1350,9 +2071,17 @@ impl LarkChannel {
         Ok(())
     }
     /// Update a single element's content in a CardKit streaming card.
-    async fn update_card_element(&self, card_id: &str, text: &str, sequence: u64) -> anyhow::Result<()> {
+    async fn update_card_element(
+        &self,
+        card_id: &str,
+        text: &str,
+        sequence: u64,
+    ) -> anyhow::Result<()> {
         let token = self.get_tenant_access_token().await?;
-        let url = format!("{}/{card_id}/elements/{STREAMING_ELEMENT_ID}/content", self.cardkit_url());
+        let url = format!(
+            "{}/{card_id}/elements/{STREAMING_ELEMENT_ID}/content",
+            self.cardkit_url()
+        );
         let uuid = format!("s_{card_id}_{sequence}");
         let body = serde_json::json!({
             "content": text,
@@ -1370,7 +2099,7 @@ impl LarkChannel {
         let status = resp.status();
         let raw = resp.text().await.unwrap_or_default();
         let parsed = serde_json::from_str::<serde_json::Value>(&raw)
-            .unwrap_or_else(|_| serde_json::json!({ "raw": raw }));
+            .unwrap_or_else(|_| serde_json::json!({ "raw": redact_log_body(&raw) }));
         if should_refresh_lark_tenant_token(status, &parsed) {
             self.invalidate_token().await;
             let new_token = self.get_tenant_access_token().await?;
@@ -1391,7 +2120,12 @@ impl LarkChannel {
         Ok(())
     }
     /// Close streaming mode on a CardKit card via settings PATCH.
-    async fn close_streaming(&self, card_id: &str, summary: &str, sequence: u64) -> anyhow::Result<()> {
+    async fn close_streaming(
+        &self,
+        card_id: &str,
+        summary: &str,
+        sequence: u64,
+    ) -> anyhow::Result<()> {
         let token = self.get_tenant_access_token().await?;
         let url = format!("{}/{card_id}/settings", self.cardkit_url());
         let uuid = format!("c_{card_id}_{sequence}");
@@ -1418,7 +2152,7 @@ impl LarkChannel {
         let status = resp.status();
         let raw = resp.text().await.unwrap_or_default();
         let parsed = serde_json::from_str::<serde_json::Value>(&raw)
-            .unwrap_or_else(|_| serde_json::json!({ "raw": raw }));
+            .unwrap_or_else(|_| serde_json::json!({ "raw": redact_log_body(&raw) }));
         if should_refresh_lark_tenant_token(status, &parsed) {
             self.invalidate_token().await;
             let new_token = self.get_tenant_access_token().await?;
@@ -1441,7 +2175,7 @@ impl LarkChannel {
     /// Send a card message referencing an existing CardKit card_id. Returns message_id.
     async fn send_card_message(&self, recipient: &str, card_id: &str) -> anyhow::Result<String> {
         let token = self.get_tenant_access_token().await?;
-        let url = self.send_message_url();
+        let url = self.send_message_url(recipient);
         let content = serde_json::json!({
             "type": "card",
             "data": {
@@ -1475,7 +2209,8 @@ impl LarkChannel {
     }
 
     /// Reply to a message with plain text via the Feishu reply API.
-    async fn reply_text(&self, message_id: &str, text: &str) -> anyhow::Result<()> {
+    /// Returns the reply's message_id when the response includes one.
+    async fn reply_text(&self, message_id: &str, text: &str) -> anyhow::Result<Option<String>> {
         let token = self.get_tenant_access_token().await?;
         let url = self.reply_message_url(message_id);
         let content = serde_json::json!({ "text": text }).to_string();
@@ -1490,10 +2225,90 @@ impl LarkChannel {
             let new_token = self.get_tenant_access_token().await?;
             let (rs, rr) = self.send_text_once(&url, &new_token, &body).await?;
             ensure_lark_send_success(rs, &rr, "reply_text after token refresh")?;
-        } else {
-            ensure_lark_send_success(status, &response, "reply_text")?;
+            return Ok(response_message_id(&rr));
+        }
+        ensure_lark_send_success(status, &response, "reply_text")?;
+        Ok(response_message_id(&response))
+    }
+
+    /// Open IDs of the users in `chat_id`, following pagination.
+    async fn chat_member_open_ids(
+        &self,
+        token: &str,
+        chat_id: &str,
+    ) -> anyhow::Result<Vec<String>> {
+        let mut open_ids = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut request = self
+                .http_client()
+                .get(self.chat_members_url(chat_id))
+                .header("Authorization", format!("Bearer {token}"));
+            if let Some(page_token) = &page_token {
+                request = request.query(&[("page_token", page_token)]);
+            }
+            let resp = request.send().await?;
+            let status = resp.status();
+            let body: serde_json::Value = resp.json().await.unwrap_or_default();
+            ensure_lark_send_success(status, &body, "chat_members")?;
+
+            let data = &body["data"];
+            open_ids.extend(
+                data["items"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|item| item["member_id"].as_str())
+                    .map(ToString::to_string),
+            );
+            page_token = data["page_token"]
+                .as_str()
+                .filter(|t| data["has_more"].as_bool() == Some(true) && !t.is_empty())
+                .map(ToString::to_string);
+            if page_token.is_none() {
+                return Ok(open_ids);
+            }
+        }
+    }
+
+    /// Send the configured urgent notification for `message_id` to
+    /// `recipient`, the chat or user it was delivered to. A chat escalates to
+    /// all of its user members. Failures are only logged: the message itself
+    /// has already been delivered.
+    async fn escalate_urgent(&self, message_id: &str, recipient: &str) {
+        let kind = match self.urgent_escalation {
+            LarkUrgentEscalation::Off => return,
+            LarkUrgentEscalation::App => "urgent_app",
+            LarkUrgentEscalation::Sms => "urgent_sms",
+        };
+        let result = async {
+            let token = self.get_tenant_access_token().await?;
+            let (user_id_type, user_ids) = match lark_receive_id_type(recipient) {
+                "chat_id" => (
+                    "open_id",
+                    self.chat_member_open_ids(&token, recipient).await?,
+                ),
+                id_type => (id_type, vec![recipient.to_string()]),
+            };
+            if user_ids.is_empty() {
+                tracing::debug!("Lark: chat {recipient} has no user members; skipping {kind}");
+                return Ok(());
+            }
+            let resp = self
+                .http_client()
+                .patch(self.urgent_message_url(message_id, kind, user_id_type))
+                .header("Authorization", format!("Bearer {token}"))
+                .json(&serde_json::json!({ "user_id_list": user_ids }))
+                .send()
+                .await?;
+            let status = resp.status();
+            let body: serde_json::Value = resp.json().await.unwrap_or_default();
+            ensure_lark_send_success(status, &body, kind)
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("Lark: {kind} failed for {message_id}: {e}");
         }
-        Ok(())
     }
 
     // ── Typing indicator helpers ───────────────────────────────────────────
@@ -1515,13 +2330,19 @@ impl LarkChannel {
         let card_id = match self.create_card(&card_json).await {
             Ok(id) => id,
             Err(e) => {
-                tracing::warn!("[{}] start_typing: CardKit unavailable, skipping: {e}", self.channel_name());
+                tracing::warn!(
+                    "[{}] start_typing: CardKit unavailable, skipping: {e}",
+                    self.channel_name()
+                );
                 return Ok(());
             }
         };
 
         if let Err(e) = self.send_card_message(recipient, &card_id).await {
-            tracing::warn!("[{}] start_typing: failed to send typing card: {e}", self.channel_name());
+            tracing::warn!(
+                "[{}] start_typing: failed to send typing card: {e}",
+                self.channel_name()
+            );
             return Ok(());
         }
 
@@ -1569,77 +2390,52 @@ impl Channel for LarkChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let (text, attachments) = parse_lark_attachment_markers(&message.content);
-        for att in &attachments {
-            let result = match att.kind {
-                LarkAttachmentKind::Image => {
-                    self.send_lark_image(&message.recipient, &att.target).await
+        if self.interleave_attachments {
+            let mut last_message_id = None;
+            for part in split_lark_outbound_parts(&message.content) {
+                match part {
+                    LarkOutboundPart::Text(text) => {
+                        if let Some(id) = self.send_text_content(message, &text).await? {
+                            last_message_id = Some(id);
+                        }
+                    }
+                    LarkOutboundPart::Attachment(att) => {
+                        if let Some(id) =
+                            self.send_attachment_logged(&message.recipient, &att).await
+                        {
+                            last_message_id = Some(id);
+                        }
+                    }
                 }
-                _ => self.send_lark_attachment(&message.recipient, att).await,
-            };
-            if let Err(e) = result {
-                tracing::warn!(
-                    "Lark: {:?} send failed for {}: {e}",
-                    att.kind,
-                    att.target
-                );
             }
-        }
-        if text.is_empty() {
+            if let (true, Some(message_id)) = (message.urgent, last_message_id) {
+                self.escalate_urgent(&message_id, &message.recipient).await;
+            }
             return Ok(());
         }
-        // If thread_ts is set (P2P message), reply via reply API with plain text
-        if let Some(ref msg_id) = message.thread_ts {
-            if let Err(e) = self.reply_text(msg_id, &text).await {
-                tracing::warn!("Lark reply_text failed, falling back to card send: {e}");
-                // Fall through to existing card-based send below
-            } else {
-                return Ok(());
+        let (text, attachments) = parse_lark_attachment_markers(&message.content);
+        let mut last_message_id = None;
+        for att in &attachments {
+            if let Some(id) = self.send_attachment_logged(&message.recipient, att).await {
+                last_message_id = Some(id);
             }
         }
-        let sections = split_lark_sections(&text);
-        let token = self.get_tenant_access_token().await?;
-        let url = self.send_message_url();
-        for (title, body) in &sections {
-            if body.is_empty() && title.is_none() {
-                continue;
-            }
-            let elements = build_lark_card_elements(body);
-            let mut card = serde_json::json!({
-                "elements": elements
-            });
-            if let Some(t) = title {
-                card["header"] = serde_json::json!({
-                    "title": { "tag": "plain_text", "content": t }
-                });
-            }
-            let content = card.to_string();
-            let msg_body = serde_json::json!({
-                "receive_id": message.recipient,
-                "msg_type": "interactive",
-                "content": content,
-            });
-            let (status, response) = self.send_text_once(&url, &token, &msg_body).await?;
-            if should_refresh_lark_tenant_token(status, &response) {
-                self.invalidate_token().await;
-                let new_token = self.get_tenant_access_token().await?;
-                let (rs, rr) = self.send_text_once(&url, &new_token, &msg_body).await?;
-                if should_refresh_lark_tenant_token(rs, &rr) {
-                    anyhow::bail!("Lark send failed after token refresh: status={rs}, body={rr}");
-                }
-                ensure_lark_send_success(rs, &rr, "after token refresh")?;
-            } else {
-                ensure_lark_send_success(status, &response, "without token refresh")?;
+        if !text.is_empty() {
+            if let Some(id) = self.send_text_content(message, &text).await? {
+                last_message_id = Some(id);
             }
         }
+        if let (true, Some(message_id)) = (message.urgent, last_message_id) {
+            self.escalate_urgent(&message_id, &message.recipient).await;
+        }
         Ok(())
     }
 
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
         use crate::config::schema::LarkReceiveMode;
         match self.receive_mode {
-            LarkReceiveMode::Websocket => self.listen_ws(tx).await,
-            LarkReceiveMode::Webhook => self.listen_http(tx).await,
+            LarkReceiveMode::Websocket => self.listen_ws(tx, self.shutdown.clone()).await,
+            LarkReceiveMode::Webhook => self.listen_http(tx, self.shutdown.clone()).await,
         }
     }
 
@@ -1650,6 +2446,22 @@ impl Channel for LarkChannel {
         self.stream_mode != StreamMode::Off
     }
 
+    fn capabilities(&self) -> ChannelCapabilities {
+        ChannelCapabilities {
+            draft_updates: self.supports_draft_updates(),
+            attachments: vec![
+                AttachmentKind::Image,
+                AttachmentKind::Document,
+                AttachmentKind::Audio,
+                AttachmentKind::Video,
+            ],
+            reactions: false,
+            ack_reactions: true,
+            threads: true,
+            typing: false,
+        }
+    }
+
     async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
         if self.stream_mode == StreamMode::Off {
             return Ok(None);
@@ -1658,7 +2470,7 @@ impl Channel for LarkChannel {
         let initial_text = if message.content.is_empty() {
             "...".to_string()
         } else {
-            lark_headers_to_bold(&message.content)
+            truncate_card_content(lark_headers_to_bold(&message.content))
         };
 
         let card_json = serde_json::json!({
@@ -1690,7 +2502,14 @@ impl Channel for LarkChannel {
         };
 
         match self.send_card_message(&message.recipient, &card_id).await {
-            Ok(_msg_id) => {
+            Ok(msg_id) => {
+                if message.urgent {
+                    self.urgent_drafts
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .insert(card_id.clone());
+                    self.escalate_urgent(&msg_id, &message.recipient).await;
+                }
                 self.card_sequence
                     .lock()
                     .unwrap_or_else(|e| e.into_inner())
@@ -1713,31 +2532,29 @@ impl Channel for LarkChannel {
         draft_id: &str,
         text: &str,
     ) -> anyhow::Result<()> {
-        // Throttle: skip update if too soon since last one
-        {
-            let last_updates = self.last_draft_update.lock().unwrap_or_else(|e| e.into_inner());
-            if let Some(last_time) = last_updates.get(draft_id) {
-                let elapsed = u64::try_from(last_time.elapsed().as_millis()).unwrap_or(u64::MAX);
-                if elapsed < self.draft_update_interval_ms.max(500) {
-                    return Ok(());
-                }
-            }
+        let urgent = self
+            .urgent_drafts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(draft_id);
+        if self.draft_update_debounce_ms > 0 && !urgent {
+            self.schedule_debounced_draft(draft_id, text);
+            return Ok(());
         }
-        let sequence = {
-            let mut seqs = self.card_sequence.lock().unwrap_or_else(|e| e.into_inner());
-            let seq = seqs.entry(draft_id.to_string()).or_insert(1);
-            *seq += 1;
-            *seq
-        };
-        let text = lark_headers_to_bold(text);
-        if let Err(e) = self.update_card_element(draft_id, &text, sequence).await {
-            tracing::warn!("Lark CardKit update_card_element failed (non-fatal): {e}");
-        } else {
-            self.last_draft_update
+        // Throttle: skip update if too soon since last one, but remember the
+        // text so finalize can still show it.
+        {
+            let mut pending = self
+                .pending_draft_text
                 .lock()
-                .unwrap_or_else(|e| e.into_inner())
-                .insert(draft_id.to_string(), Instant::now());
+                .unwrap_or_else(|e| e.into_inner());
+            if !urgent && self.draft_throttle_remaining(draft_id).is_some() {
+                pending.insert(draft_id.to_string(), text.to_string());
+                return Ok(());
+            }
+            pending.remove(draft_id);
         }
+        self.push_draft_update(draft_id, text).await;
         Ok(())
     }
     async fn finalize_draft(
@@ -1746,6 +2563,29 @@ impl Channel for LarkChannel {
         draft_id: &str,
         text: &str,
     ) -> anyhow::Result<()> {
+        // Make sure the card ends on the final text (or the last update the
+        // throttle skipped) before streaming is closed.
+        let pending = self
+            .pending_draft_text
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(draft_id);
+        let final_text = if text.is_empty() {
+            pending
+        } else {
+            Some(text.to_string())
+        };
+        if let Some(final_text) = final_text {
+            let shown = self
+                .shown_draft_text
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(draft_id)
+                .cloned();
+            if shown.as_deref() != Some(final_text.as_str()) {
+                self.push_draft_update(draft_id, &final_text).await;
+            }
+        }
         let sequence = {
             let mut seqs = self.card_sequence.lock().unwrap_or_else(|e| e.into_inner());
             let seq = seqs.entry(draft_id.to_string()).or_insert(1);
@@ -1769,6 +2609,18 @@ impl Channel for LarkChannel {
             .lock()
             .unwrap_or_else(|e| e.into_inner())
             .remove(draft_id);
+        self.pending_draft_text
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(draft_id);
+        self.shown_draft_text
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(draft_id);
+        self.urgent_drafts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(draft_id);
         Ok(())
     }
     async fn cancel_draft(&self, _recipient: &str, draft_id: &str) -> anyhow::Result<()> {
@@ -1780,6 +2632,18 @@ impl Channel for LarkChannel {
             .lock()
             .unwrap_or_else(|e| e.into_inner())
             .remove(draft_id);
+        self.pending_draft_text
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(draft_id);
+        self.shown_draft_text
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(draft_id);
+        self.urgent_drafts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(draft_id);
         Ok(())
     }
 }
@@ -1787,9 +2651,12 @@ impl Channel for LarkChannel {
 impl LarkChannel {
     /// HTTP callback server (legacy — requires a public endpoint).
     /// Use `listen()` (WS long-connection) for new deployments.
+    ///
+    /// Shuts down gracefully (finishing in-flight callbacks) when `cancel` fires.
     pub async fn listen_http(
         &self,
         tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        cancel: CancellationToken,
     ) -> anyhow::Result<()> {
         use axum::{extract::State, routing::post, Json, Router};
 
@@ -1823,21 +2690,29 @@ impl LarkChannel {
                 return (StatusCode::OK, Json(resp)).into_response();
             }
 
-            // Parse event messages
-            let messages = state.channel.parse_event_payload(&payload);
+            // Parse event messages; the length limit runs after the parser's dedup
+            let mut messages = Vec::new();
+            for mut msg in state.channel.parse_event_payload(&payload) {
+                match state
+                    .channel
+                    .apply_inbound_limit(msg.content, &msg.reply_target)
+                {
+                    Ok(text) => {
+                        msg.content = text;
+                        messages.push(msg);
+                    }
+                    Err(reply) => {
+                        let channel = Arc::clone(&state.channel);
+                        tokio::spawn(async move { channel.send_inbound_rejection(&reply).await });
+                    }
+                }
+            }
             if !messages.is_empty() {
                 if let Some(message_id) = payload
                     .pointer("/event/message/message_id")
                     .and_then(|m| m.as_str())
                 {
-                    let ack_emoji = "OK";
-                    let reaction_channel = Arc::clone(&state.channel);
-                    let reaction_message_id = message_id.to_string();
-                    tokio::spawn(async move {
-                        reaction_channel
-                            .try_add_ack_reaction(&reaction_message_id, &ack_emoji)
-                            .await;
-                    });
+                    state.channel.spawn_ack_reaction(message_id);
                 }
             }
 
@@ -1846,6 +2721,8 @@ impl LarkChannel {
                     tracing::warn!("Lark: message channel closed");
                     break;
                 }
+                #[cfg(feature = "metrics")]
+                crate::metrics::global().record_lark_message("inbound");
             }
 
             (StatusCode::OK, "ok").into_response()
@@ -1869,17 +2746,167 @@ impl LarkChannel {
         tracing::info!("Lark event callback server listening on {addr}");
 
         let listener = tokio::net::TcpListener::bind(addr).await?;
-        axum::serve(listener, app).await?;
+        axum::serve(listener, app)
+            .with_graceful_shutdown(cancel.cancelled_owned())
+            .await?;
 
         Ok(())
     }
 }
 
+/// How long shutdown waits for the dispatcher to accept buffered WS messages.
+const OVERFLOW_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Deliver messages left in the WS overflow buffer before the listener exits.
+async fn drain_overflow(
+    tx: &tokio::sync::mpsc::Sender<ChannelMessage>,
+    overflow: &mut VecDeque<ChannelMessage>,
+) {
+    let pending = overflow.len();
+    let drain = async {
+        while let Some(msg) = overflow.pop_front() {
+            if tx.send(msg).await.is_err() {
+                break;
+            }
+        }
+    };
+    if tokio::time::timeout(OVERFLOW_DRAIN_TIMEOUT, drain)
+        .await
+        .is_err()
+    {
+        tracing::warn!("Lark WS: shutdown drain timed out with {pending} buffered message(s)");
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // WS helper functions
 // ─────────────────────────────────────────────────────────────────────────────
 
+fn unix_now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Outcome of [`ReplayGuard::check`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Delivery {
+    New,
+    /// message_id already handled.
+    Duplicate,
+}
+
+/// WS message_id dedup that survives restarts and reports redeliveries.
+///
+/// Feishu redelivers unacknowledged events from its buffer when the WS
+/// connection comes back. Ids are kept for [`LARK_SEEN_TTL_SECS`]; when a
+/// path is set they are written to disk in batches by [`flush_replay_guard`]
+/// rather than on every message. Duplicates seen in the first
+/// [`LARK_REPLAY_WINDOW_SECS`] after a connect are counted and logged once.
+#[derive(Debug, Default)]
+struct ReplayGuard {
+    /// message_id → unix seconds first seen.
+    seen: HashMap<String, u64>,
+    path: Option<std::path::PathBuf>,
+    /// Ids changed since the last flush.
+    dirty: bool,
+    replay_until: u64,
+    suppressed: usize,
+}
 
+impl ReplayGuard {
+    /// Load persisted ids from `path`; a missing or corrupt file starts empty.
+    fn load(path: std::path::PathBuf) -> Self {
+        let seen = std::fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        Self {
+            seen,
+            path: Some(path),
+            ..Self::default()
+        }
+    }
+
+    /// Serialized ids and their path if they changed since the last call.
+    fn take_snapshot(&mut self) -> Option<(std::path::PathBuf, Vec<u8>)> {
+        if !self.dirty {
+            return None;
+        }
+        let path = self.path.clone()?;
+        let bytes = serde_json::to_vec(&self.seen).ok()?;
+        self.dirty = false;
+        Some((path, bytes))
+    }
+
+    fn in_replay_window(&self, now: u64) -> bool {
+        now < self.replay_until
+    }
+
+    /// Start a replay-prone window after a (re)connect.
+    fn on_connect(&mut self, now: u64) {
+        self.report_suppressed();
+        self.replay_until = now + LARK_REPLAY_WINDOW_SECS;
+    }
+
+    fn report_suppressed(&mut self) {
+        if self.suppressed > 0 {
+            tracing::info!(
+                "Lark WS: suppressed {} redelivered event(s) after reconnect",
+                self.suppressed
+            );
+            self.suppressed = 0;
+        }
+    }
+
+    fn check(&mut self, message_id: &str, now: u64) -> Delivery {
+        let before = self.seen.len();
+        self.seen
+            .retain(|_, t| now.saturating_sub(*t) < LARK_SEEN_TTL_SECS);
+        self.dirty |= self.seen.len() != before;
+        let in_window = self.in_replay_window(now);
+        if !in_window {
+            self.report_suppressed();
+        }
+
+        if self.seen.contains_key(message_id) {
+            if in_window {
+                self.suppressed += 1;
+            }
+            return Delivery::Duplicate;
+        }
+        self.seen.insert(message_id.to_string(), now);
+        self.dirty = true;
+        Delivery::New
+    }
+}
+
+/// Write `guard`'s ids to disk if they changed since the last flush.
+///
+/// Only the snapshot is taken under the lock; the write itself is async and
+/// goes through a temp file so a crash never leaves a truncated store.
+async fn flush_replay_guard(guard: &std::sync::Mutex<ReplayGuard>) {
+    let Some((path, bytes)) = guard.lock().unwrap().take_snapshot() else {
+        return;
+    };
+    let tmp = path.with_extension("json.tmp");
+    let result = async {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&tmp, &bytes).await?;
+        tokio::fs::rename(&tmp, &path).await
+    }
+    .await;
+    if let Err(e) = result {
+        tracing::warn!(
+            "Lark WS: failed to persist seen ids to {}: {e}",
+            path.display()
+        );
+        guard.lock().unwrap().dirty = true;
+    }
+}
 
 /// Compute a fast content hash for thread-reply deduplication.
 fn content_hash(s: &str) -> u64 {
@@ -1981,7 +3008,11 @@ fn extract_file_key(content: &str) -> Option<String> {
 /// Extract `file_key` and optional `file_name` from a Lark file/media message content JSON.
 fn extract_file_key_and_name(content: &str) -> Option<(String, Option<String>)> {
     let v = serde_json::from_str::<serde_json::Value>(content).ok()?;
-    let key = v.get("file_key")?.as_str().filter(|s| !s.is_empty())?.to_string();
+    let key = v
+        .get("file_key")?
+        .as_str()
+        .filter(|s| !s.is_empty())?
+        .to_string();
     let name = v
         .get("file_name")
         .and_then(|n| n.as_str())
@@ -2048,6 +3079,75 @@ struct LarkAttachment {
     target: String,
 }
 
+/// Bytes and inferred MIME type of a downloaded inbound attachment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedAttachment {
+    pub bytes: Vec<u8>,
+    pub mime: String,
+}
+
+/// An attachment marker produced for an inbound Lark message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct InboundAttachment {
+    kind: LarkAttachmentKind,
+    key: String,
+    name: Option<String>,
+}
+
+impl InboundAttachment {
+    /// `type` query parameter for the message resource endpoint.
+    fn resource_type(&self) -> &'static str {
+        match self.kind {
+            LarkAttachmentKind::Image => "image",
+            _ => "file",
+        }
+    }
+}
+
+/// Parse an inbound marker (`[IMAGE:lark_image_key:KEY]`,
+/// `[DOCUMENT:lark_file_key:KEY:NAME]`, `[AUDIO:lark_file_key:KEY]`,
+/// `[VIDEO:lark_file_key:KEY:NAME]`). Surrounding brackets are optional.
+fn parse_inbound_attachment_marker(marker: &str) -> Option<InboundAttachment> {
+    let inner = marker.trim();
+    let inner = inner
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .unwrap_or(inner);
+    let mut parts = inner.splitn(4, ':');
+    let kind = match parts.next()?.trim().to_ascii_uppercase().as_str() {
+        "IMAGE" => LarkAttachmentKind::Image,
+        "DOCUMENT" => LarkAttachmentKind::Document,
+        "AUDIO" => LarkAttachmentKind::Audio,
+        "VIDEO" => LarkAttachmentKind::Video,
+        _ => return None,
+    };
+    let expected_source = match kind {
+        LarkAttachmentKind::Image => "lark_image_key",
+        _ => "lark_file_key",
+    };
+    if parts.next()? != expected_source {
+        return None;
+    }
+    let key = parts.next().filter(|k| !k.is_empty())?.to_string();
+    let name = parts.next().filter(|n| !n.is_empty()).map(String::from);
+    Some(InboundAttachment { kind, key, name })
+}
+
+/// Best-effort MIME type for a downloaded attachment.
+fn infer_attachment_mime(kind: LarkAttachmentKind, name: Option<&str>, bytes: &[u8]) -> String {
+    if let Some(mime) = name.and_then(|n| mime_guess::from_path(n).first()) {
+        return mime.essence_str().to_string();
+    }
+    let sniffed = match kind {
+        LarkAttachmentKind::Image => crate::multimodal::mime_from_magic(bytes),
+        LarkAttachmentKind::Video => crate::multimodal::video_mime_from_magic(bytes),
+        // Feishu voice messages are always Opus.
+        LarkAttachmentKind::Audio => Some("audio/opus"),
+        LarkAttachmentKind::Document => None,
+    };
+    sniffed.unwrap_or("application/octet-stream").to_string()
+}
+
 /// Extract attachment markers (`[IMAGE:…]`, `[DOCUMENT:…]`, `[AUDIO:…]`, `[VIDEO:…]`) from
 /// outgoing message content. Returns (cleaned_text, attachments).
 fn parse_lark_attachment_markers(message: &str) -> (String, Vec<LarkAttachment>) {
@@ -2093,20 +3193,62 @@ fn parse_lark_attachment_markers(message: &str) -> (String, Vec<LarkAttachment>)
     (cleaned.trim().to_string(), attachments)
 }
 
+/// A piece of outgoing message content, in source order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LarkOutboundPart {
+    Text(String),
+    Attachment(LarkAttachment),
+}
+
+/// Split outgoing content into text segments and attachments in the order
+/// they appear. Blank text between markers is dropped.
+fn split_lark_outbound_parts(message: &str) -> Vec<LarkOutboundPart> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut cursor = 0;
+    let flush = |text: &mut String, parts: &mut Vec<LarkOutboundPart>| {
+        let trimmed = text.trim();
+        if !trimmed.is_empty() {
+            parts.push(LarkOutboundPart::Text(trimmed.to_string()));
+        }
+        text.clear();
+    };
+    while cursor < message.len() {
+        let Some(open_rel) = message[cursor..].find('[') else {
+            text.push_str(&message[cursor..]);
+            break;
+        };
+        let open = cursor + open_rel;
+        text.push_str(&message[cursor..open]);
+        let Some(close_rel) = message[open..].find(']') else {
+            text.push_str(&message[open..]);
+            break;
+        };
+        let close = open + close_rel;
+        let marker = &message[open..=close];
+        cursor = close + 1;
+        match parse_lark_attachment_markers(marker).1.pop() {
+            Some(att) => {
+                flush(&mut text, &mut parts);
+                parts.push(LarkOutboundPart::Attachment(att));
+            }
+            None => text.push_str(marker),
+        }
+    }
+    flush(&mut text, &mut parts);
+    parts
+}
+
 /// Check if a line looks like a markdown table separator row (e.g. `|---|---|`).
 fn is_table_separator(line: &str) -> bool {
     let trimmed = line.trim();
     if !trimmed.starts_with('|') || !trimmed.ends_with('|') {
         return false;
     }
-    trimmed[1..trimmed.len() - 1]
-        .split('|')
-        .all(|cell| {
-            let c = cell.trim();
-            !c.is_empty()
-                && c.chars()
-                    .all(|ch| ch == '-' || ch == ':' || ch == ' ')
-        })
+    trimmed[1..trimmed.len() - 1].split('|').all(|cell| {
+        let c = cell.trim();
+        !c.is_empty() && c.chars().all(|ch| ch == '-' || ch == ':' || ch == ' ')
+    })
 }
 
 /// Parse a contiguous block of markdown table lines into a Feishu CardKit table element.
@@ -2267,6 +3409,41 @@ fn split_lark_sections(text: &str) -> Vec<(Option<String>, String)> {
     sections
 }
 
+/// Bytes `c` takes once JSON-escaped inside a string literal.
+fn json_escaped_len(c: char) -> usize {
+    match c {
+        '"' | '\\' | '\n' | '\r' | '\t' | '\u{08}' | '\u{0c}' => 2,
+        c if u32::from(c) < 0x20 => 6,
+        c => c.len_utf8(),
+    }
+}
+
+/// Cut card markdown so its JSON-escaped size stays within
+/// [`LARK_CARD_MAX_CONTENT_BYTES`], ending with a truncation notice.
+fn truncate_card_content(content: String) -> String {
+    let escaped_len: usize = content.chars().map(json_escaped_len).sum();
+    if escaped_len <= LARK_CARD_MAX_CONTENT_BYTES {
+        return content;
+    }
+    let notice_len: usize = LARK_CARD_TRUNCATION_NOTICE
+        .chars()
+        .map(json_escaped_len)
+        .sum();
+    let budget = LARK_CARD_MAX_CONTENT_BYTES - notice_len;
+    let mut used = 0;
+    let mut cut = 0;
+    for (idx, c) in content.char_indices() {
+        used += json_escaped_len(c);
+        if used > budget {
+            break;
+        }
+        cut = idx + c.len_utf8();
+    }
+    let mut truncated = content[..cut].trim_end().to_string();
+    truncated.push_str(LARK_CARD_TRUNCATION_NOTICE);
+    truncated
+}
+
 /// Replace all markdown headings (`#` through `######`) with bold text.
 /// Also converts markdown tables to a readable list format for streaming paths
 /// where we cannot use native table elements.
@@ -2297,7 +3474,11 @@ fn lark_headers_to_bold(text: &str) -> String {
                     .enumerate()
                     .filter_map(|(j, h)| {
                         let val = cells.get(j).map(|s| s.as_str()).unwrap_or("");
-                        if val.is_empty() { None } else { Some(format!("**{h}**: {val}")) }
+                        if val.is_empty() {
+                            None
+                        } else {
+                            Some(format!("**{h}**: {val}"))
+                        }
                     })
                     .collect();
                 result_lines.push(pairs.join(" | "));
@@ -2337,6 +3518,59 @@ fn parse_lark_image_markers(message: &str) -> (String, Vec<String>) {
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn replay_guard_suppresses_redelivery_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state").join("lark_seen_ids.json");
+
+        let guard = std::sync::Mutex::new(ReplayGuard::load(path.clone()));
+        guard.lock().unwrap().on_connect(1_000);
+        assert_eq!(guard.lock().unwrap().check("om_1", 1_000), Delivery::New);
+        assert!(
+            !path.exists(),
+            "ids are persisted in batches, not per message"
+        );
+        flush_replay_guard(&guard).await;
+        assert!(!guard.lock().unwrap().dirty);
+        drop(guard);
+
+        // Process restarts; Feishu redelivers om_1 from its buffer.
+        let mut guard = ReplayGuard::load(path);
+        guard.on_connect(1_100);
+        assert_eq!(guard.check("om_1", 1_101), Delivery::Duplicate);
+        assert_eq!(guard.suppressed, 1);
+        assert_eq!(guard.check("om_2", 1_102), Delivery::New);
+    }
+
+    #[test]
+    fn replay_guard_delivers_unseen_messages_after_long_downtime() {
+        let connected = 100_000;
+        let mut guard = ReplayGuard::default();
+        guard.on_connect(connected);
+
+        // Sent while we were offline and never seen: must not be dropped.
+        assert_eq!(guard.check("om_missed", connected + 1), Delivery::New);
+        assert_eq!(guard.suppressed, 0);
+    }
+
+    #[test]
+    fn replay_guard_dedups_across_reconnect_and_expires_ids() {
+        let mut guard = ReplayGuard::default();
+        guard.on_connect(0);
+        assert_eq!(guard.check("om_1", 10), Delivery::New);
+
+        guard.on_connect(200);
+        assert_eq!(guard.check("om_1", 201), Delivery::Duplicate);
+        assert_eq!(guard.suppressed, 1);
+
+        // Outside the window, duplicates are still dropped but not counted.
+        assert_eq!(guard.check("om_1", 400), Delivery::Duplicate);
+        assert_eq!(guard.suppressed, 0);
+
+        // Ids are forgotten after the TTL.
+        assert_eq!(guard.check("om_1", 10 + LARK_SEEN_TTL_SECS), Delivery::New);
+    }
+
     fn make_channel() -> LarkChannel {
         LarkChannel::new(
             "cli_test_app_id".into(),
@@ -2353,7 +3587,6 @@ mod tests {
         assert_eq!(ch.name(), "lark");
     }
 
-
     #[test]
     fn lark_should_refresh_token_on_http_401() {
         let body = serde_json::json!({ "code": 0 });
@@ -2416,6 +3649,22 @@ mod tests {
         assert!(ensure_lark_send_success(reqwest::StatusCode::OK, &bad, "test").is_err());
     }
 
+    #[test]
+    fn lark_send_failure_error_masks_tokens() {
+        let bad = serde_json::json!({
+            "code": 99_991_663,
+            "msg": "invalid token",
+            "tenant_access_token": "t-leaked-token-value",
+        });
+
+        let err = ensure_lark_send_success(reqwest::StatusCode::BAD_REQUEST, &bad, "test")
+            .unwrap_err()
+            .to_string();
+        assert!(!err.contains("t-leaked-token-value"));
+        assert!(err.contains("[REDACTED]"));
+        assert!(err.contains("invalid token"));
+    }
+
     #[test]
     fn lark_user_allowed_exact() {
         let ch = make_channel();
@@ -2578,15 +3827,82 @@ mod tests {
     }
 
     #[test]
-    fn lark_parse_wrong_event_type() {
+    fn lark_parse_wrong_event_type() {
+        let ch = make_channel();
+        let payload = serde_json::json!({
+            "header": { "event_type": "im.chat.disbanded_v1" },
+            "event": {}
+        });
+
+        let msgs = ch.parse_event_payload(&payload);
+        assert!(msgs.is_empty());
+    }
+
+    fn card_action_payload(open_id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "schema": "2.0",
+            "header": {
+                "event_id": "ev_card_1",
+                "event_type": "card.action.trigger",
+                "create_time": "1700000000000",
+                "app_id": "cli_test_app_id"
+            },
+            "event": {
+                "operator": { "open_id": open_id, "user_id": "u1" },
+                "token": "c-token",
+                "action": {
+                    "tag": "button",
+                    "value": { "action": "approve", "request_id": 42 }
+                },
+                "host": "im_message",
+                "context": {
+                    "open_message_id": "om_card1",
+                    "open_chat_id": "oc_chat1"
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn lark_parse_card_action() {
+        let ch = make_channel();
+        let action = ch
+            .parse_card_action(&card_action_payload("ou_testuser123"))
+            .expect("card action");
+        assert_eq!(action.operator_open_id, "ou_testuser123");
+        assert_eq!(action.chat_id, "oc_chat1");
+        assert_eq!(action.message_id, "om_card1");
+        assert_eq!(action.tag, "button");
+        assert_eq!(
+            action.value,
+            serde_json::json!({ "action": "approve", "request_id": 42 })
+        );
+        assert_eq!(action.timestamp, 1_700_000_000);
+
+        let msgs = ch.parse_event_payload(&card_action_payload("ou_testuser123"));
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].sender, "ou_testuser123");
+        assert_eq!(msgs[0].reply_target, "oc_chat1");
+        assert_eq!(
+            msgs[0].content,
+            r#"[CARD_ACTION:button:{"action":"approve","request_id":42}]"#
+        );
+        assert_eq!(msgs[0].channel, "lark");
+    }
+
+    #[test]
+    fn lark_parse_card_action_rejects_unauthorized_and_other_events() {
         let ch = make_channel();
-        let payload = serde_json::json!({
-            "header": { "event_type": "im.chat.disbanded_v1" },
-            "event": {}
-        });
+        assert!(ch
+            .parse_card_action(&card_action_payload("ou_stranger"))
+            .is_none());
+        assert!(ch
+            .parse_event_payload(&card_action_payload("ou_stranger"))
+            .is_empty());
 
-        let msgs = ch.parse_event_payload(&payload);
-        assert!(msgs.is_empty());
+        let mut payload = card_action_payload("ou_testuser123");
+        payload["header"]["event_type"] = "im.message.receive_v1".into();
+        assert!(ch.parse_card_action(&payload).is_none());
     }
 
     #[test]
@@ -2688,6 +4004,17 @@ mod tests {
             use_feishu: false,
             receive_mode: LarkReceiveMode::default(),
             port: None,
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 500,
+            draft_update_min_interval_ms: 500,
+            draft_update_debounce_ms: 0,
+            urgent_escalation: crate::config::schema::LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: crate::config::schema::LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
         };
         let json = serde_json::to_string(&lc).unwrap();
         let parsed: LarkConfig = serde_json::from_str(&json).unwrap();
@@ -2709,6 +4036,17 @@ mod tests {
             use_feishu: false,
             receive_mode: LarkReceiveMode::Webhook,
             port: Some(9898),
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 500,
+            draft_update_min_interval_ms: 500,
+            draft_update_debounce_ms: 0,
+            urgent_escalation: crate::config::schema::LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: crate::config::schema::LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
         };
         let toml_str = toml::to_string(&lc).unwrap();
         let parsed: LarkConfig = toml::from_str(&toml_str).unwrap();
@@ -2726,6 +4064,11 @@ mod tests {
         assert!(parsed.allowed_users.is_empty());
         assert_eq!(parsed.receive_mode, LarkReceiveMode::Websocket);
         assert!(parsed.port.is_none());
+        assert_eq!(parsed.urgent_escalation, LarkUrgentEscalation::Off);
+
+        let json = r#"{"app_id":"a","app_secret":"s","urgent_escalation":"sms"}"#;
+        let parsed: LarkConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.urgent_escalation, LarkUrgentEscalation::Sms);
     }
 
     #[test]
@@ -2741,6 +4084,17 @@ mod tests {
             use_feishu: false,
             receive_mode: LarkReceiveMode::Webhook,
             port: Some(9898),
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 500,
+            draft_update_min_interval_ms: 500,
+            draft_update_debounce_ms: 0,
+            urgent_escalation: crate::config::schema::LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: crate::config::schema::LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
         };
 
         let ch = LarkChannel::from_config(&cfg);
@@ -2764,6 +4118,17 @@ mod tests {
             use_feishu: true,
             receive_mode: LarkReceiveMode::Webhook,
             port: Some(9898),
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 500,
+            draft_update_min_interval_ms: 500,
+            draft_update_debounce_ms: 0,
+            urgent_escalation: crate::config::schema::LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: crate::config::schema::LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
         };
 
         let ch = LarkChannel::from_lark_config(&cfg);
@@ -2785,6 +4150,17 @@ mod tests {
             allowed_users: vec!["*".into()],
             receive_mode: LarkReceiveMode::Webhook,
             port: Some(9898),
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 500,
+            draft_update_min_interval_ms: 500,
+            draft_update_debounce_ms: 0,
+            urgent_escalation: crate::config::schema::LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: crate::config::schema::LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
         };
 
         let ch = LarkChannel::from_feishu_config(&cfg);
@@ -2794,6 +4170,57 @@ mod tests {
         assert_eq!(ch.name(), "feishu");
     }
 
+    #[test]
+    fn lark_config_base_url_overrides_replace_platform_endpoints() {
+        use crate::config::schema::{FeishuConfig, LarkReceiveMode};
+
+        let cfg = FeishuConfig {
+            app_id: "cli_feishu_app123".into(),
+            app_secret: "secret456".into(),
+            encrypt_key: None,
+            verification_token: None,
+            allowed_users: vec!["*".into()],
+            receive_mode: LarkReceiveMode::Websocket,
+            port: None,
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 500,
+            draft_update_min_interval_ms: 500,
+            draft_update_debounce_ms: 0,
+            urgent_escalation: crate::config::schema::LarkUrgentEscalation::Off,
+            api_base_url: Some("https://gateway.example.com/open-apis/".into()),
+            ws_base_url: Some("https://gateway.example.com".into()),
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: crate::config::schema::LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
+        };
+
+        let ch = LarkChannel::from_feishu_config(&cfg);
+
+        assert_eq!(
+            ch.send_message_url("oc_chat"),
+            "https://gateway.example.com/open-apis/im/v1/messages?receive_id_type=chat_id"
+        );
+        assert_eq!(
+            ch.send_message_url("ou_user"),
+            "https://gateway.example.com/open-apis/im/v1/messages?receive_id_type=open_id"
+        );
+        assert_eq!(ch.ws_base(), "https://gateway.example.com");
+
+        let blank = FeishuConfig {
+            api_base_url: Some("  ".into()),
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: crate::config::schema::LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
+            ..cfg
+        };
+        let ch = LarkChannel::from_feishu_config(&blank);
+        assert_eq!(ch.api_base(), FEISHU_BASE_URL);
+        assert_eq!(ch.ws_base(), FEISHU_WS_BASE_URL);
+    }
+
     #[test]
     fn lark_parse_fallback_sender_to_open_id() {
         // When chat_id is missing, sender should fall back to open_id
@@ -2837,6 +4264,17 @@ mod tests {
             allowed_users: vec!["*".into()],
             receive_mode: crate::config::schema::LarkReceiveMode::Webhook,
             port: Some(9898),
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 500,
+            draft_update_min_interval_ms: 500,
+            draft_update_debounce_ms: 0,
+            urgent_escalation: crate::config::schema::LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: crate::config::schema::LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
         };
         let ch_feishu = LarkChannel::from_feishu_config(&feishu_cfg);
         assert_eq!(
@@ -2911,6 +4349,35 @@ mod tests {
         assert!(attachments.is_empty());
     }
     #[test]
+    fn split_lark_outbound_parts_keeps_source_order() {
+        let parts = split_lark_outbound_parts(
+            "Intro [IMAGE:/tmp/a.png] middle [link] [DOCUMENT:/tmp/b.pdf][AUDIO:/tmp/c.opus] end",
+        );
+        let image = LarkAttachment {
+            kind: LarkAttachmentKind::Image,
+            target: "/tmp/a.png".into(),
+        };
+        let document = LarkAttachment {
+            kind: LarkAttachmentKind::Document,
+            target: "/tmp/b.pdf".into(),
+        };
+        let audio = LarkAttachment {
+            kind: LarkAttachmentKind::Audio,
+            target: "/tmp/c.opus".into(),
+        };
+        assert_eq!(
+            parts,
+            vec![
+                LarkOutboundPart::Text("Intro".into()),
+                LarkOutboundPart::Attachment(image),
+                LarkOutboundPart::Text("middle [link]".into()),
+                LarkOutboundPart::Attachment(document),
+                LarkOutboundPart::Attachment(audio),
+                LarkOutboundPart::Text("end".into()),
+            ]
+        );
+    }
+    #[test]
     fn resolve_feishu_file_type_known_extensions() {
         assert_eq!(resolve_feishu_file_type("pdf"), "pdf");
         assert_eq!(resolve_feishu_file_type("doc"), "doc");
@@ -2952,6 +4419,17 @@ mod tests {
             allowed_users: vec!["*".into()],
             receive_mode: crate::config::schema::LarkReceiveMode::Webhook,
             port: Some(9898),
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 500,
+            draft_update_min_interval_ms: 500,
+            draft_update_debounce_ms: 0,
+            urgent_escalation: crate::config::schema::LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: crate::config::schema::LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
         };
         let ch_feishu = LarkChannel::from_feishu_config(&feishu_cfg);
         assert_eq!(
@@ -2975,7 +4453,10 @@ mod tests {
     fn extract_file_key_and_name_with_name() {
         let content = r#"{"file_key":"file_v3_abc","file_name":"report.pdf"}"#;
         let result = extract_file_key_and_name(content);
-        assert_eq!(result, Some(("file_v3_abc".to_string(), Some("report.pdf".to_string()))));
+        assert_eq!(
+            result,
+            Some(("file_v3_abc".to_string(), Some("report.pdf".to_string())))
+        );
     }
     #[test]
     fn extract_file_key_and_name_without_name() {
@@ -2993,7 +4474,7 @@ mod tests {
         let payload = serde_json::json!({
             "header": { "event_type": "im.message.receive_v1", "event_id": "ev1" },
             "event": {
-                "sender": { "sender_id": { "open_id": "ou_user" } },
+                "sender": { "sender_id": { "open_id": "ou_testuser123" } },
                 "message": {
                     "message_type": "file",
                     "content": "{\"file_key\":\"file_v3_abc\",\"file_name\":\"report.pdf\"}",
@@ -3006,7 +4487,10 @@ mod tests {
         });
         let msgs = ch.parse_event_payload(&payload);
         assert_eq!(msgs.len(), 1);
-        assert_eq!(msgs[0].content, "[DOCUMENT:lark_file_key:file_v3_abc:report.pdf]");
+        assert_eq!(
+            msgs[0].content,
+            "[DOCUMENT:lark_file_key:file_v3_abc:report.pdf]"
+        );
     }
     #[test]
     fn parse_event_payload_audio_message() {
@@ -3014,7 +4498,7 @@ mod tests {
         let payload = serde_json::json!({
             "header": { "event_type": "im.message.receive_v1", "event_id": "ev2" },
             "event": {
-                "sender": { "sender_id": { "open_id": "ou_user" } },
+                "sender": { "sender_id": { "open_id": "ou_testuser123" } },
                 "message": {
                     "message_type": "audio",
                     "content": "{\"file_key\":\"file_v3_audio\"}",
@@ -3035,7 +4519,7 @@ mod tests {
         let payload = serde_json::json!({
             "header": { "event_type": "im.message.receive_v1", "event_id": "ev3" },
             "event": {
-                "sender": { "sender_id": { "open_id": "ou_user" } },
+                "sender": { "sender_id": { "open_id": "ou_testuser123" } },
                 "message": {
                     "message_type": "media",
                     "content": "{\"file_key\":\"file_v3_video\",\"file_name\":\"clip.mp4\"}",
@@ -3048,7 +4532,10 @@ mod tests {
         });
         let msgs = ch.parse_event_payload(&payload);
         assert_eq!(msgs.len(), 1);
-        assert_eq!(msgs[0].content, "[VIDEO:lark_file_key:file_v3_video:clip.mp4]");
+        assert_eq!(
+            msgs[0].content,
+            "[VIDEO:lark_file_key:file_v3_video:clip.mp4]"
+        );
     }
     #[test]
     fn parse_event_payload_file_message_no_name() {
@@ -3056,7 +4543,7 @@ mod tests {
         let payload = serde_json::json!({
             "header": { "event_type": "im.message.receive_v1", "event_id": "ev4" },
             "event": {
-                "sender": { "sender_id": { "open_id": "ou_user" } },
+                "sender": { "sender_id": { "open_id": "ou_testuser123" } },
                 "message": {
                     "message_type": "file",
                     "content": "{\"file_key\":\"file_v3_noname\"}",
@@ -3069,7 +4556,10 @@ mod tests {
         });
         let msgs = ch.parse_event_payload(&payload);
         assert_eq!(msgs.len(), 1);
-        assert_eq!(msgs[0].content, "[DOCUMENT:lark_file_key:file_v3_noname:file_v3_noname]");
+        assert_eq!(
+            msgs[0].content,
+            "[DOCUMENT:lark_file_key:file_v3_noname:file_v3_noname]"
+        );
     }
     #[test]
     fn lark_download_file_url_matches_region() {
@@ -3133,8 +4623,7 @@ mod tests {
     }
     #[test]
     fn throttle_enforcement_respects_interval() {
-        let ch = make_channel()
-            .with_streaming(StreamMode::Partial, 500);
+        let ch = make_channel().with_streaming(StreamMode::Partial, 500);
         // Insert a recent timestamp
         ch.last_draft_update
             .lock()
@@ -3146,15 +4635,508 @@ mod tests {
         let elapsed = u64::try_from(last_time.elapsed().as_millis()).unwrap_or(u64::MAX);
         assert!(elapsed < 500, "should be within throttle window");
     }
+    #[tokio::test]
+    async fn inbound_markers_route_to_matching_download() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, ResponseTemplate};
+
+        let server = mock_cardkit_server().await;
+        let cases = [
+            (
+                "[IMAGE:lark_image_key:img_v3_a]",
+                "img_v3_a",
+                "image",
+                b"\x89PNG\r\n\x1a\n....".as_slice(),
+                "image/png",
+            ),
+            (
+                "[DOCUMENT:lark_file_key:file_v3_b:report.pdf]",
+                "file_v3_b",
+                "file",
+                b"%PDF-1.7".as_slice(),
+                "application/pdf",
+            ),
+            (
+                "[AUDIO:lark_file_key:file_v3_c]",
+                "file_v3_c",
+                "file",
+                b"OggS".as_slice(),
+                "audio/opus",
+            ),
+            (
+                "VIDEO:lark_file_key:file_v3_d:clip.mp4",
+                "file_v3_d",
+                "file",
+                b"....ftypisom".as_slice(),
+                "video/mp4",
+            ),
+        ];
+        for (_, key, resource_type, body, _) in &cases {
+            Mock::given(method("GET"))
+                .and(path(format!("/im/v1/messages/om_1/resources/{key}")))
+                .and(query_param("type", *resource_type))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes(body.to_vec()))
+                .mount(&server)
+                .await;
+        }
+        let ch = make_channel().with_api_base(server.uri());
+
+        for (marker, _, _, body, mime) in cases {
+            let fetched = ch.fetch_attachment("om_1", marker).await.expect(marker);
+            assert_eq!(fetched.bytes, body, "{marker}");
+            assert_eq!(fetched.mime, mime, "{marker}");
+        }
+        assert!(ch
+            .fetch_attachment("om_1", "[IMAGE:/tmp/a.png]")
+            .await
+            .is_err());
+    }
+    #[test]
+    fn inbound_marker_rejects_outgoing_and_mismatched_forms() {
+        assert!(parse_inbound_attachment_marker("[IMAGE:/tmp/a.png]").is_none());
+        assert!(parse_inbound_attachment_marker("[IMAGE:lark_file_key:k]").is_none());
+        assert!(parse_inbound_attachment_marker("[AUDIO:lark_file_key:]").is_none());
+        assert!(parse_inbound_attachment_marker("[STICKER:lark_file_key:k]").is_none());
+    }
+    #[test]
+    fn attachment_mime_prefers_name_then_content() {
+        assert_eq!(
+            infer_attachment_mime(LarkAttachmentKind::Document, Some("report.pdf"), b""),
+            "application/pdf"
+        );
+        assert_eq!(
+            infer_attachment_mime(LarkAttachmentKind::Image, None, b"\x89PNG\r\n\x1a\n...."),
+            "image/png"
+        );
+        assert_eq!(
+            infer_attachment_mime(LarkAttachmentKind::Audio, None, b"OggS"),
+            "audio/opus"
+        );
+        assert_eq!(
+            infer_attachment_mime(LarkAttachmentKind::Document, Some("file_v3_x"), b"data"),
+            "application/octet-stream"
+        );
+    }
+    #[test]
+    fn draft_throttle_honors_configured_floor() {
+        let ch = make_channel().with_streaming(StreamMode::Partial, 100);
+        let last = Instant::now()
+            .checked_sub(Duration::from_millis(200))
+            .unwrap();
+        ch.last_draft_update
+            .lock()
+            .unwrap()
+            .insert("card_1".into(), last);
+        // Default 500ms floor still applies to a 100ms interval.
+        assert_eq!(ch.draft_throttle(), Duration::from_millis(500));
+        assert!(ch.draft_throttle_remaining("card_1").is_some());
+
+        let ch = ch.with_draft_timing(50, 0);
+        assert_eq!(ch.draft_throttle(), Duration::from_millis(100));
+        assert!(ch.draft_throttle_remaining("card_1").is_none());
+        assert!(ch.draft_throttle_remaining("card_unknown").is_none());
+    }
+    #[tokio::test]
+    async fn debounce_coalesces_rapid_deltas() {
+        let ch = make_channel()
+            .with_streaming(StreamMode::Partial, 100)
+            .with_draft_timing(0, 60_000);
+        for text in ["he", "hello", "hello world"] {
+            ch.update_draft("oc_chat", "card_1", text).await.unwrap();
+        }
+        {
+            let pending = ch.pending_draft_text.lock().unwrap();
+            assert_eq!(pending.len(), 1);
+            assert_eq!(
+                pending.get("card_1").map(String::as_str),
+                Some("hello world")
+            );
+        }
+        ch.cancel_draft("oc_chat", "card_1").await.unwrap();
+        assert!(ch.pending_draft_text.lock().unwrap().is_empty());
+    }
+    /// Mock Open API server accepting token, card update and close requests.
+    async fn mock_cardkit_server() -> wiremock::MockServer {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/auth/v3/tenant_access_token/internal"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "code": 0,
+                "tenant_access_token": "t-test",
+                "expire": 7200
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"code": 0})))
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"code": 0})))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    /// Content of every card element update received by the mock server.
+    async fn card_updates(server: &wiremock::MockServer) -> Vec<String> {
+        server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|r| r.method.as_str() == "PUT")
+            .map(|r| {
+                let body: serde_json::Value = serde_json::from_slice(&r.body).unwrap();
+                body["content"].as_str().unwrap_or_default().to_string()
+            })
+            .collect()
+    }
+
+    /// A channel with an open draft card whose last update was just now.
+    fn open_draft_channel(server: &wiremock::MockServer) -> LarkChannel {
+        let ch = make_channel()
+            .with_api_base(server.uri())
+            .with_streaming(StreamMode::Partial, 60_000);
+        ch.card_sequence.lock().unwrap().insert("card_1".into(), 1);
+        ch.last_draft_update
+            .lock()
+            .unwrap()
+            .insert("card_1".into(), Instant::now());
+        ch
+    }
+
+    #[tokio::test]
+    async fn finalize_flushes_last_throttled_update() {
+        let server = mock_cardkit_server().await;
+        let ch = open_draft_channel(&server);
+
+        ch.update_draft("oc_chat", "card_1", "partial")
+            .await
+            .unwrap();
+        ch.update_draft("oc_chat", "card_1", "partial answer")
+            .await
+            .unwrap();
+        assert!(card_updates(&server).await.is_empty());
+
+        ch.finalize_draft("oc_chat", "card_1", "").await.unwrap();
+        assert_eq!(card_updates(&server).await, vec!["partial answer"]);
+        assert!(ch.pending_draft_text.lock().unwrap().is_empty());
+        assert!(ch.shown_draft_text.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn finalize_shows_explicit_final_text() {
+        let server = mock_cardkit_server().await;
+        let ch = open_draft_channel(&server);
+
+        ch.update_draft("oc_chat", "card_1", "partial")
+            .await
+            .unwrap();
+        ch.finalize_draft("oc_chat", "card_1", "final answer")
+            .await
+            .unwrap();
+        assert_eq!(card_updates(&server).await, vec!["final answer"]);
+    }
+
+    #[tokio::test]
+    async fn oversized_draft_is_truncated_to_card_limit() {
+        let server = mock_cardkit_server().await;
+        let ch = open_draft_channel(&server);
+        let huge = "第 \"quoted\" line of a very long answer\n".repeat(5_000);
+
+        ch.finalize_draft("oc_chat", "card_1", &huge).await.unwrap();
+
+        let updates = card_updates(&server).await;
+        assert_eq!(updates.len(), 1);
+        let content = &updates[0];
+        assert!(content.ends_with(LARK_CARD_TRUNCATION_NOTICE));
+        assert!(content.starts_with("第 \"quoted\" line"));
+        let escaped = serde_json::to_string(content).unwrap();
+        // The serialized string also carries its two surrounding quotes.
+        assert!(escaped.len() - 2 <= LARK_CARD_MAX_CONTENT_BYTES);
+        assert!(escaped.len() > LARK_CARD_MAX_CONTENT_BYTES - 200);
+
+        assert_eq!(truncate_card_content("short".into()), "short");
+    }
+
+    #[tokio::test]
+    async fn finalize_skips_update_when_card_is_current() {
+        let server = mock_cardkit_server().await;
+        let ch = make_channel()
+            .with_api_base(server.uri())
+            .with_streaming(StreamMode::Partial, 0)
+            .with_draft_timing(0, 0);
+        ch.card_sequence.lock().unwrap().insert("card_1".into(), 1);
+
+        ch.update_draft("oc_chat", "card_1", "done").await.unwrap();
+        ch.finalize_draft("oc_chat", "card_1", "done")
+            .await
+            .unwrap();
+        assert_eq!(card_updates(&server).await, vec!["done"]);
+    }
+    /// `mock_cardkit_server` plus card creation and message sends.
+    async fn mock_send_server() -> wiremock::MockServer {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        let server = mock_cardkit_server().await;
+        Mock::given(method("POST"))
+            .and(path("/cardkit/v1/cards"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "code": 0,
+                "data": { "card_id": "card_1" }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/im/v1/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "code": 0,
+                "data": { "message_id": "om_sent" }
+            })))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    /// Paths of urgent notification requests received by the mock server.
+    async fn urgent_requests(server: &wiremock::MockServer) -> Vec<(String, serde_json::Value)> {
+        server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|r| r.url.path().contains("/urgent_"))
+            .map(|r| {
+                (
+                    r.url.path().to_string(),
+                    serde_json::from_slice(&r.body).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn urgent_draft_updates_skip_throttle_and_debounce() {
+        let server = mock_send_server().await;
+        let ch = make_channel()
+            .with_api_base(server.uri())
+            .with_streaming(StreamMode::Partial, 60_000)
+            .with_draft_timing(500, 60_000);
+
+        let draft_id = ch
+            .send_draft(&SendMessage::new("", "oc_chat").urgent(true))
+            .await
+            .unwrap()
+            .unwrap();
+        ch.update_draft("oc_chat", &draft_id, "first")
+            .await
+            .unwrap();
+        ch.update_draft("oc_chat", &draft_id, "second")
+            .await
+            .unwrap();
+        assert_eq!(card_updates(&server).await, vec!["first", "second"]);
+
+        ch.finalize_draft("oc_chat", &draft_id, "second")
+            .await
+            .unwrap();
+        assert!(ch.urgent_drafts.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn non_urgent_draft_updates_stay_throttled() {
+        let server = mock_send_server().await;
+        let ch = make_channel()
+            .with_api_base(server.uri())
+            .with_streaming(StreamMode::Partial, 60_000);
+
+        let draft_id = ch
+            .send_draft(&SendMessage::new("", "oc_chat"))
+            .await
+            .unwrap()
+            .unwrap();
+        ch.update_draft("oc_chat", &draft_id, "first")
+            .await
+            .unwrap();
+        assert!(card_updates(&server).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn plain_text_error_body_is_redacted() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        let server = mock_cardkit_server().await;
+        Mock::given(method("POST"))
+            .and(path("/im/v1/messages"))
+            .respond_with(
+                ResponseTemplate::new(502)
+                    .set_body_string("bad gateway: tenant_access_token=t-leaked123 upstream=x"),
+            )
+            .mount(&server)
+            .await;
+        let ch = make_channel().with_api_base(server.uri());
+
+        let err = ch
+            .send(&SendMessage::new("hello", "oc_chat"))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(!err.contains("t-leaked123"), "{err}");
+        assert!(err.contains("tenant_access_token=[REDACTED]"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn urgent_send_escalates_to_recipient_chat_members_only() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, ResponseTemplate};
+
+        let server = mock_send_server().await;
+        Mock::given(method("GET"))
+            .and(path("/im/v1/chats/oc_chat/members"))
+            .and(query_param("member_id_type", "open_id"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "code": 0,
+                "data": {
+                    "items": [{ "member_id": "ou_recipient", "member_id_type": "open_id" }],
+                    "has_more": false
+                }
+            })))
+            .mount(&server)
+            .await;
+        // `make_channel` allows ou_testuser123, who is not in the chat.
+        let ch = make_channel()
+            .with_api_base(server.uri())
+            .with_urgent_escalation(LarkUrgentEscalation::App);
+
+        ch.send(&SendMessage::new("routine", "oc_chat"))
+            .await
+            .unwrap();
+        assert!(urgent_requests(&server).await.is_empty());
+
+        ch.send(&SendMessage::new("disk full", "oc_chat").urgent(true))
+            .await
+            .unwrap();
+        let requests = urgent_requests(&server).await;
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].0, "/im/v1/messages/om_sent/urgent_app");
+        assert_eq!(
+            requests[0].1,
+            serde_json::json!({ "user_id_list": ["ou_recipient"] })
+        );
+    }
+
+    #[tokio::test]
+    async fn urgent_send_to_open_id_escalates_to_that_user() {
+        let server = mock_send_server().await;
+        let ch = make_channel()
+            .with_api_base(server.uri())
+            .with_urgent_escalation(LarkUrgentEscalation::Sms);
+
+        ch.send(&SendMessage::new("disk full", "ou_alice").urgent(true))
+            .await
+            .unwrap();
+        let requests = server.received_requests().await.unwrap();
+        let sent = requests
+            .iter()
+            .find(|r| r.url.path() == "/im/v1/messages")
+            .unwrap();
+        assert_eq!(sent.url.query(), Some("receive_id_type=open_id"));
+        let urgent = requests
+            .iter()
+            .find(|r| r.url.path().contains("/urgent_"))
+            .unwrap();
+        assert_eq!(urgent.url.path(), "/im/v1/messages/om_sent/urgent_sms");
+        assert_eq!(urgent.url.query(), Some("user_id_type=open_id"));
+        assert!(!requests.iter().any(|r| r.url.path().contains("/members")));
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&urgent.body).unwrap(),
+            serde_json::json!({ "user_id_list": ["ou_alice"] })
+        );
+    }
+
+    #[tokio::test]
+    async fn urgent_attachment_only_send_is_escalated() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        let server = mock_send_server().await;
+        Mock::given(method("POST"))
+            .and(path("/im/v1/images"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "code": 0,
+                "data": { "image_key": "img_v3_urgent" }
+            })))
+            .mount(&server)
+            .await;
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("alert.png");
+        std::fs::write(&image, b"\x89PNG\r\n\x1a\n").unwrap();
+        let ch = make_channel()
+            .with_api_base(server.uri())
+            .with_urgent_escalation(LarkUrgentEscalation::App);
+
+        ch.send(&SendMessage::new(format!("[IMAGE:{}]", image.display()), "ou_alice").urgent(true))
+            .await
+            .unwrap();
+        let requests = urgent_requests(&server).await;
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].0, "/im/v1/messages/om_sent/urgent_app");
+    }
+
     #[test]
     fn supports_draft_updates_respects_stream_mode() {
         let ch_off = make_channel();
         assert!(!ch_off.supports_draft_updates());
-        let ch_on = make_channel()
-            .with_streaming(StreamMode::Partial, 500);
+        let ch_on = make_channel().with_streaming(StreamMode::Partial, 500);
         assert!(ch_on.supports_draft_updates());
     }
     #[test]
+    fn capabilities_follow_config() {
+        let mut config = crate::config::schema::FeishuConfig {
+            app_id: "cli_test".into(),
+            app_secret: "secret".into(),
+            encrypt_key: None,
+            verification_token: None,
+            allowed_users: vec![],
+            receive_mode: crate::config::schema::LarkReceiveMode::default(),
+            port: None,
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            draft_update_min_interval_ms: 500,
+            draft_update_debounce_ms: 0,
+            urgent_escalation: crate::config::schema::LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: crate::config::schema::LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
+        };
+        let caps = LarkChannel::from_feishu_config(&config).capabilities();
+        assert!(!caps.draft_updates);
+        assert!(caps.ack_reactions);
+        assert!(!caps.reactions);
+        assert!(caps.threads);
+        for kind in [
+            AttachmentKind::Image,
+            AttachmentKind::Document,
+            AttachmentKind::Audio,
+            AttachmentKind::Video,
+        ] {
+            assert!(caps.supports_attachment(kind), "{kind:?}");
+        }
+        assert!(!caps.supports_attachment(AttachmentKind::Voice));
+
+        config.stream_mode = StreamMode::Partial;
+        let caps = LarkChannel::from_feishu_config(&config).capabilities();
+        assert!(caps.draft_updates);
+    }
+    #[test]
     fn typing_card_ids_lifecycle() {
         let ch = make_channel();
         // Initially empty
@@ -3165,7 +5147,11 @@ mod tests {
             .unwrap()
             .insert("oc_chat_abc".to_string(), "card_typing_1".to_string());
         assert_eq!(
-            ch.typing_card_ids.lock().unwrap().get("oc_chat_abc").unwrap(),
+            ch.typing_card_ids
+                .lock()
+                .unwrap()
+                .get("oc_chat_abc")
+                .unwrap(),
             "card_typing_1"
         );
         // Simulate stop_typing removing the entry
@@ -3182,10 +5168,19 @@ mod tests {
             encrypt_key: None,
             verification_token: None,
             allowed_users: vec![],
-            receive_mode: LarkReceiveMode::default(),
+            receive_mode: crate::config::schema::LarkReceiveMode::default(),
             port: None,
             stream_mode: StreamMode::Partial,
             draft_update_interval_ms: 1000,
+            draft_update_min_interval_ms: 500,
+            draft_update_debounce_ms: 0,
+            urgent_escalation: crate::config::schema::LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: crate::config::schema::LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
         };
         let ch = LarkChannel::from_feishu_config(&config);
         assert_eq!(ch.stream_mode, StreamMode::Partial);
@@ -3271,7 +5266,10 @@ mod tests {
         assert!(elems[0]["content"].as_str().unwrap().contains("intro"));
         assert_eq!(elems[1]["tag"], "table");
         assert_eq!(elems[2]["tag"], "markdown");
-        assert!(elems[2]["content"].as_str().unwrap().contains("After table"));
+        assert!(elems[2]["content"]
+            .as_str()
+            .unwrap()
+            .contains("After table"));
     }
     #[test]
     fn build_lark_card_elements_no_table() {
@@ -3381,7 +5379,11 @@ mod tests {
             }
         });
         let msgs2 = ch.parse_event_payload(&payload_thread);
-        assert_eq!(msgs2.len(), 0, "thread reply after fwd copy should be deduped");
+        assert_eq!(
+            msgs2.len(),
+            0,
+            "thread reply after fwd copy should be deduped"
+        );
     }
 
     #[test]
@@ -3405,4 +5407,451 @@ mod tests {
         assert_eq!(msg.root_id.as_deref(), Some("om_root"));
         assert_eq!(msg.parent_id.as_deref(), Some("om_parent"));
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn tenant_token_refreshes_when_clock_passes_deadline() {
+        let server = mock_cardkit_server().await;
+        let clock = Arc::new(crate::clock::MockClock::new());
+        let ch = make_channel()
+            .with_api_base(server.uri())
+            .with_clock(clock.clone());
+        let token_requests = || async {
+            server
+                .received_requests()
+                .await
+                .unwrap_or_default()
+                .iter()
+                .filter(|r| r.url.path() == "/auth/v3/tenant_access_token/internal")
+                .count()
+        };
+
+        assert_eq!(ch.get_tenant_access_token().await.unwrap(), "t-test");
+        let refresh_in = Duration::from_secs(7200)
+            .checked_sub(LARK_TOKEN_REFRESH_SKEW)
+            .unwrap();
+        clock.advance(refresh_in.checked_sub(Duration::from_secs(1)).unwrap());
+        ch.get_tenant_access_token().await.unwrap();
+        assert_eq!(token_requests().await, 1);
+
+        clock.advance(Duration::from_secs(2));
+        ch.get_tenant_access_token().await.unwrap();
+        assert_eq!(token_requests().await, 2);
+    }
+
+    #[tokio::test]
+    async fn concurrent_token_fetches_share_one_request() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path(
+                "/auth/v3/tenant_access_token/internal",
+            ))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200)
+                    .set_delay(Duration::from_millis(100))
+                    .set_body_json(serde_json::json!({
+                        "code": 0,
+                        "tenant_access_token": "t-shared",
+                        "expire": 7200
+                    })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let ch = make_channel().with_api_base(server.uri());
+        let clones: Vec<LarkChannel> = (0..8).map(|_| ch.clone()).collect();
+        let tokens = futures_util::future::join_all(
+            clones.iter().map(|clone| clone.get_tenant_access_token()),
+        )
+        .await;
+        for token in tokens {
+            assert_eq!(token.unwrap(), "t-shared");
+        }
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn cancelling_shutdown_ends_ws_listener_promptly() {
+        let token = CancellationToken::new();
+        let mut ch = make_channel().with_shutdown(token.clone());
+        ch.set_ws_manager(Arc::new(LarkWsManager::new(
+            "cli_test_app_id".into(),
+            "test_app_secret".into(),
+            false,
+            16,
+        )));
+        let (tx, _rx) = tokio::sync::mpsc::channel(4);
+        let listener = tokio::spawn(async move { ch.listen(tx).await });
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!listener.is_finished());
+        token.cancel();
+        let result = tokio::time::timeout(Duration::from_secs(1), listener)
+            .await
+            .expect("ws listener should stop after cancellation")
+            .unwrap();
+        assert!(result.is_ok());
+    }
+
+    fn ws_message_event(
+        message_id: &str,
+        chat_type: &str,
+    ) -> crate::channels::lark_ws_manager::LarkWsEvent {
+        ws_text_event(message_id, chat_type, "hello")
+    }
+
+    fn ws_text_event(
+        message_id: &str,
+        chat_type: &str,
+        text: &str,
+    ) -> crate::channels::lark_ws_manager::LarkWsEvent {
+        let payload = serde_json::json!({
+            "header": { "event_type": "im.message.receive_v1", "event_id": format!("ev_{message_id}") },
+            "event": {
+                "sender": { "sender_id": { "open_id": "ou_testuser123" }, "sender_type": "user" },
+                "message": {
+                    "message_id": message_id,
+                    "chat_id": format!("oc_{chat_type}"),
+                    "chat_type": chat_type,
+                    "message_type": "text",
+                    "content": serde_json::json!({ "text": text }).to_string(),
+                    "create_time": (unix_now_secs() * 1000).to_string()
+                }
+            }
+        });
+        crate::channels::lark_ws_manager::LarkWsEvent {
+            event_type: "im.message.receive_v1".into(),
+            payload: serde_json::to_vec(&payload).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn ws_ack_reaction_skips_unaddressed_group_messages() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path(
+                "/auth/v3/tenant_access_token/internal",
+            ))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "code": 0, "tenant_access_token": "t-ack", "expire": 7200 }),
+            ))
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path_regex(
+                r"^/im/v1/messages/.+/reactions$",
+            ))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "code": 0 })),
+            )
+            .mount(&server)
+            .await;
+
+        let token = CancellationToken::new();
+        let manager = Arc::new(LarkWsManager::new(
+            "cli_test_app_id".into(),
+            "test_app_secret".into(),
+            false,
+            16,
+        ));
+        let mut ch = make_channel()
+            .with_api_base(server.uri())
+            .with_shutdown(token.clone());
+        ch.ack_reaction_emoji = Some("THUMBSUP".into());
+        ch.set_ws_manager(Arc::clone(&manager));
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        let listener = tokio::spawn(async move { ch.listen(tx).await });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        manager.inject_event(ws_message_event("om_group_unaddressed", "group"));
+        manager.inject_event(ws_message_event("om_direct", "p2p"));
+        let msg = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .expect("p2p message should be delivered")
+            .unwrap();
+        assert_eq!(msg.reply_target, "oc_p2p");
+
+        let reaction_requests = || async {
+            server
+                .received_requests()
+                .await
+                .unwrap()
+                .into_iter()
+                .filter(|r| r.url.path().ends_with("/reactions"))
+                .collect::<Vec<_>>()
+        };
+        for _ in 0..50 {
+            if !reaction_requests().await.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let reactions = reaction_requests().await;
+        assert_eq!(reactions.len(), 1);
+        assert_eq!(
+            reactions[0].url.path(),
+            "/im/v1/messages/om_direct/reactions"
+        );
+        let body: serde_json::Value = serde_json::from_slice(&reactions[0].body).unwrap();
+        assert_eq!(body["reaction_type"]["emoji_type"], "THUMBSUP");
+
+        token.cancel();
+        listener.await.unwrap().unwrap();
+    }
+
+    #[test]
+    fn lark_config_ack_reaction_emoji_overrides_or_disables_default() {
+        use crate::config::schema::LarkConfig;
+
+        let mut cfg: LarkConfig =
+            toml::from_str("app_id = \"cli\"\napp_secret = \"secret\"\n").unwrap();
+        assert_eq!(
+            LarkChannel::from_lark_config(&cfg)
+                .ack_reaction_emoji
+                .as_deref(),
+            Some(ACK_REACTION_EMOJI_TYPE)
+        );
+        cfg.ack_reaction_emoji = Some("DONE".into());
+        assert_eq!(
+            LarkChannel::from_lark_config(&cfg)
+                .ack_reaction_emoji
+                .as_deref(),
+            Some("DONE")
+        );
+        cfg.ack_reaction_emoji = Some(String::new());
+        assert!(LarkChannel::from_lark_config(&cfg)
+            .ack_reaction_emoji
+            .is_none());
+    }
+
+    fn text_event_payload(text: &str) -> serde_json::Value {
+        serde_json::json!({
+            "header": { "event_type": "im.message.receive_v1", "event_id": "ev_long" },
+            "event": {
+                "sender": { "sender_id": { "open_id": "ou_testuser123" } },
+                "message": {
+                    "message_type": "text",
+                    "content": serde_json::json!({ "text": text }).to_string(),
+                    "chat_id": "oc_chat1",
+                    "chat_type": "p2p",
+                    "message_id": "om_long",
+                    "create_time": "1000"
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn oversized_inbound_message_is_truncated_with_marker() {
+        let mut ch = make_channel();
+        ch.max_inbound_chars = Some(5);
+
+        assert_eq!(
+            ch.apply_inbound_limit("héllo world".into(), "oc_chat1")
+                .unwrap(),
+            "héllo\n\n[message truncated: showing 5 of 11 characters]"
+        );
+        assert_eq!(
+            ch.apply_inbound_limit("short".into(), "oc_chat1").unwrap(),
+            "short"
+        );
+
+        // The webhook parser leaves the limit to the listener.
+        let msgs = ch.parse_event_payload(&text_event_payload("héllo world"));
+        assert_eq!(msgs[0].content, "héllo world");
+    }
+
+    #[tokio::test]
+    async fn oversized_ws_message_is_rejected_once_after_dedup() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path(
+                "/auth/v3/tenant_access_token/internal",
+            ))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "code": 0, "tenant_access_token": "t-len", "expire": 7200 }),
+            ))
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/im/v1/messages"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "code": 0, "data": { "message_id": "om_reply" } }),
+            ))
+            .mount(&server)
+            .await;
+
+        let token = CancellationToken::new();
+        let manager = Arc::new(LarkWsManager::new(
+            "cli_test_app_id".into(),
+            "test_app_secret".into(),
+            false,
+            16,
+        ));
+        let mut ch = make_channel()
+            .with_api_base(server.uri())
+            .with_shutdown(token.clone());
+        ch.ack_reaction_emoji = None;
+        ch.max_inbound_chars = Some(5);
+        ch.inbound_overflow = LarkInboundOverflow::Reject;
+        ch.set_ws_manager(Arc::clone(&manager));
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        let listener = tokio::spawn(async move { ch.listen(tx).await });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // A redelivery and a forwarded copy of the same over-long message.
+        manager.inject_event(ws_text_event("om_long", "p2p", "hello world"));
+        manager.inject_event(ws_text_event("om_long", "p2p", "hello world"));
+        manager.inject_event(ws_text_event("om_long_copy", "p2p", "hello world"));
+        tokio::time::sleep(Duration::from_millis(400)).await;
+
+        let replies: Vec<_> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|r| r.url.path() == "/im/v1/messages")
+            .collect();
+        assert_eq!(replies.len(), 1);
+        let body: serde_json::Value = serde_json::from_slice(&replies[0].body).unwrap();
+        assert_eq!(body["receive_id"], "oc_p2p");
+        assert!(body["content"]
+            .as_str()
+            .unwrap()
+            .contains("too long (11 characters)"));
+        assert!(rx.try_recv().is_err());
+
+        token.cancel();
+        listener.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn interleaved_send_follows_marker_order() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path(
+                "/auth/v3/tenant_access_token/internal",
+            ))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "code": 0, "tenant_access_token": "t-order", "expire": 7200 }),
+            ))
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/im/v1/images"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "code": 0, "data": { "image_key": "img_v3_order" } }),
+            ))
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/im/v1/messages"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "code": 0, "data": { "message_id": "om_sent" } }),
+            ))
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("chart.png");
+        std::fs::write(&image, b"\x89PNG\r\n\x1a\n").unwrap();
+        let content = format!(
+            "Before the chart [IMAGE:{}] after the chart",
+            image.display()
+        );
+
+        let sent_types = || async {
+            server
+                .received_requests()
+                .await
+                .unwrap()
+                .into_iter()
+                .filter(|r| r.url.path() == "/im/v1/messages")
+                .map(|r| {
+                    let body: serde_json::Value = serde_json::from_slice(&r.body).unwrap();
+                    body["msg_type"].as_str().unwrap().to_string()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut ch = make_channel().with_api_base(server.uri());
+        ch.send(&SendMessage::new(content.clone(), "oc_chat1"))
+            .await
+            .unwrap();
+        assert_eq!(sent_types().await, ["image", "interactive"]);
+
+        server.reset().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/im/v1/images"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "code": 0, "data": { "image_key": "img_v3_order" } }),
+            ))
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/im/v1/messages"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "code": 0, "data": { "message_id": "om_sent" } }),
+            ))
+            .mount(&server)
+            .await;
+
+        ch.interleave_attachments = true;
+        ch.send(&SendMessage::new(content, "oc_chat1"))
+            .await
+            .unwrap();
+        assert_eq!(sent_types().await, ["interactive", "image", "interactive"]);
+    }
+
+    #[tokio::test]
+    async fn cancelling_shutdown_ends_http_listener_promptly() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let token = CancellationToken::new();
+        let mut ch = LarkChannel::new(
+            "cli_test_app_id".into(),
+            "test_app_secret".into(),
+            "test_verification_token".into(),
+            Some(port),
+            vec![],
+        )
+        .with_shutdown(token.clone());
+        ch.receive_mode = crate::config::schema::LarkReceiveMode::Webhook;
+        let (tx, _rx) = tokio::sync::mpsc::channel(4);
+        let listener = tokio::spawn(async move { ch.listen(tx).await });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!listener.is_finished());
+        token.cancel();
+        let result = tokio::time::timeout(Duration::from_secs(1), listener)
+            .await
+            .expect("http listener should stop after cancellation")
+            .unwrap();
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn drain_overflow_delivers_buffered_messages() {
+        let message = |id: &str| ChannelMessage {
+            id: id.into(),
+            sender: "ou_testuser123".into(),
+            reply_target: "oc_chat".into(),
+            content: "hi".into(),
+            channel: "lark".into(),
+            timestamp: 0,
+            thread_ts: None,
+        };
+        let mut overflow: VecDeque<_> = [message("om_1"), message("om_2")].into();
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+
+        drain_overflow(&tx, &mut overflow).await;
+
+        assert!(overflow.is_empty());
+        assert_eq!(rx.recv().await.unwrap().id, "om_1");
+        assert_eq!(rx.recv().await.unwrap().id, "om_2");
+    }
+}
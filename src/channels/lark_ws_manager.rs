@@ -7,7 +7,7 @@ use futures_util::{SinkExt, StreamExt};
 use prost::Message as ProstMessage;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, watch};
 use tokio_tungstenite::tungstenite::Message as WsMsg;
 
 const FEISHU_WS_BASE_URL: &str = "https://open.feishu.cn";
@@ -121,6 +121,8 @@ pub struct LarkWsManager {
     ws_base_url: String,
     locale_header: String,
     event_tx: broadcast::Sender<LarkWsEvent>,
+    /// Bumped on every successful (re)connect.
+    connections: watch::Sender<u64>,
     http: reqwest::Client,
 }
 
@@ -150,15 +152,39 @@ impl LarkWsManager {
             ws_base_url,
             locale_header,
             event_tx,
+            connections: watch::Sender::new(0),
             http,
         }
     }
 
+    /// Override the WS endpoint base URL (self-hosted gateways). Blank
+    /// values keep the platform default.
+    pub fn with_ws_base_url(mut self, ws_base_url: Option<&str>) -> Self {
+        if let Some(url) = ws_base_url.map(|u| u.trim().trim_end_matches('/')) {
+            if !url.is_empty() {
+                self.ws_base_url = url.to_string();
+            }
+        }
+        self
+    }
+
+    /// Broadcast `event` to subscribers as if it arrived over the socket.
+    #[cfg(test)]
+    pub(crate) fn inject_event(&self, event: LarkWsEvent) {
+        let _ = self.event_tx.send(event);
+    }
+
     /// Subscribe to decoded events from the WS connection.
     pub fn subscribe(&self) -> broadcast::Receiver<LarkWsEvent> {
         self.event_tx.subscribe()
     }
 
+    /// Watch the connection counter. It changes on each (re)connect, before
+    /// any event from the new connection is broadcast.
+    pub fn subscribe_connections(&self) -> watch::Receiver<u64> {
+        self.connections.subscribe()
+    }
+
     /// Run the manager forever — reconnects automatically on error.
     pub async fn run(&self) -> ! {
         loop {
@@ -213,6 +239,7 @@ impl LarkWsManager {
         let (ws_stream, _) = tokio_tungstenite::connect_async(&wss_url).await?;
         let (mut write, mut read) = ws_stream.split();
         tracing::info!("LarkWsManager: WS connected (service_id={service_id})");
+        self.connections.send_modify(|n| *n += 1);
 
         let mut ping_secs = client_config.ping_interval.unwrap_or(30).max(10);
         let mut hb_interval = tokio::time::interval(Duration::from_secs(ping_secs));
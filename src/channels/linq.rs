@@ -1,4 +1,4 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Channel, ChannelCapabilities, ChannelMessage, SendMessage};
 use async_trait::async_trait;
 use uuid::Uuid;
 
@@ -225,6 +225,13 @@ impl Channel for LinqChannel {
         "linq"
     }
 
+    fn capabilities(&self) -> ChannelCapabilities {
+        ChannelCapabilities {
+            typing: true,
+            ..ChannelCapabilities::default()
+        }
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         // If reply_target looks like a chat_id, send to existing chat.
         // Otherwise create a new chat with the recipient phone number.
@@ -117,6 +117,11 @@ const CHANNEL_MIN_IN_FLIGHT_MESSAGES: usize = 8;
 const CHANNEL_MAX_IN_FLIGHT_MESSAGES: usize = 64;
 const CHANNEL_TYPING_REFRESH_INTERVAL_SECS: u64 = 4;
 const CHANNEL_HEALTH_HEARTBEAT_SECS: u64 = 30;
+/// How long a listener may keep running after shutdown is requested.
+const CHANNEL_SHUTDOWN_GRACE_SECS: u64 = 10;
+/// Interval between `Channel::health_check` (credential validity) probes.
+const CHANNEL_AUTH_CHECK_SECS: u64 = 300;
+const CHANNEL_AUTH_CHECK_TIMEOUT_SECS: u64 = 10;
 const MODEL_CACHE_FILE: &str = "models_cache.json";
 const MODEL_CACHE_PREVIEW_LIMIT: usize = 10;
 const MEMORY_CONTEXT_MAX_ENTRIES: usize = 4;
@@ -129,6 +134,7 @@ const CHANNEL_HOOK_MAX_OUTBOUND_CHARS: usize = 20_000;
 
 type ProviderCacheMap = Arc<Mutex<HashMap<String, Arc<dyn Provider>>>>;
 type RouteSelectionMap = Arc<Mutex<HashMap<String, ChannelRouteSelection>>>;
+type ChannelToolsMap = HashMap<String, Arc<Vec<Box<dyn Tool>>>>;
 
 fn effective_channel_message_timeout_secs(configured: u64) -> u64 {
     configured.max(MIN_CHANNEL_MESSAGE_TIMEOUT_SECS)
@@ -207,6 +213,9 @@ struct ChannelRuntimeContext {
     default_provider: Arc<String>,
     memory: Arc<dyn Memory>,
     tools_registry: Arc<Vec<Box<dyn Tool>>>,
+    /// Per-channel registries with the MCP tools each channel may use on top
+    /// of `tools_registry`; channels without an entry use `tools_registry`.
+    channel_tools: Arc<ChannelToolsMap>,
     observer: Arc<dyn Observer>,
     system_prompt: Arc<String>,
     model: Arc<String>,
@@ -612,7 +621,7 @@ fn decrypt_optional_secret_for_runtime_reload(
     Ok(())
 }
 
-async fn load_runtime_defaults_from_config_file(path: &Path) -> Result<ChannelRuntimeDefaults> {
+async fn load_runtime_config_from_file(path: &Path) -> Result<Config> {
     let contents = tokio::fs::read_to_string(path)
         .await
         .with_context(|| format!("Failed to read {}", path.display()))?;
@@ -626,7 +635,7 @@ async fn load_runtime_defaults_from_config_file(path: &Path) -> Result<ChannelRu
     }
 
     parsed.apply_env_overrides();
-    Ok(runtime_defaults_from_config(&parsed))
+    Ok(parsed)
 }
 
 async fn maybe_apply_runtime_config_update(ctx: &ChannelRuntimeContext) -> Result<()> {
@@ -649,7 +658,8 @@ async fn maybe_apply_runtime_config_update(ctx: &ChannelRuntimeContext) -> Resul
         }
     }
 
-    let next_defaults = load_runtime_defaults_from_config_file(&config_path).await?;
+    let next_config = load_runtime_config_from_file(&config_path).await?;
+    let next_defaults = runtime_defaults_from_config(&next_config);
     let next_default_provider = providers::create_resilient_provider_with_options(
         &next_defaults.default_provider,
         next_defaults.api_key.as_deref(),
@@ -696,6 +706,10 @@ async fn maybe_apply_runtime_config_update(ctx: &ChannelRuntimeContext) -> Resul
         "Applied updated channel runtime config from disk"
     );
 
+    if let Some(hooks) = &ctx.hooks {
+        hooks.fire_config_reload(&next_config).await;
+    }
+
     Ok(())
 }
 
@@ -983,6 +997,32 @@ fn build_providers_help_response(current: &ChannelRouteSelection) -> String {
     response
 }
 
+/// Reply to `msg` on `channel`, running `before_send` hooks first so they can
+/// rewrite or suppress the outgoing text.
+async fn send_reply(
+    ctx: &ChannelRuntimeContext,
+    channel: &dyn Channel,
+    msg: &traits::ChannelMessage,
+    content: String,
+) -> anyhow::Result<()> {
+    let content = match ctx.hooks.as_deref() {
+        Some(hooks) => match hooks
+            .run_before_send(&msg.channel, &msg.reply_target, content)
+            .await
+        {
+            crate::hooks::HookResult::Cancel(reason) => {
+                tracing::info!(%reason, "outgoing message suppressed by before_send hook");
+                return Ok(());
+            }
+            crate::hooks::HookResult::Continue(content) => content,
+        },
+        None => content,
+    };
+    channel
+        .send(&SendMessage::new(content, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+        .await
+}
+
 async fn handle_runtime_command_if_needed(
     ctx: &ChannelRuntimeContext,
     msg: &traits::ChannelMessage,
@@ -1048,10 +1088,7 @@ async fn handle_runtime_command_if_needed(
         }
     };
 
-    if let Err(err) = channel
-        .send(&SendMessage::new(response, &msg.reply_target).in_thread(msg.thread_ts.clone()))
-        .await
-    {
+    if let Err(err) = send_reply(ctx, channel.as_ref(), msg, response).await {
         tracing::warn!(
             "Failed to send runtime command response on {}: {err}",
             channel.name()
@@ -1374,6 +1411,7 @@ fn spawn_supervised_listener(
     tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
     initial_backoff_secs: u64,
     max_backoff_secs: u64,
+    shutdown: CancellationToken,
 ) -> tokio::task::JoinHandle<()> {
     spawn_supervised_listener_with_health_interval(
         ch,
@@ -1381,15 +1419,21 @@ fn spawn_supervised_listener(
         initial_backoff_secs,
         max_backoff_secs,
         Duration::from_secs(CHANNEL_HEALTH_HEARTBEAT_SECS),
+        shutdown,
     )
 }
 
+/// Supervise `ch.listen`, restarting it with backoff until `tx` closes or
+/// `shutdown` is cancelled. On shutdown the listener gets
+/// [`CHANNEL_SHUTDOWN_GRACE_SECS`] to return on its own (channels that watch
+/// the token use it to drain buffered messages) before it is dropped.
 fn spawn_supervised_listener_with_health_interval(
     ch: Arc<dyn Channel>,
     tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
     initial_backoff_secs: u64,
     max_backoff_secs: u64,
     health_interval: Duration,
+    shutdown: CancellationToken,
 ) -> tokio::task::JoinHandle<()> {
     let health_interval = if health_interval.is_zero() {
         Duration::from_secs(1)
@@ -1416,11 +1460,21 @@ fn spawn_supervised_listener_with_health_interval(
                             crate::health::mark_component_ok(&component);
                         }
                         result = &mut listen_future => break result,
+                        () = shutdown.cancelled() => {
+                            let grace = Duration::from_secs(CHANNEL_SHUTDOWN_GRACE_SECS);
+                            if tokio::time::timeout(grace, &mut listen_future).await.is_err() {
+                                tracing::warn!(
+                                    "Channel {} did not stop within {CHANNEL_SHUTDOWN_GRACE_SECS}s of shutdown",
+                                    ch.name()
+                                );
+                            }
+                            break Ok(());
+                        }
                     }
                 }
             };
 
-            if tx.is_closed() {
+            if tx.is_closed() || shutdown.is_cancelled() {
                 break;
             }
 
@@ -1438,13 +1492,41 @@ fn spawn_supervised_listener_with_health_interval(
             }
 
             crate::health::bump_component_restart(&component);
-            tokio::time::sleep(Duration::from_secs(backoff)).await;
+            tokio::select! {
+                () = tokio::time::sleep(Duration::from_secs(backoff)) => {}
+                () = shutdown.cancelled() => break,
+            }
             // Double backoff AFTER sleeping so first error uses initial_backoff
             backoff = backoff.saturating_mul(2).min(max_backoff);
         }
     })
 }
 
+/// Periodically run `ch.health_check()` and report the result as the
+/// `channel:<name>:auth` health component until `shutdown` is cancelled.
+fn spawn_channel_auth_check(
+    ch: Arc<dyn Channel>,
+    shutdown: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let component = format!("channel:{}:auth", ch.name());
+        let mut interval = tokio::time::interval(Duration::from_secs(CHANNEL_AUTH_CHECK_SECS));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                () = shutdown.cancelled() => break,
+            }
+            let timeout = Duration::from_secs(CHANNEL_AUTH_CHECK_TIMEOUT_SECS);
+            match tokio::time::timeout(timeout, ch.health_check()).await {
+                Ok(true) => crate::health::mark_component_ok(&component),
+                Ok(false) => crate::health::mark_component_error(&component, "health check failed"),
+                Err(_) => crate::health::mark_component_error(&component, "health check timed out"),
+            }
+        }
+    })
+}
+
 fn compute_max_in_flight_messages(channel_count: usize) -> usize {
     channel_count
         .saturating_mul(CHANNEL_PARALLELISM_PER_CHANNEL)
@@ -1558,6 +1640,22 @@ async fn process_channel_message(
     }
 
     let history_key = conversation_history_key(&msg);
+    let hook_ctx = crate::hooks::HookContext::session(history_key.as_str(), msg.channel.as_str())
+        .with_sender(msg.sender.as_str());
+
+    // ── Hook: before_session_start (once per inbound message) ──
+    if let Some(hooks) = &ctx.hooks {
+        if let crate::hooks::HookResult::Cancel(reason) =
+            hooks.run_before_session_start_with_context(&hook_ctx).await
+        {
+            tracing::info!(%reason, "incoming message rejected by hook");
+            if let Some(channel) = target_channel.as_ref() {
+                let _ = send_reply(ctx.as_ref(), channel.as_ref(), &msg, reason).await;
+            }
+            return;
+        }
+    }
+
     let route = get_route_selection(ctx.as_ref(), &history_key);
     let runtime_defaults = runtime_defaults_snapshot(ctx.as_ref());
     let active_provider = match get_or_create_provider(ctx.as_ref(), &route.provider).await {
@@ -1569,12 +1667,7 @@ async fn process_channel_message(
                 route.provider
             );
             if let Some(channel) = target_channel.as_ref() {
-                let _ = channel
-                    .send(
-                        &SendMessage::new(message, &msg.reply_target)
-                            .in_thread(msg.thread_ts.clone()),
-                    )
-                    .await;
+                let _ = send_reply(ctx.as_ref(), channel.as_ref(), &msg, message).await;
             }
             return;
         }
@@ -1712,7 +1805,10 @@ async fn process_channel_message(
         }
     }
 
-    let typing_cancellation = target_channel.as_ref().map(|_| CancellationToken::new());
+    let typing_cancellation = target_channel
+        .as_ref()
+        .filter(|ch| ch.capabilities().typing)
+        .map(|_| CancellationToken::new());
     let typing_task = match (target_channel.as_ref(), typing_cancellation.as_ref()) {
         (Some(channel), Some(token)) => Some(spawn_scoped_typing_task(
             Arc::clone(channel),
@@ -1724,6 +1820,10 @@ async fn process_channel_message(
 
     // Record history length before tool loop so we can extract tool context after.
     let history_len_before_tools = history.len();
+    let tools_registry = ctx
+        .channel_tools
+        .get(&msg.channel)
+        .unwrap_or(&ctx.tools_registry);
 
     enum LlmExecutionResult {
         Completed(Result<Result<String, anyhow::Error>, tokio::time::error::Elapsed>),
@@ -1739,7 +1839,7 @@ async fn process_channel_message(
             run_tool_call_loop(
                 active_provider.as_ref(),
                 &mut history,
-                ctx.tools_registry.as_ref(),
+                tools_registry.as_ref(),
                 ctx.observer.as_ref(),
                 route.provider.as_str(),
                 route.model.as_str(),
@@ -1752,6 +1852,7 @@ async fn process_channel_message(
                 Some(cancellation_token.clone()),
                 delta_tx,
                 ctx.hooks.as_deref(),
+                Some(&hook_ctx),
                 if msg.channel == "cli" {
                     &[]
                 } else {
@@ -1866,7 +1967,7 @@ async fn process_channel_message(
             }
 
             let sanitized_response =
-                sanitize_channel_response(&outbound_response, ctx.tools_registry.as_ref());
+                sanitize_channel_response(&outbound_response, tools_registry.as_ref());
             let delivered_response = if sanitized_response.is_empty()
                 && !outbound_response.trim().is_empty()
             {
@@ -1918,12 +2019,13 @@ async fn process_channel_message(
                         tracing::warn!("Failed to finalize draft: {e}");
                     }
                 }
-                if let Err(e) = channel
-                    .send(
-                        &SendMessage::new(&delivered_response, &msg.reply_target)
-                            .in_thread(msg.thread_ts.clone()),
-                    )
-                    .await
+                if let Err(e) = send_reply(
+                    ctx.as_ref(),
+                    channel.as_ref(),
+                    &msg,
+                    delivered_response.clone(),
+                )
+                .await
                 {
                     eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
                 }
@@ -1992,12 +2094,8 @@ async fn process_channel_message(
                             tracing::warn!("Failed to finalize draft on context window error: {e}");
                         }
                     }
-                    let _ = channel
-                        .send(
-                            &SendMessage::new(error_text, &msg.reply_target)
-                                .in_thread(msg.thread_ts.clone()),
-                        )
-                        .await;
+                    let _ =
+                        send_reply(ctx.as_ref(), channel.as_ref(), &msg, error_text.into()).await;
                 }
             } else {
                 eprintln!(
@@ -2043,12 +2141,7 @@ async fn process_channel_message(
                             tracing::warn!("Failed to finalize draft on error: {e}");
                         }
                     }
-                    let _ = channel
-                        .send(
-                            &SendMessage::new(error_text, &msg.reply_target)
-                                .in_thread(msg.thread_ts.clone()),
-                        )
-                        .await;
+                    let _ = send_reply(ctx.as_ref(), channel.as_ref(), &msg, error_text).await;
                 }
             }
         }
@@ -2093,12 +2186,7 @@ async fn process_channel_message(
                         tracing::warn!("Failed to finalize draft on timeout: {e}");
                     }
                 }
-                let _ = channel
-                    .send(
-                        &SendMessage::new(error_text, &msg.reply_target)
-                            .in_thread(msg.thread_ts.clone()),
-                    )
-                    .await;
+                let _ = send_reply(ctx.as_ref(), channel.as_ref(), &msg, error_text.into()).await;
             }
         }
     }
@@ -2252,7 +2340,7 @@ pub fn build_system_prompt(
         identity_config,
         bootstrap_max_chars,
         false,
-        crate::config::SkillsPromptInjectionMode::Full,
+        &crate::config::SkillsConfig::default(),
     )
 }
 
@@ -2264,7 +2352,7 @@ pub fn build_system_prompt_with_mode(
     identity_config: Option<&crate::config::IdentityConfig>,
     bootstrap_max_chars: Option<usize>,
     native_tools: bool,
-    skills_prompt_mode: crate::config::SkillsPromptInjectionMode,
+    skills_config: &crate::config::SkillsConfig,
 ) -> String {
     use std::fmt::Write;
     let mut prompt = String::with_capacity(8192);
@@ -2329,10 +2417,10 @@ pub fn build_system_prompt_with_mode(
 
     // ── 3. Skills (full or compact, based on config) ─────────────
     if !skills.is_empty() {
-        prompt.push_str(&crate::skills::skills_to_prompt_with_mode(
+        prompt.push_str(&crate::skills::skills_to_prompt_from_config(
             skills,
             workspace_dir,
-            skills_prompt_mode,
+            skills_config,
         ));
         prompt.push_str("\n\n");
     }
@@ -2705,6 +2793,8 @@ fn collect_configured_channels(
     matrix_skip_context: &str,
     #[cfg(any(feature = "channel-lark", feature = "feishu-docs-sync"))]
     ws_manager: &Option<std::sync::Arc<lark_ws_manager::LarkWsManager>>,
+    #[cfg(any(feature = "channel-lark", feature = "feishu-docs-sync"))]
+    shutdown: &CancellationToken,
 ) -> Vec<ConfiguredChannel> {
     let mut channels = Vec::new();
 
@@ -2931,7 +3021,10 @@ fn collect_configured_channels(
                     .parent()
                     .map_or_else(|| std::path::PathBuf::from("."), std::path::PathBuf::from)
                     .join("docs_sync.lock");
-                Some(std::sync::Arc::new(crate::docs_sync::DocsSyncSharer::new(id, secret, lock_path)))
+                Some(std::sync::Arc::new(
+                    crate::docs_sync::DocsSyncSharer::new(id, secret, lock_path)
+                        .with_permission(config.docs_sync.share_permission),
+                ))
             }
             _ => None,
         }
@@ -2953,7 +3046,9 @@ fn collect_configured_channels(
                 channels.push(ConfiguredChannel {
                     display_name: "Feishu",
                     channel: Arc::new({
-                        let mut ch = LarkChannel::from_config(lk);
+                        let mut ch = LarkChannel::from_config(lk)
+                            .with_workspace_dir(config.workspace_dir.clone())
+                            .with_shutdown(shutdown.clone());
                         #[cfg(feature = "feishu-docs-sync")]
                         if let Some(ref sharer) = docs_sharer {
                             ch.set_docs_sharer(std::sync::Arc::clone(sharer));
@@ -2969,7 +3064,9 @@ fn collect_configured_channels(
             channels.push(ConfiguredChannel {
                 display_name: "Lark",
                 channel: Arc::new({
-                    let mut ch = LarkChannel::from_lark_config(lk);
+                    let mut ch = LarkChannel::from_lark_config(lk)
+                        .with_workspace_dir(config.workspace_dir.clone())
+                        .with_shutdown(shutdown.clone());
                     #[cfg(feature = "feishu-docs-sync")]
                     if let Some(ref sharer) = docs_sharer {
                         ch.set_docs_sharer(std::sync::Arc::clone(sharer));
@@ -2988,7 +3085,9 @@ fn collect_configured_channels(
         channels.push(ConfiguredChannel {
             display_name: "Feishu",
             channel: Arc::new({
-                let mut ch = LarkChannel::from_feishu_config(fs);
+                let mut ch = LarkChannel::from_feishu_config(fs)
+                    .with_workspace_dir(config.workspace_dir.clone())
+                    .with_shutdown(shutdown.clone());
                 #[cfg(feature = "feishu-docs-sync")]
                 if let Some(ref sharer) = docs_sharer {
                     ch.set_docs_sharer(std::sync::Arc::clone(sharer));
@@ -3043,7 +3142,8 @@ fn collect_configured_channels(
 /// Run health checks for configured channels.
 pub async fn doctor_channels(config: Config) -> Result<()> {
     #[cfg(any(feature = "channel-lark", feature = "feishu-docs-sync"))]
-    let mut channels = collect_configured_channels(&config, "health check", &None);
+    let mut channels =
+        collect_configured_channels(&config, "health check", &None, &CancellationToken::new());
     #[cfg(not(any(feature = "channel-lark", feature = "feishu-docs-sync")))]
     let mut channels = collect_configured_channels(&config, "health check");
 
@@ -3101,10 +3201,46 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
     Ok(())
 }
 
-/// Start all configured channels and route messages to the agent
+/// Build a tool registry per channel holding the shared tools plus the MCP
+/// tools (bridged servers and `mcp` skill tools) that `[mcp.channels]` lets
+/// that channel use. Servers are connected up front, before any message is
+/// handled.
+#[cfg(feature = "mcp")]
+async fn mcp_channel_tools<'a>(
+    config: &Config,
+    base_tools: &[Arc<dyn Tool>],
+    skills: &[crate::skills::Skill],
+    channels: impl Iterator<Item = &'a String>,
+) -> ChannelToolsMap {
+    let builtin_names = base_tools.iter().map(|t| t.name().to_string());
+    let Some((registry, pending)) = tools::mcp_bridge::registry_from_config(config, builtin_names)
+    else {
+        return HashMap::new();
+    };
+    tools::mcp_bridge::connect_servers(&registry, pending).await;
+
+    let mut by_channel = HashMap::new();
+    for channel in channels {
+        let mut channel_tools = tools::boxed_registry_from_arcs(base_tools.to_vec());
+        if config.skills.register_tools {
+            channel_tools.extend(tools::skill_tool::mcp_skill_tools(
+                skills, &registry, channel,
+            ));
+        }
+        channel_tools.extend(tools::mcp_bridge::channel_tools(&registry, channel).await);
+        by_channel.insert(channel.clone(), Arc::new(channel_tools));
+    }
+    by_channel
+}
+
+/// Start all configured channels and route messages to the agent.
+///
+/// Listeners stop (and drain) once `shutdown` is cancelled; the caller owns
+/// signal handling.
 #[allow(clippy::too_many_lines, unused_variables)]
 pub async fn start_channels(
     config: Config,
+    shutdown: CancellationToken,
     #[cfg(any(feature = "channel-lark", feature = "feishu-docs-sync"))]
     ws_manager: Option<std::sync::Arc<lark_ws_manager::LarkWsManager>>,
 ) -> Result<()> {
@@ -3172,7 +3308,7 @@ pub async fn start_channels(
     };
     // Build system prompt from workspace identity files + skills
     let workspace = config.workspace_dir.clone();
-    let tools_registry = Arc::new(tools::all_tools_with_runtime(
+    let base_tools: Vec<Arc<dyn Tool>> = tools::all_tools_with_runtime(
         Arc::new(config.clone()),
         &security,
         runtime,
@@ -3186,7 +3322,11 @@ pub async fn start_channels(
         config.api_key.as_deref(),
         &config,
         None,  // shared_skills
-    ));
+    )
+    .into_iter()
+    .map(Arc::from)
+    .collect();
+    let tools_registry = Arc::new(tools::boxed_registry_from_arcs(base_tools.clone()));
 
     let skills = crate::skills::load_skills_with_config(&workspace, &config);
 
@@ -3309,12 +3449,18 @@ pub async fn start_channels(
         Some(&config.identity),
         bootstrap_max_chars,
         native_tools,
-        config.skills.prompt_injection_mode,
+        &config.skills,
     );
     if !native_tools {
         system_prompt.push_str(&build_tool_instructions(tools_registry.as_ref()));
     }
     append_unrestricted_commands_hint(&mut system_prompt, &config.autonomy.allowed_commands);
+    crate::skills::apply_skills_prompt_position(
+        &mut system_prompt,
+        &skills,
+        &workspace,
+        &config.skills,
+    );
 
     if !skills.is_empty() {
         println!(
@@ -3330,7 +3476,7 @@ pub async fn start_channels(
     // Collect active channels from a shared builder to keep startup and doctor parity.
     #[cfg(any(feature = "channel-lark", feature = "feishu-docs-sync"))]
     let mut channels: Vec<Arc<dyn Channel>> =
-        collect_configured_channels(&config, "runtime startup", &ws_manager)
+        collect_configured_channels(&config, "runtime startup", &ws_manager, &shutdown)
             .into_iter()
             .map(|configured| configured.channel)
             .collect();
@@ -3396,7 +3542,9 @@ pub async fn start_channels(
             tx.clone(),
             initial_backoff_secs,
             max_backoff_secs,
+            shutdown.clone(),
         ));
+        spawn_channel_auth_check(ch.clone(), shutdown.clone());
     }
     drop(tx); // Drop our copy so rx closes when all channels stop
 
@@ -3420,12 +3568,27 @@ pub async fn start_channels(
         .as_ref()
         .is_some_and(|tg| tg.interrupt_on_new_message);
 
+    let hook_runner = if config.hooks.enabled {
+        let mut runner = crate::hooks::HookRunner::new();
+        crate::hooks::builtin::register_builtins(&mut runner, &config);
+        Some(Arc::new(runner))
+    } else {
+        None
+    };
+
+    #[cfg(feature = "mcp")]
+    let channel_tools =
+        mcp_channel_tools(&config, &base_tools, &skills, channels_by_name.keys()).await;
+    #[cfg(not(feature = "mcp"))]
+    let channel_tools = HashMap::new();
+
     let runtime_ctx = Arc::new(ChannelRuntimeContext {
         channels_by_name,
         provider: Arc::clone(&provider),
         default_provider: Arc::new(provider_name),
         memory: Arc::clone(&mem),
         tools_registry: Arc::clone(&tools_registry),
+        channel_tools: Arc::new(channel_tools),
         observer,
         system_prompt: Arc::new(system_prompt),
         model: Arc::new(model.clone()),
@@ -3444,20 +3607,33 @@ pub async fn start_channels(
         message_timeout_secs,
         interrupt_on_new_message,
         multimodal: config.multimodal.clone(),
-        hooks: if config.hooks.enabled {
-            let mut runner = crate::hooks::HookRunner::new();
-            if config.hooks.builtin.command_logger {
-                runner.register(Box::new(crate::hooks::builtin::CommandLoggerHook::new()));
-            }
-            Some(Arc::new(runner))
-        } else {
-            None
-        },
+        hooks: hook_runner,
         hooks_config: Arc::new(config.hooks.clone()),
         last_hook_stamp: Arc::new(Mutex::new(None)),
         non_cli_excluded_tools: Arc::new(config.autonomy.non_cli_excluded_tools.clone()),
     });
 
+    // Kept alive for the dispatch loop; dropping it stops the watcher.
+    #[cfg(feature = "hooks-watch")]
+    let _hooks_watcher = runtime_ctx.hooks.as_ref().and_then(|runner| {
+        let hooks_dir = config
+            .hooks
+            .hooks_dir
+            .clone()
+            .unwrap_or_else(|| config.workspace_dir.join("hooks"));
+        match crate::hooks::watcher::HooksWatcher::spawn(
+            hooks_dir,
+            config.hooks.clone(),
+            Arc::clone(runner),
+        ) {
+            Ok(watcher) => Some(watcher),
+            Err(err) => {
+                tracing::warn!("HOOK.toml hot-reload disabled: {err}");
+                None
+            }
+        }
+    });
+
     run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
 
     // Wait for all channel tasks
@@ -3649,6 +3825,7 @@ mod tests {
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("system".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -3700,6 +3877,7 @@ mod tests {
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("system".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -3754,6 +3932,7 @@ mod tests {
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("system".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -3814,6 +3993,8 @@ mod tests {
         stop_typing_calls: AtomicUsize,
         reactions_added: tokio::sync::Mutex<Vec<(String, String, String)>>,
         reactions_removed: tokio::sync::Mutex<Vec<(String, String, String)>>,
+        /// Report no typing support in `capabilities()`.
+        without_typing: bool,
     }
 
     #[derive(Default)]
@@ -3857,6 +4038,14 @@ mod tests {
             "test-channel"
         }
 
+        fn capabilities(&self) -> traits::ChannelCapabilities {
+            traits::ChannelCapabilities {
+                reactions: true,
+                typing: !self.without_typing,
+                ..traits::ChannelCapabilities::default()
+            }
+        }
+
         async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
             self.sent_messages
                 .lock()
@@ -4231,6 +4420,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4278,6 +4468,171 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(!sent_messages[0].contains("mock_price"));
     }
 
+    #[tokio::test]
+    async fn process_channel_message_rate_limits_per_sender_message_not_llm_call() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let mut runner = crate::hooks::HookRunner::new();
+        runner.register(Box::new(crate::hooks::builtin::RateLimitHook::new(
+            crate::config::schema::RateLimitHookConfig {
+                max_calls: 1,
+                window_secs: 60,
+            },
+        )));
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::new(ToolCallingProvider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            channel_tools: Arc::new(HashMap::new()),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 10,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: Some(Arc::new(runner)),
+            hooks_config: Arc::new(crate::config::schema::HooksConfig::default()),
+            last_hook_stamp: Arc::new(Mutex::new(None)),
+        });
+
+        let message = |id: &str, sender: &str| traits::ChannelMessage {
+            id: id.to_string(),
+            sender: sender.to_string(),
+            reply_target: "chat-42".to_string(),
+            content: "What is the BTC price now?".to_string(),
+            channel: "test-channel".to_string(),
+            timestamp: 1,
+            thread_ts: None,
+        };
+        // The first message runs a tool loop (two LLM calls) within one budget slot.
+        for (id, sender) in [("msg-1", "alice"), ("msg-2", "alice"), ("msg-3", "bob")] {
+            process_channel_message(
+                Arc::clone(&runtime_ctx),
+                message(id, sender),
+                CancellationToken::new(),
+            )
+            .await;
+        }
+
+        let sent_messages = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent_messages.len(), 3, "{sent_messages:#?}");
+        assert!(sent_messages[0].contains("BTC is currently around"));
+        assert!(sent_messages[1].contains("too quickly"));
+        assert!(sent_messages[2].contains("BTC is currently around"));
+    }
+
+    /// Records the context every `before_tool_call` dispatch receives.
+    #[derive(Default)]
+    struct ToolCallContextRecorder {
+        seen: Arc<std::sync::Mutex<Vec<crate::hooks::HookContext>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::hooks::HookHandler for ToolCallContextRecorder {
+        fn name(&self) -> &str {
+            "tool_call_context_recorder"
+        }
+
+        async fn before_tool_call_with_context(
+            &self,
+            ctx: &crate::hooks::HookContext,
+            name: String,
+            args: serde_json::Value,
+        ) -> crate::hooks::HookResult<(String, serde_json::Value)> {
+            self.seen.lock().unwrap().push(ctx.clone());
+            crate::hooks::HookResult::Continue((name, args))
+        }
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_passes_sender_context_to_tool_hooks() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let recorder = ToolCallContextRecorder::default();
+        let seen = Arc::clone(&recorder.seen);
+        let mut runner = crate::hooks::HookRunner::new();
+        runner.register(Box::new(recorder));
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::new(ToolCallingProvider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            channel_tools: Arc::new(HashMap::new()),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 10,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: Some(Arc::new(runner)),
+            hooks_config: Arc::new(crate::config::schema::HooksConfig::default()),
+            last_hook_stamp: Arc::new(Mutex::new(None)),
+        });
+
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "msg-1".to_string(),
+                sender: "alice".to_string(),
+                reply_target: "chat-42".to_string(),
+                content: "What is the BTC price now?".to_string(),
+                channel: "test-channel".to_string(),
+                timestamp: 1,
+                thread_ts: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        let seen = seen.lock().unwrap();
+        assert!(!seen.is_empty(), "tool hook never ran");
+        for ctx in seen.iter() {
+            assert_eq!(ctx.channel.as_deref(), Some("test-channel"));
+            assert_eq!(ctx.sender.as_deref(), Some("alice"));
+            assert_eq!(ctx.model.as_deref(), Some("test-model"));
+            assert!(ctx.session_id.is_some());
+        }
+    }
+
     #[tokio::test]
     async fn process_channel_message_telegram_does_not_persist_tool_summary_prefix() {
         let channel_impl = Arc::new(TelegramRecordingChannel::default());
@@ -4292,6 +4647,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4367,6 +4723,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4428,6 +4785,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4498,6 +4856,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("default-model".to_string()),
@@ -4589,6 +4948,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("default-model".to_string()),
@@ -4662,6 +5022,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("default-model".to_string()),
@@ -4747,6 +5108,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("startup-model".to_string()),
@@ -4823,6 +5185,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4885,6 +5248,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5058,6 +5422,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5140,6 +5505,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5234,6 +5600,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5310,6 +5677,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5355,6 +5723,70 @@ BTC is currently around $65,000 based on latest tool output."#
         assert_eq!(stops, 1, "stop_typing should be called once");
     }
 
+    #[tokio::test]
+    async fn process_channel_message_skips_typing_without_capability() {
+        let channel_impl = Arc::new(RecordingChannel {
+            without_typing: true,
+            ..RecordingChannel::default()
+        });
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::new(SlowProvider {
+                delay: Duration::from_millis(20),
+            }),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 10,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            hooks_config: Arc::new(crate::config::schema::HooksConfig::default()),
+            last_hook_stamp: Arc::new(Mutex::new(None)),
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+        });
+
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "typing-msg".to_string(),
+                sender: "alice".to_string(),
+                reply_target: "chat-typing".to_string(),
+                content: "hello".to_string(),
+                channel: "test-channel".to_string(),
+                timestamp: 1,
+                thread_ts: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        assert_eq!(channel_impl.start_typing_calls.load(Ordering::SeqCst), 0);
+        assert_eq!(channel_impl.stop_typing_calls.load(Ordering::SeqCst), 0);
+        assert_eq!(channel_impl.sent_messages.lock().await.len(), 1);
+    }
+
     #[tokio::test]
     async fn process_channel_message_adds_and_swaps_reactions() {
         let channel_impl = Arc::new(RecordingChannel::default());
@@ -5371,6 +5803,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5600,6 +6033,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 kind: "shell".into(),
                 command: "cargo clippy".into(),
                 args: HashMap::new(),
+                required: vec![],
             }],
             prompts: vec!["Always run cargo test before final response.".into()],
             location: None,
@@ -5635,6 +6069,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 kind: "shell".into(),
                 command: "cargo clippy".into(),
                 args: HashMap::new(),
+                required: vec![],
             }],
             prompts: vec!["Always run cargo test before final response.".into()],
             location: None,
@@ -5648,7 +6083,10 @@ BTC is currently around $65,000 based on latest tool output."#
             None,
             None,
             false,
-            crate::config::SkillsPromptInjectionMode::Compact,
+            &crate::config::SkillsConfig {
+                prompt_injection_mode: crate::config::SkillsPromptInjectionMode::Compact,
+                ..crate::config::SkillsConfig::default()
+            },
         );
 
         assert!(prompt.contains("<available_skills>"), "missing skills XML");
@@ -5676,6 +6114,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 kind: "shell&exec".into(),
                 command: "cargo clippy".into(),
                 args: HashMap::new(),
+                required: vec![],
             }],
             prompts: vec!["Use <tool_call> and & keep output \"safe\"".into()],
             location: None,
@@ -5889,6 +6328,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5976,6 +6416,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(RecallMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -6063,6 +6504,7 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -6403,7 +6845,8 @@ This is an example JSON object for profile settings."#;
         });
 
         #[cfg(any(feature = "channel-lark", feature = "feishu-docs-sync"))]
-        let channels = collect_configured_channels(&config, "test", &None);
+        let channels =
+            collect_configured_channels(&config, "test", &None, &CancellationToken::new());
         #[cfg(not(any(feature = "channel-lark", feature = "feishu-docs-sync")))]
         let channels = collect_configured_channels(&config, "test");
 
@@ -6442,6 +6885,10 @@ This is an example JSON object for profile settings."#;
             self.calls.fetch_add(1, Ordering::SeqCst);
             anyhow::bail!("listen boom")
         }
+
+        async fn health_check(&self) -> bool {
+            false
+        }
     }
 
     #[async_trait::async_trait]
@@ -6473,7 +6920,7 @@ This is an example JSON object for profile settings."#;
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(1);
-        let handle = spawn_supervised_listener(channel, tx, 1, 1);
+        let handle = spawn_supervised_listener(channel, tx, 1, 1, CancellationToken::new());
 
         tokio::time::sleep(Duration::from_millis(80)).await;
         drop(rx);
@@ -6491,6 +6938,48 @@ This is an example JSON object for profile settings."#;
         assert!(calls.load(Ordering::SeqCst) >= 1);
     }
 
+    #[tokio::test]
+    async fn supervised_listener_stops_restarting_after_shutdown() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let channel: Arc<dyn Channel> = Arc::new(AlwaysFailChannel {
+            name: "test-supervised-shutdown",
+            calls: Arc::clone(&calls),
+        });
+        let shutdown = CancellationToken::new();
+
+        let (tx, _rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(1);
+        let handle = spawn_supervised_listener(channel, tx, 60, 60, shutdown.clone());
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        shutdown.cancel();
+        let join = tokio::time::timeout(Duration::from_secs(1), handle).await;
+        assert!(
+            join.is_ok(),
+            "supervisor should stop during restart backoff"
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn channel_auth_check_reports_failed_health_check() {
+        let channel: Arc<dyn Channel> = Arc::new(AlwaysFailChannel {
+            name: "test-auth-check",
+            calls: Arc::new(AtomicUsize::new(0)),
+        });
+        let shutdown = CancellationToken::new();
+
+        let handle = spawn_channel_auth_check(channel, shutdown.clone());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        shutdown.cancel();
+        let join = tokio::time::timeout(Duration::from_secs(1), handle).await;
+        assert!(join.is_ok(), "auth check should stop on shutdown");
+
+        let snapshot = crate::health::snapshot_json();
+        let component = &snapshot["components"]["channel:test-auth-check:auth"];
+        assert_eq!(component["status"], "error");
+        assert_eq!(component["last_error"], "health check failed");
+    }
+
     #[tokio::test]
     async fn supervised_listener_refreshes_health_while_running() {
         let calls = Arc::new(AtomicUsize::new(0));
@@ -6508,6 +6997,7 @@ This is an example JSON object for profile settings."#;
             1,
             1,
             Duration::from_millis(20),
+            CancellationToken::new(),
         );
 
         tokio::time::sleep(Duration::from_millis(35)).await;
@@ -6617,6 +7107,7 @@ This is an example JSON object for profile settings."#;
             default_provider: Arc::new("dummy".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("You are a helpful assistant.".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -6685,6 +7176,7 @@ This is an example JSON object for profile settings."#;
             default_provider: Arc::new("dummy".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("You are a helpful assistant.".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -1,4 +1,4 @@
-use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::channels::traits::{Channel, ChannelCapabilities, ChannelMessage, SendMessage};
 use async_trait::async_trait;
 use futures_util::StreamExt;
 use reqwest::Client;
@@ -276,6 +276,13 @@ impl Channel for SignalChannel {
         "signal"
     }
 
+    fn capabilities(&self) -> ChannelCapabilities {
+        ChannelCapabilities {
+            typing: true,
+            ..ChannelCapabilities::default()
+        }
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         let params = match Self::parse_recipient_target(&message.recipient) {
             RecipientTarget::Direct(number) => serde_json::json!({
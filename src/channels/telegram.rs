@@ -1,4 +1,4 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{AttachmentKind, Channel, ChannelCapabilities, ChannelMessage, SendMessage};
 use crate::config::{Config, StreamMode};
 use crate::security::pairing::PairingGuard;
 use anyhow::Context;
@@ -2185,6 +2185,21 @@ impl Channel for TelegramChannel {
         self.stream_mode != StreamMode::Off
     }
 
+    fn capabilities(&self) -> ChannelCapabilities {
+        ChannelCapabilities {
+            draft_updates: self.supports_draft_updates(),
+            attachments: vec![
+                AttachmentKind::Image,
+                AttachmentKind::Document,
+                AttachmentKind::Video,
+                AttachmentKind::Audio,
+                AttachmentKind::Voice,
+            ],
+            typing: true,
+            ..ChannelCapabilities::default()
+        }
+    }
+
     async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
         if self.stream_mode == StreamMode::Off {
             return Ok(None);
@@ -22,6 +22,9 @@ pub struct SendMessage {
     pub subject: Option<String>,
     /// Platform thread identifier for threaded replies (e.g. Slack `thread_ts`).
     pub thread_ts: Option<String>,
+    /// Deliver immediately, bypassing draft-update throttling (e.g. alerts).
+    /// Channels without a notion of priority ignore it.
+    pub urgent: bool,
 }
 
 impl SendMessage {
@@ -32,6 +35,7 @@ impl SendMessage {
             recipient: recipient.into(),
             subject: None,
             thread_ts: None,
+            urgent: false,
         }
     }
 
@@ -46,6 +50,7 @@ impl SendMessage {
             recipient: recipient.into(),
             subject: Some(subject.into()),
             thread_ts: None,
+            urgent: false,
         }
     }
 
@@ -54,6 +59,50 @@ impl SendMessage {
         self.thread_ts = thread_ts;
         self
     }
+
+    /// Mark the message as urgent.
+    pub fn urgent(mut self, urgent: bool) -> Self {
+        self.urgent = urgent;
+        self
+    }
+}
+
+/// Kind of outgoing attachment a channel can deliver natively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AttachmentKind {
+    Image,
+    Document,
+    Audio,
+    Video,
+    Voice,
+}
+
+/// What a channel supports beyond plain text send/receive.
+///
+/// Returned by [`Channel::capabilities`] so callers can skip features a
+/// channel would silently ignore.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChannelCapabilities {
+    /// Progressive draft edits (`send_draft` / `update_draft` / `finalize_draft`).
+    pub draft_updates: bool,
+    /// Attachment kinds delivered natively rather than as text.
+    pub attachments: Vec<AttachmentKind>,
+    /// `add_reaction` / `remove_reaction` are implemented.
+    pub reactions: bool,
+    /// The channel acknowledges incoming messages with a reaction on its own.
+    pub ack_reactions: bool,
+    /// Replies honour `thread_ts`.
+    pub threads: bool,
+    /// `start_typing` / `stop_typing` show an indicator.
+    pub typing: bool,
+}
+
+impl ChannelCapabilities {
+    /// Whether the channel can deliver `kind` as a native attachment.
+    pub fn supports_attachment(&self, kind: AttachmentKind) -> bool {
+        self.attachments.contains(&kind)
+    }
 }
 
 /// Core channel trait — implement for any messaging platform
@@ -89,6 +138,14 @@ pub trait Channel: Send + Sync {
         false
     }
 
+    /// Describe the optional features this channel supports.
+    fn capabilities(&self) -> ChannelCapabilities {
+        ChannelCapabilities {
+            draft_updates: self.supports_draft_updates(),
+            ..ChannelCapabilities::default()
+        }
+    }
+
     /// Send an initial draft message. Returns a platform-specific message ID for later edits.
     async fn send_draft(&self, _message: &SendMessage) -> anyhow::Result<Option<String>> {
         Ok(None)
@@ -244,6 +301,14 @@ mod tests {
         assert!(channel.cancel_draft("bob", "msg_1").await.is_ok());
     }
 
+    #[test]
+    fn default_capabilities_are_minimal() {
+        let caps = DummyChannel.capabilities();
+
+        assert_eq!(caps, ChannelCapabilities::default());
+        assert!(!caps.supports_attachment(AttachmentKind::Image));
+    }
+
     #[tokio::test]
     async fn listen_sends_message_to_channel() {
         let channel = DummyChannel;
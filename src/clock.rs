@@ -0,0 +1,111 @@
+//! Injectable time source for time-dependent logic.
+//!
+//! Code that decides things based on elapsed time (open-skills sync cadence,
+//! tenant token refresh, dedup windows) reads the time through [`Clock`]
+//! instead of calling `Instant::now()` / `SystemTime::now()` directly, so tests
+//! can drive those decisions with a [`MockClock`].
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Source of monotonic and wall-clock time.
+pub trait Clock: Send + Sync {
+    /// Monotonic time, for measuring intervals.
+    fn now(&self) -> Instant;
+
+    /// Wall-clock time, for comparing against file timestamps and the like.
+    fn system_now(&self) -> SystemTime;
+}
+
+/// The real clock backed by the operating system.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn system_now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A manually driven clock. Time only moves when [`MockClock::advance`] or
+/// [`MockClock::set_system_time`] is called.
+#[derive(Debug)]
+pub struct MockClock {
+    state: Mutex<(Instant, SystemTime)>,
+}
+
+impl MockClock {
+    /// Start at the current real time.
+    pub fn new() -> Self {
+        Self::starting_at(SystemTime::now())
+    }
+
+    /// Start with the wall clock at `system_time`.
+    pub fn starting_at(system_time: SystemTime) -> Self {
+        Self {
+            state: Mutex::new((Instant::now(), system_time)),
+        }
+    }
+
+    /// Move both the monotonic and the wall clock forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.0 += by;
+        state.1 += by;
+    }
+
+    /// Jump the wall clock to `system_time` (forwards or backwards) without
+    /// touching the monotonic clock, e.g. to simulate an NTP correction.
+    pub fn set_system_time(&self, system_time: SystemTime) {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).1 = system_time;
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).0
+    }
+
+    fn system_now(&self) -> SystemTime {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mock_clock_only_moves_when_advanced() {
+        let clock = MockClock::new();
+        let (instant, system) = (clock.now(), clock.system_now());
+        assert_eq!(clock.now(), instant);
+
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(clock.now().duration_since(instant), Duration::from_secs(90));
+        assert_eq!(
+            clock.system_now().duration_since(system).unwrap(),
+            Duration::from_secs(90)
+        );
+    }
+
+    #[test]
+    fn mock_clock_wall_time_can_jump_backwards() {
+        let clock = MockClock::new();
+        let instant = clock.now();
+        clock.set_system_time(SystemTime::UNIX_EPOCH);
+
+        assert_eq!(clock.system_now(), SystemTime::UNIX_EPOCH);
+        assert_eq!(clock.now(), instant);
+    }
+}
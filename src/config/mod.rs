@@ -6,17 +6,18 @@ pub use schema::{
     apply_runtime_proxy_to_builder, build_runtime_proxy_client,
     build_runtime_proxy_client_with_timeouts, runtime_proxy_config, set_runtime_proxy_config,
     AgentConfig, AuditConfig, AutonomyConfig, BrowserComputerUseConfig, BrowserConfig,
-    BuiltinHooksConfig, ChannelsConfig, ClassificationRule, ComposioConfig, Config, CostConfig,
-    CronConfig, DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig, DocsSyncConfig,
-    EmbeddingRouteConfig, EstopConfig, FeishuConfig, GatewayConfig, HardwareConfig,
-    HardwareTransport, HeartbeatConfig, HooksConfig, HttpRequestConfig, IMessageConfig,
-    IdentityConfig, LarkConfig, MatrixConfig, McpConfig, MemoryConfig, ModelRouteConfig,
-    MultimodalConfig, NextcloudTalkConfig, ObservabilityConfig, OtpConfig, PeripheralBoardConfig,
-    PeripheralsConfig, ProxyConfig, ProxyScope, QueryClassificationConfig, ReliabilityConfig,
-    RemoteSyncMode, ResourceLimitsConfig, RuntimeConfig, SandboxBackend, SandboxConfig,
-    SecretsConfig, SecurityConfig, SkillsConfig, SkillsPromptInjectionMode, SlackConfig,
-    StorageConfig, StorageProviderConfig, StorageProviderSection, StreamMode, TelegramConfig,
-    TranscriptionConfig, TunnelConfig, VpnConfig, WebSearchConfig, WebhookConfig,
+    BuiltinHooksConfig, ChannelsConfig, ClassificationRule, ComposioConfig, Config, ConflictPolicy,
+    CostConfig, CronConfig, DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig,
+    DocsSyncConfig, EmbeddingRouteConfig, EstopConfig, EventLogHookConfig, FeishuConfig,
+    GatewayConfig, HardwareConfig, HardwareTransport, HeartbeatConfig, HooksConfig,
+    HttpRequestConfig, IMessageConfig, IdentityConfig, LarkConfig, MatrixConfig, McpConfig,
+    MemoryConfig, ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig, ObservabilityConfig,
+    OtpConfig, PeripheralBoardConfig, PeripheralsConfig, ProxyConfig, ProxyScope,
+    QueryClassificationConfig, ReliabilityConfig, RemoteSyncMode, ResourceLimitsConfig,
+    RuntimeConfig, SandboxBackend, SandboxConfig, SecretsConfig, SecurityConfig, SharePermission,
+    SkillsConfig, SkillsPromptInjectionMode, SkillsPromptPosition, SlackConfig, StorageConfig,
+    StorageProviderConfig, StorageProviderSection, StreamMode, TelegramConfig, TranscriptionConfig,
+    TunnelConfig, VpnConfig, VpnHealthStrategy, WebSearchConfig, WebhookConfig, WebhookHookConfig,
 };
 
 pub fn name_and_presence<T: traits::ChannelConfig>(channel: Option<&T>) -> (&'static str, bool) {
@@ -66,6 +67,15 @@ mod tests {
             port: None,
             stream_mode: crate::config::schema::StreamMode::default(),
             draft_update_interval_ms: 500,
+            draft_update_min_interval_ms: 500,
+            draft_update_debounce_ms: 0,
+            urgent_escalation: crate::config::schema::LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: crate::config::schema::LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
         };
         let feishu = FeishuConfig {
             app_id: "app-id".into(),
@@ -77,6 +87,15 @@ mod tests {
             port: None,
             stream_mode: crate::config::schema::StreamMode::default(),
             draft_update_interval_ms: 500,
+            draft_update_min_interval_ms: 500,
+            draft_update_debounce_ms: 0,
+            urgent_escalation: crate::config::schema::LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: crate::config::schema::LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
         };
 
         let nextcloud_talk = NextcloudTalkConfig {
@@ -403,18 +403,59 @@ pub struct McpConfig {
     /// Path to .mcp.json config file. Default: ".mcp.json" (relative to workspace).
     #[serde(default)]
     pub config_path: Option<String>,
+    /// Editor-style registry files (`mcp.json` / `mcp.toml` in the
+    /// `{"mcpServers": {...}}` format) to import servers from, relative to
+    /// the workspace. Servers already in `.mcp.json` take precedence.
+    #[serde(default)]
+    pub registries: Vec<String>,
+    /// Per-channel server allowlists (`[mcp.channels]`), keyed by channel name,
+    /// e.g. `telegram = ["github"]`. Channels not listed see every server.
+    #[serde(default)]
+    pub channels: HashMap<String, Vec<String>>,
+    /// Truncate MCP tool result text above this many characters, keeping the
+    /// head and tail. `0` disables truncation. Servers can override this with
+    /// `maxOutputChars` in `.mcp.json`. Default: 50000.
+    #[serde(default = "default_mcp_max_output_chars")]
+    pub max_output_chars: usize,
+    /// Directory to dump each server's JSON-RPC traffic into, one
+    /// `<server>.jsonl` file per server, for debugging protocol issues.
+    /// Disabled when unset. Env override: `ZEROCLAW_MCP_TRACE_DIR`.
+    #[serde(default)]
+    pub trace_dir: Option<String>,
+    /// Seconds before an MCP tool call is abandoned. Default: 30.
+    #[serde(default = "default_mcp_tool_timeout_secs")]
+    pub tool_timeout_secs: u64,
+    /// Per-tool timeout overrides in seconds (`[mcp.tool_timeouts]`), keyed
+    /// by `server:tool` (e.g. `"ci:run_build" = 900`) or by a bare tool name
+    /// that applies to that tool on every server.
+    #[serde(default)]
+    pub tool_timeouts: HashMap<String, u64>,
 }
 
 fn default_mcp_tool_cap() -> usize {
     50
 }
 
+fn default_mcp_tool_timeout_secs() -> u64 {
+    30
+}
+
+fn default_mcp_max_output_chars() -> usize {
+    50_000
+}
+
 impl Default for McpConfig {
     fn default() -> Self {
         Self {
             enabled: false,
             tool_cap: 50,
             config_path: None,
+            registries: Vec::new(),
+            channels: HashMap::new(),
+            max_output_chars: default_mcp_max_output_chars(),
+            trace_dir: None,
+            tool_timeout_secs: default_mcp_tool_timeout_secs(),
+            tool_timeouts: HashMap::new(),
         }
     }
 }
@@ -440,10 +481,36 @@ pub struct VpnConfig {
     /// Can be overridden by `ZEROCLAW_VPN_HEALTH_INTERVAL_SECS` env var.
     #[serde(default = "default_vpn_health_interval")]
     pub health_check_interval_secs: u64,
+    /// Maximum number of nodes probed concurrently per health check round. Default: 8.
+    /// Can be overridden by `ZEROCLAW_VPN_HEALTH_CONCURRENCY` env var.
+    #[serde(default = "default_vpn_health_concurrency")]
+    pub health_check_concurrency: usize,
+    /// Per-node probe timeout in seconds. Default: 5.
+    /// Can be overridden by `ZEROCLAW_VPN_HEALTH_TIMEOUT_SECS` env var.
+    #[serde(default = "default_vpn_health_timeout")]
+    pub health_check_timeout_secs: u64,
+    /// How nodes are probed during health checks. Default: `url_test`.
+    /// Can be overridden by `ZEROCLAW_VPN_HEALTH_STRATEGY` env var.
+    #[serde(default)]
+    pub health_check_strategy: VpnHealthStrategy,
+    /// URL fetched through the proxy by the `url_test` strategy.
+    /// Can be overridden by `ZEROCLAW_VPN_HEALTH_URL` env var.
+    #[serde(default = "default_vpn_health_url")]
+    pub health_check_url: String,
+    /// Subscription re-fetch interval in seconds. Default: 3600. `0` disables.
+    /// Can be overridden by `ZEROCLAW_VPN_REFRESH_INTERVAL_SECS` env var.
+    #[serde(default = "default_vpn_refresh_interval")]
+    pub subscription_refresh_interval_secs: u64,
     /// Extra bypass domains (comma-separated in env, array in TOML).
     /// Can be overridden by `ZEROCLAW_VPN_BYPASS_EXTRA` env var.
     #[serde(default)]
     pub bypass_extra: Vec<String>,
+    /// Only select nodes in these regions (ISO codes such as `US`, `JP`),
+    /// detected from node names. Empty = all regions. Falls back to all
+    /// nodes when none match.
+    /// Can be overridden by `ZEROCLAW_VPN_REGIONS` env var (comma-separated).
+    #[serde(default)]
+    pub regions: Vec<String>,
 }
 
 fn default_vpn_listen_port() -> u16 {
@@ -454,6 +521,44 @@ fn default_vpn_health_interval() -> u64 {
     30
 }
 
+fn default_vpn_health_concurrency() -> usize {
+    8
+}
+
+fn default_vpn_health_timeout() -> u64 {
+    5
+}
+
+fn default_vpn_health_url() -> String {
+    "http://connectivitycheck.gstatic.com/generate_204".into()
+}
+
+fn default_vpn_refresh_interval() -> u64 {
+    3600
+}
+
+/// VPN node health-check strategy.
+///
+/// - `url_test` (default) — HTTP request through the proxy to `health_check_url`,
+///   like Clash's delay test. Accurate end-to-end latency.
+/// - `tcp_connect`        — TCP handshake to the node's server address. Fast,
+///   but only proves the port is reachable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VpnHealthStrategy {
+    #[default]
+    UrlTest,
+    TcpConnect,
+}
+
+fn parse_vpn_health_strategy(raw: &str) -> Option<VpnHealthStrategy> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "url_test" => Some(VpnHealthStrategy::UrlTest),
+        "tcp_connect" => Some(VpnHealthStrategy::TcpConnect),
+        _ => None,
+    }
+}
+
 impl Default for VpnConfig {
     fn default() -> Self {
         Self {
@@ -461,7 +566,13 @@ impl Default for VpnConfig {
             subscription_url: None,
             listen_port: 7890,
             health_check_interval_secs: 30,
+            health_check_concurrency: 8,
+            health_check_timeout_secs: 5,
+            health_check_strategy: VpnHealthStrategy::default(),
+            health_check_url: default_vpn_health_url(),
+            subscription_refresh_interval_secs: 3600,
             bypass_extra: Vec::new(),
+            regions: Vec::new(),
         }
     }
 }
@@ -494,6 +605,47 @@ pub enum RemoteSyncMode {
     Event,
 }
 
+/// What docs sync does when a file was edited both locally and in Feishu
+/// since the last sync.
+///
+/// - `remote_wins` (default) — overwrite the local file with the remote version.
+/// - `local_wins`            — keep the local file and push it over the remote edits.
+/// - `keep_both`             — keep the local file and save the remote version
+///   next to it as `<file>.remote` for manual merging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    #[default]
+    RemoteWins,
+    LocalWins,
+    KeepBoth,
+}
+
+/// Permission granted when docs sync shares a document with a user.
+///
+/// - `view`        — read-only access.
+/// - `edit` (default) — read and edit.
+/// - `full_access` — edit plus manage sharing and settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SharePermission {
+    View,
+    #[default]
+    Edit,
+    FullAccess,
+}
+
+impl SharePermission {
+    /// Feishu `perm` value for the permission member API.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::View => "view",
+            Self::Edit => "edit",
+            Self::FullAccess => "full_access",
+        }
+    }
+}
+
 /// Feishu Docs bidirectional sync configuration (`[docs_sync]` section).
 ///
 /// Enables syncing local config files to/from a Feishu document.
@@ -518,6 +670,14 @@ pub struct DocsSyncConfig {
     /// How to receive remote changes: "polling" (default) or "event" (WebSocket subscription).
     #[serde(default)]
     pub remote_mode: RemoteSyncMode,
+    /// How to resolve files edited both locally and remotely: "remote_wins" (default),
+    /// "local_wins" or "keep_both".
+    #[serde(default)]
+    pub conflict_policy: ConflictPolicy,
+    /// Permission granted to users documents are shared with: "view", "edit"
+    /// (default) or "full_access".
+    #[serde(default)]
+    pub share_permission: SharePermission,
     /// Feishu App ID for event subscription. Falls back to `[channels_config.feishu].app_id`.
     #[serde(default)]
     pub app_id: Option<String>,
@@ -538,6 +698,8 @@ impl Default for DocsSyncConfig {
             sync_files: default_sync_files(),
             sync_interval_secs: default_sync_interval_secs(),
             remote_mode: RemoteSyncMode::default(),
+            conflict_policy: ConflictPolicy::default(),
+            share_permission: SharePermission::default(),
             app_id: None,
             app_secret: None,
             encrypt_key: None,
@@ -599,16 +761,30 @@ pub enum SkillsPromptInjectionMode {
     Full,
     /// Inline only compact skill metadata (name/description/location) and load details on demand.
     Compact,
+    /// Inline full skills until `prompt_budget_chars` is reached, then fall
+    /// back to compact entries for the rest.
+    Budgeted,
 }
 
 fn parse_skills_prompt_injection_mode(raw: &str) -> Option<SkillsPromptInjectionMode> {
     match raw.trim().to_ascii_lowercase().as_str() {
         "full" => Some(SkillsPromptInjectionMode::Full),
         "compact" => Some(SkillsPromptInjectionMode::Compact),
+        "budgeted" => Some(SkillsPromptInjectionMode::Budgeted),
         _ => None,
     }
 }
 
+/// Where the skills block is placed in the system prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillsPromptPosition {
+    /// Before everything else in the system prompt.
+    Prepend,
+    /// After everything else in the system prompt.
+    Append,
+}
+
 /// Skills loading configuration (`[skills]` section).
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct SkillsConfig {
@@ -620,14 +796,40 @@ pub struct SkillsConfig {
     /// If unset, defaults to `$HOME/open-skills` when enabled.
     #[serde(default)]
     pub open_skills_dir: Option<String>,
+    /// Directory that markdown links in open-skills may point into without
+    /// being flagged as escaping, e.g. the monorepo root when
+    /// `open_skills_dir` is nested inside one. Must contain `open_skills_dir`.
+    /// Defaults to the open-skills `skills/` directory (or the repository root
+    /// for the flat layout).
+    #[serde(default)]
+    pub open_skills_audit_boundary: Option<String>,
     /// Controls how skills are injected into the system prompt.
     /// `full` preserves legacy behavior. `compact` keeps context small and loads skills on demand.
+    /// `budgeted` inlines full skills up to `prompt_budget_chars`, then compacts the rest.
     #[serde(default)]
     pub prompt_injection_mode: SkillsPromptInjectionMode,
+    /// Character budget for the skills block in `budgeted` mode.
+    /// Unset uses the built-in default (8000).
+    #[serde(default)]
+    pub prompt_budget_chars: Option<usize>,
+    /// Move the skills block to the start (`prepend`) or end (`append`) of
+    /// the system prompt. Unset keeps it after the safety section.
+    #[serde(default)]
+    pub prompt_position: Option<SkillsPromptPosition>,
+    /// Preferred language (e.g. `en`, `zh-CN`) for open-skill descriptions.
+    /// When a SKILL.md has per-language sections, the description comes from
+    /// the matching section; otherwise the first paragraph line is used.
+    #[serde(default)]
+    pub preferred_language: Option<String>,
     /// Skip the built-in security audit when installing skills.
     /// Default: `false` (audit always runs). Set to `true` only on fully trusted devices.
     #[serde(default)]
     pub skip_security_audit: bool,
+    /// Register each skill's `[[tools]]` entries as callable agent tools
+    /// (`skill_<skill>_<tool>`). Only skills that pass the security audit are
+    /// exposed. Default: `false`.
+    #[serde(default)]
+    pub register_tools: bool,
 }
 
 /// Multimodal (image + video) handling configuration (`[multimodal]` section).
@@ -642,12 +844,21 @@ pub struct MultimodalConfig {
     /// Allow fetching remote image URLs (http/https). Disabled by default.
     #[serde(default)]
     pub allow_remote_fetch: bool,
+    /// Hosts remote image fetches may target when `allow_remote_fetch` is on.
+    /// Subdomains match their parent; `"*"` allows any host. Empty allows any
+    /// host too, with a warning at startup.
+    #[serde(default)]
+    pub remote_fetch_allowed_hosts: Vec<String>,
     /// Maximum number of video attachments accepted per request.
     #[serde(default = "default_multimodal_max_videos")]
     pub max_videos: usize,
     /// Maximum video payload size in MiB.
     #[serde(default = "default_multimodal_max_video_size_mb")]
     pub max_video_size_mb: usize,
+    /// Maximum combined size in bytes of all normalized (base64 data URI)
+    /// images in one request. Unset means only the per-image limit applies.
+    #[serde(default)]
+    pub max_total_bytes: Option<usize>,
 }
 
 fn default_multimodal_max_images() -> usize {
@@ -680,6 +891,30 @@ impl MultimodalConfig {
         let max_video_size_mb = self.max_video_size_mb.clamp(1, 100);
         (max_videos, max_video_size_mb)
     }
+
+    /// Whether `remote_fetch_allowed_hosts` lists at least one host.
+    pub fn has_remote_host_allowlist(&self) -> bool {
+        self.remote_fetch_allowed_hosts
+            .iter()
+            .any(|h| !h.trim().is_empty())
+    }
+
+    /// Whether `host` matches `remote_fetch_allowed_hosts` (any host when the
+    /// list is empty).
+    pub fn is_remote_host_allowed(&self, host: &str) -> bool {
+        if !self.has_remote_host_allowlist() {
+            return true;
+        }
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        self.remote_fetch_allowed_hosts.iter().any(|allowed| {
+            let allowed = allowed.trim().trim_end_matches('.').to_ascii_lowercase();
+            allowed == "*"
+                || host == allowed
+                || host
+                    .strip_suffix(&allowed)
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        })
+    }
 }
 
 impl Default for MultimodalConfig {
@@ -689,7 +924,9 @@ impl Default for MultimodalConfig {
             max_image_size_mb: default_multimodal_max_image_size_mb(),
             max_videos: default_multimodal_max_videos(),
             max_video_size_mb: default_multimodal_max_video_size_mb(),
+            max_total_bytes: None,
             allow_remote_fetch: false,
+            remote_fetch_allowed_hosts: Vec::new(),
         }
     }
 }
@@ -1719,13 +1956,50 @@ fn parse_proxy_scope(raw: &str) -> Option<ProxyScope> {
     }
 }
 
-fn parse_proxy_enabled(raw: &str) -> Option<bool> {
+fn parse_env_flag(raw: &str) -> Option<bool> {
     match raw.trim().to_ascii_lowercase().as_str() {
         "1" | "true" | "yes" | "on" => Some(true),
         "0" | "false" | "no" | "off" => Some(false),
         _ => None,
     }
 }
+
+/// Values accepted by boolean `ZEROCLAW_*` overrides.
+const ENV_FLAG_VALUES: &str = "1|0|true|false|yes|no|on|off";
+
+/// Read the override `name`, parsed with `parse`.
+///
+/// Unset or blank variables yield `None`. Unparsable values are logged and
+/// also yield `None`, so the TOML (or default) value stays in effect.
+fn env_override<T>(name: &str, valid: &str, parse: impl FnOnce(&str) -> Option<T>) -> Option<T> {
+    let raw = std::env::var(name).ok()?;
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    let parsed = parse(raw);
+    if parsed.is_none() {
+        tracing::warn!("Ignoring invalid {name} (valid: {valid})");
+    }
+    parsed
+}
+
+fn env_flag_override(name: &str) -> Option<bool> {
+    env_override(name, ENV_FLAG_VALUES, parse_env_flag)
+}
+
+fn env_number_override<T: std::str::FromStr>(name: &str) -> Option<T> {
+    env_override(name, "a non-negative integer", |raw| raw.parse().ok())
+}
+
+/// Split a comma-separated override into trimmed, non-empty entries.
+fn split_env_list(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
 // ── Memory ───────────────────────────────────────────────────
 
 /// Persistent storage configuration (`[storage]` section).
@@ -2041,6 +2315,18 @@ pub struct HooksConfig {
     /// Default timeout in seconds for hook execution.
     #[serde(default = "default_hook_timeout_secs")]
     pub default_timeout_secs: u64,
+
+    /// POST void hook events as JSON to an external URL (`[hooks.webhook]`).
+    #[serde(default)]
+    pub webhook: Option<WebhookHookConfig>,
+
+    /// Record every hook event to a rotating JSONL file (`[hooks.event_log]`).
+    #[serde(default)]
+    pub event_log: Option<EventLogHookConfig>,
+
+    /// Throttle LLM calls per sender (`[hooks.rate_limit]`).
+    #[serde(default)]
+    pub rate_limit: Option<RateLimitHookConfig>,
 }
 
 impl Default for HooksConfig {
@@ -2052,6 +2338,9 @@ impl Default for HooksConfig {
             skip_security_audit: false,
             max_hooks: default_max_hooks(),
             default_timeout_secs: default_hook_timeout_secs(),
+            webhook: None,
+            event_log: None,
+            rate_limit: None,
         }
     }
 }
@@ -2062,6 +2351,71 @@ pub struct BuiltinHooksConfig {
     pub command_logger: bool,
 }
 
+fn default_webhook_hook_timeout_secs() -> u64 {
+    5
+}
+
+/// Built-in webhook hook: POSTs each void hook event as JSON to `url`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WebhookHookConfig {
+    /// Endpoint that receives the JSON event payloads.
+    pub url: String,
+    /// Extra HTTP headers sent with every request (e.g. `Authorization`).
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Per-request timeout in seconds. Default: `5`.
+    #[serde(default = "default_webhook_hook_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Optional shared secret. When set, each request carries an
+    /// `X-ZeroClaw-Signature: t=<unix>,v1=<hex>` header, where `v1` is the
+    /// HMAC-SHA256 of `"<unix>.<body>"` keyed by this secret.
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+fn default_event_log_max_bytes() -> u64 {
+    10 * 1024 * 1024
+}
+
+fn default_event_log_max_files() -> usize {
+    3
+}
+
+/// Built-in event log hook: appends every hook event as one JSON line.
+///
+/// Modifying events are logged twice, with the value before and after the
+/// hook chain ran, so the log can be used to reconstruct what the agent did.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EventLogHookConfig {
+    /// Log file path. Default: `{workspace}/state/hook-events.jsonl`.
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    /// Rotate once the file would grow past this size. Default: 10 MiB.
+    #[serde(default = "default_event_log_max_bytes")]
+    pub max_bytes: u64,
+    /// Number of rotated files (`.1`, `.2`, …) to keep. Default: `3`.
+    #[serde(default = "default_event_log_max_files")]
+    pub max_files: usize,
+}
+
+fn default_rate_limit_window_secs() -> u64 {
+    60
+}
+
+/// Built-in rate-limit hook: rejects a message from a sender that already
+/// sent `max_calls` messages within the last `window_secs`.
+///
+/// Senders are keyed per channel; when the sender is unknown the whole
+/// channel shares one budget.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RateLimitHookConfig {
+    /// Messages allowed per sender within the window. Must be greater than 0.
+    pub max_calls: u32,
+    /// Sliding window length in seconds. Default: `60`.
+    #[serde(default = "default_rate_limit_window_secs")]
+    pub window_secs: u64,
+}
+
 // ── Autonomy / Security ──────────────────────────────────────────
 
 /// Autonomy and security policy configuration (`[autonomy]` section).
@@ -2843,6 +3197,10 @@ fn default_draft_update_interval_ms() -> u64 {
     1000
 }
 
+fn default_draft_update_min_interval_ms() -> u64 {
+    500
+}
+
 /// Telegram bot channel configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TelegramConfig {
@@ -3263,6 +3621,41 @@ pub enum LarkReceiveMode {
     Webhook,
 }
 
+/// Feishu/Lark "buzz" escalation applied to urgent outgoing messages.
+///
+/// - `off` (default) — urgent messages only bypass draft throttling.
+/// - `app`           — also send an in-app urgent notification.
+/// - `sms`           — also send an SMS urgent notification (billed by Feishu).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LarkUrgentEscalation {
+    #[default]
+    Off,
+    App,
+    Sms,
+}
+
+/// What to do with inbound Lark/Feishu messages longer than `max_inbound_chars`.
+///
+/// - `truncate` (default) — keep the first `max_inbound_chars` characters and
+///   append a truncation marker.
+/// - `reject`             — drop the message and reply asking for a shorter one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LarkInboundOverflow {
+    #[default]
+    Truncate,
+    Reject,
+}
+
+fn parse_lark_receive_mode(raw: &str) -> Option<LarkReceiveMode> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "websocket" => Some(LarkReceiveMode::Websocket),
+        "webhook" => Some(LarkReceiveMode::Webhook),
+        _ => None,
+    }
+}
+
 /// Lark/Feishu configuration for messaging integration.
 /// Lark is the international version; Feishu is the Chinese version.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -3298,6 +3691,41 @@ pub struct LarkConfig {
     /// Minimum interval (ms) between card updates to avoid rate limits.
     #[serde(default = "default_draft_update_interval_ms")]
     pub draft_update_interval_ms: u64,
+    /// Floor applied to `draft_update_interval_ms`. Lower it for faster card
+    /// updates on fast networks; CardKit rate limits still apply.
+    #[serde(default = "default_draft_update_min_interval_ms")]
+    pub draft_update_min_interval_ms: u64,
+    /// Coalesce text deltas arriving within this window (ms) into a single
+    /// card update. `0` disables debouncing.
+    #[serde(default)]
+    pub draft_update_debounce_ms: u64,
+    /// Urgent notification sent to `allowed_users` for urgent messages:
+    /// "off" (default), "app" or "sms".
+    #[serde(default)]
+    pub urgent_escalation: LarkUrgentEscalation,
+    /// Override for the Open API base URL (e.g. a self-hosted gateway).
+    /// Defaults to the platform endpoint when unset.
+    #[serde(default)]
+    pub api_base_url: Option<String>,
+    /// Override for the WebSocket endpoint base URL used in websocket mode.
+    /// Defaults to the platform endpoint when unset.
+    #[serde(default)]
+    pub ws_base_url: Option<String>,
+    /// Emoji type added as an acknowledgement reaction to messages the bot
+    /// will answer. Defaults to "OK"; set to "" to disable.
+    #[serde(default)]
+    pub ack_reaction_emoji: Option<String>,
+    /// Maximum inbound message length in characters (unset = unlimited).
+    #[serde(default)]
+    pub max_inbound_chars: Option<usize>,
+    /// Handling of messages over `max_inbound_chars`: "truncate" (default)
+    /// or "reject".
+    #[serde(default)]
+    pub inbound_overflow: LarkInboundOverflow,
+    /// Send text and `[IMAGE:…]`-style attachments in the order they appear in
+    /// the reply instead of all attachments before the text.
+    #[serde(default)]
+    pub interleave_attachments: bool,
 }
 
 impl ChannelConfig for LarkConfig {
@@ -3338,6 +3766,41 @@ pub struct FeishuConfig {
     /// Minimum interval (ms) between card updates to avoid rate limits.
     #[serde(default = "default_draft_update_interval_ms")]
     pub draft_update_interval_ms: u64,
+    /// Floor applied to `draft_update_interval_ms`. Lower it for faster card
+    /// updates on fast networks; CardKit rate limits still apply.
+    #[serde(default = "default_draft_update_min_interval_ms")]
+    pub draft_update_min_interval_ms: u64,
+    /// Coalesce text deltas arriving within this window (ms) into a single
+    /// card update. `0` disables debouncing.
+    #[serde(default)]
+    pub draft_update_debounce_ms: u64,
+    /// Urgent notification sent to `allowed_users` for urgent messages:
+    /// "off" (default), "app" or "sms".
+    #[serde(default)]
+    pub urgent_escalation: LarkUrgentEscalation,
+    /// Override for the Open API base URL (e.g. a self-hosted gateway).
+    /// Defaults to the platform endpoint when unset.
+    #[serde(default)]
+    pub api_base_url: Option<String>,
+    /// Override for the WebSocket endpoint base URL used in websocket mode.
+    /// Defaults to the platform endpoint when unset.
+    #[serde(default)]
+    pub ws_base_url: Option<String>,
+    /// Emoji type added as an acknowledgement reaction to messages the bot
+    /// will answer. Defaults to "OK"; set to "" to disable.
+    #[serde(default)]
+    pub ack_reaction_emoji: Option<String>,
+    /// Maximum inbound message length in characters (unset = unlimited).
+    #[serde(default)]
+    pub max_inbound_chars: Option<usize>,
+    /// Handling of messages over `max_inbound_chars`: "truncate" (default)
+    /// or "reject".
+    #[serde(default)]
+    pub inbound_overflow: LarkInboundOverflow,
+    /// Send text and `[IMAGE:…]`-style attachments in the order they appear in
+    /// the reply instead of all attachments before the text.
+    #[serde(default)]
+    pub interleave_attachments: bool,
 }
 
 impl ChannelConfig for FeishuConfig {
@@ -4309,9 +4772,137 @@ impl Config {
         // Proxy (delegate to existing validation)
         self.proxy.validate()?;
 
+        // Multimodal remote fetch
+        if self.multimodal.allow_remote_fetch && !self.multimodal.has_remote_host_allowlist() {
+            tracing::warn!(
+                "multimodal.allow_remote_fetch is enabled without multimodal.remote_fetch_allowed_hosts; images may be fetched from any host"
+            );
+        }
+
+        let problems = self.validation_problems();
+        if !problems.is_empty() {
+            anyhow::bail!(
+                "invalid configuration ({} problem(s)):\n  - {}",
+                problems.len(),
+                problems.join("\n  - ")
+            );
+        }
+
         Ok(())
     }
 
+    /// Cross-section consistency checks.
+    ///
+    /// Unlike the single-field checks in [`Config::validate`], these collect
+    /// every problem so one edit-and-retry cycle can fix them all.
+    pub fn validation_problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        // Lark / Feishu webhook mode
+        let lark_channels = [
+            (
+                "channels_config.lark",
+                self.channels_config
+                    .lark
+                    .as_ref()
+                    .map(|c| (&c.receive_mode, c.port)),
+            ),
+            (
+                "channels_config.feishu",
+                self.channels_config
+                    .feishu
+                    .as_ref()
+                    .map(|c| (&c.receive_mode, c.port)),
+            ),
+        ];
+        for (section, settings) in lark_channels {
+            if let Some((LarkReceiveMode::Webhook, None)) = settings {
+                problems.push(format!(
+                    "{section}: receive_mode = \"webhook\" requires `port` (or switch to \"websocket\")"
+                ));
+            }
+        }
+
+        // MCP
+        if self.mcp.enabled {
+            if self.mcp.tool_cap == 0 {
+                problems
+                    .push("mcp.tool_cap must be greater than 0 when MCP is enabled".to_string());
+            }
+            if self
+                .mcp
+                .config_path
+                .as_deref()
+                .is_some_and(|p| p.trim().is_empty())
+            {
+                problems.push(
+                    "mcp.config_path must not be empty; remove it to use .mcp.json".to_string(),
+                );
+            }
+            if self.mcp.tool_timeout_secs == 0 {
+                problems.push("mcp.tool_timeout_secs must be greater than 0".to_string());
+            }
+            let mut zero_timeouts: Vec<_> = self
+                .mcp
+                .tool_timeouts
+                .iter()
+                .filter(|(_, secs)| **secs == 0)
+                .map(|(tool, _)| tool.as_str())
+                .collect();
+            zero_timeouts.sort_unstable();
+            for tool in zero_timeouts {
+                problems.push(format!(
+                    "mcp.tool_timeouts.\"{tool}\" must be greater than 0"
+                ));
+            }
+        }
+        let mut mcp_channels: Vec<_> = self.mcp.channels.iter().collect();
+        mcp_channels.sort_by_key(|(channel, _)| channel.as_str());
+        for (channel, servers) in mcp_channels {
+            if servers.iter().any(|s| s.trim().is_empty()) {
+                problems.push(format!(
+                    "mcp.channels.{channel} contains an empty server name"
+                ));
+            }
+        }
+
+        // Hooks
+        if let Some(rate_limit) = &self.hooks.rate_limit {
+            if rate_limit.max_calls == 0 {
+                problems.push(
+                    "hooks.rate_limit.max_calls must be greater than 0; remove [hooks.rate_limit] to disable rate limiting".to_string(),
+                );
+            }
+            if rate_limit.window_secs == 0 {
+                problems.push("hooks.rate_limit.window_secs must be greater than 0".to_string());
+            }
+        }
+
+        // VPN
+        if self.vpn.enabled
+            && self
+                .vpn
+                .subscription_url
+                .as_deref()
+                .is_none_or(|url| url.trim().is_empty())
+        {
+            problems.push(
+                "vpn.enabled requires vpn.subscription_url (or ZEROCLAW_VPN_CLASH_PROXY_URL)"
+                    .to_string(),
+            );
+        }
+        if self.vpn.enabled {
+            if self.vpn.health_check_timeout_secs == 0 {
+                problems.push("vpn.health_check_timeout_secs must be greater than 0".to_string());
+            }
+            if self.vpn.health_check_concurrency == 0 {
+                problems.push("vpn.health_check_concurrency must be greater than 0".to_string());
+            }
+        }
+
+        problems
+    }
+
     /// Apply environment variable overrides to config
     pub fn apply_env_overrides(&mut self) {
         // API Key: ZEROCLAW_API_KEY or API_KEY (generic)
@@ -4374,16 +4965,8 @@ impl Config {
         }
 
         // Open-skills opt-in flag: ZEROCLAW_OPEN_SKILLS_ENABLED
-        if let Ok(flag) = std::env::var("ZEROCLAW_OPEN_SKILLS_ENABLED") {
-            if !flag.trim().is_empty() {
-                match flag.trim().to_ascii_lowercase().as_str() {
-                    "1" | "true" | "yes" | "on" => self.skills.open_skills_enabled = true,
-                    "0" | "false" | "no" | "off" => self.skills.open_skills_enabled = false,
-                    _ => tracing::warn!(
-                        "Ignoring invalid ZEROCLAW_OPEN_SKILLS_ENABLED (valid: 1|0|true|false|yes|no|on|off)"
-                    ),
-                }
-            }
+        if let Some(enabled) = env_flag_override("ZEROCLAW_OPEN_SKILLS_ENABLED") {
+            self.skills.open_skills_enabled = enabled;
         }
 
         // Open-skills directory override: ZEROCLAW_OPEN_SKILLS_DIR
@@ -4395,33 +4978,128 @@ impl Config {
         }
 
         // Skills prompt mode override: ZEROCLAW_SKILLS_PROMPT_MODE
-        if let Ok(mode) = std::env::var("ZEROCLAW_SKILLS_PROMPT_MODE") {
-            if !mode.trim().is_empty() {
-                if let Some(parsed) = parse_skills_prompt_injection_mode(&mode) {
-                    self.skills.prompt_injection_mode = parsed;
-                } else {
-                    tracing::warn!(
-                        "Ignoring invalid ZEROCLAW_SKILLS_PROMPT_MODE (valid: full|compact)"
-                    );
-                }
-            }
+        if let Some(mode) = env_override(
+            "ZEROCLAW_SKILLS_PROMPT_MODE",
+            "full|compact|budgeted",
+            parse_skills_prompt_injection_mode,
+        ) {
+            self.skills.prompt_injection_mode = mode;
         }
 
         // Skip security audit flag: ZEROCLAW_SKIP_SECURITY_AUDIT
-        if let Ok(flag) = std::env::var("ZEROCLAW_SKIP_SECURITY_AUDIT") {
-            if !flag.trim().is_empty() {
-                match flag.trim().to_ascii_lowercase().as_str() {
-                    "1" | "true" | "yes" | "on" => {
-                        self.skills.skip_security_audit = true;
-                        tracing::warn!("Security audit for skills is DISABLED \u{2014} device is fully trusted by ZeroClaw");
-                    }
-                    "0" | "false" | "no" | "off" => self.skills.skip_security_audit = false,
-                    _ => tracing::warn!(
-                        "Ignoring invalid ZEROCLAW_SKIP_SECURITY_AUDIT (valid: 1|0|true|false|yes|no|on|off)"
-                    ),
+        if let Some(skip) = env_flag_override("ZEROCLAW_SKIP_SECURITY_AUDIT") {
+            self.skills.skip_security_audit = skip;
+            if skip {
+                tracing::warn!("Security audit for skills is DISABLED \u{2014} device is fully trusted by ZeroClaw");
+            }
+        }
+
+        // Multimodal limits: ZEROCLAW_MULTIMODAL_*
+        if let Some(max) = env_number_override("ZEROCLAW_MULTIMODAL_MAX_IMAGES") {
+            self.multimodal.max_images = max;
+        }
+        if let Some(max) = env_number_override("ZEROCLAW_MULTIMODAL_MAX_IMAGE_SIZE_MB") {
+            self.multimodal.max_image_size_mb = max;
+        }
+        if let Some(max) = env_number_override("ZEROCLAW_MULTIMODAL_MAX_VIDEOS") {
+            self.multimodal.max_videos = max;
+        }
+        if let Some(max) = env_number_override("ZEROCLAW_MULTIMODAL_MAX_VIDEO_SIZE_MB") {
+            self.multimodal.max_video_size_mb = max;
+        }
+        if let Some(max) = env_number_override("ZEROCLAW_MULTIMODAL_MAX_TOTAL_BYTES") {
+            self.multimodal.max_total_bytes = Some(max);
+        }
+        if let Some(allow) = env_flag_override("ZEROCLAW_MULTIMODAL_ALLOW_REMOTE_FETCH") {
+            self.multimodal.allow_remote_fetch = allow;
+        }
+        if let Some(hosts) = env_override(
+            "ZEROCLAW_MULTIMODAL_REMOTE_FETCH_ALLOWED_HOSTS",
+            "comma-separated host names",
+            |raw| Some(split_env_list(raw)),
+        ) {
+            self.multimodal.remote_fetch_allowed_hosts = hosts;
+        }
+
+        // Lark / Feishu receive mode: ZEROCLAW_LARK_RECEIVE_MODE, ZEROCLAW_FEISHU_RECEIVE_MODE
+        if let Some(lark) = self.channels_config.lark.as_mut() {
+            if let Some(mode) = env_override(
+                "ZEROCLAW_LARK_RECEIVE_MODE",
+                "websocket|webhook",
+                parse_lark_receive_mode,
+            ) {
+                lark.receive_mode = mode;
+            }
+        }
+        if let Some(feishu) = self.channels_config.feishu.as_mut() {
+            if let Some(mode) = env_override(
+                "ZEROCLAW_FEISHU_RECEIVE_MODE",
+                "websocket|webhook",
+                parse_lark_receive_mode,
+            ) {
+                feishu.receive_mode = mode;
+            }
+        }
+
+        // VPN: ZEROCLAW_VPN_*
+        if let Some(enabled) = env_flag_override("ZEROCLAW_VPN_ENABLED") {
+            self.vpn.enabled = enabled;
+        }
+        if let Some(url) = env_override("ZEROCLAW_VPN_CLASH_PROXY_URL", "a URL", |raw| {
+            Some(raw.to_string())
+        }) {
+            self.vpn.subscription_url = Some(url);
+        }
+        if let Some(port) = env_number_override("ZEROCLAW_VPN_LISTEN_PORT") {
+            self.vpn.listen_port = port;
+        }
+        if let Some(secs) = env_number_override("ZEROCLAW_VPN_HEALTH_INTERVAL_SECS") {
+            self.vpn.health_check_interval_secs = secs;
+        }
+        if let Some(concurrency) = env_number_override("ZEROCLAW_VPN_HEALTH_CONCURRENCY") {
+            self.vpn.health_check_concurrency = concurrency;
+        }
+        if let Some(secs) = env_number_override("ZEROCLAW_VPN_HEALTH_TIMEOUT_SECS") {
+            self.vpn.health_check_timeout_secs = secs;
+        }
+        if let Some(strategy) = env_override(
+            "ZEROCLAW_VPN_HEALTH_STRATEGY",
+            "url_test|tcp_connect",
+            parse_vpn_health_strategy,
+        ) {
+            self.vpn.health_check_strategy = strategy;
+        }
+        if let Some(url) = env_override("ZEROCLAW_VPN_HEALTH_URL", "a URL", |raw| {
+            Some(raw.to_string())
+        }) {
+            self.vpn.health_check_url = url;
+        }
+        if let Some(secs) = env_number_override("ZEROCLAW_VPN_REFRESH_INTERVAL_SECS") {
+            self.vpn.subscription_refresh_interval_secs = secs;
+        }
+        if let Some(domains) = env_override(
+            "ZEROCLAW_VPN_BYPASS_EXTRA",
+            "comma-separated domains",
+            |raw| Some(split_env_list(raw)),
+        ) {
+            for domain in domains {
+                if !self
+                    .vpn
+                    .bypass_extra
+                    .iter()
+                    .any(|d| d.eq_ignore_ascii_case(&domain))
+                {
+                    self.vpn.bypass_extra.push(domain);
                 }
             }
         }
+        if let Some(regions) = env_override(
+            "ZEROCLAW_VPN_REGIONS",
+            "comma-separated region codes",
+            |raw| Some(split_env_list(raw)),
+        ) {
+            self.vpn.regions = regions;
+        }
 
         // Gateway port: ZEROCLAW_GATEWAY_PORT or PORT
         if let Ok(port_str) =
@@ -4539,11 +5217,19 @@ impl Config {
                 }
             }
         }
+
+        // MCP protocol trace directory: ZEROCLAW_MCP_TRACE_DIR
+        if let Some(trace_dir) = env_override("ZEROCLAW_MCP_TRACE_DIR", "a directory path", |raw| {
+            Some(raw.to_string())
+        }) {
+            self.mcp.trace_dir = Some(trace_dir);
+        }
+
         // Proxy enabled flag: ZEROCLAW_PROXY_ENABLED
         let explicit_proxy_enabled = std::env::var("ZEROCLAW_PROXY_ENABLED")
             .ok()
             .as_deref()
-            .and_then(parse_proxy_enabled);
+            .and_then(parse_env_flag);
         if let Some(enabled) = explicit_proxy_enabled {
             self.proxy.enabled = enabled;
         }
@@ -6218,6 +6904,109 @@ default_temperature = 0.7
         std::env::remove_var("ZEROCLAW_SKILLS_PROMPT_MODE");
     }
 
+    #[test]
+    async fn env_override_precedence_for_multimodal_limits() {
+        let _env_guard = env_override_lock().await;
+        std::env::remove_var("ZEROCLAW_MULTIMODAL_MAX_IMAGES");
+
+        // default
+        let mut config = Config::default();
+        config.apply_env_overrides();
+        assert_eq!(config.multimodal.max_images, 4);
+
+        // config beats default
+        config.multimodal.max_images = 8;
+        config.apply_env_overrides();
+        assert_eq!(config.multimodal.max_images, 8);
+
+        // invalid env falls back to config
+        std::env::set_var("ZEROCLAW_MULTIMODAL_MAX_IMAGES", "lots");
+        config.apply_env_overrides();
+        assert_eq!(config.multimodal.max_images, 8);
+
+        // env beats config
+        std::env::set_var("ZEROCLAW_MULTIMODAL_MAX_IMAGES", " 12 ");
+        std::env::set_var("ZEROCLAW_MULTIMODAL_ALLOW_REMOTE_FETCH", "on");
+        std::env::set_var(
+            "ZEROCLAW_MULTIMODAL_REMOTE_FETCH_ALLOWED_HOSTS",
+            "cdn.example.com, images.example.com",
+        );
+        config.apply_env_overrides();
+        assert_eq!(config.multimodal.max_images, 12);
+        assert!(config.multimodal.allow_remote_fetch);
+        assert_eq!(
+            config.multimodal.remote_fetch_allowed_hosts,
+            vec!["cdn.example.com", "images.example.com"]
+        );
+
+        std::env::remove_var("ZEROCLAW_MULTIMODAL_MAX_IMAGES");
+        std::env::remove_var("ZEROCLAW_MULTIMODAL_ALLOW_REMOTE_FETCH");
+        std::env::remove_var("ZEROCLAW_MULTIMODAL_REMOTE_FETCH_ALLOWED_HOSTS");
+    }
+
+    #[test]
+    async fn env_override_lark_receive_mode_and_vpn() {
+        let _env_guard = env_override_lock().await;
+        let mut config = Config::default();
+        config.channels_config.feishu = Some(FeishuConfig {
+            app_id: "cli_test".into(),
+            app_secret: "secret".into(),
+            encrypt_key: None,
+            verification_token: None,
+            allowed_users: vec![],
+            receive_mode: LarkReceiveMode::Websocket,
+            port: Some(8081),
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            draft_update_min_interval_ms: 500,
+            draft_update_debounce_ms: 0,
+            urgent_escalation: LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
+        });
+        config.vpn.bypass_extra = vec!["corp.example.com".into()];
+
+        std::env::set_var("ZEROCLAW_FEISHU_RECEIVE_MODE", "carrier-pigeon");
+        std::env::set_var("ZEROCLAW_LARK_RECEIVE_MODE", "webhook");
+        std::env::set_var("ZEROCLAW_VPN_ENABLED", "yes");
+        std::env::set_var(
+            "ZEROCLAW_VPN_BYPASS_EXTRA",
+            "CORP.example.com,git.example.com",
+        );
+        std::env::set_var("ZEROCLAW_VPN_REGIONS", "US, jp");
+        config.apply_env_overrides();
+
+        let feishu = config.channels_config.feishu.as_ref().unwrap();
+        assert_eq!(feishu.receive_mode, LarkReceiveMode::Websocket);
+        // No [channels_config.lark] section: the override has nothing to apply to.
+        assert!(config.channels_config.lark.is_none());
+        assert!(config.vpn.enabled);
+        assert_eq!(
+            config.vpn.bypass_extra,
+            vec!["corp.example.com", "git.example.com"]
+        );
+        assert_eq!(config.vpn.regions, vec!["US", "jp"]);
+
+        std::env::set_var("ZEROCLAW_FEISHU_RECEIVE_MODE", "Webhook");
+        config.apply_env_overrides();
+        let feishu = config.channels_config.feishu.as_ref().unwrap();
+        assert_eq!(feishu.receive_mode, LarkReceiveMode::Webhook);
+
+        for key in [
+            "ZEROCLAW_FEISHU_RECEIVE_MODE",
+            "ZEROCLAW_LARK_RECEIVE_MODE",
+            "ZEROCLAW_VPN_ENABLED",
+            "ZEROCLAW_VPN_BYPASS_EXTRA",
+            "ZEROCLAW_VPN_REGIONS",
+        ] {
+            std::env::remove_var(key);
+        }
+    }
+
     #[test]
     async fn env_override_provider_fallback() {
         let _env_guard = env_override_lock().await;
@@ -7072,6 +7861,15 @@ default_model = "legacy-model"
             port: None,
             stream_mode: StreamMode::default(),
             draft_update_interval_ms: default_draft_update_interval_ms(),
+            draft_update_min_interval_ms: default_draft_update_min_interval_ms(),
+            draft_update_debounce_ms: 0,
+            urgent_escalation: LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
         };
         let json = serde_json::to_string(&lc).unwrap();
         let parsed: LarkConfig = serde_json::from_str(&json).unwrap();
@@ -7096,6 +7894,15 @@ default_model = "legacy-model"
             port: Some(9898),
             stream_mode: StreamMode::default(),
             draft_update_interval_ms: default_draft_update_interval_ms(),
+            draft_update_min_interval_ms: default_draft_update_min_interval_ms(),
+            draft_update_debounce_ms: 0,
+            urgent_escalation: LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
         };
         let toml_str = toml::to_string(&lc).unwrap();
         let parsed: LarkConfig = toml::from_str(&toml_str).unwrap();
@@ -7143,6 +7950,15 @@ default_model = "legacy-model"
             port: None,
             stream_mode: StreamMode::default(),
             draft_update_interval_ms: default_draft_update_interval_ms(),
+            draft_update_min_interval_ms: default_draft_update_min_interval_ms(),
+            draft_update_debounce_ms: 0,
+            urgent_escalation: LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
         };
         let json = serde_json::to_string(&fc).unwrap();
         let parsed: FeishuConfig = serde_json::from_str(&json).unwrap();
@@ -7165,6 +7981,15 @@ default_model = "legacy-model"
             port: Some(9898),
             stream_mode: StreamMode::default(),
             draft_update_interval_ms: default_draft_update_interval_ms(),
+            draft_update_min_interval_ms: default_draft_update_min_interval_ms(),
+            draft_update_debounce_ms: 0,
+            urgent_escalation: LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
         };
         let toml_str = toml::to_string(&fc).unwrap();
         let parsed: FeishuConfig = toml::from_str(&toml_str).unwrap();
@@ -7350,6 +8175,119 @@ require_otp_to_resume = true
         assert!(err.to_string().contains("gated_domains"));
     }
 
+    #[test]
+    async fn validation_problems_empty_for_default_config() {
+        assert!(Config::default().validation_problems().is_empty());
+    }
+
+    #[test]
+    async fn validation_reports_every_cross_section_problem() {
+        let mut config = Config::default();
+        config.channels_config.feishu = Some(FeishuConfig {
+            app_id: "cli_test".into(),
+            app_secret: "secret".into(),
+            encrypt_key: None,
+            verification_token: None,
+            allowed_users: vec![],
+            receive_mode: LarkReceiveMode::Webhook,
+            port: None,
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            draft_update_min_interval_ms: 500,
+            draft_update_debounce_ms: 0,
+            urgent_escalation: LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
+        });
+        config.multimodal.allow_remote_fetch = true;
+        config.mcp.enabled = true;
+        config.mcp.tool_cap = 0;
+        config
+            .mcp
+            .channels
+            .insert("telegram".into(), vec!["github".into(), " ".into()]);
+        config.vpn.enabled = true;
+
+        let problems = config.validation_problems();
+        assert_eq!(problems.len(), 4, "{problems:#?}");
+        assert!(problems[0].starts_with("channels_config.feishu"));
+        assert!(problems[1].contains("mcp.tool_cap"));
+        assert!(problems[2].contains("mcp.channels.telegram"));
+        assert!(problems[3].contains("vpn.subscription_url"));
+
+        let err = config.validate().expect_err("expected validation to fail");
+        assert!(err.to_string().contains("4 problem(s)"));
+    }
+
+    #[test]
+    async fn validation_rejects_zero_mcp_tool_timeouts() {
+        let mut config = Config::default();
+        config.mcp.enabled = true;
+        config.mcp.tool_timeout_secs = 0;
+        config.mcp.tool_timeouts.insert("ci:run_build".into(), 0);
+        config.mcp.tool_timeouts.insert("lint".into(), 60);
+
+        let problems = config.validation_problems();
+        assert_eq!(problems.len(), 2, "{problems:#?}");
+        assert!(problems[0].contains("mcp.tool_timeout_secs"));
+        assert!(problems[1].contains("\"ci:run_build\""));
+    }
+
+    #[test]
+    async fn remote_fetch_without_allowlist_allows_any_host() {
+        let mut config = Config::default();
+        config.multimodal.allow_remote_fetch = true;
+
+        assert!(config.validation_problems().is_empty());
+        config.validate().unwrap();
+        assert!(config
+            .multimodal
+            .is_remote_host_allowed("images.example.org"));
+    }
+
+    #[test]
+    async fn validation_accepts_consistent_cross_section_config() {
+        let mut config = Config::default();
+        config.multimodal.allow_remote_fetch = true;
+        config.multimodal.remote_fetch_allowed_hosts = vec!["*".into()];
+        config.vpn.enabled = true;
+        config.vpn.subscription_url = Some("https://sub.example.com/clash".into());
+
+        assert!(config.validation_problems().is_empty());
+        config.validate().unwrap();
+    }
+
+    #[test]
+    async fn validation_rejects_zero_vpn_health_check_limits() {
+        let mut config = Config::default();
+        config.vpn.enabled = true;
+        config.vpn.subscription_url = Some("https://sub.example.com/clash".into());
+        config.vpn.health_check_timeout_secs = 0;
+        config.vpn.health_check_concurrency = 0;
+
+        let problems = config.validation_problems();
+        assert_eq!(problems.len(), 2, "{problems:#?}");
+        assert!(problems[0].contains("vpn.health_check_timeout_secs"));
+        assert!(problems[1].contains("vpn.health_check_concurrency"));
+    }
+
+    #[test]
+    async fn validation_rejects_zero_rate_limit_budget() {
+        let mut config = Config::default();
+        config.hooks.rate_limit = Some(RateLimitHookConfig {
+            max_calls: 0,
+            window_secs: 60,
+        });
+
+        let problems = config.validation_problems();
+        assert_eq!(problems.len(), 1, "{problems:#?}");
+        assert!(problems[0].contains("hooks.rate_limit.max_calls"));
+    }
+
     #[tokio::test]
     async fn security_validation_rejects_unknown_domain_category() {
         let mut config = Config::default();
@@ -8,6 +8,7 @@ use crate::cron::{
     due_jobs, next_run_for_schedule, record_last_run, record_run, remove_job, reschedule_after_run,
     update_job, CronJob, CronJobPatch, DeliveryConfig, JobType, Schedule, SessionTarget,
 };
+use crate::retry::{RetryDecision, RetryPolicy};
 use crate::security::SecurityPolicy;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -30,6 +31,13 @@ pub async fn run(config: Config) -> Result<()> {
         &config.workspace_dir,
     ));
 
+    // Deliveries run the same before_send hooks as channel replies.
+    let hooks = config.hooks.enabled.then(|| {
+        let mut runner = crate::hooks::HookRunner::new();
+        crate::hooks::builtin::register_builtins(&mut runner, &config);
+        Arc::new(runner)
+    });
+
     crate::health::mark_component_ok(SCHEDULER_COMPONENT);
 
     loop {
@@ -46,7 +54,14 @@ pub async fn run(config: Config) -> Result<()> {
             }
         };
 
-        process_due_jobs(&config, &security, jobs, SCHEDULER_COMPONENT).await;
+        process_due_jobs(
+            &config,
+            &security,
+            hooks.as_ref(),
+            jobs,
+            SCHEDULER_COMPONENT,
+        )
+        .await;
     }
 }
 
@@ -60,39 +75,45 @@ async fn execute_job_with_retry(
     security: &SecurityPolicy,
     job: &CronJob,
 ) -> (bool, String) {
-    let mut last_output = String::new();
-    let retries = config.reliability.scheduler_retries;
-    let mut backoff_ms = config.reliability.provider_backoff_ms.max(200);
-
-    for attempt in 0..=retries {
-        let (success, output) = match job.job_type {
-            JobType::Shell => run_job_command(config, security, job).await,
-            JobType::Agent => run_agent_job(config, security, job).await,
-        };
-        last_output = output;
-
-        if success {
-            return (true, last_output);
-        }
-
-        if last_output.starts_with("blocked by security policy:") {
-            // Deterministic policy violations are not retryable.
-            return (false, last_output);
-        }
+    let policy = RetryPolicy {
+        max_attempts: config.reliability.scheduler_retries.saturating_add(1),
+        base_delay: Duration::from_millis(config.reliability.provider_backoff_ms.max(200)),
+        max_delay: Duration::from_secs(30),
+        jitter: Duration::from_millis(250),
+    };
+    let outcome = policy
+        .retry(
+            |outcome: &Result<String, String>| match outcome {
+                // Deterministic policy violations are not retryable.
+                Err(output) if !output.starts_with("blocked by security policy:") => {
+                    RetryDecision::Retry
+                }
+                _ => RetryDecision::Stop,
+            },
+            || async {
+                let (success, output) = match job.job_type {
+                    JobType::Shell => run_job_command(config, security, job).await,
+                    JobType::Agent => run_agent_job(config, security, job).await,
+                };
+                if success {
+                    Ok(output)
+                } else {
+                    Err(output)
+                }
+            },
+        )
+        .await;
 
-        if attempt < retries {
-            let jitter_ms = u64::from(Utc::now().timestamp_subsec_millis() % 250);
-            time::sleep(Duration::from_millis(backoff_ms + jitter_ms)).await;
-            backoff_ms = (backoff_ms.saturating_mul(2)).min(30_000);
-        }
+    match outcome {
+        Ok(output) => (true, output),
+        Err(output) => (false, output),
     }
-
-    (false, last_output)
 }
 
 async fn process_due_jobs(
     config: &Config,
     security: &Arc<SecurityPolicy>,
+    hooks: Option<&Arc<crate::hooks::HookRunner>>,
     jobs: Vec<CronJob>,
     component: &str,
 ) {
@@ -100,18 +121,23 @@ async fn process_due_jobs(
     crate::health::mark_component_ok(component);
 
     let max_concurrent = config.scheduler.max_concurrent.max(1);
-    let mut in_flight =
-        stream::iter(
-            jobs.into_iter().map(|job| {
-                let config = config.clone();
-                let security = Arc::clone(security);
-                let component = component.to_owned();
-                async move {
-                    execute_and_persist_job(&config, security.as_ref(), &job, &component).await
-                }
-            }),
-        )
-        .buffer_unordered(max_concurrent);
+    let mut in_flight = stream::iter(jobs.into_iter().map(|job| {
+        let config = config.clone();
+        let security = Arc::clone(security);
+        let hooks = hooks.cloned();
+        let component = component.to_owned();
+        async move {
+            execute_and_persist_job(
+                &config,
+                security.as_ref(),
+                hooks.as_deref(),
+                &job,
+                &component,
+            )
+            .await
+        }
+    }))
+    .buffer_unordered(max_concurrent);
 
     while let Some((job_id, success, output)) = in_flight.next().await {
         if !success {
@@ -123,6 +149,7 @@ async fn process_due_jobs(
 async fn execute_and_persist_job(
     config: &Config,
     security: &SecurityPolicy,
+    hooks: Option<&crate::hooks::HookRunner>,
     job: &CronJob,
     component: &str,
 ) -> (String, bool, String) {
@@ -132,7 +159,16 @@ async fn execute_and_persist_job(
     let started_at = Utc::now();
     let (success, output) = execute_job_with_retry(config, security, job).await;
     let finished_at = Utc::now();
-    let success = persist_job_result(config, job, success, &output, started_at, finished_at).await;
+    let success = persist_job_result(
+        config,
+        job,
+        success,
+        &output,
+        started_at,
+        finished_at,
+        hooks,
+    )
+    .await;
 
     (job.id.clone(), success, output)
 }
@@ -202,10 +238,11 @@ async fn persist_job_result(
     output: &str,
     started_at: DateTime<Utc>,
     finished_at: DateTime<Utc>,
+    hooks: Option<&crate::hooks::HookRunner>,
 ) -> bool {
     let duration_ms = (finished_at - started_at).num_milliseconds();
 
-    if let Err(e) = deliver_if_configured(config, job, output, None).await {
+    if let Err(e) = deliver_if_configured(config, job, output, hooks).await {
         if job.delivery.best_effort {
             tracing::warn!("Cron delivery failed (best_effort): {e}");
         } else {
@@ -283,6 +320,29 @@ fn warn_if_high_frequency_agent_job(job: &CronJob) {
     }
 }
 
+/// Run `before_send` hooks on a cron delivery. Returns the (possibly
+/// rewritten) content, or `None` when a hook suppressed the delivery.
+async fn before_send(
+    hook_runner: Option<&crate::hooks::HookRunner>,
+    channel: &str,
+    target: &str,
+    output: &str,
+) -> Option<String> {
+    let Some(hooks) = hook_runner else {
+        return Some(output.to_string());
+    };
+    match hooks
+        .run_before_send(channel, target, output.to_string())
+        .await
+    {
+        crate::hooks::HookResult::Cancel(reason) => {
+            tracing::info!(%reason, "cron delivery to {channel} suppressed by before_send hook");
+            None
+        }
+        crate::hooks::HookResult::Continue(content) => Some(content),
+    }
+}
+
 async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str, hook_runner: Option<&crate::hooks::HookRunner>) -> Result<()> {
     let delivery: &DeliveryConfig = &job.delivery;
     if !delivery.mode.eq_ignore_ascii_case("announce") {
@@ -310,7 +370,11 @@ async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str, hoo
                 tg.allowed_users.clone(),
                 tg.mention_only,
             );
-            channel.send(&SendMessage::new(output, target)).await?;
+            if let Some(content) = before_send(hook_runner, "telegram", target, output).await {
+                channel
+                    .send(&SendMessage::new(content, target).urgent(delivery.urgent))
+                    .await?;
+            }
         }
         "discord" => {
             let dc = config
@@ -325,7 +389,11 @@ async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str, hoo
                 dc.listen_to_bots,
                 dc.mention_only,
             );
-            channel.send(&SendMessage::new(output, target)).await?;
+            if let Some(content) = before_send(hook_runner, "discord", target, output).await {
+                channel
+                    .send(&SendMessage::new(content, target).urgent(delivery.urgent))
+                    .await?;
+            }
         }
         "slack" => {
             let sl = config
@@ -338,7 +406,11 @@ async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str, hoo
                 sl.channel_id.clone(),
                 sl.allowed_users.clone(),
             );
-            channel.send(&SendMessage::new(output, target)).await?;
+            if let Some(content) = before_send(hook_runner, "slack", target, output).await {
+                channel
+                    .send(&SendMessage::new(content, target).urgent(delivery.urgent))
+                    .await?;
+            }
         }
         "mattermost" => {
             let mm = config
@@ -354,7 +426,11 @@ async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str, hoo
                 mm.thread_replies.unwrap_or(true),
                 mm.mention_only.unwrap_or(false),
             );
-            channel.send(&SendMessage::new(output, target)).await?;
+            if let Some(content) = before_send(hook_runner, "mattermost", target, output).await {
+                channel
+                    .send(&SendMessage::new(content, target).urgent(delivery.urgent))
+                    .await?;
+            }
         }
         #[cfg(feature = "channel-lark")]
         "lark" => {
@@ -381,7 +457,14 @@ async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str, hoo
                     }
                 }
             }
-            channel.send(&SendMessage::new(&delivery_output, target)).await?;
+            let Some(delivery_output) =
+                before_send(hook_runner, "lark", target, &delivery_output).await
+            else {
+                return Ok(());
+            };
+            channel
+                .send(&SendMessage::new(&delivery_output, target).urgent(delivery.urgent))
+                .await?;
             if let Some(hooks) = hook_runner {
                 hooks.fire_message_sent("lark", target, &delivery_output).await;
             }
@@ -411,7 +494,14 @@ async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str, hoo
                     }
                 }
             }
-            channel.send(&SendMessage::new(&delivery_output, target)).await?;
+            let Some(delivery_output) =
+                before_send(hook_runner, "feishu", target, &delivery_output).await
+            else {
+                return Ok(());
+            };
+            channel
+                .send(&SendMessage::new(&delivery_output, target).urgent(delivery.urgent))
+                .await?;
             if let Some(hooks) = hook_runner {
                 hooks.fire_message_sent("feishu", target, &delivery_output).await;
             }
@@ -814,7 +904,7 @@ mod tests {
         let component = unique_component("scheduler-idle");
 
         crate::health::mark_component_error(&component, "pre-existing error");
-        process_due_jobs(&config, &security, Vec::new(), &component).await;
+        process_due_jobs(&config, &security, None, Vec::new(), &component).await;
 
         let snapshot = crate::health::snapshot_json();
         let entry = &snapshot["components"][component.as_str()];
@@ -835,7 +925,7 @@ mod tests {
         let component = unique_component("scheduler-fail");
 
         crate::health::mark_component_ok(&component);
-        process_due_jobs(&config, &security, vec![job], &component).await;
+        process_due_jobs(&config, &security, None, vec![job], &component).await;
 
         let snapshot = crate::health::snapshot_json();
         let entry = &snapshot["components"][component.as_str()];
@@ -850,7 +940,7 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, true, "ok", started, finished).await;
+        let success = persist_job_result(&config, &job, true, "ok", started, finished, None).await;
         assert!(success);
 
         let runs = cron::list_runs(&config, &job.id, 10).unwrap();
@@ -878,7 +968,7 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, true, "ok", started, finished).await;
+        let success = persist_job_result(&config, &job, true, "ok", started, finished, None).await;
         assert!(success);
         let lookup = cron::get_job(&config, &job.id);
         assert!(lookup.is_err());
@@ -903,13 +993,53 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, false, "boom", started, finished).await;
+        let success = persist_job_result(&config, &job, false, "boom", started, finished, None).await;
         assert!(!success);
         let updated = cron::get_job(&config, &job.id).unwrap();
         assert!(!updated.enabled);
         assert_eq!(updated.last_status.as_deref(), Some("error"));
     }
 
+    struct SuppressHook;
+
+    #[async_trait::async_trait]
+    impl crate::hooks::HookHandler for SuppressHook {
+        fn name(&self) -> &str {
+            "suppress"
+        }
+        async fn before_send(
+            &self,
+            channel: &str,
+            _recipient: &str,
+            content: String,
+        ) -> crate::hooks::HookResult<String> {
+            if channel == "slack" {
+                crate::hooks::HookResult::Cancel("quiet hours".into())
+            } else {
+                crate::hooks::HookResult::Continue(format!("{content}!"))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn before_send_rewrites_or_suppresses_delivery() {
+        let mut runner = crate::hooks::HookRunner::new();
+        runner.register(Box::new(SuppressHook));
+
+        assert_eq!(
+            before_send(Some(&runner), "telegram", "chat", "done").await,
+            Some("done!".to_string())
+        );
+        assert_eq!(
+            before_send(Some(&runner), "slack", "C1", "done").await,
+            None
+        );
+        assert_eq!(
+            before_send(None, "slack", "C1", "done").await,
+            Some("done".to_string())
+        );
+    }
+
     #[tokio::test]
     async fn deliver_if_configured_handles_none_and_invalid_channel() {
         let tmp = TempDir::new().unwrap();
@@ -923,6 +1053,7 @@ mod tests {
             channel: Some("invalid".into()),
             to: Some("target".into()),
             best_effort: true,
+            urgent: false,
         };
         let err = deliver_if_configured(&config, &job, "x", None).await.unwrap_err();
         assert!(err.to_string().contains("unsupported delivery channel"));
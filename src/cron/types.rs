@@ -84,6 +84,10 @@ pub struct DeliveryConfig {
     pub to: Option<String>,
     #[serde(default = "default_true")]
     pub best_effort: bool,
+    /// Send as an urgent message, e.g. for alerts. Lark delivers it without
+    /// draft throttling and can escalate it; other channels ignore the flag.
+    #[serde(default)]
+    pub urgent: bool,
 }
 
 impl Default for DeliveryConfig {
@@ -93,6 +97,7 @@ impl Default for DeliveryConfig {
             channel: None,
             to: None,
             best_effort: true,
+            urgent: false,
         }
     }
 }
@@ -148,7 +153,7 @@ pub struct CronJobPatch {
 
 #[cfg(test)]
 mod tests {
-    use super::JobType;
+    use super::{DeliveryConfig, JobType};
 
     #[test]
     fn job_type_try_from_accepts_known_values_case_insensitive() {
@@ -163,4 +168,17 @@ mod tests {
         assert!(JobType::try_from("").is_err());
         assert!(JobType::try_from("unknown").is_err());
     }
+
+    #[test]
+    fn delivery_urgent_defaults_to_false() {
+        let delivery: DeliveryConfig =
+            serde_json::from_str(r#"{"mode":"announce","channel":"lark","to":"oc_1"}"#).unwrap();
+        assert!(!delivery.urgent);
+
+        let delivery: DeliveryConfig = serde_json::from_str(
+            r#"{"mode":"announce","channel":"lark","to":"oc_1","urgent":true}"#,
+        )
+        .unwrap();
+        assert!(delivery.urgent);
+    }
 }
@@ -5,8 +5,12 @@ use std::future::Future;
 use std::path::PathBuf;
 use tokio::task::JoinHandle;
 use tokio::time::Duration;
+use tokio_util::sync::CancellationToken;
 
 const STATUS_FLUSH_SECONDS: u64 = 5;
+/// How long channels get to drain after a shutdown signal before the
+/// remaining components are aborted.
+const SHUTDOWN_GRACE_SECONDS: u64 = 15;
 
 pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     let initial_backoff = config.reliability.channel_initial_backoff_secs.max(1);
@@ -16,6 +20,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         .max(initial_backoff);
 
     crate::health::mark_component_ok("daemon");
+    let shutdown = CancellationToken::new();
 
     if config.heartbeat.enabled {
         let _ =
@@ -46,6 +51,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
             "gateway",
             initial_backoff,
             max_backoff,
+            shutdown.clone(),
             move || {
                 let cfg = gateway_cfg.clone();
                 let host = gateway_host.clone();
@@ -59,17 +65,18 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     let lark_ws_manager: Option<std::sync::Arc<crate::channels::lark_ws_manager::LarkWsManager>> = {
         // Try feishu config first (always is_feishu=true), then lark config
         let lark_creds = config.channels_config.feishu.as_ref().map(|fs| {
-            (fs.app_id.clone(), fs.app_secret.clone(), true)
+            (fs.app_id.clone(), fs.app_secret.clone(), true, fs.ws_base_url.clone())
         }).or_else(|| {
             config.channels_config.lark.as_ref().map(|lk| {
-                (lk.app_id.clone(), lk.app_secret.clone(), lk.use_feishu)
+                (lk.app_id.clone(), lk.app_secret.clone(), lk.use_feishu, lk.ws_base_url.clone())
             })
         });
-        if let Some((app_id, app_secret, is_feishu)) = lark_creds {
+        if let Some((app_id, app_secret, is_feishu, ws_base_url)) = lark_creds {
             let manager = std::sync::Arc::new(
                 crate::channels::lark_ws_manager::LarkWsManager::new(
                     app_id, app_secret, is_feishu, 256,
                 )
+                .with_ws_base_url(ws_base_url.as_deref()),
             );
             let mgr = manager.clone();
             tokio::spawn(async move { mgr.run().await });
@@ -79,24 +86,28 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         }
     };
 
+    let mut channels_handle = None;
     {
         if has_supervised_channels(&config) {
             let channels_cfg = config.clone();
+            let channels_shutdown = shutdown.clone();
             #[cfg(any(feature = "channel-lark", feature = "feishu-docs-sync"))]
             let channels_ws_mgr = lark_ws_manager.clone();
-            handles.push(spawn_component_supervisor(
+            channels_handle = Some(spawn_component_supervisor(
                 "channels",
                 initial_backoff,
                 max_backoff,
+                shutdown.clone(),
                 move || {
                     let cfg = channels_cfg.clone();
+                    let shutdown = channels_shutdown.clone();
                     #[cfg(any(feature = "channel-lark", feature = "feishu-docs-sync"))]
                     let mgr = channels_ws_mgr.clone();
                     async move {
                         #[cfg(any(feature = "channel-lark", feature = "feishu-docs-sync"))]
-                        { crate::channels::start_channels(cfg, mgr).await }
+                        { crate::channels::start_channels(cfg, shutdown, mgr).await }
                         #[cfg(not(any(feature = "channel-lark", feature = "feishu-docs-sync")))]
-                        { crate::channels::start_channels(cfg).await }
+                        { crate::channels::start_channels(cfg, shutdown).await }
                     }
                 },
             ));
@@ -112,6 +123,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
             "heartbeat",
             initial_backoff,
             max_backoff,
+            shutdown.clone(),
             move || {
                 let cfg = heartbeat_cfg.clone();
                 async move { Box::pin(run_heartbeat_worker(cfg)).await }
@@ -125,6 +137,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
             "scheduler",
             initial_backoff,
             max_backoff,
+            shutdown.clone(),
             move || {
                 let cfg = scheduler_cfg.clone();
                 async move { crate::cron::scheduler::run(cfg).await }
@@ -143,6 +156,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
                 "docs_sync",
                 initial_backoff,
                 max_backoff,
+                shutdown.clone(),
                 move || {
                     let cfg = docs_sync_cfg.clone();
                     let mgr = docs_sync_ws_mgr.clone();
@@ -160,9 +174,20 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     println!("   Components: gateway, channels, heartbeat, scheduler, docs_sync");
     println!("   Ctrl+C to stop");
 
-    tokio::signal::ctrl_c().await?;
+    wait_for_shutdown_signal().await;
     crate::health::mark_component_error("daemon", "shutdown requested");
 
+    // Stop supervisors from restarting anything and let channels drain.
+    shutdown.cancel();
+    if let Some(handle) = channels_handle {
+        if tokio::time::timeout(Duration::from_secs(SHUTDOWN_GRACE_SECONDS), handle)
+            .await
+            .is_err()
+        {
+            tracing::warn!("Channels did not stop within {SHUTDOWN_GRACE_SECONDS}s of shutdown");
+        }
+    }
+
     for handle in &handles {
         handle.abort();
     }
@@ -173,6 +198,39 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     Ok(())
 }
 
+/// Resolve on Ctrl+C, or on SIGTERM on Unix.
+pub async fn wait_for_shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = sigterm.recv() => {}
+                }
+                return;
+            }
+            Err(err) => tracing::warn!("Failed to install SIGTERM handler: {err}"),
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// A token that is cancelled once [`wait_for_shutdown_signal`] resolves.
+pub fn shutdown_on_signal() -> CancellationToken {
+    let shutdown = CancellationToken::new();
+    tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move {
+            wait_for_shutdown_signal().await;
+            tracing::info!("Shutdown signal received; stopping");
+            shutdown.cancel();
+        }
+    });
+    shutdown
+}
+
 pub fn state_file_path(config: &Config) -> PathBuf {
     config
         .config_path
@@ -208,6 +266,7 @@ fn spawn_component_supervisor<F, Fut>(
     name: &'static str,
     initial_backoff_secs: u64,
     max_backoff_secs: u64,
+    shutdown: CancellationToken,
     mut run_component: F,
 ) -> JoinHandle<()>
 where
@@ -220,7 +279,11 @@ where
 
         loop {
             crate::health::mark_component_ok(name);
-            match run_component().await {
+            let result = run_component().await;
+            if shutdown.is_cancelled() {
+                break;
+            }
+            match result {
                 Ok(()) => {
                     crate::health::mark_component_error(name, "component exited unexpectedly");
                     tracing::warn!("Daemon component '{name}' exited unexpectedly");
@@ -234,7 +297,10 @@ where
             }
 
             crate::health::bump_component_restart(name);
-            tokio::time::sleep(Duration::from_secs(backoff)).await;
+            tokio::select! {
+                () = tokio::time::sleep(Duration::from_secs(backoff)) => {}
+                () = shutdown.cancelled() => break,
+            }
             // Double backoff AFTER sleeping so first error uses initial_backoff
             backoff = backoff.saturating_mul(2).min(max_backoff);
         }
@@ -318,9 +384,13 @@ mod tests {
 
     #[tokio::test]
     async fn supervisor_marks_error_and_restart_on_failure() {
-        let handle = spawn_component_supervisor("daemon-test-fail", 1, 1, || async {
-            anyhow::bail!("boom")
-        });
+        let handle = spawn_component_supervisor(
+            "daemon-test-fail",
+            1,
+            1,
+            CancellationToken::new(),
+            || async { anyhow::bail!("boom") },
+        );
 
         tokio::time::sleep(Duration::from_millis(50)).await;
         handle.abort();
@@ -338,7 +408,13 @@ mod tests {
 
     #[tokio::test]
     async fn supervisor_marks_unexpected_exit_as_error() {
-        let handle = spawn_component_supervisor("daemon-test-exit", 1, 1, || async { Ok(()) });
+        let handle = spawn_component_supervisor(
+            "daemon-test-exit",
+            1,
+            1,
+            CancellationToken::new(),
+            || async { Ok(()) },
+        );
 
         tokio::time::sleep(Duration::from_millis(50)).await;
         handle.abort();
@@ -354,6 +430,32 @@ mod tests {
             .contains("component exited unexpectedly"));
     }
 
+    #[tokio::test]
+    async fn supervisor_does_not_restart_after_shutdown() {
+        let shutdown = CancellationToken::new();
+        let runs = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let handle = spawn_component_supervisor("daemon-test-shutdown", 1, 1, shutdown.clone(), {
+            let runs = runs.clone();
+            let shutdown = shutdown.clone();
+            move || {
+                runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let shutdown = shutdown.clone();
+                async move {
+                    shutdown.cancelled().await;
+                    Ok(())
+                }
+            }
+        });
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        shutdown.cancel();
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("supervisor should exit after shutdown")
+            .unwrap();
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
     #[test]
     fn detects_no_supervised_channels() {
         let config = Config::default();
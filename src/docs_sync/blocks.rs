@@ -0,0 +1,816 @@
+//! Conversion between synced files and Feishu docx blocks.
+//!
+//! Markdown files are mapped onto native block types so they render properly
+//! in Feishu: headings, fenced code (as code blocks with their language),
+//! tables, and bullet/ordered lists with nesting. Every other markdown line,
+//! and every line of non-markdown files such as `config.toml`, becomes a
+//! plain text block. [`blocks_to_markdown`] is the inverse used when pulling
+//! remote edits back into local files.
+
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Feishu docx block type ids.
+pub const PAGE_BLOCK_TYPE: i64 = 1;
+pub const TEXT_BLOCK_TYPE: i64 = 2;
+/// `heading1` … `heading9` are consecutive from here.
+pub const HEADING1_BLOCK_TYPE: i64 = 3;
+pub const BULLET_BLOCK_TYPE: i64 = 12;
+pub const ORDERED_BLOCK_TYPE: i64 = 13;
+pub const CODE_BLOCK_TYPE: i64 = 14;
+pub const TABLE_BLOCK_TYPE: i64 = 31;
+pub const TABLE_CELL_BLOCK_TYPE: i64 = 32;
+
+/// Feishu code block language for unrecognised or missing fence info.
+const PLAIN_TEXT_LANGUAGE: u64 = 1;
+
+/// Fence info strings and their Feishu code block language ids. The first
+/// name listed for an id is the one written back when pulling.
+const CODE_LANGUAGES: &[(&str, u64)] = &[
+    ("bash", 7),
+    ("csharp", 8),
+    ("cs", 8),
+    ("cpp", 9),
+    ("c++", 9),
+    ("c", 10),
+    ("css", 12),
+    ("dockerfile", 18),
+    ("go", 22),
+    ("golang", 22),
+    ("html", 24),
+    ("http", 26),
+    ("json", 28),
+    ("java", 29),
+    ("javascript", 30),
+    ("js", 30),
+    ("kotlin", 32),
+    ("lua", 36),
+    ("makefile", 38),
+    ("markdown", 39),
+    ("md", 39),
+    ("php", 43),
+    ("powershell", 46),
+    ("python", 49),
+    ("py", 49),
+    ("ruby", 52),
+    ("rb", 52),
+    ("rust", 53),
+    ("rs", 53),
+    ("sql", 56),
+    ("shell", 60),
+    ("sh", 60),
+    ("zsh", 60),
+    ("swift", 61),
+    ("typescript", 63),
+    ("ts", 63),
+    ("xml", 66),
+    ("yaml", 67),
+    ("yml", 67),
+    ("diff", 69),
+    ("graphql", 71),
+    ("toml", 75),
+];
+
+/// Content of a single block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockKind {
+    Text(String),
+    Heading {
+        level: u8,
+        text: String,
+    },
+    Bullet(String),
+    Ordered(String),
+    Code {
+        language: u64,
+        text: String,
+    },
+    /// Cell texts row by row; the first row is the header.
+    Table {
+        rows: Vec<Vec<String>>,
+    },
+}
+
+/// A block and its nested children (only list items nest).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeishuBlock {
+    pub kind: BlockKind,
+    pub children: Vec<FeishuBlock>,
+}
+
+/// A top-level block read from a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteBlock {
+    pub block_id: String,
+    /// `None` when the block (or one of its children) has no markdown
+    /// equivalent, e.g. images or callouts added in Feishu.
+    pub block: Option<FeishuBlock>,
+    /// Fingerprint of the block's content, including nested children. Feishu
+    /// only versions whole documents, so this stands in for a per-block
+    /// revision id when detecting which blocks changed since the last sync.
+    pub revision: String,
+}
+
+pub(crate) fn text_elements(content: &str) -> Value {
+    json!({ "elements": [{ "text_run": { "content": content } }] })
+}
+
+impl FeishuBlock {
+    fn leaf(kind: BlockKind) -> Self {
+        Self {
+            kind,
+            children: Vec::new(),
+        }
+    }
+
+    /// Feishu block type id.
+    pub fn block_type(&self) -> i64 {
+        match &self.kind {
+            BlockKind::Text(_) => TEXT_BLOCK_TYPE,
+            BlockKind::Heading { level, .. } => HEADING1_BLOCK_TYPE + i64::from(*level) - 1,
+            BlockKind::Bullet(_) => BULLET_BLOCK_TYPE,
+            BlockKind::Ordered(_) => ORDERED_BLOCK_TYPE,
+            BlockKind::Code { .. } => CODE_BLOCK_TYPE,
+            BlockKind::Table { .. } => TABLE_BLOCK_TYPE,
+        }
+    }
+
+    fn is_list_item(&self) -> bool {
+        matches!(self.kind, BlockKind::Bullet(_) | BlockKind::Ordered(_))
+    }
+
+    /// The `update_text_elements` payload that turns `self` into `new` in
+    /// place, or `None` when the block has to be replaced instead (different
+    /// type, heading level or code language, or nested children).
+    pub fn text_update(&self, new: &FeishuBlock) -> Option<Value> {
+        if !self.children.is_empty() || !new.children.is_empty() {
+            return None;
+        }
+        match (&self.kind, &new.kind) {
+            (BlockKind::Text(_), BlockKind::Text(text))
+            | (BlockKind::Bullet(_), BlockKind::Bullet(text))
+            | (BlockKind::Ordered(_), BlockKind::Ordered(text)) => Some(text_elements(text)),
+            (BlockKind::Heading { level: a, .. }, BlockKind::Heading { level: b, text })
+                if a == b =>
+            {
+                Some(text_elements(text))
+            }
+            (BlockKind::Code { language: a, .. }, BlockKind::Code { language: b, text })
+                if a == b =>
+            {
+                Some(text_elements(text))
+            }
+            _ => None,
+        }
+    }
+
+    /// Append this block and its descendants to `out` (parent first) and
+    /// return the temporary id assigned to it.
+    fn push_descendants(&self, next_id: &mut usize, out: &mut Vec<Value>) -> String {
+        let id = temp_block_id(next_id);
+        let slot = out.len();
+        out.push(Value::Null);
+
+        let mut body = Map::new();
+        body.insert("block_id".into(), json!(id));
+        body.insert("block_type".into(), json!(self.block_type()));
+        let children: Vec<String> = match &self.kind {
+            BlockKind::Text(text) => {
+                body.insert("text".into(), text_elements(text));
+                self.push_children(next_id, out)
+            }
+            BlockKind::Heading { level, text } => {
+                body.insert(format!("heading{level}"), text_elements(text));
+                Vec::new()
+            }
+            BlockKind::Bullet(text) => {
+                body.insert("bullet".into(), text_elements(text));
+                self.push_children(next_id, out)
+            }
+            BlockKind::Ordered(text) => {
+                body.insert("ordered".into(), text_elements(text));
+                self.push_children(next_id, out)
+            }
+            BlockKind::Code { language, text } => {
+                let mut code = text_elements(text);
+                code["style"] = json!({ "language": language, "wrap": false });
+                body.insert("code".into(), code);
+                Vec::new()
+            }
+            BlockKind::Table { rows } => {
+                let columns = rows.first().map_or(0, Vec::len);
+                body.insert(
+                    "table".into(),
+                    json!({
+                        "property": {
+                            "row_size": rows.len(),
+                            "column_size": columns,
+                            "header_row": true,
+                        }
+                    }),
+                );
+                rows.iter()
+                    .flatten()
+                    .map(|cell| {
+                        let cell_id = temp_block_id(next_id);
+                        let text_id = temp_block_id(next_id);
+                        out.push(json!({
+                            "block_id": cell_id,
+                            "block_type": TABLE_CELL_BLOCK_TYPE,
+                            "table_cell": {},
+                            "children": [text_id],
+                        }));
+                        out.push(json!({
+                            "block_id": text_id,
+                            "block_type": TEXT_BLOCK_TYPE,
+                            "text": text_elements(cell),
+                            "children": [],
+                        }));
+                        cell_id
+                    })
+                    .collect()
+            }
+        };
+        body.insert("children".into(), json!(children));
+        out[slot] = Value::Object(body);
+        id
+    }
+
+    fn push_children(&self, next_id: &mut usize, out: &mut Vec<Value>) -> Vec<String> {
+        self.children
+            .iter()
+            .map(|child| child.push_descendants(next_id, out))
+            .collect()
+    }
+}
+
+fn temp_block_id(next_id: &mut usize) -> String {
+    *next_id += 1;
+    format!("tmp_{next_id}")
+}
+
+/// Serialize `blocks` for the create-descendant API: the temporary ids of the
+/// top-level blocks (`children_id`) and every block flattened (`descendants`).
+pub fn to_descendants(blocks: &[FeishuBlock]) -> (Vec<String>, Vec<Value>) {
+    let mut next_id = 0;
+    let mut descendants = Vec::new();
+    let ids = blocks
+        .iter()
+        .map(|block| block.push_descendants(&mut next_id, &mut descendants))
+        .collect();
+    (ids, descendants)
+}
+
+/// Whether `filename` is synced with markdown block conversion.
+pub fn is_markdown(filename: &str) -> bool {
+    Path::new(filename)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown"))
+}
+
+/// Blocks for a synced file: markdown conversion for `.md` files, one text
+/// block per line otherwise.
+pub fn file_to_blocks(filename: &str, content: &str) -> Vec<FeishuBlock> {
+    if is_markdown(filename) {
+        markdown_to_blocks(content)
+    } else {
+        content
+            .lines()
+            .map(|line| FeishuBlock::leaf(BlockKind::Text(line.to_string())))
+            .collect()
+    }
+}
+
+/// Feishu code block language for a fence info string such as `rust` or
+/// `py title="x"`.
+pub fn code_language(info: &str) -> u64 {
+    let name = info.split_whitespace().next().unwrap_or("");
+    CODE_LANGUAGES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+        .map_or(PLAIN_TEXT_LANGUAGE, |(_, id)| *id)
+}
+
+fn language_name(language: u64) -> &'static str {
+    CODE_LANGUAGES
+        .iter()
+        .find(|(_, id)| *id == language)
+        .map_or("", |(name, _)| name)
+}
+
+/// Convert markdown into blocks.
+pub fn markdown_to_blocks(content: &str) -> Vec<FeishuBlock> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+
+        if let Some((fence, info)) = fence_open(line) {
+            let end = (i + 1..lines.len())
+                .find(|&j| is_fence_close(lines[j], fence))
+                .unwrap_or(lines.len());
+            blocks.push(FeishuBlock::leaf(BlockKind::Code {
+                language: code_language(info),
+                text: lines[i + 1..end].join("\n"),
+            }));
+            i = end + 1;
+            continue;
+        }
+
+        if let Some((level, text)) = heading(line) {
+            blocks.push(FeishuBlock::leaf(BlockKind::Heading {
+                level,
+                text: text.to_string(),
+            }));
+            i += 1;
+            continue;
+        }
+
+        if is_table_row(line) && lines.get(i + 1).is_some_and(|l| is_table_separator(l)) {
+            let header = split_table_row(line);
+            let columns = header.len();
+            let mut rows = vec![header];
+            i += 2;
+            while i < lines.len() && is_table_row(lines[i]) {
+                let mut row = split_table_row(lines[i]);
+                row.resize(columns, String::new());
+                rows.push(row);
+                i += 1;
+            }
+            blocks.push(FeishuBlock::leaf(BlockKind::Table { rows }));
+            continue;
+        }
+
+        if list_item(line).is_some() {
+            let mut items = Vec::new();
+            while let Some(item) = lines.get(i).and_then(|l| list_item(l)) {
+                items.push(item);
+                i += 1;
+            }
+            blocks.extend(build_list(&items));
+            continue;
+        }
+
+        blocks.push(FeishuBlock::leaf(BlockKind::Text(line.to_string())));
+        i += 1;
+    }
+    blocks
+}
+
+/// Opening code fence: the fence marker and the info string after it.
+fn fence_open(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = trimmed.chars().take_while(|c| *c == marker).count();
+    if len < 3 {
+        return None;
+    }
+    let (fence, info) = trimmed.split_at(len);
+    if marker == '`' && info.contains('`') {
+        return None;
+    }
+    Some((fence, info.trim()))
+}
+
+fn is_fence_close(line: &str, fence: &str) -> bool {
+    let trimmed = line.trim();
+    let marker = fence.chars().next().unwrap_or('`');
+    trimmed.len() >= fence.len() && trimmed.chars().all(|c| c == marker)
+}
+
+fn heading(line: &str) -> Option<(u8, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=9).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some((u8::try_from(level).ok()?, rest.trim()))
+}
+
+fn is_table_row(line: &str) -> bool {
+    line.trim_start().starts_with('|')
+}
+
+fn is_table_separator(line: &str) -> bool {
+    let trimmed = line.trim();
+    is_table_row(trimmed)
+        && trimmed.contains('-')
+        && trimmed.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+fn split_table_row(line: &str) -> Vec<String> {
+    let trimmed = line.trim();
+    let inner = trimmed.strip_prefix('|').unwrap_or(trimmed);
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+    inner
+        .split('|')
+        .map(|cell| cell.trim().to_string())
+        .collect()
+}
+
+/// A list item line: its indentation width and content.
+fn list_item(line: &str) -> Option<(usize, BlockKind)> {
+    let rest = line.trim_start();
+    let indent: usize = line[..line.len() - rest.len()]
+        .chars()
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum();
+    for marker in ["- ", "* ", "+ "] {
+        if let Some(text) = rest.strip_prefix(marker) {
+            return Some((indent, BlockKind::Bullet(text.to_string())));
+        }
+    }
+    let digits = rest.chars().take_while(char::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    let text = rest[digits..]
+        .strip_prefix(". ")
+        .or_else(|| rest[digits..].strip_prefix(") "))?;
+    Some((indent, BlockKind::Ordered(text.to_string())))
+}
+
+/// Nest consecutive list items by indentation: an item indented deeper than
+/// the one before it becomes that item's child.
+fn build_list(items: &[(usize, BlockKind)]) -> Vec<FeishuBlock> {
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < items.len() {
+        let (indent, kind) = &items[i];
+        let end = (i + 1..items.len())
+            .find(|&j| items[j].0 <= *indent)
+            .unwrap_or(items.len());
+        blocks.push(FeishuBlock {
+            kind: kind.clone(),
+            children: build_list(&items[i + 1..end]),
+        });
+        i = end;
+    }
+    blocks
+}
+
+/// Render blocks back into markdown (one trailing newline).
+pub fn blocks_to_markdown(blocks: &[FeishuBlock]) -> String {
+    let mut lines = Vec::new();
+    render_blocks(blocks, 0, &mut lines);
+    if lines.is_empty() {
+        String::new()
+    } else {
+        lines.join("\n") + "\n"
+    }
+}
+
+fn render_blocks(blocks: &[FeishuBlock], indent: usize, out: &mut Vec<String>) {
+    let pad = " ".repeat(indent);
+    let mut ordinal = 0;
+    for block in blocks {
+        ordinal = if matches!(block.kind, BlockKind::Ordered(_)) {
+            ordinal + 1
+        } else {
+            0
+        };
+        match &block.kind {
+            BlockKind::Text(text) => out.push(format!("{pad}{text}")),
+            BlockKind::Heading { level, text } => {
+                let hashes = "#".repeat(usize::from(*level));
+                if text.is_empty() {
+                    out.push(hashes);
+                } else {
+                    out.push(format!("{hashes} {text}"));
+                }
+            }
+            BlockKind::Bullet(text) => {
+                out.push(format!("{pad}- {text}"));
+                render_blocks(&block.children, indent + 2, out);
+            }
+            BlockKind::Ordered(text) => {
+                let marker = format!("{ordinal}. ");
+                out.push(format!("{pad}{marker}{text}"));
+                render_blocks(&block.children, indent + marker.len(), out);
+            }
+            BlockKind::Code { language, text } => {
+                out.push(format!("```{}", language_name(*language)));
+                if !text.is_empty() {
+                    out.push(text.clone());
+                }
+                out.push("```".into());
+            }
+            BlockKind::Table { rows } => {
+                let render_row = |row: &[String]| format!("| {} |", row.join(" | "));
+                if let Some(header) = rows.first() {
+                    out.push(render_row(header));
+                    out.push(render_row(&vec!["---".to_string(); header.len()]));
+                }
+                out.extend(rows.iter().skip(1).map(|row| render_row(row)));
+            }
+        }
+    }
+}
+
+/// The top-level blocks of a document from its (flat) block listing.
+pub fn parse_document(document_id: &str, items: &[Value]) -> Vec<RemoteBlock> {
+    let by_id: HashMap<&str, &Value> = items
+        .iter()
+        .filter_map(|item| Some((item.get("block_id")?.as_str()?, item)))
+        .collect();
+
+    // The page block (id == document id) lists its children in order; fall
+    // back to parent_id filtering when it is missing from the response.
+    let top_level: Vec<&Value> = match by_id.get(document_id).map(|page| child_ids(page)) {
+        Some(ids) => ids.iter().filter_map(|id| by_id.get(id).copied()).collect(),
+        None => items
+            .iter()
+            .filter(|item| item.get("parent_id").and_then(Value::as_str) == Some(document_id))
+            .collect(),
+    };
+    top_level
+        .into_iter()
+        .filter_map(|item| {
+            let block = parse_block(item, &by_id);
+            let revision = match &block {
+                Some(block) => fingerprint(&blocks_to_markdown(std::slice::from_ref(block))),
+                None => fingerprint(&item.to_string()),
+            };
+            Some(RemoteBlock {
+                block_id: item.get("block_id")?.as_str()?.to_string(),
+                block,
+                revision,
+            })
+        })
+        .collect()
+}
+
+fn fingerprint(content: &str) -> String {
+    let digest = Sha256::digest(content.as_bytes());
+    format!("{digest:x}")
+}
+
+fn child_ids(item: &Value) -> Vec<&str> {
+    item.get("children")
+        .and_then(Value::as_array)
+        .map(|ids| ids.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn elements_text(payload: Option<&Value>) -> String {
+    payload
+        .and_then(|p| p.get("elements"))
+        .and_then(Value::as_array)
+        .map(|elements| {
+            elements
+                .iter()
+                .filter_map(|e| e.pointer("/text_run/content").and_then(Value::as_str))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_block(item: &Value, by_id: &HashMap<&str, &Value>) -> Option<FeishuBlock> {
+    let block_type = item.get("block_type")?.as_i64()?;
+    let kind = match block_type {
+        TEXT_BLOCK_TYPE => BlockKind::Text(elements_text(item.get("text"))),
+        t if (HEADING1_BLOCK_TYPE..HEADING1_BLOCK_TYPE + 9).contains(&t) => {
+            let level = u8::try_from(t - HEADING1_BLOCK_TYPE + 1).ok()?;
+            BlockKind::Heading {
+                level,
+                text: elements_text(item.get(format!("heading{level}").as_str())),
+            }
+        }
+        BULLET_BLOCK_TYPE => BlockKind::Bullet(elements_text(item.get("bullet"))),
+        ORDERED_BLOCK_TYPE => BlockKind::Ordered(elements_text(item.get("ordered"))),
+        CODE_BLOCK_TYPE => BlockKind::Code {
+            language: item
+                .pointer("/code/style/language")
+                .and_then(Value::as_u64)
+                .unwrap_or(PLAIN_TEXT_LANGUAGE),
+            text: elements_text(item.get("code")),
+        },
+        TABLE_BLOCK_TYPE => return parse_table(item, by_id),
+        _ => return None,
+    };
+    let children = child_ids(item)
+        .into_iter()
+        .map(|id| parse_block(by_id.get(id)?, by_id))
+        .collect::<Option<Vec<_>>>()?;
+    let block = FeishuBlock { kind, children };
+    // Markdown can only express nesting between list items.
+    let nesting_ok = block.children.is_empty()
+        || (block.is_list_item() && block.children.iter().all(FeishuBlock::is_list_item));
+    nesting_ok.then_some(block)
+}
+
+fn parse_table(item: &Value, by_id: &HashMap<&str, &Value>) -> Option<FeishuBlock> {
+    let columns = usize::try_from(
+        item.pointer("/table/property/column_size")
+            .and_then(Value::as_u64)?,
+    )
+    .ok()
+    .filter(|c| *c > 0)?;
+    let cell_ids = match item.pointer("/table/cells").and_then(Value::as_array) {
+        Some(cells) => cells.iter().filter_map(Value::as_str).collect(),
+        None => child_ids(item),
+    };
+    let cells = cell_ids
+        .into_iter()
+        .map(|id| {
+            let cell = by_id.get(id)?;
+            if cell.get("block_type")?.as_i64()? != TABLE_CELL_BLOCK_TYPE {
+                return None;
+            }
+            let texts: Vec<String> = child_ids(cell)
+                .into_iter()
+                .filter_map(|id| by_id.get(id))
+                .map(|text| elements_text(text.get("text")))
+                .collect();
+            Some(texts.join(" "))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let rows = cells.chunks(columns).map(<[String]>::to_vec).collect();
+    Some(FeishuBlock::leaf(BlockKind::Table { rows }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descendants(markdown: &str) -> (Vec<String>, Vec<Value>) {
+        to_descendants(&markdown_to_blocks(markdown))
+    }
+
+    fn find<'a>(blocks: &'a [Value], id: &str) -> &'a Value {
+        blocks
+            .iter()
+            .find(|b| b["block_id"] == id)
+            .unwrap_or_else(|| panic!("no block {id}"))
+    }
+
+    fn content(payload: &Value) -> &Value {
+        &payload["elements"][0]["text_run"]["content"]
+    }
+
+    #[test]
+    fn fenced_code_becomes_code_block_with_language() {
+        let (top, blocks) = descendants("Intro\n```rust\nfn main() {}\nlet x = 1;\n```\n");
+        assert_eq!(top.len(), 2);
+        let code = find(&blocks, &top[1]);
+        assert_eq!(code["block_type"], CODE_BLOCK_TYPE);
+        assert_eq!(code["code"]["style"]["language"], 53);
+        assert_eq!(content(&code["code"]), "fn main() {}\nlet x = 1;");
+    }
+
+    #[test]
+    fn unknown_or_missing_fence_language_is_plain_text() {
+        let blocks = markdown_to_blocks("~~~\nplain\n~~~\n```brainfuck\n+++\n```\n");
+        assert_eq!(
+            blocks,
+            vec![
+                FeishuBlock::leaf(BlockKind::Code {
+                    language: PLAIN_TEXT_LANGUAGE,
+                    text: "plain".into()
+                }),
+                FeishuBlock::leaf(BlockKind::Code {
+                    language: PLAIN_TEXT_LANGUAGE,
+                    text: "+++".into()
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn table_becomes_table_block_with_cells() {
+        let (top, blocks) =
+            descendants("| Tool | Enabled |\n|------|:-------:|\n| shell | yes |\n| http |\n");
+        assert_eq!(top.len(), 1);
+        let table = find(&blocks, &top[0]);
+        assert_eq!(table["block_type"], TABLE_BLOCK_TYPE);
+        assert_eq!(table["table"]["property"]["row_size"], 3);
+        assert_eq!(table["table"]["property"]["column_size"], 2);
+
+        let cells = table["children"].as_array().unwrap();
+        assert_eq!(cells.len(), 6);
+        let texts: Vec<&Value> = cells
+            .iter()
+            .map(|id| {
+                let cell = find(&blocks, id.as_str().unwrap());
+                assert_eq!(cell["block_type"], TABLE_CELL_BLOCK_TYPE);
+                let text = find(&blocks, cell["children"][0].as_str().unwrap());
+                assert_eq!(text["block_type"], TEXT_BLOCK_TYPE);
+                content(&text["text"])
+            })
+            .collect();
+        assert_eq!(texts, ["Tool", "Enabled", "shell", "yes", "http", ""]);
+    }
+
+    #[test]
+    fn nested_bullets_become_child_blocks() {
+        let (top, blocks) = descendants("- tools\n  - shell\n    - bash\n  - http\n- memory\n");
+        assert_eq!(top.len(), 2);
+        let tools = find(&blocks, &top[0]);
+        assert_eq!(tools["block_type"], BULLET_BLOCK_TYPE);
+        assert_eq!(content(&tools["bullet"]), "tools");
+
+        let nested = tools["children"].as_array().unwrap();
+        assert_eq!(nested.len(), 2);
+        let shell = find(&blocks, nested[0].as_str().unwrap());
+        assert_eq!(content(&shell["bullet"]), "shell");
+        let bash = find(&blocks, shell["children"][0].as_str().unwrap());
+        assert_eq!(content(&bash["bullet"]), "bash");
+        let http = find(&blocks, nested[1].as_str().unwrap());
+        assert_eq!(http["children"], json!([]));
+
+        let memory = find(&blocks, &top[1]);
+        assert_eq!(memory["children"], json!([]));
+    }
+
+    #[test]
+    fn headings_and_ordered_lists_use_their_block_types() {
+        let (top, blocks) = descendants("## Steps\n1. clone\n2) build\n   - release\n");
+        let heading = find(&blocks, &top[0]);
+        assert_eq!(heading["block_type"], HEADING1_BLOCK_TYPE + 1);
+        assert_eq!(content(&heading["heading2"]), "Steps");
+
+        let clone = find(&blocks, &top[1]);
+        assert_eq!(clone["block_type"], ORDERED_BLOCK_TYPE);
+        assert_eq!(content(&clone["ordered"]), "clone");
+        let build = find(&blocks, &top[2]);
+        let release = find(&blocks, build["children"][0].as_str().unwrap());
+        assert_eq!(release["block_type"], BULLET_BLOCK_TYPE);
+    }
+
+    #[test]
+    fn other_lines_stay_text_blocks() {
+        let blocks = markdown_to_blocks("#hashtag\n\n---\n| not a table\n-not a list\n");
+        assert!(blocks.iter().all(|b| matches!(b.kind, BlockKind::Text(_))));
+        assert_eq!(blocks.len(), 5);
+    }
+
+    #[test]
+    fn non_markdown_files_are_one_text_block_per_line() {
+        let blocks = file_to_blocks("config.toml", "# comment\n- x\n");
+        assert_eq!(
+            blocks,
+            vec![
+                FeishuBlock::leaf(BlockKind::Text("# comment".into())),
+                FeishuBlock::leaf(BlockKind::Text("- x".into())),
+            ]
+        );
+        assert!(is_markdown("SOUL.md"));
+        assert!(!is_markdown("config.toml"));
+    }
+
+    #[test]
+    fn parsed_document_renders_back_to_markdown() {
+        let markdown = "# Tools\n\nUse these:\n- shell\n  - bash\n- http\n\n1. first\n2. second\n\n```python\nprint(1)\n```\n\n| a | b |\n| --- | --- |\n| 1 | 2 |\n";
+        let (top, mut items) = descendants(markdown);
+        items.push(json!({ "block_id": "doc_1", "block_type": PAGE_BLOCK_TYPE, "children": top }));
+
+        let remote = parse_document("doc_1", &items);
+        let blocks: Vec<FeishuBlock> = remote.into_iter().map(|r| r.block.unwrap()).collect();
+        assert_eq!(blocks, markdown_to_blocks(markdown));
+        assert_eq!(blocks_to_markdown(&blocks), markdown);
+    }
+
+    #[test]
+    fn unsupported_remote_blocks_are_not_representable() {
+        let items = vec![
+            json!({ "block_id": "doc_1", "block_type": PAGE_BLOCK_TYPE, "children": ["img", "t", "q"] }),
+            json!({ "block_id": "img", "block_type": 27, "parent_id": "doc_1" }),
+            json!({ "block_id": "t", "block_type": 2, "text": text_elements("hi"), "children": ["c"] }),
+            json!({ "block_id": "c", "block_type": 2, "text": text_elements("nested") }),
+            json!({ "block_id": "q", "block_type": 2, "text": text_elements("ok") }),
+        ];
+        let remote = parse_document("doc_1", &items);
+        assert_eq!(remote.len(), 3);
+        assert_ne!(remote[0].revision, remote[2].revision);
+        assert_eq!(remote[0].block, None);
+        assert_eq!(remote[1].block, None);
+        assert_eq!(
+            remote[2].block,
+            Some(FeishuBlock::leaf(BlockKind::Text("ok".into())))
+        );
+    }
+
+    #[test]
+    fn text_update_only_for_compatible_blocks() {
+        let text = FeishuBlock::leaf(BlockKind::Text("a".into()));
+        let code = |language| {
+            FeishuBlock::leaf(BlockKind::Code {
+                language,
+                text: "x".into(),
+            })
+        };
+        assert!(text
+            .text_update(&FeishuBlock::leaf(BlockKind::Text("b".into())))
+            .is_some());
+        assert!(text.text_update(&code(53)).is_none());
+        assert!(code(53).text_update(&code(53)).is_some());
+        assert!(code(53).text_update(&code(49)).is_none());
+    }
+}
@@ -2,7 +2,11 @@
 //!
 //! Shares the same token caching pattern as `LarkChannel` in `src/channels/lark.rs`.
 
+use super::blocks::{to_descendants, FeishuBlock};
+use crate::retry::{RetryDecision, RetryPolicy};
+use crate::security::{redact_json_for_log, redact_log_body};
 use anyhow::{bail, Result};
+use async_trait::async_trait;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
@@ -15,10 +19,14 @@ const DEFAULT_TOKEN_TTL: Duration = Duration::from_secs(7200);
 /// Rate limit for batch_update_blocks: 3 requests per second.
 const BATCH_UPDATE_MIN_INTERVAL: Duration = Duration::from_millis(334);
 
-/// Retry constants following src/providers/reliable.rs pattern.
-const RETRY_MAX_ATTEMPTS: u32 = 3;
-const RETRY_BASE_BACKOFF: Duration = Duration::from_secs(1);
-const RETRY_BACKOFF_CAP: Duration = Duration::from_secs(10);
+/// Backoff for Feishu requests: one attempt plus three retries.
+const RETRY_POLICY: RetryPolicy = RetryPolicy {
+    max_attempts: 4,
+    base_delay: Duration::from_secs(1),
+    max_delay: Duration::from_secs(10),
+    jitter: Duration::from_millis(250),
+};
+/// Upper bound for a server-announced `Retry-After`.
 const RETRY_AFTER_CAP: Duration = Duration::from_secs(30);
 
 /// A single block update operation for the Feishu Docs API.
@@ -30,16 +38,139 @@ pub struct BlockUpdate {
     pub update_text_elements: serde_json::Value,
 }
 
+/// Max top-level blocks created by a single create-descendant call.
+const MAX_CHILDREN_PER_INSERT: usize = 50;
+
+/// Fail with the Feishu error message unless the response is a success with `code == 0`.
+fn ensure_feishu_ok(op: &str, status: reqwest::StatusCode, data: &serde_json::Value) -> Result<()> {
+    if !status.is_success() {
+        bail!(
+            "Feishu {op} failed: status={status}, body={}",
+            redact_json_for_log(data)
+        );
+    }
+    let code = data.get("code").and_then(|c| c.as_i64()).unwrap_or(-1);
+    if code != 0 {
+        let msg = data
+            .get("msg")
+            .and_then(|m| m.as_str())
+            .unwrap_or("unknown");
+        bail!("Feishu {op} error: {}", redact_log_body(msg));
+    }
+    Ok(())
+}
+
+/// Retry network errors, 429 (honouring `Retry-After`) and 5xx; hand
+/// everything else back to the caller.
+fn classify_feishu_response(outcome: &reqwest::Result<reqwest::Response>) -> RetryDecision {
+    let resp = match outcome {
+        Ok(resp) => resp,
+        Err(e) => {
+            tracing::warn!("Feishu request failed (network): {e}, retrying");
+            return RetryDecision::Retry;
+        }
+    };
+    let status = resp.status();
+    if status.is_success() || (status.is_client_error() && status.as_u16() != 429) {
+        return RetryDecision::Stop;
+    }
+    tracing::warn!(status = %status, "Feishu request failed, retrying");
+    if status.as_u16() != 429 {
+        return RetryDecision::Retry;
+    }
+    resp.headers()
+        .get("retry-after")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.parse::<f64>().ok())
+        .map(|secs| Duration::from_secs_f64(secs.max(0.0)).min(RETRY_AFTER_CAP))
+        .map_or(RetryDecision::Retry, RetryDecision::RetryAfter)
+}
+
 #[derive(Debug, Clone)]
 struct CachedToken {
     value: String,
     refresh_after: Instant,
 }
 
+/// Document and messaging operations docs_sync needs from Feishu.
+///
+/// Implemented by [`FeishuDocsClient`]; sharing and block diffing are written
+/// against this trait so they can be exercised without a network.
+#[async_trait]
+pub trait DocsClient: Send + Sync {
+    /// Every block of the document, flattened.
+    async fn list_blocks(&self, document_id: &str) -> Result<Vec<serde_json::Value>>;
+    /// The document's plain-text content.
+    async fn get_raw_content(&self, document_id: &str) -> Result<String>;
+    /// Update the text of existing blocks in place.
+    async fn batch_update_blocks(&self, document_id: &str, updates: &[BlockUpdate]) -> Result<()>;
+    /// Insert `blocks` at `index` under the page block.
+    async fn insert_blocks(
+        &self,
+        document_id: &str,
+        index: usize,
+        blocks: &[FeishuBlock],
+    ) -> Result<()>;
+    /// Remove the page block's children in `[start, end)`.
+    async fn delete_blocks(&self, document_id: &str, start: usize, end: usize) -> Result<()>;
+    /// Grant `open_id` the `perm` permission on the document.
+    async fn add_permission_member(
+        &self,
+        document_id: &str,
+        open_id: &str,
+        perm: &str,
+    ) -> Result<()>;
+    /// Send an interactive card to a user by open_id.
+    async fn send_message_card(&self, open_id: &str, card_json: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl DocsClient for FeishuDocsClient {
+    async fn list_blocks(&self, document_id: &str) -> Result<Vec<serde_json::Value>> {
+        FeishuDocsClient::list_blocks(self, document_id).await
+    }
+
+    async fn get_raw_content(&self, document_id: &str) -> Result<String> {
+        FeishuDocsClient::get_raw_content(self, document_id).await
+    }
+
+    async fn batch_update_blocks(&self, document_id: &str, updates: &[BlockUpdate]) -> Result<()> {
+        FeishuDocsClient::batch_update_blocks(self, document_id, updates).await
+    }
+
+    async fn insert_blocks(
+        &self,
+        document_id: &str,
+        index: usize,
+        blocks: &[FeishuBlock],
+    ) -> Result<()> {
+        FeishuDocsClient::insert_blocks(self, document_id, index, blocks).await
+    }
+
+    async fn delete_blocks(&self, document_id: &str, start: usize, end: usize) -> Result<()> {
+        FeishuDocsClient::delete_blocks(self, document_id, start, end).await
+    }
+
+    async fn add_permission_member(
+        &self,
+        document_id: &str,
+        open_id: &str,
+        perm: &str,
+    ) -> Result<()> {
+        FeishuDocsClient::add_permission_member(self, document_id, open_id, perm).await
+    }
+
+    async fn send_message_card(&self, open_id: &str, card_json: &str) -> Result<()> {
+        FeishuDocsClient::send_message_card(self, open_id, card_json).await
+    }
+}
+
 /// Feishu Docs API client with tenant_access_token caching.
+#[derive(Clone)]
 pub struct FeishuDocsClient {
     app_id: String,
     app_secret: String,
+    base_url: String,
     http: reqwest::Client,
     token: Arc<RwLock<Option<CachedToken>>>,
     last_batch_update: Arc<tokio::sync::Mutex<Instant>>,
@@ -52,6 +183,7 @@ impl FeishuDocsClient {
         Self {
             app_id,
             app_secret,
+            base_url: FEISHU_BASE_URL.to_string(),
             http,
             token: Arc::new(RwLock::new(None)),
             last_batch_update: Arc::new(tokio::sync::Mutex::new(
@@ -60,6 +192,12 @@ impl FeishuDocsClient {
         }
     }
 
+    /// Override the Open API base URL. Useful for local proxies or testing.
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
     /// Get or refresh tenant access token (cached with proactive refresh).
     async fn get_token(&self) -> Result<String> {
         // Fast path: read lock
@@ -82,7 +220,7 @@ impl FeishuDocsClient {
             }
         }
 
-        let url = format!("{FEISHU_BASE_URL}/auth/v3/tenant_access_token/internal");
+        let url = format!("{}/auth/v3/tenant_access_token/internal", self.base_url);
         let body = serde_json::json!({
             "app_id": self.app_id,
             "app_secret": self.app_secret,
@@ -93,7 +231,10 @@ impl FeishuDocsClient {
         let data: serde_json::Value = resp.json().await?;
 
         if !status.is_success() {
-            bail!("Feishu tenant_access_token request failed: status={status}, body={data}");
+            bail!(
+                "Feishu tenant_access_token request failed: status={status}, body={}",
+                redact_json_for_log(&data)
+            );
         }
 
         let code = data.get("code").and_then(|c| c.as_i64()).unwrap_or(-1);
@@ -102,7 +243,10 @@ impl FeishuDocsClient {
                 .get("msg")
                 .and_then(|m| m.as_str())
                 .unwrap_or("unknown");
-            bail!("Feishu tenant_access_token failed: {msg}");
+            bail!(
+                "Feishu tenant_access_token failed: {}",
+                redact_log_body(msg)
+            );
         }
 
         let token_value = data
@@ -136,80 +280,29 @@ impl FeishuDocsClient {
     where
         F: Fn() -> reqwest::RequestBuilder,
     {
-        let mut backoff = RETRY_BASE_BACKOFF;
-        let mut last_err: Option<anyhow::Error> = None;
-
-        for attempt in 0..=RETRY_MAX_ATTEMPTS {
-            let resp = match build_request().send().await {
-                Ok(resp) => resp,
-                Err(e) => {
-                    if attempt == RETRY_MAX_ATTEMPTS {
-                        return Err(e.into());
-                    }
-                    tracing::warn!(attempt, "Feishu request failed (network): {e}, retrying");
-                    last_err = Some(anyhow::Error::from(e));
-                    tokio::time::sleep(backoff).await;
-                    backoff = (backoff * 2).min(RETRY_BACKOFF_CAP);
-                    continue;
-                }
-            };
-
-            let status = resp.status();
-
-            // Success — return immediately
-            if status.is_success() {
-                return Ok(resp);
-            }
-
-            // Non-retryable 4xx (except 429) — let caller handle
-            if status.is_client_error() && status.as_u16() != 429 {
-                return Ok(resp);
-            }
-
-            // Last attempt — return as-is
-            if attempt == RETRY_MAX_ATTEMPTS {
-                return Ok(resp);
-            }
-
-            // 429: parse Retry-After header; 5xx: use exponential backoff
-            let wait = if status.as_u16() == 429 {
-                resp.headers()
-                    .get("retry-after")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|s| s.parse::<f64>().ok())
-                    .map(|secs| Duration::from_secs_f64(secs.max(0.0)).min(RETRY_AFTER_CAP))
-                    .unwrap_or(backoff)
-            } else {
-                backoff
-            };
-
-            tracing::warn!(
-                attempt,
-                status = %status,
-                wait_ms = wait.as_millis() as u64,
-                "Feishu request failed, retrying"
-            );
-            tokio::time::sleep(wait).await;
-            backoff = (backoff * 2).min(RETRY_BACKOFF_CAP);
-        }
-
-        match last_err {
-            Some(e) => Err(e),
-            None => bail!("Feishu request failed after retries"),
-        }
+        let resp = RETRY_POLICY
+            .retry(classify_feishu_response, || build_request().send())
+            .await?;
+        Ok(resp)
     }
 
     /// GET /docx/v1/documents/{id}/raw_content — fetch raw document text.
     pub async fn get_raw_content(&self, document_id: &str) -> Result<String> {
         let token = self.get_token().await?;
-        let url = format!("{FEISHU_BASE_URL}/docx/v1/documents/{document_id}/raw_content");
+        let url = format!(
+            "{}/docx/v1/documents/{document_id}/raw_content",
+            self.base_url
+        );
         let resp = self
             .send_with_retry(|| self.http.get(&url).bearer_auth(&token))
             .await?;
         let status = resp.status();
         let data: serde_json::Value = resp.json().await?;
         if !status.is_success() {
-            bail!("Feishu get_raw_content failed: status={status}, body={data}");
+            bail!(
+                "Feishu get_raw_content failed: status={status}, body={}",
+                redact_json_for_log(&data)
+            );
         }
         let code = data.get("code").and_then(|c| c.as_i64()).unwrap_or(-1);
         if code != 0 {
@@ -217,7 +310,7 @@ impl FeishuDocsClient {
                 .get("msg")
                 .and_then(|m| m.as_str())
                 .unwrap_or("unknown");
-            bail!("Feishu get_raw_content error: {msg}");
+            bail!("Feishu get_raw_content error: {}", redact_log_body(msg));
         }
         let content = data
             .pointer("/data/content")
@@ -246,7 +339,10 @@ impl FeishuDocsClient {
             *last = Instant::now();
         }
         let token = self.get_token().await?;
-        let url = format!("{FEISHU_BASE_URL}/docx/v1/documents/{document_id}/blocks/batch_update");
+        let url = format!(
+            "{}/docx/v1/documents/{document_id}/blocks/batch_update",
+            self.base_url
+        );
         let body = serde_json::json!({ "requests": updates });
         let resp = self
             .send_with_retry(|| self.http.patch(&url).bearer_auth(&token).json(&body))
@@ -254,7 +350,10 @@ impl FeishuDocsClient {
         let status = resp.status();
         let data: serde_json::Value = resp.json().await?;
         if !status.is_success() {
-            bail!("Feishu batch_update_blocks failed: status={status}, body={data}");
+            bail!(
+                "Feishu batch_update_blocks failed: status={status}, body={}",
+                redact_json_for_log(&data)
+            );
         }
         let code = data.get("code").and_then(|c| c.as_i64()).unwrap_or(-1);
         if code != 0 {
@@ -262,7 +361,7 @@ impl FeishuDocsClient {
                 .get("msg")
                 .and_then(|m| m.as_str())
                 .unwrap_or("unknown");
-            bail!("Feishu batch_update_blocks error: {msg}");
+            bail!("Feishu batch_update_blocks error: {}", redact_log_body(msg));
         }
         Ok(())
     }
@@ -270,19 +369,22 @@ impl FeishuDocsClient {
     /// Returns a vec of (block_id, block_type) tuples.
     pub async fn get_document_blocks(&self, document_id: &str) -> Result<Vec<(String, i64)>> {
         let token = self.get_token().await?;
-        let url = format!("{FEISHU_BASE_URL}/docx/v1/documents/{document_id}/blocks");
+        let url = format!("{}/docx/v1/documents/{document_id}/blocks", self.base_url);
         let resp = self
             .send_with_retry(|| self.http.get(&url).bearer_auth(&token))
             .await?;
         let status = resp.status();
         let data: serde_json::Value = resp.json().await?;
         if !status.is_success() {
-            bail!("Feishu get_document_blocks failed: status={status}, body={data}");
+            bail!(
+                "Feishu get_document_blocks failed: status={status}, body={}",
+                redact_json_for_log(&data)
+            );
         }
         let code = data.get("code").and_then(|c| c.as_i64()).unwrap_or(-1);
         if code != 0 {
             let msg = data.get("msg").and_then(|m| m.as_str()).unwrap_or("unknown");
-            bail!("Feishu get_document_blocks error: {msg}");
+            bail!("Feishu get_document_blocks error: {}", redact_log_body(msg));
         }
         let items = data.pointer("/data/items").and_then(|v| v.as_array());
         let blocks = items
@@ -298,10 +400,97 @@ impl FeishuDocsClient {
             .unwrap_or_default();
         Ok(blocks)
     }
+    /// GET /docx/v1/documents/{id}/blocks (all pages) — every block of the
+    /// document, flattened.
+    pub async fn list_blocks(&self, document_id: &str) -> Result<Vec<serde_json::Value>> {
+        let token = self.get_token().await?;
+        let mut items: Vec<serde_json::Value> = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let page_query = page_token
+                .as_deref()
+                .map(|pt| format!("&page_token={pt}"))
+                .unwrap_or_default();
+            let url = format!(
+                "{}/docx/v1/documents/{document_id}/blocks?page_size=500{page_query}",
+                self.base_url
+            );
+            let resp = self
+                .send_with_retry(|| self.http.get(&url).bearer_auth(&token))
+                .await?;
+            let status = resp.status();
+            let data: serde_json::Value = resp.json().await?;
+            ensure_feishu_ok("list_blocks", status, &data)?;
+            if let Some(page) = data.pointer("/data/items").and_then(|v| v.as_array()) {
+                items.extend(page.iter().cloned());
+            }
+            page_token = data
+                .pointer("/data/page_token")
+                .and_then(|v| v.as_str())
+                .filter(|_| data.pointer("/data/has_more") == Some(&serde_json::Value::Bool(true)))
+                .map(str::to_string);
+            if page_token.is_none() {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    /// POST /docx/v1/documents/{id}/blocks/{id}/descendant — insert `blocks`
+    /// (with their nested children and table cells) at `index` under the page
+    /// block.
+    pub async fn insert_blocks(
+        &self,
+        document_id: &str,
+        index: usize,
+        blocks: &[FeishuBlock],
+    ) -> Result<()> {
+        let token = self.get_token().await?;
+        let url = format!(
+            "{}/docx/v1/documents/{document_id}/blocks/{document_id}/descendant",
+            self.base_url
+        );
+        for (chunk_no, chunk) in blocks.chunks(MAX_CHILDREN_PER_INSERT).enumerate() {
+            let (children_id, descendants) = to_descendants(chunk);
+            let body = serde_json::json!({
+                "children_id": children_id,
+                "descendants": descendants,
+                "index": index + chunk_no * MAX_CHILDREN_PER_INSERT,
+            });
+            let resp = self
+                .send_with_retry(|| self.http.post(&url).bearer_auth(&token).json(&body))
+                .await?;
+            let status = resp.status();
+            let data: serde_json::Value = resp.json().await?;
+            ensure_feishu_ok("insert_blocks", status, &data)?;
+        }
+        Ok(())
+    }
+
+    /// DELETE /docx/v1/documents/{id}/blocks/{id}/children/batch_delete —
+    /// remove the page block's children in `[start, end)`.
+    pub async fn delete_blocks(&self, document_id: &str, start: usize, end: usize) -> Result<()> {
+        if start >= end {
+            return Ok(());
+        }
+        let token = self.get_token().await?;
+        let url = format!(
+            "{}/docx/v1/documents/{document_id}/blocks/{document_id}/children/batch_delete",
+            self.base_url
+        );
+        let body = serde_json::json!({ "start_index": start, "end_index": end });
+        let resp = self
+            .send_with_retry(|| self.http.delete(&url).bearer_auth(&token).json(&body))
+            .await?;
+        let status = resp.status();
+        let data: serde_json::Value = resp.json().await?;
+        ensure_feishu_ok("delete_blocks", status, &data)
+    }
+
     /// POST /docx/v1/documents — create a new document.
     pub async fn create_document(&self, title: &str) -> Result<String> {
         let token = self.get_token().await?;
-        let url = format!("{FEISHU_BASE_URL}/docx/v1/documents");
+        let url = format!("{}/docx/v1/documents", self.base_url);
         let body = serde_json::json!({ "title": title });
         let resp = self
             .send_with_retry(|| self.http.post(&url).bearer_auth(&token).json(&body))
@@ -309,7 +498,10 @@ impl FeishuDocsClient {
         let status = resp.status();
         let data: serde_json::Value = resp.json().await?;
         if !status.is_success() {
-            bail!("Feishu create_document failed: status={status}, body={data}");
+            bail!(
+                "Feishu create_document failed: status={status}, body={}",
+                redact_json_for_log(&data)
+            );
         }
         let code = data.get("code").and_then(|c| c.as_i64()).unwrap_or(-1);
         if code != 0 {
@@ -317,7 +509,7 @@ impl FeishuDocsClient {
                 .get("msg")
                 .and_then(|m| m.as_str())
                 .unwrap_or("unknown");
-            bail!("Feishu create_document error: {msg}");
+            bail!("Feishu create_document error: {}", redact_log_body(msg));
         }
         let doc_id = data
             .pointer("/data/document/document_id")
@@ -336,7 +528,8 @@ impl FeishuDocsClient {
     ) -> Result<()> {
         let token = self.get_token().await?;
         let url = format!(
-            "{FEISHU_BASE_URL}/drive/v1/permissions/{document_id}/members?type=docx&need_notification=false"
+            "{}/drive/v1/permissions/{document_id}/members?type=docx&need_notification=false",
+            self.base_url
         );
         let body = serde_json::json!({
             "member_type": "openid",
@@ -350,7 +543,10 @@ impl FeishuDocsClient {
         let status = resp.status();
         let data: serde_json::Value = resp.json().await?;
         if !status.is_success() {
-            bail!("Feishu add_permission_member failed: status={status}, body={data}");
+            bail!(
+                "Feishu add_permission_member failed: status={status}, body={}",
+                redact_json_for_log(&data)
+            );
         }
         let code = data.get("code").and_then(|c| c.as_i64()).unwrap_or(-1);
         if code != 0 {
@@ -358,7 +554,10 @@ impl FeishuDocsClient {
                 .get("msg")
                 .and_then(|m| m.as_str())
                 .unwrap_or("unknown");
-            bail!("Feishu add_permission_member error: {msg}");
+            bail!(
+                "Feishu add_permission_member error: {}",
+                redact_log_body(msg)
+            );
         }
         Ok(())
     }
@@ -370,9 +569,7 @@ impl FeishuDocsClient {
         card_json: &str,
     ) -> Result<()> {
         let token = self.get_token().await?;
-        let url = format!(
-            "{FEISHU_BASE_URL}/im/v1/messages?receive_id_type=open_id"
-        );
+        let url = format!("{}/im/v1/messages?receive_id_type=open_id", self.base_url);
         let body = serde_json::json!({
             "receive_id": open_id,
             "msg_type": "interactive",
@@ -384,7 +581,10 @@ impl FeishuDocsClient {
         let status = resp.status();
         let data: serde_json::Value = resp.json().await?;
         if !status.is_success() {
-            bail!("Feishu send_message_card failed: status={status}, body={data}");
+            bail!(
+                "Feishu send_message_card failed: status={status}, body={}",
+                redact_json_for_log(&data)
+            );
         }
         let code = data.get("code").and_then(|c| c.as_i64()).unwrap_or(-1);
         if code != 0 {
@@ -392,7 +592,7 @@ impl FeishuDocsClient {
                 .get("msg")
                 .and_then(|m| m.as_str())
                 .unwrap_or("unknown");
-            bail!("Feishu send_message_card error: {msg}");
+            bail!("Feishu send_message_card error: {}", redact_log_body(msg));
         }
         Ok(())
     }
@@ -409,7 +609,8 @@ impl FeishuDocsClient {
     ) -> Result<()> {
         let token = self.get_token().await?;
         let url = format!(
-            "{FEISHU_BASE_URL}/drive/v1/files/{file_token}/subscribe?file_type={file_type}&event_type=file.edited_v1"
+            "{}/drive/v1/files/{file_token}/subscribe?file_type={file_type}&event_type=file.edited_v1",
+            self.base_url
         );
         let resp = self
             .send_with_retry(|| self.http.post(&url).bearer_auth(&token))
@@ -417,7 +618,10 @@ impl FeishuDocsClient {
         let status = resp.status();
         let data: serde_json::Value = resp.json().await?;
         if !status.is_success() {
-            bail!("Feishu subscribe_file_events failed: status={status}, body={data}");
+            bail!(
+                "Feishu subscribe_file_events failed: status={status}, body={}",
+                redact_json_for_log(&data)
+            );
         }
         let code = data.get("code").and_then(|c| c.as_i64()).unwrap_or(-1);
         if code != 0 {
@@ -425,7 +629,10 @@ impl FeishuDocsClient {
                 .get("msg")
                 .and_then(|m| m.as_str())
                 .unwrap_or("unknown");
-            bail!("Feishu subscribe_file_events error: {msg}");
+            bail!(
+                "Feishu subscribe_file_events error: {}",
+                redact_log_body(msg)
+            );
         }
         tracing::info!("docs_sync: subscribed to edit events for {file_token} (type={file_type})");
         Ok(())
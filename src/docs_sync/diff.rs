@@ -0,0 +1,559 @@
+//! Block-level diffing for docs_sync pushes.
+//!
+//! Each synced file maps to a sequence of top-level blocks in its Feishu
+//! document (see [`super::blocks`]). Instead of rewriting the whole document
+//! on every local change, [`diff_blocks`] computes the insert/update/delete
+//! operations between the blocks currently in the document and the new file
+//! content, and [`push_content`] applies only those through a
+//! [`DocsClient`]. Untouched blocks keep their ids, so concurrent
+//! editors keep their cursor positions.
+
+use super::blocks::{blocks_to_markdown, file_to_blocks, parse_document, FeishuBlock};
+use super::client::{BlockUpdate, DocsClient};
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// Upper bound on the LCS table size; larger diffs replace the whole changed
+/// region instead of searching for the minimal edit.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+/// Max block updates per `batch_update_blocks` request.
+const MAX_UPDATES_PER_BATCH: usize = 200;
+
+/// One edit to a document's top-level blocks.
+///
+/// Operations are returned in the order they must be applied: later positions
+/// first, so indices of earlier blocks stay valid throughout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockOp<T> {
+    /// Replace the existing block at `index` with `block`.
+    Update { index: usize, block: T },
+    /// Insert `blocks` starting at `index`.
+    Insert { index: usize, blocks: Vec<T> },
+    /// Delete the blocks in `[start, end)`.
+    Delete { start: usize, end: usize },
+}
+
+/// Compute the operations that turn `old` into `new`.
+///
+/// Unchanged blocks are matched via longest common subsequence; within each
+/// changed region, removed and added blocks are paired up as in-place updates
+/// and only the surplus is inserted or deleted.
+pub fn diff_blocks<T: PartialEq + Clone>(old: &[T], new: &[T]) -> Vec<BlockOp<T>> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let max_suffix = old.len().min(new.len()) - prefix;
+    let suffix = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    // Changed regions as (old_start, old_end, new_start, new_end).
+    let mut hunks = Vec::new();
+    let (mut old_pos, mut new_pos) = (0, 0);
+    let sentinel = (old_mid.len(), new_mid.len());
+    for (i, j) in lcs_pairs(old_mid, new_mid).into_iter().chain([sentinel]) {
+        if i > old_pos || j > new_pos {
+            hunks.push((prefix + old_pos, prefix + i, prefix + new_pos, prefix + j));
+        }
+        old_pos = i + 1;
+        new_pos = j + 1;
+    }
+
+    let mut ops = Vec::new();
+    for (old_start, old_end, new_start, new_end) in hunks.into_iter().rev() {
+        let paired = (old_end - old_start).min(new_end - new_start);
+        if old_end - old_start > paired {
+            ops.push(BlockOp::Delete {
+                start: old_start + paired,
+                end: old_end,
+            });
+        }
+        if new_end - new_start > paired {
+            ops.push(BlockOp::Insert {
+                index: old_start + paired,
+                blocks: new[new_start + paired..new_end].to_vec(),
+            });
+        }
+        for offset in (0..paired).rev() {
+            ops.push(BlockOp::Update {
+                index: old_start + offset,
+                block: new[new_start + offset].clone(),
+            });
+        }
+    }
+    ops
+}
+
+/// Index pairs of a longest common subsequence of `old` and `new`, ascending.
+fn lcs_pairs<T: PartialEq>(old: &[T], new: &[T]) -> Vec<(usize, usize)> {
+    let (n, m) = (old.len(), new.len());
+    if n == 0 || m == 0 || n.saturating_mul(m) > MAX_DIFF_CELLS {
+        return Vec::new();
+    }
+    // table[i][j] = LCS length of old[i..] and new[j..]
+    let width = m + 1;
+    let mut table = vec![0u32; (n + 1) * width];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i * width + j] = if old[i] == new[j] {
+                table[(i + 1) * width + j + 1] + 1
+            } else {
+                table[(i + 1) * width + j].max(table[i * width + j + 1])
+            };
+        }
+    }
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// Operations replacing all `existing` blocks with `new`.
+fn rewrite_ops<T>(existing: usize, new: Vec<T>) -> Vec<BlockOp<T>> {
+    let mut ops = Vec::new();
+    if existing > 0 {
+        ops.push(BlockOp::Delete {
+            start: 0,
+            end: existing,
+        });
+    }
+    if !new.is_empty() {
+        ops.push(BlockOp::Insert {
+            index: 0,
+            blocks: new,
+        });
+    }
+    ops
+}
+
+/// Sync `content` of the synced file `filename` into a document by applying
+/// only the changed blocks.
+///
+/// Changed blocks are updated in place when only their text differs, and
+/// replaced otherwise. Documents containing blocks with no markdown
+/// equivalent (e.g. images added in Feishu) cannot be diffed and are
+/// rewritten in full. Returns the number of operations applied.
+pub async fn push_content(
+    client: &impl DocsClient,
+    document_id: &str,
+    filename: &str,
+    content: &str,
+) -> Result<usize> {
+    let remote = parse_document(document_id, &client.list_blocks(document_id).await?);
+    let new = file_to_blocks(filename, content);
+    let ops = match remote
+        .iter()
+        .map(|r| r.block.clone())
+        .collect::<Option<Vec<_>>>()
+    {
+        Some(old) => diff_blocks(&old, &new),
+        None => {
+            tracing::info!(
+                "docs_sync: doc {document_id} has unsupported blocks, rewriting in full"
+            );
+            rewrite_ops(remote.len(), new)
+        }
+    };
+
+    let mut updates = Vec::new();
+    for op in &ops {
+        match op {
+            BlockOp::Update { index, block } => {
+                let existing = &remote[*index];
+                match existing
+                    .block
+                    .as_ref()
+                    .and_then(|old| old.text_update(block))
+                {
+                    Some(elements) => updates.push(BlockUpdate {
+                        block_id: existing.block_id.clone(),
+                        update_text_elements: elements,
+                    }),
+                    None => {
+                        client.delete_blocks(document_id, *index, index + 1).await?;
+                        client
+                            .insert_blocks(document_id, *index, std::slice::from_ref(block))
+                            .await?;
+                    }
+                }
+            }
+            BlockOp::Insert { index, blocks } => {
+                client.insert_blocks(document_id, *index, blocks).await?;
+            }
+            BlockOp::Delete { start, end } => {
+                client.delete_blocks(document_id, *start, *end).await?;
+            }
+        }
+    }
+    for batch in updates.chunks(MAX_UPDATES_PER_BATCH) {
+        client.batch_update_blocks(document_id, batch).await?;
+    }
+    tracing::debug!(
+        "docs_sync: applied {} block op(s) to doc {document_id}",
+        ops.len()
+    );
+    Ok(ops.len())
+}
+
+/// A document as read back for a pull.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteSnapshot {
+    /// The document rendered as file content.
+    pub content: String,
+    /// Content fingerprint of each top-level block, keyed by block id.
+    pub revisions: HashMap<String, String>,
+}
+
+/// Read a document back as file content: markdown for the supported block
+/// types (plain text blocks come back as one line each).
+///
+/// Falls back to the document's raw text when it contains blocks with no
+/// markdown equivalent.
+pub async fn fetch_content(client: &impl DocsClient, document_id: &str) -> Result<String> {
+    Ok(fetch_snapshot(client, document_id).await?.content)
+}
+
+/// Like [`fetch_content`], also returning the per-block revisions.
+pub async fn fetch_snapshot(client: &impl DocsClient, document_id: &str) -> Result<RemoteSnapshot> {
+    let remote = parse_document(document_id, &client.list_blocks(document_id).await?);
+    let revisions = remote
+        .iter()
+        .map(|r| (r.block_id.clone(), r.revision.clone()))
+        .collect();
+    let content = match remote
+        .into_iter()
+        .map(|r| r.block)
+        .collect::<Option<Vec<FeishuBlock>>>()
+    {
+        Some(blocks) => blocks_to_markdown(&blocks),
+        None => client.get_raw_content(document_id).await?,
+    };
+    Ok(RemoteSnapshot { content, revisions })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::client::FeishuDocsClient;
+    use super::*;
+
+    fn blocks(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    /// Apply ops the way the Feishu API would, to check they reproduce `new`.
+    fn apply(old: &[String], ops: &[BlockOp<String>]) -> Vec<String> {
+        let mut doc = old.to_vec();
+        for op in ops {
+            match op {
+                BlockOp::Update { index, block } => doc[*index] = block.clone(),
+                BlockOp::Insert { index, blocks } => {
+                    doc.splice(*index..*index, blocks.iter().cloned());
+                }
+                BlockOp::Delete { start, end } => {
+                    doc.drain(*start..*end);
+                }
+            }
+        }
+        doc
+    }
+
+    fn assert_roundtrip(before: &str, after: &str) -> Vec<BlockOp<String>> {
+        let (old, new) = (blocks(before), blocks(after));
+        let ops = diff_blocks(&old, &new);
+        assert_eq!(apply(&old, &ops), new, "ops: {ops:?}");
+        ops
+    }
+
+    #[test]
+    fn identical_content_needs_no_ops() {
+        let doc = "# Identity\n\nName: Claw\n";
+        assert!(assert_roundtrip(doc, doc).is_empty());
+    }
+
+    #[test]
+    fn edited_line_is_a_single_update() {
+        let ops = assert_roundtrip(
+            "# Identity\n\nName: Claw\nRole: helper\n",
+            "# Identity\n\nName: ZeroClaw\nRole: helper\n",
+        );
+        assert_eq!(
+            ops,
+            vec![BlockOp::Update {
+                index: 2,
+                block: "Name: ZeroClaw".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn appended_section_is_a_single_insert() {
+        let ops = assert_roundtrip(
+            "# Soul\n\n- kind\n",
+            "# Soul\n\n- kind\n- curious\n\n## Limits\n- no spam\n",
+        );
+        assert_eq!(
+            ops,
+            vec![BlockOp::Insert {
+                index: 3,
+                blocks: vec![
+                    "- curious".into(),
+                    String::new(),
+                    "## Limits".into(),
+                    "- no spam".into()
+                ]
+            }]
+        );
+    }
+
+    #[test]
+    fn removed_lines_are_a_single_delete() {
+        let ops = assert_roundtrip(
+            "# Tools\n\n- shell\n- browser\n- http\n\nEnd\n",
+            "# Tools\n\n- shell\n\nEnd\n",
+        );
+        assert_eq!(ops, vec![BlockOp::Delete { start: 3, end: 5 }]);
+    }
+
+    #[test]
+    fn scattered_edits_touch_only_changed_blocks() {
+        let before =
+            "# Config\n\n```toml\nmodel = \"a\"\ntemperature = 0.7\n```\n\nNotes:\n- one\n- two\n";
+        let after = "# Config\n\n```toml\nmodel = \"b\"\ntemperature = 0.7\nmax_tokens = 10\n```\n\nNotes:\n- two\n";
+        let ops = assert_roundtrip(before, after);
+        // model line updated, max_tokens inserted, "- one" deleted; nothing else.
+        assert_eq!(ops.len(), 3, "ops: {ops:?}");
+        assert!(ops.contains(&BlockOp::Update {
+            index: 3,
+            block: "model = \"b\"".into()
+        }));
+    }
+
+    #[test]
+    fn ops_are_ordered_from_the_end() {
+        let ops = assert_roundtrip("a\nb\nc\nd\ne\n", "A\nb\nc\nd\nE\nf\n");
+        let first_index = |op: &BlockOp<String>| match op {
+            BlockOp::Update { index, .. } | BlockOp::Insert { index, .. } => *index,
+            BlockOp::Delete { start, .. } => *start,
+        };
+        assert!(first_index(&ops[0]) > first_index(ops.last().unwrap()));
+    }
+
+    #[test]
+    fn rewrite_ops_replace_everything() {
+        let old = blocks("```\nlegacy code block\n```\n");
+        let new = blocks("fresh\nlines\n");
+        let ops = rewrite_ops(old.len(), new.clone());
+        assert_eq!(apply(&old, &ops), new);
+        assert!(rewrite_ops(0, Vec::<String>::new()).is_empty());
+    }
+
+    #[test]
+    fn rewrite_from_and_to_empty() {
+        assert_roundtrip("", "line one\nline two\n");
+        assert_roundtrip("line one\nline two\n", "");
+        assert_roundtrip("old\ncontent\n", "entirely\nnew\ntext\n");
+    }
+
+    async fn mock_doc_server(items: serde_json::Value) -> wiremock::MockServer {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let ok = || ResponseTemplate::new(200).set_body_json(serde_json::json!({ "code": 0 }));
+        Mock::given(method("POST"))
+            .and(path("/auth/v3/tenant_access_token/internal"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "code": 0,
+                "tenant_access_token": "t-test",
+                "expire": 7200
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/docx/v1/documents/doc_1/blocks"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "code": 0, "data": { "items": items } })),
+            )
+            .mount(&server)
+            .await;
+        for verb in ["POST", "PATCH", "DELETE"] {
+            Mock::given(method(verb))
+                .respond_with(ok())
+                .mount(&server)
+                .await;
+        }
+        server
+    }
+
+    fn text_block(id: &str, text: &str) -> serde_json::Value {
+        serde_json::json!({
+            "block_id": id,
+            "block_type": 2,
+            "parent_id": "doc_1",
+            "text": { "elements": [{ "text_run": { "content": text } }] }
+        })
+    }
+
+    async fn doc_requests(server: &wiremock::MockServer) -> Vec<(String, serde_json::Value)> {
+        server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|r| r.method.as_str() != "GET" && r.url.path().starts_with("/docx/"))
+            .map(|r| {
+                (
+                    format!("{} {}", r.method, r.url.path()),
+                    serde_json::from_slice(&r.body).unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn push_content_sends_only_changed_blocks() {
+        let server = mock_doc_server(serde_json::json!([
+            { "block_id": "doc_1", "block_type": 1, "children": ["b1", "b2", "b3"] },
+            text_block("b1", "a"),
+            text_block("b2", "b"),
+            text_block("b3", "c"),
+        ]))
+        .await;
+        let client =
+            FeishuDocsClient::new("app".into(), "secret".into()).with_base_url(server.uri());
+
+        let applied = push_content(&client, "doc_1", "config.toml", "a\nB\nc\nd\n")
+            .await
+            .unwrap();
+        assert_eq!(applied, 2);
+
+        let requests = doc_requests(&server).await;
+        assert_eq!(requests.len(), 2, "{requests:?}");
+        let (insert, body) = &requests[0];
+        assert_eq!(
+            insert,
+            "POST /docx/v1/documents/doc_1/blocks/doc_1/descendant"
+        );
+        assert_eq!(body["index"], 3);
+        assert_eq!(
+            body["descendants"][0]["text"]["elements"][0]["text_run"]["content"],
+            "d"
+        );
+        let (update, body) = &requests[1];
+        assert_eq!(update, "PATCH /docx/v1/documents/doc_1/blocks/batch_update");
+        assert_eq!(body["requests"][0]["block_id"], "b2");
+    }
+
+    #[tokio::test]
+    async fn push_content_updates_code_block_text_in_place() {
+        let server = mock_doc_server(serde_json::json!([
+            { "block_id": "doc_1", "block_type": 1, "children": ["h", "code"] },
+            {
+                "block_id": "h",
+                "block_type": 3,
+                "heading1": { "elements": [{ "text_run": { "content": "Config" } }] }
+            },
+            {
+                "block_id": "code",
+                "block_type": 14,
+                "code": {
+                    "elements": [{ "text_run": { "content": "a = 1" } }],
+                    "style": { "language": 75 }
+                }
+            },
+        ]))
+        .await;
+        let client =
+            FeishuDocsClient::new("app".into(), "secret".into()).with_base_url(server.uri());
+
+        push_content(
+            &client,
+            "doc_1",
+            "NOTES.md",
+            "# Config\n```toml\na = 2\n```\n",
+        )
+        .await
+        .unwrap();
+
+        let requests = doc_requests(&server).await;
+        assert_eq!(requests.len(), 1, "{requests:?}");
+        let body = &requests[0].1;
+        assert_eq!(body["requests"][0]["block_id"], "code");
+        assert_eq!(
+            body["requests"][0]["update_text_elements"]["elements"][0]["text_run"]["content"],
+            "a = 2"
+        );
+    }
+
+    #[tokio::test]
+    async fn push_content_replaces_block_whose_type_changed() {
+        let server = mock_doc_server(serde_json::json!([
+            { "block_id": "doc_1", "block_type": 1, "children": ["b1", "b2"] },
+            text_block("b1", "Steps"),
+            text_block("b2", "run it"),
+        ]))
+        .await;
+        let client =
+            FeishuDocsClient::new("app".into(), "secret".into()).with_base_url(server.uri());
+
+        push_content(&client, "doc_1", "SOUL.md", "## Steps\nrun it\n")
+            .await
+            .unwrap();
+
+        let requests = doc_requests(&server).await;
+        let calls: Vec<&str> = requests.iter().map(|(c, _)| c.as_str()).collect();
+        assert_eq!(
+            calls,
+            [
+                "DELETE /docx/v1/documents/doc_1/blocks/doc_1/children/batch_delete",
+                "POST /docx/v1/documents/doc_1/blocks/doc_1/descendant",
+            ]
+        );
+        assert_eq!(requests[0].1["start_index"], 0);
+        assert_eq!(requests[0].1["end_index"], 1);
+        assert_eq!(requests[1].1["index"], 0);
+        assert_eq!(requests[1].1["descendants"][0]["block_type"], 4);
+    }
+
+    #[tokio::test]
+    async fn push_content_rewrites_docs_with_unsupported_blocks() {
+        let server = mock_doc_server(serde_json::json!([
+            { "block_id": "doc_1", "block_type": 1, "children": ["img"] },
+            { "block_id": "img", "block_type": 27, "parent_id": "doc_1" },
+        ]))
+        .await;
+        let client =
+            FeishuDocsClient::new("app".into(), "secret".into()).with_base_url(server.uri());
+
+        push_content(&client, "doc_1", "SOUL.md", "x\ny\n")
+            .await
+            .unwrap();
+
+        let requests = doc_requests(&server).await;
+        let calls: Vec<&str> = requests.iter().map(|(c, _)| c.as_str()).collect();
+        assert_eq!(
+            calls,
+            [
+                "DELETE /docx/v1/documents/doc_1/blocks/doc_1/children/batch_delete",
+                "POST /docx/v1/documents/doc_1/blocks/doc_1/descendant",
+            ]
+        );
+        assert_eq!(requests[0].1["end_index"], 1);
+        assert_eq!(requests[1].1["children_id"].as_array().unwrap().len(), 2);
+    }
+}
@@ -3,21 +3,29 @@
 //! Enables syncing local config files (config.toml, IDENTITY.md, etc.)
 //! to/from a Feishu document. Gated behind `feishu-docs-sync` feature.
 
+pub mod blocks;
 pub mod client;
+pub mod diff;
+pub mod event_subscriber;
+pub mod status;
+pub mod store;
 pub mod sync;
 pub mod watcher;
-pub mod event_subscriber;
 pub mod worker;
 
-pub use client::{FeishuDocsClient, BlockUpdate};
+pub use client::{DocsClient, FeishuDocsClient};
+pub use event_subscriber::EventSubscriber;
+pub use status::sync_status;
+pub use store::{FsStateStore, StateStore};
 pub use sync::{sync_local_to_remote, sync_remote_to_local, validate_remote_config};
 pub use watcher::FileWatcher;
-pub use event_subscriber::EventSubscriber;
-pub use worker::run as run_worker;
+pub use worker::{force_resync, run as run_worker};
 
-use std::path::{Path, PathBuf};
-use std::collections::HashMap;
+use crate::config::SharePermission;
 use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 
 // ── DocsSyncSharer ──
 
@@ -28,63 +36,97 @@ struct SyncLockEntry {
 
 /// Shares docs_sync documents with Feishu users via permission API.
 /// Used by LarkChannel to auto-share on first user message.
-pub struct DocsSyncSharer {
-    client: FeishuDocsClient,
-    lock_path: PathBuf,
-    shared_users_path: PathBuf,
+pub struct DocsSyncSharer<C = FeishuDocsClient> {
+    client: C,
+    store: Arc<dyn StateStore>,
+    /// Permission granted to users documents are shared with.
+    permission: SharePermission,
+    /// Serializes read-modify-write of the shared-users and shared-docs state
+    /// so concurrent shares don't drop each other's entries.
+    state_lock: Mutex<()>,
 }
 
-fn load_shared_users(path: &Path) -> Vec<String> {
-    std::fs::read_to_string(path)
-        .ok()
-        .and_then(|s| serde_json::from_str(&s).ok())
-        .unwrap_or_default()
+fn load_shared_users(store: &dyn StateStore) -> Vec<String> {
+    store::load_json(store, store::SHARED_USERS_KEY)
 }
 
-fn save_shared_users(path: &Path, users: &[String]) -> Result<()> {
-    let json = serde_json::to_string_pretty(users)?;
-    std::fs::write(path, json)?;
-    Ok(())
+fn save_shared_users(store: &dyn StateStore, users: &[String]) -> Result<()> {
+    store::save_json(store, store::SHARED_USERS_KEY, users)
 }
 
+/// Documents already shared with users whose share is still incomplete.
+type SharedDocs = HashMap<String, HashSet<String>>;
+
 impl DocsSyncSharer {
     /// Create a new sharer from Feishu app credentials and the lock file path.
     pub fn new(app_id: String, app_secret: String, lock_path: PathBuf) -> Self {
-        let client = FeishuDocsClient::new(app_id, app_secret);
-        let shared_users_path = lock_path
-            .parent()
-            .map_or_else(|| PathBuf::from("."), PathBuf::from)
-            .join("docs_sync_shared_users.json");
+        Self::with_client(FeishuDocsClient::new(app_id, app_secret), lock_path)
+    }
+}
+
+impl<C: DocsClient> DocsSyncSharer<C> {
+    /// Create a sharer around an existing client (sharing its token cache).
+    /// State lives in files next to `lock_path`.
+    pub fn with_client(client: C, lock_path: PathBuf) -> Self {
+        Self::with_store(client, Arc::new(FsStateStore::beside(&lock_path)))
+    }
+
+    /// Create a sharer whose lock and shared-users state live in `store`.
+    pub fn with_store(client: C, store: Arc<dyn StateStore>) -> Self {
         Self {
             client,
-            lock_path,
-            shared_users_path,
+            store,
+            permission: SharePermission::default(),
+            state_lock: Mutex::new(()),
         }
     }
 
+    /// Grant `permission` instead of the default edit access.
+    pub fn with_permission(mut self, permission: SharePermission) -> Self {
+        self.permission = permission;
+        self
+    }
+
     /// Share all synced documents with a Feishu user by open_id.
-    /// Skips if the user was already shared with. Logs and continues on per-doc failures.
+    /// Skips if the user was already shared with. Logs and continues on per-doc
+    /// failures; documents that succeeded are remembered so a retry only
+    /// shares (and sends cards for) the ones that failed.
     pub async fn share_all_docs_with(&self, open_id: &str) -> Result<()> {
         // Check if user already has shared docs
-        let mut users = load_shared_users(&self.shared_users_path);
-        if users.iter().any(|u| u == open_id) {
+        if load_shared_users(self.store.as_ref())
+            .iter()
+            .any(|u| u == open_id)
+        {
             tracing::debug!("docs_sync: user {open_id} already has shared docs, skipping");
             return Ok(());
         }
 
-        // Load lock file to get doc_ids
-        let lock: HashMap<String, SyncLockEntry> = std::fs::read_to_string(&self.lock_path)
-            .ok()
-            .and_then(|s| serde_json::from_str(&s).ok())
-            .unwrap_or_default();
+        // Load lock to get doc_ids
+        let lock: HashMap<String, SyncLockEntry> =
+            store::load_json(self.store.as_ref(), store::LOCK_KEY);
 
-        let doc_count = lock.len();
-        tracing::info!("docs_sync: sharing {doc_count} docs with user {open_id}");
+        let already_shared =
+            store::load_json::<SharedDocs>(self.store.as_ref(), store::SHARED_DOCS_KEY)
+                .remove(open_id)
+                .unwrap_or_default();
+        let pending: Vec<_> = lock
+            .iter()
+            .filter(|(_, entry)| !already_shared.contains(&entry.doc_id))
+            .collect();
+        tracing::info!(
+            "docs_sync: sharing {} of {} docs with user {open_id}",
+            pending.len(),
+            lock.len()
+        );
 
         let mut had_failure = false;
-        for (filename, entry) in &lock {
+        for (filename, entry) in pending {
             let doc_id = &entry.doc_id;
-            if let Err(e) = self.client.add_permission_member(doc_id, open_id, "edit").await {
+            if let Err(e) = self
+                .client
+                .add_permission_member(doc_id, open_id, self.permission.as_str())
+                .await
+            {
                 tracing::warn!("docs_sync: failed to share doc {doc_id} with {open_id}: {e}");
                 had_failure = true;
                 continue;
@@ -93,30 +135,74 @@ impl DocsSyncSharer {
             if let Err(e) = self.client.send_message_card(open_id, &card).await {
                 tracing::warn!("docs_sync: failed to send share card for {filename} to {open_id}: {e}");
                 had_failure = true;
+                continue;
             }
+            self.record_shared_doc(open_id, doc_id)?;
         }
 
         // Only record user as shared if all docs succeeded — allows retry on next message
         if had_failure {
             tracing::warn!("docs_sync: some docs failed to share with {open_id}, will retry next message");
         } else {
-            users.push(open_id.to_owned());
-            save_shared_users(&self.shared_users_path, &users)?;
+            self.record_shared_user(open_id)?;
             tracing::info!("docs_sync: shared all docs with user {open_id}");
         }
         Ok(())
     }
 
+    /// Append `open_id` to the shared-users list, re-reading it under the lock
+    /// so entries recorded by concurrent shares are kept.
+    fn record_shared_user(&self, open_id: &str) -> Result<()> {
+        let _guard = self
+            .state_lock
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let mut users = load_shared_users(self.store.as_ref());
+        if !users.iter().any(|u| u == open_id) {
+            users.push(open_id.to_owned());
+            save_shared_users(self.store.as_ref(), &users)?;
+        }
+        // Per-doc progress is only needed while the share is incomplete.
+        let mut shared_docs: SharedDocs =
+            store::load_json(self.store.as_ref(), store::SHARED_DOCS_KEY);
+        if shared_docs.remove(open_id).is_some() {
+            store::save_json(self.store.as_ref(), store::SHARED_DOCS_KEY, &shared_docs)?;
+        }
+        Ok(())
+    }
+
+    /// Remember that `doc_id` was shared with `open_id` (permission and card).
+    fn record_shared_doc(&self, open_id: &str, doc_id: &str) -> Result<()> {
+        let _guard = self
+            .state_lock
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let mut shared_docs: SharedDocs =
+            store::load_json(self.store.as_ref(), store::SHARED_DOCS_KEY);
+        if shared_docs
+            .entry(open_id.to_owned())
+            .or_default()
+            .insert(doc_id.to_owned())
+        {
+            store::save_json(self.store.as_ref(), store::SHARED_DOCS_KEY, &shared_docs)?;
+        }
+        Ok(())
+    }
+
     /// Share a single document with all previously shared users.
     /// Called when a new Feishu document is created for a newly synced local file.
     pub async fn share_single_doc_with_all(&self, filename: &str, doc_id: &str) {
-        let users = load_shared_users(&self.shared_users_path);
+        let users = load_shared_users(self.store.as_ref());
         if users.is_empty() {
             return;
         }
         tracing::info!("docs_sync: sharing new doc {doc_id} ({filename}) with {} existing users", users.len());
         for open_id in &users {
-            if let Err(e) = self.client.add_permission_member(doc_id, open_id, "edit").await {
+            if let Err(e) = self
+                .client
+                .add_permission_member(doc_id, open_id, self.permission.as_str())
+                .await
+            {
                 tracing::warn!("docs_sync: failed to share doc {doc_id} with {open_id}: {e}");
                 continue;
             }
@@ -148,3 +234,298 @@ fn build_share_card(filename: &str, doc_id: &str) -> String {
     });
     card.to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::client::BlockUpdate;
+    use super::*;
+    use wiremock::matchers::{method, path, path_regex};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Records sharing calls; permission grants for `failing_docs` fail.
+    #[derive(Default)]
+    struct MockDocsClient {
+        calls: Mutex<Vec<String>>,
+        failing_docs: Mutex<HashSet<String>>,
+    }
+
+    impl MockDocsClient {
+        fn take_calls(&self) -> Vec<String> {
+            std::mem::take(&mut *self.calls.lock().unwrap())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl DocsClient for MockDocsClient {
+        async fn list_blocks(&self, _document_id: &str) -> Result<Vec<serde_json::Value>> {
+            anyhow::bail!("not used by the sharer")
+        }
+
+        async fn get_raw_content(&self, _document_id: &str) -> Result<String> {
+            anyhow::bail!("not used by the sharer")
+        }
+
+        async fn batch_update_blocks(
+            &self,
+            _document_id: &str,
+            _updates: &[BlockUpdate],
+        ) -> Result<()> {
+            anyhow::bail!("not used by the sharer")
+        }
+
+        async fn insert_blocks(
+            &self,
+            _document_id: &str,
+            _index: usize,
+            _blocks: &[blocks::FeishuBlock],
+        ) -> Result<()> {
+            anyhow::bail!("not used by the sharer")
+        }
+
+        async fn delete_blocks(
+            &self,
+            _document_id: &str,
+            _start: usize,
+            _end: usize,
+        ) -> Result<()> {
+            anyhow::bail!("not used by the sharer")
+        }
+
+        async fn add_permission_member(
+            &self,
+            document_id: &str,
+            open_id: &str,
+            perm: &str,
+        ) -> Result<()> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("permission {document_id} {open_id} {perm}"));
+            if self.failing_docs.lock().unwrap().contains(document_id) {
+                anyhow::bail!("permission denied");
+            }
+            Ok(())
+        }
+
+        async fn send_message_card(&self, open_id: &str, card_json: &str) -> Result<()> {
+            let doc = if card_json.contains("doc_1") {
+                "doc_1"
+            } else {
+                "doc_2"
+            };
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("card {doc} {open_id}"));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn share_all_docs_with_calls_client_once_per_doc() {
+        let store: Arc<dyn StateStore> = Arc::new(store::MemoryStateStore::default());
+        store::save_json(
+            store.as_ref(),
+            store::LOCK_KEY,
+            &serde_json::json!({
+                "SOUL.md": { "doc_id": "doc_1", "hash": "a" },
+                "IDENTITY.md": { "doc_id": "doc_2", "hash": "b" }
+            }),
+        )
+        .unwrap();
+        let client = MockDocsClient::default();
+        client.failing_docs.lock().unwrap().insert("doc_2".into());
+        let sharer = DocsSyncSharer::with_store(client, Arc::clone(&store));
+
+        sharer.share_all_docs_with("ou_alice").await.unwrap();
+        let mut calls = sharer.client.take_calls();
+        calls.sort();
+        assert_eq!(
+            calls,
+            [
+                "card doc_1 ou_alice",
+                "permission doc_1 ou_alice edit",
+                "permission doc_2 ou_alice edit",
+            ]
+        );
+        assert!(load_shared_users(store.as_ref()).is_empty());
+
+        sharer.client.failing_docs.lock().unwrap().clear();
+        sharer.share_all_docs_with("ou_alice").await.unwrap();
+        assert_eq!(
+            sharer.client.take_calls(),
+            ["permission doc_2 ou_alice edit", "card doc_2 ou_alice"]
+        );
+        assert_eq!(load_shared_users(store.as_ref()), ["ou_alice"]);
+
+        sharer.share_all_docs_with("ou_alice").await.unwrap();
+        assert!(sharer.client.take_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn retry_only_shares_previously_failed_docs() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/auth/v3/tenant_access_token/internal"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "code": 0,
+                "tenant_access_token": "t-test",
+                "expire": 7200
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/drive/v1/permissions/doc_2/members"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "code": 1_063_001, "msg": "denied" })),
+            )
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/(drive/v1/permissions/|im/v1/messages)"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "code": 0 })),
+            )
+            .mount(&server)
+            .await;
+
+        let store: Arc<dyn StateStore> = Arc::new(store::MemoryStateStore::default());
+        store::save_json(
+            store.as_ref(),
+            store::LOCK_KEY,
+            &serde_json::json!({
+                "SOUL.md": { "doc_id": "doc_1", "hash": "a" },
+                "IDENTITY.md": { "doc_id": "doc_2", "hash": "b" }
+            }),
+        )
+        .unwrap();
+        let client =
+            FeishuDocsClient::new("app".into(), "secret".into()).with_base_url(server.uri());
+        let sharer = DocsSyncSharer::with_store(client, Arc::clone(&store));
+        let posts_to = |prefix: &'static str| {
+            let server = &server;
+            async move {
+                server
+                    .received_requests()
+                    .await
+                    .unwrap()
+                    .iter()
+                    .filter(|r| r.url.path().starts_with(prefix))
+                    .count()
+            }
+        };
+
+        sharer.share_all_docs_with("ou_alice").await.unwrap();
+        assert!(load_shared_users(store.as_ref()).is_empty());
+        let progress: SharedDocs = store::load_json(store.as_ref(), store::SHARED_DOCS_KEY);
+        assert_eq!(progress["ou_alice"], HashSet::from(["doc_1".to_string()]));
+
+        sharer.share_all_docs_with("ou_alice").await.unwrap();
+        assert_eq!(posts_to("/drive/v1/permissions/doc_1/").await, 1);
+        assert_eq!(posts_to("/drive/v1/permissions/doc_2/").await, 2);
+        assert_eq!(posts_to("/im/v1/messages").await, 2);
+        assert_eq!(load_shared_users(store.as_ref()), ["ou_alice"]);
+        let progress: SharedDocs = store::load_json(store.as_ref(), store::SHARED_DOCS_KEY);
+        assert!(progress.is_empty());
+    }
+
+    #[tokio::test]
+    async fn configured_permission_is_sent_to_feishu() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/auth/v3/tenant_access_token/internal"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "code": 0,
+                "tenant_access_token": "t-test",
+                "expire": 7200
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/(drive/v1/permissions/|im/v1/messages)"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "code": 0 })),
+            )
+            .mount(&server)
+            .await;
+
+        let store: Arc<dyn StateStore> = Arc::new(store::MemoryStateStore::default());
+        store::save_json(
+            store.as_ref(),
+            store::LOCK_KEY,
+            &serde_json::json!({ "SOUL.md": { "doc_id": "doc_1", "hash": "a" } }),
+        )
+        .unwrap();
+        let client =
+            FeishuDocsClient::new("app".into(), "secret".into()).with_base_url(server.uri());
+        let sharer = DocsSyncSharer::with_store(client, Arc::clone(&store))
+            .with_permission(SharePermission::View);
+
+        sharer.share_all_docs_with("ou_alice").await.unwrap();
+        sharer.share_single_doc_with_all("USER.md", "doc_2").await;
+
+        let perms: Vec<serde_json::Value> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|r| r.url.path().starts_with("/drive/v1/permissions/"))
+            .map(|r| serde_json::from_slice::<serde_json::Value>(&r.body).unwrap()["perm"].clone())
+            .collect();
+        assert_eq!(perms, ["view", "view"]);
+    }
+
+    #[tokio::test]
+    async fn concurrent_shares_record_every_user() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/auth/v3/tenant_access_token/internal"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "code": 0,
+                "tenant_access_token": "t-test",
+                "expire": 7200
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/(drive/v1/permissions/|im/v1/messages)"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_delay(std::time::Duration::from_millis(20))
+                    .set_body_json(serde_json::json!({ "code": 0 })),
+            )
+            .mount(&server)
+            .await;
+
+        let store: Arc<dyn StateStore> = Arc::new(store::MemoryStateStore::default());
+        store::save_json(
+            store.as_ref(),
+            store::LOCK_KEY,
+            &serde_json::json!({ "SOUL.md": { "doc_id": "doc_1", "hash": "abc" } }),
+        )
+        .unwrap();
+        let client =
+            FeishuDocsClient::new("app".into(), "secret".into()).with_base_url(server.uri());
+        let sharer = Arc::new(DocsSyncSharer::with_store(client, Arc::clone(&store)));
+
+        let users: Vec<String> = (0..8).map(|i| format!("ou_user{i}")).collect();
+        let tasks: Vec<_> = users
+            .iter()
+            .map(|open_id| {
+                let sharer = Arc::clone(&sharer);
+                let open_id = open_id.clone();
+                tokio::spawn(async move { sharer.share_all_docs_with(&open_id).await })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        let mut recorded = load_shared_users(store.as_ref());
+        recorded.sort();
+        assert_eq!(recorded, users);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,128 @@
+//! Read-only report of docs_sync state for operators.
+//!
+//! Built from the sync lock and the dead-letter list in the [`StateStore`],
+//! so it works without Feishu credentials and while the worker is running.
+
+use super::store::{self, StateStore, DEAD_LETTERS_KEY, LOCK_KEY};
+use crate::config::Config;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Snapshot of what docs_sync is tracking.
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SyncStatus {
+    /// Files with a Feishu document, sorted by filename.
+    pub files: Vec<TrackedFile>,
+    /// Items that failed for good and were set aside.
+    pub dead_letters: Vec<DeadLetter>,
+}
+
+/// A local file mapped to a Feishu document.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct TrackedFile {
+    pub filename: String,
+    pub doc_id: String,
+    /// `None` when the lock entry predates sync-time tracking.
+    pub last_synced_at: Option<DateTime<Utc>>,
+}
+
+/// A sync item that failed for good.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DeadLetter {
+    pub filename: String,
+    #[serde(default)]
+    pub doc_id: Option<String>,
+    pub error: String,
+    #[serde(default)]
+    pub failed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(serde::Deserialize)]
+struct StatusLockEntry {
+    doc_id: String,
+    #[serde(default)]
+    synced_at: Option<DateTime<Utc>>,
+}
+
+/// Status of the docs_sync state kept next to `config.toml`.
+pub fn sync_status(config: &Config) -> SyncStatus {
+    status_from_store(super::worker::state_store(config).as_ref())
+}
+
+/// Status of the docs_sync state in `store`.
+pub fn status_from_store(store: &dyn StateStore) -> SyncStatus {
+    let lock: HashMap<String, StatusLockEntry> = store::load_json(store, LOCK_KEY);
+    let mut files: Vec<TrackedFile> = lock
+        .into_iter()
+        .map(|(filename, entry)| TrackedFile {
+            filename,
+            doc_id: entry.doc_id,
+            last_synced_at: entry.synced_at,
+        })
+        .collect();
+    files.sort_by(|a, b| a.filename.cmp(&b.filename));
+    SyncStatus {
+        files,
+        dead_letters: store::load_json(store, DEAD_LETTERS_KEY),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docs_sync::store::MemoryStateStore;
+
+    #[test]
+    fn status_lists_tracked_files_and_dead_letters() {
+        let store = MemoryStateStore::default();
+        store
+            .save(
+                LOCK_KEY,
+                br#"{
+                    "SOUL.md": { "doc_id": "doc_2", "hash": "b", "synced_at": "2025-03-01T12:00:00Z" },
+                    "IDENTITY.md": { "doc_id": "doc_1", "hash": "a", "revisions": { "blk": "r1" } }
+                }"#,
+            )
+            .unwrap();
+        store
+            .save(
+                DEAD_LETTERS_KEY,
+                br#"[{ "filename": "TOOLS.md", "error": "create doc: 403" }]"#,
+            )
+            .unwrap();
+
+        let status = status_from_store(&store);
+        assert_eq!(
+            status.files,
+            [
+                TrackedFile {
+                    filename: "IDENTITY.md".into(),
+                    doc_id: "doc_1".into(),
+                    last_synced_at: None,
+                },
+                TrackedFile {
+                    filename: "SOUL.md".into(),
+                    doc_id: "doc_2".into(),
+                    last_synced_at: Some("2025-03-01T12:00:00Z".parse().unwrap()),
+                },
+            ]
+        );
+        assert_eq!(
+            status.dead_letters,
+            [DeadLetter {
+                filename: "TOOLS.md".into(),
+                doc_id: None,
+                error: "create doc: 403".into(),
+                failed_at: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn status_is_empty_without_state() {
+        assert_eq!(
+            status_from_store(&MemoryStateStore::default()),
+            SyncStatus::default()
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Storage backend for docs_sync state.
+//!
+//! The sync lock and the shared-users list are opaque keyed blobs behind
+//! [`StateStore`], so larger deployments can keep them in e.g. sqlite or
+//! redis instead of next to `config.toml`. [`FsStateStore`] is the default;
+//! [`MemoryStateStore`] keeps everything in-process.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Key of the sync lock (`{filename: {doc_id, hash, revisions}}`).
+pub const LOCK_KEY: &str = "docs_sync.lock";
+/// Key of the list of open_ids every document has been shared with.
+pub const SHARED_USERS_KEY: &str = "docs_sync_shared_users.json";
+/// Key of `{open_id: [doc_id, ...]}` for users whose share is still
+/// incomplete, so retries skip documents that already went through.
+pub const SHARED_DOCS_KEY: &str = "docs_sync_shared_docs.json";
+/// Key of the list of [`super::status::DeadLetter`]s: sync items that failed
+/// for good and need an operator to look at them.
+pub const DEAD_LETTERS_KEY: &str = "docs_sync_dead_letters.json";
+
+/// Keyed blob storage for docs_sync state.
+pub trait StateStore: Send + Sync {
+    /// Read the blob stored under `key`, or `None` when there is none.
+    fn load(&self, key: &str) -> Result<Option<Vec<u8>>>;
+    /// Replace the blob stored under `key`.
+    fn save(&self, key: &str, data: &[u8]) -> Result<()>;
+    /// Delete the blob stored under `key`; missing keys are not an error.
+    fn remove(&self, key: &str) -> Result<()>;
+}
+
+/// Stores each key as a file of the same name in one directory.
+#[derive(Debug, Clone)]
+pub struct FsStateStore {
+    dir: PathBuf,
+}
+
+impl FsStateStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Store rooted at the directory containing `path`.
+    pub fn beside(path: &Path) -> Self {
+        Self::new(
+            path.parent()
+                .map_or_else(|| PathBuf::from("."), PathBuf::from),
+        )
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(key)
+    }
+}
+
+impl StateStore for FsStateStore {
+    fn load(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match std::fs::read(self.path(key)) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("docs_sync: failed to read {key}")),
+        }
+    }
+
+    fn save(&self, key: &str, data: &[u8]) -> Result<()> {
+        std::fs::write(self.path(key), data)
+            .with_context(|| format!("docs_sync: failed to write {key}"))
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        match std::fs::remove_file(self.path(key)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("docs_sync: failed to remove {key}"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// In-process store; state is lost when it is dropped.
+#[derive(Debug, Default)]
+pub struct MemoryStateStore {
+    entries: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl StateStore for MemoryStateStore {
+    fn load(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.entries.lock().unwrap().get(key).cloned())
+    }
+
+    fn save(&self, key: &str, data: &[u8]) -> Result<()> {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), data.to_vec());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        self.entries.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+/// Deserialize the JSON blob under `key`, falling back to `T::default()` when
+/// it is missing or unreadable.
+pub fn load_json<T: serde::de::DeserializeOwned + Default>(store: &dyn StateStore, key: &str) -> T {
+    store
+        .load(key)
+        .ok()
+        .flatten()
+        .and_then(|data| serde_json::from_slice(&data).ok())
+        .unwrap_or_default()
+}
+
+/// Serialize `value` as pretty JSON under `key`.
+pub fn save_json<T: serde::Serialize + ?Sized>(
+    store: &dyn StateStore,
+    key: &str,
+    value: &T,
+) -> Result<()> {
+    store.save(key, &serde_json::to_vec_pretty(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(store: &dyn StateStore) {
+        assert!(store.load(LOCK_KEY).unwrap().is_none());
+        save_json(store, SHARED_USERS_KEY, &["ou_alice", "ou_bob"]).unwrap();
+        let users: Vec<String> = load_json(store, SHARED_USERS_KEY);
+        assert_eq!(users, ["ou_alice", "ou_bob"]);
+
+        store.save(LOCK_KEY, b"not json").unwrap();
+        let lock: HashMap<String, String> = load_json(store, LOCK_KEY);
+        assert!(lock.is_empty());
+
+        store.remove(LOCK_KEY).unwrap();
+        store.remove(LOCK_KEY).unwrap();
+        assert!(store.load(LOCK_KEY).unwrap().is_none());
+    }
+
+    #[test]
+    fn memory_store_roundtrips_keyed_blobs() {
+        roundtrip(&MemoryStateStore::default());
+    }
+
+    #[test]
+    fn fs_store_keeps_each_key_in_its_own_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsStateStore::beside(&dir.path().join(LOCK_KEY));
+        roundtrip(&store);
+        assert!(dir.path().join(SHARED_USERS_KEY).is_file());
+    }
+}
@@ -210,6 +210,7 @@ mod tests {
         assert!(config.document_id.is_empty());
         assert!(config.document_ids.is_empty());
         assert_eq!(config.remote_mode, crate::config::schema::RemoteSyncMode::Polling);
+        assert_eq!(config.conflict_policy, crate::config::schema::ConflictPolicy::RemoteWins);
         assert!(config.app_id.is_none());
         assert!(config.app_secret.is_none());
         assert!(config.encrypt_key.is_none());
@@ -223,6 +224,17 @@ mod tests {
         assert_eq!(event, RemoteSyncMode::Event);
     }
     #[test]
+    fn test_conflict_policy_serde() {
+        use crate::config::schema::ConflictPolicy;
+        for (raw, policy) in [
+            ("\"remote_wins\"", ConflictPolicy::RemoteWins),
+            ("\"local_wins\"", ConflictPolicy::LocalWins),
+            ("\"keep_both\"", ConflictPolicy::KeepBoth),
+        ] {
+            assert_eq!(serde_json::from_str::<ConflictPolicy>(raw).unwrap(), policy);
+        }
+    }
+    #[test]
     fn test_docs_sync_config_with_event_mode() {
         let json = r#"{
             "enabled": true,
@@ -245,6 +257,7 @@ mod tests {
         let json = r#"{ "enabled": true, "document_id": "doxcn456" }"#;
         let config: crate::config::schema::DocsSyncConfig = serde_json::from_str(json).unwrap();
         assert_eq!(config.remote_mode, crate::config::schema::RemoteSyncMode::Polling);
+        assert_eq!(config.conflict_policy, crate::config::schema::ConflictPolicy::RemoteWins);
         assert!(config.app_id.is_none());
         assert!(config.app_secret.is_none());
         assert!(config.encrypt_key.is_none());
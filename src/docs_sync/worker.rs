@@ -5,17 +5,25 @@
 //! - Push: only uploads files that exist locally. Auto-creates Feishu doc if needed.
 //! - Pull: only updates files that already exist locally. Never creates new local files.
 //!
-//! Lock file (`docs_sync.lock`) tracks `{filename: {doc_id, hash}}`.
+//! Lock file (`docs_sync.lock`) tracks `{filename: {doc_id, hash, revisions, synced_at}}`.
+//! A pull compares the document's block revisions against the lock to tell
+//! remote edits apart, and a file edited on both sides since the last sync is
+//! resolved with the configured [`ConflictPolicy`].
 
-use crate::config::Config;
+use super::client::DocsClient;
+use super::diff::RemoteSnapshot;
+use super::store::{self, FsStateStore, StateStore, LOCK_KEY};
+use crate::config::{Config, ConflictPolicy};
 use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tokio::time::Duration;
-
-/// Lock file name, stored next to config.toml.
-const LOCK_FILENAME: &str = "docs_sync.lock";
+/// Health component updated after each push/pull, so Feishu reachability
+/// shows up in [`crate::health::overall_status`].
+const HEALTH_COMPONENT: &str = "docs_sync";
 
 // ── Lock entry ──
 
@@ -23,31 +31,44 @@ const LOCK_FILENAME: &str = "docs_sync.lock";
 struct LockEntry {
     doc_id: String,
     hash: String,
+    /// Block revisions of the document as of the last sync, keyed by block id
+    /// (see [`super::blocks::RemoteBlock::revision`]). Empty for entries
+    /// written before revisions were tracked.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    revisions: HashMap<String, String>,
+    /// When the file was last pushed or pulled. `None` for entries written
+    /// before sync times were tracked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    synced_at: Option<DateTime<Utc>>,
+}
+
+impl LockEntry {
+    /// Entry for a file that was just synced.
+    fn synced(doc_id: String, hash: String, revisions: HashMap<String, String>) -> Self {
+        Self {
+            doc_id,
+            hash,
+            revisions,
+            synced_at: Some(Utc::now()),
+        }
+    }
 }
 
-/// Lock file: `{ "IDENTITY.md": { "doc_id": "doxcn_abc", "hash": "sha256hex" }, ... }`
+/// Lock file: `{ "IDENTITY.md": { "doc_id": "doxcn_abc", "hash": "sha256hex", "revisions": { "blk": "sha256hex" }, "synced_at": "2025-01-01T00:00:00Z" }, ... }`
 type LockMap = HashMap<String, LockEntry>;
 // ── Helpers ──
 
-fn lock_file_path(config: &Config) -> PathBuf {
-    config
-        .config_path
-        .parent()
-        .map_or_else(|| PathBuf::from("."), PathBuf::from)
-        .join(LOCK_FILENAME)
+/// Default state store: files next to config.toml.
+pub(super) fn state_store(config: &Config) -> Arc<dyn StateStore> {
+    Arc::new(FsStateStore::beside(&config.config_path))
 }
 
-fn load_lock(path: &Path) -> LockMap {
-    std::fs::read_to_string(path)
-        .ok()
-        .and_then(|s| serde_json::from_str(&s).ok())
-        .unwrap_or_default()
+fn load_lock(store: &dyn StateStore) -> LockMap {
+    store::load_json(store, LOCK_KEY)
 }
 
-fn save_lock(path: &Path, lock: &LockMap) -> Result<()> {
-    let json = serde_json::to_string_pretty(lock)?;
-    std::fs::write(path, json)?;
-    Ok(())
+fn save_lock(store: &dyn StateStore, lock: &LockMap) -> Result<()> {
+    store::save_json(store, LOCK_KEY, lock)
 }
 
 fn sha256_hex(content: &str) -> String {
@@ -69,26 +90,195 @@ fn resolve_credentials(config: &Config) -> Result<(String, String)> {
         _ => bail!("docs_sync: no app_id/app_secret in [docs_sync] or [channels_config.feishu/lark]"),
     }
 }
-/// Push one local file to its Feishu document. Returns content hash.
+/// Push one local file to its Feishu document, touching only changed blocks.
+/// Returns the document's block revisions after the push.
 async fn push_single_file(
-    client: &super::FeishuDocsClient,
+    client: &impl DocsClient,
+    filename: &str,
     doc_id: &str,
     content: &str,
-) -> Result<()> {
-    let blocks = client.get_document_blocks(doc_id).await?;
-    let target = blocks.iter()
-        .find(|(_, bt)| *bt == 14)
-        .or_else(|| blocks.iter().find(|(_, bt)| *bt == 1))
-        .map(|(id, _)| id.clone())
-        .unwrap_or_else(|| doc_id.to_string());
-    let update = super::BlockUpdate {
-        block_id: target,
-        update_text_elements: serde_json::json!({
-            "elements": [{ "text_run": { "content": content } }]
-        }),
+) -> Result<HashMap<String, String>> {
+    super::diff::push_content(client, doc_id, filename, content).await?;
+    Ok(super::diff::fetch_snapshot(client, doc_id).await?.revisions)
+}
+
+/// How a pull should treat one file, judged against its lock entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PullAction {
+    /// The document has not changed since the last sync.
+    Skip,
+    /// Only the document changed: take the remote content.
+    Pull,
+    /// Both the document and the local file changed since the last sync.
+    Collision,
+}
+
+fn classify_pull(entry: &LockEntry, local: &str, remote: &RemoteSnapshot) -> PullAction {
+    let remote_hash = sha256_hex(&remote.content);
+    // Entries from before revision tracking can only compare content.
+    let remote_changed = if entry.revisions.is_empty() {
+        remote_hash != entry.hash
+    } else {
+        remote.revisions != entry.revisions
+    };
+    let local_hash = sha256_hex(local);
+    if !remote_changed {
+        PullAction::Skip
+    } else if local_hash == entry.hash || local_hash == remote_hash {
+        PullAction::Pull
+    } else {
+        PullAction::Collision
+    }
+}
+
+/// Pull remote edits of one existing local file, resolving a collision with
+/// local edits per `policy`. Returns the new lock entry, or `None` when the
+/// document has not changed since the last sync.
+async fn pull_single_file(
+    client: &impl DocsClient,
+    policy: ConflictPolicy,
+    workspace: &Path,
+    filename: &str,
+    entry: &LockEntry,
+) -> Result<Option<LockEntry>> {
+    let local_path = workspace.join(filename);
+    let local = std::fs::read_to_string(&local_path)?;
+    let remote = super::diff::fetch_snapshot(client, &entry.doc_id).await?;
+    let policy = match classify_pull(entry, &local, &remote) {
+        PullAction::Skip => return Ok(None),
+        PullAction::Pull => ConflictPolicy::RemoteWins,
+        PullAction::Collision => {
+            tracing::warn!(
+                "docs_sync: '{filename}' was edited both locally and in doc {} since the last sync; resolving with {policy:?}",
+                entry.doc_id
+            );
+            policy
+        }
     };
-    client.batch_update_blocks(doc_id, &[update]).await
+    let doc_id = entry.doc_id.clone();
+    match policy {
+        ConflictPolicy::RemoteWins => {
+            // Security: validate config.toml before writing
+            if filename == "config.toml" {
+                super::sync::validate_remote_config(&remote.content)?;
+            }
+            std::fs::write(&local_path, &remote.content)?;
+            tracing::info!("docs_sync: pulled '{filename}'");
+            Ok(Some(LockEntry::synced(
+                doc_id,
+                sha256_hex(&remote.content),
+                remote.revisions,
+            )))
+        }
+        ConflictPolicy::LocalWins => {
+            let revisions = push_single_file(client, filename, &doc_id, &local).await?;
+            tracing::info!("docs_sync: kept local '{filename}' and pushed it over remote edits");
+            Ok(Some(LockEntry::synced(
+                doc_id,
+                sha256_hex(&local),
+                revisions,
+            )))
+        }
+        ConflictPolicy::KeepBoth => {
+            let remote_path = workspace.join(format!("{filename}.remote"));
+            if remote_path.is_symlink() {
+                bail!("refusing to write symlink target '{filename}.remote'");
+            }
+            std::fs::write(&remote_path, &remote.content)?;
+            tracing::info!(
+                "docs_sync: saved remote '{filename}' as '{filename}.remote' for merging"
+            );
+            // Acknowledge the remote revisions so the collision is reported
+            // once; keeping the old hash lets the next local save push the merge.
+            Ok(Some(LockEntry::synced(
+                doc_id,
+                entry.hash.clone(),
+                remote.revisions,
+            )))
+        }
+    }
 }
+/// Outcome of a forced full resync.
+#[derive(Debug, Default)]
+pub struct ResyncReport {
+    /// `(filename, doc_id)` for every file pushed to Feishu.
+    pub pushed: Vec<(String, String)>,
+    /// Files that could not be created or pushed (details are logged).
+    pub failed: Vec<String>,
+}
+
+/// Force a full resync: clear the sync lock and push every local sync file to
+/// Feishu again, re-sharing each document with all previously shared users.
+///
+/// Files with an explicit `document_ids` mapping overwrite that document; all
+/// other files get a freshly created document. Refuses to run unless
+/// `confirmed` is set, since remote edits not yet pulled are lost.
+pub async fn force_resync(config: &Config, confirmed: bool) -> Result<ResyncReport> {
+    if !confirmed {
+        bail!("docs_sync: resync overwrites every remote document; re-run with --yes to confirm");
+    }
+    if !config.docs_sync.enabled {
+        bail!("docs_sync: not enabled");
+    }
+    let (app_id, app_secret) = resolve_credentials(config)?;
+    let client = super::FeishuDocsClient::new(app_id, app_secret);
+    resync_all(&client, config, state_store(config)).await
+}
+
+async fn resync_all(
+    client: &super::FeishuDocsClient,
+    config: &Config,
+    store: Arc<dyn StateStore>,
+) -> Result<ResyncReport> {
+    let ds = &config.docs_sync;
+    let sharer = super::DocsSyncSharer::with_store(client.clone(), Arc::clone(&store))
+        .with_permission(ds.share_permission);
+
+    // Drop the old lock up front so an interrupted resync never leaves stale doc ids.
+    store.remove(LOCK_KEY)?;
+    let mut lock = LockMap::new();
+    let mut report = ResyncReport::default();
+
+    for filename in &ds.sync_files {
+        let Some(content) = super::sync::read_single_file(filename, &config.workspace_dir)? else {
+            tracing::debug!("docs_sync: '{filename}' not found locally, skipping resync");
+            continue;
+        };
+        let doc_id = if let Some(id) = ds.document_ids.get(filename) {
+            id.clone()
+        } else {
+            match client
+                .create_document(&format!("ZeroClaw - {filename}"))
+                .await
+            {
+                Ok(id) => id,
+                Err(e) => {
+                    tracing::warn!("docs_sync: create doc for '{filename}' failed: {e}");
+                    report.failed.push(filename.clone());
+                    continue;
+                }
+            }
+        };
+        let revisions = match push_single_file(client, filename, &doc_id, &content).await {
+            Ok(revisions) => revisions,
+            Err(e) => {
+                tracing::warn!("docs_sync: resync push '{filename}' failed: {e}");
+                report.failed.push(filename.clone());
+                continue;
+            }
+        };
+        lock.insert(
+            filename.clone(),
+            LockEntry::synced(doc_id.clone(), sha256_hex(&content), revisions),
+        );
+        save_lock(store.as_ref(), &lock)?;
+        sharer.share_single_doc_with_all(filename, &doc_id).await;
+        tracing::info!("docs_sync: resynced '{filename}' to doc {doc_id}");
+        report.pushed.push((filename.clone(), doc_id));
+    }
+    Ok(report)
+}
+
 /// Main docs_sync daemon worker.
 ///
 /// Local files are source-of-truth for existence:
@@ -103,13 +293,15 @@ pub async fn run(
         bail!("docs_sync: not enabled");
     }
     let (app_id, app_secret) = resolve_credentials(&config)?;
-    let client = super::FeishuDocsClient::new(app_id.clone(), app_secret.clone());
-    let sharer = super::DocsSyncSharer::new(app_id.clone(), app_secret.clone(), lock_file_path(&config));
+    let client = super::FeishuDocsClient::new(app_id, app_secret);
+    let store = state_store(&config);
+    let sharer = super::DocsSyncSharer::with_store(client.clone(), Arc::clone(&store))
+        .with_permission(ds.share_permission);
     let workspace = config.workspace_dir.clone();
     let sync_files = ds.sync_files.clone();
     let sync_interval = Duration::from_secs(ds.sync_interval_secs.max(10));
-    let lock_path = lock_file_path(&config);
-    let mut lock: LockMap = load_lock(&lock_path);
+    let conflict_policy = ds.conflict_policy;
+    let mut lock: LockMap = load_lock(store.as_ref());
     // ── Step 1: Initial push — only for files that exist locally ──
     // For each sync_file: if local file exists, ensure it has a Feishu doc, push if hash differs.
     // Config document_ids provides explicit mapping; missing ones get auto-created.
@@ -145,16 +337,23 @@ pub async fn run(
             }
         }
         // Push
-        match push_single_file(&client, &doc_id, &content).await {
-            Ok(()) => {
+        match push_single_file(&client, filename, &doc_id, &content).await {
+            Ok(revisions) => {
                 tracing::info!("docs_sync: pushed '{filename}' to doc {doc_id}");
-                lock.insert(filename.clone(), LockEntry { doc_id: doc_id.clone(), hash });
-                let _ = save_lock(&lock_path, &lock);
+                crate::health::mark_component_ok(HEALTH_COMPONENT);
+                lock.insert(
+                    filename.clone(),
+                    LockEntry::synced(doc_id.clone(), hash, revisions),
+                );
+                let _ = save_lock(store.as_ref(), &lock);
                 if is_new_doc {
                     sharer.share_single_doc_with_all(filename, &doc_id).await;
                 }
             }
-            Err(e) => tracing::warn!("docs_sync: push '{filename}' failed: {e}"),
+            Err(e) => {
+                tracing::warn!("docs_sync: push '{filename}' failed: {e}");
+                crate::health::mark_component_error(HEALTH_COMPONENT, &e);
+            }
         }
     }
     // ── Step 1b: Subscribe to edit events for all synced documents ──
@@ -254,11 +453,12 @@ pub async fn run(
                         }
                     }
                 };
-                match push_single_file(&client, &doc_id, &content).await {
-                    Ok(()) => {
+                match push_single_file(&client, &filename, &doc_id, &content).await {
+                    Ok(revisions) => {
                         tracing::info!("docs_sync: pushed '{filename}'");
-                        lock.insert(filename.clone(), LockEntry { doc_id: doc_id.clone(), hash });
-                        let _ = save_lock(&lock_path, &lock);
+                        crate::health::mark_component_ok(HEALTH_COMPONENT);
+                        lock.insert(filename.clone(), LockEntry::synced(doc_id.clone(), hash, revisions));
+                        let _ = save_lock(store.as_ref(), &lock);
                         if is_new_doc {
                             sharer.share_single_doc_with_all(&filename, &doc_id).await;
                             // Subscribe to edit events so drive.file.edit_v1 fires
@@ -267,7 +467,10 @@ pub async fn run(
                             }
                         }
                     }
-                    Err(e) => tracing::warn!("docs_sync: push failed: {e}"),
+                    Err(e) => {
+                        tracing::warn!("docs_sync: push failed: {e}");
+                        crate::health::mark_component_error(HEALTH_COMPONENT, &e);
+                    }
                 }
             }
             // ── Remote change → pull (only existing local files) ──
@@ -287,36 +490,18 @@ pub async fn run(
                         tracing::debug!("docs_sync: '{filename}' not local, skipping pull");
                         continue;
                     }
-                    // Fetch remote content
-                    let raw = match client.get_raw_content(&entry.doc_id).await {
-                        Ok(r) => r,
+                    match pull_single_file(&client, conflict_policy, &workspace, filename, &entry).await {
+                        Ok(Some(updated)) => {
+                            lock.insert(filename.clone(), updated);
+                            let _ = save_lock(store.as_ref(), &lock);
+                            crate::health::mark_component_ok(HEALTH_COMPONENT);
+                        }
+                        Ok(None) => crate::health::mark_component_ok(HEALTH_COMPONENT),
                         Err(e) => {
                             tracing::warn!("docs_sync: pull '{filename}' failed: {e}");
-                            continue;
-                        }
-                    };
-                    let remote_hash = sha256_hex(&raw);
-                    // Skip if remote content matches our last known hash
-                    if remote_hash == entry.hash {
-                        continue;
-                    }
-                    // Security: validate config.toml before writing
-                    if filename == "config.toml" {
-                        if let Err(e) = super::sync::validate_remote_config(&raw) {
-                            tracing::warn!("docs_sync: pull '{filename}' blocked: {e}");
-                            continue;
+                            crate::health::mark_component_error(HEALTH_COMPONENT, &e);
                         }
                     }
-                    if let Err(e) = std::fs::write(&local_path, &raw) {
-                        tracing::warn!("docs_sync: write '{filename}' failed: {e}");
-                        continue;
-                    }
-                    tracing::info!("docs_sync: pulled '{filename}'");
-                    lock.insert(filename.clone(), LockEntry {
-                        doc_id: entry.doc_id.clone(),
-                        hash: remote_hash,
-                    });
-                    let _ = save_lock(&lock_path, &lock);
                 }
             }
             else => break,
@@ -324,3 +509,355 @@ pub async fn run(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path, path_regex};
+    use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+    /// Hands out `doc_1`, `doc_2`, ... for each create_document call.
+    struct CreateDocResponder(std::sync::atomic::AtomicUsize);
+
+    impl Respond for CreateDocResponder {
+        fn respond(&self, _: &Request) -> ResponseTemplate {
+            let n = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "code": 0,
+                "data": { "document": { "document_id": format!("doc_{n}") } }
+            }))
+        }
+    }
+
+    async fn mock_feishu_server() -> MockServer {
+        let server = MockServer::start().await;
+        let ok = || ResponseTemplate::new(200).set_body_json(serde_json::json!({ "code": 0 }));
+        Mock::given(method("POST"))
+            .and(path("/auth/v3/tenant_access_token/internal"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "code": 0,
+                "tenant_access_token": "t-test",
+                "expire": 7200
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/docx/v1/documents"))
+            .respond_with(CreateDocResponder(std::sync::atomic::AtomicUsize::new(0)))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/docx/v1/documents/[^/]+/blocks$"))
+            .respond_with(|req: &Request| {
+                // Every document holds a single outdated heading.
+                let doc_id = req.url.path().split('/').nth(4).unwrap_or_default();
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "code": 0,
+                    "data": { "items": [
+                        { "block_id": doc_id, "block_type": 1, "children": ["blk_heading"] },
+                        {
+                            "block_id": "blk_heading",
+                            "block_type": 3,
+                            "parent_id": doc_id,
+                            "heading1": { "elements": [{ "text_run": { "content": "stale" } }] }
+                        }
+                    ] }
+                }))
+            })
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .respond_with(ok())
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/drive/v1/permissions/"))
+            .respond_with(ok())
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/im/v1/messages"))
+            .respond_with(ok())
+            .mount(&server)
+            .await;
+        server
+    }
+
+    async fn requests_matching(server: &MockServer, verb: &str, prefix: &str) -> Vec<String> {
+        server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|r| r.method.as_str() == verb && r.url.path().starts_with(prefix))
+            .map(|r| r.url.path().to_string())
+            .collect()
+    }
+
+    fn resync_config(dir: &Path) -> Config {
+        let mut config = Config::default();
+        config.config_path = dir.join("config.toml");
+        config.workspace_dir = dir.join("workspace");
+        config.docs_sync.enabled = true;
+        config.docs_sync.sync_files =
+            vec!["IDENTITY.md".into(), "SOUL.md".into(), "MISSING.md".into()];
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        std::fs::write(config.workspace_dir.join("IDENTITY.md"), "# identity").unwrap();
+        std::fs::write(config.workspace_dir.join("SOUL.md"), "# soul").unwrap();
+        config
+    }
+
+    #[tokio::test]
+    async fn force_resync_requires_confirmation() {
+        let err = force_resync(&Config::default(), false).await.unwrap_err();
+        assert!(err.to_string().contains("--yes"));
+    }
+
+    #[tokio::test]
+    async fn resync_creates_and_shares_every_local_file() {
+        let server = mock_feishu_server().await;
+        let dir = tempfile::tempdir().unwrap();
+        let config = resync_config(dir.path());
+        let store = state_store(&config);
+        let mut stale = LockMap::new();
+        stale.insert(
+            "IDENTITY.md".into(),
+            LockEntry {
+                doc_id: "doc_stale".into(),
+                hash: "old".into(),
+                revisions: HashMap::new(),
+                synced_at: None,
+            },
+        );
+        save_lock(store.as_ref(), &stale).unwrap();
+        save_shared_users_for_test(dir.path(), &["ou_alice", "ou_bob"]);
+
+        let client = super::super::FeishuDocsClient::new("app".into(), "secret".into())
+            .with_base_url(server.uri());
+        let report = resync_all(&client, &config, Arc::clone(&store))
+            .await
+            .unwrap();
+
+        assert!(report.failed.is_empty());
+        let mut pushed: Vec<&str> = report.pushed.iter().map(|(f, _)| f.as_str()).collect();
+        pushed.sort_unstable();
+        assert_eq!(pushed, ["IDENTITY.md", "SOUL.md"]);
+        assert_eq!(
+            requests_matching(&server, "POST", "/docx/v1/documents")
+                .await
+                .len(),
+            2
+        );
+        assert_eq!(requests_matching(&server, "PATCH", "/docx/").await.len(), 2);
+
+        // Each new doc is shared with both existing users.
+        let shares = requests_matching(&server, "POST", "/drive/v1/permissions/").await;
+        assert_eq!(shares.len(), 4);
+        for (_, doc_id) in &report.pushed {
+            let doc_shares = shares
+                .iter()
+                .filter(|p| p.contains(doc_id.as_str()))
+                .count();
+            assert_eq!(
+                doc_shares, 2,
+                "doc {doc_id} should be shared with both users"
+            );
+        }
+
+        let lock = load_lock(store.as_ref());
+        assert_eq!(lock.len(), 2);
+        assert!(lock.values().all(|e| e.doc_id != "doc_stale"));
+        assert!(lock.values().all(|e| e.synced_at.is_some()));
+    }
+
+    #[tokio::test]
+    async fn resync_keeps_lock_and_shared_users_in_custom_store() {
+        let server = mock_feishu_server().await;
+        let dir = tempfile::tempdir().unwrap();
+        let config = resync_config(dir.path());
+        let store: Arc<dyn StateStore> = Arc::new(store::MemoryStateStore::default());
+        store::save_json(store.as_ref(), store::SHARED_USERS_KEY, &["ou_alice"]).unwrap();
+
+        let client = super::super::FeishuDocsClient::new("app".into(), "secret".into())
+            .with_base_url(server.uri());
+        let report = resync_all(&client, &config, Arc::clone(&store))
+            .await
+            .unwrap();
+
+        assert_eq!(report.pushed.len(), 2);
+        assert_eq!(
+            requests_matching(&server, "POST", "/drive/v1/permissions/")
+                .await
+                .len(),
+            2
+        );
+        let lock = load_lock(store.as_ref());
+        let mut synced: Vec<&str> = lock.keys().map(String::as_str).collect();
+        synced.sort_unstable();
+        assert_eq!(synced, ["IDENTITY.md", "SOUL.md"]);
+        assert!(!dir.path().join(LOCK_KEY).exists());
+
+        // A new user gets every document from the store's lock and is recorded there.
+        let sharer = super::super::DocsSyncSharer::with_store(client, Arc::clone(&store));
+        sharer.share_all_docs_with("ou_bob").await.unwrap();
+        let users: Vec<String> = store::load_json(store.as_ref(), store::SHARED_USERS_KEY);
+        assert_eq!(users, ["ou_alice", "ou_bob"]);
+    }
+
+    #[tokio::test]
+    async fn resync_overwrites_explicitly_mapped_doc() {
+        let server = mock_feishu_server().await;
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = resync_config(dir.path());
+        config
+            .docs_sync
+            .document_ids
+            .insert("SOUL.md".into(), "doc_pinned".into());
+
+        let client = super::super::FeishuDocsClient::new("app".into(), "secret".into())
+            .with_base_url(server.uri());
+        let report = resync_all(&client, &config, state_store(&config))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            requests_matching(&server, "POST", "/docx/v1/documents")
+                .await
+                .len(),
+            1
+        );
+        assert!(report
+            .pushed
+            .iter()
+            .any(|(f, id)| f == "SOUL.md" && id == "doc_pinned"));
+        assert_eq!(
+            requests_matching(&server, "PATCH", "/docx/v1/documents/doc_pinned/")
+                .await
+                .len(),
+            1
+        );
+    }
+
+    /// A synced `SOUL.md` whose lock entry predates a remote edit of the
+    /// document's only block (the mock serves a heading reading "stale").
+    fn pull_fixture(dir: &Path, local: &str) -> LockEntry {
+        std::fs::write(dir.join("SOUL.md"), local).unwrap();
+        LockEntry {
+            doc_id: "doc_1".into(),
+            hash: sha256_hex("# original\n"),
+            revisions: HashMap::from([("blk_heading".into(), "before-remote-edit".into())]),
+            synced_at: None,
+        }
+    }
+
+    async fn pull_with(
+        policy: ConflictPolicy,
+        local: &str,
+    ) -> (MockServer, tempfile::TempDir, Option<LockEntry>) {
+        let server = mock_feishu_server().await;
+        let dir = tempfile::tempdir().unwrap();
+        let entry = pull_fixture(dir.path(), local);
+        let client = super::super::FeishuDocsClient::new("app".into(), "secret".into())
+            .with_base_url(server.uri());
+        let updated = pull_single_file(&client, policy, dir.path(), "SOUL.md", &entry)
+            .await
+            .unwrap();
+        (server, dir, updated)
+    }
+
+    #[tokio::test]
+    async fn pull_takes_remote_edit_when_local_is_unchanged() {
+        let (_server, dir, updated) = pull_with(ConflictPolicy::LocalWins, "# original\n").await;
+
+        let updated = updated.unwrap();
+        let local = std::fs::read_to_string(dir.path().join("SOUL.md")).unwrap();
+        assert_eq!(local, "# stale\n");
+        assert_eq!(updated.hash, sha256_hex(&local));
+        assert_ne!(updated.revisions["blk_heading"], "before-remote-edit");
+    }
+
+    #[tokio::test]
+    async fn pull_skips_when_block_revisions_are_unchanged() {
+        let server = mock_feishu_server().await;
+        let dir = tempfile::tempdir().unwrap();
+        let client = super::super::FeishuDocsClient::new("app".into(), "secret".into())
+            .with_base_url(server.uri());
+        let mut entry = pull_fixture(dir.path(), "# local edit\n");
+        entry.revisions = super::super::diff::fetch_snapshot(&client, "doc_1")
+            .await
+            .unwrap()
+            .revisions;
+
+        let updated = pull_single_file(
+            &client,
+            ConflictPolicy::RemoteWins,
+            dir.path(),
+            "SOUL.md",
+            &entry,
+        )
+        .await
+        .unwrap();
+
+        assert!(updated.is_none());
+        let local = std::fs::read_to_string(dir.path().join("SOUL.md")).unwrap();
+        assert_eq!(local, "# local edit\n");
+    }
+
+    #[tokio::test]
+    async fn concurrent_edit_remote_wins_overwrites_local() {
+        let (_server, dir, updated) = pull_with(ConflictPolicy::RemoteWins, "# local edit\n").await;
+
+        assert!(updated.is_some());
+        let local = std::fs::read_to_string(dir.path().join("SOUL.md")).unwrap();
+        assert_eq!(local, "# stale\n");
+    }
+
+    #[tokio::test]
+    async fn concurrent_edit_local_wins_pushes_local_over_remote() {
+        let (server, dir, updated) = pull_with(ConflictPolicy::LocalWins, "# local edit\n").await;
+
+        let updated = updated.unwrap();
+        let local = std::fs::read_to_string(dir.path().join("SOUL.md")).unwrap();
+        assert_eq!(local, "# local edit\n");
+        assert_eq!(updated.hash, sha256_hex("# local edit\n"));
+        assert_eq!(
+            requests_matching(&server, "PATCH", "/docx/v1/documents/doc_1/")
+                .await
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn concurrent_edit_keep_both_saves_remote_copy() {
+        let (server, dir, updated) = pull_with(ConflictPolicy::KeepBoth, "# local edit\n").await;
+
+        let updated = updated.unwrap();
+        let local = std::fs::read_to_string(dir.path().join("SOUL.md")).unwrap();
+        assert_eq!(local, "# local edit\n");
+        let remote = std::fs::read_to_string(dir.path().join("SOUL.md.remote")).unwrap();
+        assert_eq!(remote, "# stale\n");
+        // The local edit stays pending (old hash) but the remote edit is acknowledged.
+        assert_eq!(updated.hash, sha256_hex("# original\n"));
+        assert_ne!(updated.revisions["blk_heading"], "before-remote-edit");
+        assert!(requests_matching(&server, "PATCH", "/docx/")
+            .await
+            .is_empty());
+    }
+
+    #[test]
+    fn lock_entries_without_revisions_still_load() {
+        let lock: LockMap =
+            serde_json::from_str(r#"{ "SOUL.md": { "doc_id": "doc_1", "hash": "abc" } }"#).unwrap();
+        assert!(lock["SOUL.md"].revisions.is_empty());
+    }
+
+    fn save_shared_users_for_test(dir: &Path, users: &[&str]) {
+        let users: Vec<String> = users.iter().map(|u| (*u).to_string()).collect();
+        std::fs::write(
+            dir.join("docs_sync_shared_users.json"),
+            serde_json::to_string(&users).unwrap(),
+        )
+        .unwrap();
+    }
+}
@@ -311,6 +311,8 @@ pub struct AppState {
     pub cost_tracker: Option<Arc<CostTracker>>,
     /// SSE broadcast channel for real-time events
     pub event_tx: tokio::sync::broadcast::Sender<serde_json::Value>,
+    /// Hook runner for `before_send` on webhook channel replies
+    pub hooks: Option<Arc<crate::hooks::HookRunner>>,
 }
 
 /// Run the HTTP gateway using axum with proper HTTP/1.1 compliance.
@@ -329,7 +331,9 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
 
     // ── Hooks ──────────────────────────────────────────────────────
     let hooks: Option<std::sync::Arc<crate::hooks::HookRunner>> = if config.hooks.enabled {
-        Some(std::sync::Arc::new(crate::hooks::HookRunner::new()))
+        let mut runner = crate::hooks::HookRunner::new();
+        crate::hooks::builtin::register_builtins(&mut runner, &config);
+        Some(std::sync::Arc::new(runner))
     } else {
         None
     };
@@ -645,6 +649,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         tools_registry,
         cost_tracker,
         event_tx,
+        hooks: hooks.clone(),
     };
 
     // Config PUT needs larger body limit (1MB)
@@ -656,6 +661,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     let app = Router::new()
         // ── Existing routes ──
         .route("/health", get(handle_health))
+        .route("/healthz", get(handle_healthz))
         .route("/metrics", get(handle_metrics))
         .route("/pair", post(handle_pair))
         .route("/webhook", post(handle_webhook))
@@ -721,6 +727,17 @@ async fn handle_health(State(state): State<AppState>) -> impl IntoResponse {
     Json(body)
 }
 
+/// GET /healthz — per-subsystem readiness; 503 while any subsystem is down
+async fn handle_healthz() -> impl IntoResponse {
+    let overall = crate::health::overall_status();
+    let status = if overall.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(overall))
+}
+
 /// Prometheus content type for text exposition format.
 const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
 
@@ -736,6 +753,8 @@ async fn handle_metrics(State(state): State<AppState>) -> impl IntoResponse {
     } else {
         String::from("# Prometheus backend not enabled. Set [observability] backend = \"prometheus\" in config.\n")
     };
+    #[cfg(feature = "metrics")]
+    let body = body + &crate::metrics::global().encode();
 
     (
         StatusCode::OK,
@@ -856,6 +875,30 @@ async fn run_gateway_chat_simple(state: &AppState, message: &str) -> anyhow::Res
 }
 
 /// Full-featured chat with tools for channel handlers (WhatsApp, Linq, Nextcloud Talk).
+/// Reply on a webhook channel, running `before_send` hooks first so they can
+/// rewrite or suppress the outgoing text.
+async fn send_reply(
+    state: &AppState,
+    channel: &dyn Channel,
+    content: String,
+    recipient: &str,
+) -> anyhow::Result<()> {
+    let content = match state.hooks.as_deref() {
+        Some(hooks) => match hooks
+            .run_before_send(channel.name(), recipient, content)
+            .await
+        {
+            crate::hooks::HookResult::Cancel(reason) => {
+                tracing::info!(%reason, "gateway reply suppressed by before_send hook");
+                return Ok(());
+            }
+            crate::hooks::HookResult::Continue(content) => content,
+        },
+        None => content,
+    };
+    channel.send(&SendMessage::new(content, recipient)).await
+}
+
 async fn run_gateway_chat_with_tools(state: &AppState, message: &str) -> anyhow::Result<String> {
     let config = state.config.lock().clone();
     crate::agent::process_message(config, message).await
@@ -1189,21 +1232,19 @@ async fn handle_whatsapp_message(
         match run_gateway_chat_with_tools(&state, &msg.content).await {
             Ok(response) => {
                 // Send reply via WhatsApp
-                if let Err(e) = wa
-                    .send(&SendMessage::new(response, &msg.reply_target))
-                    .await
-                {
+                if let Err(e) = send_reply(&state, wa.as_ref(), response, &msg.reply_target).await {
                     tracing::error!("Failed to send WhatsApp reply: {e}");
                 }
             }
             Err(e) => {
                 tracing::error!("LLM error for WhatsApp message: {e:#}");
-                let _ = wa
-                    .send(&SendMessage::new(
-                        "Sorry, I couldn't process your message right now.",
-                        &msg.reply_target,
-                    ))
-                    .await;
+                let _ = send_reply(
+                    &state,
+                    wa.as_ref(),
+                    "Sorry, I couldn't process your message right now.".into(),
+                    &msg.reply_target,
+                )
+                .await;
             }
         }
     }
@@ -1297,21 +1338,20 @@ async fn handle_linq_webhook(
         match run_gateway_chat_with_tools(&state, &msg.content).await {
             Ok(response) => {
                 // Send reply via Linq
-                if let Err(e) = linq
-                    .send(&SendMessage::new(response, &msg.reply_target))
-                    .await
+                if let Err(e) = send_reply(&state, linq.as_ref(), response, &msg.reply_target).await
                 {
                     tracing::error!("Failed to send Linq reply: {e}");
                 }
             }
             Err(e) => {
                 tracing::error!("LLM error for Linq message: {e:#}");
-                let _ = linq
-                    .send(&SendMessage::new(
-                        "Sorry, I couldn't process your message right now.",
-                        &msg.reply_target,
-                    ))
-                    .await;
+                let _ = send_reply(
+                    &state,
+                    linq.as_ref(),
+                    "Sorry, I couldn't process your message right now.".into(),
+                    &msg.reply_target,
+                )
+                .await;
             }
         }
     }
@@ -1389,21 +1429,20 @@ async fn handle_wati_webhook(State(state): State<AppState>, body: Bytes) -> impl
         match run_gateway_chat_with_tools(&state, &msg.content).await {
             Ok(response) => {
                 // Send reply via WATI
-                if let Err(e) = wati
-                    .send(&SendMessage::new(response, &msg.reply_target))
-                    .await
+                if let Err(e) = send_reply(&state, wati.as_ref(), response, &msg.reply_target).await
                 {
                     tracing::error!("Failed to send WATI reply: {e}");
                 }
             }
             Err(e) => {
                 tracing::error!("LLM error for WATI message: {e:#}");
-                let _ = wati
-                    .send(&SendMessage::new(
-                        "Sorry, I couldn't process your message right now.",
-                        &msg.reply_target,
-                    ))
-                    .await;
+                let _ = send_reply(
+                    &state,
+                    wati.as_ref(),
+                    "Sorry, I couldn't process your message right now.".into(),
+                    &msg.reply_target,
+                )
+                .await;
             }
         }
     }
@@ -1492,21 +1531,21 @@ async fn handle_nextcloud_talk_webhook(
 
         match run_gateway_chat_with_tools(&state, &msg.content).await {
             Ok(response) => {
-                if let Err(e) = nextcloud_talk
-                    .send(&SendMessage::new(response, &msg.reply_target))
-                    .await
+                if let Err(e) =
+                    send_reply(&state, nextcloud_talk.as_ref(), response, &msg.reply_target).await
                 {
                     tracing::error!("Failed to send Nextcloud Talk reply: {e}");
                 }
             }
             Err(e) => {
                 tracing::error!("LLM error for Nextcloud Talk message: {e:#}");
-                let _ = nextcloud_talk
-                    .send(&SendMessage::new(
-                        "Sorry, I couldn't process your message right now.",
-                        &msg.reply_target,
-                    ))
-                    .await;
+                let _ = send_reply(
+                    &state,
+                    nextcloud_talk.as_ref(),
+                    "Sorry, I couldn't process your message right now.".into(),
+                    &msg.reply_target,
+                )
+                .await;
             }
         }
     }
@@ -1596,6 +1635,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            hooks: None,
         };
 
         let response = handle_metrics(State(state)).await.into_response();
@@ -1644,6 +1684,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            hooks: None,
             wati: None,
         };
 
@@ -2011,6 +2052,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            hooks: None,
         };
 
         let mut headers = HeaderMap::new();
@@ -2075,6 +2117,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            hooks: None,
         };
 
         let headers = HeaderMap::new();
@@ -2151,6 +2194,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            hooks: None,
         };
 
         let response = handle_webhook(
@@ -2199,6 +2243,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            hooks: None,
         };
 
         let mut headers = HeaderMap::new();
@@ -2252,6 +2297,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            hooks: None,
         };
 
         let mut headers = HeaderMap::new();
@@ -2310,6 +2356,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            hooks: None,
         };
 
         let response = handle_nextcloud_talk_webhook(
@@ -2363,6 +2410,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            hooks: None,
             wati: None,
         };
 
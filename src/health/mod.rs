@@ -82,6 +82,11 @@ pub fn bump_component_restart(component: &str) {
     });
 }
 
+/// Forget a component, e.g. an MCP server that was removed at runtime.
+pub fn remove_component(component: &str) {
+    registry().components.lock().remove(component);
+}
+
 pub fn snapshot() -> HealthSnapshot {
     let components = registry().components.lock().clone();
 
@@ -102,6 +107,108 @@ pub fn snapshot_json() -> serde_json::Value {
     })
 }
 
+/// Rolled-up state of a subsystem or of the whole process.
+///
+/// Ordered from best to worst so the overall state is the `max` of its parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReadinessState {
+    /// Every component reports `ok`.
+    Ok,
+    /// Some components are starting or failing, others are `ok`.
+    Degraded,
+    /// No component of the subsystem is `ok` and at least one is failing.
+    Down,
+}
+
+/// Per-subsystem readiness, derived from the components registered under it.
+#[derive(Debug, Clone, Serialize)]
+pub struct SubsystemStatus {
+    pub status: ReadinessState,
+    pub ok: usize,
+    pub total: usize,
+    /// `last_error` of each failing component.
+    pub errors: BTreeMap<String, String>,
+}
+
+/// Readiness summary across subsystems, suitable for a `/healthz` body.
+#[derive(Debug, Clone, Serialize)]
+pub struct OverallStatus {
+    pub status: ReadinessState,
+    pub subsystems: BTreeMap<&'static str, SubsystemStatus>,
+}
+
+impl OverallStatus {
+    /// Whether the process should be reported as ready (nothing is down).
+    pub fn is_ready(&self) -> bool {
+        self.status != ReadinessState::Down
+    }
+}
+
+/// Subsystem a component is reported under: `channel:<name>` components
+/// (listeners and token checks) under `channels`, `mcp:<server>` under `mcp`,
+/// `vpn` and `docs_sync` under themselves, everything else under `core`.
+fn subsystem_of(component: &str) -> &'static str {
+    if component.starts_with("channel:") {
+        "channels"
+    } else if component.starts_with("mcp:") {
+        "mcp"
+    } else if component == "vpn" {
+        "vpn"
+    } else if component == "docs_sync" {
+        "docs_sync"
+    } else {
+        "core"
+    }
+}
+
+fn aggregate(components: &BTreeMap<String, ComponentHealth>) -> OverallStatus {
+    let mut subsystems: BTreeMap<&'static str, SubsystemStatus> = BTreeMap::new();
+    for (name, component) in components {
+        let entry = subsystems
+            .entry(subsystem_of(name))
+            .or_insert_with(|| SubsystemStatus {
+                status: ReadinessState::Ok,
+                ok: 0,
+                total: 0,
+                errors: BTreeMap::new(),
+            });
+        entry.total += 1;
+        match component.status.as_str() {
+            "ok" => entry.ok += 1,
+            "error" => {
+                let error = component.last_error.clone().unwrap_or_default();
+                entry.errors.insert(name.clone(), error);
+            }
+            _ => {}
+        }
+    }
+
+    for entry in subsystems.values_mut() {
+        entry.status = if entry.ok == entry.total {
+            ReadinessState::Ok
+        } else if entry.ok == 0 && !entry.errors.is_empty() {
+            ReadinessState::Down
+        } else {
+            ReadinessState::Degraded
+        };
+    }
+
+    OverallStatus {
+        status: subsystems
+            .values()
+            .map(|entry| entry.status)
+            .max()
+            .unwrap_or(ReadinessState::Ok),
+        subsystems,
+    }
+}
+
+/// Aggregate the registered components into one readiness summary.
+pub fn overall_status() -> OverallStatus {
+    aggregate(&registry().components.lock())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,4 +288,73 @@ mod tests {
         assert!(component_json["last_ok"].as_str().is_some());
         assert!(json["uptime_seconds"].as_u64().is_some());
     }
+
+    fn component(status: &str, last_error: Option<&str>) -> ComponentHealth {
+        ComponentHealth {
+            status: status.into(),
+            updated_at: now_rfc3339(),
+            last_ok: None,
+            last_error: last_error.map(Into::into),
+            restart_count: 0,
+        }
+    }
+
+    #[test]
+    fn aggregate_rolls_up_mixed_subsystem_results() {
+        let components = BTreeMap::from([
+            ("daemon".to_string(), component("ok", None)),
+            ("channel:lark".to_string(), component("ok", None)),
+            (
+                "channel:lark:auth".to_string(),
+                component("error", Some("health check failed")),
+            ),
+            ("mcp:github".to_string(), component("ok", None)),
+            ("mcp:fetch".to_string(), component("starting", None)),
+            (
+                "vpn".to_string(),
+                component("error", Some("no healthy nodes")),
+            ),
+            ("docs_sync".to_string(), component("ok", None)),
+        ]);
+
+        let overall = aggregate(&components);
+
+        let channels = &overall.subsystems["channels"];
+        assert_eq!(channels.status, ReadinessState::Degraded);
+        assert_eq!((channels.ok, channels.total), (1, 2));
+        assert_eq!(
+            channels.errors.get("channel:lark:auth").map(String::as_str),
+            Some("health check failed")
+        );
+        assert_eq!(overall.subsystems["mcp"].status, ReadinessState::Degraded);
+        assert!(overall.subsystems["mcp"].errors.is_empty());
+        assert_eq!(overall.subsystems["vpn"].status, ReadinessState::Down);
+        assert_eq!(overall.subsystems["docs_sync"].status, ReadinessState::Ok);
+        assert_eq!(overall.subsystems["core"].status, ReadinessState::Ok);
+        assert_eq!(overall.status, ReadinessState::Down);
+        assert!(!overall.is_ready());
+
+        let json = serde_json::to_value(&overall).unwrap();
+        assert_eq!(json["status"], "down");
+        assert_eq!(
+            json["subsystems"]["vpn"]["errors"]["vpn"],
+            "no healthy nodes"
+        );
+    }
+
+    #[test]
+    fn aggregate_is_ready_when_nothing_is_down() {
+        let components = BTreeMap::from([
+            ("gateway".to_string(), component("ok", None)),
+            ("mcp:github".to_string(), component("error", Some("boom"))),
+            ("mcp:fetch".to_string(), component("ok", None)),
+        ]);
+        let overall = aggregate(&components);
+        assert_eq!(overall.status, ReadinessState::Degraded);
+        assert!(overall.is_ready());
+
+        let empty = aggregate(&BTreeMap::new());
+        assert_eq!(empty.status, ReadinessState::Ok);
+        assert!(empty.subsystems.is_empty());
+    }
 }
@@ -33,7 +33,12 @@ impl HookHandler for CommandLoggerHook {
         -50
     }
 
-    async fn on_after_tool_call(&self, tool: &str, result: &ToolResult, duration: Duration) {
+    async fn on_after_tool_call(
+        &self,
+        tool: &str,
+        result: &ToolResult,
+        duration: Duration,
+    ) -> anyhow::Result<()> {
         let entry = format!(
             "[{}] {} ({}ms) success={}",
             chrono::Utc::now().format("%H:%M:%S"),
@@ -43,6 +48,7 @@ impl HookHandler for CommandLoggerHook {
         );
         tracing::info!(hook = "command-logger", "{}", entry);
         self.log.lock().unwrap().push(entry);
+        Ok(())
     }
 }
 
@@ -59,7 +65,8 @@ mod tests {
             error: None,
         };
         hook.on_after_tool_call("shell", &result, Duration::from_millis(42))
-            .await;
+            .await
+            .unwrap();
         let entries = hook.entries();
         assert_eq!(entries.len(), 1);
         assert!(entries[0].contains("shell"));
@@ -0,0 +1,497 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::channels::traits::ChannelMessage;
+use crate::config::Config;
+use crate::hooks::manifest::HookEvent;
+use crate::hooks::traits::{HookHandler, HookResult};
+use crate::providers::traits::{ChatMessage, ChatResponse};
+use crate::tools::traits::ToolResult;
+
+/// Append-only JSONL file that rotates to `<path>.1`, `<path>.2`, … once it
+/// would grow past `max_bytes`.
+struct RotatingLog {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    file: File,
+    size: u64,
+}
+
+impl RotatingLog {
+    fn open(path: PathBuf, max_bytes: u64, max_files: usize) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let file = open_append(&path)?;
+        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok(Self {
+            path,
+            max_bytes,
+            max_files,
+            file,
+            size,
+        })
+    }
+
+    fn append(&mut self, record: &Value) -> Result<()> {
+        let mut line = record.to_string();
+        line.push('\n');
+        if self.size > 0 && self.size + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(line.as_bytes())?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        if self.max_files > 0 {
+            for n in (1..self.max_files).rev() {
+                let from = rotated_path(&self.path, n);
+                if from.exists() {
+                    std::fs::rename(&from, rotated_path(&self.path, n + 1))?;
+                }
+            }
+            std::fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        } else {
+            std::fs::remove_file(&self.path)?;
+        }
+        self.file = open_append(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open hook event log {}", path.display()))
+}
+
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{n}"));
+    PathBuf::from(name)
+}
+
+/// Which side of the modifying chain a handler observes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    /// Highest priority: sees void events and modifying inputs.
+    Before,
+    /// Lowest priority: sees modifying outputs after every other hook.
+    After,
+}
+
+/// Records every hook event as one JSON line for replay and inspection.
+///
+/// Modifying events are observed from both ends of the chain, so this hook is
+/// registered as a pair (see [`EventLogHook::open`]): one handler runs first
+/// and logs the input, the other runs last and logs the final value. When a
+/// hook cancels, only the `before` record is written.
+pub struct EventLogHook {
+    log: Arc<Mutex<RotatingLog>>,
+    stage: Stage,
+}
+
+impl EventLogHook {
+    /// Open (or create) the log file and return the `(before, after)` pair.
+    pub fn open(path: PathBuf, max_bytes: u64, max_files: usize) -> Result<(Self, Self)> {
+        let log = Arc::new(Mutex::new(RotatingLog::open(path, max_bytes, max_files)?));
+        Ok((
+            Self {
+                log: Arc::clone(&log),
+                stage: Stage::Before,
+            },
+            Self {
+                log,
+                stage: Stage::After,
+            },
+        ))
+    }
+
+    fn record(&self, event: HookEvent, data: Value) {
+        let mut record = json!({
+            "ts": chrono::Utc::now().to_rfc3339(),
+            "event": event.to_string(),
+        });
+        if is_modifying(event) {
+            record["stage"] = json!(match self.stage {
+                Stage::Before => "before",
+                Stage::After => "after",
+            });
+        }
+        record["data"] = data;
+        let mut log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = log.append(&record) {
+            tracing::warn!(hook = "event-log", "failed to write hook event log: {e}");
+        }
+    }
+
+    /// Void events are only recorded once, by the `before` handler.
+    fn record_void(&self, event: HookEvent, data: Value) {
+        if self.stage == Stage::Before {
+            self.record(event, data);
+        }
+    }
+}
+
+fn is_modifying(event: HookEvent) -> bool {
+    matches!(
+        event,
+        HookEvent::BeforeSessionStart
+            | HookEvent::BeforeModelResolve
+            | HookEvent::BeforePromptBuild
+            | HookEvent::BeforeLlmCall
+            | HookEvent::BeforeToolCall
+            | HookEvent::OnMessageReceived
+            | HookEvent::OnMessageSending
+            | HookEvent::OnCronDelivery
+            | HookEvent::OnDocsSyncNotify
+            | HookEvent::BeforeSend
+    )
+}
+
+fn channel_message_json(message: &ChannelMessage) -> Value {
+    json!({
+        "id": message.id,
+        "sender": message.sender,
+        "reply_target": message.reply_target,
+        "content": message.content,
+        "channel": message.channel,
+        "timestamp": message.timestamp,
+        "thread_ts": message.thread_ts,
+    })
+}
+
+#[async_trait]
+impl HookHandler for EventLogHook {
+    fn name(&self) -> &str {
+        match self.stage {
+            Stage::Before => "event-log",
+            Stage::After => "event-log-after",
+        }
+    }
+
+    fn priority(&self) -> i32 {
+        match self.stage {
+            Stage::Before => i32::MAX,
+            Stage::After => i32::MIN,
+        }
+    }
+
+    fn handles(&self, event: HookEvent) -> bool {
+        self.stage == Stage::Before || is_modifying(event)
+    }
+
+    // --- Void hooks ---
+
+    async fn on_gateway_start(&self, host: &str, port: u16) -> anyhow::Result<()> {
+        self.record_void(
+            HookEvent::OnGatewayStart,
+            json!({ "host": host, "port": port }),
+        );
+        Ok(())
+    }
+
+    async fn on_gateway_stop(&self) -> anyhow::Result<()> {
+        self.record_void(HookEvent::OnGatewayStop, json!({}));
+        Ok(())
+    }
+
+    async fn on_session_start(&self, session_id: &str, channel: &str) -> anyhow::Result<()> {
+        self.record_void(
+            HookEvent::OnSessionStart,
+            json!({ "session_id": session_id, "channel": channel }),
+        );
+        Ok(())
+    }
+
+    async fn on_session_end(&self, session_id: &str, channel: &str) -> anyhow::Result<()> {
+        self.record_void(
+            HookEvent::OnSessionEnd,
+            json!({ "session_id": session_id, "channel": channel }),
+        );
+        Ok(())
+    }
+
+    async fn on_llm_input(&self, messages: &[ChatMessage], model: &str) -> anyhow::Result<()> {
+        self.record_void(
+            HookEvent::OnLlmInput,
+            json!({ "model": model, "messages": messages }),
+        );
+        Ok(())
+    }
+
+    async fn on_llm_output(&self, response: &ChatResponse) -> anyhow::Result<()> {
+        let tool_calls: Vec<Value> = response
+            .tool_calls
+            .iter()
+            .map(|c| json!({ "name": c.name, "arguments": c.arguments }))
+            .collect();
+        self.record_void(
+            HookEvent::OnLlmOutput,
+            json!({ "text": response.text, "tool_calls": tool_calls }),
+        );
+        Ok(())
+    }
+
+    async fn on_after_tool_call(
+        &self,
+        tool: &str,
+        result: &ToolResult,
+        duration: Duration,
+    ) -> anyhow::Result<()> {
+        self.record_void(
+            HookEvent::OnAfterToolCall,
+            json!({
+                "tool": tool,
+                "success": result.success,
+                "output": result.output,
+                "error": result.error,
+                "duration_ms": u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            }),
+        );
+        Ok(())
+    }
+
+    async fn on_message_sent(
+        &self,
+        channel: &str,
+        recipient: &str,
+        content: &str,
+    ) -> anyhow::Result<()> {
+        self.record_void(
+            HookEvent::OnMessageSent,
+            json!({ "channel": channel, "recipient": recipient, "content": content }),
+        );
+        Ok(())
+    }
+
+    async fn on_heartbeat_tick(&self) -> anyhow::Result<()> {
+        self.record_void(HookEvent::OnHeartbeatTick, json!({}));
+        Ok(())
+    }
+
+    async fn on_config_reload(&self, config: &Config) -> anyhow::Result<()> {
+        self.record_void(
+            HookEvent::OnConfigReload,
+            json!({
+                "default_provider": config.default_provider,
+                "default_model": config.default_model,
+            }),
+        );
+        Ok(())
+    }
+
+    // --- Modifying hooks (observe only, never change the value) ---
+
+    async fn before_model_resolve(
+        &self,
+        provider: String,
+        model: String,
+    ) -> HookResult<(String, String)> {
+        self.record(
+            HookEvent::BeforeModelResolve,
+            json!({ "provider": provider, "model": model }),
+        );
+        HookResult::Continue((provider, model))
+    }
+
+    async fn before_session_start(&self, session_id: &str, channel: &str) -> HookResult<()> {
+        self.record(
+            HookEvent::BeforeSessionStart,
+            json!({ "session_id": session_id, "channel": channel }),
+        );
+        HookResult::Continue(())
+    }
+
+    async fn before_prompt_build(&self, prompt: String) -> HookResult<String> {
+        self.record(HookEvent::BeforePromptBuild, json!({ "prompt": prompt }));
+        HookResult::Continue(prompt)
+    }
+
+    async fn before_llm_call(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: String,
+    ) -> HookResult<(Vec<ChatMessage>, String)> {
+        self.record(
+            HookEvent::BeforeLlmCall,
+            json!({ "messages": messages, "model": model }),
+        );
+        HookResult::Continue((messages, model))
+    }
+
+    async fn before_tool_call(&self, name: String, args: Value) -> HookResult<(String, Value)> {
+        self.record(
+            HookEvent::BeforeToolCall,
+            json!({ "name": name, "args": args }),
+        );
+        HookResult::Continue((name, args))
+    }
+
+    async fn on_message_received(&self, message: ChannelMessage) -> HookResult<ChannelMessage> {
+        self.record(HookEvent::OnMessageReceived, channel_message_json(&message));
+        HookResult::Continue(message)
+    }
+
+    async fn on_message_sending(
+        &self,
+        channel: String,
+        recipient: String,
+        content: String,
+    ) -> HookResult<(String, String, String)> {
+        self.record(
+            HookEvent::OnMessageSending,
+            json!({ "channel": channel, "recipient": recipient, "content": content }),
+        );
+        HookResult::Continue((channel, recipient, content))
+    }
+
+    async fn on_cron_delivery(
+        &self,
+        source: String,
+        channel: String,
+        recipient: String,
+        content: String,
+    ) -> HookResult<(String, String, String, String)> {
+        self.record(
+            HookEvent::OnCronDelivery,
+            json!({
+                "source": source,
+                "channel": channel,
+                "recipient": recipient,
+                "content": content,
+            }),
+        );
+        HookResult::Continue((source, channel, recipient, content))
+    }
+
+    async fn on_docs_sync_notify(
+        &self,
+        file_path: String,
+        channel: String,
+        recipient: String,
+        content: String,
+    ) -> HookResult<(String, String, String, String)> {
+        self.record(
+            HookEvent::OnDocsSyncNotify,
+            json!({
+                "file_path": file_path,
+                "channel": channel,
+                "recipient": recipient,
+                "content": content,
+            }),
+        );
+        HookResult::Continue((file_path, channel, recipient, content))
+    }
+
+    async fn before_send(
+        &self,
+        channel: &str,
+        recipient: &str,
+        content: String,
+    ) -> HookResult<String> {
+        self.record(
+            HookEvent::BeforeSend,
+            json!({ "channel": channel, "recipient": recipient, "content": content }),
+        );
+        HookResult::Continue(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::HookRunner;
+    use tempfile::TempDir;
+
+    struct ShoutHook;
+
+    #[async_trait]
+    impl HookHandler for ShoutHook {
+        fn name(&self) -> &str {
+            "shout"
+        }
+
+        async fn before_prompt_build(&self, prompt: String) -> HookResult<String> {
+            HookResult::Continue(prompt.to_uppercase())
+        }
+    }
+
+    fn read_records(path: &Path) -> Vec<Value> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect()
+    }
+
+    fn runner_with_log(path: PathBuf, max_bytes: u64) -> HookRunner {
+        let (before, after) = EventLogHook::open(path, max_bytes, 2).unwrap();
+        let mut runner = HookRunner::new();
+        runner.register(Box::new(before));
+        runner.register(Box::new(ShoutHook));
+        runner.register(Box::new(after));
+        runner
+    }
+
+    #[tokio::test]
+    async fn appends_events_in_order_with_before_and_after_values() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state/hook-events.jsonl");
+        let runner = runner_with_log(path.clone(), 1024 * 1024);
+
+        runner.fire_session_start("s1", "cli").await;
+        let prompt = runner.run_before_prompt_build("hello".into()).await;
+        runner.fire_message_sent("cli", "user", "HELLO").await;
+        runner.fire_session_end("s1", "cli").await;
+        assert!(matches!(prompt, HookResult::Continue(ref p) if p == "HELLO"));
+
+        let records = read_records(&path);
+        let events: Vec<(&str, Option<&str>)> = records
+            .iter()
+            .map(|r| (r["event"].as_str().unwrap(), r["stage"].as_str()))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                ("on_session_start", None),
+                ("before_prompt_build", Some("before")),
+                ("before_prompt_build", Some("after")),
+                ("on_message_sent", None),
+                ("on_session_end", None),
+            ]
+        );
+        assert_eq!(records[1]["data"]["prompt"], "hello");
+        assert_eq!(records[2]["data"]["prompt"], "HELLO");
+        assert!(chrono::DateTime::parse_from_rfc3339(records[0]["ts"].as_str().unwrap()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn rotates_when_file_exceeds_max_bytes() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("events.jsonl");
+        let runner = runner_with_log(path.clone(), 200);
+
+        for _ in 0..10 {
+            runner.fire_heartbeat_tick().await;
+        }
+
+        assert!(std::fs::metadata(&path).unwrap().len() <= 200);
+        assert!(rotated_path(&path, 1).exists());
+        assert!(rotated_path(&path, 2).exists());
+        assert!(!rotated_path(&path, 3).exists());
+    }
+}
@@ -1,3 +1,39 @@
 pub mod command_logger;
+pub mod event_log;
+pub mod rate_limit;
+pub mod webhook;
 
 pub use command_logger::CommandLoggerHook;
+pub use event_log::EventLogHook;
+pub use rate_limit::RateLimitHook;
+pub use webhook::WebhookHook;
+
+use crate::config::Config;
+use crate::hooks::HookRunner;
+
+/// Register the built-in handlers enabled in `[hooks]` config.
+pub fn register_builtins(runner: &mut HookRunner, config: &Config) {
+    let hooks = &config.hooks;
+    if hooks.builtin.command_logger {
+        runner.register(Box::new(CommandLoggerHook::new()));
+    }
+    if let Some(rate_limit) = &hooks.rate_limit {
+        runner.register(Box::new(RateLimitHook::new(rate_limit.clone())));
+    }
+    if let Some(webhook) = &hooks.webhook {
+        runner.register(Box::new(WebhookHook::new(webhook.clone())));
+    }
+    if let Some(event_log) = &hooks.event_log {
+        let path = event_log
+            .path
+            .clone()
+            .unwrap_or_else(|| config.workspace_dir.join("state").join("hook-events.jsonl"));
+        match EventLogHook::open(path, event_log.max_bytes, event_log.max_files) {
+            Ok((before, after)) => {
+                runner.register(Box::new(before));
+                runner.register(Box::new(after));
+            }
+            Err(e) => tracing::warn!("Hook event log disabled: {e}"),
+        }
+    }
+}
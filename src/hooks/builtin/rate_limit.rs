@@ -0,0 +1,199 @@
+use async_trait::async_trait;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::config::schema::RateLimitHookConfig;
+use crate::hooks::traits::{HookContext, HookHandler, HookResult};
+
+/// Rejects sessions from senders that exceed a sliding-window rate limit.
+///
+/// Runs in `before_session_start`, which fires once per inbound message (or
+/// CLI turn), so tool-loop iterations within one request are not counted.
+/// Requests without a channel or sender in the hook context are not limited.
+pub struct RateLimitHook {
+    max_calls: usize,
+    window: Duration,
+    calls: Mutex<HashMap<String, VecDeque<Instant>>>,
+}
+
+impl RateLimitHook {
+    pub fn new(config: RateLimitHookConfig) -> Self {
+        Self {
+            max_calls: config.max_calls as usize,
+            window: Duration::from_secs(config.window_secs),
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Rate-limit key for a request: the sender scoped by channel, or the
+    /// channel alone when the sender is unknown.
+    fn key(ctx: &HookContext) -> Option<String> {
+        match (ctx.channel.as_deref(), ctx.sender.as_deref()) {
+            (Some(channel), Some(sender)) => Some(format!("{channel}:{sender}")),
+            (None, Some(sender)) => Some(sender.to_string()),
+            (Some(channel), None) => Some(channel.to_string()),
+            (None, None) => None,
+        }
+    }
+
+    /// Record a call for `key` at `now`. Returns how long until the oldest
+    /// call leaves the window when the limit is already reached.
+    ///
+    /// Senders whose calls have all left the window are dropped, so the map
+    /// only holds senders active within the last window.
+    fn check(&self, key: String, now: Instant) -> Result<(), Duration> {
+        let mut calls = self.calls.lock().unwrap_or_else(|e| e.into_inner());
+        calls.retain(|_, recent| {
+            recent
+                .back()
+                .is_some_and(|at| now.duration_since(*at) < self.window)
+        });
+        let recent = calls.entry(key).or_default();
+        while recent
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= self.window)
+        {
+            recent.pop_front();
+        }
+        if recent.len() >= self.max_calls {
+            let retry_after = recent.front().map_or(self.window, |at| {
+                self.window.saturating_sub(now.duration_since(*at))
+            });
+            return Err(retry_after);
+        }
+        recent.push_back(now);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HookHandler for RateLimitHook {
+    fn name(&self) -> &str {
+        "rate-limit"
+    }
+
+    fn priority(&self) -> i32 {
+        // Reject before other hooks spend work on a call that will not run.
+        100
+    }
+
+    async fn before_session_start_with_context(&self, ctx: &HookContext) -> HookResult<()> {
+        let Some(key) = Self::key(ctx) else {
+            return HookResult::Continue(());
+        };
+        match self.check(key, Instant::now()) {
+            Ok(()) => HookResult::Continue(()),
+            Err(retry_after) => HookResult::Cancel(format!(
+                "You're sending requests too quickly. Please try again in {}s.",
+                retry_after.as_secs().max(1)
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::HookRunner;
+
+    fn hook(max_calls: u32) -> RateLimitHook {
+        RateLimitHook::new(RateLimitHookConfig {
+            max_calls,
+            window_secs: 60,
+        })
+    }
+
+    #[tokio::test]
+    async fn cancels_after_threshold() {
+        let mut runner = HookRunner::new();
+        runner.register(Box::new(hook(3)));
+        let ctx = HookContext::session("s1", "telegram").with_sender("alice");
+
+        for _ in 0..3 {
+            let result = runner.run_before_session_start_with_context(&ctx).await;
+            assert!(!result.is_cancel());
+        }
+        match runner.run_before_session_start_with_context(&ctx).await {
+            HookResult::Cancel(reason) => assert!(reason.contains("try again"), "{reason}"),
+            HookResult::Continue(()) => panic!("fourth call should be rate limited"),
+        }
+    }
+
+    #[tokio::test]
+    async fn senders_have_separate_budgets() {
+        let hook = hook(1);
+        let alice = HookContext::session("s1", "telegram").with_sender("alice");
+        let bob = HookContext::session("s2", "telegram").with_sender("bob");
+
+        assert!(!hook
+            .before_session_start_with_context(&alice)
+            .await
+            .is_cancel());
+        assert!(!hook
+            .before_session_start_with_context(&bob)
+            .await
+            .is_cancel());
+        assert!(hook
+            .before_session_start_with_context(&alice)
+            .await
+            .is_cancel());
+    }
+
+    #[test]
+    fn calls_leave_the_window() {
+        let hook = hook(1);
+        let start = Instant::now();
+
+        assert!(hook.check("cli".into(), start).is_ok());
+        assert!(hook.check("cli".into(), start).is_err());
+        assert!(hook
+            .check("cli".into(), start + Duration::from_secs(60))
+            .is_ok());
+    }
+
+    #[test]
+    fn idle_senders_are_forgotten() {
+        let hook = hook(1);
+        let start = Instant::now();
+
+        assert!(hook.check("telegram:alice".into(), start).is_ok());
+        assert!(hook.check("telegram:bob".into(), start).is_ok());
+        assert_eq!(hook.calls.lock().unwrap().len(), 2);
+
+        assert!(hook
+            .check("telegram:carol".into(), start + Duration::from_secs(60))
+            .is_ok());
+        let calls = hook.calls.lock().unwrap();
+        assert_eq!(calls.keys().collect::<Vec<_>>(), ["telegram:carol"]);
+    }
+
+    #[tokio::test]
+    async fn calls_without_context_are_not_limited() {
+        let hook = hook(1);
+        for _ in 0..2 {
+            let result = hook
+                .before_session_start_with_context(&HookContext::default())
+                .await;
+            assert!(!result.is_cancel());
+        }
+    }
+
+    #[tokio::test]
+    async fn llm_calls_within_a_session_are_not_counted() {
+        let mut runner = HookRunner::new();
+        runner.register(Box::new(hook(1)));
+        let ctx = HookContext::session("s1", "telegram").with_sender("alice");
+
+        assert!(!runner
+            .run_before_session_start_with_context(&ctx)
+            .await
+            .is_cancel());
+        for _ in 0..3 {
+            let result = runner
+                .run_before_llm_call_with_context(&ctx, vec![], "model".into())
+                .await;
+            assert!(!result.is_cancel());
+        }
+    }
+}
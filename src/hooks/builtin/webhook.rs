@@ -0,0 +1,317 @@
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::time::Duration;
+
+use crate::config::schema::WebhookHookConfig;
+use crate::config::Config;
+use crate::hooks::traits::HookHandler;
+use crate::providers::traits::{ChatMessage, ChatResponse};
+use crate::tools::traits::ToolResult;
+
+/// Header carrying the payload signature when a secret is configured.
+pub const SIGNATURE_HEADER: &str = "X-ZeroClaw-Signature";
+
+/// Compute the `X-ZeroClaw-Signature` header value for a request body.
+///
+/// The signature is the hex HMAC-SHA256 of `"<timestamp>.<body>"`, so the
+/// timestamp is authenticated too and receivers can reject stale requests
+/// to prevent replay. Format: `t=<unix seconds>,v1=<hex>`.
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    format!(
+        "t={timestamp},v1={}",
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+/// POSTs void hook events as JSON to an external URL.
+///
+/// Requests are fire-and-forget: each event is sent from a spawned task so a
+/// slow or unreachable endpoint never delays the agent. Failures are logged.
+/// When a secret is configured, each body is signed (see [`sign_payload`]).
+pub struct WebhookHook {
+    client: reqwest::Client,
+    config: WebhookHookConfig,
+}
+
+impl WebhookHook {
+    pub fn new(config: WebhookHookConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs.max(1)))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self { client, config }
+    }
+
+    /// Build the JSON body for an event.
+    fn payload(event: &str, data: Value) -> Value {
+        json!({
+            "event": event,
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "data": data,
+        })
+    }
+
+    fn post(&self, event: &str, data: Value) {
+        let body = Self::payload(event, data).to_string();
+        let mut request = self
+            .client
+            .post(&self.config.url)
+            .header("Content-Type", "application/json");
+        for (name, value) in &self.config.headers {
+            request = request.header(name, value);
+        }
+        if let Some(secret) = self.config.secret.as_deref() {
+            let timestamp = chrono::Utc::now().timestamp();
+            request = request.header(SIGNATURE_HEADER, sign_payload(secret, timestamp, &body));
+        }
+        let request = request.body(body);
+        let event = event.to_string();
+        tokio::spawn(async move {
+            match request.send().await {
+                Ok(resp) if !resp.status().is_success() => {
+                    tracing::warn!(
+                        hook = "webhook",
+                        event,
+                        status = %resp.status(),
+                        "webhook endpoint rejected event"
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(hook = "webhook", event, "webhook delivery failed: {e}");
+                }
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl HookHandler for WebhookHook {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    fn priority(&self) -> i32 {
+        -50
+    }
+
+    async fn on_gateway_start(&self, host: &str, port: u16) -> anyhow::Result<()> {
+        self.post("on_gateway_start", json!({ "host": host, "port": port }));
+        Ok(())
+    }
+
+    async fn on_gateway_stop(&self) -> anyhow::Result<()> {
+        self.post("on_gateway_stop", json!({}));
+        Ok(())
+    }
+
+    async fn on_session_start(&self, session_id: &str, channel: &str) -> anyhow::Result<()> {
+        self.post(
+            "on_session_start",
+            json!({ "session_id": session_id, "channel": channel }),
+        );
+        Ok(())
+    }
+
+    async fn on_session_end(&self, session_id: &str, channel: &str) -> anyhow::Result<()> {
+        self.post(
+            "on_session_end",
+            json!({ "session_id": session_id, "channel": channel }),
+        );
+        Ok(())
+    }
+
+    async fn on_llm_input(&self, messages: &[ChatMessage], model: &str) -> anyhow::Result<()> {
+        self.post(
+            "on_llm_input",
+            json!({ "model": model, "message_count": messages.len() }),
+        );
+        Ok(())
+    }
+
+    async fn on_llm_output(&self, response: &ChatResponse) -> anyhow::Result<()> {
+        let tool_calls: Vec<&str> = response
+            .tool_calls
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        self.post(
+            "on_llm_output",
+            json!({ "text": response.text, "tool_calls": tool_calls }),
+        );
+        Ok(())
+    }
+
+    async fn on_after_tool_call(
+        &self,
+        tool: &str,
+        result: &ToolResult,
+        duration: Duration,
+    ) -> anyhow::Result<()> {
+        self.post(
+            "on_after_tool_call",
+            json!({
+                "tool": tool,
+                "success": result.success,
+                "duration_ms": u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            }),
+        );
+        Ok(())
+    }
+
+    async fn on_message_sent(
+        &self,
+        channel: &str,
+        recipient: &str,
+        content: &str,
+    ) -> anyhow::Result<()> {
+        self.post(
+            "on_message_sent",
+            json!({ "channel": channel, "recipient": recipient, "content": content }),
+        );
+        Ok(())
+    }
+
+    async fn on_heartbeat_tick(&self) -> anyhow::Result<()> {
+        self.post("on_heartbeat_tick", json!({}));
+        Ok(())
+    }
+
+    async fn on_config_reload(&self, config: &Config) -> anyhow::Result<()> {
+        self.post(
+            "on_config_reload",
+            json!({
+                "default_provider": config.default_provider,
+                "default_model": config.default_model,
+            }),
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn wait_for_requests(server: &MockServer, count: usize) -> Vec<wiremock::Request> {
+        for _ in 0..100 {
+            let received = server.received_requests().await.unwrap_or_default();
+            if received.len() >= count {
+                return received;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("webhook did not receive {count} request(s)");
+    }
+
+    #[tokio::test]
+    async fn posts_message_sent_payload() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/events"))
+            .and(header("x-api-key", "k1"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let hook = WebhookHook::new(WebhookHookConfig {
+            url: format!("{}/events", server.uri()),
+            headers: HashMap::from([("x-api-key".to_string(), "k1".to_string())]),
+            timeout_secs: 5,
+            secret: None,
+        });
+        hook.on_message_sent("lark", "oc_123", "hello")
+            .await
+            .unwrap();
+
+        let requests = wait_for_requests(&server, 1).await;
+        let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["event"], "on_message_sent");
+        assert_eq!(
+            body["data"],
+            json!({ "channel": "lark", "recipient": "oc_123", "content": "hello" })
+        );
+        assert!(chrono::DateTime::parse_from_rfc3339(body["timestamp"].as_str().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn signature_matches_known_vectors() {
+        assert_eq!(
+            sign_payload(
+                "whsec_test",
+                1_700_000_000,
+                r#"{"event":"on_heartbeat_tick"}"#
+            ),
+            "t=1700000000,v1=2c7c37db91d7c0b76da47835f2785ac3d95ebb2397f3adb9805d118566bc3b33"
+        );
+        assert_eq!(
+            sign_payload("key", 1_700_000_000, ""),
+            "t=1700000000,v1=0f1cc1f811f42fd12af9618acf321769899fa521fe07a642f70a61785e130770"
+        );
+    }
+
+    #[test]
+    fn signature_depends_on_timestamp() {
+        let body = r#"{"event":"on_heartbeat_tick"}"#;
+        assert_ne!(
+            sign_payload("s", 1, body).split_once(",v1=").unwrap().1,
+            sign_payload("s", 2, body).split_once(",v1=").unwrap().1
+        );
+    }
+
+    #[tokio::test]
+    async fn signs_body_when_secret_is_set() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let hook = WebhookHook::new(WebhookHookConfig {
+            url: server.uri(),
+            headers: HashMap::new(),
+            timeout_secs: 5,
+            secret: Some("shared".into()),
+        });
+        hook.on_heartbeat_tick().await.unwrap();
+
+        let requests = wait_for_requests(&server, 1).await;
+        let header = requests[0]
+            .headers
+            .get(SIGNATURE_HEADER)
+            .expect("signature header")
+            .to_str()
+            .unwrap();
+        let timestamp: i64 = header
+            .strip_prefix("t=")
+            .and_then(|rest| rest.split_once(','))
+            .map(|(t, _)| t.parse().unwrap())
+            .unwrap();
+        let body = std::str::from_utf8(&requests[0].body).unwrap();
+        assert_eq!(header, sign_payload("shared", timestamp, body));
+    }
+
+    #[tokio::test]
+    async fn unreachable_endpoint_does_not_block() {
+        let hook = WebhookHook::new(WebhookHookConfig {
+            url: "http://127.0.0.1:1/events".into(),
+            headers: HashMap::new(),
+            timeout_secs: 1,
+            secret: None,
+        });
+        tokio::time::timeout(Duration::from_millis(200), hook.on_heartbeat_tick())
+            .await
+            .expect("webhook hook must not wait for delivery")
+            .unwrap();
+    }
+}
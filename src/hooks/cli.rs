@@ -1,10 +1,14 @@
 use anyhow::{bail, Result};
+use std::fmt::Write as _;
 use std::path::{Path, PathBuf};
 
 use crate::config::Config;
 use crate::hooks::audit::audit_hook_directory;
+use crate::hooks::dynamic::DynamicHookHandler;
 use crate::hooks::loader::load_hooks_from_dir;
+use crate::hooks::manifest::HookEvent;
 use crate::hooks::reload::write_reload_stamp;
+use crate::hooks::{HookHandler, HookInfo, HookRunner, HookTestResult};
 use crate::HooksCommands;
 
 /// Resolve the hooks directory from config or default to `{workspace}/hooks/`.
@@ -17,28 +21,93 @@ fn resolve_hooks_dir(config: &Config) -> PathBuf {
 }
 
 /// Handle all `zeroclaw hooks <subcommand>` CLI commands.
-pub fn handle_hooks_command(command: HooksCommands, config: &Config) -> Result<()> {
+pub async fn handle_hooks_command(command: HooksCommands, config: &Config) -> Result<()> {
     match command {
-        HooksCommands::List => handle_list(config),
+        HooksCommands::List => handle_list(config).await,
         HooksCommands::Reload => handle_reload(config),
         HooksCommands::Create { name } => handle_create(&name, config),
         HooksCommands::Audit { path } => handle_audit(&path, config),
+        HooksCommands::Test { event, execute } => handle_test(&event, execute, config).await,
     }
 }
 
-/// `zeroclaw hooks list` — print table of installed hooks (builtin + dynamic).
-fn handle_list(config: &Config) -> Result<()> {
-    // Builtin hooks
-    println!("Builtin hooks:");
-    println!(
-        "  {:<20} {:<10}",
-        "command-logger",
-        if config.hooks.builtin.command_logger {
-            "enabled"
-        } else {
-            "disabled"
-        },
-    );
+/// Build a runner with the same static and dynamic handlers the daemon would
+/// register. Dynamic hooks are skipped if the hooks directory is missing.
+async fn build_runner(config: &Config) -> HookRunner {
+    let mut runner = HookRunner::new();
+    crate::hooks::builtin::register_builtins(&mut runner, config);
+    let hooks_dir = resolve_hooks_dir(config);
+    if hooks_dir.is_dir() {
+        if let Ok(hooks) = load_hooks_from_dir(&hooks_dir, &config.hooks) {
+            let handlers = hooks
+                .into_iter()
+                .map(|hook| {
+                    Box::new(DynamicHookHandler::new(
+                        hook,
+                        config.hooks.default_timeout_secs,
+                    )) as Box<dyn HookHandler>
+                })
+                .collect();
+            runner.reload_dynamic_hooks(handlers).await;
+        }
+    }
+    runner
+}
+
+/// Render registered hooks as a table in dispatch order.
+fn format_hook_list(hooks: &[HookInfo]) -> String {
+    let mut out = format!("  {:<24} {:<10} {:<8}\n", "NAME", "PRIORITY", "SOURCE");
+    let _ = writeln!(out, "  {}", "-".repeat(44));
+    for h in hooks {
+        let _ = writeln!(
+            out,
+            "  {:<24} {:<10} {:<8}",
+            h.name,
+            h.priority,
+            h.source.to_string()
+        );
+    }
+    out
+}
+
+/// Render the per-hook results of a synthetic event.
+fn format_test_report(event: HookEvent, results: &[HookTestResult], execute: bool) -> String {
+    let mut out = if execute {
+        format!("Fired synthetic {event} to {} hook(s):\n", results.len())
+    } else {
+        format!(
+            "Dry run of {event} for {} hook(s) (pass --execute to run hook actions):\n",
+            results.len()
+        )
+    };
+    for r in results {
+        let duration = r
+            .duration
+            .map(|d| format!("{:.1}ms", d.as_secs_f64() * 1000.0))
+            .unwrap_or_else(|| "-".into());
+        let _ = writeln!(
+            out,
+            "  {:<24} {:<8} {:>10}  {}",
+            r.name,
+            r.source.to_string(),
+            duration,
+            r.outcome
+        );
+    }
+    out
+}
+
+/// `zeroclaw hooks list` — print registered hooks (builtin + dynamic) in
+/// dispatch order, followed by the manifests found on disk.
+async fn handle_list(config: &Config) -> Result<()> {
+    let runner = build_runner(config).await;
+    let registered = runner.describe().await;
+    if registered.is_empty() {
+        println!("Registered hooks: (none)");
+    } else {
+        println!("Registered hooks ({}):", registered.len());
+        print!("{}", format_hook_list(&registered));
+    }
     println!();
 
     // Dynamic hooks
@@ -84,6 +153,16 @@ fn handle_list(config: &Config) -> Result<()> {
 
     Ok(())
 }
+/// `zeroclaw hooks test <event> [--execute]` — report which hooks a
+/// synthetic event reaches, firing it at each one only with `--execute`.
+async fn handle_test(event: &str, execute: bool, config: &Config) -> Result<()> {
+    let event: HookEvent = serde_json::from_value(serde_json::Value::String(event.to_string()))
+        .map_err(|_| anyhow::anyhow!("Unknown hook event '{event}'"))?;
+    let runner = build_runner(config).await;
+    let results = runner.test_event(event, execute).await;
+    print!("{}", format_test_report(event, &results, execute));
+    Ok(())
+}
 /// `zeroclaw hooks reload` — write stamp file so daemon picks up changes.
 fn handle_reload(config: &Config) -> Result<()> {
     write_reload_stamp(&config.workspace_dir)?;
@@ -167,3 +246,124 @@ fn handle_audit(path: &str, config: &Config) -> Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::loader::LoadedHook;
+    use crate::hooks::manifest::{HookAction, HookManifest};
+    use crate::hooks::HookResult;
+    use async_trait::async_trait;
+
+    struct NamedHook {
+        name: &'static str,
+        priority: i32,
+        cancel: bool,
+    }
+
+    #[async_trait]
+    impl HookHandler for NamedHook {
+        fn name(&self) -> &str {
+            self.name
+        }
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+        async fn before_send(
+            &self,
+            _channel: &str,
+            _recipient: &str,
+            content: String,
+        ) -> HookResult<String> {
+            if self.cancel {
+                HookResult::Cancel("blocked".into())
+            } else {
+                HookResult::Continue(format!("{content} [signed]"))
+            }
+        }
+    }
+
+    async fn runner_with_hooks() -> HookRunner {
+        let mut runner = HookRunner::new();
+        runner.register(Box::new(NamedHook {
+            name: "signer",
+            priority: 5,
+            cancel: false,
+        }));
+        runner.register(Box::new(NamedHook {
+            name: "blocker",
+            priority: 1,
+            cancel: true,
+        }));
+        let dynamic = DynamicHookHandler::new(
+            LoadedHook {
+                manifest: HookManifest {
+                    name: "session-logger".into(),
+                    description: None,
+                    version: None,
+                    event: HookEvent::OnSessionStart,
+                    priority: 10,
+                    enabled: true,
+                    conditions: None,
+                    scope: None,
+                    action: HookAction::Shell {
+                        command: "true".into(),
+                        timeout_secs: None,
+                        workdir: None,
+                    },
+                    skip_security_audit: true,
+                },
+                hook_dir: std::env::temp_dir(),
+            },
+            30,
+        );
+        runner.reload_dynamic_hooks(vec![Box::new(dynamic)]).await;
+        runner
+    }
+
+    #[tokio::test]
+    async fn list_shows_hooks_in_dispatch_order_with_source() {
+        let runner = runner_with_hooks().await;
+        let table = format_hook_list(&runner.describe().await);
+        let lines: Vec<&str> = table.lines().skip(2).collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].contains("session-logger") && lines[0].contains("dynamic"));
+        assert!(lines[1].contains("signer") && lines[1].contains("static"));
+        assert!(lines[2].contains("blocker") && lines[2].contains('1'));
+    }
+
+    #[tokio::test]
+    async fn test_event_reports_each_hook_result() {
+        let runner = runner_with_hooks().await;
+        let results = runner.test_event(HookEvent::BeforeSend, true).await;
+        let outcomes: Vec<(&str, &str)> = results
+            .iter()
+            .map(|r| (r.name.as_str(), r.outcome.as_str()))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("session-logger", "skipped"),
+                ("signer", "modified"),
+                ("blocker", "cancelled: blocked"),
+            ]
+        );
+        assert!(results[0].duration.is_none());
+        assert!(results[1].duration.is_some());
+
+        let report = format_test_report(HookEvent::BeforeSend, &results, true);
+        assert!(report.starts_with("Fired synthetic before_send to 3 hook(s)"));
+    }
+
+    #[tokio::test]
+    async fn test_event_dry_run_fires_nothing() {
+        let runner = runner_with_hooks().await;
+        let results = runner.test_event(HookEvent::BeforeSend, false).await;
+        let outcomes: Vec<&str> = results.iter().map(|r| r.outcome.as_str()).collect();
+        assert_eq!(outcomes, vec!["skipped", "would fire", "would fire"]);
+        assert!(results.iter().all(|r| r.duration.is_none()));
+
+        let report = format_test_report(HookEvent::BeforeSend, &results, false);
+        assert!(report.contains("--execute"));
+    }
+}
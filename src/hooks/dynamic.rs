@@ -4,8 +4,9 @@ use std::time::Duration;
 use tracing::{debug, warn};
 
 use crate::channels::traits::ChannelMessage;
+use crate::config::Config;
 use crate::hooks::loader::LoadedHook;
-use crate::hooks::manifest::{HookAction, HookConditions, HookEvent};
+use crate::hooks::manifest::{HookAction, HookConditions, HookEvent, HookScope};
 use crate::hooks::traits::{HookHandler, HookResult};
 use crate::providers::traits::{ChatMessage, ChatResponse};
 use crate::tools::traits::ToolResult;
@@ -143,17 +144,17 @@ impl DynamicHookHandler {
         Ok(text)
     }
 
-    /// Fire a void hook action (fire-and-forget via spawn).
-    fn fire_void_action(&self) {
-        let hook = self.clone();
-        tokio::spawn(async move {
-            if let Err(e) = hook.execute_action().await {
-                warn!(
-                    hook = hook.hook.manifest.name.as_str(),
-                    "void hook action failed: {e}"
-                );
-            }
-        });
+    /// Run the action for a void event. The dispatcher awaits it (bounded by
+    /// the action timeout) so a failure shows up in its report.
+    async fn run_void_action(&self, event: HookEvent) -> anyhow::Result<()> {
+        if !self.matches_event(event) {
+            return Ok(());
+        }
+        debug!(hook = self.name(), "firing {event}");
+        self.execute_action()
+            .await
+            .map(|_| ())
+            .map_err(|e| anyhow::anyhow!("{event} action failed: {e}"))
     }
 
     /// Execute the hook's action and return the result string.
@@ -190,72 +191,83 @@ impl HookHandler for DynamicHookHandler {
         self.hook.manifest.priority
     }
 
+    fn scope(&self) -> Option<&HookScope> {
+        self.hook.manifest.scope.as_ref()
+    }
+
+    fn handles(&self, event: HookEvent) -> bool {
+        self.matches_event(event)
+    }
+
     // --- Void hooks (fire-and-forget) ---
 
-    async fn on_gateway_start(&self, _host: &str, _port: u16) {
-        if self.matches_event(HookEvent::OnGatewayStart) {
-            debug!(hook = self.name(), "firing on_gateway_start");
-            self.fire_void_action();
-        }
+    async fn on_gateway_start(&self, _host: &str, _port: u16) -> anyhow::Result<()> {
+        self.run_void_action(HookEvent::OnGatewayStart).await
     }
 
-    async fn on_gateway_stop(&self) {
-        if self.matches_event(HookEvent::OnGatewayStop) {
-            debug!(hook = self.name(), "firing on_gateway_stop");
-            self.fire_void_action();
-        }
+    async fn on_gateway_stop(&self) -> anyhow::Result<()> {
+        self.run_void_action(HookEvent::OnGatewayStop).await
     }
 
-    async fn on_session_start(&self, _session_id: &str, _channel: &str) {
-        if self.matches_event(HookEvent::OnSessionStart) {
-            debug!(hook = self.name(), "firing on_session_start");
-            self.fire_void_action();
-        }
+    async fn on_session_start(&self, _session_id: &str, _channel: &str) -> anyhow::Result<()> {
+        self.run_void_action(HookEvent::OnSessionStart).await
     }
 
-    async fn on_session_end(&self, _session_id: &str, _channel: &str) {
-        if self.matches_event(HookEvent::OnSessionEnd) {
-            debug!(hook = self.name(), "firing on_session_end");
-            self.fire_void_action();
-        }
+    async fn on_session_end(&self, _session_id: &str, _channel: &str) -> anyhow::Result<()> {
+        self.run_void_action(HookEvent::OnSessionEnd).await
     }
 
-    async fn on_llm_input(&self, _messages: &[ChatMessage], _model: &str) {
-        if self.matches_event(HookEvent::OnLlmInput) {
-            debug!(hook = self.name(), "firing on_llm_input");
-            self.fire_void_action();
-        }
+    async fn on_llm_input(&self, _messages: &[ChatMessage], _model: &str) -> anyhow::Result<()> {
+        self.run_void_action(HookEvent::OnLlmInput).await
     }
 
-    async fn on_llm_output(&self, _response: &ChatResponse) {
-        if self.matches_event(HookEvent::OnLlmOutput) {
-            debug!(hook = self.name(), "firing on_llm_output");
-            self.fire_void_action();
-        }
+    async fn on_llm_output(&self, _response: &ChatResponse) -> anyhow::Result<()> {
+        self.run_void_action(HookEvent::OnLlmOutput).await
     }
 
-    async fn on_after_tool_call(&self, _tool: &str, _result: &ToolResult, _duration: Duration) {
-        if self.matches_event(HookEvent::OnAfterToolCall) {
-            debug!(hook = self.name(), "firing on_after_tool_call");
-            self.fire_void_action();
-        }
+    async fn on_after_tool_call(
+        &self,
+        _tool: &str,
+        _result: &ToolResult,
+        _duration: Duration,
+    ) -> anyhow::Result<()> {
+        self.run_void_action(HookEvent::OnAfterToolCall).await
     }
 
-    async fn on_message_sent(&self, _channel: &str, _recipient: &str, _content: &str) {
-        if self.matches_event(HookEvent::OnMessageSent) {
-            debug!(hook = self.name(), "firing on_message_sent");
-            self.fire_void_action();
-        }
+    async fn on_message_sent(
+        &self,
+        _channel: &str,
+        _recipient: &str,
+        _content: &str,
+    ) -> anyhow::Result<()> {
+        self.run_void_action(HookEvent::OnMessageSent).await
     }
 
-    async fn on_heartbeat_tick(&self) {
-        if self.matches_event(HookEvent::OnHeartbeatTick) {
-            debug!(hook = self.name(), "firing on_heartbeat_tick");
-            self.fire_void_action();
-        }
+    async fn on_heartbeat_tick(&self) -> anyhow::Result<()> {
+        self.run_void_action(HookEvent::OnHeartbeatTick).await
+    }
+
+    async fn on_config_reload(&self, _config: &Config) -> anyhow::Result<()> {
+        self.run_void_action(HookEvent::OnConfigReload).await
     }
 
     // --- Modifying hooks (sequential by priority) ---
+    async fn before_session_start(&self, _session_id: &str, channel: &str) -> HookResult<()> {
+        if !self.matches_event(HookEvent::BeforeSessionStart) {
+            return HookResult::Continue(());
+        }
+        if !self.check_conditions(Some(channel), None, None) {
+            return HookResult::Continue(());
+        }
+        if let Err(e) = self.execute_action().await {
+            warn!(
+                hook = self.name(),
+                "before_session_start action failed: {e}"
+            );
+        }
+        HookResult::Continue(())
+    }
+
     async fn before_model_resolve(
         &self,
         provider: String,
@@ -434,6 +446,40 @@ impl HookHandler for DynamicHookHandler {
             }
         }
     }
+    async fn before_send(
+        &self,
+        channel: &str,
+        _recipient: &str,
+        content: String,
+    ) -> HookResult<String> {
+        if !self.matches_event(HookEvent::BeforeSend) {
+            return HookResult::Continue(content);
+        }
+        if !self.check_conditions(Some(channel), None, Some(&content)) {
+            return HookResult::Continue(content);
+        }
+        // PromptInject before send: append/prepend to the outbound content
+        if let HookAction::PromptInject {
+            content: inject,
+            position,
+        } = &self.hook.manifest.action
+        {
+            let pos = position.as_deref().unwrap_or("prepend");
+            let modified = if pos == "append" {
+                format!("{content}\n{inject}")
+            } else {
+                format!("{inject}\n{content}")
+            };
+            return HookResult::Continue(modified);
+        }
+        match self.execute_action().await {
+            Ok(_) => HookResult::Continue(content),
+            Err(e) => {
+                warn!(hook = self.name(), "before_send action failed: {e}");
+                HookResult::Continue(content)
+            }
+        }
+    }
 }
 
 // --- Helper functions ---
@@ -507,6 +553,7 @@ mod tests {
                 priority,
                 enabled: true,
                 conditions: None,
+                scope: None,
                 action,
                 skip_security_audit: true,
             },
@@ -602,6 +649,38 @@ mod tests {
         assert!(result.is_cancel());
     }
     #[tokio::test]
+    async fn failed_void_action_is_reported_by_dispatch() {
+        let tmp = std::env::temp_dir().to_string_lossy().into_owned();
+        let shell_in_tmp = |cmd: &str| HookAction::Shell {
+            command: cmd.to_string(),
+            timeout_secs: None,
+            workdir: Some(tmp.clone()),
+        };
+        let failing = make_hook(
+            "failing-hook",
+            HookEvent::OnHeartbeatTick,
+            0,
+            shell_in_tmp("exit 3"),
+        );
+        let healthy = make_hook(
+            "healthy-hook",
+            HookEvent::OnHeartbeatTick,
+            0,
+            shell_in_tmp("true"),
+        );
+        let runner = crate::hooks::HookRunner::new();
+        runner
+            .reload_dynamic_hooks(vec![
+                Box::new(DynamicHookHandler::new(failing, 30)),
+                Box::new(DynamicHookHandler::new(healthy, 30)),
+            ])
+            .await;
+
+        let report = runner.fire_heartbeat_tick().await;
+        assert_eq!(report.ran, 2);
+        assert_eq!(report.failed, vec!["failing-hook".to_string()]);
+    }
+    #[tokio::test]
     async fn prompt_inject_prepends_content() {
         let hook = make_hook(
             "inject-hook",
@@ -13,7 +13,7 @@ mod tests {
 
     use crate::hooks::dynamic::DynamicHookHandler;
     use crate::hooks::loader::LoadedHook;
-    use crate::hooks::manifest::{HookAction, HookConditions, HookEvent, HookManifest};
+    use crate::hooks::manifest::{HookAction, HookConditions, HookEvent, HookManifest, HookScope};
     use crate::hooks::reload::{check_reload_stamp, delete_reload_stamp, write_reload_stamp};
     use crate::hooks::runner::HookRunner;
     use crate::hooks::traits::{HookHandler, HookResult};
@@ -32,6 +32,7 @@ mod tests {
             priority,
             enabled: true,
             conditions: None,
+            scope: None,
             action: HookAction::Shell {
                 command: "echo ok".to_string(),
                 timeout_secs: Some(5),
@@ -335,4 +336,35 @@ mod tests {
             HookResult::Cancel(_) => panic!("discord message should pass through"),
         }
     }
+
+    // ---------------------------------------------------------------
+    // Test 7: Scope filtering — runner skips hooks scoped to other channels
+    // ---------------------------------------------------------------
+    #[tokio::test]
+    async fn scope_restricts_hook_to_channel() {
+        let dir = TempDir::new().unwrap();
+        let mut manifest = make_manifest("slack-signature", HookEvent::BeforeSend, 5);
+        manifest.scope = Some(HookScope {
+            channels: Some(vec!["slack".to_string()]),
+            events: None,
+        });
+        manifest.action = HookAction::PromptInject {
+            content: "-- sent from slack bot".to_string(),
+            position: Some("append".to_string()),
+        };
+        let handler = make_dynamic_handler(make_loaded_hook(manifest, &dir));
+        let runner = HookRunner::new();
+        runner.reload_dynamic_hooks(vec![handler]).await;
+
+        match runner.run_before_send("slack", "C1", "hi".into()).await {
+            HookResult::Continue(content) => {
+                assert_eq!(content, "hi\n-- sent from slack bot");
+            }
+            HookResult::Cancel(_) => panic!("should not cancel"),
+        }
+        match runner.run_before_send("lark", "oc_1", "hi".into()).await {
+            HookResult::Continue(content) => assert_eq!(content, "hi"),
+            HookResult::Cancel(_) => panic!("should not cancel"),
+        }
+    }
 }
@@ -118,6 +118,28 @@ pub fn load_hooks_from_dir(hooks_dir: &Path, config: &HooksConfig) -> Result<Vec
     Ok(hooks)
 }
 
+/// Parse every `HOOK.toml` under `hooks_dir` without loading anything.
+///
+/// Unlike [`load_hooks_from_dir`], which skips invalid hooks, this fails on the
+/// first manifest that cannot be read or parsed. Used before a reload so that a
+/// half-written edit keeps the currently loaded hooks in place.
+pub fn validate_hooks_dir(hooks_dir: &Path) -> Result<()> {
+    if !hooks_dir.is_dir() {
+        bail!("hooks path is not a directory: {}", hooks_dir.display());
+    }
+    for entry in std::fs::read_dir(hooks_dir)? {
+        let manifest_path = entry?.path().join("HOOK.toml");
+        if !manifest_path.is_file() {
+            continue;
+        }
+        let content = std::fs::read_to_string(&manifest_path)
+            .map_err(|e| anyhow::anyhow!("failed to read {}: {e}", manifest_path.display()))?;
+        HookManifest::from_toml(&content)
+            .map_err(|e| anyhow::anyhow!("{}: {e}", manifest_path.display()))?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,6 +153,9 @@ mod tests {
             skip_security_audit: true, // skip audit in tests for simplicity
             max_hooks: 50,
             default_timeout_secs: 30,
+            webhook: None,
+            event_log: None,
+            rate_limit: None,
         }
     }
 
@@ -185,6 +210,18 @@ command = "echo hello"
         assert_eq!(hooks[0].manifest.name, "good");
     }
     #[test]
+    fn validate_rejects_malformed_hook_toml() {
+        let dir = tempdir().unwrap();
+        write_hook_toml(dir.path(), "good", "on_session_start", 5);
+        validate_hooks_dir(dir.path()).unwrap();
+
+        let bad_dir = dir.path().join("bad-hook");
+        std::fs::create_dir_all(&bad_dir).unwrap();
+        std::fs::write(bad_dir.join("HOOK.toml"), "not valid toml {{{").unwrap();
+        let err = validate_hooks_dir(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("bad-hook"));
+    }
+    #[test]
     fn enforce_max_hooks_limit() {
         let dir = tempdir().unwrap();
         write_hook_toml(dir.path(), "hook-a", "on_session_start", 10);
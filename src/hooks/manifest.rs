@@ -17,7 +17,9 @@ pub enum HookEvent {
     OnAfterToolCall,
     OnMessageSent,
     OnHeartbeatTick,
+    OnConfigReload,
     // Modifying hooks (sequential by priority)
+    BeforeSessionStart,
     BeforeModelResolve,
     BeforePromptBuild,
     BeforeLlmCall,
@@ -26,6 +28,7 @@ pub enum HookEvent {
     OnMessageSending,
     OnCronDelivery,
     OnDocsSyncNotify,
+    BeforeSend,
 }
 
 impl fmt::Display for HookEvent {
@@ -40,6 +43,8 @@ impl fmt::Display for HookEvent {
             HookEvent::OnAfterToolCall => "on_after_tool_call",
             HookEvent::OnMessageSent => "on_message_sent",
             HookEvent::OnHeartbeatTick => "on_heartbeat_tick",
+            HookEvent::OnConfigReload => "on_config_reload",
+            HookEvent::BeforeSessionStart => "before_session_start",
             HookEvent::BeforeModelResolve => "before_model_resolve",
             HookEvent::BeforePromptBuild => "before_prompt_build",
             HookEvent::BeforeLlmCall => "before_llm_call",
@@ -48,6 +53,7 @@ impl fmt::Display for HookEvent {
             HookEvent::OnMessageSending => "on_message_sending",
             HookEvent::OnCronDelivery => "on_cron_delivery",
             HookEvent::OnDocsSyncNotify => "on_docs_sync_notify",
+            HookEvent::BeforeSend => "before_send",
         };
         write!(f, "{}", s)
     }
@@ -93,6 +99,35 @@ pub struct HookConditions {
     pub pattern: Option<String>,
 }
 
+/// Restricts a hook to specific events and/or channels. `HookRunner` skips
+/// handlers whose scope excludes the event or channel being dispatched.
+///
+/// `events` is only meaningful for built-in handlers that implement several
+/// events; HOOK.toml manifests already name a single event and reject it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HookScope {
+    #[serde(default)]
+    pub channels: Option<Vec<String>>,
+    #[serde(default)]
+    pub events: Option<Vec<HookEvent>>,
+}
+
+impl HookScope {
+    /// Whether this scope admits the given event on the given channel.
+    /// Events without channel context are only filtered by `events`.
+    pub fn allows(&self, event: HookEvent, channel: Option<&str>) -> bool {
+        if let Some(events) = &self.events {
+            if !events.contains(&event) {
+                return false;
+            }
+        }
+        match (&self.channels, channel) {
+            (Some(channels), Some(ch)) => channels.iter().any(|c| c == ch),
+            _ => true,
+        }
+    }
+}
+
 /// TOML wrapper: `[hook]` top-level key.
 #[derive(Debug, Clone, Deserialize)]
 struct HookManifestWrapper {
@@ -114,6 +149,8 @@ struct HookManifestInner {
     enabled: bool,
     #[serde(default)]
     conditions: Option<HookConditions>,
+    #[serde(default)]
+    scope: Option<HookScope>,
     action: HookAction,
     #[serde(default)]
     skip_security_audit: bool,
@@ -137,6 +174,8 @@ pub struct HookManifest {
     pub enabled: bool,
     #[serde(default)]
     pub conditions: Option<HookConditions>,
+    #[serde(default)]
+    pub scope: Option<HookScope>,
     pub action: HookAction,
     #[serde(default)]
     pub skip_security_audit: bool,
@@ -155,6 +194,7 @@ impl HookManifest {
             priority: inner.priority,
             enabled: inner.enabled,
             conditions: inner.conditions,
+            scope: inner.scope,
             action: inner.action,
             skip_security_audit: inner.skip_security_audit,
         };
@@ -166,6 +206,17 @@ impl HookManifest {
         if self.name.trim().is_empty() {
             bail!("hook name must not be empty");
         }
+        if let Some(scope) = &self.scope {
+            if scope.channels.as_ref().is_some_and(|c| c.is_empty()) {
+                bail!("scope.channels must not be empty");
+            }
+            if scope.events.is_some() {
+                bail!(
+                    "scope.events is not supported in HOOK.toml; the hook only runs for its event '{}'",
+                    self.event
+                );
+            }
+        }
         match &self.action {
             HookAction::Shell {
                 command,
@@ -355,6 +406,52 @@ command = "echo tick"
         assert!(m.description.is_none());
         assert!(m.version.is_none());
         assert!(m.conditions.is_none());
+        assert!(m.scope.is_none());
+    }
+    #[test]
+    fn parse_scope() {
+        let toml = r#"
+[hook]
+name = "slack-only"
+event = "before_send"
+[hook.scope]
+channels = ["slack"]
+[hook.action.shell]
+command = "echo slack"
+"#;
+        let m = HookManifest::from_toml(toml).unwrap();
+        let scope = m.scope.unwrap();
+        assert!(scope.allows(HookEvent::BeforeSend, Some("slack")));
+        assert!(!scope.allows(HookEvent::BeforeSend, Some("lark")));
+        // No channel context: the hook is not filtered
+        assert!(scope.allows(HookEvent::BeforeSend, None));
+    }
+    #[test]
+    fn reject_scope_events() {
+        let toml = r#"
+[hook]
+name = "bad-scope"
+event = "before_send"
+[hook.scope]
+events = ["before_send"]
+[hook.action.shell]
+command = "echo nope"
+"#;
+        let err = HookManifest::from_toml(toml).unwrap_err();
+        assert!(err.to_string().contains("scope.events"));
+    }
+    #[test]
+    fn reject_empty_scope_channels() {
+        let toml = r#"
+[hook]
+name = "empty-scope"
+event = "before_send"
+[hook.scope]
+channels = []
+[hook.action.shell]
+command = "echo nope"
+"#;
+        assert!(HookManifest::from_toml(toml).is_err());
     }
     #[test]
     fn display_hook_event() {
@@ -5,17 +5,23 @@ pub mod dynamic;
 pub mod loader;
 pub mod manifest;
 pub mod reload;
+#[cfg(feature = "hooks-watch")]
+pub mod watcher;
 pub use dynamic::*;
 pub use loader::*;
 pub use manifest::*;
 mod runner;
 mod traits;
 
-pub use runner::HookRunner;
+pub use runner::{HookInfo, HookRunner, HookTestResult};
+// Returned by the void dispatchers; callers are free to ignore it, so the
+// binary may not name it.
+#[allow(unused_imports)]
+pub use runner::VoidDispatchReport;
 // HookHandler and HookResult are part of the crate's public hook API surface.
 // They may appear unused internally but are intentionally re-exported for
 // external integrations and future plugin authors.
 #[allow(unused_imports)]
-pub use traits::{HookHandler, HookResult};
+pub use traits::{HookContext, HookHandler, HookResult};
 #[cfg(test)]
 mod integration_tests;
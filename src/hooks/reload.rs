@@ -79,7 +79,7 @@ pub async fn do_reload_hooks(
     .await;
 
     match result {
-        Ok(Ok(())) => {
+        Ok(Ok(_)) => {
             tracing::info!("Dynamic hooks reloaded successfully");
             // Delete stamp on success; preserve on failure to allow retry next cycle.
             if let Err(err) = delete_reload_stamp(workspace_dir) {
@@ -95,11 +95,25 @@ pub async fn do_reload_hooks(
     }
 }
 
+/// Reload dynamic hooks only if every manifest in `hooks_dir` parses.
+///
+/// On a validation error the currently loaded set is left untouched, so a bad
+/// edit to one HOOK.toml does not drop the hooks that were working. Returns the
+/// number of hooks loaded.
+pub async fn reload_hooks_validated(
+    hooks_dir: &Path,
+    hooks_config: &crate::config::schema::HooksConfig,
+    hook_runner: &Arc<crate::hooks::HookRunner>,
+) -> Result<usize> {
+    crate::hooks::loader::validate_hooks_dir(hooks_dir)?;
+    reload_dynamic_hooks_inner(hooks_dir, hooks_config, hook_runner).await
+}
+
 async fn reload_dynamic_hooks_inner(
     hooks_dir: &Path,
     hooks_config: &crate::config::schema::HooksConfig,
     hook_runner: &Arc<crate::hooks::HookRunner>,
-) -> Result<()> {
+) -> Result<usize> {
     let loaded = crate::hooks::loader::load_hooks_from_dir(hooks_dir, hooks_config)?;
     let handlers: Vec<Box<dyn crate::hooks::traits::HookHandler>> = loaded
         .into_iter()
@@ -110,8 +124,9 @@ async fn reload_dynamic_hooks_inner(
             )) as Box<dyn crate::hooks::traits::HookHandler>
         })
         .collect();
+    let count = handlers.len();
     hook_runner.reload_dynamic_hooks(handlers).await;
-    Ok(())
+    Ok(count)
 }
 
 #[cfg(test)]
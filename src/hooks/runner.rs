@@ -1,5 +1,7 @@
+use std::fmt;
+use std::future::Future;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use futures_util::{future::join_all, FutureExt};
 use serde_json::Value;
@@ -8,14 +10,17 @@ use tokio::sync::RwLock;
 use tracing::info;
 
 use crate::channels::traits::ChannelMessage;
+use crate::config::Config;
 use crate::providers::traits::{ChatMessage, ChatResponse};
 use crate::tools::traits::ToolResult;
 
-use super::traits::{HookHandler, HookResult};
+use super::manifest::HookEvent;
+use super::traits::{HookContext, HookHandler, HookResult};
 
 /// Dispatcher that manages registered hook handlers.
 ///
-/// Void hooks are dispatched in parallel via `join_all`.
+/// Void hooks are dispatched in parallel via `join_all`; a handler that fails
+/// or panics is isolated and reported in the returned [`VoidDispatchReport`].
 /// Modifying hooks run sequentially by priority (higher first), piping output
 /// and short-circuiting on `Cancel`.
 ///
@@ -26,6 +31,103 @@ pub struct HookRunner {
     dynamic_handlers: Arc<RwLock<Vec<Box<dyn HookHandler>>>>,
 }
 
+/// Where a registered handler came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookSource {
+    Static,
+    Dynamic,
+}
+
+impl fmt::Display for HookSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HookSource::Static => write!(f, "static"),
+            HookSource::Dynamic => write!(f, "dynamic"),
+        }
+    }
+}
+
+/// A registered handler as reported by [`HookRunner::describe`].
+#[derive(Debug, Clone)]
+pub struct HookInfo {
+    pub name: String,
+    pub priority: i32,
+    pub source: HookSource,
+}
+
+/// Outcome of dispatching a synthetic event to a single handler.
+#[derive(Debug, Clone)]
+pub struct HookTestResult {
+    pub name: String,
+    pub source: HookSource,
+    /// `None` when the handler was skipped (event or scope mismatch) or
+    /// not fired in a dry run.
+    pub duration: Option<Duration>,
+    pub outcome: String,
+}
+
+/// Outcome of a void hook dispatch. Callers may ignore it; failures are
+/// already logged as one consolidated warning.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VoidDispatchReport {
+    /// Number of handlers the event was dispatched to.
+    pub ran: usize,
+    /// Names of handlers that returned an error or panicked.
+    pub failed: Vec<String>,
+}
+
+impl VoidDispatchReport {
+    pub fn all_succeeded(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Run named void-hook futures concurrently, isolating errors and panics so
+/// one failing handler neither stops the others nor unwinds into the caller.
+async fn join_void<F>(event: HookEvent, futs: Vec<(&str, F)>) -> VoidDispatchReport
+where
+    F: Future<Output = anyhow::Result<()>>,
+{
+    let ran = futs.len();
+    let (names, futs): (Vec<&str>, Vec<F>) = futs.into_iter().unzip();
+    let results = join_all(
+        futs.into_iter()
+            .map(|fut| AssertUnwindSafe(fut).catch_unwind()),
+    )
+    .await;
+    let mut failed = Vec::new();
+    for (name, result) in names.into_iter().zip(results) {
+        match result {
+            Ok(Ok(())) => continue,
+            Ok(Err(e)) => tracing::warn!(hook = name, "{event} hook failed: {e:#}"),
+            Err(_) => tracing::warn!(hook = name, "{event} hook panicked"),
+        }
+        failed.push(name.to_string());
+    }
+    if !failed.is_empty() {
+        tracing::warn!(
+            event = %event,
+            ran,
+            failed = failed.len(),
+            "{event} hooks failed: {}",
+            failed.join(", ")
+        );
+    }
+    VoidDispatchReport { ran, failed }
+}
+
+/// Channel name used for synthetic events fired by [`HookRunner::test_event`].
+pub const TEST_CHANNEL: &str = "hooks-test";
+
+/// Whether a handler's declared scope admits this event/channel combination.
+/// Handlers without a scope are dispatched for everything.
+fn in_scope(handler: &dyn HookHandler, event: HookEvent, channel: Option<&str>) -> bool {
+    handler.handles(event)
+        && handler
+            .scope()
+            .is_none_or(|scope| scope.allows(event, channel))
+}
+
 impl HookRunner {
     /// Create an empty runner with no handlers.
     pub fn new() -> Self {
@@ -54,127 +156,227 @@ impl HookRunner {
     // Void dispatchers (parallel, fire-and-forget)
     // ---------------------------------------------------------------
 
-    pub async fn fire_gateway_start(&self, host: &str, port: u16) {
+    pub async fn fire_gateway_start(&self, host: &str, port: u16) -> VoidDispatchReport {
+        let dynamic = self.dynamic_handlers.read().await;
+        let futs: Vec<_> = self
+            .static_handlers
+            .iter()
+            .chain(dynamic.iter())
+            .filter(|h| in_scope(h.as_ref(), HookEvent::OnGatewayStart, None))
+            .map(|h| (h.name(), h.on_gateway_start(host, port)))
+            .collect();
+        join_void(HookEvent::OnGatewayStart, futs).await
+    }
+
+    pub async fn fire_gateway_stop(&self) -> VoidDispatchReport {
         let dynamic = self.dynamic_handlers.read().await;
         let futs: Vec<_> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
-            .map(|h| h.on_gateway_start(host, port))
+            .filter(|h| in_scope(h.as_ref(), HookEvent::OnGatewayStop, None))
+            .map(|h| (h.name(), h.on_gateway_stop()))
             .collect();
-        join_all(futs).await;
+        join_void(HookEvent::OnGatewayStop, futs).await
     }
 
-    pub async fn fire_gateway_stop(&self) {
+    pub async fn fire_session_start(&self, session_id: &str, channel: &str) -> VoidDispatchReport {
         let dynamic = self.dynamic_handlers.read().await;
         let futs: Vec<_> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
-            .map(|h| h.on_gateway_stop())
+            .filter(|h| in_scope(h.as_ref(), HookEvent::OnSessionStart, Some(channel)))
+            .map(|h| (h.name(), h.on_session_start(session_id, channel)))
             .collect();
-        join_all(futs).await;
+        join_void(HookEvent::OnSessionStart, futs).await
     }
 
-    pub async fn fire_session_start(&self, session_id: &str, channel: &str) {
+    pub async fn fire_session_end(&self, session_id: &str, channel: &str) -> VoidDispatchReport {
         let dynamic = self.dynamic_handlers.read().await;
         let futs: Vec<_> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
-            .map(|h| h.on_session_start(session_id, channel))
+            .filter(|h| in_scope(h.as_ref(), HookEvent::OnSessionEnd, Some(channel)))
+            .map(|h| (h.name(), h.on_session_end(session_id, channel)))
             .collect();
-        join_all(futs).await;
+        join_void(HookEvent::OnSessionEnd, futs).await
     }
 
-    pub async fn fire_session_end(&self, session_id: &str, channel: &str) {
+    pub async fn fire_llm_input(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+    ) -> VoidDispatchReport {
         let dynamic = self.dynamic_handlers.read().await;
         let futs: Vec<_> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
-            .map(|h| h.on_session_end(session_id, channel))
+            .filter(|h| in_scope(h.as_ref(), HookEvent::OnLlmInput, None))
+            .map(|h| (h.name(), h.on_llm_input(messages, model)))
             .collect();
-        join_all(futs).await;
+        join_void(HookEvent::OnLlmInput, futs).await
     }
 
-    pub async fn fire_llm_input(&self, messages: &[ChatMessage], model: &str) {
+    pub async fn fire_llm_output(&self, response: &ChatResponse) -> VoidDispatchReport {
         let dynamic = self.dynamic_handlers.read().await;
         let futs: Vec<_> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
-            .map(|h| h.on_llm_input(messages, model))
+            .filter(|h| in_scope(h.as_ref(), HookEvent::OnLlmOutput, None))
+            .map(|h| (h.name(), h.on_llm_output(response)))
             .collect();
-        join_all(futs).await;
+        join_void(HookEvent::OnLlmOutput, futs).await
     }
 
-    pub async fn fire_llm_output(&self, response: &ChatResponse) {
+    pub async fn fire_after_tool_call(
+        &self,
+        tool: &str,
+        result: &ToolResult,
+        duration: Duration,
+    ) -> VoidDispatchReport {
         let dynamic = self.dynamic_handlers.read().await;
         let futs: Vec<_> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
-            .map(|h| h.on_llm_output(response))
+            .filter(|h| in_scope(h.as_ref(), HookEvent::OnAfterToolCall, None))
+            .map(|h| (h.name(), h.on_after_tool_call(tool, result, duration)))
             .collect();
-        join_all(futs).await;
+        join_void(HookEvent::OnAfterToolCall, futs).await
     }
 
-    pub async fn fire_after_tool_call(&self, tool: &str, result: &ToolResult, duration: Duration) {
+    pub async fn fire_message_sent(
+        &self,
+        channel: &str,
+        recipient: &str,
+        content: &str,
+    ) -> VoidDispatchReport {
         let dynamic = self.dynamic_handlers.read().await;
         let futs: Vec<_> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
-            .map(|h| h.on_after_tool_call(tool, result, duration))
+            .filter(|h| in_scope(h.as_ref(), HookEvent::OnMessageSent, Some(channel)))
+            .map(|h| (h.name(), h.on_message_sent(channel, recipient, content)))
             .collect();
-        join_all(futs).await;
+        join_void(HookEvent::OnMessageSent, futs).await
     }
 
-    pub async fn fire_message_sent(&self, channel: &str, recipient: &str, content: &str) {
+    pub async fn fire_heartbeat_tick(&self) -> VoidDispatchReport {
         let dynamic = self.dynamic_handlers.read().await;
         let futs: Vec<_> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
-            .map(|h| h.on_message_sent(channel, recipient, content))
+            .filter(|h| in_scope(h.as_ref(), HookEvent::OnHeartbeatTick, None))
+            .map(|h| (h.name(), h.on_heartbeat_tick()))
             .collect();
-        join_all(futs).await;
+        join_void(HookEvent::OnHeartbeatTick, futs).await
     }
 
-    pub async fn fire_heartbeat_tick(&self) {
+    pub async fn fire_config_reload(&self, config: &Config) -> VoidDispatchReport {
         let dynamic = self.dynamic_handlers.read().await;
         let futs: Vec<_> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
-            .map(|h| h.on_heartbeat_tick())
+            .filter(|h| in_scope(h.as_ref(), HookEvent::OnConfigReload, None))
+            .map(|h| (h.name(), h.on_config_reload(config)))
             .collect();
-        join_all(futs).await;
+        join_void(HookEvent::OnConfigReload, futs).await
     }
 
     // ---------------------------------------------------------------
     // Modifying dispatchers (sequential by priority, short-circuit on Cancel)
     // ---------------------------------------------------------------
 
+    /// Ask handlers whether a session may start. `Cancel` means the session
+    /// must be rejected before any of it is handled.
+    pub async fn run_before_session_start(
+        &self,
+        session_id: &str,
+        channel: &str,
+    ) -> HookResult<()> {
+        self.run_before_session_start_with_context(&HookContext::session(session_id, channel))
+            .await
+    }
+
+    pub async fn run_before_session_start_with_context(&self, ctx: &HookContext) -> HookResult<()> {
+        let dynamic = self.dynamic_handlers.read().await;
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::global().hook_timer(HookEvent::BeforeSessionStart);
+        let mut all: Vec<&dyn HookHandler> = self
+            .static_handlers
+            .iter()
+            .chain(dynamic.iter())
+            .map(|h| h.as_ref())
+            .filter(|h| in_scope(*h, HookEvent::BeforeSessionStart, ctx.channel.as_deref()))
+            .collect();
+        all.sort_by_key(|h| std::cmp::Reverse(h.priority()));
+        for h in &all {
+            let hook_name = h.name();
+            match AssertUnwindSafe(h.before_session_start_with_context(ctx))
+                .catch_unwind()
+                .await
+            {
+                Ok(HookResult::Continue(())) => {}
+                Ok(HookResult::Cancel(reason)) => {
+                    info!(
+                        hook = hook_name,
+                        reason, "before_session_start cancelled by hook"
+                    );
+                    return HookResult::Cancel(reason);
+                }
+                Err(_) => {
+                    tracing::error!(
+                        hook = hook_name,
+                        "before_session_start hook panicked; allowing session"
+                    );
+                }
+            }
+        }
+        HookResult::Continue(())
+    }
+
     pub async fn run_before_model_resolve(
         &self,
+        provider: String,
+        model: String,
+    ) -> HookResult<(String, String)> {
+        self.run_before_model_resolve_with_context(&HookContext::default(), provider, model)
+            .await
+    }
+
+    pub async fn run_before_model_resolve_with_context(
+        &self,
+        ctx: &HookContext,
         mut provider: String,
         mut model: String,
     ) -> HookResult<(String, String)> {
         let dynamic = self.dynamic_handlers.read().await;
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::global().hook_timer(HookEvent::BeforeModelResolve);
         let mut all: Vec<&dyn HookHandler> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
             .map(|h| h.as_ref())
+            .filter(|h| in_scope(*h, HookEvent::BeforeModelResolve, ctx.channel.as_deref()))
             .collect();
         all.sort_by_key(|h| std::cmp::Reverse(h.priority()));
         for h in &all {
             let hook_name = h.name();
-            match AssertUnwindSafe(h.before_model_resolve(provider.clone(), model.clone()))
-                .catch_unwind()
-                .await
+            match AssertUnwindSafe(h.before_model_resolve_with_context(
+                ctx,
+                provider.clone(),
+                model.clone(),
+            ))
+            .catch_unwind()
+            .await
             {
                 Ok(HookResult::Continue((p, m))) => {
                     provider = p;
@@ -198,18 +400,30 @@ impl HookRunner {
         HookResult::Continue((provider, model))
     }
 
-    pub async fn run_before_prompt_build(&self, mut prompt: String) -> HookResult<String> {
+    pub async fn run_before_prompt_build(&self, prompt: String) -> HookResult<String> {
+        self.run_before_prompt_build_with_context(&HookContext::default(), prompt)
+            .await
+    }
+
+    pub async fn run_before_prompt_build_with_context(
+        &self,
+        ctx: &HookContext,
+        mut prompt: String,
+    ) -> HookResult<String> {
         let dynamic = self.dynamic_handlers.read().await;
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::global().hook_timer(HookEvent::BeforePromptBuild);
         let mut all: Vec<&dyn HookHandler> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
             .map(|h| h.as_ref())
+            .filter(|h| in_scope(*h, HookEvent::BeforePromptBuild, ctx.channel.as_deref()))
             .collect();
         all.sort_by_key(|h| std::cmp::Reverse(h.priority()));
         for h in &all {
             let hook_name = h.name();
-            match AssertUnwindSafe(h.before_prompt_build(prompt.clone()))
+            match AssertUnwindSafe(h.before_prompt_build_with_context(ctx, prompt.clone()))
                 .catch_unwind()
                 .await
             {
@@ -234,22 +448,39 @@ impl HookRunner {
 
     pub async fn run_before_llm_call(
         &self,
+        messages: Vec<ChatMessage>,
+        model: String,
+    ) -> HookResult<(Vec<ChatMessage>, String)> {
+        self.run_before_llm_call_with_context(&HookContext::default(), messages, model)
+            .await
+    }
+
+    pub async fn run_before_llm_call_with_context(
+        &self,
+        ctx: &HookContext,
         mut messages: Vec<ChatMessage>,
         mut model: String,
     ) -> HookResult<(Vec<ChatMessage>, String)> {
         let dynamic = self.dynamic_handlers.read().await;
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::global().hook_timer(HookEvent::BeforeLlmCall);
         let mut all: Vec<&dyn HookHandler> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
             .map(|h| h.as_ref())
+            .filter(|h| in_scope(*h, HookEvent::BeforeLlmCall, ctx.channel.as_deref()))
             .collect();
         all.sort_by_key(|h| std::cmp::Reverse(h.priority()));
         for h in &all {
             let hook_name = h.name();
-            match AssertUnwindSafe(h.before_llm_call(messages.clone(), model.clone()))
-                .catch_unwind()
-                .await
+            match AssertUnwindSafe(h.before_llm_call_with_context(
+                ctx,
+                messages.clone(),
+                model.clone(),
+            ))
+            .catch_unwind()
+            .await
             {
                 Ok(HookResult::Continue((m, mdl))) => {
                     messages = m;
@@ -275,20 +506,33 @@ impl HookRunner {
 
     pub async fn run_before_tool_call(
         &self,
+        name: String,
+        args: Value,
+    ) -> HookResult<(String, Value)> {
+        self.run_before_tool_call_with_context(&HookContext::default(), name, args)
+            .await
+    }
+
+    pub async fn run_before_tool_call_with_context(
+        &self,
+        ctx: &HookContext,
         mut name: String,
         mut args: Value,
     ) -> HookResult<(String, Value)> {
         let dynamic = self.dynamic_handlers.read().await;
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::global().hook_timer(HookEvent::BeforeToolCall);
         let mut all: Vec<&dyn HookHandler> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
             .map(|h| h.as_ref())
+            .filter(|h| in_scope(*h, HookEvent::BeforeToolCall, ctx.channel.as_deref()))
             .collect();
         all.sort_by_key(|h| std::cmp::Reverse(h.priority()));
         for h in &all {
             let hook_name = h.name();
-            match AssertUnwindSafe(h.before_tool_call(name.clone(), args.clone()))
+            match AssertUnwindSafe(h.before_tool_call_with_context(ctx, name.clone(), args.clone()))
                 .catch_unwind()
                 .await
             {
@@ -319,11 +563,14 @@ impl HookRunner {
         mut message: ChannelMessage,
     ) -> HookResult<ChannelMessage> {
         let dynamic = self.dynamic_handlers.read().await;
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::global().hook_timer(HookEvent::OnMessageReceived);
         let mut all: Vec<&dyn HookHandler> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
             .map(|h| h.as_ref())
+            .filter(|h| in_scope(*h, HookEvent::OnMessageReceived, Some(&message.channel)))
             .collect();
         all.sort_by_key(|h| std::cmp::Reverse(h.priority()));
         for h in &all {
@@ -358,11 +605,14 @@ impl HookRunner {
         mut content: String,
     ) -> HookResult<(String, String, String)> {
         let dynamic = self.dynamic_handlers.read().await;
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::global().hook_timer(HookEvent::OnMessageSending);
         let mut all: Vec<&dyn HookHandler> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
             .map(|h| h.as_ref())
+            .filter(|h| in_scope(*h, HookEvent::OnMessageSending, Some(&channel)))
             .collect();
         all.sort_by_key(|h| std::cmp::Reverse(h.priority()));
         for h in &all {
@@ -406,11 +656,14 @@ impl HookRunner {
         mut content: String,
     ) -> HookResult<(String, String, String, String)> {
         let dynamic = self.dynamic_handlers.read().await;
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::global().hook_timer(HookEvent::OnCronDelivery);
         let mut all: Vec<&dyn HookHandler> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
             .map(|h| h.as_ref())
+            .filter(|h| in_scope(*h, HookEvent::OnCronDelivery, Some(&channel)))
             .collect();
         all.sort_by_key(|h| std::cmp::Reverse(h.priority()));
         for h in &all {
@@ -455,11 +708,14 @@ impl HookRunner {
         mut content: String,
     ) -> HookResult<(String, String, String, String)> {
         let dynamic = self.dynamic_handlers.read().await;
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::global().hook_timer(HookEvent::OnDocsSyncNotify);
         let mut all: Vec<&dyn HookHandler> = self
             .static_handlers
             .iter()
             .chain(dynamic.iter())
             .map(|h| h.as_ref())
+            .filter(|h| in_scope(*h, HookEvent::OnDocsSyncNotify, Some(&channel)))
             .collect();
         all.sort_by_key(|h| std::cmp::Reverse(h.priority()));
         for h in &all {
@@ -496,6 +752,240 @@ impl HookRunner {
         }
         HookResult::Continue((file_path, channel, recipient, content))
     }
+
+    /// Last chance to rewrite or block outbound content right before it
+    /// reaches a channel. `Cancel` means the message must not be sent.
+    pub async fn run_before_send(
+        &self,
+        channel: &str,
+        recipient: &str,
+        mut content: String,
+    ) -> HookResult<String> {
+        let dynamic = self.dynamic_handlers.read().await;
+        #[cfg(feature = "metrics")]
+        let _timer = crate::metrics::global().hook_timer(HookEvent::BeforeSend);
+        let mut all: Vec<&dyn HookHandler> = self
+            .static_handlers
+            .iter()
+            .chain(dynamic.iter())
+            .map(|h| h.as_ref())
+            .filter(|h| in_scope(*h, HookEvent::BeforeSend, Some(channel)))
+            .collect();
+        all.sort_by_key(|h| std::cmp::Reverse(h.priority()));
+        for h in &all {
+            let hook_name = h.name();
+            match AssertUnwindSafe(h.before_send(channel, recipient, content.clone()))
+                .catch_unwind()
+                .await
+            {
+                Ok(HookResult::Continue(ct)) => content = ct,
+                Ok(HookResult::Cancel(reason)) => {
+                    info!(hook = hook_name, reason, "before_send cancelled by hook");
+                    return HookResult::Cancel(reason);
+                }
+                Err(_) => {
+                    tracing::error!(
+                        hook = hook_name,
+                        "before_send hook panicked; continuing with previous value"
+                    );
+                }
+            }
+        }
+        HookResult::Continue(content)
+    }
+
+    // ---------------------------------------------------------------
+    // Diagnostics
+    // ---------------------------------------------------------------
+
+    /// List every registered handler in dispatch order (descending priority).
+    pub async fn describe(&self) -> Vec<HookInfo> {
+        let dynamic = self.dynamic_handlers.read().await;
+        let mut infos: Vec<HookInfo> = self
+            .static_handlers
+            .iter()
+            .map(|h| (h, HookSource::Static))
+            .chain(dynamic.iter().map(|h| (h, HookSource::Dynamic)))
+            .map(|(h, source)| HookInfo {
+                name: h.name().to_string(),
+                priority: h.priority(),
+                source,
+            })
+            .collect();
+        infos.sort_by_key(|info| std::cmp::Reverse(info.priority));
+        infos
+    }
+
+    /// Fire a synthetic `event` at each handler individually, in priority
+    /// order, and report what each one did. Cancellation does not stop the
+    /// remaining handlers so the whole chain is visible.
+    ///
+    /// Unless `execute` is set nothing is fired: handlers in scope are only
+    /// reported as "would fire", so shell and HTTP actions never run.
+    pub async fn test_event(&self, event: HookEvent, execute: bool) -> Vec<HookTestResult> {
+        let dynamic = self.dynamic_handlers.read().await;
+        let mut all: Vec<(&dyn HookHandler, HookSource)> = self
+            .static_handlers
+            .iter()
+            .map(|h| (h.as_ref(), HookSource::Static))
+            .chain(dynamic.iter().map(|h| (h.as_ref(), HookSource::Dynamic)))
+            .collect();
+        all.sort_by_key(|(h, _)| std::cmp::Reverse(h.priority()));
+
+        let mut results = Vec::with_capacity(all.len());
+        for (h, source) in all {
+            if !in_scope(h, event, Some(TEST_CHANNEL)) {
+                results.push(HookTestResult {
+                    name: h.name().to_string(),
+                    source,
+                    duration: None,
+                    outcome: "skipped".into(),
+                });
+                continue;
+            }
+            if !execute {
+                results.push(HookTestResult {
+                    name: h.name().to_string(),
+                    source,
+                    duration: None,
+                    outcome: "would fire".into(),
+                });
+                continue;
+            }
+            let started = Instant::now();
+            let outcome = AssertUnwindSafe(fire_synthetic(h, event))
+                .catch_unwind()
+                .await
+                .unwrap_or_else(|_| "panicked".into());
+            results.push(HookTestResult {
+                name: h.name().to_string(),
+                source,
+                duration: Some(started.elapsed()),
+                outcome,
+            });
+        }
+        results
+    }
+}
+
+/// Describe a modifying hook's result relative to its (Debug-formatted) input.
+fn describe_outcome<T: fmt::Debug>(input: &str, result: HookResult<T>) -> String {
+    match result {
+        HookResult::Cancel(reason) => format!("cancelled: {reason}"),
+        HookResult::Continue(out) if format!("{out:?}") == input => "continued".into(),
+        HookResult::Continue(_) => "modified".into(),
+    }
+}
+
+/// Dispatch a synthetic payload for `event` to a single handler.
+async fn fire_synthetic(h: &dyn HookHandler, event: HookEvent) -> String {
+    let channel = TEST_CHANNEL.to_string();
+    let ctx = HookContext::session("hooks-test", TEST_CHANNEL).with_model("test-model");
+    let recipient = "hooks-test-recipient".to_string();
+    let content = "hooks test message".to_string();
+    let fired = match event {
+        HookEvent::OnGatewayStart => h.on_gateway_start("127.0.0.1", 0).await,
+        HookEvent::OnGatewayStop => h.on_gateway_stop().await,
+        HookEvent::OnSessionStart => h.on_session_start("hooks-test", &channel).await,
+        HookEvent::OnSessionEnd => h.on_session_end("hooks-test", &channel).await,
+        HookEvent::OnLlmInput => {
+            h.on_llm_input(&[ChatMessage::user(&content)], "test-model")
+                .await
+        }
+        HookEvent::OnLlmOutput => {
+            let response = ChatResponse {
+                text: Some(content),
+                tool_calls: Vec::new(),
+                usage: None,
+                reasoning_content: None,
+            };
+            h.on_llm_output(&response).await
+        }
+        HookEvent::OnAfterToolCall => {
+            let result = ToolResult {
+                success: true,
+                output: content,
+                error: None,
+            };
+            h.on_after_tool_call("shell", &result, Duration::ZERO).await
+        }
+        HookEvent::OnMessageSent => h.on_message_sent(&channel, &recipient, &content).await,
+        HookEvent::OnHeartbeatTick => h.on_heartbeat_tick().await,
+        HookEvent::OnConfigReload => h.on_config_reload(&Config::default()).await,
+        // Modifying hooks report how they treated the payload.
+        HookEvent::BeforeSessionStart => {
+            return describe_outcome("()", h.before_session_start("hooks-test", &channel).await);
+        }
+        HookEvent::BeforeModelResolve => {
+            let input = ("test-provider".to_string(), "test-model".to_string());
+            let debug = format!("{input:?}");
+            let result = h
+                .before_model_resolve_with_context(&ctx, input.0, input.1)
+                .await;
+            return describe_outcome(&debug, result);
+        }
+        HookEvent::BeforePromptBuild => {
+            let debug = format!("{content:?}");
+            let result = h.before_prompt_build_with_context(&ctx, content).await;
+            return describe_outcome(&debug, result);
+        }
+        HookEvent::BeforeLlmCall => {
+            let input = (vec![ChatMessage::user(&content)], "test-model".to_string());
+            let debug = format!("{input:?}");
+            let result = h.before_llm_call_with_context(&ctx, input.0, input.1).await;
+            return describe_outcome(&debug, result);
+        }
+        HookEvent::BeforeToolCall => {
+            let args = serde_json::json!({"command": "echo test"});
+            let debug = format!("{:?}", ("shell".to_string(), &args));
+            let result = h
+                .before_tool_call_with_context(&ctx, "shell".into(), args)
+                .await;
+            return describe_outcome(&debug, result);
+        }
+        HookEvent::OnMessageReceived => {
+            let message = ChannelMessage {
+                id: "hooks-test".into(),
+                sender: recipient.clone(),
+                reply_target: recipient,
+                content,
+                channel,
+                timestamp: 0,
+                thread_ts: None,
+            };
+            let debug = format!("{message:?}");
+            return describe_outcome(&debug, h.on_message_received(message).await);
+        }
+        HookEvent::OnMessageSending => {
+            let debug = format!("{:?}", (&channel, &recipient, &content));
+            let result = h.on_message_sending(channel, recipient, content).await;
+            return describe_outcome(&debug, result);
+        }
+        HookEvent::OnCronDelivery => {
+            let source = "hooks-test-job".to_string();
+            let debug = format!("{:?}", (&source, &channel, &recipient, &content));
+            let result = h
+                .on_cron_delivery(source, channel, recipient, content)
+                .await;
+            return describe_outcome(&debug, result);
+        }
+        HookEvent::OnDocsSyncNotify => {
+            let file_path = "hooks-test.md".to_string();
+            let debug = format!("{:?}", (&file_path, &channel, &recipient, &content));
+            let result = h
+                .on_docs_sync_notify(file_path, channel, recipient, content)
+                .await;
+            return describe_outcome(&debug, result);
+        }
+        HookEvent::BeforeSend => {
+            let debug = format!("{content:?}");
+            return describe_outcome(&debug, h.before_send(&channel, &recipient, content).await);
+        }
+    };
+    match fired {
+        Ok(()) => "fired".into(),
+        Err(e) => format!("failed: {e:#}"),
+    }
 }
 
 #[cfg(test)]
@@ -534,8 +1024,9 @@ mod tests {
         fn priority(&self) -> i32 {
             self.priority
         }
-        async fn on_heartbeat_tick(&self) {
+        async fn on_heartbeat_tick(&self) -> anyhow::Result<()> {
             self.fire_count.fetch_add(1, Ordering::SeqCst);
+            Ok(())
         }
     }
 
@@ -627,6 +1118,202 @@ mod tests {
         assert_eq!(c2.load(Ordering::SeqCst), 1);
     }
 
+    /// A void hook that always panics.
+    struct PanickingHook;
+
+    #[async_trait]
+    impl HookHandler for PanickingHook {
+        fn name(&self) -> &str {
+            "broken"
+        }
+        async fn on_heartbeat_tick(&self) -> anyhow::Result<()> {
+            panic!("heartbeat handler failed");
+        }
+    }
+
+    /// A void hook whose side effect always fails.
+    struct ErroringHook;
+
+    #[async_trait]
+    impl HookHandler for ErroringHook {
+        fn name(&self) -> &str {
+            "erroring"
+        }
+        async fn on_heartbeat_tick(&self) -> anyhow::Result<()> {
+            anyhow::bail!("webhook unreachable")
+        }
+    }
+
+    #[tokio::test]
+    async fn void_dispatch_reports_failing_handler() {
+        let mut runner = HookRunner::new();
+        let (counting, count) = CountingHook::new("healthy", 0);
+        runner.register(Box::new(counting));
+        runner.register(Box::new(PanickingHook));
+        runner.register(Box::new(ErroringHook));
+
+        let report = runner.fire_heartbeat_tick().await;
+
+        assert_eq!(report.ran, 3);
+        let mut failed = report.failed.clone();
+        failed.sort();
+        assert_eq!(failed, vec!["broken".to_string(), "erroring".to_string()]);
+        assert!(!report.all_succeeded());
+        // The healthy handler still ran.
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    /// A hook that records the default model of each reloaded config.
+    struct ConfigReloadHook {
+        seen: Arc<std::sync::Mutex<Vec<Option<String>>>>,
+    }
+
+    #[async_trait]
+    impl HookHandler for ConfigReloadHook {
+        fn name(&self) -> &str {
+            "config_reload"
+        }
+        async fn on_config_reload(&self, config: &Config) -> anyhow::Result<()> {
+            self.seen.lock().unwrap().push(config.default_model.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn config_reload_hook_observes_new_config() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let runner = HookRunner::new();
+        runner
+            .reload_dynamic_hooks(vec![Box::new(ConfigReloadHook { seen: seen.clone() })])
+            .await;
+
+        let config = Config {
+            default_model: Some("reloaded-model".into()),
+            ..Config::default()
+        };
+        runner.fire_config_reload(&config).await;
+
+        assert_eq!(*seen.lock().unwrap(), vec![Some("reloaded-model".into())]);
+    }
+
+    /// Rejects sessions on one channel, e.g. a channel under maintenance.
+    struct MaintenanceHook {
+        channel: &'static str,
+    }
+
+    #[async_trait]
+    impl HookHandler for MaintenanceHook {
+        fn name(&self) -> &str {
+            "maintenance"
+        }
+        async fn before_session_start(&self, _session_id: &str, channel: &str) -> HookResult<()> {
+            if channel == self.channel {
+                HookResult::Cancel("channel under maintenance".into())
+            } else {
+                HookResult::Continue(())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn before_session_start_allows_other_channels() {
+        let mut runner = HookRunner::new();
+        runner.register(Box::new(MaintenanceHook { channel: "slack" }));
+
+        let result = runner.run_before_session_start("s1", "telegram").await;
+        assert!(!result.is_cancel());
+    }
+
+    #[tokio::test]
+    async fn before_session_start_cancel_rejects_session() {
+        let mut runner = HookRunner::new();
+        runner.register(Box::new(MaintenanceHook { channel: "slack" }));
+
+        match runner.run_before_session_start("s1", "slack").await {
+            HookResult::Cancel(reason) => assert_eq!(reason, "channel under maintenance"),
+            HookResult::Continue(()) => panic!("should have been cancelled"),
+        }
+    }
+
+    /// Tags the prompt with the channel it is built for, and refuses tool
+    /// calls from one sender.
+    struct ChannelAwareHook;
+
+    #[async_trait]
+    impl HookHandler for ChannelAwareHook {
+        fn name(&self) -> &str {
+            "channel_aware"
+        }
+        async fn before_prompt_build_with_context(
+            &self,
+            ctx: &HookContext,
+            prompt: String,
+        ) -> HookResult<String> {
+            match ctx.channel.as_deref() {
+                Some("slack") => HookResult::Continue(format!("{prompt} [slack formatting]")),
+                _ => HookResult::Continue(prompt),
+            }
+        }
+        async fn before_tool_call_with_context(
+            &self,
+            ctx: &HookContext,
+            name: String,
+            args: Value,
+        ) -> HookResult<(String, Value)> {
+            if ctx.sender.as_deref() == Some("guest") {
+                return HookResult::Cancel("guests cannot call tools".into());
+            }
+            HookResult::Continue((name, args))
+        }
+    }
+
+    #[tokio::test]
+    async fn modifying_hook_branches_on_context_channel() {
+        let mut runner = HookRunner::new();
+        runner.register(Box::new(ChannelAwareHook));
+
+        let slack = HookContext::session("s1", "slack");
+        match runner
+            .run_before_prompt_build_with_context(&slack, "base".into())
+            .await
+        {
+            HookResult::Continue(p) => assert_eq!(p, "base [slack formatting]"),
+            HookResult::Cancel(_) => panic!("should not cancel"),
+        }
+
+        let cli = HookContext::session("s2", "cli");
+        match runner
+            .run_before_prompt_build_with_context(&cli, "base".into())
+            .await
+        {
+            HookResult::Continue(p) => assert_eq!(p, "base"),
+            HookResult::Cancel(_) => panic!("should not cancel"),
+        }
+    }
+
+    #[tokio::test]
+    async fn modifying_hook_without_context_gets_default() {
+        let mut runner = HookRunner::new();
+        runner.register(Box::new(ChannelAwareHook));
+        runner.register(Box::new(UppercasePromptHook {
+            name: "upper".into(),
+            priority: 0,
+        }));
+
+        // Plain dispatchers pass an empty context; legacy handlers that only
+        // implement the context-free method still run.
+        match runner.run_before_prompt_build("base".into()).await {
+            HookResult::Continue(p) => assert_eq!(p, "BASE"),
+            HookResult::Cancel(_) => panic!("should not cancel"),
+        }
+
+        let guest = HookContext::session("s1", "slack").with_sender("guest");
+        let result = runner
+            .run_before_tool_call_with_context(&guest, "shell".into(), serde_json::json!({}))
+            .await;
+        assert!(result.is_cancel());
+    }
+
     #[tokio::test]
     async fn modifying_hook_can_cancel() {
         let mut runner = HookRunner::new();
@@ -665,6 +1352,52 @@ mod tests {
         }
     }
 
+    /// A before_send hook that redacts a secret token and blocks one channel.
+    struct RedactSendHook;
+
+    #[async_trait]
+    impl HookHandler for RedactSendHook {
+        fn name(&self) -> &str {
+            "redact"
+        }
+        async fn before_send(
+            &self,
+            channel: &str,
+            _recipient: &str,
+            content: String,
+        ) -> HookResult<String> {
+            if channel == "blocked" {
+                return HookResult::Cancel("channel muted".into());
+            }
+            HookResult::Continue(content.replace("sk-secret", "[REDACTED]"))
+        }
+    }
+
+    #[tokio::test]
+    async fn before_send_rewrites_content() {
+        let mut runner = HookRunner::new();
+        runner.register(Box::new(RedactSendHook));
+
+        match runner
+            .run_before_send("lark", "oc_123", "token sk-secret".into())
+            .await
+        {
+            HookResult::Continue(content) => assert_eq!(content, "token [REDACTED]"),
+            HookResult::Cancel(_) => panic!("should not cancel"),
+        }
+    }
+
+    #[tokio::test]
+    async fn before_send_cancel_suppresses_send() {
+        let mut runner = HookRunner::new();
+        runner.register(Box::new(RedactSendHook));
+
+        let result = runner
+            .run_before_send("blocked", "oc_123", "hello".into())
+            .await;
+        assert!(result.is_cancel());
+    }
+
     #[tokio::test]
     async fn reload_swaps_dynamic_handlers() {
         let mut runner = HookRunner::new();
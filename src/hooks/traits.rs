@@ -3,6 +3,8 @@ use serde_json::Value;
 use std::time::Duration;
 
 use crate::channels::traits::ChannelMessage;
+use crate::config::Config;
+use crate::hooks::manifest::{HookEvent, HookScope};
 use crate::providers::traits::{ChatMessage, ChatResponse};
 use crate::tools::traits::ToolResult;
 
@@ -19,6 +21,40 @@ impl<T> HookResult<T> {
     }
 }
 
+/// Who and what a modifying hook is being run for. Every field is optional;
+/// dispatchers fill in what they know and callers without context pass
+/// `HookContext::default()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HookContext {
+    pub session_id: Option<String>,
+    pub channel: Option<String>,
+    pub sender: Option<String>,
+    pub model: Option<String>,
+}
+
+impl HookContext {
+    /// Context for a session on `channel`.
+    pub fn session(session_id: impl Into<String>, channel: impl Into<String>) -> Self {
+        Self {
+            session_id: Some(session_id.into()),
+            channel: Some(channel.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Set the model the request is routed to.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Set the sender the request originates from.
+    pub fn with_sender(mut self, sender: impl Into<String>) -> Self {
+        self.sender = Some(sender.into());
+        self
+    }
+}
+
 /// Trait for hook handlers. All methods have default no-op implementations.
 /// Implement only the events you care about.
 #[async_trait]
@@ -27,19 +63,66 @@ pub trait HookHandler: Send + Sync {
     fn priority(&self) -> i32 {
         0
     }
+    /// Events/channels this handler is restricted to. `None` means unrestricted.
+    fn scope(&self) -> Option<&HookScope> {
+        None
+    }
+    /// Whether this handler reacts to `event` at all. The runner skips
+    /// handlers that return `false`.
+    fn handles(&self, _event: HookEvent) -> bool {
+        true
+    }
 
     // --- Void hooks (parallel, fire-and-forget) ---
-    async fn on_gateway_start(&self, _host: &str, _port: u16) {}
-    async fn on_gateway_stop(&self) {}
-    async fn on_session_start(&self, _session_id: &str, _channel: &str) {}
-    async fn on_session_end(&self, _session_id: &str, _channel: &str) {}
-    async fn on_llm_input(&self, _messages: &[ChatMessage], _model: &str) {}
-    async fn on_llm_output(&self, _response: &ChatResponse) {}
-    async fn on_after_tool_call(&self, _tool: &str, _result: &ToolResult, _duration: Duration) {}
-    async fn on_message_sent(&self, _channel: &str, _recipient: &str, _content: &str) {}
-    async fn on_heartbeat_tick(&self) {}
+    // An `Err` does not affect the caller; it is logged and counted as a
+    // failure in the dispatch's `VoidDispatchReport`.
+    async fn on_gateway_start(&self, _host: &str, _port: u16) -> anyhow::Result<()> {
+        Ok(())
+    }
+    async fn on_gateway_stop(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+    async fn on_session_start(&self, _session_id: &str, _channel: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+    async fn on_session_end(&self, _session_id: &str, _channel: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+    async fn on_llm_input(&self, _messages: &[ChatMessage], _model: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+    async fn on_llm_output(&self, _response: &ChatResponse) -> anyhow::Result<()> {
+        Ok(())
+    }
+    async fn on_after_tool_call(
+        &self,
+        _tool: &str,
+        _result: &ToolResult,
+        _duration: Duration,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+    async fn on_message_sent(
+        &self,
+        _channel: &str,
+        _recipient: &str,
+        _content: &str,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+    async fn on_heartbeat_tick(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+    async fn on_config_reload(&self, _config: &Config) -> anyhow::Result<()> {
+        Ok(())
+    }
 
     // --- Modifying hooks (sequential by priority, can cancel) ---
+    /// Runs before a session starts; `Cancel` rejects the session.
+    async fn before_session_start(&self, _session_id: &str, _channel: &str) -> HookResult<()> {
+        HookResult::Continue(())
+    }
+
     async fn before_model_resolve(
         &self,
         provider: String,
@@ -96,6 +179,62 @@ pub trait HookHandler: Send + Sync {
     ) -> HookResult<(String, String, String, String)> {
         HookResult::Continue((file_path, channel, recipient, content))
     }
+
+    async fn before_send(
+        &self,
+        _channel: &str,
+        _recipient: &str,
+        content: String,
+    ) -> HookResult<String> {
+        HookResult::Continue(content)
+    }
+
+    // --- Context-aware modifying hooks ---
+    // The runner calls these. The defaults ignore the context and delegate to
+    // the plain methods above, so handlers only override them when they need
+    // to branch on session, channel, sender or model.
+    async fn before_session_start_with_context(&self, ctx: &HookContext) -> HookResult<()> {
+        self.before_session_start(
+            ctx.session_id.as_deref().unwrap_or_default(),
+            ctx.channel.as_deref().unwrap_or_default(),
+        )
+        .await
+    }
+
+    async fn before_model_resolve_with_context(
+        &self,
+        _ctx: &HookContext,
+        provider: String,
+        model: String,
+    ) -> HookResult<(String, String)> {
+        self.before_model_resolve(provider, model).await
+    }
+
+    async fn before_prompt_build_with_context(
+        &self,
+        _ctx: &HookContext,
+        prompt: String,
+    ) -> HookResult<String> {
+        self.before_prompt_build(prompt).await
+    }
+
+    async fn before_llm_call_with_context(
+        &self,
+        _ctx: &HookContext,
+        messages: Vec<ChatMessage>,
+        model: String,
+    ) -> HookResult<(Vec<ChatMessage>, String)> {
+        self.before_llm_call(messages, model).await
+    }
+
+    async fn before_tool_call_with_context(
+        &self,
+        _ctx: &HookContext,
+        name: String,
+        args: Value,
+    ) -> HookResult<(String, Value)> {
+        self.before_tool_call(name, args).await
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,200 @@
+//! Hot-reload watcher for dynamic hook manifests.
+//!
+//! Watches the hooks directory with `notify` and reloads the dynamic hook set
+//! whenever a `HOOK.toml` (or a hook subdirectory) changes. Bursts of events
+//! are debounced, and reloads go through [`reload_hooks_validated`] so a
+//! broken manifest keeps the previously loaded hooks active.
+
+use anyhow::{bail, Result};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::config::schema::HooksConfig;
+use crate::hooks::reload::reload_hooks_validated;
+use crate::hooks::HookRunner;
+
+/// Quiet period after the last file event before a reload is triggered.
+const DEBOUNCE_DURATION: Duration = Duration::from_millis(500);
+
+/// Watches a hooks directory and reloads dynamic hooks on change.
+/// Watching stops when this value is dropped.
+pub struct HooksWatcher {
+    _watcher: RecommendedWatcher,
+    task: JoinHandle<()>,
+}
+
+impl HooksWatcher {
+    /// Start watching `hooks_dir` and reload `runner` when manifests change.
+    pub fn spawn(
+        hooks_dir: PathBuf,
+        hooks_config: HooksConfig,
+        runner: Arc<HookRunner>,
+    ) -> Result<Self> {
+        if !hooks_dir.is_dir() {
+            bail!("hooks directory does not exist: {}", hooks_dir.display());
+        }
+
+        let (tx, rx) = mpsc::unbounded_channel::<()>();
+        let root = hooks_dir.clone();
+        let mut watcher =
+            notify::recommended_watcher(move |res: std::result::Result<notify::Event, _>| {
+                let event = match res {
+                    Ok(e) => e,
+                    Err(err) => {
+                        tracing::warn!("hooks watcher error: {err}");
+                        return;
+                    }
+                };
+                if matches!(event.kind, notify::EventKind::Access(_)) {
+                    return;
+                }
+                if event.paths.iter().any(|p| is_manifest_change(&root, p)) {
+                    let _ = tx.send(());
+                }
+            })?;
+        watcher.watch(&hooks_dir, RecursiveMode::Recursive)?;
+
+        let task = tokio::spawn(reload_loop(rx, hooks_dir, hooks_config, runner));
+        Ok(Self {
+            _watcher: watcher,
+            task,
+        })
+    }
+}
+
+impl Drop for HooksWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// A change is relevant if it touches a `HOOK.toml` or adds/removes a hook
+/// subdirectory directly under the hooks root.
+fn is_manifest_change(root: &Path, path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == "HOOK.toml") || path.parent() == Some(root)
+}
+
+async fn reload_loop(
+    mut rx: mpsc::UnboundedReceiver<()>,
+    hooks_dir: PathBuf,
+    hooks_config: HooksConfig,
+    runner: Arc<HookRunner>,
+) {
+    while rx.recv().await.is_some() {
+        // Debounce: keep absorbing events until the directory is quiet.
+        loop {
+            match tokio::time::timeout(DEBOUNCE_DURATION, rx.recv()).await {
+                Ok(Some(())) => {}
+                Ok(None) => return,
+                Err(_) => break,
+            }
+        }
+
+        match reload_hooks_validated(&hooks_dir, &hooks_config, &runner).await {
+            Ok(count) => tracing::info!(count, "Dynamic hooks reloaded after HOOK.toml change"),
+            Err(err) => {
+                tracing::warn!("Keeping current hooks; reload after file change failed: {err}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::schema::BuiltinHooksConfig;
+    use crate::hooks::HookResult;
+    use tempfile::TempDir;
+
+    fn test_config() -> HooksConfig {
+        HooksConfig {
+            enabled: true,
+            builtin: BuiltinHooksConfig::default(),
+            hooks_dir: None,
+            skip_security_audit: true,
+            max_hooks: 50,
+            default_timeout_secs: 30,
+            webhook: None,
+            event_log: None,
+            rate_limit: None,
+        }
+    }
+
+    fn write_inject_hook(root: &Path, name: &str, inject: &str) {
+        let dir = root.join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        let toml = format!(
+            r#"[hook]
+name = "{name}"
+event = "before_prompt_build"
+[hook.action.prompt_inject]
+content = "{inject}"
+position = "append"
+"#
+        );
+        std::fs::write(dir.join("HOOK.toml"), toml).unwrap();
+    }
+
+    async fn prompt_after_hooks(runner: &HookRunner) -> String {
+        match runner.run_before_prompt_build("base".into()).await {
+            HookResult::Continue(p) => p,
+            HookResult::Cancel(reason) => panic!("unexpected cancel: {reason}"),
+        }
+    }
+
+    async fn wait_for_prompt(runner: &HookRunner, expected: &str) {
+        for _ in 0..100 {
+            if prompt_after_hooks(runner).await == expected {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!(
+            "hooks never reloaded; last prompt: {:?}",
+            prompt_after_hooks(runner).await
+        );
+    }
+
+    #[tokio::test]
+    async fn manifest_change_triggers_reload() {
+        let dir = TempDir::new().unwrap();
+        let runner = Arc::new(HookRunner::new());
+        let _watcher =
+            HooksWatcher::spawn(dir.path().to_path_buf(), test_config(), runner.clone()).unwrap();
+
+        write_inject_hook(dir.path(), "signature", "v1");
+        wait_for_prompt(&runner, "base\nv1").await;
+
+        write_inject_hook(dir.path(), "signature", "v2");
+        wait_for_prompt(&runner, "base\nv2").await;
+    }
+
+    #[tokio::test]
+    async fn invalid_manifest_keeps_current_hooks() {
+        let dir = TempDir::new().unwrap();
+        let runner = Arc::new(HookRunner::new());
+        let _watcher =
+            HooksWatcher::spawn(dir.path().to_path_buf(), test_config(), runner.clone()).unwrap();
+
+        write_inject_hook(dir.path(), "signature", "v1");
+        wait_for_prompt(&runner, "base\nv1").await;
+
+        std::fs::write(dir.path().join("signature/HOOK.toml"), "[hook\nbroken").unwrap();
+        tokio::time::sleep(DEBOUNCE_DURATION * 3).await;
+        assert_eq!(prompt_after_hooks(&runner).await, "base\nv1");
+    }
+
+    #[test]
+    fn missing_directory_is_an_error() {
+        let result = HooksWatcher::spawn(
+            PathBuf::from("/nonexistent/hooks/dir"),
+            test_config(),
+            Arc::new(HookRunner::new()),
+        );
+        assert!(result.is_err());
+    }
+}
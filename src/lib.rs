@@ -42,6 +42,7 @@ pub mod agent;
 pub(crate) mod approval;
 pub(crate) mod auth;
 pub mod channels;
+pub(crate) mod clock;
 pub mod config;
 pub(crate) mod cost;
 pub(crate) mod cron;
@@ -55,6 +56,8 @@ pub mod hooks;
 pub(crate) mod identity;
 pub(crate) mod integrations;
 pub mod memory;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub(crate) mod migration;
 pub(crate) mod multimodal;
 pub mod observability;
@@ -62,6 +65,7 @@ pub(crate) mod onboard;
 pub mod peripherals;
 pub mod providers;
 pub mod rag;
+pub(crate) mod retry;
 pub mod runtime;
 pub(crate) mod security;
 pub(crate) mod service;
@@ -142,6 +146,17 @@ Examples:
     },
 }
 
+/// Output format for commands that can emit machine-readable reports
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// A single JSON document on stdout
+    Json,
+}
+
 /// Skills management subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum SkillCommands {
@@ -151,6 +166,9 @@ pub enum SkillCommands {
     Audit {
         /// Skill path or installed skill name
         source: String,
+        /// Report format; the command exits non-zero on findings either way
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
     },
     /// Install a new skill from a URL or local path
     Install {
@@ -162,6 +180,8 @@ pub enum SkillCommands {
         /// Skill name to remove
         name: String,
     },
+    /// Diagnose the skills setup (directory, git, open-skills, audits)
+    Doctor,
 }
 
 /// Hooks management subcommands
@@ -181,6 +201,15 @@ pub enum HooksCommands {
         /// Path to hook directory (or hook name)
         path: String,
     },
+    /// Fire a synthetic event and report which hooks ran
+    Test {
+        /// Event name (e.g. before_send, on_message_sent)
+        event: String,
+        /// Actually run hook actions (shell commands, HTTP calls). Without
+        /// this flag the command only reports which hooks would fire.
+        #[arg(long)]
+        execute: bool,
+    },
 }
 
 /// Migration subcommands
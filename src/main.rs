@@ -52,6 +52,7 @@ mod agent;
 mod approval;
 mod auth;
 mod channels;
+mod clock;
 mod rag {
     pub use zeroclaw::rag::*;
 }
@@ -68,12 +69,15 @@ mod hooks;
 mod identity;
 mod integrations;
 mod memory;
+#[cfg(feature = "metrics")]
+mod metrics;
 mod migration;
 mod multimodal;
 mod observability;
 mod onboard;
 mod peripherals;
 mod providers;
+mod retry;
 mod runtime;
 mod security;
 mod service;
@@ -92,7 +96,7 @@ use config::Config;
 // Re-export so binary modules can use crate::<CommandEnum> while keeping a single source of truth.
 pub use zeroclaw::{
     ChannelCommands, CronCommands, HardwareCommands, HooksCommands, IntegrationCommands,
-    MigrateCommands, PeripheralCommands, ServiceCommands, SkillCommands,
+    MigrateCommands, OutputFormat, PeripheralCommands, ServiceCommands, SkillCommands,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
@@ -450,6 +454,46 @@ Examples:
         config_command: ConfigCommands,
     },
 
+    /// Maintain Feishu Docs sync state
+    #[cfg(feature = "feishu-docs-sync")]
+    #[command(long_about = "\
+Maintain Feishu Docs sync state.
+
+Use 'resync' when local files and Feishu documents have drifted apart: \
+it clears the sync lock, pushes every configured local file to Feishu \
+(overwriting mapped documents, creating new ones otherwise) and shares \
+each document with all users it was previously shared with.
+
+Use 'status' to list tracked files, their document ids and last sync \
+times, plus any items that failed for good.
+
+Examples:
+  zeroclaw docs-sync status
+  zeroclaw docs-sync resync --yes")]
+    DocsSync {
+        #[command(subcommand)]
+        docs_sync_command: DocsSyncCommands,
+    },
+
+    /// Inspect configured MCP servers
+    #[cfg(feature = "mcp")]
+    #[command(long_about = "\
+Inspect configured MCP (Model Context Protocol) servers.
+
+Use 'test' to smoke-test a server config: it spawns the server, runs the \
+initialize handshake, prints the server info, advertised capabilities and \
+tool/resource/prompt counts, then disconnects. Pass a server name from \
+.mcp.json (or an imported registry), or the path to a config file in the \
+same format to test every server it lists.
+
+Examples:
+  zeroclaw mcp test github
+  zeroclaw mcp test ./new-server.json")]
+    Mcp {
+        #[command(subcommand)]
+        mcp_command: McpCommands,
+    },
+
     /// Generate shell completion script to stdout
     #[command(long_about = "\
 Generate shell completion scripts for `zeroclaw`.
@@ -473,6 +517,29 @@ enum ConfigCommands {
     Schema,
 }
 
+#[cfg(feature = "feishu-docs-sync")]
+#[derive(Subcommand, Debug)]
+enum DocsSyncCommands {
+    /// Show tracked files, last sync times and dead-lettered items
+    Status,
+    /// Clear the sync lock and push all local files to Feishu again
+    Resync {
+        /// Confirm overwriting remote documents with local content
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+#[cfg(feature = "mcp")]
+#[derive(Subcommand, Debug)]
+enum McpCommands {
+    /// Connect to a server, print what it advertises, and disconnect
+    Test {
+        /// Configured server name, or path to an `.mcp.json`-style file
+        server: String,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum EstopSubcommands {
     /// Print current estop status.
@@ -735,10 +802,11 @@ async fn main() -> Result<()> {
         }?;
         // Auto-start channels if user said yes during wizard
         if std::env::var("ZEROCLAW_AUTOSTART_CHANNELS").as_deref() == Ok("1") {
+            let shutdown = daemon::shutdown_on_signal();
             #[cfg(any(feature = "channel-lark", feature = "feishu-docs-sync"))]
-            channels::start_channels(config, None).await?;
+            channels::start_channels(config, shutdown, None).await?;
             #[cfg(not(any(feature = "channel-lark", feature = "feishu-docs-sync")))]
-            channels::start_channels(config).await?;
+            channels::start_channels(config, shutdown).await?;
         }
         return Ok(());
     }
@@ -995,10 +1063,11 @@ async fn main() -> Result<()> {
 
         Commands::Channel { channel_command } => match channel_command {
             ChannelCommands::Start => {
+                let shutdown = daemon::shutdown_on_signal();
                 #[cfg(any(feature = "channel-lark", feature = "feishu-docs-sync"))]
-                { channels::start_channels(config, None).await }
+                { channels::start_channels(config, shutdown, None).await }
                 #[cfg(not(any(feature = "channel-lark", feature = "feishu-docs-sync")))]
-                { channels::start_channels(config).await }
+                { channels::start_channels(config, shutdown).await }
             }
             ChannelCommands::Doctor => channels::doctor_channels(config).await,
             other => channels::handle_command(other, &config).await,
@@ -1010,7 +1079,7 @@ async fn main() -> Result<()> {
 
         Commands::Skills { skill_command } => skills::handle_command(skill_command, &config),
         Commands::Hooks { hooks_command } => {
-            hooks::cli::handle_hooks_command(hooks_command, &config)
+            hooks::cli::handle_hooks_command(hooks_command, &config).await
         }
 
         Commands::Migrate { migrate_command } => {
@@ -1031,6 +1100,52 @@ async fn main() -> Result<()> {
             peripherals::handle_command(peripheral_command.clone(), &config).await
         }
 
+        #[cfg(feature = "feishu-docs-sync")]
+        Commands::DocsSync { docs_sync_command } => match docs_sync_command {
+            DocsSyncCommands::Status => {
+                let status = docs_sync::sync_status(&config);
+                if status.files.is_empty() {
+                    println!("No files tracked yet.");
+                }
+                for file in &status.files {
+                    let synced = file
+                        .last_synced_at
+                        .map_or_else(|| "unknown".to_string(), |t| t.to_rfc3339());
+                    println!(
+                        "  {} → {} (last synced: {synced})",
+                        file.filename, file.doc_id
+                    );
+                }
+                for item in &status.dead_letters {
+                    println!("  ✗ {}: {}", item.filename, item.error);
+                }
+                println!(
+                    "{} tracked file(s), {} dead-lettered item(s).",
+                    status.files.len(),
+                    status.dead_letters.len()
+                );
+                Ok(())
+            }
+            DocsSyncCommands::Resync { yes } => {
+                let report = docs_sync::force_resync(&config, yes).await?;
+                for (filename, doc_id) in &report.pushed {
+                    println!("  ✓ {filename} → {doc_id}");
+                }
+                for filename in &report.failed {
+                    println!("  ✗ {filename} (see logs)");
+                }
+                println!(
+                    "Resynced {} file(s), {} failed.",
+                    report.pushed.len(),
+                    report.failed.len()
+                );
+                Ok(())
+            }
+        },
+
+        #[cfg(feature = "mcp")]
+        Commands::Mcp { mcp_command } => handle_mcp_command(mcp_command, &config).await,
+
         Commands::Config { config_command } => match config_command {
             ConfigCommands::Schema => {
                 let schema = schemars::schema_for!(config::Config);
@@ -1044,6 +1159,71 @@ async fn main() -> Result<()> {
     }
 }
 
+#[cfg(feature = "mcp")]
+async fn handle_mcp_command(mcp_command: McpCommands, config: &Config) -> Result<()> {
+    match mcp_command {
+        McpCommands::Test { server } => {
+            let servers = resolve_mcp_servers(&server, config)?;
+            let client_info = zeroclaw_mcp::types::Implementation {
+                name: "zeroclaw".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+            };
+            let mut failed = 0usize;
+            for server_config in servers {
+                println!("Testing MCP server '{}'...", server_config.name);
+                let result = async {
+                    let transport =
+                        zeroclaw_mcp::transport::StdioTransport::from_config(&server_config)
+                            .await?;
+                    zeroclaw_mcp::probe::probe(
+                        Box::new(transport),
+                        client_info.clone(),
+                        zeroclaw_mcp::probe::DEFAULT_PROBE_TIMEOUT,
+                    )
+                    .await
+                }
+                .await;
+                match result {
+                    Ok(report) => println!("{report}\n"),
+                    Err(e) => {
+                        failed += 1;
+                        println!("  ✗ {e:#}\n");
+                    }
+                }
+            }
+            if failed > 0 {
+                bail!("{failed} MCP server(s) failed the connection test");
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Servers to test: every entry of `target` if it is a config file,
+/// otherwise the configured server named `target`.
+#[cfg(feature = "mcp")]
+fn resolve_mcp_servers(
+    target: &str,
+    config: &Config,
+) -> Result<Vec<zeroclaw_mcp::config::McpServerConfig>> {
+    let path = std::path::Path::new(target);
+    if path.is_file() {
+        let servers = zeroclaw_mcp::config::parse_mcp_registry(path)?;
+        if servers.is_empty() {
+            bail!("No runnable MCP servers found in {}", path.display());
+        }
+        return Ok(servers);
+    }
+
+    tools::mcp_bridge::configured_servers(config)?
+        .into_iter()
+        .find(|server| server.name == target)
+        .map(|server| vec![server])
+        .with_context(|| {
+            format!("No MCP server named '{target}' is configured and no such config file exists")
+        })
+}
+
 fn handle_estop_command(
     config: &Config,
     estop_command: Option<EstopSubcommands>,
@@ -1912,6 +2092,32 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "mcp")]
+    #[test]
+    fn mcp_test_cli_parses_server_argument() {
+        let cli = Cli::try_parse_from(["zeroclaw", "mcp", "test", "github"])
+            .expect("mcp test invocation should parse");
+        match cli.command {
+            Commands::Mcp {
+                mcp_command: McpCommands::Test { server },
+            } => assert_eq!(server, "github"),
+            other => panic!("expected mcp command, got {other:?}"),
+        }
+    }
+
+    #[cfg(feature = "feishu-docs-sync")]
+    #[test]
+    fn docs_sync_resync_cli_parses_confirmation_flag() {
+        let cli = Cli::try_parse_from(["zeroclaw", "docs-sync", "resync", "--yes"])
+            .expect("docs-sync resync invocation should parse");
+        match cli.command {
+            Commands::DocsSync {
+                docs_sync_command: DocsSyncCommands::Resync { yes },
+            } => assert!(yes),
+            other => panic!("expected docs-sync command, got {other:?}"),
+        }
+    }
+
     #[test]
     fn completions_cli_parses_supported_shells() {
         for shell in ["bash", "fish", "zsh", "powershell", "elvish"] {
@@ -0,0 +1,257 @@
+//! Subsystem metrics for the gateway's `GET /metrics` endpoint.
+//!
+//! The [`Observer`](crate::observability::Observer) pipeline covers agent,
+//! LLM and tool activity. Counters that live in other subsystems — Lark
+//! message throughput, hook dispatch timings, MCP tool calls and VPN node
+//! health — are registered here instead, in one process-wide [`Metrics`]
+//! registry, and appended to the Prometheus exposition served by the gateway.
+//!
+//! Compiled only with the `metrics` feature.
+
+use std::sync::OnceLock;
+
+use prometheus::{
+    Encoder, GaugeVec, HistogramOpts, HistogramTimer, HistogramVec, IntCounterVec, IntGaugeVec,
+    Opts, Registry, TextEncoder,
+};
+
+use crate::hooks::HookEvent;
+
+/// Registry of subsystem counters and gauges.
+pub struct Metrics {
+    registry: Registry,
+    lark_messages: IntCounterVec,
+    hook_dispatch_duration: HistogramVec,
+    mcp_tool_calls: IntCounterVec,
+    vpn_nodes: IntGaugeVec,
+    vpn_node_latency: GaugeVec,
+}
+
+static GLOBAL: OnceLock<Metrics> = OnceLock::new();
+
+/// The process-wide registry subsystems record into.
+pub fn global() -> &'static Metrics {
+    GLOBAL.get_or_init(Metrics::new)
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let lark_messages = IntCounterVec::new(
+            Opts::new(
+                "zeroclaw_lark_messages_total",
+                "Lark/Feishu messages received and sent",
+            ),
+            &["direction"],
+        )
+        .expect("valid metric");
+
+        let hook_dispatch_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "zeroclaw_hook_dispatch_duration_seconds",
+                "Time spent running modifying hook handlers per event",
+            )
+            .buckets(vec![0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0]),
+            &["event"],
+        )
+        .expect("valid metric");
+
+        let mcp_tool_calls = IntCounterVec::new(
+            Opts::new("zeroclaw_mcp_tool_calls_total", "MCP tool calls by server"),
+            &["server", "success"],
+        )
+        .expect("valid metric");
+
+        let vpn_nodes = IntGaugeVec::new(
+            Opts::new(
+                "zeroclaw_vpn_nodes",
+                "VPN nodes by status in the latest health check",
+            ),
+            &["status"],
+        )
+        .expect("valid metric");
+
+        let vpn_node_latency = GaugeVec::new(
+            Opts::new(
+                "zeroclaw_vpn_node_latency_seconds",
+                "Latency of healthy VPN nodes in the latest health check",
+            ),
+            &["node"],
+        )
+        .expect("valid metric");
+
+        registry.register(Box::new(lark_messages.clone())).ok();
+        registry
+            .register(Box::new(hook_dispatch_duration.clone()))
+            .ok();
+        registry.register(Box::new(mcp_tool_calls.clone())).ok();
+        registry.register(Box::new(vpn_nodes.clone())).ok();
+        registry.register(Box::new(vpn_node_latency.clone())).ok();
+
+        Self {
+            registry,
+            lark_messages,
+            hook_dispatch_duration,
+            mcp_tool_calls,
+            vpn_nodes,
+            vpn_node_latency,
+        }
+    }
+
+    /// Count a Lark message; `direction` is `"inbound"` or `"outbound"`.
+    pub fn record_lark_message(&self, direction: &str) {
+        self.lark_messages.with_label_values(&[direction]).inc();
+    }
+
+    /// Start timing a modifying hook dispatch. The duration is recorded when
+    /// the returned timer is dropped.
+    pub fn hook_timer(&self, event: HookEvent) -> HistogramTimer {
+        self.hook_dispatch_duration
+            .with_label_values(&[event.to_string()])
+            .start_timer()
+    }
+
+    /// Count a call to a tool on MCP server `server`.
+    pub fn record_mcp_call(&self, server: &str, success: bool) {
+        let success_str = if success { "true" } else { "false" };
+        self.mcp_tool_calls
+            .with_label_values(&[server, success_str])
+            .inc();
+    }
+
+    /// Replace the VPN gauges with the results of a health check round.
+    #[cfg(feature = "vpn")]
+    pub fn set_vpn_health(&self, results: &[(String, crate::vpn::HealthResult)]) {
+        use crate::vpn::NodeStatus;
+
+        self.vpn_node_latency.reset();
+        for status in [
+            NodeStatus::Healthy,
+            NodeStatus::Unhealthy,
+            NodeStatus::Unknown,
+        ] {
+            let count = results.iter().filter(|(_, hr)| hr.status == status).count();
+            self.vpn_nodes
+                .with_label_values(&[status.to_string()])
+                .set(i64::try_from(count).unwrap_or(i64::MAX));
+        }
+        for (name, hr) in results {
+            if let (NodeStatus::Healthy, Some(ms)) = (hr.status, hr.latency_ms) {
+                self.vpn_node_latency
+                    .with_label_values(&[name.as_str()])
+                    .set(ms as f64 / 1000.0);
+            }
+        }
+    }
+
+    /// Encode all registered metrics into Prometheus text exposition format.
+    pub fn encode(&self) -> String {
+        let encoder = TextEncoder::new();
+        let families = self.registry.gather();
+        let mut buf = Vec::new();
+        encoder.encode(&families, &mut buf).unwrap_or_default();
+        String::from_utf8(buf).unwrap_or_default()
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check every line is a `# HELP`/`# TYPE` comment or a
+    /// `name{labels} value` sample whose family was declared by `# TYPE`.
+    fn assert_well_formed(text: &str) {
+        let mut declared = Vec::new();
+        for line in text.lines() {
+            if let Some(rest) = line.strip_prefix("# TYPE ") {
+                let mut parts = rest.split(' ');
+                let name = parts.next().unwrap();
+                let kind = parts.next().unwrap();
+                assert!(
+                    ["counter", "gauge", "histogram"].contains(&kind),
+                    "bad type line: {line}"
+                );
+                declared.push(name.to_string());
+                continue;
+            }
+            if line.starts_with("# HELP ") {
+                continue;
+            }
+            let (series, value) = line.rsplit_once(' ').expect("sample has a value");
+            value
+                .parse::<f64>()
+                .unwrap_or_else(|_| panic!("bad value: {line}"));
+            let name = series.split('{').next().unwrap();
+            assert!(
+                name.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':'),
+                "bad metric name: {line}"
+            );
+            let labels = &series[name.len()..];
+            assert!(
+                labels.is_empty() || (labels.starts_with('{') && labels.ends_with('}')),
+                "bad labels: {line}"
+            );
+            assert!(
+                declared
+                    .iter()
+                    .any(|family| name.starts_with(family.as_str())),
+                "sample without TYPE: {line}"
+            );
+        }
+    }
+
+    #[test]
+    fn encode_renders_well_formed_exposition() {
+        let metrics = Metrics::new();
+        metrics.record_lark_message("inbound");
+        metrics.record_lark_message("inbound");
+        metrics.record_lark_message("outbound");
+        metrics.record_mcp_call("filesystem", true);
+        metrics.record_mcp_call("filesystem", false);
+        drop(metrics.hook_timer(HookEvent::BeforeLlmCall));
+
+        let text = metrics.encode();
+        assert_well_formed(&text);
+        assert!(text.contains("# TYPE zeroclaw_lark_messages_total counter"));
+        assert!(text.contains("zeroclaw_lark_messages_total{direction=\"inbound\"} 2"));
+        assert!(text
+            .contains("zeroclaw_mcp_tool_calls_total{server=\"filesystem\",success=\"false\"} 1"));
+        assert!(text.contains(
+            "zeroclaw_hook_dispatch_duration_seconds_count{event=\"before_llm_call\"} 1"
+        ));
+    }
+
+    #[cfg(feature = "vpn")]
+    #[test]
+    fn vpn_health_gauges_reflect_latest_round() {
+        use crate::vpn::{HealthResult, NodeStatus};
+        use std::time::Instant;
+
+        let result = |status, latency_ms| HealthResult {
+            status,
+            latency_ms,
+            checked_at: Instant::now(),
+        };
+        let metrics = Metrics::new();
+        metrics.set_vpn_health(&[
+            ("tokyo".into(), result(NodeStatus::Healthy, Some(120))),
+            ("sg".into(), result(NodeStatus::Unhealthy, None)),
+        ]);
+        metrics.set_vpn_health(&[("sg".into(), result(NodeStatus::Healthy, Some(80)))]);
+
+        let text = metrics.encode();
+        assert_well_formed(&text);
+        assert!(text.contains("zeroclaw_vpn_nodes{status=\"healthy\"} 1"));
+        assert!(text.contains("zeroclaw_vpn_nodes{status=\"unhealthy\"} 0"));
+        assert!(text.contains("zeroclaw_vpn_node_latency_seconds{node=\"sg\"} 0.08"));
+        assert!(!text.contains("node=\"tokyo\""));
+    }
+}
@@ -1,7 +1,9 @@
 use crate::config::{build_runtime_proxy_client_with_timeouts, MultimodalConfig};
 use crate::providers::ChatMessage;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
+use futures_util::StreamExt;
 use reqwest::Client;
+use std::io::Write as _;
 use std::path::Path;
 
 const IMAGE_MARKER_PREFIX: &str = "[IMAGE:";
@@ -27,6 +29,16 @@ pub struct PreparedMessages {
     pub messages: Vec<ChatMessage>,
     pub contains_images: bool,
     pub contains_videos: bool,
+    /// Images skipped by [`prepare_messages_for_provider_lenient`]; always
+    /// empty in strict mode.
+    pub failed_images: Vec<FailedImage>,
+}
+
+/// An image reference that could not be normalized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailedImage {
+    pub reference: String,
+    pub error: String,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -37,6 +49,12 @@ pub enum MultimodalError {
     #[error("multimodal video limit exceeded: max_videos={max_videos}, found={found}")]
     TooManyVideos { max_videos: usize, found: usize },
 
+    #[error("multimodal total image payload limit exceeded: {total_bytes} bytes > max_total_bytes={max_total_bytes}")]
+    TotalPayloadTooLarge {
+        total_bytes: usize,
+        max_total_bytes: usize,
+    },
+
     #[error("multimodal image size limit exceeded for '{input}': {size_bytes} bytes > {max_bytes} bytes")]
     ImageTooLarge {
         input: String,
@@ -50,6 +68,11 @@ pub enum MultimodalError {
     #[error("multimodal remote image fetch is disabled for '{input}'")]
     RemoteFetchDisabled { input: String },
 
+    #[error(
+        "multimodal remote image host '{host}' is not in remote_fetch_allowed_hosts for '{input}'"
+    )]
+    RemoteHostNotAllowed { input: String, host: String },
+
     #[error("multimodal image source not found or unreadable: '{input}'")]
     ImageSourceNotFound { input: String },
 
@@ -63,6 +86,25 @@ pub enum MultimodalError {
     LocalReadFailed { input: String, reason: String },
 }
 
+impl MultimodalError {
+    /// Short reason shown in place of an image that was skipped.
+    fn unavailable_reason(&self) -> String {
+        match self {
+            Self::TooManyImages { .. } => "too many images".to_string(),
+            Self::TooManyVideos { .. } => "too many videos".to_string(),
+            Self::TotalPayloadTooLarge { .. } => "total image size too large".to_string(),
+            Self::ImageTooLarge { .. } => "too large".to_string(),
+            Self::UnsupportedMime { mime, .. } => format!("unsupported type {mime}"),
+            Self::RemoteFetchDisabled { .. } => "remote fetch disabled".to_string(),
+            Self::RemoteHostNotAllowed { host, .. } => format!("host {host} not allowed"),
+            Self::ImageSourceNotFound { .. } => "not found".to_string(),
+            Self::InvalidMarker { reason, .. }
+            | Self::RemoteFetchFailed { reason, .. }
+            | Self::LocalReadFailed { reason, .. } => reason.clone(),
+        }
+    }
+}
+
 pub fn parse_image_markers(content: &str) -> (String, Vec<String>) {
     let mut refs = Vec::new();
     let mut cleaned = String::with_capacity(content.len());
@@ -98,6 +140,40 @@ pub fn parse_image_markers(content: &str) -> (String, Vec<String>) {
     (cleaned.trim().to_string(), refs)
 }
 
+/// Rewrite each non-empty image marker for which `replace` returns `Some`;
+/// other markers are kept verbatim. Markers are visited in the same order as
+/// [`parse_image_markers`] returns them.
+fn replace_image_markers(content: &str, mut replace: impl FnMut(&str) -> Option<String>) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut cursor = 0usize;
+
+    while let Some(rel_start) = content[cursor..].find(IMAGE_MARKER_PREFIX) {
+        let start = cursor + rel_start;
+        output.push_str(&content[cursor..start]);
+
+        let marker_start = start + IMAGE_MARKER_PREFIX.len();
+        let Some(rel_end) = content[marker_start..].find(']') else {
+            cursor = start;
+            break;
+        };
+
+        let end = marker_start + rel_end;
+        let candidate = content[marker_start..end].trim();
+        match Some(candidate)
+            .filter(|candidate| !candidate.is_empty())
+            .and_then(&mut replace)
+        {
+            Some(replacement) => output.push_str(&replacement),
+            None => output.push_str(&content[start..=end]),
+        }
+
+        cursor = end + 1;
+    }
+
+    output.push_str(&content[cursor..]);
+    output
+}
+
 pub fn parse_video_markers(content: &str) -> (String, Vec<String>) {
     let mut refs = Vec::new();
     let mut cleaned = String::with_capacity(content.len());
@@ -175,6 +251,26 @@ pub fn extract_ollama_image_payload(image_ref: &str) -> Option<String> {
 pub async fn prepare_messages_for_provider(
     messages: &[ChatMessage],
     config: &MultimodalConfig,
+) -> anyhow::Result<PreparedMessages> {
+    prepare_messages(messages, config, false).await
+}
+
+/// Like [`prepare_messages_for_provider`], but an image that cannot be loaded
+/// (missing, too large, fetch failure, ...) no longer fails the whole turn:
+/// its marker is replaced by `[image unavailable: <reason>]` and it is listed
+/// in [`PreparedMessages::failed_images`]. Image and video count limits are
+/// still enforced.
+pub async fn prepare_messages_for_provider_lenient(
+    messages: &[ChatMessage],
+    config: &MultimodalConfig,
+) -> anyhow::Result<PreparedMessages> {
+    prepare_messages(messages, config, true).await
+}
+
+async fn prepare_messages(
+    messages: &[ChatMessage],
+    config: &MultimodalConfig,
+    lenient: bool,
 ) -> anyhow::Result<PreparedMessages> {
     let (max_images, max_image_size_mb) = config.effective_limits();
     let max_bytes = max_image_size_mb.saturating_mul(1024 * 1024);
@@ -203,12 +299,15 @@ pub async fn prepare_messages_for_provider(
             messages: messages.to_vec(),
             contains_images: false,
             contains_videos: false,
+            failed_images: Vec::new(),
         });
     }
 
     let remote_client = build_runtime_proxy_client_with_timeouts("provider.ollama", 30, 10);
 
     let mut normalized_messages = Vec::with_capacity(messages.len());
+    let mut failed_images = Vec::new();
+    let mut total_image_bytes = 0usize;
     for message in messages {
         if message.role != "user" {
             normalized_messages.push(message.clone());
@@ -216,7 +315,7 @@ pub async fn prepare_messages_for_provider(
         }
 
         let (text_after_images, image_refs) = parse_image_markers(&message.content);
-        let (cleaned_text, video_refs) = parse_video_markers(&text_after_images);
+        let (mut cleaned_text, video_refs) = parse_video_markers(&text_after_images);
 
         if image_refs.is_empty() && video_refs.is_empty() {
             normalized_messages.push(message.clone());
@@ -224,13 +323,49 @@ pub async fn prepare_messages_for_provider(
         }
 
         let mut normalized_image_refs = Vec::with_capacity(image_refs.len());
+        let mut unavailable = Vec::with_capacity(image_refs.len());
         for reference in &image_refs {
-            let data_uri =
-                normalize_image_reference(reference, config, max_bytes, &remote_client).await?;
-            normalized_image_refs.push(data_uri);
+            match normalize_image_reference(reference, config, max_bytes, &remote_client).await {
+                Ok(data_uri) => {
+                    total_image_bytes = total_image_bytes.saturating_add(data_uri.len());
+                    if let Some(max_total_bytes) = config.max_total_bytes {
+                        if total_image_bytes > max_total_bytes {
+                            return Err(MultimodalError::TotalPayloadTooLarge {
+                                total_bytes: total_image_bytes,
+                                max_total_bytes,
+                            }
+                            .into());
+                        }
+                    }
+                    normalized_image_refs.push(data_uri);
+                    unavailable.push(None);
+                }
+                Err(error) if lenient => {
+                    tracing::warn!("multimodal: skipping image '{reference}': {error}");
+                    let reason = error
+                        .downcast_ref::<MultimodalError>()
+                        .map_or_else(|| error.to_string(), MultimodalError::unavailable_reason);
+                    unavailable.push(Some(format!("[image unavailable: {reason}]")));
+                    failed_images.push(FailedImage {
+                        reference: reference.clone(),
+                        error: error.to_string(),
+                    });
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        if unavailable.iter().any(Option::is_some) {
+            // Put each note where its marker was, then strip the markers of
+            // the images that did load.
+            let mut notes = unavailable.into_iter();
+            let annotated = replace_image_markers(&message.content, |_| notes.next().flatten());
+            let (text_after_images, _) = parse_image_markers(&annotated);
+            cleaned_text = parse_video_markers(&text_after_images).0;
         }
 
-        let content = compose_multimodal_message(&cleaned_text, &normalized_image_refs, &video_refs);
+        let content =
+            compose_multimodal_message(&cleaned_text, &normalized_image_refs, &video_refs);
         normalized_messages.push(ChatMessage {
             role: message.role.clone(),
             content,
@@ -241,6 +376,7 @@ pub async fn prepare_messages_for_provider(
         messages: normalized_messages,
         contains_images: found_images > 0,
         contains_videos: found_videos > 0,
+        failed_images,
     })
 }
 
@@ -292,6 +428,18 @@ async fn normalize_image_reference(
             .into());
         }
 
+        let host = reqwest::Url::parse(source)
+            .ok()
+            .and_then(|url| url.host_str().map(ToString::to_string))
+            .unwrap_or_default();
+        if !config.is_remote_host_allowed(&host) {
+            return Err(MultimodalError::RemoteHostNotAllowed {
+                input: source.to_string(),
+                host,
+            }
+            .into());
+        }
+
         return normalize_remote_image(source, max_bytes, remote_client).await;
     }
 
@@ -345,20 +493,23 @@ async fn normalize_remote_image(
     max_bytes: usize,
     remote_client: &Client,
 ) -> anyhow::Result<String> {
-    let response = remote_client.get(source).send().await.map_err(|error| {
-        MultimodalError::RemoteFetchFailed {
-            input: source.to_string(),
-            reason: error.to_string(),
-        }
-    })?;
+    let fetch_failed = |reason: String| MultimodalError::RemoteFetchFailed {
+        input: source.to_string(),
+        reason,
+    };
+
+    // Compression is negotiated for this request only; the body is decoded
+    // here so the size cap applies to the decoded image, not the wire bytes.
+    let response = remote_client
+        .get(source)
+        .header(reqwest::header::ACCEPT_ENCODING, "gzip, deflate")
+        .send()
+        .await
+        .map_err(|error| fetch_failed(error.to_string()))?;
 
     let status = response.status();
     if !status.is_success() {
-        return Err(MultimodalError::RemoteFetchFailed {
-            input: source.to_string(),
-            reason: format!("HTTP {status}"),
-        }
-        .into());
+        return Err(fetch_failed(format!("HTTP {status}")).into());
     }
 
     if let Some(content_length) = response.content_length() {
@@ -371,16 +522,35 @@ async fn normalize_remote_image(
         .get(reqwest::header::CONTENT_TYPE)
         .and_then(|value| value.to_str().ok())
         .map(ToString::to_string);
+    let content_encoding = response
+        .headers()
+        .get(reqwest::header::CONTENT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("identity")
+        .trim()
+        .to_ascii_lowercase();
 
-    let bytes = response
-        .bytes()
-        .await
-        .map_err(|error| MultimodalError::RemoteFetchFailed {
-            input: source.to_string(),
-            reason: error.to_string(),
-        })?;
-
-    validate_size(source, bytes.len(), max_bytes)?;
+    let Some(mut body) = BodyDecoder::new(&content_encoding, max_bytes) else {
+        return Err(
+            fetch_failed(format!("unsupported content encoding '{content_encoding}'")).into(),
+        );
+    };
+    let mut stream = response.bytes_stream();
+    let mut decoded = Ok(());
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|error| fetch_failed(error.to_string()))?;
+        decoded = body.write_all(&chunk);
+        if decoded.is_err() {
+            break;
+        }
+    }
+    let bytes = match decoded.and_then(|()| body.finish()) {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            validate_size(source, body.capped().attempted, max_bytes)?;
+            return Err(fetch_failed(format!("invalid {content_encoding} body: {error}")).into());
+        }
+    };
 
     let mime = detect_mime(None, bytes.as_ref(), content_type.as_deref()).ok_or_else(|| {
         MultimodalError::UnsupportedMime {
@@ -394,6 +564,96 @@ async fn normalize_remote_image(
     Ok(format!("data:{mime};base64,{}", STANDARD.encode(bytes)))
 }
 
+/// Collects a decoded response body and fails as soon as it would exceed
+/// `max_bytes`, so a compressed body cannot expand past the cap in memory.
+struct CappedBody {
+    bytes: Vec<u8>,
+    max_bytes: usize,
+    /// Size the body would have reached; exceeds `max_bytes` after a failure.
+    attempted: usize,
+}
+
+impl std::io::Write for CappedBody {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.attempted = self.bytes.len() + buf.len();
+        if self.attempted > self.max_bytes {
+            return Err(std::io::Error::other("body exceeds size limit"));
+        }
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Streaming decoder for the `Content-Encoding`s requested by
+/// [`normalize_remote_image`].
+enum BodyDecoder {
+    Identity(CappedBody),
+    Gzip(flate2::write::GzDecoder<CappedBody>),
+    Deflate(flate2::write::ZlibDecoder<CappedBody>),
+}
+
+impl BodyDecoder {
+    fn new(content_encoding: &str, max_bytes: usize) -> Option<Self> {
+        let sink = CappedBody {
+            bytes: Vec::new(),
+            max_bytes,
+            attempted: 0,
+        };
+        match content_encoding {
+            "" | "identity" => Some(Self::Identity(sink)),
+            "gzip" | "x-gzip" => Some(Self::Gzip(flate2::write::GzDecoder::new(sink))),
+            "deflate" => Some(Self::Deflate(flate2::write::ZlibDecoder::new(sink))),
+            _ => None,
+        }
+    }
+
+    fn capped(&self) -> &CappedBody {
+        match self {
+            Self::Identity(sink) => sink,
+            Self::Gzip(decoder) => decoder.get_ref(),
+            Self::Deflate(decoder) => decoder.get_ref(),
+        }
+    }
+
+    /// Flush buffered compressed input and take the decoded body.
+    fn finish(&mut self) -> std::io::Result<Vec<u8>> {
+        let sink = match self {
+            Self::Identity(sink) => sink,
+            Self::Gzip(decoder) => {
+                decoder.try_finish()?;
+                decoder.get_mut()
+            }
+            Self::Deflate(decoder) => {
+                decoder.try_finish()?;
+                decoder.get_mut()
+            }
+        };
+        Ok(std::mem::take(&mut sink.bytes))
+    }
+}
+
+impl std::io::Write for BodyDecoder {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Identity(sink) => sink.write(buf),
+            Self::Gzip(decoder) => decoder.write(buf),
+            Self::Deflate(decoder) => decoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Identity(sink) => sink.flush(),
+            Self::Gzip(decoder) => decoder.flush(),
+            Self::Deflate(decoder) => decoder.flush(),
+        }
+    }
+}
+
 async fn normalize_local_image(source: &str, max_bytes: usize) -> anyhow::Result<String> {
     let path = Path::new(source);
     if !path.exists() || !path.is_file() {
@@ -502,7 +762,7 @@ fn mime_from_extension(ext: &str) -> Option<&'static str> {
     }
 }
 
-fn mime_from_magic(bytes: &[u8]) -> Option<&'static str> {
+pub fn mime_from_magic(bytes: &[u8]) -> Option<&'static str> {
     if bytes.len() >= 8 && bytes.starts_with(&[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n']) {
         return Some("image/png");
     }
@@ -615,6 +875,60 @@ mod tests {
         assert!(refs[0].starts_with("data:image/png;base64,"));
     }
 
+    #[tokio::test]
+    async fn lenient_prepare_replaces_failed_images_with_note() {
+        let temp = tempfile::tempdir().unwrap();
+        let image_path = temp.path().join("sample.png");
+        std::fs::write(
+            &image_path,
+            [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'],
+        )
+        .unwrap();
+
+        let messages = vec![ChatMessage::user(format!(
+            "Compare [IMAGE:{}] with [IMAGE:https://example.com/b.png] please",
+            image_path.display()
+        ))];
+
+        let strict = prepare_messages_for_provider(&messages, &MultimodalConfig::default()).await;
+        assert!(strict.is_err());
+
+        let prepared =
+            prepare_messages_for_provider_lenient(&messages, &MultimodalConfig::default())
+                .await
+                .unwrap();
+
+        assert!(prepared.contains_images);
+        assert_eq!(prepared.failed_images.len(), 1);
+        assert_eq!(
+            prepared.failed_images[0].reference,
+            "https://example.com/b.png"
+        );
+        assert!(prepared.failed_images[0]
+            .error
+            .contains("remote image fetch is disabled"));
+
+        let (cleaned, refs) = parse_image_markers(&prepared.messages[0].content);
+        assert_eq!(
+            cleaned,
+            "Compare  with [image unavailable: remote fetch disabled] please"
+        );
+        assert_eq!(refs.len(), 1);
+        assert!(refs[0].starts_with("data:image/png;base64,"));
+    }
+
+    #[test]
+    fn replace_image_markers_keeps_unreplaced_markers() {
+        let mut calls = Vec::new();
+        let replaced =
+            replace_image_markers("a [IMAGE:x] b [IMAGE:] c [IMAGE: y ] d [IMAGE:z", |r| {
+                calls.push(r.to_string());
+                (r == "y").then(|| "Y".to_string())
+            });
+        assert_eq!(replaced, "a [IMAGE:x] b [IMAGE:] c Y d [IMAGE:z");
+        assert_eq!(calls, ["x", "y"]);
+    }
+
     #[tokio::test]
     async fn prepare_messages_rejects_too_many_images() {
         let messages = vec![ChatMessage::user(
@@ -625,8 +939,10 @@ mod tests {
             max_images: 1,
             max_image_size_mb: 5,
             allow_remote_fetch: false,
+            remote_fetch_allowed_hosts: Vec::new(),
             max_videos: 2,
             max_video_size_mb: 20,
+            max_total_bytes: None,
         };
 
         let error = prepare_messages_for_provider(&messages, &config)
@@ -653,6 +969,143 @@ mod tests {
             .contains("multimodal remote image fetch is disabled"));
     }
 
+    #[tokio::test]
+    async fn prepare_messages_rejects_remote_host_outside_allowlist() {
+        let messages = vec![ChatMessage::user(
+            "Look [IMAGE:https://evil.example.org/img.png]".to_string(),
+        )];
+        let config = MultimodalConfig {
+            allow_remote_fetch: true,
+            remote_fetch_allowed_hosts: vec!["example.com".to_string()],
+            ..MultimodalConfig::default()
+        };
+
+        let error = prepare_messages_for_provider(&messages, &config)
+            .await
+            .expect_err("should reject host outside the allowlist");
+
+        assert!(error.to_string().contains("'evil.example.org' is not in"));
+        assert!(config.is_remote_host_allowed("cdn.example.com"));
+        assert!(!config.is_remote_host_allowed("notexample.com"));
+    }
+
+    #[tokio::test]
+    async fn remote_image_with_gzip_encoding_is_decompressed() {
+        use flate2::{write::GzEncoder, Compression};
+        use wiremock::matchers::{headers, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let png = [
+            0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n', 0, 0, 0, 0,
+        ];
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&png).unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/img"))
+            .and(headers("accept-encoding", vec!["gzip", "deflate"]))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Encoding", "gzip")
+                    .set_body_bytes(gzipped),
+            )
+            .mount(&server)
+            .await;
+
+        let data_uri =
+            normalize_remote_image(&format!("{}/img", server.uri()), 1024, &Client::new())
+                .await
+                .unwrap();
+
+        assert_eq!(
+            data_uri,
+            format!("data:image/png;base64,{}", STANDARD.encode(png))
+        );
+    }
+
+    #[tokio::test]
+    async fn prepare_messages_rejects_images_over_total_payload_cap() {
+        use std::fmt::Write;
+
+        let temp = tempfile::tempdir().unwrap();
+        let mut content = String::from("Compare these");
+        for index in 0..3 {
+            let image_path = temp.path().join(format!("{index}.png"));
+            let mut bytes = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+            bytes.resize(600, 0);
+            std::fs::write(&image_path, bytes).unwrap();
+            write!(content, " [IMAGE:{}]", image_path.display()).unwrap();
+        }
+        let messages = vec![ChatMessage::user(content)];
+
+        // Each image is ~822 bytes as a data URI; two fit, three do not.
+        let config = MultimodalConfig {
+            max_total_bytes: Some(2000),
+            ..MultimodalConfig::default()
+        };
+        let error = prepare_messages_for_provider(&messages, &config)
+            .await
+            .expect_err("should reject images over the aggregate cap");
+        assert!(error
+            .to_string()
+            .contains("multimodal total image payload limit exceeded"));
+
+        let error = prepare_messages_for_provider_lenient(&messages, &config)
+            .await
+            .expect_err("aggregate cap applies in lenient mode too");
+        assert!(error.to_string().contains("max_total_bytes=2000"));
+
+        let config = MultimodalConfig {
+            max_total_bytes: Some(3000),
+            ..MultimodalConfig::default()
+        };
+        let prepared = prepare_messages_for_provider(&messages, &config)
+            .await
+            .unwrap();
+        assert_eq!(
+            parse_image_markers(&prepared.messages[0].content).1.len(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn remote_image_decoded_past_limit_is_rejected() {
+        use flate2::{write::GzEncoder, Compression};
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // Tiny on the wire, far over the cap once decoded.
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&vec![0u8; 1024 * 1024]).unwrap();
+        let gzipped = encoder.finish().unwrap();
+        assert!(gzipped.len() < 4096);
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bomb"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Encoding", "gzip")
+                    .set_body_bytes(gzipped),
+            )
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/bomb", server.uri());
+        let error = normalize_remote_image(&url, 4096, &Client::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<MultimodalError>(),
+            Some(MultimodalError::ImageTooLarge {
+                max_bytes: 4096,
+                ..
+            })
+        ));
+    }
+
     #[tokio::test]
     async fn prepare_messages_rejects_oversized_local_image() {
         let temp = tempfile::tempdir().unwrap();
@@ -669,8 +1122,10 @@ mod tests {
             max_images: 4,
             max_image_size_mb: 1,
             allow_remote_fetch: false,
+            remote_fetch_allowed_hosts: Vec::new(),
             max_videos: 2,
             max_video_size_mb: 20,
+            max_total_bytes: None,
         };
 
         let error = prepare_messages_for_provider(&messages, &config)
@@ -759,8 +1214,10 @@ mod tests {
             max_images: 4,
             max_image_size_mb: 5,
             allow_remote_fetch: false,
+            remote_fetch_allowed_hosts: Vec::new(),
             max_videos: 2,
             max_video_size_mb: 20,
+            max_total_bytes: None,
         };
         let prepared = prepare_messages_for_provider(&messages, &config)
             .await
@@ -781,8 +1238,10 @@ mod tests {
             max_images: 4,
             max_image_size_mb: 5,
             allow_remote_fetch: false,
+            remote_fetch_allowed_hosts: Vec::new(),
             max_videos: 2,
             max_video_size_mb: 20,
+            max_total_bytes: None,
         };
         let error = prepare_messages_for_provider(&messages, &config)
             .await
@@ -806,8 +1265,10 @@ mod tests {
             max_images: 4,
             max_image_size_mb: 5,
             allow_remote_fetch: false,
+            remote_fetch_allowed_hosts: Vec::new(),
             max_videos: 2,
             max_video_size_mb: 20,
+            max_total_bytes: None,
         };
         let prepared = prepare_messages_for_provider(&messages, &config)
             .await
@@ -870,8 +1331,10 @@ mod tests {
             max_images: 4,
             max_image_size_mb: 5,
             allow_remote_fetch: false,
+            remote_fetch_allowed_hosts: Vec::new(),
             max_videos: 2,
             max_video_size_mb: 20,
+            max_total_bytes: None,
         };
 
         let prepared = prepare_messages_for_provider(&messages, &config)
@@ -900,8 +1363,10 @@ mod tests {
             max_images: 4,
             max_image_size_mb: 5,
             allow_remote_fetch: false,
+            remote_fetch_allowed_hosts: Vec::new(),
             max_videos: 2,
             max_video_size_mb: 20,
+            max_total_bytes: None,
         };
         let error = prepare_messages_for_provider(&messages, &config)
             .await
@@ -925,8 +1390,10 @@ mod tests {
             max_images: 4,
             max_image_size_mb: 5,
             allow_remote_fetch: false,
+            remote_fetch_allowed_hosts: Vec::new(),
             max_videos: 2,
             max_video_size_mb: 20,
+            max_total_bytes: None,
         };
         let prepared = prepare_messages_for_provider(&messages, &config)
             .await
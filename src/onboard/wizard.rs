@@ -4737,6 +4737,15 @@ fn setup_channels() -> Result<ChannelsConfig> {
                         port,
                         stream_mode: StreamMode::default(),
                         draft_update_interval_ms: 1000,
+                        draft_update_min_interval_ms: 500,
+                        draft_update_debounce_ms: 0,
+                        urgent_escalation: crate::config::schema::LarkUrgentEscalation::Off,
+                        api_base_url: None,
+                        ws_base_url: None,
+                        ack_reaction_emoji: None,
+                        max_inbound_chars: None,
+                        inbound_overflow: crate::config::schema::LarkInboundOverflow::Truncate,
+                        interleave_attachments: false,
                     });
                 } else {
                     config.lark = Some(LarkConfig {
@@ -4750,6 +4759,15 @@ fn setup_channels() -> Result<ChannelsConfig> {
                         port,
                         stream_mode: StreamMode::default(),
                         draft_update_interval_ms: 1000,
+                        draft_update_min_interval_ms: 500,
+                        draft_update_debounce_ms: 0,
+                        urgent_escalation: crate::config::schema::LarkUrgentEscalation::Off,
+                        api_base_url: None,
+                        ws_base_url: None,
+                        ack_reaction_emoji: None,
+                        max_inbound_chars: None,
+                        inbound_overflow: crate::config::schema::LarkInboundOverflow::Truncate,
+                        interleave_attachments: false,
                     });
                 }
             }
@@ -6992,6 +7010,15 @@ mod tests {
             port: None,
             stream_mode: crate::config::schema::StreamMode::default(),
             draft_update_interval_ms: 500,
+            draft_update_min_interval_ms: 500,
+            draft_update_debounce_ms: 0,
+            urgent_escalation: crate::config::schema::LarkUrgentEscalation::Off,
+            api_base_url: None,
+            ws_base_url: None,
+            ack_reaction_emoji: None,
+            max_inbound_chars: None,
+            inbound_overflow: crate::config::schema::LarkInboundOverflow::Truncate,
+            interleave_attachments: false,
         });
         assert!(has_launchable_channels(&channels));
     }
@@ -0,0 +1,73 @@
+//! Request capture for golden tests.
+//!
+//! A provider with a [`RequestCapture`] attached runs in dry-run mode: it
+//! records the URL and exact JSON body it would send, skips the HTTP call and
+//! answers with an empty response. Tests can then compare the captured body
+//! against a golden file to pin down provider-specific formatting (multimodal
+//! payloads in particular) without a live endpoint.
+
+use serde::Serialize;
+use std::sync::Arc;
+
+/// One request a provider would have sent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedRequest {
+    pub url: String,
+    pub body: serde_json::Value,
+}
+
+/// Shared sink for captured requests. Clones record into the same list.
+#[derive(Debug, Clone, Default)]
+pub struct RequestCapture {
+    requests: Arc<parking_lot::Mutex<Vec<CapturedRequest>>>,
+}
+
+impl RequestCapture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serialize `body` and record it as a request to `url`.
+    pub fn record(&self, url: &str, body: &impl Serialize) -> anyhow::Result<()> {
+        let body = serde_json::to_value(body)?;
+        self.requests.lock().push(CapturedRequest {
+            url: url.to_string(),
+            body,
+        });
+        Ok(())
+    }
+
+    /// All requests captured so far, oldest first.
+    pub fn requests(&self) -> Vec<CapturedRequest> {
+        self.requests.lock().clone()
+    }
+
+    /// The most recently captured request.
+    pub fn last(&self) -> Option<CapturedRequest> {
+        self.requests.lock().last().cloned()
+    }
+}
+
+/// Compare `actual` with the JSON golden file at `path` (relative to the crate
+/// root). Set `ZEROCLAW_UPDATE_GOLDEN=1` to rewrite the file instead.
+#[cfg(test)]
+pub(crate) fn assert_golden(path: &str, actual: &serde_json::Value) {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+    if std::env::var_os("ZEROCLAW_UPDATE_GOLDEN").is_some() {
+        let mut pretty = serde_json::to_string_pretty(actual).unwrap();
+        pretty.push('\n');
+        std::fs::write(&path, pretty).unwrap();
+        return;
+    }
+    let expected: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("missing golden file {}: {e}", path.display())),
+    )
+    .unwrap();
+    assert_eq!(
+        actual,
+        &expected,
+        "request differs from {}; rerun with ZEROCLAW_UPDATE_GOLDEN=1 if the change is intended",
+        path.display()
+    );
+}
@@ -18,6 +18,7 @@
 
 pub mod anthropic;
 pub mod bedrock;
+pub mod capture;
 pub mod compatible;
 pub mod copilot;
 pub mod external;
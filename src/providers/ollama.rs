@@ -1,4 +1,5 @@
 use crate::multimodal;
+use crate::providers::capture::RequestCapture;
 use crate::providers::traits::{
     ChatMessage, ChatResponse, Provider, ProviderCapabilities, TokenUsage, ToolCall,
 };
@@ -11,6 +12,7 @@ pub struct OllamaProvider {
     base_url: String,
     api_key: Option<String>,
     reasoning_enabled: Option<bool>,
+    capture: Option<RequestCapture>,
 }
 
 // ─── Request Structures ───────────────────────────────────────────────────────
@@ -127,9 +129,17 @@ impl OllamaProvider {
             base_url: Self::normalize_base_url(base_url.unwrap_or("http://localhost:11434")),
             api_key,
             reasoning_enabled,
+            capture: None,
         }
     }
 
+    /// Dry-run mode: record each request body in `capture` instead of sending
+    /// it, and answer with an empty response.
+    pub fn with_request_capture(mut self, capture: RequestCapture) -> Self {
+        self.capture = Some(capture);
+        self
+    }
+
     fn is_local_endpoint(&self) -> bool {
         reqwest::Url::parse(&self.base_url)
             .ok()
@@ -336,6 +346,19 @@ impl OllamaProvider {
             request.tools.as_ref().map_or(0, |t| t.len()),
         );
 
+        if let Some(capture) = &self.capture {
+            capture.record(&url, &request)?;
+            return Ok(ApiChatResponse {
+                message: ResponseMessage {
+                    content: String::new(),
+                    tool_calls: Vec::new(),
+                    thinking: None,
+                },
+                prompt_eval_count: None,
+                eval_count: None,
+            });
+        }
+
         let mut request_builder = self.http_client().post(&url).json(&request);
 
         if should_auth {
@@ -1028,6 +1051,31 @@ mod tests {
         assert_eq!(images, &vec!["abcd==".to_string()]);
     }
 
+    #[tokio::test]
+    async fn image_request_matches_golden() {
+        let capture = RequestCapture::new();
+        let provider = OllamaProvider::new(Some("http://ollama.local:11434"), None)
+            .with_request_capture(capture.clone());
+        let messages = vec![
+            ChatMessage::system("You are a vision assistant."),
+            ChatMessage::user(
+                "What is in this picture? [IMAGE:data:image/png;base64,iVBORw0KGgo=]",
+            ),
+        ];
+
+        provider
+            .chat_with_history(&messages, "llava:13b", 0.2)
+            .await
+            .unwrap();
+
+        let request = capture.last().expect("request captured");
+        assert_eq!(request.url, "http://ollama.local:11434/api/chat");
+        crate::providers::capture::assert_golden(
+            "tests/fixtures/golden/ollama_image_request.json",
+            &request.body,
+        );
+    }
+
     #[test]
     fn capabilities_include_native_tools_and_vision() {
         let provider = OllamaProvider::new(None, None);
@@ -0,0 +1,177 @@
+//! Shared retry/backoff policy for outbound calls.
+//!
+//! [`RetryPolicy::retry`] runs an operation until it succeeds, a
+//! caller-supplied classifier says the outcome is not worth retrying, or the
+//! attempt budget is spent. Delays grow exponentially from `base_delay`, are
+//! capped at `max_delay`, and get up to `jitter` of random spread so
+//! concurrent callers don't retry in lockstep.
+
+use std::future::Future;
+use std::time::Duration;
+
+/// How a classifier wants [`RetryPolicy::retry`] to treat an outcome.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// Return the outcome as-is (success, or a permanent failure).
+    Stop,
+    /// Retry after the policy's backoff delay.
+    Retry,
+    /// Retry after a server-specified delay (e.g. `Retry-After`).
+    RetryAfter(Duration),
+}
+
+/// Exponential backoff with a cap and random jitter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first one. `0` behaves like `1`.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub base_delay: Duration,
+    /// Upper bound for the exponential delay (jitter is added on top).
+    pub max_delay: Duration,
+    /// Maximum random extra delay added to each backoff.
+    pub jitter: Duration,
+}
+
+impl RetryPolicy {
+    /// Backoff before retry number `retry` (0-based), without jitter:
+    /// `base_delay * 2^retry`, capped at `max_delay`.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1u32.checked_shl(retry).unwrap_or(u32::MAX);
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    /// [`backoff`](Self::backoff) plus a random `0..=jitter` spread.
+    fn delay(&self, retry: u32) -> Duration {
+        let jitter_ms = u64::try_from(self.jitter.as_millis()).unwrap_or(u64::MAX);
+        let spread = if jitter_ms == 0 {
+            0
+        } else {
+            rand::random_range(0..=jitter_ms)
+        };
+        self.backoff(retry) + Duration::from_millis(spread)
+    }
+
+    /// Run `op` until `classify` returns [`RetryDecision::Stop`] or the
+    /// attempt budget is spent, and return the last outcome.
+    pub async fn retry<T, E, C, Op, Fut>(&self, classify: C, mut op: Op) -> Result<T, E>
+    where
+        C: Fn(&Result<T, E>) -> RetryDecision,
+        Op: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let max_attempts = self.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            let outcome = op().await;
+            if attempt >= max_attempts {
+                return outcome;
+            }
+            let wait = match classify(&outcome) {
+                RetryDecision::Stop => return outcome,
+                RetryDecision::Retry => self.delay(attempt - 1),
+                RetryDecision::RetryAfter(wait) => wait,
+            };
+            tracing::debug!(
+                attempt,
+                max_attempts,
+                wait_ms = u64::try_from(wait.as_millis()).unwrap_or(u64::MAX),
+                "retrying after failed attempt"
+            );
+            tokio::time::sleep(wait).await;
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(4),
+            jitter: Duration::ZERO,
+        }
+    }
+
+    fn classify(outcome: &Result<u32, &'static str>) -> RetryDecision {
+        match outcome {
+            Ok(_) | Err("fatal") => RetryDecision::Stop,
+            Err(_) => RetryDecision::Retry,
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(10),
+            jitter: Duration::ZERO,
+        };
+        let schedule: Vec<u64> = (0..6).map(|n| policy.backoff(n).as_secs()).collect();
+        assert_eq!(schedule, vec![1, 2, 4, 8, 10, 10]);
+        assert_eq!(policy.backoff(200), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        let policy = RetryPolicy {
+            jitter: Duration::from_millis(250),
+            ..policy(3)
+        };
+        for retry in 0..4 {
+            let delay = policy.delay(retry);
+            assert!(delay >= policy.backoff(retry));
+            assert!(delay <= policy.backoff(retry) + policy.jitter);
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_retryable_errors_until_success() {
+        let calls = AtomicU32::new(0);
+        let result = policy(5)
+            .retry(classify, || async {
+                match calls.fetch_add(1, Ordering::SeqCst) {
+                    0 | 1 => Err("transient"),
+                    n => Ok(n),
+                }
+            })
+            .await;
+        assert_eq!(result, Ok(2));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn stops_on_non_retryable_error() {
+        let calls = AtomicU32::new(0);
+        let result = policy(5)
+            .retry(classify, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err::<u32, _>("fatal")
+            })
+            .await;
+        assert_eq!(result, Err("fatal"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn returns_last_outcome_when_attempts_are_exhausted() {
+        let calls = AtomicU32::new(0);
+        let result = policy(3)
+            .retry(
+                |_: &Result<u32, u32>| RetryDecision::RetryAfter(Duration::from_millis(1)),
+                || async { Err(calls.fetch_add(1, Ordering::SeqCst)) },
+            )
+            .await;
+        assert_eq!(result, Err(2));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
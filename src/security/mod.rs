@@ -66,6 +66,77 @@ pub fn redact(value: &str) -> String {
     }
 }
 
+/// Keys whose values must never appear in logged request/response bodies.
+/// Matched case-insensitively against JSON object keys and `key: value` /
+/// `key=value` pairs in plain text.
+const SENSITIVE_LOG_KEYS: &[&str] = &[
+    "app_secret",
+    "tenant_access_token",
+    "app_access_token",
+    "user_access_token",
+    "access_token",
+    "refresh_token",
+    "authorization",
+];
+
+const REDACTED_PLACEHOLDER: &str = "[REDACTED]";
+
+static SENSITIVE_LOG_PAIR_REGEX: std::sync::LazyLock<regex::Regex> =
+    std::sync::LazyLock::new(|| {
+        let keys = SENSITIVE_LOG_KEYS.join("|");
+        regex::Regex::new(&format!(
+            r#"(?i)("?(?:{keys})"?\s*[:=]\s*)(?:"[^"]*"|(?:bearer\s+|basic\s+)?[^\s,;&"}}]+)"#
+        ))
+        .expect("sensitive log key regex must compile")
+    });
+
+fn is_sensitive_log_key(key: &str) -> bool {
+    SENSITIVE_LOG_KEYS
+        .iter()
+        .any(|candidate| key.eq_ignore_ascii_case(candidate))
+}
+
+fn redact_json_value(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, entry) in map.iter_mut() {
+                if is_sensitive_log_key(key) && !entry.is_null() {
+                    *entry = serde_json::Value::String(REDACTED_PLACEHOLDER.to_string());
+                } else {
+                    redact_json_value(entry);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_json_value),
+        _ => {}
+    }
+}
+
+/// Render a JSON request/response body for logging with sensitive fields
+/// (`app_secret`, `tenant_access_token`, `Authorization`, ...) masked.
+pub fn redact_json_for_log(body: &serde_json::Value) -> String {
+    let mut masked = body.clone();
+    redact_json_value(&mut masked);
+    masked.to_string()
+}
+
+/// Mask sensitive fields in a raw request/response body before logging.
+///
+/// JSON bodies are masked structurally; anything else falls back to masking
+/// `key: value` / `key=value` pairs for the known sensitive keys.
+pub fn redact_log_body(body: &str) -> String {
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(body) {
+        if value.is_object() || value.is_array() {
+            return redact_json_for_log(&value);
+        }
+    }
+    SENSITIVE_LOG_PAIR_REGEX
+        .replace_all(body, |caps: &regex::Captures| {
+            format!("{}{REDACTED_PLACEHOLDER}", &caps[1])
+        })
+        .into_owned()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,4 +168,37 @@ mod tests {
         assert_eq!(redact(""), "***");
         assert_eq!(redact("12345"), "1234***");
     }
+
+    #[test]
+    fn redact_json_for_log_masks_nested_tokens() {
+        let body = serde_json::json!({
+            "code": 0,
+            "tenant_access_token": "t-g1044ghJRUIJJ5ELM3NTLRBWIZ5KTCVQ",
+            "data": {"items": [{"App_Secret": "super-secret-value", "name": "ok"}]},
+        });
+        let logged = redact_json_for_log(&body);
+        assert!(!logged.contains("t-g1044ghJRUIJJ5ELM3NTLRBWIZ5KTCVQ"));
+        assert!(!logged.contains("super-secret-value"));
+        assert!(logged.contains("\"tenant_access_token\":\"[REDACTED]\""));
+        assert!(logged.contains("\"name\":\"ok\""));
+    }
+
+    #[test]
+    fn redact_log_body_masks_plain_text_pairs() {
+        let logged = redact_log_body(
+            "upstream rejected: Authorization: Bearer t-abc123def456, tenant_access_token=t-zzz999 code=99991663",
+        );
+        assert!(!logged.contains("t-abc123def456"));
+        assert!(!logged.contains("t-zzz999"));
+        assert!(logged.contains("Authorization: [REDACTED]"));
+        assert!(logged.contains("tenant_access_token=[REDACTED]"));
+        assert!(logged.contains("code=99991663"));
+    }
+
+    #[test]
+    fn redact_log_body_masks_json_text() {
+        let logged = redact_log_body(r#"{"app_id":"cli_x","app_secret":"s3cr3t-value"}"#);
+        assert!(!logged.contains("s3cr3t-value"));
+        assert!(logged.contains("cli_x"));
+    }
 }
@@ -6,7 +6,7 @@ use std::sync::OnceLock;
 
 const MAX_TEXT_FILE_BYTES: u64 = 512 * 1024;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, serde::Serialize)]
 pub struct SkillAuditReport {
     pub files_scanned: usize,
     pub findings: Vec<String>,
@@ -70,6 +70,17 @@ pub fn audit_skill_directory_with_boundary(
 }
 
 pub fn audit_open_skill_markdown(path: &Path, repo_root: &Path) -> Result<SkillAuditReport> {
+    audit_open_skill_markdown_with_boundary(path, repo_root, None)
+}
+
+/// Audit an open-skill markdown file in `repo_root`. When `link_boundary` is
+/// provided, markdown link escape detection uses it instead of the repository
+/// root; the file itself must still live inside the repository.
+pub fn audit_open_skill_markdown_with_boundary(
+    path: &Path,
+    repo_root: &Path,
+    link_boundary: Option<&Path>,
+) -> Result<SkillAuditReport> {
     if !path.exists() {
         bail!("Open-skill markdown not found: {}", path.display());
     }
@@ -86,11 +97,26 @@ pub fn audit_open_skill_markdown(path: &Path, repo_root: &Path) -> Result<SkillA
         );
     }
 
+    let canonical_boundary = match link_boundary {
+        Some(boundary) => boundary.canonicalize().with_context(|| {
+            format!(
+                "failed to canonicalize link boundary {}",
+                boundary.display()
+            )
+        })?,
+        None => canonical_repo.clone(),
+    };
+
     let mut report = SkillAuditReport {
         files_scanned: 1,
         findings: Vec::new(),
     };
-    audit_markdown_file(&canonical_repo, &canonical_repo, &canonical_path, &mut report)?;
+    audit_markdown_file(
+        &canonical_repo,
+        &canonical_boundary,
+        &canonical_path,
+        &mut report,
+    )?;
     Ok(report)
 }
 
@@ -161,8 +187,10 @@ fn audit_path(root: &Path, link_boundary: &Path, path: &Path, report: &mut Skill
 }
 
 fn audit_markdown_file(root: &Path, link_boundary: &Path, path: &Path, report: &mut SkillAuditReport) -> Result<()> {
-    let content = fs::read_to_string(path)
+    // Skill loading tolerates non-UTF-8 markdown, so audit the same lossy text.
+    let bytes = fs::read(path)
         .with_context(|| format!("failed to read markdown file {}", path.display()))?;
+    let content = String::from_utf8_lossy(&bytes);
     let rel = relative_display(root, path);
 
     if let Some(pattern) = detect_high_risk_snippet(&content) {
@@ -454,6 +454,7 @@ impl DelegateTool {
                 None,
                 None,
                 None,
+                None,
                 &[],
                 None,
             ),
@@ -227,19 +227,7 @@ impl DocsSyncTool {
                 Some(c) => c,
                 None => continue,
             };
-            let blocks = client.get_document_blocks(doc_id).await?;
-            let target_block_id = blocks.iter()
-                .find(|(_, bt)| *bt == 14)
-                .or_else(|| blocks.iter().find(|(_, bt)| *bt == 1))
-                .map(|(id, _)| id.clone())
-                .unwrap_or_else(|| doc_id.clone());
-            let update = crate::docs_sync::client::BlockUpdate {
-                block_id: target_block_id,
-                update_text_elements: serde_json::json!({
-                    "elements": [{ "text_run": { "content": content } }]
-                }),
-            };
-            client.batch_update_blocks(doc_id, &[update]).await?;
+            crate::docs_sync::diff::push_content(&client, doc_id, filename, &content).await?;
             pushed.push(filename.as_str());
         }
         Ok(ToolResult {
@@ -288,7 +276,7 @@ impl DocsSyncTool {
                 .unwrap_or_default();
         let mut updated = Vec::new();
         for (filename, doc_id) in &ds.document_ids {
-            let raw = client.get_raw_content(doc_id).await?;
+            let raw = crate::docs_sync::diff::fetch_content(&client, doc_id).await?;
             let target = self.workspace_dir.join(filename);
             // Reject symlinks but allow pulling new files (agent-initiated pull)
             if target.is_symlink() { continue; }
@@ -2,18 +2,144 @@
 //!
 //! Each `McpBridgeTool` wraps one MCP tool and delegates execution to
 //! `McpRegistry::call_tool()`. Tool names are namespaced as `mcp_{server}_{tool}`.
+//! A bridge is bound to the channel it was built for, so the registry can apply
+//! the `[mcp.channels]` allowlist on every call.
 
 use super::traits::{Tool, ToolResult};
+use crate::config::Config;
 use async_trait::async_trait;
+use std::path::PathBuf;
 use std::sync::Arc;
+use zeroclaw_mcp::config::McpServerConfig;
 use zeroclaw_mcp::registry::McpRegistry;
-use zeroclaw_mcp::types::McpToolInfo;
+use zeroclaw_mcp::types::{McpToolCallResult, McpToolInfo};
+
+/// Build the MCP registry described by `[mcp]` along with the server configs
+/// still waiting to be connected.
+///
+/// Returns `None` when MCP is disabled or neither `.mcp.json` nor any import
+/// registry is configured. `builtin_names` are the tool names MCP tools may
+/// not shadow.
+pub fn registry_from_config(
+    config: &Config,
+    builtin_names: impl IntoIterator<Item = String>,
+) -> Option<(Arc<McpRegistry>, Vec<McpServerConfig>)> {
+    if !config.mcp.enabled {
+        return None;
+    }
+    let (mcp_json_path, registry_paths) = config_paths(config);
+    if !mcp_json_path.exists() && registry_paths.is_empty() {
+        return None;
+    }
+
+    let mut registry = McpRegistry::new(config.mcp.tool_cap, builtin_names.into_iter().collect())
+        .with_config_path(mcp_json_path.clone())
+        .with_channel_servers(config.mcp.channels.clone())
+        .with_max_output_chars(config.mcp.max_output_chars)
+        .with_tool_call_timeout(std::time::Duration::from_secs(config.mcp.tool_timeout_secs))
+        .with_tool_timeouts(
+            config
+                .mcp
+                .tool_timeouts
+                .iter()
+                .map(|(tool, secs)| (tool.clone(), std::time::Duration::from_secs(*secs)))
+                .collect(),
+        )
+        .with_client_info(zeroclaw_mcp::types::Implementation {
+            name: "zeroclaw".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        });
+    if let Some(trace_dir) = &config.mcp.trace_dir {
+        registry = registry.with_trace_dir(
+            config
+                .workspace_dir
+                .join(shellexpand::tilde(trace_dir).as_ref()),
+            crate::security::redact_json_for_log,
+        );
+    }
+
+    let mut pending = match zeroclaw_mcp::config::parse_mcp_config(&mcp_json_path) {
+        Ok(configs) => configs,
+        Err(e) => {
+            tracing::warn!("Failed to load MCP config from {:?}: {}", mcp_json_path, e);
+            Vec::new()
+        }
+    };
+    zeroclaw_mcp::config::import_mcp_registries(&mut pending, &registry_paths);
+    Some((Arc::new(registry), pending))
+}
+
+/// Servers from `.mcp.json` followed by those imported from
+/// `[mcp].registries`.
+///
+/// A missing `.mcp.json` counts as empty, so registries work without one;
+/// an unreadable or malformed one is an error.
+pub fn configured_servers(config: &Config) -> anyhow::Result<Vec<McpServerConfig>> {
+    let (mcp_json_path, registry_paths) = config_paths(config);
+    let mut servers = zeroclaw_mcp::config::parse_mcp_config(&mcp_json_path)?;
+    zeroclaw_mcp::config::import_mcp_registries(&mut servers, &registry_paths);
+    Ok(servers)
+}
+
+/// The `.mcp.json` path and import registry paths under `[mcp]`, resolved
+/// against the workspace.
+fn config_paths(config: &Config) -> (PathBuf, Vec<PathBuf>) {
+    let config_path = config.mcp.config_path.as_deref().unwrap_or(".mcp.json");
+    let registry_paths = config
+        .mcp
+        .registries
+        .iter()
+        .map(|path| config.workspace_dir.join(shellexpand::tilde(path).as_ref()))
+        .collect();
+    (config.workspace_dir.join(config_path), registry_paths)
+}
+
+/// Connect each server in `pending` to `registry`, recording the outcome in
+/// the `mcp:<server>` health component. Failures are logged and skipped.
+pub async fn connect_servers(registry: &McpRegistry, pending: Vec<McpServerConfig>) {
+    for server_config in pending {
+        let name = server_config.name.clone();
+        let component = format!("mcp:{name}");
+        match registry.add_server(server_config).await {
+            Ok(tools) => {
+                tracing::info!(
+                    "MCP: server '{}' connected with {} tools",
+                    name,
+                    tools.len()
+                );
+                crate::health::mark_component_ok(&component);
+            }
+            Err(e) => {
+                tracing::warn!("MCP: failed to connect server '{}': {}", name, e);
+                crate::health::mark_component_error(&component, &e);
+            }
+        }
+    }
+}
+
+/// Bridge tools for every MCP tool `channel` may use under `[mcp.channels]`.
+pub async fn channel_tools(registry: &Arc<McpRegistry>, channel: &str) -> Vec<Box<dyn Tool>> {
+    registry
+        .get_tools_for_channel(channel)
+        .await
+        .into_iter()
+        .map(|(server_name, tool_info)| {
+            Box::new(McpBridgeTool::new(
+                server_name,
+                tool_info,
+                Arc::clone(registry),
+                channel.to_string(),
+            )) as Box<dyn Tool>
+        })
+        .collect()
+}
 
 /// A tool that wraps a single MCP tool from a connected server
 pub struct McpBridgeTool {
     server_name: String,
     tool_info: McpToolInfo,
     registry: Arc<McpRegistry>,
+    channel: String,
     namespaced_name: String,
 }
 
@@ -24,17 +150,72 @@ impl McpBridgeTool {
     /// * `server_name` - Name of the MCP server hosting this tool
     /// * `tool_info` - Tool metadata (name, description, input_schema)
     /// * `registry` - Shared MCP registry for tool execution
-    pub fn new(server_name: String, tool_info: McpToolInfo, registry: Arc<McpRegistry>) -> Self {
+    /// * `channel` - Channel the tool is offered on, checked against `[mcp.channels]`
+    pub fn new(
+        server_name: String,
+        tool_info: McpToolInfo,
+        registry: Arc<McpRegistry>,
+        channel: String,
+    ) -> Self {
         let namespaced_name = format!("mcp_{}_{}", server_name, tool_info.name);
         Self {
             server_name,
             tool_info,
             registry,
+            channel,
             namespaced_name,
         }
     }
 }
 
+/// Convert image content in an MCP tool result into `[IMAGE:data:…]` markers
+/// that `prepare_messages_for_provider` can hand to a vision model.
+///
+/// Covers `image` blocks and embedded `resource` blocks carrying an `image/*`
+/// blob; all other content is skipped.
+pub fn image_markers(result: &McpToolCallResult) -> Vec<String> {
+    let images = result
+        .images()
+        .into_iter()
+        .filter_map(|c| Some((c.mime_type.as_deref()?, c.data.as_deref()?)));
+    let image_resources = result
+        .content
+        .iter()
+        .filter_map(|c| c.resource.as_ref())
+        .filter_map(|r| Some((r.mime_type.as_deref()?, r.blob.as_deref()?)));
+    images
+        .chain(image_resources)
+        .filter(|(mime, data)| mime.starts_with("image/") && !data.trim().is_empty())
+        .map(|(mime, data)| format!("[IMAGE:data:{mime};base64,{}]", data.trim()))
+        .collect()
+}
+
+/// Convert an MCP tool result into the agent's `ToolResult`
+///
+/// Text blocks are newline-joined; on success any image markers follow the
+/// text so the next provider turn can see them.
+fn to_tool_result(result: &McpToolCallResult) -> ToolResult {
+    let output = result.text();
+    if result.is_error() {
+        return ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(output),
+        };
+    }
+
+    let output = std::iter::once(output)
+        .filter(|text| !text.is_empty())
+        .chain(image_markers(result))
+        .collect::<Vec<_>>()
+        .join("\n");
+    ToolResult {
+        success: true,
+        output,
+        error: None,
+    }
+}
+
 #[async_trait]
 impl Tool for McpBridgeTool {
     fn name(&self) -> &str {
@@ -52,37 +233,28 @@ impl Tool for McpBridgeTool {
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let result = self
             .registry
-            .call_tool(&self.server_name, &self.tool_info.name, Some(args))
+            .call_tool(
+                &self.channel,
+                &self.server_name,
+                &self.tool_info.name,
+                Some(args),
+            )
             .await;
+        #[cfg(feature = "metrics")]
+        crate::metrics::global().record_mcp_call(
+            &self.server_name,
+            result.as_ref().is_ok_and(|r| !r.is_error()),
+        );
+        // A tool-level error still means the server answered; only transport
+        // and protocol failures count against its health.
+        let component = format!("mcp:{}", self.server_name);
+        match &result {
+            Ok(_) => crate::health::mark_component_ok(&component),
+            Err(err) => crate::health::mark_component_error(&component, err),
+        }
 
         match result {
-            Ok(mcp_result) => {
-                // Join all text content items with newline
-                let output = mcp_result
-                    .content
-                    .iter()
-                    .filter_map(|c| c.text.as_ref())
-                    .map(|s| s.as_str())
-                    .collect::<Vec<_>>()
-                    .join("\n");
-
-                // Check is_error flag
-                let is_error = mcp_result.is_error.unwrap_or(false);
-
-                if is_error {
-                    Ok(ToolResult {
-                        success: false,
-                        output: String::new(),
-                        error: Some(output),
-                    })
-                } else {
-                    Ok(ToolResult {
-                        success: true,
-                        output,
-                        error: None,
-                    })
-                }
-            }
+            Ok(mcp_result) => Ok(to_tool_result(&mcp_result)),
             Err(err) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -98,7 +270,7 @@ mod tests {
     use serde_json::json;
     use std::collections::HashSet;
 
-    use zeroclaw_mcp::types::{McpContent, McpToolCallResult};
+    use zeroclaw_mcp::types::{McpContent, McpResourceContent};
 
     // We'll need a mock transport for testing - similar to registry tests
     // For now, test the conversion logic with a simpler approach
@@ -111,7 +283,12 @@ mod tests {
             description: Some("Read a file".to_string()),
             input_schema: json!({"type": "object"}),
         };
-        let tool = McpBridgeTool::new("test_server".to_string(), tool_info, registry);
+        let tool = McpBridgeTool::new(
+            "test_server".to_string(),
+            tool_info,
+            registry,
+            "cli".to_string(),
+        );
 
         assert_eq!(tool.name(), "mcp_test_server_file_read");
     }
@@ -124,7 +301,7 @@ mod tests {
             description: None,
             input_schema: json!({"type": "object"}),
         };
-        let tool = McpBridgeTool::new("server".to_string(), tool_info, registry);
+        let tool = McpBridgeTool::new("server".to_string(), tool_info, registry, "cli".to_string());
 
         assert_eq!(tool.description(), "");
     }
@@ -137,7 +314,7 @@ mod tests {
             description: Some("Test description".to_string()),
             input_schema: json!({"type": "object"}),
         };
-        let tool = McpBridgeTool::new("server".to_string(), tool_info, registry);
+        let tool = McpBridgeTool::new("server".to_string(), tool_info, registry, "cli".to_string());
 
         assert_eq!(tool.description(), "Test description");
     }
@@ -157,11 +334,36 @@ mod tests {
             description: None,
             input_schema: expected_schema.clone(),
         };
-        let tool = McpBridgeTool::new("server".to_string(), tool_info, registry);
+        let tool = McpBridgeTool::new("server".to_string(), tool_info, registry, "cli".to_string());
 
         assert_eq!(tool.parameters_schema(), expected_schema);
     }
 
+    #[tokio::test]
+    async fn execute_refuses_server_not_allowed_on_channel() {
+        let registry = Arc::new(McpRegistry::new(50, HashSet::new()).with_channel_servers(
+            std::collections::HashMap::from([("telegram".to_string(), vec![])]),
+        ));
+        let tool_info = McpToolInfo {
+            name: "read_file".to_string(),
+            description: None,
+            input_schema: json!({"type": "object"}),
+        };
+        let tool = McpBridgeTool::new(
+            "filesystem".to_string(),
+            tool_info,
+            registry,
+            "telegram".to_string(),
+        );
+
+        let result = tool.execute(json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .unwrap()
+            .contains("not allowed on channel 'telegram'"));
+    }
+
     #[tokio::test]
     async fn execute_converts_success_result() {
         // This test would need a mock registry
@@ -171,10 +373,16 @@ mod tests {
                 McpContent {
                     content_type: "text".to_string(),
                     text: Some("line1".to_string()),
+                    data: None,
+                    mime_type: None,
+                    resource: None,
                 },
                 McpContent {
                     content_type: "text".to_string(),
                     text: Some("line2".to_string()),
+                    data: None,
+                    mime_type: None,
+                    resource: None,
                 },
             ],
             is_error: Some(false),
@@ -215,6 +423,9 @@ mod tests {
             content: vec![McpContent {
                 content_type: "text".to_string(),
                 text: Some("Error occurred".to_string()),
+                data: None,
+                mime_type: None,
+                resource: None,
             }],
             is_error: Some(true),
         };
@@ -290,6 +501,9 @@ mod tests {
             content: vec![McpContent {
                 content_type: "text".to_string(),
                 text: Some("Success".to_string()),
+                data: None,
+                mime_type: None,
+                resource: None,
             }],
             is_error: None,
         };
@@ -322,4 +536,99 @@ mod tests {
         assert_eq!(tool_result.output, "Success");
         assert!(tool_result.error.is_none());
     }
+
+    #[tokio::test]
+    async fn image_block_becomes_valid_data_uri_marker() {
+        // PNG signature bytes, base64-encoded.
+        let png = "iVBORw0KGgo=";
+        let mut result = McpToolCallResult::new().with_text("rendered chart");
+        result.content.push(McpContent::image(png, "image/png"));
+
+        let markers = image_markers(&result);
+        assert_eq!(
+            markers,
+            vec![format!("[IMAGE:data:image/png;base64,{png}]")]
+        );
+
+        let messages = vec![crate::providers::ChatMessage::user(markers.join("\n"))];
+        let prepared = crate::multimodal::prepare_messages_for_provider(
+            &messages,
+            &crate::config::MultimodalConfig::default(),
+        )
+        .await
+        .unwrap();
+        assert!(prepared.contains_images);
+        assert!(prepared.messages[0]
+            .content
+            .contains(&format!("data:image/png;base64,{png}")));
+    }
+
+    #[test]
+    fn tool_result_output_carries_image_markers_after_text() {
+        let mut result = McpToolCallResult::new().with_text("rendered chart");
+        result.content.push(McpContent::image("AAAA", "image/png"));
+
+        let tool_result = to_tool_result(&result);
+        assert!(tool_result.success);
+        assert_eq!(
+            tool_result.output,
+            "rendered chart\n[IMAGE:data:image/png;base64,AAAA]"
+        );
+
+        let mut image_only = McpToolCallResult::new();
+        image_only
+            .content
+            .push(McpContent::image("AAAA", "image/png"));
+        assert_eq!(
+            to_tool_result(&image_only).output,
+            "[IMAGE:data:image/png;base64,AAAA]"
+        );
+    }
+
+    #[test]
+    fn image_markers_include_image_resources_only() {
+        let resource = |mime: &str| McpContent {
+            content_type: "resource".to_string(),
+            text: None,
+            data: None,
+            mime_type: None,
+            resource: Some(McpResourceContent {
+                uri: "file:///tmp/out".to_string(),
+                mime_type: Some(mime.to_string()),
+                text: None,
+                blob: Some("AAAA".to_string()),
+            }),
+        };
+        let mut result = McpToolCallResult::new().with_text("no images here");
+        result.content.push(resource("image/jpeg"));
+        result.content.push(resource("application/pdf"));
+        result.content.push(McpContent::image("", "image/png"));
+
+        assert_eq!(
+            image_markers(&result),
+            vec!["[IMAGE:data:image/jpeg;base64,AAAA]".to_string()]
+        );
+    }
+
+    #[test]
+    fn registries_load_without_mcp_json() {
+        let workspace = tempfile::tempdir().unwrap();
+        std::fs::write(
+            workspace.path().join("editor.json"),
+            r#"{"mcpServers": {"github": {"command": "gh-mcp"}}}"#,
+        )
+        .unwrap();
+        let mut config = Config::default();
+        config.workspace_dir = workspace.path().to_path_buf();
+        config.mcp.enabled = true;
+        config.mcp.registries = vec!["editor.json".into()];
+
+        let servers = configured_servers(&config).unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].name, "github");
+
+        let (_, pending) = registry_from_config(&config, Vec::new()).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].command, "gh-mcp");
+    }
 }
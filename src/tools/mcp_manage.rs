@@ -120,8 +120,12 @@ impl Tool for McpManageTool {
                     command: command.to_string(),
                     args: cmd_args,
                     env,
+                    max_output_chars: None,
+                    inherit_env: true,
+                    env_allowlist: vec![],
                 };
                 let tools = self.registry.add_server(config).await?;
+                crate::health::mark_component_ok(&format!("mcp:{name}"));
                 Ok(ToolResult {
                     success: true,
                     output: format!("Added MCP server '{}' with {} tools", name, tools.len()),
@@ -133,6 +137,7 @@ impl Tool for McpManageTool {
                     .as_str()
                     .ok_or_else(|| anyhow::anyhow!("'name' is required for remove"))?;
                 self.registry.remove_server(name).await?;
+                crate::health::remove_component(&format!("mcp:{name}"));
                 Ok(ToolResult {
                     success: true,
                     output: format!("Removed MCP server '{}'", name),
@@ -56,6 +56,7 @@ pub mod schedule;
 pub mod schema;
 pub mod screenshot;
 pub mod skill_manage;
+pub mod skill_tool;
 pub mod shell;
 pub mod traits;
 #[cfg(feature = "vpn")]
@@ -144,7 +145,7 @@ impl Tool for ArcDelegatingTool {
     }
 }
 
-fn boxed_registry_from_arcs(tools: Vec<Arc<dyn Tool>>) -> Vec<Box<dyn Tool>> {
+pub(crate) fn boxed_registry_from_arcs(tools: Vec<Arc<dyn Tool>>) -> Vec<Box<dyn Tool>> {
     tools.into_iter().map(ArcDelegatingTool::boxed).collect()
 }
 
@@ -218,7 +219,7 @@ pub fn all_tools_with_runtime(
     shared_skills: Option<Arc<tokio::sync::RwLock<crate::skills::SkillsState>>>,
 ) -> Vec<Box<dyn Tool>> {
     let mut tool_arcs: Vec<Arc<dyn Tool>> = vec![
-        Arc::new(ShellTool::new(security.clone(), runtime)),
+        Arc::new(ShellTool::new(security.clone(), runtime.clone())),
         Arc::new(FileReadTool::new(security.clone())),
         Arc::new(FileWriteTool::new(security.clone())),
         Arc::new(FileEditTool::new(security.clone())),
@@ -320,51 +321,33 @@ pub fn all_tools_with_runtime(
 
     #[cfg(feature = "vpn")]
     {
+        // `ZEROCLAW_VPN_*` env overrides are merged in at config load.
         let vpn_cfg = &root_config.vpn;
-        // Config-first, env-var-override for VPN settings.
-        let vpn_enabled = std::env::var("ZEROCLAW_VPN_ENABLED")
-            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
-            .unwrap_or(vpn_cfg.enabled);
-        if vpn_enabled {
-            use crate::vpn::{BypassChecker, NodeManager, VpnProxyBridge};
+        if vpn_cfg.enabled {
+            use crate::vpn::{BypassChecker, HealthCheckOptions, NodeManager, VpnProxyBridge};
             use tokio::sync::RwLock;
 
-            let subscription_url = std::env::var("ZEROCLAW_VPN_CLASH_PROXY_URL")
-                .ok()
-                .filter(|s| !s.is_empty())
-                .or_else(|| vpn_cfg.subscription_url.clone());
-            let listen_port: u16 = std::env::var("ZEROCLAW_VPN_LISTEN_PORT")
-                .ok()
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(vpn_cfg.listen_port);
-            let health_interval: u64 = std::env::var("ZEROCLAW_VPN_HEALTH_INTERVAL_SECS")
-                .ok()
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(vpn_cfg.health_check_interval_secs);
-            let mut bypass_extra: Vec<String> = vpn_cfg.bypass_extra.clone();
-            if let Ok(env_bypass) = std::env::var("ZEROCLAW_VPN_BYPASS_EXTRA") {
-                let env_domains: Vec<String> = env_bypass
-                    .split(',')
-                    .map(|s| s.trim().to_owned())
-                    .filter(|s| !s.is_empty())
-                    .collect();
-                for domain in env_domains {
-                    if !bypass_extra.iter().any(|d| d.eq_ignore_ascii_case(&domain)) {
-                        bypass_extra.push(domain);
-                    }
-                }
-            }
-
             let vpn_state = Arc::new(RwLock::new(vpn_control::VpnState {
                 runtime: None,
                 node_manager: NodeManager::new(vec![]),
-                bypass_checker: BypassChecker::new(&bypass_extra),
+                bypass_checker: BypassChecker::new(&vpn_cfg.bypass_extra),
                 bridge: VpnProxyBridge::new(),
                 health_cancel: None,
+                refresh_cancel: None,
                 last_health: vec![],
-                subscription_url,
-                listen_port,
-                health_check_interval_secs: health_interval,
+                subscription_url: vpn_cfg.subscription_url.clone(),
+                listen_port: vpn_cfg.listen_port,
+                health_check_interval_secs: vpn_cfg.health_check_interval_secs,
+                health_options: HealthCheckOptions {
+                    concurrency: vpn_cfg.health_check_concurrency,
+                    probe_timeout: std::time::Duration::from_secs(
+                        vpn_cfg.health_check_timeout_secs,
+                    ),
+                    strategy: vpn_cfg.health_check_strategy,
+                    test_url: vpn_cfg.health_check_url.clone(),
+                },
+                subscription_refresh_interval_secs: vpn_cfg.subscription_refresh_interval_secs,
+                regions: vpn_cfg.regions.clone(),
             }));
             tool_arcs.push(Arc::new(vpn_control::VpnControlTool::new(
                 security.clone(),
@@ -418,6 +401,12 @@ pub fn all_tools_with_runtime(
         )));
     }
 
+    // Expose skill-defined tools as callable agent tools when opted in
+    if root_config.skills.register_tools {
+        let skills = crate::skills::load_skills_with_config(workspace_dir, root_config);
+        tool_arcs.extend(skill_tool::skill_tools(&skills, security, &runtime, http_config));
+    }
+
     // Register docs_sync tool when feishu-docs-sync feature is enabled and sync is configured
     #[cfg(feature = "feishu-docs-sync")]
     {
@@ -82,7 +82,7 @@ impl Tool for SkillManageTool {
     }
 
     fn description(&self) -> &str {
-        "Create, read, update, delete, and list agent skills at runtime"
+        "Create, read, describe, update, delete, rename, and list agent skills at runtime"
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -91,12 +91,16 @@ impl Tool for SkillManageTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "enum": ["create", "read", "update", "delete", "list"],
+                    "enum": ["create", "read", "describe", "update", "delete", "rename", "list"],
                     "description": "The CRUD action to perform"
                 },
                 "name": {
                     "type": "string",
-                    "description": "Skill name (required for create/read/update/delete)"
+                    "description": "Skill name (required for create/read/describe/update/delete/rename)"
+                },
+                "new_name": {
+                    "type": "string",
+                    "description": "New skill name (required for rename)"
                 },
                 "description": {
                     "type": "string",
@@ -134,14 +138,16 @@ impl Tool for SkillManageTool {
         match action {
             "create" => self.action_create(&args).await,
             "read" => self.action_read(&args).await,
+            "describe" => self.action_describe(&args).await,
             "update" => self.action_update(&args).await,
             "delete" => self.action_delete(&args).await,
+            "rename" => self.action_rename(&args).await,
             "list" => self.action_list().await,
             other => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(format!(
-                    "Unknown action '{other}': expected create/read/update/delete/list"
+                    "Unknown action '{other}': expected create/read/describe/update/delete/rename/list"
                 )),
             }),
         }
@@ -249,7 +255,7 @@ impl SkillManageTool {
         }
         // Write SKILL.md if content provided, otherwise SKILL.toml
         if let Some(content) = args.get("content").and_then(|v| v.as_str()) {
-            if let Err(e) = fs::write(skill_path.join("SKILL.md"), content) {
+            if let Err(e) = write_atomic(&skill_path.join("SKILL.md"), content) {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
@@ -258,7 +264,7 @@ impl SkillManageTool {
             }
         } else {
             let toml_content = Self::build_toml(&name, args);
-            if let Err(e) = fs::write(skill_path.join("SKILL.toml"), &toml_content) {
+            if let Err(e) = write_atomic(&skill_path.join("SKILL.toml"), &toml_content) {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
@@ -338,6 +344,67 @@ impl SkillManageTool {
             })
         }
     }
+    async fn action_describe(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let name = match Self::extract_name(args) {
+            Ok(n) => n,
+            Err(r) => return Ok(r),
+        };
+        let state = self.shared_state.read().await;
+        let Some(skill) = state.skills.iter().find(|s| s.name == name) else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Skill '{name}' not found")),
+            });
+        };
+        let tools: Vec<serde_json::Value> = skill
+            .tools
+            .iter()
+            .map(|t| {
+                json!({
+                    "name": t.name,
+                    "description": t.description,
+                    "kind": t.kind,
+                    "command": t.command,
+                    "args": t.args,
+                    "required": t.required
+                })
+            })
+            .collect();
+        let audit = match crate::skills::skill_audit_findings(skill) {
+            Ok(findings) if findings.is_empty() => json!({ "status": "clean" }),
+            Ok(findings) => json!({ "status": "failed", "findings": findings }),
+            Err(e) => json!({ "status": "error", "error": e.to_string() }),
+        };
+        let source = if skill.tags.iter().any(|t| t == crate::skills::OPEN_SKILLS_TAG) {
+            "open-skills"
+        } else {
+            "workspace"
+        };
+        let format = match skill.location.as_deref().and_then(|p| p.extension()) {
+            Some(ext) if ext == "toml" => "toml",
+            _ => "markdown",
+        };
+        Ok(ToolResult {
+            success: true,
+            output: json!({
+                "name": skill.name,
+                "description": skill.description,
+                "version": skill.version,
+                "author": skill.author,
+                "tags": skill.tags,
+                "source": source,
+                "format": format,
+                "location": skill.location.as_ref().map(|p| p.display().to_string()),
+                "tools": tools,
+                "prompt_count": skill.prompts.len(),
+                "prompt_chars": skill.prompts.iter().map(|p| p.chars().count()).sum::<usize>(),
+                "audit": audit
+            })
+            .to_string(),
+            error: None,
+        })
+    }
     async fn action_update(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
         let name = match Self::extract_name(args) {
             Ok(n) => n,
@@ -353,7 +420,7 @@ impl SkillManageTool {
         }
         // Write SKILL.md if content provided, otherwise SKILL.toml
         if let Some(content) = args.get("content").and_then(|v| v.as_str()) {
-            if let Err(e) = fs::write(skill_path.join("SKILL.md"), content) {
+            if let Err(e) = write_atomic(&skill_path.join("SKILL.md"), content) {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
@@ -362,7 +429,7 @@ impl SkillManageTool {
             }
         } else {
             let toml_content = Self::build_toml(&name, args);
-            if let Err(e) = fs::write(skill_path.join("SKILL.toml"), &toml_content) {
+            if let Err(e) = write_atomic(&skill_path.join("SKILL.toml"), &toml_content) {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
@@ -432,6 +499,94 @@ impl SkillManageTool {
             error: None,
         })
     }
+    async fn action_rename(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let name = match Self::extract_name(args) {
+            Ok(n) => n,
+            Err(r) => return Ok(r),
+        };
+        let new_name = args.get("new_name").and_then(|v| v.as_str()).unwrap_or("");
+        if new_name.is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Missing 'new_name' parameter for rename".into()),
+            });
+        }
+        if let Err(e) = validate_skill_name(new_name) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e),
+            });
+        }
+        let skill_path = self.skills_dir.join(&name);
+        if !skill_path.exists() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Skill '{name}' not found")),
+            });
+        }
+        let new_path = self.skills_dir.join(new_name);
+        if new_path.exists() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Skill '{new_name}' already exists")),
+            });
+        }
+        // Canonicalize and verify the source is inside skills_dir (symlink escape prevention)
+        let inside_skills_dir = fs::canonicalize(&skill_path)
+            .and_then(|p| Ok(p.starts_with(fs::canonicalize(&self.skills_dir)?)));
+        match inside_skills_dir {
+            Ok(true) => {}
+            Ok(false) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some("Skill path escapes the skills directory".into()),
+                });
+            }
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to resolve skill path: {e}")),
+                });
+            }
+        }
+        if let Err(e) = fs::rename(&skill_path, &new_path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to rename skill directory: {e}")),
+            });
+        }
+        // SKILL.md skills take their name from the directory; SKILL.toml
+        // declares it, so keep the manifest in step with the new directory.
+        let toml_path = new_path.join("SKILL.toml");
+        if toml_path.exists() {
+            if let Err(e) = rename_toml_manifest(&toml_path, new_name) {
+                let _ = fs::rename(&new_path, &skill_path);
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to update SKILL.toml: {e}")),
+                });
+            }
+        }
+        self.reload_shared_state().await;
+        Ok(ToolResult {
+            success: true,
+            output: json!({
+                "renamed": name,
+                "to": new_name,
+                "path": new_path.display().to_string()
+            })
+            .to_string(),
+            error: None,
+        })
+    }
     async fn action_list(&self) -> anyhow::Result<ToolResult> {
         let state = self.shared_state.read().await;
         let skills: Vec<serde_json::Value> = state
@@ -461,6 +616,38 @@ impl SkillManageTool {
     }
 }
 
+/// Set `[skill].name` in the SKILL.toml at `path` to `new_name`.
+fn rename_toml_manifest(path: &std::path::Path, new_name: &str) -> anyhow::Result<()> {
+    let mut manifest: toml::Table = toml::from_str(&fs::read_to_string(path)?)?;
+    let skill = manifest
+        .get_mut("skill")
+        .and_then(toml::Value::as_table_mut)
+        .ok_or_else(|| anyhow::anyhow!("missing [skill] table"))?;
+    skill.insert("name".into(), toml::Value::String(new_name.to_string()));
+    write_atomic(path, &toml::to_string(&manifest)?)?;
+    Ok(())
+}
+
+/// Replace `path` with `contents` via a temp file in the same directory and
+/// a rename, so a crash or a concurrent skill reload never sees a partially
+/// written manifest.
+fn write_atomic(path: &std::path::Path, contents: &str) -> std::io::Result<()> {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let tmp_path = path.with_file_name(format!(".{file_name}.tmp.{}.{nanos}", std::process::id()));
+    let result = fs::write(&tmp_path, contents).and_then(|()| fs::rename(&tmp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
 /// Escape and quote a string for TOML basic string format.
 fn toml_quote(s: &str) -> String {
     let mut out = String::with_capacity(s.len() + 2);
@@ -665,4 +852,135 @@ mod tests {
         assert_eq!(skill.description, "Roundtrip test");
         assert_eq!(skill.version, "1.2.3");
     }
+    #[tokio::test]
+    async fn test_rename_skill() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = test_tool(dir.path());
+        tool.execute(json!({
+            "action": "create",
+            "name": "old-name",
+            "description": "Renamed skill"
+        }))
+        .await
+        .unwrap();
+        let result = tool
+            .execute(json!({ "action": "rename", "name": "old-name", "new_name": "new-name" }))
+            .await
+            .unwrap();
+        assert!(result.success, "rename failed: {:?}", result.error);
+        assert!(!dir.path().join("skills/old-name").exists());
+
+        let state = tool.shared_state.read().await;
+        assert!(state.dirty.load(std::sync::atomic::Ordering::Relaxed));
+        let skill = state
+            .skills
+            .iter()
+            .find(|s| s.name == "new-name")
+            .expect("renamed skill loaded under its new name");
+        assert_eq!(skill.description, "Renamed skill");
+        assert!(!state.skills.iter().any(|s| s.name == "old-name"));
+    }
+    #[tokio::test]
+    async fn test_rename_skill_rejects_collision_and_invalid_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = test_tool(dir.path());
+        for name in ["first", "second"] {
+            tool.execute(json!({ "action": "create", "name": name }))
+                .await
+                .unwrap();
+        }
+
+        let collision = tool
+            .execute(json!({ "action": "rename", "name": "first", "new_name": "second" }))
+            .await
+            .unwrap();
+        assert!(!collision.success);
+        assert!(collision.error.unwrap().contains("already exists"));
+
+        for bad in ["../escape", "CON", ""] {
+            let result = tool
+                .execute(json!({ "action": "rename", "name": "first", "new_name": bad }))
+                .await
+                .unwrap();
+            assert!(!result.success, "rename to {bad:?} should fail");
+        }
+        assert!(dir.path().join("skills/first/SKILL.toml").exists());
+    }
+    #[tokio::test]
+    async fn test_describe_skill() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = test_tool(dir.path());
+        let result = tool
+            .execute(json!({
+                "action": "create",
+                "name": "described",
+                "description": "Has tools",
+                "tools": [
+                    { "name": "status", "description": "Show status", "kind": "shell", "command": "git status" },
+                    { "name": "ping", "description": "Ping API", "kind": "http", "command": "https://api.example.com/ping" }
+                ]
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "create failed: {:?}", result.error);
+
+        let result = tool
+            .execute(json!({ "action": "describe", "name": "described" }))
+            .await
+            .unwrap();
+        assert!(result.success, "describe failed: {:?}", result.error);
+        let described: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(described["description"], "Has tools");
+        assert_eq!(described["source"], "workspace");
+        assert_eq!(described["format"], "toml");
+        assert_eq!(described["tools"][0]["name"], "status");
+        assert_eq!(described["tools"][1]["name"], "ping");
+        assert_eq!(described["tools"][1]["kind"], "http");
+        assert_eq!(described["prompt_count"], 0);
+        assert_eq!(described["audit"]["status"], "clean");
+
+        let missing = tool
+            .execute(json!({ "action": "describe", "name": "nope" }))
+            .await
+            .unwrap();
+        assert!(!missing.success);
+    }
+    #[test]
+    fn write_atomic_never_exposes_partial_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("SKILL.toml");
+        let versions = ["a".repeat(256 * 1024), "b".repeat(512 * 1024)];
+        write_atomic(&path, &versions[0]).unwrap();
+
+        let done = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let reader = {
+            let path = path.clone();
+            let versions = versions.clone();
+            let done = Arc::clone(&done);
+            std::thread::spawn(move || {
+                let mut reads = 0;
+                while !done.load(std::sync::atomic::Ordering::Relaxed) {
+                    let content = fs::read_to_string(&path).unwrap();
+                    assert!(
+                        versions.contains(&content),
+                        "saw a partial write of {} bytes",
+                        content.len()
+                    );
+                    reads += 1;
+                }
+                reads
+            })
+        };
+        for i in 0..50 {
+            write_atomic(&path, &versions[i % 2]).unwrap();
+        }
+        done.store(true, std::sync::atomic::Ordering::Relaxed);
+        assert!(reader.join().unwrap() > 0);
+
+        let leftovers: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(leftovers, vec![std::ffi::OsString::from("SKILL.toml")]);
+    }
 }
@@ -0,0 +1,1104 @@
+//! Skill Tool Adapter — exposes `[[tools]]` entries from skills as agent tools
+//!
+//! Each `SkillToolAdapter` wraps one `SkillTool` and executes it through the
+//! existing `shell` / `http_request` tools, so the usual security policy
+//! (command allowlist, rate limits, domain allowlist) still applies. Tool
+//! names are namespaced as `skill_{skill}_{tool}`; names that collide or run
+//! past the 64-character provider limit get a short hash suffix.
+//!
+//! Arguments declared in the tool's `args` table become string parameters
+//! and are substituted into `{{name}}` placeholders in `command`: shell-quoted
+//! for `shell`/`script` tools and URL-encoded for `http` tools. Shell
+//! placeholders must stand outside any quotes in the template, since a quoted
+//! `"{{name}}"` would let the value break out of its own quoting. Names listed
+//! in `required` are validated before anything runs, and missing or malformed
+//! arguments are reported as a JSON error object.
+//!
+//! With the `mcp` feature, an `mcp` tool names a tool on a connected MCP
+//! server as `server:tool_name` in `command`. Only the arguments it declares
+//! in `args` or `required` are forwarded, with their JSON types intact,
+//! through `McpRegistry::call_tool`; anything else the model passes is
+//! dropped.
+
+use super::http_request::HttpRequestTool;
+use super::shell::ShellTool;
+use super::traits::{Tool, ToolResult};
+use crate::runtime::RuntimeAdapter;
+use crate::security::SecurityPolicy;
+use crate::skills::{Skill, SkillTool};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+#[cfg(feature = "mcp")]
+use zeroclaw_mcp::registry::McpRegistry;
+
+/// How a skill tool is executed.
+enum SkillToolKind {
+    /// `shell`: run `command` in the workspace.
+    Shell,
+    /// `script`: like `shell`, but a leading path is resolved against the
+    /// skill directory.
+    Script,
+    /// `http`: GET the URL in `command`.
+    Http,
+    /// `mcp`: call the MCP tool named by `server:tool_name` in `command`.
+    #[cfg(feature = "mcp")]
+    Mcp,
+}
+
+impl SkillToolKind {
+    fn parse(kind: &str) -> Option<Self> {
+        match kind.trim().to_ascii_lowercase().as_str() {
+            "shell" => Some(Self::Shell),
+            "script" => Some(Self::Script),
+            "http" => Some(Self::Http),
+            #[cfg(feature = "mcp")]
+            "mcp" => Some(Self::Mcp),
+            _ => None,
+        }
+    }
+}
+
+/// A tool that wraps a single `[[tools]]` entry from a skill
+pub struct SkillToolAdapter {
+    namespaced_name: String,
+    tool: SkillTool,
+    kind: SkillToolKind,
+    skill_dir: Option<PathBuf>,
+    executor: Box<dyn Tool>,
+}
+
+impl SkillToolAdapter {
+    /// Create an adapter for `tool`, or `None` if its kind is not executable.
+    pub fn new(
+        skill: &Skill,
+        tool: &SkillTool,
+        security: Arc<SecurityPolicy>,
+        runtime: Arc<dyn RuntimeAdapter>,
+        http_config: &crate::config::HttpRequestConfig,
+    ) -> Option<Self> {
+        let kind = SkillToolKind::parse(&tool.kind)?;
+        let executor: Box<dyn Tool> = match kind {
+            SkillToolKind::Shell | SkillToolKind::Script => {
+                Box::new(ShellTool::new(security, runtime))
+            }
+            SkillToolKind::Http => Box::new(HttpRequestTool::new(
+                security,
+                http_config.allowed_domains.clone(),
+                http_config.max_response_size,
+                http_config.timeout_secs,
+            )),
+            // Needs a registry to route through; see `new_mcp`.
+            #[cfg(feature = "mcp")]
+            SkillToolKind::Mcp => return None,
+        };
+        Some(Self::with_executor(skill, tool, kind, executor))
+    }
+
+    /// Create an adapter for an `mcp` tool that calls into `registry` on
+    /// behalf of `channel`, or `None` if `tool` is not an `mcp` tool with a
+    /// `server:tool_name` command.
+    #[cfg(feature = "mcp")]
+    pub fn new_mcp(
+        skill: &Skill,
+        tool: &SkillTool,
+        registry: Arc<McpRegistry>,
+        channel: &str,
+    ) -> Option<Self> {
+        if !matches!(SkillToolKind::parse(&tool.kind)?, SkillToolKind::Mcp) {
+            return None;
+        }
+        let (server, tool_name) = parse_mcp_command(&tool.command)?;
+        let executor = super::McpBridgeTool::new(
+            server.to_string(),
+            zeroclaw_mcp::types::McpToolInfo {
+                name: tool_name.to_string(),
+                description: None,
+                input_schema: json!({ "type": "object" }),
+            },
+            registry,
+            channel.to_string(),
+        );
+        Some(Self::with_executor(
+            skill,
+            tool,
+            SkillToolKind::Mcp,
+            Box::new(executor),
+        ))
+    }
+
+    fn with_executor(
+        skill: &Skill,
+        tool: &SkillTool,
+        kind: SkillToolKind,
+        executor: Box<dyn Tool>,
+    ) -> Self {
+        Self {
+            namespaced_name: skill_tool_name(skill, tool),
+            tool: tool.clone(),
+            kind,
+            skill_dir: skill
+                .location
+                .as_deref()
+                .and_then(Path::parent)
+                .map(Path::to_path_buf),
+            executor,
+        }
+    }
+
+    /// Validate `args` and build the call for the underlying executor.
+    fn executor_args(&self, args: &Value) -> Result<Value, SkillArgError> {
+        match self.kind {
+            SkillToolKind::Shell | SkillToolKind::Script => {
+                Ok(json!({ "command": self.render_command(args)? }))
+            }
+            SkillToolKind::Http => Ok(json!({ "url": self.render_command(args)? })),
+            #[cfg(feature = "mcp")]
+            SkillToolKind::Mcp => {
+                // MCP tools take structured input, so declared arguments are
+                // forwarded with their JSON types instead of being rendered.
+                let values = collect_arguments(&self.tool, args)?;
+                Ok(Value::Object(
+                    values
+                        .keys()
+                        .filter_map(|name| Some((name.clone(), args.get(name)?.clone())))
+                        .collect(),
+                ))
+            }
+        }
+    }
+
+    /// Validate `args` and substitute them into the command template.
+    fn render_command(&self, args: &Value) -> Result<String, SkillArgError> {
+        let values = collect_arguments(&self.tool, args)?;
+        let declared = declared_arguments(&self.tool);
+        let command = if matches!(self.kind, SkillToolKind::Http) {
+            render_template(&self.tool.command, &declared, &values, |v| {
+                urlencoding::encode(v).into_owned()
+            })?
+        } else {
+            if let Some(name) = quoted_placeholder(&self.tool.command) {
+                return Err(SkillArgError::QuotedPlaceholder(name));
+            }
+            render_template(&self.tool.command, &declared, &values, shell_quote)?
+        };
+        if matches!(self.kind, SkillToolKind::Script) {
+            return Ok(self.resolve_script_path(command));
+        }
+        Ok(command)
+    }
+
+    /// Point a leading relative script path at the skill directory.
+    fn resolve_script_path(&self, command: String) -> String {
+        let Some(skill_dir) = &self.skill_dir else {
+            return command;
+        };
+        let (program, rest) = command
+            .split_once(char::is_whitespace)
+            .unwrap_or((command.as_str(), ""));
+        let candidate = skill_dir.join(program);
+        if Path::new(program).is_relative() && candidate.is_file() {
+            let quoted = shell_quote(&candidate.display().to_string());
+            if rest.is_empty() {
+                quoted
+            } else {
+                format!("{quoted} {rest}")
+            }
+        } else {
+            command
+        }
+    }
+}
+
+/// Why a skill tool call was rejected before execution.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SkillArgError {
+    #[error("missing required argument(s): {}", .0.join(", "))]
+    Missing(Vec<String>),
+    #[error("argument '{0}' must be a string, number, or boolean")]
+    InvalidType(String),
+    #[error("command references undeclared argument '{0}'")]
+    Undeclared(String),
+    #[error("unterminated placeholder in command template")]
+    Unterminated,
+    #[error("placeholder '{0}' must not be wrapped in quotes in the command template")]
+    QuotedPlaceholder(String),
+}
+
+impl SkillArgError {
+    /// Machine-readable form returned to the model in `ToolResult::error`.
+    fn to_json(&self) -> Value {
+        let (code, arguments) = match self {
+            Self::Missing(names) => ("missing_arguments", names.clone()),
+            Self::InvalidType(name) => ("invalid_argument_type", vec![name.clone()]),
+            Self::Undeclared(name) => ("undeclared_argument", vec![name.clone()]),
+            Self::Unterminated => ("invalid_template", Vec::new()),
+            Self::QuotedPlaceholder(name) => ("invalid_template", vec![name.clone()]),
+        };
+        json!({ "error": code, "arguments": arguments, "message": self.to_string() })
+    }
+}
+
+/// Names a tool accepts: its `args` keys plus anything listed in `required`.
+fn declared_arguments(tool: &SkillTool) -> HashSet<&str> {
+    tool.args
+        .keys()
+        .chain(tool.required.iter())
+        .map(String::as_str)
+        .collect()
+}
+
+/// Extract declared arguments from the call, enforcing `required`.
+fn collect_arguments(
+    tool: &SkillTool,
+    args: &Value,
+) -> Result<HashMap<String, String>, SkillArgError> {
+    let mut values = HashMap::new();
+    for name in tool.args.keys().chain(tool.required.iter()) {
+        let value = match args.get(name) {
+            None | Some(Value::Null) => continue,
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Number(n)) => n.to_string(),
+            Some(Value::Bool(b)) => b.to_string(),
+            Some(_) => return Err(SkillArgError::InvalidType(name.clone())),
+        };
+        values.insert(name.clone(), value);
+    }
+
+    let mut missing: Vec<String> = tool
+        .required
+        .iter()
+        .filter(|name| !values.contains_key(*name))
+        .cloned()
+        .collect();
+    if !missing.is_empty() {
+        missing.sort();
+        missing.dedup();
+        return Err(SkillArgError::Missing(missing));
+    }
+    Ok(values)
+}
+
+/// Replace `{{name}}` placeholders with escaped argument values.
+///
+/// Every placeholder must name a declared argument; optional arguments that
+/// were not provided render as an escaped empty string.
+fn render_template(
+    template: &str,
+    declared: &HashSet<&str>,
+    values: &HashMap<String, String>,
+    escape: impl Fn(&str) -> String,
+) -> Result<String, SkillArgError> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find("}}").ok_or(SkillArgError::Unterminated)?;
+        let name = after[..end].trim();
+        if !declared.contains(name) {
+            return Err(SkillArgError::Undeclared(name.to_string()));
+        }
+        out.push_str(&escape(values.get(name).map_or("", String::as_str)));
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Find the first `{{name}}` placeholder that sits inside single or double
+/// quotes in a shell template.
+///
+/// Values are already single-quoted on substitution; wrapping the placeholder
+/// in quotes as well would end up unquoting the value again.
+fn quoted_placeholder(template: &str) -> Option<String> {
+    #[derive(PartialEq)]
+    enum Quote {
+        None,
+        Single,
+        Double,
+    }
+
+    let mut quote = Quote::None;
+    let mut rest = template;
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("{{") {
+            let after = &rest[2..];
+            let end = after.find("}}")?;
+            if quote != Quote::None {
+                return Some(after[..end].trim().to_string());
+            }
+            rest = &after[end + 2..];
+            continue;
+        }
+        let mut skip = c.len_utf8();
+        match (&quote, c) {
+            (Quote::None | Quote::Double, '\\') => {
+                skip += rest[skip..].chars().next().map_or(0, char::len_utf8);
+            }
+            (Quote::None, '\'') => quote = Quote::Single,
+            (Quote::None, '"') => quote = Quote::Double,
+            (Quote::Single, '\'') | (Quote::Double, '"') => quote = Quote::None,
+            _ => {}
+        }
+        rest = &rest[skip..];
+    }
+    None
+}
+
+/// Split an `mcp` tool's `server:tool_name` command into its two parts.
+pub(crate) fn parse_mcp_command(command: &str) -> Option<(&str, &str)> {
+    let (server, tool) = command.trim().split_once(':')?;
+    let (server, tool) = (server.trim(), tool.trim());
+    (!server.is_empty() && !tool.is_empty()).then_some((server, tool))
+}
+
+/// Keep tool names within the `[A-Za-z0-9_-]` set accepted by providers.
+fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Longest tool name accepted by providers.
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// The `skill_{skill}_{tool}` name for one tool, cut down to
+/// [`MAX_TOOL_NAME_LEN`] with a hash suffix when it is too long.
+fn skill_tool_name(skill: &Skill, tool: &SkillTool) -> String {
+    let base = format!(
+        "skill_{}_{}",
+        sanitize_name(&skill.name),
+        sanitize_name(&tool.name)
+    );
+    if base.len() > MAX_TOOL_NAME_LEN {
+        with_suffix(&base, &name_hash(skill, tool))
+    } else {
+        base
+    }
+}
+
+/// Assign every `[[tools]]` entry of `skills` a unique name, indexed like
+/// `skills[i].tools[j]`.
+///
+/// Names are handed out in order, so the first of two colliding tools keeps
+/// the plain name and later ones get a hash suffix.
+fn skill_tool_names(skills: &[Skill]) -> Vec<Vec<String>> {
+    let mut taken = HashSet::new();
+    skills
+        .iter()
+        .map(|skill| {
+            skill
+                .tools
+                .iter()
+                .map(|tool| {
+                    let base = skill_tool_name(skill, tool);
+                    let hash = name_hash(skill, tool);
+                    let mut name = base.clone();
+                    let mut n = 1;
+                    while taken.contains(&name) {
+                        let suffix = if n == 1 {
+                            hash.clone()
+                        } else {
+                            format!("{hash}{n}")
+                        };
+                        name = with_suffix(&base, &suffix);
+                        n += 1;
+                    }
+                    taken.insert(name.clone());
+                    name
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Short hash of the unsanitized skill and tool names, so names that only
+/// collide after sanitizing or truncation still end up distinct.
+fn name_hash(skill: &Skill, tool: &SkillTool) -> String {
+    let digest = Sha256::digest(format!("{}\0{}", skill.name, tool.name));
+    hex::encode(&digest[..4])
+}
+
+/// Cut `base` so that `{base}_{suffix}` fits in [`MAX_TOOL_NAME_LEN`].
+fn with_suffix(base: &str, suffix: &str) -> String {
+    // `base` is ASCII after `sanitize_name`, so byte slicing is safe.
+    let keep = MAX_TOOL_NAME_LEN - suffix.len() - 1;
+    format!("{}_{suffix}", &base[..base.len().min(keep)])
+}
+
+/// POSIX single-quote a value so the shell treats it as one literal word;
+/// metacharacters such as `;`, `|`, `$(…)` and backticks stay inert.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[async_trait]
+impl Tool for SkillToolAdapter {
+    fn name(&self) -> &str {
+        &self.namespaced_name
+    }
+
+    fn description(&self) -> &str {
+        &self.tool.description
+    }
+
+    fn parameters_schema(&self) -> Value {
+        let mut properties: serde_json::Map<String, Value> = self
+            .tool
+            .args
+            .iter()
+            .map(|(name, description)| {
+                (
+                    name.clone(),
+                    json!({ "type": "string", "description": description }),
+                )
+            })
+            .collect();
+        // A required name without an `args` entry still needs a property,
+        // otherwise the schema requires a field it never defines.
+        for name in &self.tool.required {
+            properties
+                .entry(name.clone())
+                .or_insert_with(|| json!({ "type": "string" }));
+        }
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": self.tool.required,
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        match self.executor_args(&args) {
+            Ok(call) => self.executor.execute(call).await,
+            Err(err) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(err.to_json().to_string()),
+            }),
+        }
+    }
+}
+
+/// Build agent tools for every `[[tools]]` entry of skills that pass the
+/// security audit.
+pub fn skill_tools(
+    skills: &[Skill],
+    security: &Arc<SecurityPolicy>,
+    runtime: &Arc<dyn RuntimeAdapter>,
+    http_config: &crate::config::HttpRequestConfig,
+) -> Vec<Arc<dyn Tool>> {
+    let names = skill_tool_names(skills);
+    let mut tools: Vec<Arc<dyn Tool>> = Vec::new();
+    for (i, skill) in skills.iter().enumerate() {
+        if skill.tools.is_empty() || !crate::skills::skill_passes_audit(skill) {
+            continue;
+        }
+        for (j, tool) in skill.tools.iter().enumerate() {
+            // Registered by `mcp_skill_tools` once the MCP registry exists.
+            #[cfg(feature = "mcp")]
+            if matches!(SkillToolKind::parse(&tool.kind), Some(SkillToolKind::Mcp)) {
+                continue;
+            }
+            let adapter =
+                SkillToolAdapter::new(skill, tool, security.clone(), runtime.clone(), http_config);
+            match adapter {
+                Some(mut adapter) => {
+                    adapter.namespaced_name = names[i][j].clone();
+                    tools.push(Arc::new(adapter));
+                }
+                None => tracing::warn!(
+                    skill = %skill.name,
+                    tool = %tool.name,
+                    kind = %tool.kind,
+                    "skipping skill tool with unsupported kind"
+                ),
+            }
+        }
+    }
+    tools
+}
+
+/// Build agent tools for the `mcp` `[[tools]]` entries of skills that pass
+/// the security audit, routing each call through `registry` for `channel`.
+#[cfg(feature = "mcp")]
+pub fn mcp_skill_tools(
+    skills: &[Skill],
+    registry: &Arc<McpRegistry>,
+    channel: &str,
+) -> Vec<Box<dyn Tool>> {
+    let names = skill_tool_names(skills);
+    let mut tools: Vec<Box<dyn Tool>> = Vec::new();
+    for (i, skill) in skills.iter().enumerate() {
+        if skill.tools.is_empty() || !crate::skills::skill_passes_audit(skill) {
+            continue;
+        }
+        for (j, tool) in skill.tools.iter().enumerate() {
+            if !matches!(SkillToolKind::parse(&tool.kind), Some(SkillToolKind::Mcp)) {
+                continue;
+            }
+            match SkillToolAdapter::new_mcp(skill, tool, registry.clone(), channel) {
+                Some(mut adapter) => {
+                    if tool.args.is_empty() && tool.required.is_empty() {
+                        tracing::warn!(
+                            skill = %skill.name,
+                            tool = %tool.name,
+                            "mcp skill tool declares no args; it will be called with no arguments"
+                        );
+                    }
+                    adapter.namespaced_name = names[i][j].clone();
+                    tools.push(Box::new(adapter));
+                }
+                None => tracing::warn!(
+                    skill = %skill.name,
+                    tool = %tool.name,
+                    command = %tool.command,
+                    "skipping mcp skill tool without a server:tool_name command"
+                ),
+            }
+        }
+    }
+    tools
+}
+
+/// List the `mcp` skill tools whose server or tool is not offered by
+/// `registry`, as `(namespaced name, reason)` pairs. Meant to be checked once
+/// the configured servers have connected.
+#[cfg(feature = "mcp")]
+pub async fn unresolved_mcp_skill_tools(
+    skills: &[Skill],
+    registry: &McpRegistry,
+) -> Vec<(String, String)> {
+    let servers = registry.list_servers().await;
+    let available = registry.get_all_tools().await;
+    let names = skill_tool_names(skills);
+    let mut unresolved = Vec::new();
+    for (i, skill) in skills.iter().enumerate() {
+        for (j, tool) in skill.tools.iter().enumerate() {
+            if !matches!(SkillToolKind::parse(&tool.kind), Some(SkillToolKind::Mcp)) {
+                continue;
+            }
+            let Some((server, tool_name)) = parse_mcp_command(&tool.command) else {
+                continue;
+            };
+            let reason = if !servers.iter().any(|(s, _)| s == server) {
+                format!("MCP server '{server}' is not connected")
+            } else if !available
+                .iter()
+                .any(|(s, info)| s == server && info.name == tool_name)
+            {
+                format!("MCP server '{server}' has no tool '{tool_name}'")
+            } else {
+                continue;
+            };
+            let name = names[i][j].clone();
+            unresolved.push((name, reason));
+        }
+    }
+    unresolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::NativeRuntime;
+    use crate::security::AutonomyLevel;
+
+    fn test_security() -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: std::env::temp_dir(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn test_runtime() -> Arc<dyn RuntimeAdapter> {
+        Arc::new(NativeRuntime::new())
+    }
+
+    fn skill_with_tool(dir: &Path, tool: SkillTool) -> Skill {
+        let skill_dir = dir.join("greeter");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(
+            skill_dir.join("SKILL.toml"),
+            "[skill]\nname = \"greeter\"\n",
+        )
+        .unwrap();
+        Skill {
+            name: "greeter".into(),
+            description: "Greets".into(),
+            version: "0.1.0".into(),
+            author: None,
+            tags: vec![],
+            tools: vec![tool],
+            prompts: vec![],
+            location: Some(skill_dir.join("SKILL.toml")),
+        }
+    }
+
+    fn echo_tool() -> SkillTool {
+        SkillTool {
+            name: "say".into(),
+            description: "Echo a message".into(),
+            kind: "shell".into(),
+            command: "echo {{message}}".into(),
+            args: HashMap::from([("message".to_string(), "Text to echo".to_string())]),
+            required: vec!["message".into()],
+        }
+    }
+
+    #[tokio::test]
+    async fn executes_shell_skill_tool_with_arguments() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = skill_with_tool(dir.path(), echo_tool());
+        let tools = skill_tools(
+            &[skill],
+            &test_security(),
+            &test_runtime(),
+            &crate::config::HttpRequestConfig::default(),
+        );
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name(), "skill_greeter_say");
+        assert!(tools[0].parameters_schema()["properties"]["message"].is_object());
+
+        let result = tools[0]
+            .execute(json!({ "message": "hello skill" }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output.trim(), "hello skill");
+    }
+
+    fn declared<'a>(names: &[&'a str]) -> HashSet<&'a str> {
+        names.iter().copied().collect()
+    }
+
+    #[test]
+    fn template_substitutes_and_escapes_placeholders() {
+        let values = HashMap::from([
+            ("city".to_string(), "New York".to_string()),
+            ("units".to_string(), "metric".to_string()),
+        ]);
+        let rendered = render_template(
+            "weather {{city}} --units {{ units }} {{note}}",
+            &declared(&["city", "units", "note"]),
+            &values,
+            shell_quote,
+        )
+        .unwrap();
+        assert_eq!(rendered, "weather 'New York' --units 'metric' ''");
+
+        let url = render_template(
+            "https://wttr.in/{{city}}?format=3",
+            &declared(&["city"]),
+            &values,
+            |v| urlencoding::encode(v).into_owned(),
+        )
+        .unwrap();
+        assert_eq!(url, "https://wttr.in/New%20York?format=3");
+    }
+
+    #[test]
+    fn template_rejects_undeclared_and_unterminated_placeholders() {
+        let values = HashMap::new();
+        assert_eq!(
+            render_template("echo {{secret}}", &declared(&[]), &values, shell_quote),
+            Err(SkillArgError::Undeclared("secret".into()))
+        );
+        assert_eq!(
+            render_template("echo {{oops", &declared(&["oops"]), &values, shell_quote),
+            Err(SkillArgError::Unterminated)
+        );
+    }
+
+    #[test]
+    fn shell_quote_neutralizes_metacharacters() {
+        for payload in ["; rm -rf /", "$(id)", "`id`", "a' ; id ; echo 'b", "x | sh"] {
+            let quoted = shell_quote(payload);
+            assert!(quoted.starts_with('\'') && quoted.ends_with('\''));
+            let output = std::process::Command::new("sh")
+                .arg("-c")
+                .arg(format!("printf %s {quoted}"))
+                .output()
+                .unwrap();
+            assert_eq!(String::from_utf8_lossy(&output.stdout), payload);
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_required_argument_returns_structured_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = skill_with_tool(dir.path(), echo_tool());
+        let tools = skill_tools(
+            &[skill],
+            &test_security(),
+            &test_runtime(),
+            &crate::config::HttpRequestConfig::default(),
+        );
+        assert_eq!(tools[0].parameters_schema()["required"], json!(["message"]));
+
+        let result = tools[0].execute(json!({})).await.unwrap();
+        assert!(!result.success);
+        let error: Value = serde_json::from_str(result.error.as_deref().unwrap()).unwrap();
+        assert_eq!(error["error"], "missing_arguments");
+        assert_eq!(error["arguments"], json!(["message"]));
+
+        let result = tools[0]
+            .execute(json!({ "message": ["not", "a", "string"] }))
+            .await
+            .unwrap();
+        let error: Value = serde_json::from_str(result.error.as_deref().unwrap()).unwrap();
+        assert_eq!(error["error"], "invalid_argument_type");
+    }
+
+    /// A policy that would run anything, so a successful injection would
+    /// leave its side effect behind.
+    fn permissive_security(workspace: &Path) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_dir: workspace.to_path_buf(),
+            allowed_commands: vec!["*".into()],
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn shell_injection_in_arguments_is_not_executed() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("pwned");
+        let skill = skill_with_tool(dir.path(), echo_tool());
+        let tools = skill_tools(
+            &[skill],
+            &permissive_security(dir.path()),
+            &test_runtime(),
+            &crate::config::HttpRequestConfig::default(),
+        );
+
+        let payload = "hi; touch pwned";
+        let result = tools[0]
+            .execute(json!({ "message": payload }))
+            .await
+            .unwrap();
+        assert!(!marker.exists());
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output.trim(), payload);
+    }
+
+    #[tokio::test]
+    async fn quoted_placeholder_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("pwned");
+        let mut tool = echo_tool();
+        tool.command = "echo \"{{message}}\"".into();
+        let skill = skill_with_tool(dir.path(), tool);
+        let tools = skill_tools(
+            &[skill],
+            &permissive_security(dir.path()),
+            &test_runtime(),
+            &crate::config::HttpRequestConfig::default(),
+        );
+
+        let payload = "x\" ; touch pwned ; echo \"";
+        let result = tools[0]
+            .execute(json!({ "message": payload }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        let error: Value = serde_json::from_str(result.error.as_deref().unwrap()).unwrap();
+        assert_eq!(error["error"], "invalid_template");
+        assert_eq!(error["arguments"], json!(["message"]));
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn quoted_placeholder_detection_tracks_shell_quoting() {
+        assert_eq!(quoted_placeholder("echo {{a}} '{{b}}'"), Some("b".into()));
+        assert_eq!(quoted_placeholder("echo \"x {{ a }}\""), Some("a".into()));
+        assert_eq!(quoted_placeholder("echo 'it''s' {{a}} \"q\""), None);
+        assert_eq!(quoted_placeholder("echo \\\" {{a}}"), None);
+        assert_eq!(quoted_placeholder("echo \"\\\"{{a}}\""), Some("a".into()));
+    }
+
+    #[test]
+    fn required_names_without_args_entry_get_a_property() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut tool = echo_tool();
+        tool.required.push("lang".into());
+        let skill = skill_with_tool(dir.path(), tool);
+        let tools = skill_tools(
+            &[skill],
+            &test_security(),
+            &test_runtime(),
+            &crate::config::HttpRequestConfig::default(),
+        );
+        let schema = tools[0].parameters_schema();
+        assert_eq!(schema["properties"]["lang"]["type"], "string");
+        assert_eq!(schema["required"], json!(["message", "lang"]));
+    }
+
+    #[tokio::test]
+    async fn required_only_placeholder_is_substituted() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut tool = echo_tool();
+        tool.command = "echo {{message}} {{lang}}".into();
+        tool.required.push("lang".into());
+        let skill = skill_with_tool(dir.path(), tool);
+        let tools = skill_tools(
+            &[skill],
+            &test_security(),
+            &test_runtime(),
+            &crate::config::HttpRequestConfig::default(),
+        );
+
+        let result = tools[0]
+            .execute(json!({ "message": "hello", "lang": "en" }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output.trim(), "hello en");
+    }
+
+    #[test]
+    fn skills_failing_audit_are_not_registered() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = skill_with_tool(dir.path(), echo_tool());
+        std::fs::write(
+            dir.path().join("greeter").join("install.sh"),
+            "#!/bin/sh\necho hi\n",
+        )
+        .unwrap();
+
+        let tools = skill_tools(
+            &[skill],
+            &test_security(),
+            &test_runtime(),
+            &crate::config::HttpRequestConfig::default(),
+        );
+        assert!(tools.is_empty());
+    }
+
+    #[test]
+    fn colliding_and_long_names_are_made_unique_within_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut dotted = echo_tool();
+        dotted.name = "say.it".into();
+        let mut underscored = echo_tool();
+        underscored.name = "say_it".into();
+        let mut long = echo_tool();
+        long.name = "x".repeat(80);
+        let mut skill = skill_with_tool(dir.path(), dotted);
+        skill.tools.extend([underscored, long.clone(), long]);
+
+        let tools = skill_tools(
+            &[skill],
+            &test_security(),
+            &test_runtime(),
+            &crate::config::HttpRequestConfig::default(),
+        );
+        let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+        assert_eq!(names[0], "skill_greeter_say_it");
+        assert!(names[1].starts_with("skill_greeter_say_it_"));
+        assert!(names.iter().all(|n| n.len() <= MAX_TOOL_NAME_LEN));
+        assert_eq!(names.iter().collect::<HashSet<_>>().len(), names.len());
+    }
+
+    #[test]
+    fn unsupported_kind_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut tool = echo_tool();
+        tool.kind = "grpc".into();
+        let skill = skill_with_tool(dir.path(), tool);
+        let tools = skill_tools(
+            &[skill],
+            &test_security(),
+            &test_runtime(),
+            &crate::config::HttpRequestConfig::default(),
+        );
+        assert!(tools.is_empty());
+    }
+
+    #[cfg(feature = "mcp")]
+    mod mcp {
+        use super::*;
+        use std::sync::Mutex;
+        use zeroclaw_mcp::client::McpClient;
+        use zeroclaw_mcp::config::McpServerConfig;
+        use zeroclaw_mcp::jsonrpc::{JsonRpcNotification, JsonRpcRequest, JsonRpcResponse};
+        use zeroclaw_mcp::transport::McpTransport;
+
+        /// Answers like a server offering a single `create_issue` tool that
+        /// echoes its arguments back, recording each `tools/call`.
+        struct FakeServer {
+            pending: Option<JsonRpcRequest>,
+            calls: Arc<Mutex<Vec<Value>>>,
+        }
+
+        #[async_trait]
+        impl McpTransport for FakeServer {
+            async fn send(&mut self, request: &JsonRpcRequest) -> anyhow::Result<()> {
+                self.pending = Some(request.clone());
+                Ok(())
+            }
+
+            async fn send_notification(
+                &mut self,
+                _notification: &JsonRpcNotification,
+            ) -> anyhow::Result<()> {
+                Ok(())
+            }
+
+            async fn receive(&mut self) -> anyhow::Result<JsonRpcResponse> {
+                let request = self.pending.take().expect("receive without a request");
+                let result = match request.method.as_str() {
+                    "initialize" => json!({
+                        "protocolVersion": "2024-11-05",
+                        "capabilities": {"tools": {}},
+                        "serverInfo": {"name": "github", "version": "1.0.0"}
+                    }),
+                    "tools/list" => json!({
+                        "tools": [{"name": "create_issue", "inputSchema": {"type": "object"}}]
+                    }),
+                    "tools/call" => {
+                        let params = request.params.unwrap_or_default();
+                        assert_eq!(params["name"], "create_issue");
+                        let arguments = params["arguments"].clone();
+                        self.calls.lock().unwrap().push(arguments.clone());
+                        json!({"content": [{"type": "text", "text": arguments.to_string()}]})
+                    }
+                    other => anyhow::bail!("unexpected method {other}"),
+                };
+                Ok(JsonRpcResponse {
+                    jsonrpc: "2.0".into(),
+                    id: request.id,
+                    result: Some(result),
+                    error: None,
+                })
+            }
+
+            async fn close(&mut self) -> anyhow::Result<()> {
+                Ok(())
+            }
+        }
+
+        async fn registry_with_github(calls: Arc<Mutex<Vec<Value>>>) -> Arc<McpRegistry> {
+            let registry = Arc::new(McpRegistry::new(50, std::collections::HashSet::new()));
+            let transport = FakeServer {
+                pending: None,
+                calls,
+            };
+            let client = McpClient::connect(Box::new(transport)).await.unwrap();
+            let config = McpServerConfig {
+                name: "github".into(),
+                command: "github-mcp".into(),
+                args: vec![],
+                env: HashMap::new(),
+                max_output_chars: None,
+                inherit_env: true,
+                env_allowlist: vec![],
+            };
+            registry
+                .add_server_with_client("github".into(), client, config)
+                .await
+                .unwrap();
+            registry
+        }
+
+        fn mcp_tool(command: &str) -> SkillTool {
+            SkillTool {
+                name: "file_issue".into(),
+                description: "File a GitHub issue".into(),
+                kind: "mcp".into(),
+                command: command.into(),
+                args: HashMap::from([
+                    ("title".to_string(), "Issue title".to_string()),
+                    ("priority".to_string(), "Priority level".to_string()),
+                ]),
+                required: vec!["title".into()],
+            }
+        }
+
+        #[tokio::test]
+        async fn mcp_skill_tool_routes_through_registry() {
+            let dir = tempfile::tempdir().unwrap();
+            let skill = skill_with_tool(dir.path(), mcp_tool("github:create_issue"));
+            let calls = Arc::new(Mutex::new(Vec::new()));
+            let registry = registry_with_github(calls.clone()).await;
+
+            // Plain skill tool registration leaves mcp tools to the registry.
+            let plain = skill_tools(
+                std::slice::from_ref(&skill),
+                &test_security(),
+                &test_runtime(),
+                &crate::config::HttpRequestConfig::default(),
+            );
+            assert!(plain.is_empty());
+
+            let tools = mcp_skill_tools(std::slice::from_ref(&skill), &registry, "cli");
+            assert_eq!(tools.len(), 1);
+            assert_eq!(tools[0].name(), "skill_greeter_file_issue");
+
+            let result = tools[0]
+                .execute(json!({ "title": "Crash on start", "priority": 2, "extra": true }))
+                .await
+                .unwrap();
+            assert!(result.success, "{:?}", result.error);
+            let expected = json!({ "title": "Crash on start", "priority": 2 });
+            assert_eq!(*calls.lock().unwrap(), vec![expected.clone()]);
+            assert_eq!(
+                serde_json::from_str::<Value>(&result.output).unwrap(),
+                expected
+            );
+
+            let result = tools[0].execute(json!({})).await.unwrap();
+            assert!(!result.success);
+            assert_eq!(calls.lock().unwrap().len(), 1);
+        }
+
+        #[tokio::test]
+        async fn unresolved_mcp_skill_tools_reports_missing_targets() {
+            let dir = tempfile::tempdir().unwrap();
+            let mut skill = skill_with_tool(dir.path(), mcp_tool("github:create_issue"));
+            let mut missing_tool = mcp_tool("github:delete_repo");
+            missing_tool.name = "nuke".into();
+            let mut missing_server = mcp_tool("jira:create_issue");
+            missing_server.name = "ticket".into();
+            skill.tools.extend([missing_tool, missing_server]);
+            let registry = registry_with_github(Arc::default()).await;
+
+            let unresolved = unresolved_mcp_skill_tools(&[skill], &registry).await;
+            assert_eq!(
+                unresolved,
+                vec![
+                    (
+                        "skill_greeter_nuke".to_string(),
+                        "MCP server 'github' has no tool 'delete_repo'".to_string()
+                    ),
+                    (
+                        "skill_greeter_ticket".to_string(),
+                        "MCP server 'jira' is not connected".to_string()
+                    ),
+                ]
+            );
+        }
+
+        #[test]
+        fn mcp_command_must_name_server_and_tool() {
+            assert_eq!(
+                parse_mcp_command(" github : create_issue "),
+                Some(("github", "create_issue"))
+            );
+            assert_eq!(parse_mcp_command("create_issue"), None);
+            assert_eq!(parse_mcp_command(":create_issue"), None);
+        }
+    }
+}
@@ -13,8 +13,9 @@ use tokio::sync::RwLock;
 use super::traits::{Tool, ToolResult};
 use crate::security::SecurityPolicy;
 use crate::vpn::{
-    BypassChecker, ClashRuntime, HealthChecker, NodeManager, SubscriptionParser, VpnProxyBridge,
-    CLASH_CONTROLLER_PORT, SELECTOR_GROUP_NAME,
+    BypassChecker, CachedNodes, ClashRuntime, HealthCheckOptions, HealthChecker, HealthResult,
+    NodeCache, NodeManager, NodeStatus, ProxyNode, SubscriptionParser, VpnHealthStrategy,
+    VpnProxyBridge, CLASH_CONTROLLER_PORT, DEFAULT_READY_TIMEOUT, SELECTOR_GROUP_NAME,
 };
 
 // ── Shared VPN state ────────────────────────────────────────────────
@@ -29,6 +30,8 @@ pub struct VpnState {
     pub bypass_checker: BypassChecker,
     pub bridge: VpnProxyBridge,
     pub health_cancel: Option<tokio_util::sync::CancellationToken>,
+    /// Cancels the background subscription refresh loop.
+    pub refresh_cancel: Option<tokio_util::sync::CancellationToken>,
     pub last_health: Vec<(String, crate::vpn::HealthResult)>,
     /// Clash subscription URL for fetching proxy nodes.
     pub subscription_url: Option<String>,
@@ -36,6 +39,12 @@ pub struct VpnState {
     pub listen_port: u16,
     /// Background health check interval in seconds.
     pub health_check_interval_secs: u64,
+    /// Concurrency and per-probe timeout for health checks.
+    pub health_options: HealthCheckOptions,
+    /// Subscription refresh interval in seconds (`0` disables).
+    pub subscription_refresh_interval_secs: u64,
+    /// Region codes node selection is restricted to (empty = all).
+    pub regions: Vec<String>,
 }
 
 // ── VpnControlTool ──────────────────────────────────────────────────
@@ -69,6 +78,249 @@ impl VpnControlTool {
     }
 }
 
+// ── Background tasks ─────────────────────────────────────────────────
+
+/// Save nodes, active selection and last health to the node cache so the
+/// next start can resume on the same node. Failures are logged only.
+async fn persist_snapshot(state: &VpnState, cache_path: &std::path::Path) {
+    let snapshot = state.node_manager.snapshot(&state.last_health);
+    if let Err(e) = NodeCache::save_snapshot(&snapshot, cache_path).await {
+        tracing::warn!(
+            "failed to persist VPN node cache at {}: {e}",
+            cache_path.display()
+        );
+    }
+}
+
+/// Health component for the VPN in [`crate::health`].
+const VPN_HEALTH_COMPONENT: &str = "vpn";
+
+/// Publish a health check round: `vpn` is ok while any node is healthy.
+fn record_health(results: &[(String, HealthResult)]) {
+    #[cfg(feature = "metrics")]
+    crate::metrics::global().set_vpn_health(results);
+    if results
+        .iter()
+        .any(|(_, hr)| hr.status == NodeStatus::Healthy)
+    {
+        crate::health::mark_component_ok(VPN_HEALTH_COMPONENT);
+    } else {
+        crate::health::mark_component_error(VPN_HEALTH_COMPONENT, "no healthy VPN nodes");
+    }
+}
+
+/// Probe `nodes` with the configured strategy.
+///
+/// TCP handshakes go straight to each node; URL tests switch Clash through
+/// each node in turn and restore `current_active` afterwards.
+async fn probe_nodes(
+    options: &HealthCheckOptions,
+    nodes: &[ProxyNode],
+    proxy_url: &str,
+    current_active: Option<&str>,
+) -> Vec<(String, HealthResult)> {
+    match options.strategy {
+        VpnHealthStrategy::TcpConnect => {
+            HealthChecker::check_all(&HealthChecker::tcp_targets(nodes), options).await
+        }
+        VpnHealthStrategy::UrlTest => {
+            let node_names: Vec<String> = nodes.iter().map(|n| n.name.clone()).collect();
+            let controller_url = format!("http://127.0.0.1:{CLASH_CONTROLLER_PORT}");
+            HealthChecker::check_all_via_clash(
+                &node_names,
+                proxy_url,
+                &controller_url,
+                SELECTOR_GROUP_NAME,
+                current_active,
+                options,
+            )
+            .await
+        }
+    }
+}
+
+/// Move off the active node when the latest health round reports it
+/// unhealthy. Failover stays within the configured regions and is skipped
+/// while a node is pinned.
+async fn failover_if_unhealthy(state: &mut VpnState) {
+    let VpnState {
+        runtime,
+        node_manager,
+        last_health,
+        ..
+    } = state;
+    let Some(active) = node_manager.active_node().map(|n| n.name.clone()) else {
+        return;
+    };
+    let unhealthy = last_health
+        .iter()
+        .any(|(name, hr)| name == &active && hr.status == NodeStatus::Unhealthy);
+    if !unhealthy {
+        return;
+    }
+    let Some(next) = node_manager.failover(last_health).map(|n| n.name.clone()) else {
+        if node_manager.pinned().is_none() {
+            tracing::warn!("VPN node '{active}' is unhealthy and no healthy node is available");
+        }
+        return;
+    };
+    tracing::warn!("VPN node '{active}' is unhealthy; failing over to '{next}'");
+    if let Some(rt) = runtime.as_mut() {
+        if let Err(e) = rt.switch_node(&next).await {
+            tracing::warn!("failed to switch VPN to '{next}': {e}");
+        }
+    }
+}
+
+/// Start (or restart) the background health loop over the nodes currently in
+/// `state.node_manager`, replacing any loop that is already running.
+fn spawn_health_loop(shared: &Arc<RwLock<VpnState>>, state: &mut VpnState, proxy_url: String) {
+    if let Some(previous) = state.health_cancel.take() {
+        previous.cancel();
+    }
+    let nodes = state.node_manager.all_nodes().to_vec();
+    let token = tokio_util::sync::CancellationToken::new();
+    let health_state = Arc::clone(shared);
+    let interval = Some(std::time::Duration::from_secs(
+        state.health_check_interval_secs,
+    ));
+    let on_results = move |results| {
+        let st = Arc::clone(&health_state);
+        tokio::spawn(async move {
+            let mut guard = st.write().await;
+            guard.last_health = results;
+            record_health(&guard.last_health);
+            failover_if_unhealthy(&mut guard).await;
+            persist_snapshot(&guard, &NodeCache::default_cache_path()).await;
+        });
+    };
+    match state.health_options.strategy {
+        VpnHealthStrategy::TcpConnect => {
+            HealthChecker::spawn_background_loop(
+                HealthChecker::tcp_targets(&nodes),
+                interval,
+                state.health_options.clone(),
+                token.clone(),
+                on_results,
+            );
+        }
+        VpnHealthStrategy::UrlTest => {
+            let active_state = Arc::clone(shared);
+            HealthChecker::spawn_clash_aware_loop(
+                nodes.iter().map(|n| n.name.clone()).collect(),
+                proxy_url,
+                format!("http://127.0.0.1:{CLASH_CONTROLLER_PORT}"),
+                SELECTOR_GROUP_NAME.to_string(),
+                interval,
+                state.health_options.clone(),
+                token.clone(),
+                move || {
+                    // Best-effort read of current active node for restore-after-probe.
+                    // Uses try_read to avoid blocking the health check loop.
+                    active_state
+                        .try_read()
+                        .ok()
+                        .and_then(|g| g.node_manager.active_node().map(|n| n.name.clone()))
+                },
+                on_results,
+            );
+        }
+    }
+    state.health_cancel = Some(token);
+}
+
+/// Node Clash should route through after its config was reloaded.
+///
+/// A reload resets the selector group to its first entry, so the current
+/// selection has to be applied again. When the active node left the
+/// subscription, the best node by the last known health takes its place.
+/// A pinned node that is still in the subscription always wins.
+fn node_after_reload(
+    manager: &mut NodeManager,
+    health: &[(String, HealthResult)],
+) -> Option<String> {
+    if let Some(pinned) = manager.pinned().map(str::to_string) {
+        manager.set_active(&pinned);
+        return Some(pinned);
+    }
+    if manager.active_node().is_none() {
+        let best = manager.select_best_node(health).map(|n| n.name.clone())?;
+        manager.set_active(&best);
+    }
+    manager.active_node().map(|n| n.name.clone())
+}
+
+/// Apply a refreshed node list: reload Clash, then adopt the list in the
+/// `NodeManager`, re-select the active node and restart health checks. If
+/// Clash rejects the new config the previous node list is kept, so the
+/// manager never disagrees with what Clash is running.
+async fn apply_refreshed_nodes(
+    shared: &Arc<RwLock<VpnState>>,
+    state: &mut VpnState,
+    nodes: Vec<ProxyNode>,
+) {
+    let VpnState {
+        runtime,
+        node_manager,
+        last_health,
+        ..
+    } = state;
+    let Some(rt) = runtime.as_mut() else {
+        node_manager.replace_nodes(nodes);
+        return;
+    };
+    if let Err(e) = rt.reload_config(&nodes).await {
+        tracing::warn!("failed to apply refreshed VPN nodes, keeping previous list: {e}");
+        return;
+    }
+    node_manager.replace_nodes(nodes);
+    if let Some(active) = node_after_reload(node_manager, last_health) {
+        if let Err(e) = rt.switch_node(&active).await {
+            tracing::warn!("failed to re-select VPN node '{active}' after reload: {e}");
+        }
+    }
+    let proxy_url = rt.local_proxy_url();
+    spawn_health_loop(shared, state, proxy_url);
+    persist_snapshot(state, &NodeCache::default_cache_path()).await;
+}
+
+impl VpnControlTool {
+    /// Start the subscription refresh loop, applying changed node lists to
+    /// the `NodeManager` and the running Clash process.
+    fn spawn_subscription_refresh(
+        &self,
+        subscription_url: String,
+        interval_secs: u64,
+    ) -> tokio_util::sync::CancellationToken {
+        let token = tokio_util::sync::CancellationToken::new();
+        let refresh_state = Arc::clone(&self.state);
+        NodeManager::spawn_refresh_loop(
+            subscription_url,
+            NodeCache::default_cache_path(),
+            Some(std::time::Duration::from_secs(interval_secs)),
+            token.clone(),
+            move |nodes| {
+                let st = Arc::clone(&refresh_state);
+                tokio::spawn(async move {
+                    let mut guard = st.write().await;
+                    let diff = guard.node_manager.diff_nodes(&nodes);
+                    if diff.is_empty() {
+                        return;
+                    }
+                    tracing::info!(
+                        "VPN subscription refreshed: added {:?}, removed {:?}, changed {:?}",
+                        diff.added,
+                        diff.removed,
+                        diff.changed
+                    );
+                    apply_refreshed_nodes(&st, &mut guard, nodes).await;
+                });
+            },
+        );
+        token
+    }
+}
+
 // ── Action handlers ──────────────────────────────────────────────────
 
 impl VpnControlTool {
@@ -98,6 +350,7 @@ impl VpnControlTool {
             output: serde_json::to_string_pretty(&json!({
                 "enabled": enabled,
                 "active_node": node_name,
+                "pinned_node": state.node_manager.pinned(),
                 "latency_ms": latency_ms,
                 "health": health,
                 "listen_port": listen_port,
@@ -110,67 +363,94 @@ impl VpnControlTool {
         if state.runtime.is_some() {
             anyhow::bail!("VPN is already enabled");
         }
-        let sub_url = state.subscription_url.as_deref().unwrap_or_default();
+        let sub_url = state.subscription_url.clone().unwrap_or_default();
         if sub_url.is_empty() {
             anyhow::bail!("No VPN subscription URL configured");
         }
-        let nodes = SubscriptionParser::fetch_and_parse(sub_url).await?;
+        let cache_path = NodeCache::default_cache_path();
+        let snapshot = NodeCache::load_snapshot(&cache_path).await.ok().flatten();
+        let nodes = match SubscriptionParser::fetch_and_parse(&sub_url).await {
+            Ok(nodes) => nodes,
+            Err(e) => match snapshot.as_ref() {
+                Some(cached) if !cached.nodes.is_empty() => {
+                    tracing::warn!("VPN subscription fetch failed, using cached nodes: {e}");
+                    cached.nodes.clone()
+                }
+                _ => return Err(e),
+            },
+        };
         let listen_port = state.listen_port;
-        let config_yaml = crate::vpn::generate_clash_config(&nodes, listen_port)?;
-        let runtime = ClashRuntime::start(&config_yaml, listen_port).await?;
+        let mut runtime = state
+            .bridge
+            .start(
+                &nodes,
+                listen_port,
+                &state.bypass_checker,
+                DEFAULT_READY_TIMEOUT,
+            )
+            .await?;
         let proxy_url = runtime.local_proxy_url();
-        state.bridge.activate(&proxy_url, &state.bypass_checker)?;
-        state.node_manager = NodeManager::new(nodes.clone());
-        let node_names: Vec<String> = nodes.iter().map(|n| n.name.clone()).collect();
-        let controller_url = format!("http://127.0.0.1:{CLASH_CONTROLLER_PORT}");
-        let health_results = HealthChecker::check_all_via_clash(
-            &node_names,
-            &proxy_url,
-            &controller_url,
-            SELECTOR_GROUP_NAME,
-            None,
-        )
-        .await;
-        if let Some(best) = state.node_manager.select_best_node(&health_results) {
-            let best_name = best.name.clone();
-            state.node_manager.set_active(&best_name);
-            // Switch Clash to the best node after selection.
-            if let Some(ref mut rt) = state.runtime {
-                let _ = rt.switch_node(&best_name).await;
+        // Resume on the last-good node from the previous run, if it still
+        // exists and answers a probe, instead of re-probing everything up front.
+        let (manager, cached_health) = match snapshot {
+            Some(cached) => NodeManager::from_snapshot(CachedNodes {
+                nodes: nodes.clone(),
+                ..cached
+            }),
+            None => (NodeManager::new(nodes.clone()), Vec::new()),
+        };
+        state.node_manager = manager.with_regions(state.regions.clone());
+        let cached_active = state
+            .node_manager
+            .active_node()
+            .filter(|n| state.node_manager.is_selectable(&n.name))
+            .cloned();
+        let resumed = match cached_active {
+            Some(node) => {
+                let probe = probe_nodes(
+                    &state.health_options,
+                    std::slice::from_ref(&node),
+                    &proxy_url,
+                    None,
+                )
+                .await;
+                if probe.iter().any(|(_, hr)| hr.status == NodeStatus::Healthy) {
+                    let mut health = cached_health;
+                    health.retain(|(name, _)| name != &node.name);
+                    health.extend(probe);
+                    Some((node.name, health))
+                } else {
+                    tracing::info!(
+                        "cached VPN node '{}' failed its probe; selecting a new node",
+                        node.name
+                    );
+                    None
+                }
             }
+            None => None,
+        };
+        if let Some((active, health)) = resumed {
+            let _ = runtime.switch_node(&active).await;
+            record_health(&health);
+            state.last_health = health;
+        } else {
+            let health_results = probe_nodes(&state.health_options, &nodes, &proxy_url, None).await;
+            if let Some(best) = state.node_manager.select_best_node(&health_results) {
+                let best_name = best.name.clone();
+                state.node_manager.set_active(&best_name);
+                // Switch Clash to the best node after selection.
+                let _ = runtime.switch_node(&best_name).await;
+            }
+            record_health(&health_results);
+            state.last_health = health_results;
+        }
+        persist_snapshot(&state, &cache_path).await;
+        spawn_health_loop(&self.state, &mut state, proxy_url);
+        if state.subscription_refresh_interval_secs > 0 {
+            state.refresh_cancel = Some(
+                self.spawn_subscription_refresh(sub_url, state.subscription_refresh_interval_secs),
+            );
         }
-        state.last_health = health_results;
-        let token = tokio_util::sync::CancellationToken::new();
-        let health_state = Arc::clone(&self.state);
-        let interval_secs = state.health_check_interval_secs;
-        let bg_proxy_url = proxy_url.clone();
-        let bg_controller_url = controller_url.clone();
-        let bg_node_names = node_names.clone();
-        let active_state = Arc::clone(&self.state);
-        HealthChecker::spawn_clash_aware_loop(
-            bg_node_names,
-            bg_proxy_url,
-            bg_controller_url,
-            SELECTOR_GROUP_NAME.to_string(),
-            Some(std::time::Duration::from_secs(interval_secs)),
-            token.clone(),
-            move || {
-                // Best-effort read of current active node for restore-after-probe.
-                // Uses try_read to avoid blocking the health check loop.
-                active_state
-                    .try_read()
-                    .ok()
-                    .and_then(|g| g.node_manager.active_node().map(|n| n.name.clone()))
-            },
-            move |results| {
-                let st = Arc::clone(&health_state);
-                tokio::spawn(async move {
-                    let mut guard = st.write().await;
-                    guard.last_health = results;
-                });
-            },
-        );
-        state.health_cancel = Some(token);
         state.runtime = Some(runtime);
         Ok(ToolResult {
             success: true,
@@ -191,12 +471,14 @@ impl VpnControlTool {
         if let Some(token) = state.health_cancel.take() {
             token.cancel();
         }
-        state.bridge.deactivate()?;
-        if let Some(ref mut rt) = state.runtime {
-            rt.stop().await?;
+        if let Some(token) = state.refresh_cancel.take() {
+            token.cancel();
+        }
+        if let Some(mut rt) = state.runtime.take() {
+            state.bridge.stop(&mut rt).await?;
         }
-        state.runtime = None;
         state.last_health.clear();
+        crate::health::remove_component(VPN_HEALTH_COMPONENT);
         Ok(ToolResult {
             success: true,
             output: serde_json::to_string_pretty(&json!({
@@ -222,6 +504,7 @@ impl VpnControlTool {
                     "server": n.server,
                     "port": n.port,
                     "node_type": n.node_type.to_string(),
+                    "region": n.region,
                     "status": hr.map(|h| h.status.to_string())
                         .unwrap_or_else(|| "unknown".into()),
                     "latency_ms": hr.and_then(|h| h.latency_ms),
@@ -240,6 +523,9 @@ impl VpnControlTool {
             .and_then(Value::as_str)
             .ok_or_else(|| anyhow::anyhow!("Missing required parameter 'node_name'"))?;
         let mut state = self.state.write().await;
+        if let Some(pinned) = state.node_manager.pinned() {
+            anyhow::bail!("Node '{pinned}' is pinned; use 'unpin_node' first");
+        }
         if !state.node_manager.set_active(node_name) {
             anyhow::bail!("Node '{node_name}' not found in node list");
         }
@@ -255,6 +541,55 @@ impl VpnControlTool {
             error: None,
         })
     }
+    async fn handle_pin_node(&self, args: &Value) -> anyhow::Result<ToolResult> {
+        let node_name = args
+            .get("node_name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("Missing required parameter 'node_name'"))?;
+        let mut state = self.state.write().await;
+        if !state.node_manager.pin_node(node_name) {
+            anyhow::bail!("Node '{node_name}' not found in node list");
+        }
+        if let Some(ref mut rt) = state.runtime {
+            rt.switch_node(node_name).await?;
+        }
+        persist_snapshot(&state, &NodeCache::default_cache_path()).await;
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&json!({
+                "message": format!("Pinned node '{}'; automatic selection and failover are suspended", node_name),
+                "active_node": node_name,
+                "pinned_node": node_name,
+            }))?,
+            error: None,
+        })
+    }
+    async fn handle_unpin_node(&self) -> anyhow::Result<ToolResult> {
+        let mut state = self.state.write().await;
+        let Some(previous) = state.node_manager.unpin() else {
+            anyhow::bail!("No node is pinned");
+        };
+        // Hand control back to automatic selection using the latest health.
+        let best = state
+            .node_manager
+            .select_best_node(&state.last_health)
+            .map(|n| n.name.clone());
+        if let Some(best_name) = best {
+            state.node_manager.set_active(&best_name);
+            if let Some(ref mut rt) = state.runtime {
+                rt.switch_node(&best_name).await?;
+            }
+        }
+        persist_snapshot(&state, &NodeCache::default_cache_path()).await;
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&json!({
+                "message": format!("Unpinned node '{}'", previous),
+                "active_node": state.node_manager.active_node().map(|n| &n.name),
+            }))?,
+            error: None,
+        })
+    }
     async fn handle_refresh(&self) -> anyhow::Result<ToolResult> {
         let mut state = self.state.write().await;
         let sub_url = state.subscription_url.as_deref().unwrap_or_default();
@@ -262,7 +597,17 @@ impl VpnControlTool {
             anyhow::bail!("No VPN subscription URL configured");
         }
         let nodes = SubscriptionParser::fetch_and_parse(sub_url).await?;
-        state.node_manager = NodeManager::new(nodes.clone());
+        // Apply the new node list to the running Clash process live.
+        if let Some(ref mut rt) = state.runtime {
+            rt.reload_config(&nodes).await?;
+        }
+        let pinned = state.node_manager.pinned().map(str::to_string);
+        state.node_manager = NodeManager::new(nodes.clone()).with_regions(state.regions.clone());
+        if let Some(pinned) = pinned {
+            if !state.node_manager.pin_node(&pinned) {
+                tracing::warn!("pinned VPN node '{pinned}' left the subscription; unpinning");
+            }
+        }
         // Extract proxy URL and node info before health check to avoid borrow conflicts.
         let (proxy_url, do_health) = match state.runtime.as_ref() {
             Some(rt) => (Some(rt.local_proxy_url()), true),
@@ -270,14 +615,11 @@ impl VpnControlTool {
         };
         if do_health {
             let proxy_url = proxy_url.unwrap();
-            let node_names: Vec<String> = nodes.iter().map(|n| n.name.clone()).collect();
-            let controller_url = format!("http://127.0.0.1:{CLASH_CONTROLLER_PORT}");
             let current_active = state.node_manager.active_node().map(|n| n.name.clone());
-            let health_results = HealthChecker::check_all_via_clash(
-                &node_names,
+            let health_results = probe_nodes(
+                &state.health_options,
+                &nodes,
                 &proxy_url,
-                &controller_url,
-                SELECTOR_GROUP_NAME,
                 current_active.as_deref(),
             )
             .await;
@@ -288,8 +630,12 @@ impl VpnControlTool {
                     let _ = rt.switch_node(&best_name).await;
                 }
             }
+            record_health(&health_results);
             state.last_health = health_results;
+            // Keep background probes in step with the new node list.
+            spawn_health_loop(&self.state, &mut state, proxy_url);
         }
+        persist_snapshot(&state, &NodeCache::default_cache_path()).await;
         Ok(ToolResult {
             success: true,
             output: serde_json::to_string_pretty(&json!({
@@ -338,7 +684,7 @@ impl Tool for VpnControlTool {
         "vpn_control"
     }
     fn description(&self) -> &str {
-        "Manage VPN proxy lifecycle. Use 'enable' to start the Clash proxy (fetches subscription, selects fastest node). Use 'disable' to stop. Use 'status' to check if VPN is active and current node latency. Use 'list_nodes' to see all available proxy nodes with health. Use 'switch_node' to change active node. Use 'refresh' to re-fetch subscription and re-select best node. Use 'add_bypass'/'remove_bypass' to manage domains that skip the proxy (e.g. local/China sites). Typical workflow: status -> enable -> list_nodes -> switch_node if needed. Enable VPN when accessing blocked APIs or services behind a firewall."
+        "Manage VPN proxy lifecycle. Use 'enable' to start the Clash proxy (fetches subscription, selects fastest node). Use 'disable' to stop. Use 'status' to check if VPN is active and current node latency. Use 'list_nodes' to see all available proxy nodes with health. Use 'switch_node' to change active node. Use 'pin_node' to lock a specific node (disables automatic selection and failover) and 'unpin_node' to release it. Use 'refresh' to re-fetch subscription and re-select best node. Use 'add_bypass'/'remove_bypass' to manage domains that skip the proxy (e.g. local/China sites). Typical workflow: status -> enable -> list_nodes -> switch_node if needed. Enable VPN when accessing blocked APIs or services behind a firewall."
     }
     fn parameters_schema(&self) -> Value {
         json!({
@@ -346,12 +692,12 @@ impl Tool for VpnControlTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "enum": ["status", "enable", "disable", "list_nodes", "switch_node", "refresh", "add_bypass", "remove_bypass"],
+                    "enum": ["status", "enable", "disable", "list_nodes", "switch_node", "pin_node", "unpin_node", "refresh", "add_bypass", "remove_bypass"],
                     "description": "VPN control action to perform"
                 },
                 "node_name": {
                     "type": "string",
-                    "description": "Node name for switch_node action"
+                    "description": "Node name for switch_node and pin_node actions"
                 },
                 "domain": {
                     "type": "string",
@@ -370,7 +716,7 @@ impl Tool for VpnControlTool {
         let result = match action.as_str() {
             "status" => self.handle_status().await,
             "list_nodes" => self.handle_list_nodes().await,
-            "enable" | "disable" | "switch_node" | "refresh"
+            "enable" | "disable" | "switch_node" | "pin_node" | "unpin_node" | "refresh"
             | "add_bypass" | "remove_bypass" => {
                 if let Some(blocked) = self.require_write_access() {
                     return Ok(blocked);
@@ -379,6 +725,8 @@ impl Tool for VpnControlTool {
                     "enable" => self.handle_enable().await,
                     "disable" => self.handle_disable().await,
                     "switch_node" => self.handle_switch_node(&args).await,
+                    "pin_node" => self.handle_pin_node(&args).await,
+                    "unpin_node" => self.handle_unpin_node().await,
                     "refresh" => self.handle_refresh().await,
                     "add_bypass" => self.handle_add_bypass(&args).await,
                     "remove_bypass" => self.handle_remove_bypass(&args).await,
@@ -386,7 +734,7 @@ impl Tool for VpnControlTool {
                 }
             }
             other => anyhow::bail!(
-                "Unknown action '{}'. Valid: status, enable, disable, list_nodes, switch_node, refresh, add_bypass, remove_bypass",
+                "Unknown action '{}'. Valid: status, enable, disable, list_nodes, switch_node, pin_node, unpin_node, refresh, add_bypass, remove_bypass",
                 other
             ),
         };
@@ -422,12 +770,36 @@ mod tests {
             bypass_checker: BypassChecker::new(&[]),
             bridge: VpnProxyBridge::new(),
             health_cancel: None,
+            refresh_cancel: None,
             last_health: vec![],
             subscription_url: None,
             listen_port: 7890,
             health_check_interval_secs: 30,
+            health_options: HealthCheckOptions::default(),
+            subscription_refresh_interval_secs: 3600,
+            regions: vec![],
         }))
     }
+    fn test_node(name: &str) -> crate::vpn::ProxyNode {
+        crate::vpn::ProxyNode {
+            name: name.to_string(),
+            node_type: crate::vpn::NodeType::Trojan,
+            server: format!("{name}.example.com"),
+            port: 443,
+            raw_config: Value::Null,
+            region: None,
+        }
+    }
+    fn healthy(name: &str, latency_ms: u64) -> (String, HealthResult) {
+        (
+            name.to_string(),
+            HealthResult {
+                status: NodeStatus::Healthy,
+                latency_ms: Some(latency_ms),
+                checked_at: std::time::Instant::now(),
+            },
+        )
+    }
     fn test_tool() -> VpnControlTool {
         VpnControlTool::new(test_security(), test_state())
     }
@@ -450,6 +822,8 @@ mod tests {
         assert!(actions.contains(&"disable"));
         assert!(actions.contains(&"list_nodes"));
         assert!(actions.contains(&"switch_node"));
+        assert!(actions.contains(&"pin_node"));
+        assert!(actions.contains(&"unpin_node"));
         assert!(actions.contains(&"refresh"));
         assert!(actions.contains(&"add_bypass"));
         assert!(actions.contains(&"remove_bypass"));
@@ -475,4 +849,97 @@ mod tests {
         assert!(parsed.get("listen_port").is_some());
         assert_eq!(parsed["enabled"], false);
     }
+    #[test]
+    fn node_after_reload_reselects_when_active_node_leaves() {
+        let mut manager = NodeManager::new(vec![test_node("node-a"), test_node("node-b")]);
+        assert!(manager.set_active("node-a"));
+        assert_eq!(
+            node_after_reload(&mut manager, &[]).as_deref(),
+            Some("node-a")
+        );
+
+        manager.replace_nodes(vec![test_node("node-b"), test_node("node-c")]);
+        let health = vec![healthy("node-b", 80), healthy("node-c", 20)];
+        assert_eq!(
+            node_after_reload(&mut manager, &health).as_deref(),
+            Some("node-c")
+        );
+        assert_eq!(manager.active_node().unwrap().name, "node-c");
+    }
+    #[tokio::test]
+    async fn unhealthy_active_node_fails_over_within_regions() {
+        let mut us = test_node("node-us");
+        us.region = Some("US".into());
+        let mut jp = test_node("node-jp");
+        jp.region = Some("JP".into());
+        let mut us2 = test_node("node-us2");
+        us2.region = Some("US".into());
+        let state = test_state();
+        let mut guard = state.write().await;
+        guard.node_manager = NodeManager::new(vec![us, jp, us2]).with_regions(vec!["US".into()]);
+        assert!(guard.node_manager.set_active("node-us"));
+        guard.last_health = vec![
+            (
+                "node-us".to_string(),
+                HealthResult {
+                    status: NodeStatus::Unhealthy,
+                    latency_ms: None,
+                    checked_at: std::time::Instant::now(),
+                },
+            ),
+            healthy("node-jp", 10),
+            healthy("node-us2", 90),
+        ];
+
+        failover_if_unhealthy(&mut guard).await;
+        assert_eq!(guard.node_manager.active_node().unwrap().name, "node-us2");
+    }
+    #[tokio::test]
+    async fn pinned_node_is_reapplied_after_reload() {
+        let state = test_state();
+        {
+            let mut guard = state.write().await;
+            let mut manager = NodeManager::new(vec![test_node("node-a"), test_node("node-b")]);
+            assert!(manager.pin_node("node-a"));
+            manager.replace_nodes(vec![
+                test_node("node-a"),
+                test_node("node-b"),
+                test_node("node-c"),
+            ]);
+            let health = vec![healthy("node-b", 10), healthy("node-c", 5)];
+            assert_eq!(
+                node_after_reload(&mut manager, &health).as_deref(),
+                Some("node-a")
+            );
+            guard.node_manager = manager;
+        }
+        let tool = VpnControlTool::new(test_security(), state);
+
+        let result = tool.execute(json!({"action": "status"})).await.unwrap();
+        let parsed: Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(parsed["active_node"], "node-a");
+        assert_eq!(parsed["pinned_node"], "node-a");
+    }
+    #[tokio::test]
+    async fn vpn_control_status_reports_pinned_node_and_blocks_switch() {
+        let state = test_state();
+        {
+            let mut guard = state.write().await;
+            guard.node_manager = NodeManager::new(vec![test_node("node-a"), test_node("node-b")]);
+            assert!(guard.node_manager.pin_node("node-a"));
+        }
+        let tool = VpnControlTool::new(test_security(), state);
+
+        let result = tool.execute(json!({"action": "status"})).await.unwrap();
+        let parsed: Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(parsed["active_node"], "node-a");
+        assert_eq!(parsed["pinned_node"], "node-a");
+
+        let result = tool
+            .execute(json!({"action": "switch_node", "node_name": "node-b"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("pinned"));
+    }
 }
@@ -2,13 +2,30 @@
 //!
 //! Connects the VPN runtime to ZeroClaw's proxy system via
 //! `set_runtime_proxy_config()` / `runtime_proxy_config()`.
+//!
+//! `start()` launches Clash and only installs the proxy config once the
+//! controller responds and at least one node passes a delay test, so
+//! callers never route traffic through a proxy that isn't ready yet.
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 use super::bypass::BypassChecker;
+use super::health::PROBE_URL;
+use super::runtime::{generate_clash_config, ClashRuntime, CLASH_CONTROLLER_PORT};
+use super::subscription::ProxyNode;
 use crate::config::{runtime_proxy_config, set_runtime_proxy_config, ProxyConfig, ProxyScope};
 
+/// Default time `start()` waits for Clash to become ready.
+pub const DEFAULT_READY_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Interval between readiness polls against the Clash controller.
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Per-node delay test timeout passed to the Clash controller (milliseconds).
+const READY_DELAY_TIMEOUT_MS: u64 = 3000;
+
 /// Bridge between VPN runtime and ZeroClaw's proxy configuration.
 ///
 /// On `activate`, saves the current proxy config as backup, then installs a
@@ -104,6 +121,120 @@ impl VpnProxyBridge {
         set_runtime_proxy_config(current);
         Ok(())
     }
+
+    /// Start a Clash runtime for `nodes` and activate the proxy once ready.
+    ///
+    /// Returns only after the Clash controller on `CLASH_CONTROLLER_PORT`
+    /// responds and at least one node passes a delay test. If readiness is
+    /// not reached within `ready_timeout`, the child is stopped and an error
+    /// is returned without touching the proxy config.
+    pub async fn start(
+        &self,
+        nodes: &[ProxyNode],
+        listen_port: u16,
+        bypass_checker: &BypassChecker,
+        ready_timeout: Duration,
+    ) -> Result<ClashRuntime> {
+        if self.is_active() {
+            bail!("VPN proxy bridge is already active; stop first");
+        }
+
+        let config_yaml = generate_clash_config(nodes, listen_port)?;
+        let mut runtime = ClashRuntime::start(&config_yaml, listen_port).await?;
+
+        let controller_url = format!("http://127.0.0.1:{CLASH_CONTROLLER_PORT}");
+        let node_names: Vec<String> = nodes.iter().map(|n| n.name.clone()).collect();
+        let ready = wait_until_ready(&controller_url, &node_names, ready_timeout)
+            .await
+            .and_then(|_| self.activate(&runtime.local_proxy_url(), bypass_checker));
+
+        if let Err(e) = ready {
+            runtime.stop().await.ok();
+            return Err(e);
+        }
+        Ok(runtime)
+    }
+
+    /// Stop a runtime started by `start()`: restore the proxy config, then
+    /// kill the Clash child and wait for it to exit.
+    pub async fn stop(&self, runtime: &mut ClashRuntime) -> Result<()> {
+        if self.is_active() {
+            self.deactivate()?;
+        }
+        runtime.stop().await
+    }
+}
+
+/// Wait until the Clash controller at `controller_url` is up and at least
+/// one of `node_names` passes a delay test.
+///
+/// Returns the name of the first healthy node. Fails once `timeout` elapses.
+pub async fn wait_until_ready(
+    controller_url: &str,
+    node_names: &[String],
+    timeout: Duration,
+) -> Result<String> {
+    if node_names.is_empty() {
+        bail!("cannot wait for Clash readiness with zero proxy nodes");
+    }
+
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .timeout(Duration::from_millis(READY_DELAY_TIMEOUT_MS + 1000))
+        .build()
+        .context("failed to build clash controller client")?;
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        if controller_responds(&client, controller_url).await {
+            for name in node_names {
+                if node_passes_delay_test(&client, controller_url, name).await {
+                    return Ok(name.clone());
+                }
+            }
+        }
+
+        if Instant::now() + READY_POLL_INTERVAL > deadline {
+            bail!(
+                "Clash not ready after {}ms: controller unreachable or no healthy nodes",
+                timeout.as_millis()
+            );
+        }
+        tokio::time::sleep(READY_POLL_INTERVAL).await;
+    }
+}
+
+/// Whether `GET {controller}/version` succeeds.
+async fn controller_responds(client: &reqwest::Client, controller_url: &str) -> bool {
+    client
+        .get(format!("{controller_url}/version"))
+        .send()
+        .await
+        .is_ok_and(|r| r.status().is_success())
+}
+
+/// Whether Clash's `GET /proxies/{name}/delay` reports a delay for the node.
+async fn node_passes_delay_test(
+    client: &reqwest::Client,
+    controller_url: &str,
+    name: &str,
+) -> bool {
+    let url = format!(
+        "{controller_url}/proxies/{}/delay?timeout={READY_DELAY_TIMEOUT_MS}&url={}",
+        urlencoding::encode(name),
+        urlencoding::encode(PROBE_URL),
+    );
+    let Ok(resp) = client.get(url).send().await else {
+        return false;
+    };
+    if !resp.status().is_success() {
+        return false;
+    }
+    resp.json::<serde_json::Value>()
+        .await
+        .ok()
+        .and_then(|v| v.get("delay").and_then(serde_json::Value::as_u64))
+        .is_some()
 }
 
 impl Default for VpnProxyBridge {
@@ -331,4 +462,114 @@ mod tests {
         assert!(merged.contains(&"*.google.com".to_string()));
         assert!(merged.contains(&"localhost".to_string()));
     }
+
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn wait_until_ready_waits_for_controller() {
+        let server = MockServer::start().await;
+        // Controller is "starting up" for the first two polls.
+        Mock::given(method("GET"))
+            .and(path("/version"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/version"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "version": "mock"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/proxies/node-a/delay"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "delay": 42 })),
+            )
+            .mount(&server)
+            .await;
+
+        let ready = wait_until_ready(
+            &server.uri(),
+            &["node-a".to_string()],
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+        assert_eq!(ready, "node-a");
+
+        let version_calls = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|r| r.url.path() == "/version")
+            .count();
+        assert_eq!(version_calls, 3, "readiness must gate on the controller");
+    }
+
+    #[tokio::test]
+    async fn wait_until_ready_skips_unhealthy_nodes() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/version"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/proxies/node-a/delay"))
+            .respond_with(ResponseTemplate::new(504))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/proxies/node-b/delay"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "delay": 120 })),
+            )
+            .mount(&server)
+            .await;
+
+        let ready = wait_until_ready(
+            &server.uri(),
+            &["node-a".to_string(), "node-b".to_string()],
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+        assert_eq!(ready, "node-b");
+    }
+
+    #[tokio::test]
+    async fn wait_until_ready_times_out_without_healthy_node() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/version"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/proxies/node-a/delay"))
+            .respond_with(ResponseTemplate::new(504))
+            .mount(&server)
+            .await;
+
+        let err = wait_until_ready(
+            &server.uri(),
+            &["node-a".to_string()],
+            Duration::from_millis(500),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("not ready"));
+    }
+
+    #[tokio::test]
+    async fn wait_until_ready_rejects_empty_nodes() {
+        let err = wait_until_ready("http://127.0.0.1:1", &[], Duration::from_millis(100))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("zero proxy nodes"));
+    }
 }
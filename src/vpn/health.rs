@@ -1,9 +1,11 @@
 //! Health checker and latency tester for VPN proxy nodes.
 //!
-//! Probes proxy nodes by sending HTTP requests through the SOCKS5 proxy
-//! to a connectivity check endpoint. Uses Clash's controller API to switch
-//! to each node before probing, so each node gets an independent latency
-//! measurement. Supports background monitoring loop with graceful shutdown.
+//! Probes proxy nodes either by sending HTTP requests through the SOCKS5 proxy
+//! to a connectivity check endpoint (`url_test`, accurate) or by a plain TCP
+//! handshake to the node's server (`tcp_connect`, fast). For URL tests, uses
+//! Clash's controller API to switch to each node before probing, so each node
+//! gets an independent latency measurement. Supports background monitoring
+//! loop with graceful shutdown.
 
 use std::sync::OnceLock;
 use std::time::{Duration, Instant};
@@ -11,6 +13,9 @@ use std::time::{Duration, Instant};
 use anyhow::Result;
 use tokio_util::sync::CancellationToken;
 
+use super::subscription::ProxyNode;
+pub use crate::config::VpnHealthStrategy;
+
 /// Prevents concurrent health checks from interfering with each other's node switching.
 static HEALTH_CHECK_LOCK: OnceLock<tokio::sync::Mutex<()>> = OnceLock::new();
 
@@ -20,19 +25,20 @@ fn health_check_lock() -> &'static tokio::sync::Mutex<()> {
 }
 
 /// Connectivity check URL — returns HTTP 204 on success.
-const PROBE_URL: &str = "http://connectivitycheck.gstatic.com/generate_204";
+pub(crate) const PROBE_URL: &str = "http://connectivitycheck.gstatic.com/generate_204";
 
-/// Probe timeout per node.
+/// Default probe timeout per node.
 const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
 
-/// Connect timeout for the probe client.
-const PROBE_CONNECT_TIMEOUT: Duration = Duration::from_secs(4);
+/// Default number of nodes probed concurrently by `check_all`.
+const DEFAULT_PROBE_CONCURRENCY: usize = 8;
 
 /// Default background health check interval.
 const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(30);
 
 /// Health status of a proxy node.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum NodeStatus {
     /// Node responded with expected status within timeout.
     Healthy,
@@ -92,34 +98,94 @@ impl HealthResult {
     }
 }
 
+/// Tuning knobs for health-check rounds.
+#[derive(Debug, Clone)]
+pub struct HealthCheckOptions {
+    /// Maximum number of nodes probed at the same time by `check_all`.
+    pub concurrency: usize,
+    /// Per-probe timeout; nodes that exceed it are reported unhealthy.
+    pub probe_timeout: Duration,
+    /// How each node is probed.
+    pub strategy: VpnHealthStrategy,
+    /// URL fetched through the proxy by `VpnHealthStrategy::UrlTest`.
+    pub test_url: String,
+}
+
+impl Default for HealthCheckOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: DEFAULT_PROBE_CONCURRENCY,
+            probe_timeout: PROBE_TIMEOUT,
+            strategy: VpnHealthStrategy::UrlTest,
+            test_url: PROBE_URL.to_string(),
+        }
+    }
+}
+
 /// Health checker for VPN proxy nodes.
 pub struct HealthChecker;
 
 impl HealthChecker {
-    /// Probe a single proxy node and return its health status.
-    pub async fn check_node(proxy_url: &str) -> HealthResult {
-        match Self::measure_latency(proxy_url).await {
+    /// Probe a single node and return its health status.
+    ///
+    /// `target` is a proxy URL for `UrlTest` or a `host:port` address for
+    /// `TcpConnect` (see `tcp_targets`). Nodes that don't answer within
+    /// `options.probe_timeout` are reported unhealthy.
+    pub async fn check_node(target: &str, options: &HealthCheckOptions) -> HealthResult {
+        let latency = match options.strategy {
+            VpnHealthStrategy::UrlTest => {
+                Self::measure_latency(target, &options.test_url, options.probe_timeout).await
+            }
+            VpnHealthStrategy::TcpConnect => {
+                Self::measure_tcp_connect(target, options.probe_timeout).await
+            }
+        };
+        match latency {
             Ok(latency) => HealthResult::healthy(latency),
             Err(_) => HealthResult::unhealthy(),
         }
     }
 
-    /// Measure round-trip latency through a SOCKS5 proxy.
-    pub async fn measure_latency(proxy_url: &str) -> Result<Duration> {
+    /// Build `(name, host:port)` probe targets for `VpnHealthStrategy::TcpConnect`.
+    pub fn tcp_targets(nodes: &[ProxyNode]) -> Vec<(String, String)> {
+        nodes
+            .iter()
+            .map(|n| (n.name.clone(), format!("{}:{}", n.server, n.port)))
+            .collect()
+    }
+
+    /// Measure TCP handshake time to `addr` (`host:port`).
+    pub async fn measure_tcp_connect(addr: &str, probe_timeout: Duration) -> Result<Duration> {
+        let start = Instant::now();
+        tokio::time::timeout(probe_timeout, tokio::net::TcpStream::connect(addr))
+            .await
+            .map_err(|_| anyhow::anyhow!("tcp connect to {addr} timed out"))?
+            .map_err(|e| anyhow::anyhow!("tcp connect to {addr} failed: {e}"))?;
+        Ok(start.elapsed())
+    }
+
+    /// Measure round-trip latency of a GET to `test_url` through a proxy.
+    pub async fn measure_latency(
+        proxy_url: &str,
+        test_url: &str,
+        probe_timeout: Duration,
+    ) -> Result<Duration> {
         let proxy = reqwest::Proxy::all(proxy_url)
             .map_err(|e| anyhow::anyhow!("invalid proxy URL '{proxy_url}': {e}"))?;
 
         let client = reqwest::Client::builder()
-            .proxy(proxy)
-            .timeout(PROBE_TIMEOUT)
-            .connect_timeout(PROBE_CONNECT_TIMEOUT)
+            // `no_proxy()` clears every configured proxy, so it must come
+            // before the probe proxy is added.
             .no_proxy()
+            .proxy(proxy)
+            .timeout(probe_timeout)
+            .connect_timeout(probe_timeout)
             .build()
             .map_err(|e| anyhow::anyhow!("failed to build probe client: {e}"))?;
 
         let start = Instant::now();
         let resp = client
-            .get(PROBE_URL)
+            .get(test_url)
             .send()
             .await
             .map_err(|e| anyhow::anyhow!("probe failed through {proxy_url}: {e}"))?;
@@ -168,12 +234,15 @@ impl HealthChecker {
     /// measuring the same active node repeatedly.
     ///
     /// After all probes, restores the original active node (best-effort).
+    /// Probes run sequentially as URL tests regardless of `options.strategy`,
+    /// so only `probe_timeout` and `test_url` apply.
     pub async fn check_all_via_clash(
         node_names: &[String],
         proxy_url: &str,
         controller_url: &str,
         group_name: &str,
         current_active: Option<&str>,
+        options: &HealthCheckOptions,
     ) -> Vec<(String, HealthResult)> {
         let _guard = health_check_lock().lock().await;
         let mut results = Vec::with_capacity(node_names.len());
@@ -190,7 +259,13 @@ impl HealthChecker {
             tokio::time::sleep(Duration::from_millis(100)).await;
 
             // Probe through the proxy (now routed to this specific node).
-            let result = Self::check_node(proxy_url).await;
+            let result =
+                match Self::measure_latency(proxy_url, &options.test_url, options.probe_timeout)
+                    .await
+                {
+                    Ok(latency) => HealthResult::healthy(latency),
+                    Err(_) => HealthResult::unhealthy(),
+                };
             results.push((name.clone(), result));
         }
 
@@ -204,22 +279,20 @@ impl HealthChecker {
         results
     }
 
-    /// Check all nodes in parallel. Each entry is `(name, proxy_url)`.
-    /// Returns `(name, HealthResult)` for every node.
-    pub async fn check_all(nodes: &[(String, String)]) -> Vec<(String, HealthResult)> {
-        let futures: Vec<_> = nodes
-            .iter()
-            .map(|(name, url)| {
-                let name = name.clone();
-                let url = url.clone();
-                async move {
-                    let result = Self::check_node(&url).await;
-                    (name, result)
-                }
-            })
-            .collect();
-
-        futures_util::future::join_all(futures).await
+    /// Check all nodes in parallel. Each entry is `(name, target)`, where
+    /// `target` is interpreted per `options.strategy` (see `check_node`).
+    ///
+    /// At most `options.concurrency` probes run at once. Returns
+    /// `(name, HealthResult)` for every node, in input order; nodes that hit
+    /// `options.probe_timeout` are reported unhealthy.
+    pub async fn check_all(
+        nodes: &[(String, String)],
+        options: &HealthCheckOptions,
+    ) -> Vec<(String, HealthResult)> {
+        probe_bounded(nodes, options, |target, _timeout| async move {
+            Self::check_node(&target, options).await
+        })
+        .await
     }
     /// Spawn a background health-check loop.
     ///
@@ -229,6 +302,7 @@ impl HealthChecker {
     pub fn spawn_background_loop<F>(
         nodes: Vec<(String, String)>,
         interval: Option<Duration>,
+        options: HealthCheckOptions,
         token: CancellationToken,
         on_results: F,
     ) -> tokio::task::JoinHandle<()>
@@ -247,7 +321,7 @@ impl HealthChecker {
                         break;
                     }
                     _ = ticker.tick() => {
-                        let results = Self::check_all(&nodes).await;
+                        let results = Self::check_all(&nodes, &options).await;
                         on_results(results);
                     }
                 }
@@ -266,6 +340,7 @@ impl HealthChecker {
         controller_url: String,
         group_name: String,
         interval: Option<Duration>,
+        options: HealthCheckOptions,
         token: CancellationToken,
         get_active: G,
         on_results: F,
@@ -292,6 +367,7 @@ impl HealthChecker {
                             &controller_url,
                             &group_name,
                             active.as_deref(),
+                            &options,
                         ).await;
                         on_results(results);
                     }
@@ -301,6 +377,37 @@ impl HealthChecker {
     }
 }
 
+/// Run `probe` for every `(name, proxy_url)` with bounded concurrency and a
+/// per-probe timeout, preserving input order in the results.
+async fn probe_bounded<F, Fut>(
+    nodes: &[(String, String)],
+    options: &HealthCheckOptions,
+    probe: F,
+) -> Vec<(String, HealthResult)>
+where
+    F: Fn(String, Duration) -> Fut,
+    Fut: std::future::Future<Output = HealthResult>,
+{
+    let permits = tokio::sync::Semaphore::new(options.concurrency.max(1));
+    let timeout = options.probe_timeout;
+    let futures: Vec<_> = nodes
+        .iter()
+        .map(|(name, url)| {
+            let permits = &permits;
+            let probe_fut = probe(url.clone(), timeout);
+            async move {
+                let _permit = permits.acquire().await;
+                let result = tokio::time::timeout(timeout, probe_fut)
+                    .await
+                    .unwrap_or_else(|_| HealthResult::unhealthy());
+                (name.clone(), result)
+            }
+        })
+        .collect();
+
+    futures_util::future::join_all(futures).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -347,12 +454,14 @@ mod tests {
         let handle = HealthChecker::spawn_background_loop(
             vec![],
             Some(Duration::from_millis(50)),
+            HealthCheckOptions::default(),
             token.clone(),
             move |_results| {
                 counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
             },
         );
-        tokio::time::sleep(Duration::from_millis(30)).await;
+        // The first tick is consumed up front, so wait past one full interval.
+        tokio::time::sleep(Duration::from_millis(120)).await;
         token.cancel();
         handle.await.unwrap();
         let count = call_count.load(std::sync::atomic::Ordering::Relaxed);
@@ -361,4 +470,154 @@ mod tests {
             "callback should have been invoked at least once"
         );
     }
+
+    #[tokio::test]
+    async fn probe_bounded_respects_concurrency_limit() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let nodes: Vec<(String, String)> = (0..40)
+            .map(|i| {
+                (
+                    format!("node-{i}"),
+                    format!("socks5://127.0.0.1:{}", 20000 + i),
+                )
+            })
+            .collect();
+        let options = HealthCheckOptions {
+            concurrency: 4,
+            probe_timeout: Duration::from_secs(5),
+            ..HealthCheckOptions::default()
+        };
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let results = probe_bounded(&nodes, &options, |_url, _timeout| {
+            let in_flight = in_flight.clone();
+            let peak = peak.clone();
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                HealthResult::healthy(Duration::from_millis(5))
+            }
+        })
+        .await;
+
+        assert_eq!(results.len(), 40);
+        assert_eq!(results[0].0, "node-0");
+        assert_eq!(results[39].0, "node-39");
+        assert!(results.iter().all(|(_, r)| r.status == NodeStatus::Healthy));
+        let peak = peak.load(Ordering::SeqCst);
+        assert!(peak <= 4, "peak concurrency {peak} exceeded limit");
+        assert!(peak >= 2, "probes should overlap up to the limit");
+    }
+
+    #[tokio::test]
+    async fn probe_bounded_marks_timed_out_nodes_unhealthy() {
+        let nodes = vec![
+            ("fast".to_string(), "socks5://127.0.0.1:1".to_string()),
+            ("slow".to_string(), "socks5://127.0.0.1:2".to_string()),
+        ];
+        let options = HealthCheckOptions {
+            concurrency: 2,
+            probe_timeout: Duration::from_millis(50),
+            ..HealthCheckOptions::default()
+        };
+
+        let results = probe_bounded(&nodes, &options, |url, _timeout| async move {
+            if url.ends_with(":2") {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+            HealthResult::healthy(Duration::from_millis(1))
+        })
+        .await;
+
+        assert_eq!(results[0].1.status, NodeStatus::Healthy);
+        assert_eq!(results[1].0, "slow");
+        assert_eq!(results[1].1.status, NodeStatus::Unhealthy);
+        assert_eq!(results[1].1.latency_ms, None);
+    }
+
+    #[tokio::test]
+    async fn tcp_connect_strategy_measures_reachable_node() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let options = HealthCheckOptions {
+            strategy: VpnHealthStrategy::TcpConnect,
+            probe_timeout: Duration::from_secs(2),
+            ..HealthCheckOptions::default()
+        };
+
+        let result = HealthChecker::check_node(&addr, &options).await;
+        assert_eq!(result.status, NodeStatus::Healthy);
+        assert!(result.latency_ms.is_some());
+
+        drop(listener);
+        let result = HealthChecker::check_node(&addr, &options).await;
+        assert_eq!(result.status, NodeStatus::Unhealthy);
+    }
+
+    #[tokio::test]
+    async fn url_test_strategy_probes_test_url_through_proxy() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // The mock server acts as an HTTP proxy: absolute-form requests for the
+        // test URL arrive with the test URL's path.
+        let proxy = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/generate_204"))
+            .respond_with(ResponseTemplate::new(204).set_delay(Duration::from_millis(20)))
+            .expect(1)
+            .mount(&proxy)
+            .await;
+        let options = HealthCheckOptions {
+            strategy: VpnHealthStrategy::UrlTest,
+            test_url: "http://probe.invalid/generate_204".into(),
+            probe_timeout: Duration::from_secs(2),
+            ..HealthCheckOptions::default()
+        };
+
+        let result = HealthChecker::check_node(&proxy.uri(), &options).await;
+        assert_eq!(result.status, NodeStatus::Healthy);
+        assert!(result.latency_ms.unwrap() >= 20);
+    }
+
+    #[tokio::test]
+    async fn url_test_strategy_rejects_unexpected_status() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let proxy = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(502))
+            .mount(&proxy)
+            .await;
+        let options = HealthCheckOptions {
+            test_url: "http://probe.invalid/generate_204".into(),
+            ..HealthCheckOptions::default()
+        };
+
+        let result = HealthChecker::check_node(&proxy.uri(), &options).await;
+        assert_eq!(result.status, NodeStatus::Unhealthy);
+        assert_eq!(result.latency_ms, None);
+    }
+
+    #[test]
+    fn tcp_targets_use_node_server_and_port() {
+        let nodes = vec![ProxyNode {
+            name: "node-a".into(),
+            node_type: crate::vpn::subscription::NodeType::Trojan,
+            server: "a.example.com".into(),
+            port: 443,
+            raw_config: serde_json::Value::Null,
+            region: None,
+        }];
+        assert_eq!(
+            HealthChecker::tcp_targets(&nodes),
+            vec![("node-a".to_string(), "a.example.com:443".to_string())]
+        );
+    }
 }
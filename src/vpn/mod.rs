@@ -11,10 +11,10 @@ pub mod node_manager;
 pub mod runtime;
 pub mod subscription;
 
-pub use bridge::VpnProxyBridge;
+pub use bridge::{VpnProxyBridge, DEFAULT_READY_TIMEOUT};
 pub use bypass::{BypassChecker, BypassDecision};
-pub use health::{HealthChecker, HealthResult, NodeStatus};
-pub use node_manager::{NodeCache, NodeManager};
+pub use health::{HealthCheckOptions, HealthChecker, HealthResult, NodeStatus, VpnHealthStrategy};
+pub use node_manager::{CachedNodes, NodeCache, NodeManager};
 pub use runtime::{generate_clash_config, ClashRuntime};
 pub(crate) use runtime::{CLASH_CONTROLLER_PORT, SELECTOR_GROUP_NAME};
 pub use subscription::{NodeType, ProxyNode, SubscriptionParser};
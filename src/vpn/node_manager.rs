@@ -1,24 +1,86 @@
 //! Node persistence (disk cache) and selection/failover for VPN proxy nodes.
 //!
 //! Provides async save/load of `ProxyNode` lists to a JSON file on disk,
-//! with a last-fetched timestamp. Corrupt files are handled gracefully
-//! (logged as warning, returns `None`).
+//! with a last-fetched timestamp, the last-good selection and recent health
+//! results, so a restart can resume on the previous best node after probing
+//! only that node. Corrupt files are handled gracefully (logged as warning,
+//! returns `None`). A background refresh loop keeps the cache in sync with
+//! the subscription.
 
 use super::health::{HealthResult, NodeStatus};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
 
-use super::subscription::ProxyNode;
+use super::subscription::{ProxyNode, SubscriptionParser};
 
-/// Wrapper stored on disk: nodes + fetch timestamp.
+/// Default subscription refresh interval.
+const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Wrapper stored on disk: nodes + fetch timestamp + last selection/health.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CachedNodes {
     /// ISO-8601 timestamp of when nodes were last fetched.
     pub fetched_at: String,
     /// The cached proxy nodes.
     pub nodes: Vec<ProxyNode>,
+    /// Name of the last-good active node.
+    #[serde(default)]
+    pub active: Option<String>,
+    /// Name of the manually pinned node, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<String>,
+    /// Most recent health result per node.
+    #[serde(default)]
+    pub health: Vec<CachedHealth>,
+}
+
+/// A `HealthResult` in on-disk form (wall-clock timestamp instead of `Instant`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedHealth {
+    pub name: String,
+    pub status: NodeStatus,
+    pub latency_ms: Option<u64>,
+    /// ISO-8601 timestamp of when the check ran.
+    pub checked_at: String,
+}
+
+impl CachedHealth {
+    /// Convert a live health result for storage.
+    pub fn from_result(name: &str, result: &HealthResult) -> Self {
+        let age = chrono::Duration::from_std(result.checked_at.elapsed()).unwrap_or_default();
+        Self {
+            name: name.to_string(),
+            status: result.status,
+            latency_ms: result.latency_ms,
+            checked_at: (chrono::Utc::now() - age).to_rfc3339(),
+        }
+    }
+
+    /// Convert back to a live health result, preserving the check's age.
+    pub fn to_result(&self) -> (String, HealthResult) {
+        let age = chrono::DateTime::parse_from_rfc3339(&self.checked_at)
+            .ok()
+            .and_then(|t| {
+                (chrono::Utc::now() - t.with_timezone(&chrono::Utc))
+                    .to_std()
+                    .ok()
+            })
+            .unwrap_or_default();
+        let checked_at = Instant::now().checked_sub(age).unwrap_or_else(Instant::now);
+        (
+            self.name.clone(),
+            HealthResult {
+                status: self.status,
+                latency_ms: self.latency_ms,
+                checked_at,
+            },
+        )
+    }
 }
 
 /// Disk cache for proxy nodes.
@@ -38,18 +100,55 @@ impl NodeCache {
         let cached = CachedNodes {
             fetched_at: chrono::Utc::now().to_rfc3339(),
             nodes: nodes.to_vec(),
+            active: None,
+            pinned: None,
+            health: Vec::new(),
         };
+        Self::save_snapshot(&cached, path).await
+    }
+
+    /// Save a full snapshot (nodes + selection + health) to `path`.
+    ///
+    /// Creates parent directories if they don't exist.
+    pub async fn save_snapshot(snapshot: &CachedNodes, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
 
-        let json = serde_json::to_string_pretty(&cached)?;
+        let json = serde_json::to_string_pretty(snapshot)?;
         tokio::fs::write(path, json).await?;
         Ok(())
     }
 
+    /// Replace the cached node list, keeping the saved selection and health
+    /// for nodes that are still present.
+    pub async fn update_nodes(nodes: &[ProxyNode], path: &Path) -> Result<()> {
+        let mut snapshot = match Self::load_snapshot(path).await? {
+            Some(snapshot) => snapshot,
+            None => return Self::save(nodes, path).await,
+        };
+        let present = |name: &str| nodes.iter().any(|n| n.name == name);
+        snapshot.fetched_at = chrono::Utc::now().to_rfc3339();
+        snapshot.nodes = nodes.to_vec();
+        snapshot.active = snapshot.active.filter(|a| present(a));
+        snapshot.pinned = snapshot.pinned.filter(|p| present(p));
+        snapshot.health.retain(|h| present(&h.name));
+        Self::save_snapshot(&snapshot, path).await
+    }
+
     /// Load cached nodes from a JSON file at `path`.
     ///
     /// Returns `Ok(None)` if the file doesn't exist or contains corrupt JSON.
     /// Corrupt files are logged as warnings, not treated as hard errors.
     pub async fn load(path: &Path) -> Result<Option<Vec<ProxyNode>>> {
+        Ok(Self::load_snapshot(path).await?.map(|cached| cached.nodes))
+    }
+
+    /// Load the full snapshot (nodes + selection + health) from `path`.
+    ///
+    /// Same error handling as `load`. Files written before selection and
+    /// health were persisted load with `active: None` and empty `health`.
+    pub async fn load_snapshot(path: &Path) -> Result<Option<CachedNodes>> {
         if !path.exists() {
             return Ok(None);
         }
@@ -63,7 +162,7 @@ impl NodeCache {
         };
 
         match serde_json::from_str::<CachedNodes>(&data) {
-            Ok(cached) => Ok(Some(cached.nodes)),
+            Ok(cached) => Ok(Some(cached)),
             Err(e) => {
                 tracing::warn!("corrupt node cache at {}, ignoring: {e}", path.display());
                 Ok(None)
@@ -87,10 +186,14 @@ impl NodeCache {
 ///
 /// Tracks a pool of proxy nodes and an optional active node.
 /// Selection picks the lowest-latency healthy node; failover
-/// skips the current active and picks the next best.
+/// skips the current active and picks the next best. When a region set is
+/// configured, both only consider nodes in those regions. A pinned node
+/// overrides both until it is unpinned.
 pub struct NodeManager {
     nodes: Vec<ProxyNode>,
     active: Option<String>,
+    pinned: Option<String>,
+    regions: Vec<String>,
 }
 
 impl NodeManager {
@@ -99,17 +202,74 @@ impl NodeManager {
         Self {
             nodes,
             active: None,
+            pinned: None,
+            regions: Vec::new(),
         }
     }
 
+    /// Restrict selection and failover to nodes whose region is one of
+    /// `regions` (ISO codes such as `US`, compared case-insensitively).
+    /// An empty list allows every node.
+    pub fn with_regions(mut self, regions: Vec<String>) -> Self {
+        self.regions = regions;
+        self
+    }
+
+    /// Names of the nodes selection may pick under the region filter, or
+    /// `None` when every node is eligible.
+    ///
+    /// Falls back to every node (with a warning) when none match the
+    /// configured regions, so a bad setting never leaves the VPN without
+    /// candidates.
+    fn region_pool(&self) -> Option<HashSet<&str>> {
+        if self.regions.is_empty() {
+            return None;
+        }
+        let pool: HashSet<&str> = self
+            .nodes
+            .iter()
+            .filter(|n| {
+                n.region
+                    .as_deref()
+                    .is_some_and(|r| self.regions.iter().any(|want| want.eq_ignore_ascii_case(r)))
+            })
+            .map(|n| n.name.as_str())
+            .collect();
+        if pool.is_empty() {
+            tracing::warn!(
+                "no VPN nodes match regions {:?}; considering all nodes",
+                self.regions
+            );
+            return None;
+        }
+        Some(pool)
+    }
+
+    /// Whether selection may use `node_name` under the region filter. The
+    /// pinned node is always allowed.
+    pub fn is_selectable(&self, node_name: &str) -> bool {
+        if self.pinned.as_deref() == Some(node_name) {
+            return true;
+        }
+        self.region_pool()
+            .map_or(true, |pool| pool.contains(node_name))
+    }
+
     /// Select the best (lowest-latency healthy) node.
+    ///
+    /// While a node is pinned, that node is returned regardless of health.
     pub fn select_best_node(
         &self,
         health_results: &[(String, HealthResult)],
     ) -> Option<&ProxyNode> {
+        if let Some(pinned) = self.pinned.as_deref() {
+            return self.nodes.iter().find(|n| n.name == pinned);
+        }
+        let pool = self.region_pool();
         let mut healthy: Vec<(&String, u64)> = health_results
             .iter()
             .filter(|(_, hr)| hr.status == NodeStatus::Healthy)
+            .filter(|(name, _)| pool.as_ref().map_or(true, |p| p.contains(name.as_str())))
             .filter_map(|(name, hr)| hr.latency_ms.map(|ms| (name, ms)))
             .collect();
 
@@ -122,14 +282,20 @@ impl NodeManager {
 
     /// Failover: select next best healthy node, skipping the current active.
     ///
-    /// Returns `None` when all nodes are unhealthy.
+    /// Returns `None` when all nodes are unhealthy, or when a node is pinned
+    /// (the active node is then left unchanged).
     pub fn failover(&mut self, health_results: &[(String, HealthResult)]) -> Option<&ProxyNode> {
+        if self.pinned.is_some() {
+            return None;
+        }
         let skip = self.active.as_deref();
+        let pool = self.region_pool();
 
         let mut healthy: Vec<(&String, u64)> = health_results
             .iter()
             .filter(|(name, _)| skip.map_or(true, |s| s != name))
             .filter(|(_, hr)| hr.status == NodeStatus::Healthy)
+            .filter(|(name, _)| pool.as_ref().map_or(true, |p| p.contains(name.as_str())))
             .filter_map(|(name, hr)| hr.latency_ms.map(|ms| (name, ms)))
             .collect();
 
@@ -160,15 +326,185 @@ impl NodeManager {
         }
     }
 
+    /// Pin `node_name` as the active node, overriding automatic selection
+    /// and failover until [`unpin`](Self::unpin) is called. Returns `true`
+    /// if the node exists.
+    pub fn pin_node(&mut self, node_name: &str) -> bool {
+        if !self.set_active(node_name) {
+            return false;
+        }
+        self.pinned = Some(node_name.to_string());
+        true
+    }
+
+    /// Clear the pin, returning the name of the previously pinned node.
+    /// The active node is kept until the next selection or failover.
+    pub fn unpin(&mut self) -> Option<String> {
+        self.pinned.take()
+    }
+
+    /// Name of the pinned node, if any.
+    pub fn pinned(&self) -> Option<&str> {
+        self.pinned.as_deref()
+    }
+
     /// Return all managed nodes.
     pub fn all_nodes(&self) -> &[ProxyNode] {
         &self.nodes
     }
+
+    /// Capture nodes, active selection and `health` for `NodeCache::save_snapshot`.
+    pub fn snapshot(&self, health: &[(String, HealthResult)]) -> CachedNodes {
+        CachedNodes {
+            fetched_at: chrono::Utc::now().to_rfc3339(),
+            nodes: self.nodes.clone(),
+            active: self.active.clone(),
+            pinned: self.pinned.clone(),
+            health: health
+                .iter()
+                .map(|(name, hr)| CachedHealth::from_result(name, hr))
+                .collect(),
+        }
+    }
+
+    /// Rebuild a manager from a cached snapshot.
+    ///
+    /// Returns the manager (with the saved selection and pin, if those nodes
+    /// are still in the list) and the restored health results.
+    pub fn from_snapshot(snapshot: CachedNodes) -> (Self, Vec<(String, HealthResult)>) {
+        let mut manager = Self::new(snapshot.nodes);
+        if let Some(active) = snapshot.active.as_deref() {
+            manager.set_active(active);
+        }
+        if let Some(pinned) = snapshot.pinned.as_deref() {
+            manager.pin_node(pinned);
+        }
+        let health = snapshot
+            .health
+            .iter()
+            .filter(|h| manager.nodes.iter().any(|n| n.name == h.name))
+            .map(CachedHealth::to_result)
+            .collect();
+        (manager, health)
+    }
+
+    /// Compare the node pool against a freshly fetched list without
+    /// changing anything.
+    pub fn diff_nodes(&self, nodes: &[ProxyNode]) -> NodeDiff {
+        let added = nodes
+            .iter()
+            .filter(|n| !self.nodes.iter().any(|old| old.name == n.name))
+            .map(|n| n.name.clone())
+            .collect();
+        let removed = self
+            .nodes
+            .iter()
+            .filter(|old| !nodes.iter().any(|n| n.name == old.name))
+            .map(|n| n.name.clone())
+            .collect();
+        let changed = nodes
+            .iter()
+            .filter(|n| self.nodes.iter().any(|old| old.name == n.name && old != *n))
+            .map(|n| n.name.clone())
+            .collect();
+        NodeDiff {
+            added,
+            removed,
+            changed,
+        }
+    }
+
+    /// Replace the node pool with a freshly fetched list.
+    ///
+    /// Keeps the current selection and pin if a node with the same name still
+    /// exists, otherwise clears them. Returns the names of added, removed and
+    /// changed nodes.
+    pub fn replace_nodes(&mut self, nodes: Vec<ProxyNode>) -> NodeDiff {
+        let diff = self.diff_nodes(&nodes);
+        self.nodes = nodes;
+        if let Some(active) = self.active.as_deref() {
+            if !self.nodes.iter().any(|n| n.name == active) {
+                self.active = None;
+            }
+        }
+        if let Some(pinned) = self.pinned.as_deref() {
+            if !self.nodes.iter().any(|n| n.name == pinned) {
+                tracing::warn!("pinned VPN node '{pinned}' left the subscription; unpinning");
+                self.pinned = None;
+            }
+        }
+
+        diff
+    }
+
+    /// Spawn a background loop that re-fetches the subscription.
+    ///
+    /// Every `interval` (default 1h) the subscription at `subscription_url`
+    /// is fetched and parsed, the result is written to the `NodeCache` at
+    /// `cache_path`, and `on_nodes` is invoked with the new list so the caller
+    /// can update its `NodeManager` and reload Clash. Fetch failures are
+    /// logged and retried on the next tick. Cancel `token` to stop the loop.
+    pub fn spawn_refresh_loop<F>(
+        subscription_url: String,
+        cache_path: PathBuf,
+        interval: Option<Duration>,
+        token: CancellationToken,
+        on_nodes: F,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        F: Fn(Vec<ProxyNode>) + Send + Sync + 'static,
+    {
+        let interval = interval.unwrap_or(DEFAULT_REFRESH_INTERVAL);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            // First tick fires immediately; nodes were just fetched on enable.
+            ticker.tick().await;
+            loop {
+                tokio::select! {
+                    () = token.cancelled() => {
+                        tracing::debug!("subscription refresh loop cancelled");
+                        break;
+                    }
+                    _ = ticker.tick() => {
+                        let nodes = match SubscriptionParser::fetch_and_parse(&subscription_url).await {
+                            Ok(nodes) => nodes,
+                            Err(e) => {
+                                tracing::warn!("subscription refresh failed: {e}");
+                                continue;
+                            }
+                        };
+                        if let Err(e) = NodeCache::update_nodes(&nodes, &cache_path).await {
+                            tracing::warn!(
+                                "failed to update node cache at {}: {e}",
+                                cache_path.display()
+                            );
+                        }
+                        on_nodes(nodes);
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Node names added, removed or reconfigured by `NodeManager::replace_nodes`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Nodes kept under the same name whose server, port or config changed.
+    pub changed: Vec<String>,
+}
+
+impl NodeDiff {
+    /// Whether the node list changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::health::HealthResult;
     use super::*;
     use crate::vpn::subscription::NodeType;
     use std::time::Duration;
@@ -181,6 +517,7 @@ mod tests {
                 server: "vmess.example.com".into(),
                 port: 443,
                 raw_config: serde_json::json!({"uuid": "test"}),
+                region: None,
             },
             ProxyNode {
                 name: "test-trojan".into(),
@@ -188,6 +525,7 @@ mod tests {
                 server: "trojan.example.com".into(),
                 port: 443,
                 raw_config: serde_json::json!({"password": "placeholder"}),
+                region: None,
             },
         ]
     }
@@ -235,6 +573,86 @@ mod tests {
         assert_eq!(cached.nodes.len(), 2);
     }
 
+    #[tokio::test]
+    async fn snapshot_roundtrip_restores_selection_and_health() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nodes.json");
+
+        let mut mgr = NodeManager::new(sample_nodes());
+        mgr.set_active("test-trojan");
+        let health = vec![
+            (
+                "test-vmess".to_string(),
+                HealthResult {
+                    status: NodeStatus::Unhealthy,
+                    latency_ms: None,
+                    checked_at: std::time::Instant::now(),
+                },
+            ),
+            (
+                "test-trojan".to_string(),
+                HealthResult {
+                    status: NodeStatus::Healthy,
+                    latency_ms: Some(87),
+                    checked_at: std::time::Instant::now(),
+                },
+            ),
+        ];
+        NodeCache::save_snapshot(&mgr.snapshot(&health), &path)
+            .await
+            .unwrap();
+
+        let snapshot = NodeCache::load_snapshot(&path).await.unwrap().unwrap();
+        let (restored, restored_health) = NodeManager::from_snapshot(snapshot);
+        assert_eq!(restored.all_nodes().len(), 2);
+        assert_eq!(restored.active_node().unwrap().name, "test-trojan");
+        assert_eq!(restored_health.len(), 2);
+        assert_eq!(restored_health[0].1.status, NodeStatus::Unhealthy);
+        assert_eq!(restored_health[1].0, "test-trojan");
+        assert_eq!(restored_health[1].1.status, NodeStatus::Healthy);
+        assert_eq!(restored_health[1].1.latency_ms, Some(87));
+    }
+
+    #[tokio::test]
+    async fn load_snapshot_accepts_legacy_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("legacy.json");
+        let legacy = serde_json::json!({
+            "fetched_at": "2025-01-01T00:00:00Z",
+            "nodes": sample_nodes(),
+        });
+        tokio::fs::write(&path, legacy.to_string()).await.unwrap();
+
+        let snapshot = NodeCache::load_snapshot(&path).await.unwrap().unwrap();
+        assert_eq!(snapshot.nodes.len(), 2);
+        assert!(snapshot.active.is_none());
+        assert!(snapshot.health.is_empty());
+    }
+
+    #[tokio::test]
+    async fn update_nodes_keeps_selection_for_surviving_nodes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nodes.json");
+        let mut mgr = NodeManager::new(sample_nodes());
+        mgr.set_active("test-vmess");
+        NodeCache::save_snapshot(&mgr.snapshot(&[]), &path)
+            .await
+            .unwrap();
+
+        NodeCache::update_nodes(&sample_nodes()[..1], &path)
+            .await
+            .unwrap();
+        let snapshot = NodeCache::load_snapshot(&path).await.unwrap().unwrap();
+        assert_eq!(snapshot.nodes.len(), 1);
+        assert_eq!(snapshot.active.as_deref(), Some("test-vmess"));
+
+        NodeCache::update_nodes(&sample_nodes()[1..], &path)
+            .await
+            .unwrap();
+        let snapshot = NodeCache::load_snapshot(&path).await.unwrap().unwrap();
+        assert!(snapshot.active.is_none());
+    }
+
     #[test]
     fn default_cache_path_ends_with_nodes_json() {
         let path = NodeCache::default_cache_path();
@@ -251,6 +669,7 @@ mod tests {
                 server: "a.example.com".into(),
                 port: 443,
                 raw_config: serde_json::json!({}),
+                region: None,
             },
             ProxyNode {
                 name: "node-b".into(),
@@ -258,6 +677,7 @@ mod tests {
                 server: "b.example.com".into(),
                 port: 443,
                 raw_config: serde_json::json!({}),
+                region: None,
             },
             ProxyNode {
                 name: "node-c".into(),
@@ -265,6 +685,7 @@ mod tests {
                 server: "c.example.com".into(),
                 port: 8388,
                 raw_config: serde_json::json!({}),
+                region: None,
             },
         ]
     }
@@ -339,6 +760,105 @@ mod tests {
         // node-a is skipped (active), rest unhealthy
         assert!(mgr.failover(&health).is_none());
     }
+
+    fn regional_nodes() -> Vec<ProxyNode> {
+        let mut nodes = three_nodes();
+        nodes[0].region = Some("US".into());
+        nodes[1].region = Some("JP".into());
+        nodes[2].region = Some("US".into());
+        nodes
+    }
+
+    #[test]
+    fn select_best_node_only_considers_configured_regions() {
+        let mgr = NodeManager::new(regional_nodes()).with_regions(vec!["us".into()]);
+        let health = vec![
+            make_health("node-a", NodeStatus::Healthy, Some(120)),
+            make_health("node-b", NodeStatus::Healthy, Some(20)),
+            make_health("node-c", NodeStatus::Healthy, Some(90)),
+        ];
+        // node-b is fastest but in JP
+        assert_eq!(mgr.select_best_node(&health).unwrap().name, "node-c");
+    }
+
+    #[test]
+    fn is_selectable_applies_regions_except_for_pinned_node() {
+        let mut mgr = NodeManager::new(regional_nodes()).with_regions(vec!["US".into()]);
+        assert!(mgr.is_selectable("node-a"));
+        assert!(!mgr.is_selectable("node-b"));
+        assert!(mgr.pin_node("node-b"));
+        assert!(mgr.is_selectable("node-b"));
+    }
+
+    #[test]
+    fn failover_stays_within_configured_regions() {
+        let mut mgr = NodeManager::new(regional_nodes()).with_regions(vec!["US".into()]);
+        mgr.set_active("node-c");
+        let health = vec![
+            make_health("node-a", NodeStatus::Healthy, Some(120)),
+            make_health("node-b", NodeStatus::Healthy, Some(20)),
+            make_health("node-c", NodeStatus::Healthy, Some(90)),
+        ];
+        assert_eq!(mgr.failover(&health).unwrap().name, "node-a");
+
+        // No other healthy US node left: JP is not a candidate.
+        let health = vec![
+            make_health("node-a", NodeStatus::Healthy, Some(120)),
+            make_health("node-b", NodeStatus::Healthy, Some(20)),
+            make_health("node-c", NodeStatus::Unhealthy, None),
+        ];
+        assert!(mgr.failover(&health).is_none());
+    }
+
+    #[test]
+    fn region_filter_falls_back_to_all_nodes_when_none_match() {
+        let mgr = NodeManager::new(regional_nodes()).with_regions(vec!["SG".into()]);
+        let health = vec![
+            make_health("node-a", NodeStatus::Healthy, Some(120)),
+            make_health("node-b", NodeStatus::Healthy, Some(20)),
+            make_health("node-c", NodeStatus::Healthy, Some(90)),
+        ];
+        assert_eq!(mgr.select_best_node(&health).unwrap().name, "node-b");
+    }
+    #[test]
+    fn failover_is_suppressed_while_pinned() {
+        let mut mgr = NodeManager::new(three_nodes());
+        assert!(mgr.pin_node("node-a"));
+        let health = vec![
+            make_health("node-a", NodeStatus::Unhealthy, None),
+            make_health("node-b", NodeStatus::Healthy, Some(50)),
+            make_health("node-c", NodeStatus::Healthy, Some(80)),
+        ];
+        assert!(mgr.failover(&health).is_none());
+        assert_eq!(mgr.active_node().unwrap().name, "node-a");
+        assert_eq!(mgr.select_best_node(&health).unwrap().name, "node-a");
+
+        assert_eq!(mgr.unpin().as_deref(), Some("node-a"));
+        assert_eq!(mgr.failover(&health).unwrap().name, "node-b");
+        assert_eq!(mgr.select_best_node(&health).unwrap().name, "node-b");
+    }
+
+    #[test]
+    fn pin_node_rejects_unknown_node() {
+        let mut mgr = NodeManager::new(three_nodes());
+        assert!(!mgr.pin_node("no-such-node"));
+        assert!(mgr.pinned().is_none());
+        assert!(mgr.unpin().is_none());
+    }
+
+    #[test]
+    fn pin_survives_snapshot_and_is_dropped_with_its_node() {
+        let mut mgr = NodeManager::new(three_nodes());
+        mgr.pin_node("node-c");
+        let (mut restored, _) = NodeManager::from_snapshot(mgr.snapshot(&[]));
+        assert_eq!(restored.pinned(), Some("node-c"));
+
+        let mut updated = three_nodes();
+        updated.retain(|n| n.name != "node-c");
+        restored.replace_nodes(updated);
+        assert!(restored.pinned().is_none());
+    }
+
     #[test]
     fn set_active_existing_node() {
         let mut mgr = NodeManager::new(three_nodes());
@@ -357,4 +877,102 @@ mod tests {
         let mgr = NodeManager::new(three_nodes());
         assert_eq!(mgr.all_nodes().len(), 3);
     }
+
+    #[test]
+    fn replace_nodes_keeps_existing_selection() {
+        let mut mgr = NodeManager::new(three_nodes());
+        mgr.set_active("node-b");
+        let mut updated = three_nodes();
+        updated.retain(|n| n.name != "node-a");
+        updated.push(ProxyNode {
+            name: "node-d".into(),
+            node_type: NodeType::VMess,
+            server: "d.example.com".into(),
+            port: 443,
+            raw_config: serde_json::json!({}),
+            region: None,
+        });
+
+        let diff = mgr.replace_nodes(updated);
+        assert_eq!(diff.added, vec!["node-d".to_string()]);
+        assert_eq!(diff.removed, vec!["node-a".to_string()]);
+        assert_eq!(mgr.active_node().unwrap().name, "node-b");
+    }
+
+    #[test]
+    fn diff_nodes_reports_reconfigured_nodes() {
+        let mut mgr = NodeManager::new(three_nodes());
+        let mut updated = three_nodes();
+        updated[1].port = 8443;
+
+        let diff = mgr.diff_nodes(&updated);
+        assert_eq!(diff.changed, vec![updated[1].name.clone()]);
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert!(!diff.is_empty());
+        assert_eq!(mgr.all_nodes()[1].port, three_nodes()[1].port);
+
+        mgr.replace_nodes(updated);
+        assert!(mgr.diff_nodes(mgr.all_nodes()).is_empty());
+    }
+
+    #[test]
+    fn replace_nodes_clears_removed_selection() {
+        let mut mgr = NodeManager::new(three_nodes());
+        mgr.set_active("node-c");
+        let diff = mgr.replace_nodes(sample_nodes());
+        assert_eq!(diff.removed.len(), 3);
+        assert!(mgr.active_node().is_none());
+    }
+
+    #[tokio::test]
+    async fn refresh_loop_updates_cache_on_subscription_change() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let first = "proxies:\n  - name: \"old-node\"\n    type: trojan\n    server: old.example.com\n    port: 443\n    password: \"placeholder\"\n";
+        let second = "proxies:\n  - name: \"new-node\"\n    type: trojan\n    server: new.example.com\n    port: 443\n    password: \"placeholder\"\n";
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/sub"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(first))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/sub"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(second))
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache_path = dir.path().join("nodes.json");
+        let manager = std::sync::Arc::new(std::sync::Mutex::new(NodeManager::new(vec![])));
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let token = CancellationToken::new();
+        let mgr = manager.clone();
+        let handle = NodeManager::spawn_refresh_loop(
+            format!("{}/sub", server.uri()),
+            cache_path.clone(),
+            Some(Duration::from_millis(50)),
+            token.clone(),
+            move |nodes| {
+                let diff = mgr.lock().unwrap().replace_nodes(nodes);
+                let _ = tx.send(diff);
+            },
+        );
+
+        let first_diff = rx.recv().await.unwrap();
+        assert_eq!(first_diff.added, vec!["old-node".to_string()]);
+        let second_diff = rx.recv().await.unwrap();
+        assert_eq!(second_diff.added, vec!["new-node".to_string()]);
+        assert_eq!(second_diff.removed, vec!["old-node".to_string()]);
+        token.cancel();
+        handle.await.unwrap();
+
+        let cached = NodeCache::load(&cache_path).await.unwrap().unwrap();
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].name, "new-node");
+        assert_eq!(manager.lock().unwrap().all_nodes()[0].name, "new-node");
+    }
 }
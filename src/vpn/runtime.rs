@@ -12,12 +12,17 @@
 //!   → waits for startup confirmation
 //!   → returns ClashRuntime handle
 //!
+//! runtime.reload_config(nodes)
+//!   → rewrites the config file
+//!   → PUT /configs on the controller (live reload)
+//!   → falls back to a full restart if the reload is rejected
+//!
 //! runtime.stop()
 //!   → kills the child process
 //!   → waits for exit
 //! ```
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
 
@@ -190,13 +195,6 @@ impl ClashRuntime {
     /// # Errors
     /// Returns error if the clash binary is not found or fails to start.
     pub async fn start(config_yaml: &str, listen_port: u16) -> Result<Self> {
-        // Resolve the clash binary path.
-        let clash_bin = which::which("clash")
-            .or_else(|_| which::which("clash-rs"))
-            .context(
-                "'clash' binary not found in PATH. Install clash-rs or set PATH accordingly.",
-            )?;
-
         // Write config YAML to a state directory.
         let state_dir = directories::UserDirs::new()
             .map_or_else(|| PathBuf::from("."), |u| u.home_dir().to_path_buf())
@@ -208,55 +206,12 @@ impl ClashRuntime {
         let config_path = state_dir.join("clash-config.yaml");
         std::fs::write(&config_path, config_yaml).context("failed to write Clash config file")?;
 
-        // Spawn the clash binary as a child process.
-        let child = tokio::process::Command::new(&clash_bin)
-            .arg("-f")
-            .arg(&config_path)
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::piped())
-            .kill_on_drop(true)
-            .spawn()
-            .with_context(|| format!("failed to spawn clash binary: {}", clash_bin.display()))?;
-
-        // Poll until Clash binds the SOCKS5 port (max 2s).
-        let mut port_ready = false;
-        for _ in 0..20 {
-            match tokio::net::TcpStream::connect(format!("127.0.0.1:{}", listen_port)).await {
-                Ok(_) => {
-                    port_ready = true;
-                    break;
-                }
-                Err(_) => {
-                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-                }
-            }
-        }
-        if !port_ready {
-            bail!(
-                "Clash proxy failed to start: port {} not responding after 2s",
-                listen_port
-            );
-        }
-
-        // Verify the process is still alive after startup.
-        let mut runtime = Self {
+        let child = spawn_clash(&config_path, listen_port).await?;
+        Ok(Self {
             socks_port: listen_port,
             child: Some(child),
             config_path,
-        };
-
-        if !runtime.is_running() {
-            // Process exited during startup — collect exit status for diagnostics.
-            if let Some(mut child) = runtime.child.take() {
-                match child.wait().await {
-                    Ok(status) => bail!("clash process exited during startup with {status}"),
-                    Err(e) => bail!("clash process exited during startup: {e}"),
-                }
-            }
-            bail!("clash process exited during startup");
-        }
-
-        Ok(runtime)
+        })
     }
 
     /// Stop the Clash process gracefully.
@@ -318,6 +273,102 @@ impl ClashRuntime {
         }
         Ok(())
     }
+
+    /// Regenerate the config for `nodes` and apply it without restarting.
+    ///
+    /// Rewrites the config file, then asks the controller to reload it via
+    /// `PUT /configs`. If the reload request fails, the Clash process is
+    /// restarted with the new config instead.
+    pub async fn reload_config(&mut self, nodes: &[ProxyNode]) -> Result<()> {
+        let config_yaml = generate_clash_config(nodes, self.socks_port)?;
+        std::fs::write(&self.config_path, &config_yaml)
+            .context("failed to write Clash config file")?;
+
+        let controller_url = format!("http://127.0.0.1:{CLASH_CONTROLLER_PORT}");
+        match request_config_reload(&controller_url, &self.config_path).await {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                tracing::warn!("Clash config reload failed, restarting runtime: {e}");
+                self.stop().await?;
+                self.child = Some(spawn_clash(&self.config_path, self.socks_port).await?);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Spawn the `clash` binary on `config_path` and wait until it binds `listen_port`.
+///
+/// # Errors
+/// Returns error if the clash binary is not found, fails to start, or exits
+/// during startup.
+async fn spawn_clash(config_path: &Path, listen_port: u16) -> Result<tokio::process::Child> {
+    // Resolve the clash binary path.
+    let clash_bin = which::which("clash")
+        .or_else(|_| which::which("clash-rs"))
+        .context("'clash' binary not found in PATH. Install clash-rs or set PATH accordingly.")?;
+
+    // Spawn the clash binary as a child process.
+    let mut child = tokio::process::Command::new(&clash_bin)
+        .arg("-f")
+        .arg(config_path)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("failed to spawn clash binary: {}", clash_bin.display()))?;
+
+    // Poll until Clash binds the SOCKS5 port (max 2s).
+    let mut port_ready = false;
+    for _ in 0..20 {
+        match tokio::net::TcpStream::connect(format!("127.0.0.1:{}", listen_port)).await {
+            Ok(_) => {
+                port_ready = true;
+                break;
+            }
+            Err(_) => {
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            }
+        }
+    }
+    if !port_ready {
+        bail!(
+            "Clash proxy failed to start: port {} not responding after 2s",
+            listen_port
+        );
+    }
+
+    // Verify the process is still alive after startup — collect exit status
+    // for diagnostics if it exited.
+    match child.try_wait() {
+        Ok(None) => Ok(child),
+        Ok(Some(status)) => bail!("clash process exited during startup with {status}"),
+        Err(e) => bail!("clash process exited during startup: {e}"),
+    }
+}
+
+/// Ask a running Clash controller to reload its config from `config_path`.
+///
+/// Sends `PUT {controller_url}/configs?force=true` with `{"path": ...}`.
+async fn request_config_reload(controller_url: &str, config_path: &Path) -> Result<()> {
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .timeout(std::time::Duration::from_secs(5))
+        .build()
+        .context("failed to build clash controller client")?;
+    let resp = client
+        .put(format!("{controller_url}/configs"))
+        .query(&[("force", "true")])
+        .json(&serde_json::json!({ "path": config_path.to_string_lossy() }))
+        .send()
+        .await
+        .context("failed to reach Clash controller API for config reload")?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        bail!("Clash config reload failed (HTTP {status}): {body}");
+    }
+    Ok(())
 }
 
 impl Drop for ClashRuntime {
@@ -355,6 +406,7 @@ mod tests {
                     "Cipher": "auto",
                     "Tls": true
                 }),
+                region: None,
             },
             ProxyNode {
                 name: "trojan-sg".to_string(),
@@ -368,6 +420,7 @@ mod tests {
                     "Port": 443,
                     "Password": "placeholder-password"
                 }),
+                region: None,
             },
         ]
     }
@@ -488,6 +541,7 @@ mod tests {
                 "Cipher": "aes-256-gcm",
                 "Password": "placeholder"
             }),
+            region: None,
         };
         let yaml = generate_clash_config(&[node], 7891).unwrap();
         let server_count = yaml.matches("server:").count();
@@ -501,6 +555,7 @@ mod tests {
             server: "socks.example.com".to_string(),
             port: 1080,
             raw_config: serde_json::Value::Null,
+            region: None,
         };
         let yaml = generate_clash_config(&[node], 7891).unwrap();
         assert!(yaml.contains("name: \"minimal-node\""));
@@ -508,4 +563,45 @@ mod tests {
         assert!(yaml.contains("server: socks.example.com"));
         assert!(yaml.contains("port: 1080"));
     }
+
+    #[tokio::test]
+    async fn request_config_reload_sends_put_configs() {
+        use wiremock::matchers::{body_json, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/configs"))
+            .and(query_param("force", "true"))
+            .and(body_json(serde_json::json!({
+                "path": "/tmp/zeroclaw/clash-config.yaml"
+            })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        request_config_reload(&server.uri(), Path::new("/tmp/zeroclaw/clash-config.yaml"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn request_config_reload_surfaces_controller_error() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/configs"))
+            .respond_with(ResponseTemplate::new(400).set_body_string("bad config"))
+            .mount(&server)
+            .await;
+
+        let err = request_config_reload(&server.uri(), Path::new("/tmp/clash.yaml"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("HTTP 400"));
+        assert!(err.to_string().contains("bad config"));
+    }
 }
@@ -3,7 +3,12 @@
 //! Fetches and parses Clash proxy subscription URLs using the `subconverter`
 //! crate for robust YAML parsing. Converts parsed proxies into a simplified
 //! `ProxyNode` representation for downstream use by clash-lib.
+//!
+//! Subscription bodies may be plain or base64-encoded, and contain either
+//! Clash YAML or a newline-separated list of share links (`vmess://`,
+//! `ss://`, `trojan://`, ...).
 
+use base64::Engine as _;
 use std::fmt;
 use std::time::Duration;
 
@@ -37,13 +42,106 @@ impl fmt::Display for NodeType {
 ///
 /// `raw_config` preserves the full node configuration as JSON for later
 /// consumption by clash-lib without data loss.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct ProxyNode {
     pub name: String,
     pub node_type: NodeType,
     pub server: String,
     pub port: u16,
     pub raw_config: serde_json::Value,
+    /// ISO 3166-1 alpha-2 region code (e.g. `US`), detected from the node
+    /// name by [`detect_region`]. `None` when the name gives no hint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+}
+
+/// Region codes and the name fragments that identify them. Short latin
+/// aliases must match a whole word of the node name; longer aliases and CJK
+/// names match anywhere.
+const REGION_ALIASES: &[(&str, &[&str])] = &[
+    (
+        "US",
+        &[
+            "us",
+            "usa",
+            "united states",
+            "america",
+            "los angeles",
+            "san jose",
+            "seattle",
+            "new york",
+            "美国",
+            "洛杉矶",
+            "硅谷",
+        ],
+    ),
+    ("HK", &["hk", "hong kong", "hongkong", "香港"]),
+    ("TW", &["tw", "taiwan", "台湾", "台灣"]),
+    (
+        "JP",
+        &["jp", "japan", "tokyo", "osaka", "日本", "东京", "大阪"],
+    ),
+    ("KR", &["kr", "korea", "seoul", "韩国", "首尔"]),
+    ("SG", &["sg", "singapore", "新加坡", "狮城"]),
+    (
+        "GB",
+        &[
+            "gb",
+            "uk",
+            "united kingdom",
+            "britain",
+            "london",
+            "英国",
+            "伦敦",
+        ],
+    ),
+    ("DE", &["de", "germany", "frankfurt", "德国"]),
+    ("FR", &["fr", "france", "paris", "法国"]),
+    ("NL", &["nl", "netherlands", "amsterdam", "荷兰"]),
+    ("CA", &["ca", "canada", "toronto", "加拿大"]),
+    ("AU", &["au", "australia", "sydney", "澳大利亚", "澳洲"]),
+    ("RU", &["ru", "russia", "moscow", "俄罗斯"]),
+    ("IN", &["india", "mumbai", "印度"]),
+];
+
+/// Guess a node's region from its name.
+///
+/// A flag emoji (e.g. 🇯🇵) wins; otherwise the first region in
+/// `REGION_ALIASES` with a matching alias is returned.
+pub fn detect_region(name: &str) -> Option<String> {
+    if let Some(code) = flag_region(name) {
+        return Some(code);
+    }
+    let lower = name.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|w| !w.is_empty())
+        .collect();
+    REGION_ALIASES
+        .iter()
+        .find(|(_, aliases)| {
+            aliases.iter().any(|alias| {
+                if alias.len() <= 3 && alias.is_ascii() {
+                    words.contains(alias)
+                } else {
+                    lower.contains(alias)
+                }
+            })
+        })
+        .map(|(code, _)| (*code).to_string())
+}
+
+/// Region code of the first flag emoji (a pair of regional indicator symbols).
+fn flag_region(name: &str) -> Option<String> {
+    const REGIONAL_INDICATOR_A: u32 = 0x1F1E6;
+    let letter = |c: char| {
+        let offset = u32::from(c).checked_sub(REGIONAL_INDICATOR_A)?;
+        (offset < 26).then(|| char::from_u32(u32::from('A') + offset))?
+    };
+    let chars: Vec<char> = name.chars().collect();
+    chars
+        .windows(2)
+        .find_map(|pair| Some(format!("{}{}", letter(pair[0])?, letter(pair[1])?)))
 }
 
 /// HTTP fetch timeout for subscription URLs.
@@ -54,8 +152,9 @@ const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Clash subscription parser.
 ///
-/// Fetches remote Clash YAML subscriptions and converts them into
-/// `Vec<ProxyNode>` for use by the VPN runtime.
+/// Fetches remote subscriptions (Clash YAML or share-link lists, optionally
+/// base64-encoded) and converts them into `Vec<ProxyNode>` for use by the VPN
+/// runtime.
 pub struct SubscriptionParser;
 
 impl SubscriptionParser {
@@ -99,7 +198,46 @@ impl SubscriptionParser {
             .await
             .map_err(|e| anyhow::anyhow!("failed to read subscription response body: {e}"))?;
 
-        Self::parse_clash_yaml(&content)
+        Self::parse_subscription(&content)
+    }
+
+    /// Parse a subscription body of any supported format.
+    ///
+    /// A body that is entirely base64 is decoded first. The (decoded) content
+    /// is then parsed as a share-link list if its first line is a link, and as
+    /// Clash YAML otherwise.
+    pub fn parse_subscription(content: &str) -> anyhow::Result<Vec<ProxyNode>> {
+        let decoded = decode_base64_body(content);
+        let content = decoded.as_deref().unwrap_or(content);
+        if is_share_link_list(content) {
+            Self::parse_share_links(content)
+        } else {
+            Self::parse_clash_yaml(content)
+        }
+    }
+
+    /// Parse a newline-separated list of share links into proxy nodes.
+    ///
+    /// Lines that are not valid links of a supported protocol are skipped.
+    pub fn parse_share_links(content: &str) -> anyhow::Result<Vec<ProxyNode>> {
+        let mut nodes = Vec::new();
+        for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let mut proxy = libsubconverter::Proxy::default();
+            if libsubconverter::parser::explodes::explode(line, &mut proxy)
+                && proxy.proxy_type != libsubconverter::ProxyType::Unknown
+            {
+                nodes.push(proxy_to_node(proxy));
+            } else {
+                let scheme = line.split("://").next().unwrap_or_default();
+                tracing::debug!("skipping unsupported {scheme}:// share link in subscription");
+            }
+        }
+
+        if nodes.is_empty() {
+            anyhow::bail!("no valid proxy nodes found in subscription");
+        }
+
+        Ok(nodes)
     }
 
     /// Parse raw Clash YAML content into proxy nodes.
@@ -124,6 +262,42 @@ impl SubscriptionParser {
     }
 }
 
+/// Decode a subscription body that consists solely of base64 (standard or
+/// URL-safe alphabet, padded or not, possibly wrapped across lines).
+///
+/// Returns `None` for anything else, including plain YAML and share links,
+/// whose `:` and `/` characters fall outside the URL-safe alphabet.
+fn decode_base64_body(content: &str) -> Option<String> {
+    let compact: String = content.split_whitespace().collect();
+    if compact.is_empty()
+        || !compact
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'-' | b'_' | b'='))
+    {
+        return None;
+    }
+    let unpadded = compact.trim_end_matches('=');
+    let bytes = base64::engine::general_purpose::STANDARD_NO_PAD
+        .decode(unpadded)
+        .or_else(|_| base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(unpadded))
+        .ok()?;
+    String::from_utf8(bytes).ok()
+}
+
+/// Whether `content` is a share-link list rather than Clash YAML, judged by
+/// its first non-empty line looking like `scheme://...`.
+fn is_share_link_list(content: &str) -> bool {
+    let Some(first) = content.lines().map(str::trim).find(|l| !l.is_empty()) else {
+        return false;
+    };
+    first.split_once("://").is_some_and(|(scheme, _)| {
+        !scheme.is_empty()
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    })
+}
+
 /// Convert a subconverter `ProxyType` to our `NodeType`.
 fn map_proxy_type(pt: libsubconverter::ProxyType) -> NodeType {
     match pt {
@@ -149,6 +323,7 @@ fn proxy_to_node(proxy: libsubconverter::Proxy) -> ProxyNode {
 
     // Serialize the full proxy struct to JSON for lossless downstream use.
     let raw_config = serde_json::to_value(&proxy).unwrap_or(serde_json::Value::Null);
+    let region = detect_region(&name);
 
     ProxyNode {
         name,
@@ -156,6 +331,7 @@ fn proxy_to_node(proxy: libsubconverter::Proxy) -> ProxyNode {
         server,
         port,
         raw_config,
+        region,
     }
 }
 
@@ -306,6 +482,122 @@ proxies:
         assert_eq!(nodes[0].node_type, NodeType::VMess);
     }
 
+    fn base64(content: &str) -> String {
+        base64::engine::general_purpose::STANDARD.encode(content)
+    }
+
+    const SHARE_LINKS_FIXTURE: &str = "\
+trojan://trojan-password-placeholder@trojan.example.com:443?sni=trojan.example.com#trojan-link
+ss://YWVzLTI1Ni1nY206c3MtcGFzc3dvcmQtcGxhY2Vob2xkZXI@ss.example.com:8388#ss-link
+vless://a3482e88-686a-4a58-8126-99c9034e4b09@vless.example.com:443?security=tls&type=tcp#vless-link
+";
+
+    #[test]
+    fn parse_subscription_plain_clash_yaml() {
+        let nodes = SubscriptionParser::parse_subscription(CLASH_YAML_FIXTURE).unwrap();
+        assert_eq!(nodes.len(), 6);
+    }
+
+    #[test]
+    fn parse_subscription_base64_clash_yaml() {
+        let encoded = base64(CLASH_YAML_FIXTURE);
+        let nodes = SubscriptionParser::parse_subscription(&encoded).unwrap();
+        assert_eq!(nodes.len(), 6);
+        assert_eq!(nodes[0].name, "vmess-node");
+        assert_eq!(nodes[0].node_type, NodeType::VMess);
+    }
+
+    #[test]
+    fn parse_subscription_base64_wrapped_across_lines() {
+        let encoded = base64(CLASH_YAML_FIXTURE);
+        let wrapped: Vec<&str> = encoded
+            .as_bytes()
+            .chunks(76)
+            .map(|c| std::str::from_utf8(c).unwrap())
+            .collect();
+        let nodes = SubscriptionParser::parse_subscription(&wrapped.join("\r\n")).unwrap();
+        assert_eq!(nodes.len(), 6);
+    }
+
+    #[test]
+    fn parse_subscription_base64_share_links() {
+        let encoded = base64(SHARE_LINKS_FIXTURE);
+        let nodes = SubscriptionParser::parse_subscription(&encoded).unwrap();
+
+        let summary: Vec<(&str, &NodeType, &str, u16)> = nodes
+            .iter()
+            .map(|n| (n.name.as_str(), &n.node_type, n.server.as_str(), n.port))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("trojan-link", &NodeType::Trojan, "trojan.example.com", 443),
+                ("ss-link", &NodeType::Shadowsocks, "ss.example.com", 8388),
+                ("vless-link", &NodeType::VLESS, "vless.example.com", 443),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_subscription_url_safe_unpadded_base64() {
+        let encoded = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(SHARE_LINKS_FIXTURE);
+        let nodes = SubscriptionParser::parse_subscription(&encoded).unwrap();
+        assert_eq!(nodes.len(), 3);
+    }
+
+    #[test]
+    fn parse_subscription_plain_share_links_skip_unsupported() {
+        let content = format!("{SHARE_LINKS_FIXTURE}ssr://not-supported\nnot a link\n");
+        let nodes = SubscriptionParser::parse_subscription(&content).unwrap();
+        assert_eq!(nodes.len(), 3);
+    }
+
+    #[test]
+    fn parse_subscription_share_links_without_valid_nodes() {
+        let result = SubscriptionParser::parse_subscription(&base64("ssr://not-supported\n"));
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("no valid proxy nodes"));
+    }
+
+    #[test]
+    fn base64_detection_ignores_plain_content() {
+        assert!(decode_base64_body(CLASH_YAML_FIXTURE).is_none());
+        assert!(decode_base64_body(SHARE_LINKS_FIXTURE).is_none());
+        assert!(decode_base64_body("  \n").is_none());
+        assert!(is_share_link_list("\nvmess://abc\n"));
+        assert!(!is_share_link_list("proxies:\n  - name: x\n"));
+    }
+
+    #[test]
+    fn detect_region_from_flags_codes_and_names() {
+        let cases = [
+            ("🇺🇸 Premium 01", Some("US")),
+            ("🇯🇵东京 IEPL", Some("JP")),
+            ("HK-BGP-02", Some("HK")),
+            ("us1.vmess", Some("US")),
+            ("Singapore Relay", Some("SG")),
+            ("香港 03 | 1x", Some("HK")),
+            ("London | UK", Some("GB")),
+            ("Los Angeles 10G", Some("US")),
+            ("vmess-node", None),
+            ("Bonus traffic", None),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(detect_region(name).as_deref(), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn parsed_nodes_carry_detected_region() {
+        let content =
+            "trojan://pw@us.example.com:443#🇺🇸 US-01\ntrojan://pw@x.example.com:443#relay\n";
+        let nodes = SubscriptionParser::parse_share_links(content).unwrap();
+        assert_eq!(nodes[0].region.as_deref(), Some("US"));
+        assert_eq!(nodes[1].region, None);
+    }
+
     #[test]
     fn node_type_display() {
         assert_eq!(NodeType::VMess.to_string(), "vmess");
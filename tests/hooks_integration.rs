@@ -17,12 +17,19 @@ impl HookHandler for CounterHook {
         "counter"
     }
 
-    async fn on_gateway_start(&self, _host: &str, _port: u16) {
+    async fn on_gateway_start(&self, _host: &str, _port: u16) -> anyhow::Result<()> {
         self.gateway_starts.fetch_add(1, Ordering::SeqCst);
+        Ok(())
     }
 
-    async fn on_after_tool_call(&self, _tool: &str, _result: &ToolResult, _duration: Duration) {
+    async fn on_after_tool_call(
+        &self,
+        _tool: &str,
+        _result: &ToolResult,
+        _duration: Duration,
+    ) -> anyhow::Result<()> {
         self.tool_calls.fetch_add(1, Ordering::SeqCst);
+        Ok(())
     }
 }
 
@@ -9,8 +9,8 @@ use std::time::Instant;
 
 use zeroclaw::config::{runtime_proxy_config, set_runtime_proxy_config, ProxyConfig, ProxyScope};
 use zeroclaw::vpn::{
-    BypassChecker, BypassDecision, HealthResult, NodeCache, NodeManager, NodeStatus, NodeType,
-    ProxyNode, SubscriptionParser, VpnProxyBridge,
+    BypassChecker, BypassDecision, HealthCheckOptions, HealthResult, NodeCache, NodeManager,
+    NodeStatus, NodeType, ProxyNode, SubscriptionParser, VpnProxyBridge,
 };
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -52,6 +52,7 @@ fn sample_nodes() -> Vec<ProxyNode> {
             server: "a.example.com".into(),
             port: 443,
             raw_config: serde_json::json!({}),
+            region: None,
         },
         ProxyNode {
             name: "node-b".into(),
@@ -59,6 +60,7 @@ fn sample_nodes() -> Vec<ProxyNode> {
             server: "b.example.com".into(),
             port: 443,
             raw_config: serde_json::json!({}),
+            region: None,
         },
         ProxyNode {
             name: "node-c".into(),
@@ -66,6 +68,7 @@ fn sample_nodes() -> Vec<ProxyNode> {
             server: "c.example.com".into(),
             port: 8388,
             raw_config: serde_json::json!({}),
+            region: None,
         },
     ]
 }
@@ -375,10 +378,14 @@ fn vpn_control_tool_schema_valid() {
         bypass_checker: BypassChecker::new(&[]),
         bridge: VpnProxyBridge::new(),
         health_cancel: None,
+        refresh_cancel: None,
         last_health: vec![],
         subscription_url: None,
         listen_port: 7890,
         health_check_interval_secs: 30,
+        health_options: HealthCheckOptions::default(),
+        subscription_refresh_interval_secs: 3600,
+        regions: vec![],
     }));
     let tool = VpnControlTool::new(security, state);
 